@@ -0,0 +1,147 @@
+//! Taproot addresses for compiled programs.
+//!
+//! A program is committed as the single tapleaf of a taproot output whose
+//! internal key is unspendable, so the only way to spend it is by revealing
+//! and satisfying the Simplicity program. Other trees are built through
+//! [`TaprootConfig`].
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::elements::taproot::TapLeafHash;
+use simplicityhl::simplicity::elements::{Address, AddressParams, Script};
+use simplicityhl::simplicity::hashes::Hash;
+use simplicityhl::simplicity::{leaf_version, Cmr};
+
+use crate::hex;
+use crate::taproot::TaprootConfig;
+
+/// The BIP-341 "nothing up my sleeve" point; nobody knows its private key.
+pub const UNSPENDABLE_INTERNAL_KEY: &str =
+    "50929b74c1a04954b78b4b6035e97a5e078a5a0f28ec96d547bfee9ace803ac0";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Network {
+    Liquid,
+    #[default]
+    LiquidTestnet,
+    Elements,
+}
+
+impl Network {
+    pub const ALL: [Network; 3] = [Network::Liquid, Network::LiquidTestnet, Network::Elements];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Network::Liquid => "liquid",
+            Network::LiquidTestnet => "liquidtestnet",
+            Network::Elements => "elements",
+        }
+    }
+
+    pub fn address_params(self) -> &'static AddressParams {
+        match self {
+            Network::Liquid => &AddressParams::LIQUID,
+            Network::LiquidTestnet => &AddressParams::LIQUID_TESTNET,
+            Network::Elements => &AddressParams::ELEMENTS,
+        }
+    }
+
+    /// Hex id of the L-BTC asset, unknown for custom Elements chains.
+    pub fn policy_asset(self) -> Option<&'static str> {
+        match self {
+            Network::Liquid => {
+                Some("6f0279e9ed041c3d710a9f57d0c02928416460c4b722ae3457a11eec381c526d")
+            }
+            Network::LiquidTestnet => {
+                Some("144c654344aa716d6f3abcc1ca90e5641e4e2a7f633bc09fe3baf64585819a49")
+            }
+            Network::Elements => None,
+        }
+    }
+
+    /// Hex hash of the genesis block, which signature hashes commit to.
+    /// Unknown for custom Elements chains.
+    pub fn genesis_hash(self) -> Option<&'static str> {
+        match self {
+            Network::Liquid => {
+                Some("1466275836220db2944ca059a3a10ef6fd2ea684b0688d2c379296888a206003")
+            }
+            Network::LiquidTestnet => {
+                Some("a771da8e52ee6ad581ed1e9a99825e5b3b7992225534eaa2ae23244fe26ab1c1")
+            }
+            Network::Elements => None,
+        }
+    }
+
+    /// The network `address` was encoded for, told apart by its bech32 prefix.
+    pub fn of_address(address: &Address) -> Option<Network> {
+        Network::ALL
+            .into_iter()
+            .find(|n| n.address_params().bech_hrp == address.params.bech_hrp)
+    }
+
+    /// URI scheme used by Liquid wallets for payment requests.
+    pub fn uri_scheme(self) -> &'static str {
+        match self {
+            Network::Liquid => "liquidnetwork",
+            Network::LiquidTestnet | Network::Elements => "liquidtestnet",
+        }
+    }
+}
+
+impl FromStr for Network {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Network::ALL
+            .into_iter()
+            .find(|n| n.as_str() == s)
+            .ok_or_else(|| format!("Unknown network `{s}`"))
+    }
+}
+
+/// Parse a CMR from its 64-character hex rendering.
+pub fn parse_cmr(cmr: &str) -> Result<Cmr, String> {
+    let bytes: [u8; 32] = hex::decode(cmr)
+        .map_err(|e| format!("Invalid CMR: {e}"))?
+        .try_into()
+        .map_err(|_| "A CMR is 64 hex characters".to_string())?;
+    Ok(Cmr::from_byte_array(bytes))
+}
+
+/// The tapleaf script for `cmr`: the CMR bytes themselves.
+pub fn leaf_script(cmr: Cmr) -> Script {
+    Script::from(cmr.as_ref().to_vec())
+}
+
+/// The pieces a taproot tree built elsewhere needs to commit to a program.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Tapleaf {
+    /// Simplicity's tapleaf version, `0xbe`.
+    pub leaf_version: u8,
+    /// Leaf script, hex.
+    pub script: String,
+    /// Tagged hash of version and script, hex, as it appears in the tree.
+    pub tapleaf_hash: String,
+}
+
+impl Tapleaf {
+    pub fn new(cmr: Cmr) -> Tapleaf {
+        let script = leaf_script(cmr);
+        let hash = TapLeafHash::from_script(&script, leaf_version());
+        Tapleaf {
+            leaf_version: leaf_version().as_u8(),
+            script: hex::encode(script.as_bytes()),
+            tapleaf_hash: hex::encode(&hash.to_byte_array()),
+        }
+    }
+}
+
+/// The unconfidential address that locks funds to `cmr`.
+pub fn taproot_address(cmr: Cmr, network: Network) -> Address {
+    TaprootConfig::default()
+        .address(cmr, network)
+        .expect("the default tree is valid")
+}
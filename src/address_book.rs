@@ -0,0 +1,139 @@
+//! Named public keys, hashes and addresses of counterparties, kept in
+//! localStorage.
+//!
+//! Entries share the `@label` syntax of the key [vault](crate::vault):
+//! wherever a program, its parameters or the witness read `@carol`,
+//! [`expand`] writes the entry's value instead. Keys and hashes become `0x`
+//! literals; addresses are written as they are, for the fields that take
+//! one. Nothing here is secret, so entries are stored in the clear.
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::elements::Address;
+
+use crate::hex;
+use crate::storage;
+use crate::vault::{self, VaultEntry};
+
+const ADDRESS_BOOK_KEY: &str = "address_book";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ContactKind {
+    /// x-only public key.
+    Pubkey,
+    /// 32-byte hash, e.g. a hash lock's image.
+    Hash,
+    /// Liquid address.
+    Address,
+}
+
+impl ContactKind {
+    pub const ALL: [ContactKind; 3] = [ContactKind::Pubkey, ContactKind::Hash, ContactKind::Address];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ContactKind::Pubkey => "pubkey",
+            ContactKind::Hash => "hash",
+            ContactKind::Address => "address",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<ContactKind> {
+        ContactKind::ALL.into_iter().find(|k| k.as_str() == s)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Contact {
+    pub label: String,
+    pub kind: ContactKind,
+    /// Lower-case hex without a prefix for keys and hashes; the address
+    /// itself for addresses.
+    pub value: String,
+    #[serde(default)]
+    pub note: String,
+}
+
+pub fn load() -> Vec<Contact> {
+    storage::load(ADDRESS_BOOK_KEY)
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(contacts: &[Contact]) {
+    if let Ok(json) = serde_json::to_string(contacts) {
+        storage::save(ADDRESS_BOOK_KEY, &json);
+    }
+}
+
+/// Check `value` is a `kind` and return it in stored form.
+pub fn normalize(kind: ContactKind, value: &str) -> Result<String, String> {
+    let value = value.trim();
+    match kind {
+        ContactKind::Pubkey => vault::parse_pubkey(value).map(|key| key.to_string()),
+        ContactKind::Hash => {
+            let bytes = hex::decode(value)?;
+            if bytes.len() != 32 {
+                return Err("A hash is 32 bytes (64 hex digits)".to_string());
+            }
+            Ok(hex::encode(&bytes))
+        }
+        ContactKind::Address => Address::from_str(value)
+            .map(|address| address.to_string())
+            .map_err(|e| format!("Invalid address: {e}")),
+    }
+}
+
+/// Check `label` is well-formed and names neither a contact nor a vault key.
+pub fn validate_label(label: &str, contacts: &[Contact], keys: &[VaultEntry]) -> Result<(), String> {
+    vault::validate_label(label, keys)?;
+    if contacts.iter().any(|c| c.label == label) {
+        return Err(format!("A contact labelled `{label}` already exists"));
+    }
+    Ok(())
+}
+
+/// Replace every `@label` naming a contact with its value. Unknown labels
+/// are left as written, so vault keys can be expanded before or after.
+pub fn expand(text: &str, contacts: &[Contact]) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(at) = rest.find('@') {
+        out.push_str(&rest[..at]);
+        let after = &rest[at + 1..];
+        let end = after.find(|c: char| !vault::is_label_char(c)).unwrap_or(after.len());
+        match contacts.iter().find(|c| c.label == after[..end]) {
+            Some(contact) if end > 0 => {
+                if contact.kind != ContactKind::Address {
+                    out.push_str("0x");
+                }
+                out.push_str(&contact.value);
+            }
+            _ => {
+                out.push('@');
+                out.push_str(&after[..end]);
+            }
+        }
+        rest = &after[end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// `text` with both vault keys and contacts expanded, from what is stored.
+pub fn expand_all(text: &str) -> String {
+    expand(&vault::expand(text, &vault::load_vault()), &load())
+}
+
+/// Every label that can be written as `@label`, with what it stands for,
+/// for autocompletion.
+pub fn suggestions() -> Vec<(String, String)> {
+    let keys = vault::load_vault().into_iter().map(|e| (format!("@{}", e.label), "vault key".to_string()));
+    let contacts = load().into_iter().map(|c| {
+        let hint = if c.note.is_empty() { c.kind.as_str().to_string() } else { format!("{}: {}", c.kind.as_str(), c.note) };
+        (format!("@{}", c.label), hint)
+    });
+    keys.chain(contacts).collect()
+}
@@ -0,0 +1,146 @@
+//! Address book: name counterparties' keys, hashes and addresses once and
+//! refer to them as `@label`.
+
+use leptos::prelude::*;
+
+use crate::address_book::{self, Contact, ContactKind};
+use crate::i18n::{self, t, Msg};
+use crate::vault;
+
+#[component]
+pub fn AddressBookPanel() -> impl IntoView {
+    let lang = i18n::use_lang();
+    let contacts = RwSignal::new(address_book::load());
+    let (label, set_label) = signal(String::new());
+    let (kind, set_kind) = signal(ContactKind::Pubkey);
+    let (value, set_value) = signal(String::new());
+    let (note, set_note) = signal(String::new());
+    let (error, set_error) = signal::<Option<String>>(None);
+
+    let add = move |_| {
+        let label = label.get_untracked().trim().to_string();
+        let checked = contacts
+            .with_untracked(|list| address_book::validate_label(&label, list, &vault::load_vault()))
+            .and_then(|()| address_book::normalize(kind.get_untracked(), &value.get_untracked()));
+        match checked {
+            Ok(value) => {
+                contacts.update(|list| {
+                    list.push(Contact {
+                        label,
+                        kind: kind.get_untracked(),
+                        value,
+                        note: note.get_untracked().trim().to_string(),
+                    })
+                });
+                contacts.with_untracked(|list| address_book::save(list));
+                set_label.set(String::new());
+                set_value.set(String::new());
+                set_note.set(String::new());
+                set_error.set(None);
+            }
+            Err(e) => set_error.set(Some(e)),
+        }
+    };
+
+    let remove = move |index: usize| {
+        contacts.update(|list| {
+            if index < list.len() {
+                list.remove(index);
+            }
+        });
+        contacts.with_untracked(|list| address_book::save(list));
+    };
+
+    let rows = move || {
+        contacts
+            .get()
+            .into_iter()
+            .enumerate()
+            .map(|(index, contact)| {
+                let shown = if contact.value.len() > 20 {
+                    format!("{}…{}", &contact.value[..10], &contact.value[contact.value.len() - 8..])
+                } else {
+                    contact.value.clone()
+                };
+                view! {
+                    <li class="invoice-row">
+                        <span class="invoice-label" title=contact.note.clone()>
+                            {format!("@{}", contact.label)}
+                        </span>
+                        <span class="invoice-amount">{contact.kind.as_str()}</span>
+                        <code title=contact.value.clone()>{shown}</code>
+                        <button class="danger" on:click=move |_| remove(index)>
+                            {t(Msg::Remove)}
+                        </button>
+                    </li>
+                }
+            })
+            .collect_view()
+    };
+
+    // Completions for `@label` fields across the page; vault keys are read
+    // again whenever the address book changes.
+    let options = move || {
+        contacts.track();
+        address_book::suggestions()
+            .into_iter()
+            .map(|(label, hint)| view! { <option value=label>{hint}</option> })
+            .collect_view()
+    };
+
+    view! {
+        <div class="vault-panel">
+            <label>{t(Msg::AddressBook)}</label>
+            <div class="invoice-form">
+                <input
+                    type="text"
+                    placeholder=t(Msg::ContactLabelPlaceholder)
+                    prop:value=move || label.get()
+                    on:input=move |ev| set_label.set(event_target_value(&ev))
+                />
+                <select
+                    aria-label=t(Msg::Kind)
+                    on:change=move |ev| {
+                        if let Some(kind) = ContactKind::parse(&event_target_value(&ev)) {
+                            set_kind.set(kind);
+                        }
+                    }
+                >
+                    {ContactKind::ALL
+                        .into_iter()
+                        .map(|k| view! { <option value=k.as_str() selected=move || kind.get() == k>{k.as_str()}</option> })
+                        .collect_view()}
+                </select>
+                <input
+                    type="text"
+                    spellcheck="false"
+                    placeholder=move || {
+                        let msg = match kind.get() {
+                            ContactKind::Pubkey => Msg::XonlyKeyPlaceholder,
+                            ContactKind::Hash => Msg::HashPlaceholder,
+                            ContactKind::Address => Msg::Address,
+                        };
+                        i18n::text(lang.get(), msg)
+                    }
+                    prop:value=move || value.get()
+                    on:input=move |ev| set_value.set(event_target_value(&ev))
+                />
+                <input
+                    type="text"
+                    placeholder=t(Msg::NoteOptional)
+                    prop:value=move || note.get()
+                    on:input=move |ev| set_note.set(event_target_value(&ev))
+                />
+                <button on:click=add disabled=move || label.get().trim().is_empty() || value.get().trim().is_empty()>
+                    {t(Msg::AddContact)}
+                </button>
+            </div>
+            <p class="drop-zone-hint">
+                {t(Msg::AddressBookHint)}
+            </p>
+            {move || error.get().map(|e| view! { <p class="tool-error">{e}</p> })}
+            <ul class="invoice-list">{rows}</ul>
+            <datalist id="address-book">{options}</datalist>
+        </div>
+    }
+}
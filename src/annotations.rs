@@ -0,0 +1,89 @@
+//! Doc comments written in the source, for the reports that describe it.
+//!
+//! A run of `///` lines documents the line below it. On a function
+//! definition it documents the function; on a line reading `witness::NAME`,
+//! or on `const NAME` inside `mod witness { ... }`, it documents that witness.
+//! The first documented occurrence of a name wins.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::sanity::{qualified_names, strip_comment};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Annotations {
+    /// Function name to its doc comment.
+    pub functions: BTreeMap<String, String>,
+    /// Witness name, without `witness::`, to its doc comment.
+    pub witnesses: BTreeMap<String, String>,
+}
+
+impl Annotations {
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty() && self.witnesses.is_empty()
+    }
+}
+
+/// The `///` lines right above line `index`, joined into one paragraph.
+pub fn doc_above(lines: &[&str], index: usize) -> Option<String> {
+    let mut docs: Vec<&str> = lines[..index]
+        .iter()
+        .rev()
+        .map_while(|line| line.trim().strip_prefix("///"))
+        .map(str::trim)
+        .collect();
+    docs.reverse();
+    let doc = docs.join(" ").trim().to_string();
+    (!doc.is_empty()).then_some(doc)
+}
+
+/// The name of the function defined on `line`, if one is.
+pub(crate) fn defined_function(line: &str) -> Option<&str> {
+    let trimmed = line.trim();
+    let rest = trimmed.strip_prefix("pub ").unwrap_or(trimmed).strip_prefix("fn ")?;
+    let name = rest.split(|c: char| c == '(' || c == '<').next()?.trim();
+    (!name.is_empty()).then_some(name)
+}
+
+/// Every doc comment on a function or witness in `code`.
+pub fn extract(code: &str) -> Annotations {
+    let lines: Vec<&str> = code.lines().collect();
+    let mut annotations = Annotations::default();
+    // Brace depth at which a `mod witness` block was opened, while inside one.
+    let mut witness_module: Option<usize> = None;
+    let mut depth = 0usize;
+
+    for (index, line) in lines.iter().enumerate() {
+        let line = strip_comment(line);
+        let trimmed = line.trim();
+        if trimmed.starts_with("mod witness") {
+            witness_module = Some(depth);
+        }
+
+        let mut witnesses = qualified_names(line, "witness::");
+        if witness_module.is_some() {
+            if let Some(rest) = trimmed.strip_prefix("const ") {
+                witnesses.extend(rest.split(':').next().map(str::trim));
+            }
+        }
+        let function = defined_function(line);
+        if function.is_some() || !witnesses.is_empty() {
+            if let Some(doc) = doc_above(&lines, index) {
+                if let Some(name) = function {
+                    annotations.functions.entry(name.to_string()).or_insert_with(|| doc.clone());
+                }
+                for name in witnesses {
+                    annotations.witnesses.entry(name.to_string()).or_insert_with(|| doc.clone());
+                }
+            }
+        }
+
+        depth += line.matches('{').count();
+        depth = depth.saturating_sub(line.matches('}').count());
+        if line.contains('}') && witness_module.is_some_and(|opened| depth <= opened) {
+            witness_module = None;
+        }
+    }
+    annotations
+}
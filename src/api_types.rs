@@ -0,0 +1,304 @@
+//! JSON results of the `wasm_api` entry points.
+//!
+//! They live apart from `wasm_api` so the UI can read the results of a
+//! lazily loaded core without linking the compiler into the shell.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::address::Tapleaf;
+use crate::attestation::{Attestation, AttestationCheck};
+use crate::benchmark::BenchmarkReport;
+use crate::bip32::DerivedKey;
+use crate::bulk;
+use crate::cmr_verify::CmrVerification;
+use crate::coverage;
+use crate::diagnostics::Diagnostic;
+use crate::hexdump;
+use crate::jet_usage;
+use crate::locktime;
+use crate::metadata::Metadata;
+use crate::minimize;
+use crate::mock_tx::MockRun;
+use crate::offer::{Offer, OfferCheck};
+use crate::onchain::SpendComparison;
+use crate::pruning;
+use crate::repl::Evaluation;
+use crate::report;
+use crate::schema;
+use crate::sigverify;
+use crate::source_map::SourceMap;
+use crate::spend;
+use crate::summary::ProgramSummary;
+use crate::taproot;
+use crate::test_runner;
+use crate::timestamp::Stamp;
+use crate::trace::Trace;
+use crate::value_codec::Encoded;
+use crate::witness_gen;
+use crate::witness_size;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CompileResult {
+    pub cmr: Option<String>,
+    pub error: Option<String>,
+    /// When the compile ran; stamped on failures too so attempts can be ordered.
+    #[serde(default)]
+    pub timestamp: Stamp,
+    /// Leaf version, script and hash of the program's tapleaf, on success.
+    #[serde(default)]
+    pub leaf: Option<Tapleaf>,
+    /// The source's metadata header, on success if it has one.
+    #[serde(default)]
+    pub metadata: Option<Metadata>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SummaryResult {
+    pub summary: Option<ProgramSummary>,
+    pub text: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AnalysisResult {
+    /// Jet name → calls and cost.
+    pub jets: BTreeMap<String, jet_usage::JetUsage>,
+    /// Sum of the jets' total costs, in milliweight.
+    pub total_cost: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ListingResult {
+    pub text: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SourceMapResult {
+    pub map: Option<SourceMap>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct HexDumpResult {
+    pub dump: Option<hexdump::HexDump>,
+    pub text: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct CheckResult {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AddressResult {
+    pub cmr: Option<String>,
+    pub address: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct VerifyCmrResult {
+    pub verification: Option<CmrVerification>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OnChainResult {
+    pub comparison: Option<SpendComparison>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AttestationResult {
+    pub attestation: Option<Attestation>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AttestationCheckResult {
+    pub check: Option<AttestationCheck>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OfferResult {
+    /// The offer string, starting with `simoffer1`.
+    pub payload: Option<String>,
+    pub offer: Option<Offer>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OfferImportResult {
+    pub offer: Option<Offer>,
+    pub check: Option<OfferCheck>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EvaluationResult {
+    pub evaluation: Option<Evaluation>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EncodeResult {
+    pub encoded: Option<Encoded>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DecodeResult {
+    pub value: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BenchmarkResult {
+    pub report: Option<BenchmarkReport>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MockRunResult {
+    pub run: Option<MockRun>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TraceResult {
+    pub trace: Option<Trace>,
+    /// The trace rendered as text.
+    pub text: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BulkResult {
+    pub report: Option<bulk::BulkReport>,
+    pub csv: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SignatureResult {
+    pub valid: Option<bool>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WitnessSignaturesResult {
+    pub checks: Option<Vec<sigverify::SignatureCheck>>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SpendResult {
+    pub tx: Option<spend::SpendTx>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SighashResult {
+    pub sighash: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DescriptorResult {
+    pub descriptor: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TaprootResult {
+    pub spend_info: Option<taproot::SpendInfoReport>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ControlBlockResult {
+    pub control_block: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ControlBlockCheckResult {
+    pub check: Option<taproot::ControlBlockCheck>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ScriptPubkeyResult {
+    pub script_pubkey: Option<taproot::ScriptPubkeyInfo>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DeriveKeyResult {
+    pub key: Option<DerivedKey>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LockResult {
+    pub lock: Option<locktime::EncodedLock>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TestRunResult {
+    pub results: Vec<test_runner::TestOutcome>,
+    pub passed: usize,
+    pub failed: usize,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CoverageResult {
+    pub report: Option<coverage::CoverageReport>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PruningResult {
+    pub report: Option<pruning::PruningReport>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WitnessSizeResult {
+    pub estimate: Option<witness_size::WitnessEstimate>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RandomWitnessResult {
+    pub witness: Option<witness_gen::RandomWitness>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MinimizeResult {
+    pub reduction: Option<minimize::Reduction>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReportResult {
+    pub report: Option<report::Report>,
+    /// The report as a standalone HTML document.
+    pub html: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SchemaResult {
+    pub valid: bool,
+    pub errors: Vec<schema::SchemaError>,
+}
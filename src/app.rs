@@ -0,0 +1,1918 @@
+//! The full-page playground application.
+
+use leptos::prelude::*;
+use leptos::html::Textarea;
+use leptos::task::spawn_local;
+use wasm_bindgen::prelude::*;
+use web_sys::HtmlTextAreaElement;
+
+use crate::address::{parse_cmr, Network};
+use crate::address_book_panel::AddressBookPanel;
+use crate::autosave::{self, Autosave};
+use crate::bottom_sheet::{self, BottomSheet, SheetState};
+use crate::benchmark_panel::BenchmarkPanel;
+use crate::bip32;
+use crate::bulk_panel::BulkPanel;
+use crate::dev_console_panel::DevConsole;
+use crate::diagnostics::{Diagnostic, Fix};
+use crate::editor::gutter::Gutter;
+use crate::editor::history::{EditKind, History};
+use crate::editor::overlay::{Mark, Overlay};
+use crate::editor::pairs::{self, Edit, EditorSettings};
+use crate::editor::search_bar::SearchBar;
+use crate::error_explain_panel::LearnMore;
+use crate::funding_panel::FundingPanel;
+use crate::hexdump_panel::HexDumpPanel;
+use crate::i18n::{self, t, Lang, Msg};
+use crate::invoice_panel::InvoicePanel;
+use crate::jet_usage_panel::JetUsagePanel;
+use crate::leakage;
+use crate::listing_panel::ListingPanel;
+use crate::logging::{self, Category};
+use crate::manifest::KeyManifest;
+use crate::metadata;
+use crate::minimize_panel::MinimizePanel;
+use crate::mock_tx_panel::{load_mock, MockTxPanel};
+use crate::multisig_panel::MultisigPanel;
+use crate::musig_panel::MusigPanel;
+use crate::network_panel::NetworkPanel;
+use crate::options::{CompileOptions, Target};
+use crate::offer_panel::OfferPanel;
+use crate::outline_panel::OutlinePanel;
+use crate::param_preview::ParamPreview;
+use crate::param_values::{self, ValuesFile};
+use crate::plugins::{self, PluginPanels};
+use crate::pruning_panel::PruningPanel;
+use crate::quick_fix;
+use crate::recent::{self, RecentEntry};
+use crate::recent_panel::RecentPanel;
+use crate::registry_panel::{self, RegistryPanel};
+use crate::remote_panel::RemotePanel;
+use crate::reminder_panel::ReminderPanel;
+use crate::repl_panel::ReplPanel;
+use crate::routes::{self, Route};
+use crate::sanity::{self, SanityReport};
+use crate::sanity_panel::SanityPanel;
+use crate::schema::{self, SchemaError};
+use crate::session::{Session, SessionResults, SESSION_VERSION};
+use crate::settings::Settings;
+use crate::settings_panel::SettingsDialog;
+use crate::signature_panel::SignaturePanel;
+use crate::snapshot_panel::SnapshotPanel;
+use crate::source_map::SourceRange;
+use crate::spend_paths;
+use crate::spend_paths_panel::SpendPathsPanel;
+use crate::split_pane::{SplitEnd, SplitPane, SplitStart};
+use crate::taproot::TaprootConfig;
+use crate::taproot_panel::TaprootPanel;
+use crate::template_panel::TemplatePanel;
+use crate::templates::{Template, TEMPLATES};
+use crate::test_panel::{self, TestPanel};
+use crate::timestamp::{format_local, Stamp};
+use crate::triage::{TriageEnv, TriageReport};
+use crate::triage_panel::TriagePanel;
+use crate::vault_panel::VaultPanel;
+use crate::verify_panel::VerifyPanel;
+use crate::witness_sets::WitnessSets;
+use crate::witness_sets_panel::WitnessSetTabs;
+use crate::witness_wizard::WitnessWizard;
+use crate::wizards::wizard_panel::WizardPanel;
+use crate::{address_book, api_types, compiler, crash_log, descriptor, embed, esplora, fs_access, pwa, snapshots, styles};
+
+use wasm_bindgen::JsCast;
+
+#[cfg(feature = "hww")]
+fn hww_section() -> impl IntoView {
+    view! { <crate::hww_panel::HwwPanel /> }
+}
+
+#[cfg(not(feature = "hww"))]
+fn hww_section() -> impl IntoView {}
+
+/// A checkbox bound to one editor setting, saved on change.
+fn editor_option(
+    label: Msg,
+    settings: RwSignal<EditorSettings>,
+    get: fn(&EditorSettings) -> bool,
+    set: fn(&mut EditorSettings, bool),
+) -> impl IntoView {
+    view! {
+        <label class="editor-option">
+            <input
+                type="checkbox"
+                prop:checked=move || settings.with(get)
+                on:change=move |ev| {
+                    let enabled = event_target_checked(&ev);
+                    settings.update(|s| {
+                        set(s, enabled);
+                        s.save();
+                    });
+                }
+            />
+            {t(label)}
+        </label>
+    }
+}
+
+#[component]
+pub fn App(
+    /// Program shown on load instead of the default template.
+    #[prop(default = None)]
+    initial_code: Option<String>,
+    /// Hide editing controls and make the code textarea read-only.
+    #[prop(optional)]
+    readonly: bool,
+    #[prop(optional)] hide_witness: bool,
+    /// Show a published contract's results with no editing or compiling
+    /// controls; `#/view/<source>` links turn it on.
+    #[prop(optional)]
+    viewer: bool,
+    /// Element that receives `compiled` and `error` events, when embedded.
+    #[prop(default = None)]
+    events: Option<web_sys::Element>,
+) -> impl IntoView {
+    let (code, set_code) = signal(
+        initial_code.unwrap_or_else(|| "mod param {}\nfn main() {}".to_string()),
+    );
+    // Embedded playgrounds leave the host page's URL alone.
+    let routed = events.is_none();
+    let viewer = viewer || (routed && matches!(routes::current(), Route::View(_)));
+    let readonly = readonly || viewer;
+    let hide_witness = hide_witness || viewer;
+    let events = StoredValue::new_local(events);
+    let lang = RwSignal::new(Lang::load());
+    provide_context(lang);
+    Effect::new(move |_| {
+        let code = lang.get().code();
+        if let Some(root) = web_sys::window().and_then(|w| w.document()).and_then(|d| d.document_element()) {
+            let _ = root.set_attribute("lang", code);
+        }
+    });
+    // The program every compile, panel and export works on: the editor's
+    // source as the plugins' transforms leave it.
+    let (program, set_program) = signal(code.get_untracked());
+    let transform_generation = StoredValue::new(0u64);
+    Effect::new(move |_| {
+        plugins::track_registrations();
+        let source = code.get();
+        let generation = transform_generation.get_value() + 1;
+        transform_generation.set_value(generation);
+        if !plugins::has_transforms() {
+            set_program.set(source);
+            return;
+        }
+        spawn_local(async move {
+            let transformed = plugins::transform_source(source).await;
+            // A later edit may have finished first.
+            if transform_generation.get_value() == generation {
+                set_program.set(transformed);
+            }
+        });
+    });
+    let witness_sets = RwSignal::new(WitnessSets::load());
+    // A viewer shows the contract as published, without this browser's witness or settings.
+    let (witness, set_witness) = signal(if viewer {
+        String::new()
+    } else {
+        witness_sets.with_untracked(|sets| sets.active_set().witness.clone())
+    });
+    let (cmr, set_cmr) = signal::<Option<String>>(None);
+    let (code_base64, set_code_base64) = signal::<Option<String>>(None);
+    let (witness_info, set_witness_info) = signal::<Option<String>>(None);
+    let (error, set_error) = signal::<Option<String>>(None);
+    let (summary_text, set_summary_text) = signal::<Option<String>>(None);
+    // Read out by screen readers when a compile succeeds; errors are alerts.
+    let (announcement, set_announcement) = signal(String::new());
+    let results_ref = NodeRef::<leptos::html::Div>::new();
+    let sheet = RwSignal::new(SheetState::default());
+    let keyboard_inset = bottom_sheet::keyboard_inset();
+    let installable = pwa::install_available();
+    let (crash_log_on, set_crash_log_on) = signal(crash_log::is_enabled());
+    let code_file_input = NodeRef::<leptos::html::Input>::new();
+    let witness_file_input = NodeRef::<leptos::html::Input>::new();
+    let (compiled_at, set_compiled_at) = signal::<Option<Stamp>>(None);
+    // Button label while a compile is in flight.
+    let (compile_status, set_compile_status) = signal::<Option<Msg>>(None);
+    let (diagnostics, set_diagnostics) = signal::<Vec<Diagnostic>>(Vec::new());
+    let (editor_scroll_top, set_editor_scroll_top) = signal(0.0_f64);
+    let (editor_scroll_left, set_editor_scroll_left) = signal(0.0_f64);
+    // Caret position as a byte offset into the code, while there is no selection.
+    let (cursor, set_cursor) = signal::<Option<usize>>(None);
+    let editor_settings = RwSignal::new(EditorSettings::load());
+    let settings = RwSignal::new(Settings::load());
+    let settings_open = RwSignal::new(false);
+    let route = RwSignal::new(if routed { routes::current() } else { Route::Editor });
+    let template_choice = RwSignal::new(TEMPLATES[0].name);
+    let templates_open = RwSignal::new(false);
+    // Parameter shown in the inline value preview.
+    let previewed_param = RwSignal::new(None::<String>);
+    let measure_ref = NodeRef::<leptos::html::Span>::new();
+    let search_open = RwSignal::new(false);
+    let search_replacing = RwSignal::new(false);
+    let (search_marks, set_search_marks) = signal(Vec::<Mark>::new());
+    // Source of the node, step or failure last picked in a panel.
+    let (source_marks, set_source_marks) = signal(Vec::<Mark>::new());
+    let textarea_ref = NodeRef::<Textarea>::new();
+    let (drag_over_code, set_drag_over_code) = signal(false);
+    let (drag_over_witness, set_drag_over_witness) = signal(false);
+    // Edit the witness through typed fields instead of raw JSON.
+    let (witness_form, set_witness_form) = signal(false);
+    let history = RwSignal::new(History::default());
+    // Schema errors of the witness JSON, shown under its textarea.
+    let witness_errors = Memo::new(move |_| -> Vec<SchemaError> {
+        let witness = witness.get();
+        if witness.trim().is_empty() {
+            return Vec::new();
+        }
+        schema::validate(&address_book::expand_all(&witness))
+    });
+    let network = RwSignal::new(settings.with_untracked(|s| s.default_network));
+    let taproot_config = RwSignal::new(if viewer { TaprootConfig::default() } else { TaprootConfig::load() });
+    let compile_options = RwSignal::new(if viewer { CompileOptions::default() } else { CompileOptions::load() });
+    let tests = RwSignal::new(test_panel::load_tests());
+    // Where an imported session came from, until the next import.
+    let (session_note, set_session_note) = signal::<Option<String>>(None);
+    let session_input = NodeRef::<leptos::html::Input>::new();
+    // The file "Save" writes back to, once one was opened or saved.
+    let (file_name, set_file_name) = signal::<Option<String>>(None);
+    // Revisions recorded by saves, so the snapshot list can follow.
+    let (saves, set_saves) = signal(0u32);
+    let contract_name = Memo::new(move |_| {
+        file_name
+            .get()
+            .or_else(|| code.with(|code| recent::comment_name(code)))
+            .unwrap_or_else(|| i18n::text(lang.get(), Msg::Untitled).to_string())
+    });
+    // Work left unsaved by a previous visit that ended without a compile or export.
+    let recovery = RwSignal::new(if readonly { None } else { autosave::recoverable(&code.get_untracked()) });
+    Effect::new(move |_| {
+        let (code, witness) = (code.get(), witness.get());
+        // Keep the old backup until the user has answered the prompt.
+        if !readonly && recovery.with(Option::is_none) {
+            autosave::save(&code, &witness);
+        }
+    });
+    let address = Memo::new(move |_| {
+        let cmr = parse_cmr(&cmr.get()?).ok()?;
+        taproot_config.with(|config| config.address(cmr, network.get())).ok().map(|a| a.to_string())
+    });
+    // Descriptors only express the single-leaf tree.
+    let output_descriptor = Memo::new(move |_| {
+        let cmr = parse_cmr(&cmr.get()?).ok()?;
+        taproot_config.with(|config| {
+            let internal_key = config.internal_key().ok()?;
+            config.leaves.is_empty().then(|| descriptor::descriptor(cmr, &internal_key))
+        })
+    });
+    let manifest = RwSignal::new(KeyManifest::load());
+    let (sanity_report, set_sanity_report) = signal::<Option<SanityReport>>(None);
+    let address_acknowledged = RwSignal::new(false);
+    let (triage_report, set_triage_report) = signal::<Option<TriageReport>>(None);
+    // The address is withheld while sanity findings are unacknowledged.
+    let shared_address = Memo::new(move |_| {
+        let address = address.get()?;
+        let cleared = address_acknowledged.get()
+            || sanity_report.with(|r| r.as_ref().map_or(true, SanityReport::is_clean));
+        cleared.then_some(address)
+    });
+
+    // Every change to the code buffer goes through here so it can be undone.
+    let edit_code = move |new_code: String, kind: EditKind| {
+        let before = code.get_untracked();
+        if before != new_code {
+            history.update(|h| h.record(&before, kind, js_sys::Date::now()));
+            set_code.set(new_code);
+        }
+    };
+
+    let bracket_marks = Memo::new(move |_| {
+        if !editor_settings.with(|s| s.match_brackets) {
+            return Vec::new();
+        }
+        let Some(at) = cursor.get() else {
+            return Vec::new();
+        };
+        code.with(|code| pairs::matching_bracket(code, at))
+            .map(|(a, b)| {
+                [a, b]
+                    .into_iter()
+                    .map(|start| Mark {
+                        start,
+                        end: start + 1,
+                        class: "bracket-match",
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    });
+    // Ranges mapped from the code before an edit no longer fit it.
+    Effect::new(move |_| {
+        code.track();
+        set_source_marks.set(Vec::new());
+    });
+    let editor_marks = Memo::new(move |_| {
+        let mut marks = search_marks.get();
+        marks.extend(bracket_marks.get());
+        marks.extend(source_marks.get());
+        marks
+    });
+
+    // Patch the buffer with a diagnostic's fix. The offsets of other fixes
+    // no longer hold afterwards, so they are dropped until the next check.
+    let apply_fix = Callback::new(move |fix: Fix| {
+        if readonly {
+            return;
+        }
+        let Some(patched) = code.with_untracked(|code| fix.apply(code)) else {
+            return;
+        };
+        edit_code(patched, EditKind::Programmatic);
+        set_diagnostics.update(|diagnostics| {
+            diagnostics.retain(|d| d.fix.as_ref() != Some(&fix));
+            diagnostics.iter_mut().for_each(|d| d.fix = None);
+        });
+    });
+
+    let undo = move || {
+        if let Some(previous) = history.try_update(|h| h.undo(&code.get_untracked())).flatten() {
+            set_code.set(previous);
+        }
+    };
+
+    let redo = move || {
+        if let Some(next) = history.try_update(|h| h.redo(&code.get_untracked())).flatten() {
+            set_code.set(next);
+        }
+    };
+
+    // The textarea selection as byte offsets into the code.
+    let selection = move || {
+        let textarea = textarea_ref.get_untracked()?;
+        let start = textarea.selection_start().ok().flatten()?;
+        let end = textarea.selection_end().ok().flatten()?;
+        code.with_untracked(|code| Some((pairs::byte_offset(code, start), pairs::byte_offset(code, end))))
+    };
+
+    let track_cursor = move || {
+        let caret = selection().and_then(|(start, end)| (start == end).then_some(start));
+        set_cursor.set(caret);
+        if let Some(name) = caret.and_then(|at| code.with_untracked(|code| param_values::param_at(code, at))) {
+            previewed_param.set(Some(name));
+        }
+    };
+
+    let apply_edit = move |edit: Edit| {
+        let position = pairs::utf16_offset(&edit.text, edit.cursor);
+        edit_code(edit.text, EditKind::Typing);
+        set_cursor.set(Some(edit.cursor));
+        // The new value reaches the textarea on the next render.
+        set_timeout(
+            move || {
+                if let Some(textarea) = textarea_ref.get_untracked() {
+                    let _ = textarea.set_selection_range(position, position);
+                }
+            },
+            std::time::Duration::from_millis(0),
+        );
+    };
+
+    // Preview the parameter under the pointer. The textarea is monospaced, so
+    // the pointer position gives the line and column directly.
+    let handle_editor_mousemove = move |ev: web_sys::MouseEvent| {
+        let (Some(textarea), Some(measure)) = (textarea_ref.get_untracked(), measure_ref.get_untracked()) else {
+            return;
+        };
+        let char_width = measure.get_bounding_client_rect().width() / 10.0;
+        if char_width <= 0.0 {
+            return;
+        }
+        let x = f64::from(ev.offset_x()) - 12.0 + f64::from(textarea.scroll_left());
+        let y = f64::from(ev.offset_y()) - 12.0 + f64::from(textarea.scroll_top());
+        if x < 0.0 || y < 0.0 {
+            return;
+        }
+        let line_height = f64::from(settings.with_untracked(Settings::line_height));
+        let (line, column) = ((y / line_height) as usize, (x / char_width) as usize);
+        let name = code.with_untracked(|code| {
+            let start: usize = code.split('\n').take(line).map(|l| l.len() + 1).sum();
+            let text = code.split('\n').nth(line)?;
+            let offset = text.char_indices().nth(column).map(|(at, _)| at)?;
+            param_values::param_at(code, start + offset)
+        });
+        if name.is_some() && name != previewed_param.get_untracked() {
+            previewed_param.set(name);
+        }
+    };
+
+    // Put the caret at the start of `line` and scroll it into view.
+    let reveal_line = move |line: usize| {
+        let Some(textarea) = textarea_ref.get_untracked() else {
+            return;
+        };
+        let start = code.with_untracked(|code| {
+            let offset = code.split('\n').take(line.saturating_sub(1)).map(|l| l.len() + 1).sum::<usize>();
+            pairs::utf16_offset(code, offset)
+        });
+        let _ = textarea.focus();
+        let _ = textarea.set_selection_range(start, start);
+        let line_height = settings.with_untracked(Settings::line_height) as i32;
+        textarea.set_scroll_top(((line as i32 - 1) * line_height - textarea.client_height() / 2).max(0));
+        track_cursor();
+    };
+
+    // Highlight where a node came from and scroll to its first range.
+    let show_source = Callback::new(move |ranges: Vec<SourceRange>| {
+        if let Some(first) = ranges.first() {
+            reveal_line(first.line);
+        }
+        set_source_marks.set(
+            ranges
+                .into_iter()
+                .map(|range| Mark {
+                    start: range.start,
+                    end: range.end,
+                    class: "source-match",
+                })
+                .collect(),
+        );
+    });
+
+    // Return focus to the editor after a dialog or panel replaced the code.
+    let focus_editor = move || {
+        if let Some(textarea) = textarea_ref.get_untracked() {
+            let _ = textarea.focus();
+        }
+    };
+
+    // Follow the route in the address bar, including back and forward.
+    if routed {
+        let hashchange = window_event_listener(leptos::ev::hashchange, move |_| route.set(routes::current()));
+        on_cleanup(move || hashchange.remove());
+    }
+    // Choosing a template makes it the route, so it can be bookmarked.
+    Effect::new(move |previous: Option<()>| {
+        let name = template_choice.get();
+        if routed && previous.is_some() {
+            routes::navigate(&Route::Example(routes::slug(name)));
+        }
+    });
+    Effect::new(move |previous: Option<()>| match route.get() {
+        // The page starts in the editor; only moving back to it takes focus.
+        Route::Editor => {
+            if previous.is_some() {
+                focus_editor();
+            }
+        }
+        Route::Debug => {
+            sheet.update(|s| *s = (*s).max(SheetState::Half));
+            if let Some(results) = results_ref.get_untracked() {
+                let _ = results.focus();
+            }
+        }
+        Route::Example(slug) => match Template::find_slug(&slug) {
+            Some(template) => {
+                template_choice.set(template.name);
+                templates_open.set(true);
+            }
+            None => set_error.set(Some(i18n::format(i18n::text(lang.get_untracked(), Msg::NoExampleNamed), &[&slug]))),
+        },
+        Route::Saved(name) => {
+            let latest = snapshots::load()
+                .into_iter()
+                .find(|c| c.name == name)
+                .and_then(|mut c| c.revisions.pop());
+            match latest {
+                Some(revision) => {
+                    untrack(|| edit_code(revision.source, EditKind::Programmatic));
+                    set_cmr.set(None);
+                    set_error.set(None);
+                }
+                None => {
+                    set_error.set(Some(i18n::format(i18n::text(lang.get_untracked(), Msg::NoSavedContract), &[&name])))
+                }
+            }
+        }
+        // Loaded and compiled below, once compiling is set up.
+        Route::View(_) => {}
+    });
+
+    // Auto-indent and auto-closing pairs, when enabled.
+    let smart_edit = move |ev: &web_sys::KeyboardEvent| -> Option<Edit> {
+        let indent_width = settings.with_untracked(|s| s.tab_width as usize);
+        let settings = editor_settings.get_untracked();
+        let (start, end) = selection()?;
+        let key = ev.key();
+        code.with_untracked(|code| match key.as_str() {
+            "Enter" if settings.auto_indent => Some(pairs::newline(code, start, end, indent_width)),
+            "Backspace" if settings.auto_close => pairs::backspace(code, start, end),
+            _ if settings.auto_close && key.chars().count() == 1 => {
+                pairs::type_char(code, start, end, key.chars().next()?)
+            }
+            _ => None,
+        })
+    };
+
+    let handle_editor_keydown = move |ev: web_sys::KeyboardEvent| {
+        if readonly {
+            return;
+        }
+        if !(ev.ctrl_key() || ev.meta_key() || ev.alt_key()) {
+            if let Some(edit) = smart_edit(&ev) {
+                ev.prevent_default();
+                apply_edit(edit);
+            }
+            return;
+        }
+        match ev.key().to_lowercase().as_str() {
+            "z" if ev.shift_key() => redo(),
+            "z" => undo(),
+            "y" => redo(),
+            "f" => {
+                search_replacing.set(false);
+                search_open.set(true);
+            }
+            "h" => {
+                search_replacing.set(true);
+                search_open.set(true);
+            }
+            _ => return,
+        }
+        ev.prevent_default();
+    };
+
+    let handle_compile = move || {
+        let source = code.get();
+        let witness_value = address_book::expand_all(&witness.get());
+        
+        if source.trim().is_empty() {
+            set_error.set(Some(i18n::text(lang.get_untracked(), Msg::CodeEmpty).to_string()));
+            set_triage_report.set(None);
+            set_cmr.set(None);
+            set_code_base64.set(None);
+            set_witness_info.set(None);
+            return;
+        }
+        if compile_status.get_untracked().is_some() {
+            return;
+        }
+
+        set_error.set(None);
+        set_announcement.set(String::new());
+        set_compile_status.set(Some(if compiler::is_loaded() {
+            Msg::Compiling
+        } else {
+            Msg::LoadingCompiler
+        }));
+
+        spawn_local(async move {
+            // Transformed as the program signal is, so panels and exports agree with the compile.
+            let transformed = plugins::transform_source(source.clone()).await;
+            // Diagnostics only line up with the editor while the line count is kept.
+            let same_lines = transformed.lines().count() == source.lines().count();
+            // `@label` references to vault keys and contacts become their values.
+            let code_value = address_book::expand_all(&transformed);
+            logging::info(Category::Compiler, format!("Compiling {} bytes of source", code_value.len()));
+            logging::debug(Category::Compiler, format!("Source:\n{code_value}"));
+            // Check if witness data is provided and use appropriate compilation method
+            let options = compile_options.get_untracked();
+            let compile_result = if options != CompileOptions::default() {
+                logging::debug(Category::Compiler, "Using compile_with_options");
+                let options_json = serde_json::to_string(&options).unwrap_or_default();
+                compiler::compile_with_options(&code_value, &options_json, &witness_value).await
+            } else if !witness_value.trim().is_empty() {
+                logging::debug(Category::Compiler, "Using compile_with_witness");
+                compiler::compile_with_witness(&code_value, &witness_value).await
+            } else {
+                logging::debug(Category::Compiler, "Using compile_simplicity");
+                compiler::compile_simplicity(&code_value).await
+            };
+            set_compile_status.set(None);
+            // Bring the outcome into view on phones.
+            sheet.update(|s| *s = (*s).max(SheetState::Half));
+
+            logging::debug(Category::Compiler, format!("Compile result: {compile_result}"));
+            plugins::after_compile(&compile_result, &code_value);
+            
+            if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(&compile_result) {
+                let failed = parsed
+                    .get("error")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|e| !e.is_empty());
+                events.with_value(|target| {
+                    if let Some(target) = target {
+                        embed::dispatch(target, if failed { "error" } else { "compiled" }, &compile_result);
+                    }
+                });
+                set_compiled_at.set(
+                    parsed
+                        .get("timestamp")
+                        .and_then(|t| serde_json::from_value::<Stamp>(t.clone()).ok()),
+                );
+
+                if let Some(err) = parsed.get("error").and_then(|v| v.as_str()) {
+                    if err != "null" && !err.is_empty() {
+                        set_error.set(Some(err.to_string()));
+                        set_cmr.set(None);
+                        set_code_base64.set(None);
+                        set_witness_info.set(None);
+                        set_summary_text.set(None);
+                        set_sanity_report.set(None);
+                        set_triage_report.set(None);
+                        let check = compiler::check_program(&code_value).await;
+                        set_diagnostics.set(
+                            serde_json::from_str::<api_types::CheckResult>(&check)
+                                .ok()
+                                .filter(|_| same_lines)
+                                .map(|c| c.diagnostics)
+                                .unwrap_or_default(),
+                        );
+                        // The tip is only used to check height locks; triage without it.
+                        let block_height = match esplora::base_url(network.get_untracked()) {
+                            Some(base) => esplora::tip_height(&base).await.ok(),
+                            None => None,
+                        };
+                        // The mock transaction the run panels use, for running the program again.
+                        let env = TriageEnv {
+                            block_height,
+                            mock: Some(load_mock()),
+                        };
+                        let env_json = serde_json::to_string(&env).unwrap_or_default();
+                        let triage = compiler::triage_failure(&code_value, &witness_value, err, &env_json).await;
+                        set_triage_report.set(serde_json::from_str::<TriageReport>(&triage).ok());
+                        return;
+                    }
+                }
+                
+                if let Some(cmr_val) = parsed.get("cmr").and_then(|v| v.as_str()) {
+                    if cmr_val != "null" && !cmr_val.is_empty() {
+                        // History keeps the source as written, `@label`s included.
+                        if let Err(e) = recent::record(RecentEntry::new(&source, cmr_val)).await {
+                            logging::warn(Category::Ui, format!("Could not record history: {e}"));
+                        }
+                        set_cmr.set(Some(cmr_val.to_string()));
+                        set_announcement.set(i18n::format(
+                            i18n::text(lang.get_untracked(), Msg::CompileAnnounced),
+                            &[cmr_val],
+                        ));
+                        autosave::mark_clean();
+                    }
+                }
+                
+                let mut findings = leakage::check(&code_value);
+                findings.extend(quick_fix::unused_witnesses(&code_value));
+                set_diagnostics.set(if same_lines { findings } else { Vec::new() });
+                set_triage_report.set(None);
+                address_acknowledged.set(false);
+                set_sanity_report.set(Some(sanity::check(
+                    &code_value,
+                    &witness_value,
+                    &manifest.get_untracked(),
+                )));
+                let b64 = encode_base64(&code_value);
+                set_code_base64.set(Some(b64));
+                
+                if let Some(w) = parsed.get("witness") {
+                    let witness_str = w.to_string();
+                    set_witness_info.set(Some(witness_str));
+                } else if !witness_value.trim().is_empty() {
+                    set_witness_info.set(Some(i18n::text(lang.get_untracked(), Msg::WitnessProcessed).to_string()));
+                } else {
+                    set_witness_info.set(Some(i18n::text(lang.get_untracked(), Msg::NoWitnessData).to_string()));
+                }
+                
+                set_error.set(None);
+
+                let summary = compiler::summarize_program(&code_value).await;
+                set_summary_text.set(
+                    serde_json::from_str::<serde_json::Value>(&summary)
+                        .ok()
+                        .and_then(|v| v.get("text").and_then(|t| t.as_str()).map(str::to_string)),
+                );
+                return;
+            }
+            
+            set_error.set(Some(i18n::text(lang.get_untracked(), Msg::InvalidResponse).to_string()));
+            set_triage_report.set(None);
+            set_cmr.set(None);
+            set_code_base64.set(None);
+            set_witness_info.set(None);
+            set_summary_text.set(None);
+            set_compiled_at.set(None);
+            set_sanity_report.set(None);
+        });
+    };
+
+    // Viewer links show their contract compiled, on load and whenever the link changes.
+    Effect::new(move |_| {
+        if let Route::View(source) = route.get() {
+            untrack(|| {
+                edit_code(source, EditKind::Programmatic);
+                handle_compile();
+            });
+        }
+    });
+
+    // With auto-compile on, compile once typing has paused.
+    let compile_generation = StoredValue::new(0u64);
+    Effect::new(move |previous: Option<()>| {
+        code.track();
+        witness.track();
+        if previous.is_none() || readonly || !settings.with_untracked(|s| s.auto_compile) {
+            return;
+        }
+        let generation = compile_generation.get_value() + 1;
+        compile_generation.set_value(generation);
+        set_timeout(
+            move || {
+                if compile_generation.get_value() == generation {
+                    untrack(handle_compile);
+                }
+            },
+            std::time::Duration::from_millis(800),
+        );
+    });
+
+    let insert_template = move |_| {
+        if let Some(textarea) = textarea_ref.get() {
+            let textarea_el: HtmlTextAreaElement = textarea;
+            let start = match textarea_el.selection_start() {
+                Ok(Some(pos)) => pos as usize,
+                _ => 0,
+            };
+            
+            let current_code = code.get();
+            let template = TEMPLATES[0].source;
+            let mut new_code = current_code.clone();
+            new_code.insert_str(start, template);
+            
+            edit_code(new_code, EditKind::Programmatic);
+            
+            let new_pos = start + template.len();
+            set_timeout(
+                move || {
+                    if let Some(textarea) = textarea_ref.get() {
+                        let textarea_el: HtmlTextAreaElement = textarea;
+                        let _ = textarea_el.set_selection_range(new_pos as u32, new_pos as u32);
+                    }
+                },
+                std::time::Duration::from_millis(10),
+            );
+        }
+    };
+
+    let clear_code = move |_| {
+        edit_code(String::new(), EditKind::Programmatic);
+        set_cmr.set(None);
+        set_code_base64.set(None);
+        set_witness_info.set(None);
+        set_error.set(None);
+        set_summary_text.set(None);
+        set_compiled_at.set(None);
+        set_sanity_report.set(None);
+        set_diagnostics.set(Vec::new());
+    };
+
+    let export_summary = move |_| {
+        if let Some(text) = summary_text.get() {
+            let conditions = spend_paths::analyze(&program.get_untracked()).to_text();
+            let text = format!("{text}\n\nSpending conditions:\n{conditions}");
+            let text = match compiled_at.get() {
+                Some(stamp) => format!("{}\n{}", stamp.to_text(), text),
+                None => text,
+            };
+            download_text("simplicity-summary.txt", &text);
+        }
+    };
+
+    // The program as a `#/view/...` link, opened in a new tab to copy and share.
+    let open_viewer_link = move |_| {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        let base = window.location().href().unwrap_or_default();
+        let base = base.split('#').next().unwrap_or_default();
+        // Labels and xpubs only mean something in this browser.
+        let source = address_book::expand_all(&program.get_untracked());
+        let source = bip32::expand(&source).unwrap_or(source);
+        let link = format!("{base}{}", Route::View(source).to_hash());
+        if let Err(e) = window.open_with_url_and_target(&link, "_blank") {
+            logging::warn(Category::Ui, format!("Could not open the viewer link: {e:?}"));
+        }
+    };
+
+    // The report carries the address, so it is held back with it.
+    let report_withheld = move || {
+        shared_address.get().is_none().then(|| i18n::text(lang.get(), Msg::ReportNeedsAddress))
+    };
+
+    // Audit report of the current program, downloaded as HTML or printed to PDF.
+    let export_report = move |print: bool| {
+        if shared_address.get_untracked().is_none() {
+            set_error.set(Some(i18n::text(lang.get_untracked(), Msg::ReportNeedsAddress).to_string()));
+            return;
+        }
+        let code_value = address_book::expand_all(&program.get_untracked());
+        let witness_value = address_book::expand_all(&witness.get_untracked());
+        let network = network.get_untracked();
+        let tree = serde_json::to_string(&taproot_config.get_untracked()).unwrap_or_default();
+        let lang = lang.get_untracked();
+        spawn_local(async move {
+            let result = compiler::compilation_report(&code_value, &witness_value, network.as_str(), &tree).await;
+            match serde_json::from_str::<api_types::ReportResult>(&result) {
+                Ok(api_types::ReportResult { html: Some(html), .. }) if print => print_html(&html),
+                Ok(api_types::ReportResult { html: Some(html), .. }) => {
+                    download_bytes("simplicity-report.html", "text/html;charset=utf-8", html.as_bytes())
+                }
+                Ok(api_types::ReportResult { error, .. }) => {
+                    set_error.set(Some(error.unwrap_or_else(|| i18n::text(lang, Msg::ReportFailed).to_string())))
+                }
+                Err(e) => {
+                    set_error.set(Some(i18n::format(i18n::text(lang, Msg::ReportFailedWith), &[&e.to_string()])))
+                }
+            }
+        });
+    };
+
+    let current_session = move || Session {
+        version: SESSION_VERSION,
+        code: code.get_untracked(),
+        witness: witness.get_untracked(),
+        options: compile_options.get_untracked(),
+        network: network.get_untracked(),
+        taproot: taproot_config.get_untracked(),
+        tests: tests.get_untracked(),
+        witness_sets: witness_sets.get_untracked(),
+        results: SessionResults {
+            cmr: cmr.get_untracked(),
+            address: shared_address.get_untracked(),
+            error: error.get_untracked(),
+            compiled_at: compiled_at.get_untracked(),
+        },
+        metadata: code.with_untracked(|code| metadata::parse(code)),
+        exported_at: Stamp::now(),
+    };
+
+    let export_session = move |_| {
+        download_text("simplicity-session.json", &current_session().to_json());
+        autosave::mark_clean();
+    };
+
+    let export_archive = move |_| {
+        download_bytes("simplicity-project.zip", "application/zip", &current_session().to_archive());
+        autosave::mark_clean();
+    };
+
+    let open_file = move |_| {
+        spawn_local(async move {
+            match fs_access::open().await {
+                Ok(Some(file)) => {
+                    edit_code(file.text, EditKind::Programmatic);
+                    set_file_name.set(Some(file.name));
+                    set_error.set(None);
+                }
+                Ok(None) => {}
+                Err(e) => set_error.set(Some(i18n::format(i18n::text(lang.get_untracked(), Msg::OpenFileFailed), &[&e]))),
+            }
+        });
+    };
+
+    let save_file = move |save_as: bool| {
+        spawn_local(async move {
+            let text = code.get_untracked();
+            let suggested = file_name.get_untracked().unwrap_or_else(|| "contract.simf".to_string());
+            let saved = if save_as {
+                fs_access::save_as(&text, &suggested).await
+            } else {
+                fs_access::save(&text, &suggested).await
+            };
+            match saved {
+                Ok(Some(name)) => {
+                    if let Some(number) = snapshots::commit(&name, &text) {
+                        if let Err(e) = registry_panel::register_saved(&name, Some(number), &text).await {
+                            logging::warn(Category::Ui, format!("Could not register {name}: {e}"));
+                        }
+                        set_saves.update(|n| *n += 1);
+                    }
+                    if routed {
+                        routes::replace(&Route::Saved(name.clone()));
+                    }
+                    set_file_name.set(Some(name));
+                    autosave::mark_clean();
+                }
+                Ok(None) => {}
+                Err(e) => set_error.set(Some(i18n::format(i18n::text(lang.get_untracked(), Msg::SaveFileFailed), &[&e]))),
+            }
+        });
+    };
+
+    let restore_autosave = move |_| {
+        if let Some(Autosave { code, witness, .. }) = recovery.get_untracked() {
+            edit_code(code, EditKind::Programmatic);
+            set_witness.set(witness);
+        }
+        recovery.set(None);
+        focus_editor();
+    };
+
+    let discard_autosave = move |_| {
+        autosave::discard();
+        recovery.set(None);
+        focus_editor();
+    };
+
+    let import_session = move |imported: Result<Session, String>| match imported {
+        Ok(session) => {
+            edit_code(session.code.clone(), EditKind::Programmatic);
+            let mut sets = session.witness_sets.clone();
+            if sets.sets.is_empty() {
+                sets = WitnessSets::default();
+            }
+            sets.active = sets.active.min(sets.sets.len() - 1);
+            sets.set_active_witness(&session.witness);
+            witness_sets.set(sets);
+            set_witness.set(session.witness.clone());
+            session.options.save();
+            compile_options.set(session.options.clone());
+            network.set(session.network);
+            session.taproot.save();
+            taproot_config.set(session.taproot.clone());
+            test_panel::save_tests(&session.tests);
+            tests.set(session.tests.clone());
+            set_cmr.set(None);
+            set_error.set(None);
+            set_session_note.set(Some(session.describe()));
+            focus_editor();
+        }
+        Err(e) => set_error.set(Some(e)),
+    };
+
+    // Sessions come as JSON or as a project archive.
+    let import_file = move |file: web_sys::File| {
+        if file.name().ends_with(".zip") {
+            read_file_bytes(file, move |_, bytes| import_session(Session::from_archive(&bytes)));
+        } else {
+            read_file(file, move |_, text| import_session(Session::parse(&text)));
+        }
+    };
+
+    let handle_session_file = move |ev: leptos::ev::Event| {
+        let input = event_target::<web_sys::HtmlInputElement>(&ev);
+        if let Some(file) = input.files().and_then(|files| files.get(0)) {
+            import_file(file);
+        }
+        input.set_value("");
+    };
+
+    let clear_witness = move |_| {
+        set_witness.set(String::new());
+    };
+
+    // Argument files set the values in `mod param` instead of the witness.
+    let apply_arguments = move |file_name: String, text: String| {
+        match param_values::apply_arguments(&code.get_untracked(), &text) {
+            Ok((updated, count)) => {
+                edit_code(updated, EditKind::Programmatic);
+                set_error.set(None);
+                set_announcement.set(i18n::format(
+                    i18n::text(lang.get_untracked(), Msg::ArgumentsApplied),
+                    &[&count.to_string(), &file_name],
+                ));
+            }
+            Err(e) => set_error.set(Some(e)),
+        }
+    };
+
+    // Contracts, sessions, projects and argument files, dropped or picked.
+    let open_code_file = move |file: web_sys::File| {
+        let file_name = file.name();
+        if file_name.ends_with(".args") || file_name.ends_with(".args.json") {
+            read_file(file, apply_arguments);
+        } else if file_name.ends_with(".json") || file_name.ends_with(".zip") {
+            import_file(file);
+        } else if file_name.ends_with(".simf") {
+            read_file(file, move |_, text| {
+                edit_code(text, EditKind::Programmatic);
+                set_error.set(None);
+                focus_editor();
+            });
+        } else {
+            set_error.set(Some(i18n::text(lang.get_untracked(), Msg::UnsupportedFile).to_string()));
+        }
+    };
+
+    let open_witness_file = move |file: web_sys::File| {
+        read_file(file, move |file_name, text| {
+            match param_values::classify(&file_name, &text, &code.get_untracked()) {
+                ValuesFile::Arguments => apply_arguments(file_name, text),
+                ValuesFile::Witness => set_witness.set(text),
+            }
+        });
+    };
+
+    // Drag & Drop for .simf files
+    let handle_simf_drop = move |ev: web_sys::DragEvent| {
+        ev.prevent_default();
+        ev.stop_propagation();
+        set_drag_over_code.set(false);
+        if let Some(file) = ev.data_transfer().and_then(|d| d.files()).and_then(|files| files.get(0)) {
+            open_code_file(file);
+        }
+    };
+
+    // Drag & Drop for witness files
+    let handle_witness_drop = move |ev: web_sys::DragEvent| {
+        ev.prevent_default();
+        ev.stop_propagation();
+        set_drag_over_witness.set(false);
+        if let Some(file) = ev.data_transfer().and_then(|d| d.files()).and_then(|files| files.get(0)) {
+            open_witness_file(file);
+        }
+    };
+
+    // Drop zones double as file pickers for keyboard and screen-reader users.
+    let pick_file = move |input: NodeRef<leptos::html::Input>| {
+        if let Some(input) = input.get_untracked() {
+            input.click();
+        }
+    };
+    let drop_zone_keydown = move |input: NodeRef<leptos::html::Input>| {
+        move |ev: web_sys::KeyboardEvent| {
+            if matches!(ev.key().as_str(), "Enter" | " ") {
+                ev.prevent_default();
+                pick_file(input);
+            }
+        }
+    };
+    let picked_file = move |ev: &leptos::ev::Event| {
+        let input = event_target::<web_sys::HtmlInputElement>(ev);
+        let file = input.files().and_then(|files| files.get(0));
+        input.set_value("");
+        file
+    };
+
+    let handle_simf_dragover = move |ev: web_sys::DragEvent| {
+        ev.prevent_default();
+        ev.stop_propagation();
+        set_drag_over_code.set(true);
+    };
+
+    let handle_simf_dragleave = move |_: web_sys::DragEvent| {
+        set_drag_over_code.set(false);
+    };
+
+    let handle_witness_dragover = move |ev: web_sys::DragEvent| {
+        ev.prevent_default();
+        ev.stop_propagation();
+        set_drag_over_witness.set(true);
+    };
+
+    let handle_witness_dragleave = move |_: web_sys::DragEvent| {
+        set_drag_over_witness.set(false);
+    };
+
+    view! {
+        <>
+            <style>{styles::stylesheet()}</style>
+            
+            <div
+                class="container"
+                data-theme=move || settings.with(|s| s.theme.as_str())
+                style=move || format!("--sw-keyboard-inset: {}px; {}", keyboard_inset.get(), settings.with(Settings::editor_style))
+            >
+                <a
+                    class="skip-link"
+                    href="#results"
+                    on:click=move |ev| {
+                        ev.prevent_default();
+                        if routed {
+                            routes::navigate(&Route::Debug);
+                        }
+                        if let Some(results) = results_ref.get_untracked() {
+                            let _ = results.focus();
+                        }
+                    }
+                >
+                    {t(Msg::SkipToResults)}
+                </a>
+                <p class="sr-only" role="status" aria-live="polite">{move || announcement.get()}</p>
+                <div class="header">
+                    <h1>{t(Msg::Title)}</h1>
+                    <p>{t(Msg::Tagline)}</p>
+                    <select
+                        class="language-select"
+                        aria-label=t(Msg::Language)
+                        on:change=move |ev| {
+                            if let Ok(l) = event_target_value(&ev).parse::<Lang>() {
+                                l.save();
+                                lang.set(l);
+                            }
+                        }
+                    >
+                        {Lang::ALL
+                            .into_iter()
+                            .map(|l| {
+                                view! {
+                                    <option value=l.code() selected=move || lang.get() == l>
+                                        {l.name()}
+                                    </option>
+                                }
+                            })
+                            .collect_view()}
+                    </select>
+                    <button class="secondary" aria-haspopup="dialog" on:click=move |_| settings_open.set(true)>
+                        {t(Msg::Settings)}
+                    </button>
+                    <Show when=move || installable.get()>
+                        <button
+                            class="secondary"
+                            title=t(Msg::InstallAppTitle)
+                            on:click=move |_| {
+                                spawn_local(async {
+                                    if let Err(e) = pwa::install().await {
+                                        logging::warn(Category::Ui, format!("Install failed: {e}"));
+                                    }
+                                });
+                            }
+                        >
+                            {t(Msg::InstallApp)}
+                        </button>
+                    </Show>
+                </div>
+                
+                {move || recovery.get().map(|saved| view! {
+                    <div class="tool-section" role="alert">
+                        <p>{move || i18n::format(i18n::text(lang.get(), Msg::RestorePrompt), &[&format_local(saved.saved_at)])}</p>
+                        <div class="button-group">
+                            <button on:click=restore_autosave>{t(Msg::Restore)}</button>
+                            <button class="secondary" on:click=discard_autosave>{t(Msg::Discard)}</button>
+                        </div>
+                    </div>
+                })}
+
+                <SplitPane storage_key="layout.split">
+                    <SplitStart slot>
+                    {/* Left: Code Input */}
+                    <div class="section">
+                        <label for="code-input">{t(Msg::CodeLabel)}</label>
+                        
+                        <Show when=move || !readonly>
+                        <div 
+                            class=move || {
+                                if drag_over_code.get() {
+                                    "drop-zone drag-over"
+                                } else {
+                                    "drop-zone"
+                                }
+                            }
+                            role="button"
+                            tabindex="0"
+                            aria-label=t(Msg::CodeDropLabel)
+                            on:dragover=handle_simf_dragover
+                            on:dragleave=handle_simf_dragleave
+                            on:drop=handle_simf_drop
+                            on:click=move |_| pick_file(code_file_input)
+                            on:keydown=drop_zone_keydown(code_file_input)
+                        >
+                            <div class="drop-zone-icon" aria-hidden="true">"📄"</div>
+                            <div class="drop-zone-text">{t(Msg::CodeDropHint)}</div>
+                            <div class="drop-zone-hint">{t(Msg::DropHintEditBelow)}</div>
+                        </div>
+                        <button class="secondary file-picker" on:click=move |_| pick_file(code_file_input)>
+                            {t(Msg::ChooseFile)}
+                        </button>
+                        <input
+                            type="file"
+                            accept=".simf,.args,.json,.zip,application/json,application/zip"
+                            hidden=true
+                            node_ref=code_file_input
+                            on:change=move |ev| {
+                                if let Some(file) = picked_file(&ev) {
+                                    open_code_file(file);
+                                }
+                            }
+                        />
+                        </Show>
+                        
+                        <SearchBar
+                            code=code
+                            open=search_open
+                            replacing=search_replacing
+                            marks=set_search_marks
+                            textarea=textarea_ref
+                            line_height=Signal::derive(move || settings.with(Settings::line_height))
+                            on_replace=Callback::new(move |text: String| edit_code(text, EditKind::Programmatic))
+                        />
+                        <div class="editor">
+                            <Gutter
+                                code=code
+                                diagnostics=diagnostics
+                                scroll_top=editor_scroll_top
+                                on_fix=apply_fix
+                            />
+                            <div class="editor-text">
+                            <textarea
+                                id="code-input"
+                                class="code-input"
+                                wrap="off"
+                                spellcheck="false"
+                                readonly=readonly
+                                node_ref=textarea_ref
+                                prop:value=move || code.get()
+                                on:input=move |ev| {
+                                    edit_code(event_target_value(&ev), EditKind::Typing);
+                                    track_cursor();
+                                }
+                                on:keydown=handle_editor_keydown
+                                on:keyup=move |_| track_cursor()
+                                on:click=move |_| track_cursor()
+                                on:blur=move |_| set_cursor.set(None)
+                                on:mousemove=handle_editor_mousemove
+                                on:scroll=move |_| {
+                                    if let Some(textarea) = textarea_ref.get() {
+                                        set_editor_scroll_top.set(f64::from(textarea.scroll_top()));
+                                        set_editor_scroll_left.set(f64::from(textarea.scroll_left()));
+                                    }
+                                }
+                                placeholder=t(Msg::CodePlaceholder)
+                            />
+                            <Overlay
+                                code=code
+                                marks=editor_marks
+                                scroll_top=editor_scroll_top
+                                scroll_left=editor_scroll_left
+                            />
+                            <span class="editor-measure" aria-hidden="true" node_ref=measure_ref>"0000000000"</span>
+                            </div>
+                        </div>
+                        <ParamPreview
+                            code=code
+                            name=previewed_param
+                            on_change=Callback::new(move |text: String| edit_code(text, EditKind::Programmatic))
+                        />
+                        
+                        <Show when=move || !viewer>
+                        <div class="button-group">
+                            <button
+                                class="compile-button"
+                                on:click=move |_| handle_compile()
+                                disabled=move || compile_status.get().is_some()
+                                aria-busy=move || compile_status.get().is_some().to_string()
+                            >
+                                {move || i18n::text(lang.get(), compile_status.get().unwrap_or(Msg::Compile))}
+                            </button>
+                            <select
+                                aria-label=t(Msg::JetTarget)
+                                title=t(Msg::JetTargetTitle)
+                                on:change=move |ev| {
+                                    if let Ok(target) = event_target_value(&ev).parse::<Target>() {
+                                        compile_options.update(|options| {
+                                            options.target = target;
+                                            options.save();
+                                        });
+                                    }
+                                }
+                            >
+                                {Target::ALL
+                                    .into_iter()
+                                    .map(|t| {
+                                        view! {
+                                            <option
+                                                value=t.as_str()
+                                                selected=move || compile_options.with(|o| o.target == t)
+                                            >
+                                                {move || i18n::format(i18n::text(lang.get(), Msg::TargetJets), &[t.as_str()])}
+                                            </option>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </select>
+                            <Show when=move || !readonly>
+                            <button class="secondary" on:click=insert_template>
+                                {t(Msg::InsertTemplate)}
+                            </button>
+                            <button
+                                class="secondary"
+                                title=t(Msg::UndoTitle)
+                                disabled=move || !history.with(|h| h.can_undo())
+                                on:click=move |_| undo()
+                            >
+                                {t(Msg::Undo)}
+                            </button>
+                            <button
+                                class="secondary"
+                                title=t(Msg::RedoTitle)
+                                disabled=move || !history.with(|h| h.can_redo())
+                                on:click=move |_| redo()
+                            >
+                                {t(Msg::Redo)}
+                            </button>
+                            <Show when=fs_access::supported>
+                                <button class="secondary" on:click=open_file>
+                                    {t(Msg::Open)}
+                                </button>
+                                <button
+                                    class="secondary"
+                                    title=move || file_name.get().map(|name| i18n::format(i18n::text(lang.get(), Msg::SaveTo), &[&name]))
+                                    on:click=move |_| save_file(false)
+                                >
+                                    {t(Msg::Save)}
+                                </button>
+                                <button class="secondary" on:click=move |_| save_file(true)>
+                                    {t(Msg::SaveAs)}
+                                </button>
+                            </Show>
+                            <button class="danger" on:click=clear_code>
+                                {t(Msg::Clear)}
+                            </button>
+                            <button class="secondary" on:click=export_session>
+                                {t(Msg::ExportSession)}
+                            </button>
+                            <button class="secondary" on:click=export_archive>
+                                {t(Msg::ExportProject)}
+                            </button>
+                            <button
+                                class="secondary"
+                                on:click=move |_| {
+                                    if let Some(input) = session_input.get() {
+                                        input.click();
+                                    }
+                                }
+                            >
+                                {t(Msg::ImportSession)}
+                            </button>
+                            <input
+                                type="file"
+                                accept=".json,.zip,application/json,application/zip"
+                                hidden=true
+                                node_ref=session_input
+                                on:change=handle_session_file
+                            />
+                            </Show>
+                        </div>
+                        </Show>
+                        <Show when=move || viewer>
+                            <p class="reminder-note">{t(Msg::ViewerNote)}</p>
+                        </Show>
+                        <OutlinePanel code=code on_select=Callback::new(reveal_line) />
+                        {move || file_name.get().map(|name| view! { <p class="drop-zone-hint">{move || i18n::format(i18n::text(lang.get(), Msg::Editing), &[&name])}</p> })}
+                        {move || session_note.get().map(|note| view! { <p class="reminder-note">{note}</p> })}
+                        <Show when=move || !readonly>
+                            <RemotePanel on_open=Callback::new(move |(name, source): (String, String)| {
+                                edit_code(source, EditKind::Programmatic);
+                                let opened = i18n::text(lang.get_untracked(), Msg::OpenedFromUrl);
+                                set_session_note.set(Some(i18n::format(opened, &[&name])));
+                                set_cmr.set(None);
+                                set_error.set(None);
+                                focus_editor();
+                            }) />
+                            <RecentPanel
+                                cmr=cmr
+                                on_restore=Callback::new(move |source: String| edit_code(source, EditKind::Programmatic))
+                            />
+                            <details class="manifest-editor">
+                                <summary>{t(Msg::EditorSettings)}</summary>
+                                {editor_option(Msg::MatchBrackets, editor_settings, |s| s.match_brackets, |s, on| s.match_brackets = on)}
+                                {editor_option(Msg::AutoIndent, editor_settings, |s| s.auto_indent, |s, on| s.auto_indent = on)}
+                                {editor_option(Msg::AutoClose, editor_settings, |s| s.auto_close, |s, on| s.auto_close = on)}
+                            </details>
+                            <TemplatePanel
+                                chosen=template_choice
+                                open=templates_open
+                                on_create=Callback::new(move |source: String| {
+                                    edit_code(source, EditKind::Programmatic);
+                                    set_cmr.set(None);
+                                    set_error.set(None);
+                                    focus_editor();
+                                })
+                            />
+                            <WizardPanel
+                                network=network
+                                on_open=Callback::new(move |source: String| {
+                                    edit_code(source, EditKind::Programmatic);
+                                    set_cmr.set(None);
+                                    set_error.set(None);
+                                    focus_editor();
+                                })
+                            />
+                            <SnapshotPanel
+                                code=code
+                                name=contract_name
+                                saves=saves
+                                on_restore=Callback::new(move |source: String| edit_code(source, EditKind::Programmatic))
+                            />
+                            <RegistryPanel
+                                saves=saves
+                                on_restore=Callback::new(move |source: String| edit_code(source, EditKind::Programmatic))
+                            />
+                        </Show>
+                    </div>
+                    </SplitStart>
+
+                    <SplitEnd slot>
+                    {/* Right: Witness Input */}
+                    <div
+                        class="section"
+                        id="results"
+                        role="region"
+                        tabindex="-1"
+                        aria-label=t(Msg::ResultsRegion)
+                        node_ref=results_ref
+                    >
+                        <Show when=move || !hide_witness>
+                        <label for="witness-input">{t(Msg::WitnessLabel)}</label>
+                        <WitnessSetTabs code=code sets=witness_sets witness=witness set_witness=set_witness />
+                        
+                        <div 
+                            class=move || {
+                                if drag_over_witness.get() {
+                                    "drop-zone drag-over"
+                                } else {
+                                    "drop-zone"
+                                }
+                            }
+                            role="button"
+                            tabindex="0"
+                            aria-label=t(Msg::WitnessDropLabel)
+                            on:dragover=handle_witness_dragover
+                            on:dragleave=handle_witness_dragleave
+                            on:drop=handle_witness_drop
+                            on:click=move |_| pick_file(witness_file_input)
+                            on:keydown=drop_zone_keydown(witness_file_input)
+                        >
+                            <div class="drop-zone-icon" aria-hidden="true">"📋"</div>
+                            <div class="drop-zone-text">{t(Msg::WitnessDropHint)}</div>
+                            <div class="drop-zone-hint">{t(Msg::WitnessDropHintBelow)}</div>
+                        </div>
+                        <button class="secondary file-picker" on:click=move |_| pick_file(witness_file_input)>
+                            {t(Msg::ChooseWitnessFile)}
+                        </button>
+                        <input
+                            type="file"
+                            accept=".json,.args,.wit,application/json"
+                            hidden=true
+                            node_ref=witness_file_input
+                            on:change=move |ev| {
+                                if let Some(file) = picked_file(&ev) {
+                                    open_witness_file(file);
+                                }
+                            }
+                        />
+                        
+                        <Show
+                            when=move || witness_form.get()
+                            fallback=move || view! {
+                                <textarea
+                                    id="witness-input"
+                                    prop:value=move || witness.get()
+                                    on:input=move |ev| {
+                                        set_witness.set(event_target_value(&ev));
+                                    }
+                                    placeholder=t(Msg::WitnessPlaceholder)
+                                    aria-invalid=move || (!witness_errors.with(Vec::is_empty)).to_string()
+                                    aria-describedby="witness-errors"
+                                />
+                                <ul class="schema-errors" id="witness-errors" role="status">
+                                    {move || {
+                                        witness_errors
+                                            .get()
+                                            .into_iter()
+                                            .map(|SchemaError { pointer, message }| {
+                                                let pointer = (!pointer.is_empty())
+                                                    .then(|| view! { <code>{pointer}</code>": " });
+                                                view! { <li>{pointer}{message}</li> }
+                                            })
+                                            .collect_view()
+                                    }}
+                                </ul>
+                            }
+                        >
+                            <WitnessWizard code=code witness=witness set_witness=set_witness />
+                        </Show>
+                        
+                        <div class="button-group">
+                            <button class="secondary" on:click=move |_| set_witness_form.update(|form| *form = !*form)>
+                                {move || i18n::text(lang.get(), if witness_form.get() { Msg::EditAsJson } else { Msg::EditAsForm })}
+                            </button>
+                            <button class="danger" on:click=clear_witness>
+                                {t(Msg::ClearWitness)}
+                            </button>
+                        </div>
+                        </Show>
+                        
+                        <BottomSheet state=sheet>
+                        {/* Error Display */}
+                        {move || {
+                            error.get().map(|err| {
+                                view! {
+                                    <div class="error" role="alert">
+                                        <strong>{t(Msg::ErrorHeading)}</strong>
+                                        <pre>{err.clone()}</pre>
+                                        <LearnMore message=err />
+                                        <TriagePanel report=triage_report />
+                                    </div>
+                                }
+                            })
+                        }}
+
+                        {/* Success Results */}
+                        <Show
+                            when=move || cmr.get().is_some()
+                            fallback=move || {
+                                view! {
+                                    <div class="empty-state">
+                                        {t(Msg::ResultsPlaceholder)}
+                                    </div>
+                                }
+                            }
+                        >
+                            <div class="success">
+                                <strong>{t(Msg::CompileSuccess)}</strong>
+                                
+                                <div class="output-group">
+                                    <span class="output-label" id="cmr-label">{t(Msg::CmrLabel)}</span>
+                                    <div class="output-box" role="group" tabindex="0" aria-labelledby="cmr-label">
+                                        {move || cmr.get().unwrap_or_default()}
+                                    </div>
+                                </div>
+                                
+                                <div class="output-group">
+                                    <div class="section-header">
+                                        <span class="output-label" id="address-label">{t(Msg::AddressLabel)}</span>
+                                        <select
+                                            aria-label=t(Msg::Network)
+                                            on:change=move |ev| {
+                                                if let Ok(n) = event_target_value(&ev).parse::<Network>() {
+                                                    network.set(n);
+                                                }
+                                            }
+                                        >
+                                            {Network::ALL
+                                                .into_iter()
+                                                .map(|n| {
+                                                    view! {
+                                                        <option value=n.as_str() selected=move || network.get() == n>
+                                                            {n.as_str()}
+                                                        </option>
+                                                    }
+                                                })
+                                                .collect_view()}
+                                        </select>
+                                    </div>
+                                    <SanityPanel
+                                        report=sanity_report
+                                        acknowledged=address_acknowledged
+                                        manifest=manifest
+                                    />
+                                    <Show when=move || shared_address.get().is_some()>
+                                        <div class="output-box" role="group" tabindex="0" aria-labelledby="address-label">
+                                            {move || shared_address.get().unwrap_or_default()}
+                                        </div>
+                                    </Show>
+                                </div>
+                                
+                                <Show when=move || shared_address.get().is_some()>
+                                    <div class="output-group">
+                                        <span class="output-label" id="descriptor-label">{t(Msg::DescriptorLabel)}</span>
+                                        <div class="output-box" role="group" tabindex="0" aria-labelledby="descriptor-label">
+                                            {move || output_descriptor.get().unwrap_or_default()}
+                                        </div>
+                                    </div>
+                                </Show>
+
+                                <div class="output-group">
+                                    <span class="output-label" id="code-base64-label">{t(Msg::CodeBase64Label)}</span>
+                                    <div class="output-box" role="group" tabindex="0" aria-labelledby="code-base64-label">
+                                        {move || code_base64.get().unwrap_or_default()}
+                                    </div>
+                                </div>
+                                
+                                <div class="output-group">
+                                    <span class="output-label" id="compiled-at-label">{t(Msg::CompiledAtLabel)}</span>
+                                    <div class="output-box" role="group" tabindex="0" aria-labelledby="compiled-at-label">
+                                        {move || {
+                                            compiled_at.get().map(|stamp| {
+                                                view! {
+                                                    <time datetime=stamp.utc.clone() title=stamp.utc.clone()>
+                                                        {stamp.to_locale_string()}
+                                                    </time>
+                                                    {format!(" · #{}", stamp.sequence)}
+                                                }
+                                            })
+                                        }}
+                                    </div>
+                                </div>
+                                
+                                <div class="output-group">
+                                    <span class="output-label" id="witness-info-label">{t(Msg::WitnessInfoLabel)}</span>
+                                    <div class="output-box witness" role="group" tabindex="0" aria-labelledby="witness-info-label">
+                                        {move || witness_info.get().unwrap_or_default()}
+                                    </div>
+                                </div>
+
+                                <div class="output-group">
+                                    <span class="output-label">{t(Msg::SpendingConditionsLabel)}</span>
+                                    <SpendPathsPanel code=program />
+                                </div>
+
+                                <div class="output-group">
+                                    <span class="output-label">{t(Msg::JetUsageLabel)}</span>
+                                    <JetUsagePanel code=program cmr=cmr />
+                                </div>
+
+                                <PruningPanel code=program witness=witness />
+
+                                <ListingPanel code=program cmr=cmr on_source=show_source />
+                                <HexDumpPanel code=program witness=witness cmr=cmr />
+                                <PluginPanels />
+                                
+                                <p class="sr-only" role="status" aria-live="polite">
+                                    {move || summary_text.get().unwrap_or_default()}
+                                </p>
+                                
+                                <div class="button-group">
+                                    <button class="secondary" on:click=export_summary>
+                                        {t(Msg::ExportSummary)}
+                                    </button>
+                                    <button
+                                        class="secondary"
+                                        title=report_withheld
+                                        disabled=move || report_withheld().is_some()
+                                        on:click=move |_| export_report(false)
+                                    >
+                                        {t(Msg::ExportReport)}
+                                    </button>
+                                    <button
+                                        class="secondary"
+                                        title=report_withheld
+                                        disabled=move || report_withheld().is_some()
+                                        on:click=move |_| export_report(true)
+                                    >
+                                        {t(Msg::PrintReport)}
+                                    </button>
+                                    <Show when=move || !viewer>
+                                        <button class="secondary" title=t(Msg::ViewerLinkTitle) on:click=open_viewer_link>
+                                            {t(Msg::ViewerLink)}
+                                        </button>
+                                    </Show>
+                                </div>
+                            </div>
+                        </Show>
+                        </BottomSheet>
+                    </div>
+                    </SplitEnd>
+                </SplitPane>
+
+                <Show when=move || !readonly>
+                    <div class="section tool-section">
+                        <NetworkPanel network=network address=shared_address />
+                    </div>
+                </Show>
+
+                <Show when=move || !readonly>
+                    <div class="section tool-section">
+                        <TaprootPanel config=taproot_config cmr=cmr network=network address=shared_address />
+                    </div>
+                </Show>
+
+                <Show when=move || shared_address.get().is_some()>
+                    <div class="section tool-section">
+                        <InvoicePanel address=shared_address network=network />
+                    </div>
+                </Show>
+
+                <Show when=move || shared_address.get().is_some() && !readonly>
+                    <div class="section tool-section">
+                        <FundingPanel address=shared_address network=network />
+                    </div>
+                </Show>
+
+                <Show when=move || !readonly>
+                    <div class="section tool-section">
+                        <ReminderPanel address=shared_address network=network />
+                    </div>
+                </Show>
+
+                <Show when=move || !readonly>
+                    <div class="section tool-section">
+                        <VaultPanel />
+                    </div>
+                </Show>
+
+                <Show when=move || !readonly>
+                    <div class="section tool-section">
+                        <AddressBookPanel />
+                    </div>
+                </Show>
+
+                <Show when=move || !readonly>
+                    <div class="section tool-section">
+                        <MusigPanel />
+                    </div>
+                </Show>
+
+                <Show when=move || !hide_witness>
+                    <div class="section tool-section">
+                        <SignaturePanel code=program witness=witness />
+                        <MultisigPanel code=program witness=witness set_witness=set_witness />
+                        {hww_section()}
+                    </div>
+                </Show>
+
+                <Show when=move || !hide_witness>
+                    <div class="section tool-section">
+                        <TestPanel code=program witness=witness tests=tests set_diagnostics=set_diagnostics />
+                    </div>
+                </Show>
+
+                <Show when=move || !hide_witness>
+                    <div class="section tool-section">
+                        <MockTxPanel code=program witness=witness on_source=show_source />
+                        <MinimizePanel
+                            code=program
+                            witness=witness
+                            on_open=Callback::new(move |source: String| edit_code(source, EditKind::Programmatic))
+                        />
+                    </div>
+                </Show>
+
+                <Show when=move || !readonly>
+                    <div class="section tool-section">
+                        <BulkPanel />
+                    </div>
+                </Show>
+
+                <div class="section tool-section">
+                    <ReplPanel />
+                </div>
+
+                <div class="section tool-section">
+                    <BenchmarkPanel code=program witness=witness />
+                </div>
+
+                <div class="section tool-section">
+                    <VerifyPanel code=program />
+                </div>
+
+                <Show when=move || !readonly>
+                    <div class="section tool-section">
+                        <OfferPanel
+                            code=code
+                            on_apply=Callback::new(move |source: String| edit_code(source, EditKind::Programmatic))
+                        />
+                    </div>
+                </Show>
+
+                <DevConsole />
+                <SettingsDialog settings=settings open=settings_open />
+
+                <div class="footer">
+                    <p>{t(Msg::FooterRuntime)}</p>
+                    <p>{t(Msg::FooterPrivacy)}</p>
+                    <div class="footer-crash-log">
+                        <label>
+                            <input
+                                type="checkbox"
+                                prop:checked=crash_log_on
+                                on:change=move |ev| {
+                                    let on = event_target_checked(&ev);
+                                    crash_log::set_enabled(on);
+                                    set_crash_log_on.set(on);
+                                }
+                            />
+                            {t(Msg::CrashLogOptIn)}
+                        </label>
+                        <button
+                            class="secondary"
+                            on:click=move |_| download_text("simplicity-debug-log.txt", &crash_log::export_text())
+                        >
+                            {t(Msg::ExportDebugLog)}
+                        </button>
+                    </div>
+                </div>
+            </div>
+        </>
+    }
+}
+
+fn set_timeout<F>(f: F, duration: std::time::Duration)
+where
+    F: FnOnce() + 'static,
+{
+    use wasm_bindgen::closure::Closure;
+    use web_sys::window;
+
+    let closure = Closure::once(f);
+    window()
+        .expect("no window")
+        .set_timeout_with_callback_and_timeout_and_arguments_0(
+            closure.as_ref().unchecked_ref(),
+            duration.as_millis() as i32,
+        )
+        .expect("failed to set timeout");
+    closure.forget();
+}
+
+/// Offer `contents` to the user as a plain-text file download.
+pub(crate) fn download_text(file_name: &str, contents: &str) {
+    let parts = js_sys::Array::of1(&JsValue::from_str(contents));
+    download_blob(file_name, "text/plain;charset=utf-8", &parts);
+}
+
+pub(crate) fn download_bytes(file_name: &str, mime_type: &str, contents: &[u8]) {
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(contents));
+    download_blob(file_name, mime_type, &parts);
+}
+
+fn download_blob(file_name: &str, mime_type: &str, parts: &js_sys::Array) {
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type(mime_type);
+    let Ok(blob) = web_sys::Blob::new_with_u8_array_sequence_and_options(parts, &options) else {
+        return;
+    };
+    let Ok(url) = web_sys::Url::create_object_url_with_blob(&blob) else {
+        return;
+    };
+    let anchor = web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.create_element("a").ok())
+        .and_then(|el| el.dyn_into::<web_sys::HtmlAnchorElement>().ok());
+    if let Some(anchor) = anchor {
+        anchor.set_href(&url);
+        anchor.set_download(file_name);
+        anchor.click();
+    }
+    let _ = web_sys::Url::revoke_object_url(&url);
+}
+
+/// Read `file` as text and hand the contents to `on_load`.
+pub(crate) fn read_file(file: web_sys::File, on_load: impl Fn(String, String) + 'static) {
+    let Ok(reader) = web_sys::FileReader::new() else {
+        return;
+    };
+    let name = file.name();
+    let reader_clone = reader.clone();
+    let onload = Closure::wrap(Box::new(move |_: web_sys::ProgressEvent| {
+        if let Some(text) = reader_clone.result().ok().and_then(|c| c.as_string()) {
+            on_load(name.clone(), text);
+        }
+    }) as Box<dyn FnMut(web_sys::ProgressEvent)>);
+    reader.set_onload(Some(onload.as_ref().unchecked_ref()));
+    onload.forget();
+    let _ = reader.read_as_text(&file);
+}
+
+/// Read `file` as bytes and hand the contents to `on_load`.
+pub(crate) fn read_file_bytes(file: web_sys::File, on_load: impl Fn(String, Vec<u8>) + 'static) {
+    let Ok(reader) = web_sys::FileReader::new() else {
+        return;
+    };
+    let name = file.name();
+    let reader_clone = reader.clone();
+    let onload = Closure::wrap(Box::new(move |_: web_sys::ProgressEvent| {
+        if let Ok(buffer) = reader_clone.result() {
+            on_load(name.clone(), js_sys::Uint8Array::new(&buffer).to_vec());
+        }
+    }) as Box<dyn FnMut(web_sys::ProgressEvent)>);
+    reader.set_onload(Some(onload.as_ref().unchecked_ref()));
+    onload.forget();
+    let _ = reader.read_as_array_buffer(&file);
+}
+
+#[wasm_bindgen(inline_js = r#"
+export function print_document(html) {
+    const url = URL.createObjectURL(new Blob([html], { type: 'text/html' }));
+    const view = window.open(url, '_blank');
+    if (view === null) {
+        URL.revokeObjectURL(url);
+        return;
+    }
+    view.addEventListener('load', () => {
+        view.print();
+        URL.revokeObjectURL(url);
+    });
+}
+"#)]
+extern "C" {
+    fn print_document(html: &str);
+}
+
+/// Open `html` in a new tab with the print dialog, where it can be saved as PDF.
+pub(crate) fn print_html(html: &str) {
+    print_document(html);
+}
+
+fn encode_base64(data: &str) -> String {
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_name = btoa)]
+        fn btoa(s: &str) -> String;
+    }
+    
+    let bytes = data.as_bytes();
+    let mut latin1_string = String::new();
+    for &byte in bytes {
+        latin1_string.push(byte as char);
+    }
+    
+    btoa(&latin1_string)
+}
@@ -0,0 +1,58 @@
+//! Binary form of a satisfied program, for moving it between threads.
+//!
+//! Encoded programs and witnesses can run to megabytes, and as hex inside
+//! JSON they cost twice that again in every copy. An artifact is the raw
+//! bytes in one buffer instead: the 32-byte CMR, the program length as a
+//! little-endian `u32`, the program, then the witness to the end. A worker
+//! hands the buffer to the page without copying it (see
+//! [`crate::worker_pool`]).
+
+use simplicityhl::simplicity::Cmr;
+
+use crate::spend;
+
+/// Bytes before the program: the CMR and the program length.
+const HEADER_BYTES: usize = 32 + 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Artifact {
+    pub cmr: Cmr,
+    pub program: Vec<u8>,
+    pub witness: Vec<u8>,
+}
+
+impl Artifact {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_BYTES + self.program.len() + self.witness.len());
+        bytes.extend_from_slice(&self.cmr.to_byte_array());
+        bytes.extend_from_slice(&(self.program.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&self.program);
+        bytes.extend_from_slice(&self.witness);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() < HEADER_BYTES {
+            return Err(format!("Artifact is {} bytes, shorter than its header", bytes.len()));
+        }
+        let cmr = Cmr::from_byte_array(bytes[..32].try_into().expect("32 bytes"));
+        let program_len = u32::from_le_bytes(bytes[32..HEADER_BYTES].try_into().expect("4 bytes")) as usize;
+        let rest = &bytes[HEADER_BYTES..];
+        if program_len > rest.len() {
+            return Err(format!("Artifact claims a {program_len}-byte program but holds {} bytes", rest.len()));
+        }
+        let (program, witness) = rest.split_at(program_len);
+        Ok(Artifact {
+            cmr,
+            program: program.to_vec(),
+            witness: witness.to_vec(),
+        })
+    }
+}
+
+/// Compile `code` and satisfy it with `witness_json`.
+pub fn build(code: &str, witness_json: &str) -> Result<Artifact, String> {
+    let witness_json = if witness_json.trim().is_empty() { "{}" } else { witness_json };
+    let (cmr, program, witness) = spend::satisfy(code, witness_json)?;
+    Ok(Artifact { cmr, program, witness })
+}
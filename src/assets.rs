@@ -0,0 +1,107 @@
+//! Elements assets and amounts, explicit or confidential, and issuances.
+//!
+//! An asset is written as its 32-byte id in hex when explicit, or as the
+//! 33-byte generator commitment when blinded; an amount is a number of
+//! satoshis, or a 33-byte Pedersen commitment. Asset jets such as
+//! `jet::output_asset` or `jet::input_amount` see exactly what is given, so
+//! a program can be run against confidential outputs without unblinding them.
+//! New issuances derive their asset and reissuance token ids from the spent
+//! outpoint and a contract hash, as Elements does.
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::elements::confidential::{Asset, Value};
+use simplicityhl::simplicity::elements::issuance::ContractHash;
+use simplicityhl::simplicity::elements::secp256k1_zkp::ZERO_TWEAK;
+use simplicityhl::simplicity::elements::{AssetId, AssetIssuance, OutPoint};
+use simplicityhl::simplicity::hashes::Hash;
+
+use crate::hex;
+
+/// An explicit asset id: 64 hex digits.
+pub fn asset_id(text: &str) -> Result<AssetId, String> {
+    AssetId::from_str(text.trim()).map_err(|e| format!("Invalid asset id `{}`: {e}", text.trim()))
+}
+
+/// An asset id, or a confidential asset commitment (66 hex digits).
+pub fn parse_asset(text: &str) -> Result<Asset, String> {
+    let bytes = hex::decode(text)?;
+    match bytes.len() {
+        32 => asset_id(text).map(Asset::Explicit),
+        33 => Asset::from_commitment(&bytes).map_err(|e| format!("Invalid asset commitment: {e}")),
+        n => Err(format!("An asset is a 32-byte id or a 33-byte commitment, not {n} bytes")),
+    }
+}
+
+/// The explicit amount `value`, or the confidential amount `commitment`
+/// (66 hex digits) when one is given.
+pub fn parse_value(value: u64, commitment: Option<&str>) -> Result<Value, String> {
+    match commitment {
+        None => Ok(Value::Explicit(value)),
+        Some(commitment) => {
+            let bytes = hex::decode(commitment)?;
+            if bytes.len() != 33 {
+                return Err(format!("An amount commitment is 33 bytes, not {}", bytes.len()));
+            }
+            Value::from_commitment(&bytes).map_err(|e| format!("Invalid amount commitment: {e}"))
+        }
+    }
+}
+
+/// A new issuance attached to the spending input.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct NewIssuance {
+    /// Units of the new asset created.
+    #[serde(default)]
+    pub asset_amount: u64,
+    /// Reissuance tokens created.
+    #[serde(default)]
+    pub token_amount: u64,
+    /// Hash of the issuance contract, in hex; all zeros when absent.
+    #[serde(default)]
+    pub contract_hash: Option<String>,
+}
+
+/// Ids of what a [`NewIssuance`] creates.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct IssuedIds {
+    pub asset: String,
+    pub token: String,
+}
+
+fn amount(n: u64) -> Value {
+    if n == 0 {
+        Value::Null
+    } else {
+        Value::Explicit(n)
+    }
+}
+
+impl NewIssuance {
+    fn contract_hash(&self) -> Result<ContractHash, String> {
+        match &self.contract_hash {
+            None => Ok(ContractHash::all_zeros()),
+            Some(hash) => ContractHash::from_str(hash.trim()).map_err(|e| format!("Invalid contract hash: {e}")),
+        }
+    }
+
+    /// The issuance field of the spending input.
+    pub fn to_issuance(&self) -> Result<AssetIssuance, String> {
+        if self.asset_amount == 0 && self.token_amount == 0 {
+            return Err("An issuance creates some of the asset or some reissuance tokens".to_string());
+        }
+        Ok(AssetIssuance {
+            asset_blinding_nonce: ZERO_TWEAK,
+            asset_entropy: self.contract_hash()?.to_byte_array(),
+            amount: amount(self.asset_amount),
+            inflation_keys: amount(self.token_amount),
+        })
+    }
+
+    /// The asset and reissuance token an input spending `prevout` issues.
+    pub fn ids(&self, prevout: OutPoint) -> Result<(AssetId, AssetId), String> {
+        let entropy = AssetId::generate_asset_entropy(prevout, self.contract_hash()?);
+        Ok((AssetId::from_entropy(entropy), AssetId::reissuance_token_from_entropy(entropy, false)))
+    }
+}
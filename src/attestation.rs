@@ -0,0 +1,167 @@
+//! Reproducibility attestations: portable records that a source compiled to
+//! a CMR.
+//!
+//! An attestation names the SHA-256 of the source, the compiler versions,
+//! the resulting CMR and when it was made. It can be signed with a BIP-340
+//! key, in which case the signature covers the SHA-256 of the statement's
+//! JSON serialization, so reformatting the file does not break it. [`verify`]
+//! recompiles the source later and reports each check separately: a newer
+//! compiler that yields the same CMR is worth knowing about, not a failure.
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::elements::secp256k1_zkp::{schnorr, Keypair, Message, Secp256k1};
+use simplicityhl::simplicity::hashes::{sha256, Hash};
+use simplicityhl::simplicity::Cmr;
+
+use crate::hex;
+use crate::timestamp::Stamp;
+use crate::vault::{parse_pubkey, parse_secret};
+
+/// Format version written by this build.
+pub const ATTESTATION_VERSION: u32 = 1;
+
+/// The SimplicityHL release this crate is built against; keep in step with
+/// Cargo.toml.
+pub const SIMPLICITYHL_VERSION: &str = "0.3.0";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CompilerVersions {
+    pub simplicity_wasm: String,
+    pub simplicityhl: String,
+}
+
+impl CompilerVersions {
+    pub fn current() -> Self {
+        CompilerVersions {
+            simplicity_wasm: env!("CARGO_PKG_VERSION").to_string(),
+            simplicityhl: SIMPLICITYHL_VERSION.to_string(),
+        }
+    }
+}
+
+/// What is attested; the signature covers exactly these fields.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Statement {
+    pub version: u32,
+    /// SHA-256 of the UTF-8 source, as hex.
+    pub source_sha256: String,
+    pub compiler: CompilerVersions,
+    pub cmr: String,
+    pub created: Stamp,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AttestationSignature {
+    /// x-only public key, as hex.
+    pub pubkey: String,
+    /// BIP-340 signature over [`Statement::digest`], as hex.
+    pub signature: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Attestation {
+    pub statement: Statement,
+    #[serde(default)]
+    pub signature: Option<AttestationSignature>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AttestationCheck {
+    pub source_matches: bool,
+    /// Whether the source compiles to the attested CMR today.
+    pub cmr_matches: bool,
+    pub actual_cmr: Option<String>,
+    /// Why the source did not compile, if it did not.
+    pub compile_error: Option<String>,
+    /// Whether this build has the attested compiler versions.
+    pub same_compiler: bool,
+    /// `None` for an unsigned attestation.
+    pub signature_valid: Option<bool>,
+    pub signer: Option<String>,
+}
+
+impl AttestationCheck {
+    /// The attestation holds: same source, same CMR, and a valid signature
+    /// if it was signed.
+    pub fn is_valid(&self) -> bool {
+        self.source_matches && self.cmr_matches && self.signature_valid != Some(false)
+    }
+}
+
+pub fn source_hash(code: &str) -> String {
+    hex::encode(&sha256::Hash::hash(code.as_bytes()).to_byte_array())
+}
+
+impl Statement {
+    /// SHA-256 of the statement's JSON serialization.
+    pub fn digest(&self) -> [u8; 32] {
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        sha256::Hash::hash(&json).to_byte_array()
+    }
+}
+
+/// Attest that `code` compiles to `cmr`, signing with `secret_hex` unless it
+/// is empty.
+pub fn create(code: &str, cmr: Cmr, secret_hex: &str) -> Result<Attestation, String> {
+    let statement = Statement {
+        version: ATTESTATION_VERSION,
+        source_sha256: source_hash(code),
+        compiler: CompilerVersions::current(),
+        cmr: cmr.to_string(),
+        created: Stamp::now(),
+    };
+    let signature = if secret_hex.trim().is_empty() {
+        None
+    } else {
+        let (secret, pubkey) = parse_secret(secret_hex.trim())?;
+        let secp = Secp256k1::new();
+        let keypair = Keypair::from_secret_key(&secp, &secret);
+        let signature = secp.sign_schnorr_no_aux_rand(&Message::from_digest(statement.digest()), &keypair);
+        Some(AttestationSignature {
+            pubkey: pubkey.to_string(),
+            signature: hex::encode(signature.as_ref()),
+        })
+    };
+    Ok(Attestation { statement, signature })
+}
+
+/// Re-check `attestation` against `code`, compiling with `compile`.
+pub fn verify(
+    attestation: &Attestation,
+    code: &str,
+    compile: impl Fn(&str) -> Result<Cmr, String>,
+) -> Result<AttestationCheck, String> {
+    let statement = &attestation.statement;
+    if statement.version > ATTESTATION_VERSION {
+        return Err(format!(
+            "Attestation version {} is newer than this playground supports ({ATTESTATION_VERSION})",
+            statement.version
+        ));
+    }
+    let (actual_cmr, compile_error) = match compile(code) {
+        Ok(cmr) => (Some(cmr.to_string()), None),
+        Err(e) => (None, Some(e)),
+    };
+    let signature_valid = match &attestation.signature {
+        None => None,
+        Some(signed) => {
+            let pubkey = parse_pubkey(&signed.pubkey)?;
+            let signature = schnorr::Signature::from_slice(&hex::decode(&signed.signature)?)
+                .map_err(|e| format!("Invalid signature: {e}"))?;
+            Some(
+                Secp256k1::verification_only()
+                    .verify_schnorr(&signature, &Message::from_digest(statement.digest()), &pubkey)
+                    .is_ok(),
+            )
+        }
+    };
+    Ok(AttestationCheck {
+        source_matches: source_hash(code) == statement.source_sha256,
+        cmr_matches: actual_cmr.as_deref() == Some(statement.cmr.as_str()),
+        actual_cmr,
+        compile_error,
+        same_compiler: statement.compiler == CompilerVersions::current(),
+        signature_valid,
+        signer: attestation.signature.as_ref().map(|s| s.pubkey.clone()),
+    })
+}
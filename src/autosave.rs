@@ -0,0 +1,64 @@
+//! Continuous backup of the editor buffers.
+//!
+//! The code and witness are written to localStorage on every change. A
+//! successful compile or a session export marks the backup clean; a backup
+//! still dirty on the next load is work that was never kept anywhere else,
+//! and the playground offers to restore it.
+
+use serde::{Deserialize, Serialize};
+
+use crate::storage;
+
+const AUTOSAVE_KEY: &str = "autosave";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Autosave {
+    pub code: String,
+    #[serde(default)]
+    pub witness: String,
+    /// Milliseconds since the Unix epoch.
+    pub saved_at: u64,
+    /// The buffers were compiled or exported since they last changed.
+    #[serde(default)]
+    pub clean: bool,
+}
+
+fn load() -> Option<Autosave> {
+    storage::load(AUTOSAVE_KEY).and_then(|json| serde_json::from_str(&json).ok())
+}
+
+fn store(autosave: &Autosave) {
+    if let Ok(json) = serde_json::to_string(autosave) {
+        storage::save(AUTOSAVE_KEY, &json);
+    }
+}
+
+/// Back up the buffers as unsaved work.
+pub fn save(code: &str, witness: &str) {
+    if load().is_some_and(|a| a.code == code && a.witness == witness) {
+        return;
+    }
+    store(&Autosave {
+        code: code.to_string(),
+        witness: witness.to_string(),
+        saved_at: js_sys::Date::now() as u64,
+        clean: false,
+    });
+}
+
+/// Record that the current backup has been kept elsewhere.
+pub fn mark_clean() {
+    if let Some(autosave) = load().filter(|a| !a.clean) {
+        store(&Autosave { clean: true, ..autosave });
+    }
+}
+
+/// Unsaved work left from a previous visit, unless it matches `code`.
+pub fn recoverable(code: &str) -> Option<Autosave> {
+    load().filter(|a| !a.clean && a.code != code && !a.code.trim().is_empty())
+}
+
+/// Drop the backup, e.g. after the user declined to restore it.
+pub fn discard() {
+    storage::remove(AUTOSAVE_KEY);
+}
@@ -0,0 +1,131 @@
+//! Timing of the compile, satisfy and execute phases of a program.
+//!
+//! Each iteration compiles from source, bypassing the compile cache, then
+//! satisfies the result with the witness and runs it on the Bit Machine
+//! against the test runner's synthetic transaction. Times come from
+//! `performance.now()`, which browsers coarsen to somewhere between 5 µs and
+//! 1 ms, so very fast phases need many iterations to say anything.
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::BitMachine;
+use simplicityhl::WitnessValues;
+use wasm_bindgen::prelude::*;
+
+use crate::options::CompileOptions;
+use crate::sighash::elements_env;
+use crate::taproot::TaprootConfig;
+use crate::test_runner::{synthetic_tx, TestEnv};
+
+/// Most iterations one benchmark runs; each blocks the page.
+pub const MAX_ITERATIONS: u32 = 200;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = performance, js_name = now)]
+    fn performance_now() -> f64;
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct PhaseTimes {
+    /// Milliseconds per iteration, in run order.
+    pub samples: Vec<f64>,
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub max: f64,
+}
+
+impl PhaseTimes {
+    fn new(samples: Vec<f64>) -> Self {
+        if samples.is_empty() {
+            return PhaseTimes::default();
+        }
+        let mut sorted = samples.clone();
+        sorted.sort_by(f64::total_cmp);
+        let mid = sorted.len() / 2;
+        let median = if sorted.len() % 2 == 0 {
+            (sorted[mid - 1] + sorted[mid]) / 2.0
+        } else {
+            sorted[mid]
+        };
+        PhaseTimes {
+            min: sorted[0],
+            median,
+            mean: samples.iter().sum::<f64>() / samples.len() as f64,
+            max: sorted[sorted.len() - 1],
+            samples,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BenchmarkReport {
+    pub iterations: u32,
+    pub compile: PhaseTimes,
+    pub satisfy: PhaseTimes,
+    pub execute: PhaseTimes,
+}
+
+impl BenchmarkReport {
+    /// One line per phase: "compile: median 1.20 ms (min 1.10, max 2.40)".
+    pub fn to_text(&self) -> String {
+        [("compile", &self.compile), ("satisfy", &self.satisfy), ("execute", &self.execute)]
+            .iter()
+            .map(|(name, t)| {
+                format!(
+                    "{name}: median {:.2} ms, mean {:.2} ms (min {:.2}, max {:.2})",
+                    t.median, t.mean, t.min, t.max
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Milliseconds `f` takes, with its result.
+fn timed<T>(f: impl FnOnce() -> T) -> (T, f64) {
+    let start = performance_now();
+    let result = f();
+    (result, performance_now() - start)
+}
+
+/// Run `iterations` (at most [`MAX_ITERATIONS`]) timed rounds of `code`
+/// with `witness_json`.
+pub fn run(code: &str, witness_json: &str, iterations: u32) -> Result<BenchmarkReport, String> {
+    if iterations == 0 || iterations > MAX_ITERATIONS {
+        return Err(format!("Iterations must be between 1 and {MAX_ITERATIONS}"));
+    }
+    let witness_json = if witness_json.trim().is_empty() { "{}" } else { witness_json };
+    let witness =
+        serde_json::from_str::<WitnessValues>(witness_json).map_err(|e| format!("Invalid JSON witness data: {}", e))?;
+
+    let (mut compile, mut satisfy, mut execute) = (Vec::new(), Vec::new(), Vec::new());
+    for _ in 0..iterations {
+        let (compiled, ms) = timed(|| CompileOptions::default().compile_source(code, code));
+        let compiled = compiled?;
+        compile.push(ms);
+
+        let cmr = compiled.commit().cmr();
+        let (satisfied, ms) = timed(|| compiled.satisfy(witness.clone()));
+        let satisfied = satisfied.map_err(|e| format!("Witness error: {}", e))?;
+        satisfy.push(ms);
+
+        let (tx, utxo) = synthetic_tx(&TestEnv::default(), cmr)?;
+        let env = elements_env(tx, 0, &[utxo], cmr, &TaprootConfig::default())?;
+        let program = satisfied.redeem();
+        let (outcome, ms) = timed(|| {
+            BitMachine::for_program(program)
+                .map_err(|e| e.to_string())
+                .and_then(|mut machine| machine.exec(program, &env).map(|_| ()).map_err(|e| e.to_string()))
+        });
+        outcome.map_err(|e| format!("Execution error: {e}"))?;
+        execute.push(ms);
+    }
+
+    Ok(BenchmarkReport {
+        iterations,
+        compile: PhaseTimes::new(compile),
+        satisfy: PhaseTimes::new(satisfy),
+        execute: PhaseTimes::new(execute),
+    })
+}
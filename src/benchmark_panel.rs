@@ -0,0 +1,157 @@
+//! "Performance" section: timed runs of the current program, charted.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::api_types::BenchmarkResult;
+use crate::benchmark::{BenchmarkReport, MAX_ITERATIONS};
+use crate::compiler;
+use crate::i18n::{self, t, Lang, Msg};
+
+const CHART_WIDTH: f64 = 300.0;
+const CHART_HEIGHT: f64 = 80.0;
+
+/// Phase names with the CSS class their bars are drawn in.
+const PHASES: [(Msg, &str); 3] = [
+    (Msg::PhaseCompile, "bench-compile"),
+    (Msg::PhaseSatisfy, "bench-satisfy"),
+    (Msg::PhaseExecute, "bench-execute"),
+];
+
+/// Stacked bars of the three phases, one per iteration.
+fn chart(report: &BenchmarkReport, lang: Lang) -> impl IntoView {
+    let phases = [&report.compile.samples, &report.satisfy.samples, &report.execute.samples];
+    let count = phases[0].len().max(1);
+    let totals: Vec<f64> = (0..count).map(|i| phases.iter().filter_map(|p| p.get(i)).sum()).collect();
+    let scale = CHART_HEIGHT / totals.iter().cloned().fold(f64::EPSILON, f64::max);
+    let width = CHART_WIDTH / count as f64;
+
+    let bars = (0..count)
+        .flat_map(|i| {
+            let mut y = CHART_HEIGHT;
+            phases
+                .iter()
+                .zip(PHASES)
+                .filter_map(|(samples, (name, class))| {
+                    let ms = *samples.get(i)?;
+                    let height = ms * scale;
+                    y -= height;
+                    Some(view! {
+                        <rect class=class x=i as f64 * width y=y width=(width - 1.0).max(0.5) height=height>
+                            <title>
+                                {i18n::format(
+                                    i18n::text(lang, Msg::IterationTime),
+                                    &[&(i + 1).to_string(), i18n::text(lang, name), &format!("{ms:.2}")],
+                                )}
+                            </title>
+                        </rect>
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect_view();
+
+    view! {
+        <svg
+            class="bench-chart"
+            viewBox=format!("0 0 {CHART_WIDTH} {CHART_HEIGHT}")
+            preserveAspectRatio="none"
+            role="img"
+            aria-label=i18n::text(lang, Msg::TimePerIteration)
+        >
+            {bars}
+        </svg>
+    }
+}
+
+#[component]
+pub fn BenchmarkPanel(
+    #[prop(into)] code: Signal<String>,
+    #[prop(into)] witness: Signal<String>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let (iterations, set_iterations) = signal(20u32);
+    let (busy, set_busy) = signal(false);
+    let (outcome, set_outcome) = signal::<Option<Result<BenchmarkReport, String>>>(None);
+
+    let run = move |_| {
+        let (code, witness, iterations) = (code.get_untracked(), witness.get_untracked(), iterations.get_untracked());
+        set_busy.set(true);
+        spawn_local(async move {
+            let json = compiler::benchmark(&code, &witness, iterations).await;
+            set_outcome.set(Some(match serde_json::from_str::<BenchmarkResult>(&json) {
+                Ok(BenchmarkResult { report: Some(report), .. }) => Ok(report),
+                Ok(BenchmarkResult { error, .. }) => Err(error.unwrap_or_default()),
+                Err(e) => Err(e.to_string()),
+            }));
+            set_busy.set(false);
+        });
+    };
+
+    let results = move || {
+        outcome.get().map(|outcome| match outcome {
+            Err(e) => view! { <p class="tool-error">{e}</p> }.into_any(),
+            Ok(report) => {
+                let rows = [&report.compile, &report.satisfy, &report.execute]
+                    .into_iter()
+                    .zip(PHASES)
+                    .map(|(times, (name, class))| {
+                        view! {
+                            <tr>
+                                <td><span class=format!("bench-swatch {class}")></span>{t(name)}</td>
+                                <td>{format!("{:.2}", times.median)}</td>
+                                <td>{format!("{:.2}", times.mean)}</td>
+                                <td>{format!("{:.2}", times.min)}</td>
+                                <td>{format!("{:.2}", times.max)}</td>
+                            </tr>
+                        }
+                    })
+                    .collect_view();
+                view! {
+                    <table class="bulk-table">
+                        <thead>
+                            <tr>
+                                <th>{t(Msg::PhaseMs)}</th>
+                                <th>{t(Msg::Median)}</th>
+                                <th>{t(Msg::Mean)}</th>
+                                <th>{t(Msg::Min)}</th>
+                                <th>{t(Msg::Max)}</th>
+                            </tr>
+                        </thead>
+                        <tbody>{rows}</tbody>
+                    </table>
+                    {chart(&report, lang.get())}
+                }
+                .into_any()
+            }
+        })
+    };
+
+    view! {
+        <div class="benchmark-panel">
+            <label for="bench-iterations">{t(Msg::Performance)}</label>
+            <div class="button-group">
+                <input
+                    id="bench-iterations"
+                    class="bench-iterations"
+                    type="number"
+                    min="1"
+                    max=MAX_ITERATIONS
+                    prop:value=move || iterations.get().to_string()
+                    on:input=move |ev| {
+                        if let Ok(n) = event_target_value(&ev).parse::<u32>() {
+                            set_iterations.set(n.clamp(1, MAX_ITERATIONS));
+                        }
+                    }
+                />
+                <button on:click=run disabled=move || busy.get()>
+                    {move || i18n::text(lang.get(), if busy.get() { Msg::Running } else { Msg::RunBenchmark })}
+                </button>
+            </div>
+            <p class="drop-zone-hint">
+                {t(Msg::BenchmarkHint)}
+            </p>
+            {results}
+        </div>
+    }
+}
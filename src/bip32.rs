@@ -0,0 +1,95 @@
+//! Public keys derived from BIP32 extended public keys.
+//!
+//! Wherever a program or its parameters take a `Pubkey`, a wallet's account
+//! xpub with a derivation path can stand in for it: `xpub6C.../0/5` is
+//! replaced by `0x` and the x-only key at `m/0/5` below the xpub before
+//! compiling, so a key can be taken straight from a wallet's export. Only
+//! unhardened steps can be derived from public data; `tpub` keys work the
+//! same way on test networks.
+
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::bitcoin::bip32::{ChildNumber, DerivationPath, Xpub};
+use simplicityhl::simplicity::bitcoin::secp256k1::Secp256k1;
+
+use crate::hex;
+
+/// A key derived from an xpub.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DerivedKey {
+    /// x-only public key, hex, as a `Pubkey` takes it.
+    pub pubkey: String,
+    /// Compressed public key, hex.
+    pub compressed: String,
+    /// Path below the xpub, e.g. `m/0/5`.
+    pub path: String,
+}
+
+/// An xpub followed by its derivation path, e.g. `xpub.../0/5`.
+fn xpub_path() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r"\b[xt]pub[1-9A-HJ-NP-Za-km-z]{100,}((?:/[0-9]+['hH]?)*)").expect("pattern is valid")
+    })
+}
+
+/// Whether `text` is one xpub with its path and nothing else.
+pub fn is_xpub_path(text: &str) -> bool {
+    let text = text.trim();
+    xpub_path().find(text).is_some_and(|m| m.start() == 0 && m.end() == text.len())
+}
+
+/// The key at `path` (`m/0/5`, `0/5` or empty for the xpub's own key) below
+/// `xpub`.
+pub fn derive_key(xpub: &str, path: &str) -> Result<DerivedKey, String> {
+    let xpub = Xpub::from_str(xpub.trim()).map_err(|e| format!("Invalid xpub: {e}"))?;
+    let path = path.trim().trim_start_matches('m').trim_matches('/');
+    let full = if path.is_empty() { "m".to_string() } else { format!("m/{path}") };
+    let path = DerivationPath::from_str(&full).map_err(|e| format!("Invalid derivation path `{path}`: {e}"))?;
+    if path.as_ref().iter().any(ChildNumber::is_hardened) {
+        return Err(format!("`{path}` has hardened steps, which an xpub cannot derive; use the account xpub below them"));
+    }
+    let derived = xpub.derive_pub(&Secp256k1::new(), &path).map_err(|e| format!("Could not derive `{path}`: {e}"))?;
+    let (x_only, _) = derived.public_key.x_only_public_key();
+    Ok(DerivedKey {
+        pubkey: hex::encode(&x_only.serialize()),
+        compressed: hex::encode(&derived.public_key.serialize()),
+        path: path.to_string(),
+    })
+}
+
+/// `text` with every `xpub.../path` replaced by `0x` and the derived x-only
+/// key.
+pub fn expand(text: &str) -> Result<String, String> {
+    replace(text, false)
+}
+
+/// [`expand`], with each key padded by spaces to the length of the
+/// `xpub.../path` it replaces, so byte offsets into `text` stay valid.
+pub fn expand_in_place(text: &str) -> Result<String, String> {
+    replace(text, true)
+}
+
+fn replace(text: &str, pad: bool) -> Result<String, String> {
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for captures in xpub_path().captures_iter(text) {
+        let (Some(whole), Some(path)) = (captures.get(0), captures.get(1)) else {
+            continue;
+        };
+        let xpub = &text[whole.start()..path.start()];
+        out.push_str(&text[last..whole.start()]);
+        let key = format!("0x{}", derive_key(xpub, path.as_str())?.pubkey);
+        out.push_str(&key);
+        if pad {
+            // An xpub alone is longer than the key it derives.
+            out.push_str(&" ".repeat(whole.len().saturating_sub(key.len())));
+        }
+        last = whole.end();
+    }
+    out.push_str(&text[last..]);
+    Ok(out)
+}
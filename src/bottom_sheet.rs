@@ -0,0 +1,141 @@
+//! Results drawer for narrow screens.
+//!
+//! On phones the results slide up from the bottom instead of sitting below a
+//! long editor. The sheet rests at one of three heights (a peek bar, half the
+//! screen or the full screen) and its handle moves between them by swiping,
+//! tapping or the arrow keys. Above the mobile breakpoint the stylesheet lays
+//! the sheet out inline and hides the handle, so the same markup serves both
+//! layouts.
+
+use leptos::prelude::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::i18n::{t, Msg};
+
+/// Vertical travel, in CSS pixels, that counts as a swipe rather than a tap.
+const SWIPE_PX: i32 = 40;
+
+/// Resting heights, smallest first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SheetState {
+    #[default]
+    Peek,
+    Half,
+    Full,
+}
+
+impl SheetState {
+    fn class(self) -> &'static str {
+        match self {
+            SheetState::Peek => "bottom-sheet peek",
+            SheetState::Half => "bottom-sheet half",
+            SheetState::Full => "bottom-sheet full",
+        }
+    }
+
+    fn up(self) -> Self {
+        match self {
+            SheetState::Peek => SheetState::Half,
+            _ => SheetState::Full,
+        }
+    }
+
+    fn down(self) -> Self {
+        match self {
+            SheetState::Full => SheetState::Half,
+            _ => SheetState::Peek,
+        }
+    }
+}
+
+/// Height of the layout viewport hidden behind the on-screen keyboard, in
+/// CSS pixels; zero on browsers without `visualViewport`.
+pub fn keyboard_inset() -> ReadSignal<f64> {
+    let (inset, set_inset) = signal(0.0_f64);
+    let Some(window) = web_sys::window() else {
+        return inset;
+    };
+    let Some(viewport) = window.visual_viewport() else {
+        return inset;
+    };
+    let measure = {
+        let viewport = viewport.clone();
+        move || {
+            let height = window.inner_height().ok().and_then(|h| h.as_f64()).unwrap_or_default();
+            set_inset.set((height - viewport.height() - viewport.offset_top()).max(0.0));
+        }
+    };
+    let on_resize = Closure::<dyn FnMut()>::new(measure);
+    let _ = viewport.add_event_listener_with_callback("resize", on_resize.as_ref().unchecked_ref());
+    let _ = viewport.add_event_listener_with_callback("scroll", on_resize.as_ref().unchecked_ref());
+    on_resize.forget();
+    inset
+}
+
+#[component]
+pub fn BottomSheet(state: RwSignal<SheetState>, children: Children) -> impl IntoView {
+    let start_y = StoredValue::new(None::<i32>);
+    // A swipe ends in a click on the handle; that click must not toggle again.
+    let swiped = StoredValue::new(false);
+
+    let on_pointer_down = move |ev: web_sys::PointerEvent| {
+        if let Some(target) = ev.target().and_then(|t| t.dyn_into::<web_sys::Element>().ok()) {
+            let _ = target.set_pointer_capture(ev.pointer_id());
+        }
+        start_y.set_value(Some(ev.client_y()));
+        swiped.set_value(false);
+    };
+
+    let on_pointer_up = move |ev: web_sys::PointerEvent| {
+        let Some(start) = start_y.get_value() else {
+            return;
+        };
+        start_y.set_value(None);
+        let travel = ev.client_y() - start;
+        if travel.abs() >= SWIPE_PX {
+            swiped.set_value(true);
+            state.update(|s| *s = if travel < 0 { s.up() } else { s.down() });
+        }
+    };
+
+    let on_click = move |_| {
+        if swiped.get_value() {
+            swiped.set_value(false);
+            return;
+        }
+        state.update(|s| {
+            *s = match s {
+                SheetState::Peek => SheetState::Half,
+                _ => SheetState::Peek,
+            }
+        });
+    };
+
+    let on_key_down = move |ev: web_sys::KeyboardEvent| {
+        match ev.key().as_str() {
+            "ArrowUp" => state.update(|s| *s = s.up()),
+            "ArrowDown" => state.update(|s| *s = s.down()),
+            _ => return,
+        }
+        ev.prevent_default();
+    };
+
+    view! {
+        <div class=move || state.get().class()>
+            <button
+                class="bottom-sheet-handle"
+                aria-expanded=move || (state.get() != SheetState::Peek).to_string()
+                on:pointerdown=on_pointer_down
+                on:pointerup=on_pointer_up
+                on:pointercancel=move |_| start_y.set_value(None)
+                on:click=on_click
+                on:keydown=on_key_down
+            >
+                <span class="bottom-sheet-grip" aria-hidden="true"></span>
+                {t(Msg::ResultsRegion)}
+            </button>
+            {children()}
+        </div>
+    }
+}
@@ -0,0 +1,218 @@
+//! Bulk verification of deployed contract addresses.
+//!
+//! Input is CSV with one `subject,expected_address` pair per row, where the
+//! subject is either a 64-hex CMR or the name of a supplied source file. Each
+//! row is re-derived and compared by script pubkey, so blinded (confidential)
+//! expected addresses match their unconfidential counterpart.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::elements::Address;
+use simplicityhl::simplicity::Cmr;
+
+use crate::address::{parse_cmr, taproot_address, Network};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BulkRow {
+    /// 1-based line in the CSV input.
+    pub line: usize,
+    pub subject: String,
+    pub expected: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase", tag = "status", content = "message")]
+pub enum BulkStatus {
+    Pass,
+    Fail,
+    Error(String),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BulkOutcome {
+    #[serde(flatten)]
+    pub row: BulkRow,
+    pub cmr: Option<String>,
+    pub derived: Option<String>,
+    #[serde(flatten)]
+    pub status: BulkStatus,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct BulkReport {
+    pub outcomes: Vec<BulkOutcome>,
+    pub passed: usize,
+    pub failed: usize,
+    pub errors: usize,
+}
+
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => cells.push(std::mem::take(&mut cell).trim().to_string()),
+            _ => cell.push(c),
+        }
+    }
+    cells.push(cell.trim().to_string());
+    cells
+}
+
+/// Parse the CSV input. Blank lines, `#` comments and a header row whose
+/// second column is not an address are skipped.
+pub fn parse_rows(csv: &str) -> Result<Vec<BulkRow>, String> {
+    let mut rows = Vec::new();
+    for (index, line) in csv.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let cells = split_csv_line(trimmed);
+        let (Some(subject), Some(expected)) = (cells.first(), cells.get(1)) else {
+            return Err(format!("Line {}: expected `subject,expected_address`", index + 1));
+        };
+        if rows.is_empty() && expected.parse::<Address>().is_err() && parse_cmr(subject).is_err() {
+            // Header row.
+            continue;
+        }
+        rows.push(BulkRow {
+            line: index + 1,
+            subject: subject.clone(),
+            expected: expected.clone(),
+        });
+    }
+    Ok(rows)
+}
+
+/// Source files the rows name, each once, in order of first use. These are
+/// what [`verify`] compiles, so callers can compile them ahead of time.
+pub fn referenced_sources<'a>(rows: &[BulkRow], sources: &'a HashMap<String, String>) -> Vec<&'a str> {
+    let mut seen = Vec::new();
+    for row in rows {
+        if parse_cmr(&row.subject).is_ok() {
+            continue;
+        }
+        if let Some(code) = sources.get(&row.subject) {
+            if !seen.contains(&code.as_str()) {
+                seen.push(code.as_str());
+            }
+        }
+    }
+    seen
+}
+
+/// Verify every row. `sources` maps file names to SimplicityHL source and is
+/// consulted for subjects that are not CMRs. `compile` turns source into a CMR.
+pub fn verify(
+    rows: Vec<BulkRow>,
+    sources: &HashMap<String, String>,
+    compile: impl Fn(&str) -> Result<Cmr, String>,
+) -> BulkReport {
+    // The same file often backs many rows; compile it once.
+    let mut compiled: HashMap<String, Result<Cmr, String>> = HashMap::new();
+    let mut report = BulkReport::default();
+
+    for row in rows {
+        let cmr = match parse_cmr(&row.subject) {
+            Ok(cmr) => Ok(cmr),
+            Err(_) => compiled
+                .entry(row.subject.clone())
+                .or_insert_with(|| match sources.get(&row.subject) {
+                    Some(code) => compile(code),
+                    None => Err(format!("No source file named `{}`", row.subject)),
+                })
+                .clone(),
+        };
+
+        let outcome = match (cmr, row.expected.parse::<Address>()) {
+            (Err(e), _) => BulkOutcome {
+                row,
+                cmr: None,
+                derived: None,
+                status: BulkStatus::Error(e),
+            },
+            (Ok(cmr), Err(e)) => BulkOutcome {
+                status: BulkStatus::Error(format!("Invalid expected address: {e}")),
+                row,
+                cmr: Some(cmr.to_string()),
+                derived: None,
+            },
+            (Ok(cmr), Ok(expected)) => {
+                let network = Network::of_address(&expected).unwrap_or_default();
+                let derived = taproot_address(cmr, network);
+                let status = if derived.script_pubkey() == expected.script_pubkey() {
+                    BulkStatus::Pass
+                } else {
+                    BulkStatus::Fail
+                };
+                BulkOutcome {
+                    row,
+                    cmr: Some(cmr.to_string()),
+                    derived: Some(derived.to_string()),
+                    status,
+                }
+            }
+        };
+
+        match outcome.status {
+            BulkStatus::Pass => report.passed += 1,
+            BulkStatus::Fail => report.failed += 1,
+            BulkStatus::Error(_) => report.errors += 1,
+        }
+        report.outcomes.push(outcome);
+    }
+    report
+}
+
+fn csv_cell(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+impl BulkReport {
+    /// The report as CSV, one row per input row, for audit trails.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("line,subject,expected,derived,cmr,result,message\n");
+        for o in &self.outcomes {
+            let (result, message) = match &o.status {
+                BulkStatus::Pass => ("PASS", ""),
+                BulkStatus::Fail => ("FAIL", "derived address does not match"),
+                BulkStatus::Error(e) => ("ERROR", e.as_str()),
+            };
+            let cells = [
+                o.row.line.to_string(),
+                csv_cell(&o.row.subject),
+                csv_cell(&o.row.expected),
+                o.derived.clone().unwrap_or_default(),
+                o.cmr.clone().unwrap_or_default(),
+                result.to_string(),
+                csv_cell(message),
+            ];
+            out.push_str(&cells.join(","));
+            out.push('\n');
+        }
+        out
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{} passed, {} failed, {} errors out of {} rows.",
+            self.passed,
+            self.failed,
+            self.errors,
+            self.outcomes.len()
+        )
+    }
+}
@@ -0,0 +1,177 @@
+//! Bulk address verification tool.
+//!
+//! Dropped `.csv` files fill the address list; dropped `.simf` files become
+//! sources that rows can reference by file name.
+
+use std::collections::HashMap;
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::app::read_file;
+use crate::bulk::{self, BulkReport, BulkStatus};
+use crate::i18n::{self, t, Msg};
+use crate::worker_pool;
+
+#[component]
+pub fn BulkPanel() -> impl IntoView {
+    let lang = i18n::use_lang();
+    let (csv, set_csv) = signal(String::new());
+    let sources = RwSignal::new(HashMap::<String, String>::new());
+    let (report, set_report) = signal::<Option<BulkReport>>(None);
+    let (error, set_error) = signal::<Option<String>>(None);
+    let (drag_over, set_drag_over) = signal(false);
+    let (busy, set_busy) = signal(false);
+
+    let handle_drop = move |ev: web_sys::DragEvent| {
+        ev.prevent_default();
+        ev.stop_propagation();
+        set_drag_over.set(false);
+        let Some(files) = ev.data_transfer().and_then(|dt| dt.files()) else {
+            return;
+        };
+        for index in 0..files.length() {
+            let Some(file) = files.get(index) else {
+                continue;
+            };
+            if file.name().ends_with(".csv") {
+                read_file(file, move |_, text| set_csv.set(text));
+            } else if file.name().ends_with(".simf") {
+                read_file(file, move |name, text| {
+                    sources.update(|s| {
+                        s.insert(name, text);
+                    })
+                });
+            } else {
+                let skipped = i18n::text(lang.get_untracked(), Msg::SkippedFile);
+                set_error.set(Some(i18n::format(skipped, &[&file.name()])));
+            }
+        }
+    };
+
+    let run = move |_| match bulk::parse_rows(&csv.get_untracked()) {
+        Ok(rows) => {
+            set_error.set(None);
+            set_busy.set(true);
+            let sources = sources.get_untracked();
+            let not_compiled = i18n::text(lang.get_untracked(), Msg::SourceNotCompiled);
+            spawn_local(async move {
+                // Distinct sources are independent, so they compile in parallel.
+                let codes: Vec<String> = bulk::referenced_sources(&rows, &sources).into_iter().map(str::to_string).collect();
+                let cmrs = worker_pool::compile_cmrs(codes.clone()).await;
+                let compiled: HashMap<String, _> = codes.into_iter().zip(cmrs).collect();
+                // Every source a row names was compiled above.
+                let report = bulk::verify(rows, &sources, |code| {
+                    compiled.get(code).cloned().unwrap_or_else(|| Err(not_compiled.to_string()))
+                });
+                set_report.set(Some(report));
+                set_busy.set(false);
+            });
+        }
+        Err(e) => {
+            set_error.set(Some(e));
+            set_report.set(None);
+        }
+    };
+
+    let export = move |_| {
+        if let Some(report) = report.get_untracked() {
+            crate::app::download_text("address-verification.csv", &report.to_csv());
+        }
+    };
+
+    let results = move || {
+        report.get().map(|report| {
+            let summary = report.summary();
+            let rows = report
+                .outcomes
+                .into_iter()
+                .map(|o| {
+                    let (class, result, message) = match o.status {
+                        BulkStatus::Pass => ("status-badge paid", Msg::Pass, String::new()),
+                        BulkStatus::Fail => (
+                            "status-badge expired",
+                            Msg::Fail,
+                            i18n::format(i18n::text(lang.get(), Msg::Derived), &[&o.derived.unwrap_or_default()]),
+                        ),
+                        BulkStatus::Error(e) => ("status-badge pending", Msg::ErrorResult, e),
+                    };
+                    view! {
+                        <tr>
+                            <td>{o.row.line}</td>
+                            <td>{o.row.subject}</td>
+                            <td class="bulk-address">{o.row.expected}</td>
+                            <td><span class=class>{t(result)}</span></td>
+                            <td>{message}</td>
+                        </tr>
+                    }
+                })
+                .collect_view();
+            view! {
+                <p class="bulk-summary" role="status">{summary}</p>
+                <table class="bulk-table">
+                    <thead>
+                        <tr>
+                            <th>{t(Msg::Line)}</th>
+                            <th>{t(Msg::Subject)}</th>
+                            <th>{t(Msg::ExpectedAddress)}</th>
+                            <th>{t(Msg::Result)}</th>
+                            <th>{t(Msg::Details)}</th>
+                        </tr>
+                    </thead>
+                    <tbody>{rows}</tbody>
+                </table>
+                <div class="button-group">
+                    <button class="secondary" on:click=export>{t(Msg::ExportReportCsv)}</button>
+                </div>
+            }
+        })
+    };
+
+    view! {
+        <div class="bulk-panel">
+            <label>{t(Msg::BulkVerification)}</label>
+            <div
+                class=move || if drag_over.get() { "drop-zone drag-over" } else { "drop-zone" }
+                on:dragover=move |ev: web_sys::DragEvent| {
+                    ev.prevent_default();
+                    set_drag_over.set(true);
+                }
+                on:dragleave=move |_| set_drag_over.set(false)
+                on:drop=handle_drop
+            >
+                <div class="drop-zone-text">{t(Msg::BulkDropText)}</div>
+                <div class="drop-zone-hint">
+                    {move || {
+                        let names = sources.with(|s| {
+                            let mut names: Vec<_> = s.keys().cloned().collect();
+                            names.sort();
+                            names.join(", ")
+                        });
+                        if names.is_empty() {
+                            i18n::text(lang.get(), Msg::BulkCmrHint).to_string()
+                        } else {
+                            i18n::format(i18n::text(lang.get(), Msg::Sources), &[&names])
+                        }
+                    }}
+                </div>
+            </div>
+            <textarea
+                spellcheck="false"
+                placeholder="subject,expected_address\ncontract.simf,tex1p...\n<cmr hex>,lq1qq..."
+                prop:value=move || csv.get()
+                on:input=move |ev| set_csv.set(event_target_value(&ev))
+            />
+            <div class="button-group">
+                <button on:click=run disabled=move || busy.get()>
+                    {move || i18n::text(lang.get(), if busy.get() { Msg::Verifying } else { Msg::VerifyAll })}
+                </button>
+                <button class="danger" on:click=move |_| sources.set(HashMap::new())>
+                    {t(Msg::ClearSources)}
+                </button>
+            </div>
+            {move || error.get().map(|e| view! { <p class="tool-error">{e}</p> })}
+            {results}
+        </div>
+    }
+}
@@ -0,0 +1,107 @@
+//! In-memory cache of compile outcomes.
+//!
+//! Compiling is the slow part of every entry point, and the same input is
+//! compiled over and over (recompiling after a witness tweak, bulk lists that
+//! reference one file from many rows). Outcomes are keyed by the SHA-256 of
+//! the code, arguments and compile options, so a repeated request skips
+//! parsing and type-checking entirely. The witness plays no part in the CMR
+//! and is left out, so a witness tweak still hits. Errors are cached as
+//! well: they are just as deterministic. The cache lives for the page and
+//! holds at most [`CAPACITY`] entries, evicting the oldest first.
+
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::hashes::{sha256, Hash, HashEngine};
+use simplicityhl::simplicity::Cmr;
+
+pub const CAPACITY: usize = 128;
+
+pub type Key = [u8; 32];
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+#[derive(Default)]
+struct CompileCache {
+    entries: HashMap<Key, Result<Cmr, String>>,
+    order: VecDeque<Key>,
+    stats: CacheStats,
+}
+
+thread_local! {
+    static CACHE: RefCell<CompileCache> = RefCell::new(CompileCache::default());
+}
+
+/// Cache key for compiling `code` with `args`. `variant` tells apart
+/// compiles of the same source that can give different outcomes, such as
+/// [`CompileOptions::cache_tag`](crate::options::CompileOptions::cache_tag).
+///
+/// Each part is length-prefixed so different splits of the same bytes do not
+/// collide. The parts are hashed in place rather than joined first, so a
+/// lookup allocates nothing however large the program is.
+pub fn key(code: &str, args: &str, variant: &str) -> Key {
+    let mut engine = sha256::Hash::engine();
+    for part in [code, args, variant] {
+        engine.input(&(part.len() as u64).to_le_bytes());
+        engine.input(part.as_bytes());
+    }
+    sha256::Hash::from_engine(engine).to_byte_array()
+}
+
+/// The cached outcome for `key`, or the result of `compile`, which is stored.
+pub fn get_or_compile(key: Key, compile: impl FnOnce() -> Result<Cmr, String>) -> Result<Cmr, String> {
+    if let Some(hit) = CACHE.with_borrow_mut(|cache| {
+        let hit = cache.entries.get(&key).cloned();
+        if hit.is_some() {
+            cache.stats.hits += 1;
+        }
+        hit
+    }) {
+        return hit;
+    }
+
+    // Compile outside the borrow so a nested cached compile cannot panic.
+    let outcome = compile();
+    CACHE.with_borrow_mut(|cache| {
+        cache.stats.misses += 1;
+        if cache.entries.insert(key, outcome.clone()).is_none() {
+            cache.order.push_back(key);
+        }
+        while cache.order.len() > CAPACITY {
+            if let Some(oldest) = cache.order.pop_front() {
+                cache.entries.remove(&oldest);
+            }
+        }
+        cache.stats.entries = cache.entries.len();
+    });
+    outcome
+}
+
+pub fn clear() {
+    CACHE.with_borrow_mut(|cache| *cache = CompileCache::default());
+}
+
+pub fn stats() -> CacheStats {
+    CACHE.with_borrow(|cache| cache.stats)
+}
+
+/// CMRs of the programs that compiled successfully, oldest first.
+pub fn cmrs() -> Vec<Cmr> {
+    CACHE.with_borrow(|cache| {
+        let mut cmrs: Vec<Cmr> = Vec::new();
+        for key in &cache.order {
+            if let Some(Ok(cmr)) = cache.entries.get(key) {
+                if !cmrs.contains(cmr) {
+                    cmrs.push(*cmr);
+                }
+            }
+        }
+        cmrs
+    })
+}
@@ -0,0 +1,42 @@
+//! Check that source code compiles to a claimed CMR.
+//!
+//! This is the reproducible-build check for auditors: given the CMR a
+//! contract is deployed under, e.g. from a block explorer, and the source it
+//! is said to come from, compile the source and compare roots. Parameter
+//! values are taken from the source, so a program with `param::` values must
+//! be verified with the same values it was deployed with.
+
+use serde::{Deserialize, Serialize};
+
+use crate::address::parse_cmr;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CmrVerification {
+    /// The claimed CMR, normalized to lowercase hex.
+    pub claimed: String,
+    /// The CMR the source compiles to.
+    pub actual: String,
+    pub matches: bool,
+}
+
+/// `claimed` as a CMR in lowercase hex, accepting a `0x` prefix.
+pub fn normalize(claimed: &str) -> Result<String, String> {
+    let claimed = claimed.trim();
+    let hex = claimed.strip_prefix("0x").unwrap_or(claimed);
+    parse_cmr(hex).map(|cmr| cmr.to_string())
+}
+
+/// Compile `code` with `compile` and compare its CMR with `claimed`.
+pub fn verify(
+    code: &str,
+    claimed: &str,
+    compile: impl Fn(&str) -> Result<simplicityhl::simplicity::Cmr, String>,
+) -> Result<CmrVerification, String> {
+    let claimed = normalize(claimed)?;
+    let actual = compile(code)?.to_string();
+    Ok(CmrVerification {
+        matches: actual == claimed,
+        claimed,
+        actual,
+    })
+}
@@ -0,0 +1,428 @@
+//! Asynchronous access to the compiler core.
+//!
+//! The UI calls the compiler through these functions rather than `wasm_api`
+//! directly. By default they forward to the linked-in `wasm_api` and resolve
+//! immediately. With the `lazy-core` feature they instead import the headless
+//! build of this crate (see the README) the first time a compile is
+//! requested, so first paint only waits for the UI shell. The module URL is
+//! read from `<meta name="simplicity-compiler" content="...">` and defaults to
+//! [`DEFAULT_CORE_URL`].
+//!
+//! Every function returns the same JSON string as its `wasm_api` namesake;
+//! failing to load the core is reported through the usual `error` field.
+//! [`cmr_of`] and [`encode_artifact`] return their values directly, with
+//! the error as a string.
+
+use simplicityhl::simplicity::Cmr;
+
+pub const DEFAULT_CORE_URL: &str = "./compiler/simplicity_wasm.js";
+
+#[cfg(feature = "lazy-core")]
+mod core {
+    use wasm_bindgen::prelude::*;
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_futures::JsFuture;
+
+    #[wasm_bindgen(inline_js = r#"
+    let core = null;
+    let loaded = false;
+    export function load_core(url) {
+        if (core === null) {
+            core = import(new URL(url, document.baseURI).href)
+                .then(async (module) => {
+                    await module.default();
+                    loaded = true;
+                    return module;
+                })
+                .catch((e) => {
+                    core = null;
+                    throw e;
+                });
+        }
+        return core;
+    }
+    export function core_loaded() {
+        return loaded;
+    }
+    "#)]
+    extern "C" {
+        fn load_core(url: &str) -> js_sys::Promise;
+        fn core_loaded() -> bool;
+    }
+
+    pub(super) fn core_url() -> String {
+        web_sys::window()
+            .and_then(|w| w.document())
+            .and_then(|d| d.query_selector(r#"meta[name="simplicity-compiler"]"#).ok().flatten())
+            .and_then(|meta| meta.get_attribute("content"))
+            .unwrap_or_else(|| super::DEFAULT_CORE_URL.to_string())
+    }
+
+    pub fn is_loaded() -> bool {
+        core_loaded()
+    }
+
+    /// Call the exported function `name` of the core. A string the function
+    /// throws is the error.
+    pub async fn call_value(name: &str, args: &[JsValue]) -> Result<JsValue, String> {
+        let module = JsFuture::from(load_core(&core_url()))
+            .await
+            .map_err(|e| format!("Failed to load compiler: {e:?}"))?;
+        let function: js_sys::Function = js_sys::Reflect::get(&module, &JsValue::from_str(name))
+            .ok()
+            .and_then(|f| f.dyn_into().ok())
+            .ok_or_else(|| format!("Compiler does not export `{name}`"))?;
+        let js_args = args.iter().collect::<js_sys::Array>();
+        function
+            .apply(&JsValue::NULL, &js_args)
+            .map_err(|e| e.as_string().unwrap_or_else(|| format!("Compiler call `{name}` failed: {e:?}")))
+    }
+
+    /// [`call_value`] for functions that return a string.
+    pub async fn call(name: &str, args: &[JsValue]) -> Result<String, String> {
+        call_value(name, args)
+            .await?
+            .as_string()
+            .ok_or_else(|| format!("Compiler call `{name}` returned a non-string"))
+    }
+}
+
+/// Where the separately built core is loaded from.
+#[cfg(feature = "lazy-core")]
+pub(crate) fn core_url() -> String {
+    core::core_url()
+}
+
+/// Whether a compile request can start without first loading the core.
+pub fn is_loaded() -> bool {
+    #[cfg(feature = "lazy-core")]
+    return core::is_loaded();
+    #[cfg(not(feature = "lazy-core"))]
+    return true;
+}
+
+#[cfg(feature = "lazy-core")]
+async fn forward(name: &str, args: &[&str], fallback_error: impl Fn(&str) -> String) -> String {
+    let args: Vec<wasm_bindgen::JsValue> = args.iter().map(|&a| a.into()).collect();
+    forward_values(name, &args, fallback_error).await
+}
+
+/// [`forward`] for functions that also take numbers, which cross into the
+/// core as JavaScript numbers.
+#[cfg(feature = "lazy-core")]
+async fn forward_values(
+    name: &str,
+    args: &[wasm_bindgen::JsValue],
+    fallback_error: impl Fn(&str) -> String,
+) -> String {
+    core::call(name, args)
+        .await
+        .unwrap_or_else(|e| fallback_error(&e))
+}
+
+fn compile_error_json(error: &str) -> String {
+    serde_json::json!({ "cmr": null, "error": error }).to_string()
+}
+
+pub async fn compile_simplicity(code: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("compile_simplicity", &[code], compile_error_json).await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::compile_simplicity(code);
+}
+
+pub async fn compile_with_witness(code: &str, witness_data: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("compile_with_witness", &[code, witness_data], compile_error_json).await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::compile_with_witness(code, witness_data);
+}
+
+/// A blank `witness_data` compiles without one.
+pub async fn compile_with_options(code: &str, options_json: &str, witness_data: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("compile_with_options", &[code, options_json, witness_data], compile_error_json).await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::compile_with_options(code, options_json, Some(witness_data.to_string()));
+}
+
+pub async fn check_program(code: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("check_program", &[code], |_| r#"{"diagnostics":[]}"#.to_string()).await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::check_program(code);
+}
+
+pub async fn summarize_program(code: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("summarize_program", &[code], |e| {
+        serde_json::json!({ "summary": null, "text": null, "error": e }).to_string()
+    })
+    .await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::summarize_program(code);
+}
+
+pub async fn analyze_program(code: &str, costs_json: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("analyze_program", &[code, costs_json], |e| {
+        serde_json::json!({ "jets": {}, "total_cost": 0, "error": e }).to_string()
+    })
+    .await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::analyze_program(code, costs_json);
+}
+
+pub async fn to_simplicity_text(code: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("to_simplicity_text", &[code], |e| {
+        serde_json::json!({ "text": null, "error": e }).to_string()
+    })
+    .await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::to_simplicity_text(code);
+}
+
+pub async fn source_map(code: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("source_map", &[code], |e| {
+        serde_json::json!({ "map": null, "error": e }).to_string()
+    })
+    .await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::source_map(code);
+}
+
+pub async fn hex_dump(code: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("hex_dump", &[code], |e| {
+        serde_json::json!({ "dump": null, "text": null, "error": e }).to_string()
+    })
+    .await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::hex_dump(code);
+}
+
+pub async fn verify_cmr(code: &str, claimed_cmr: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("verify_cmr", &[code, claimed_cmr], |e| {
+        serde_json::json!({ "verification": null, "error": e }).to_string()
+    })
+    .await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::verify_cmr(code, claimed_cmr);
+}
+
+pub async fn compare_onchain_spend(code: &str, tx_hex: &str, input_index: Option<u32>) -> String {
+    // A missing index goes over as `undefined`, which the core reads as `None`.
+    #[cfg(feature = "lazy-core")]
+    {
+        let index = input_index.map_or(wasm_bindgen::JsValue::UNDEFINED, Into::into);
+        return forward_values("compare_onchain_spend", &[code.into(), tx_hex.into(), index], |e| {
+            serde_json::json!({ "comparison": null, "error": e }).to_string()
+        })
+        .await;
+    }
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::compare_onchain_spend(code, tx_hex, input_index);
+}
+
+pub async fn create_attestation(code: &str, secret_key: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("create_attestation", &[code, secret_key], |e| {
+        serde_json::json!({ "attestation": null, "error": e }).to_string()
+    })
+    .await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::create_attestation(code, secret_key);
+}
+
+pub async fn verify_attestation(attestation_json: &str, code: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("verify_attestation", &[attestation_json, code], |e| {
+        serde_json::json!({ "check": null, "error": e }).to_string()
+    })
+    .await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::verify_attestation(attestation_json, code);
+}
+
+pub async fn export_offer(code: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("export_offer", &[code], |e| {
+        serde_json::json!({ "payload": null, "offer": null, "error": e }).to_string()
+    })
+    .await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::export_offer(code);
+}
+
+pub async fn import_offer(payload: &str, code: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("import_offer", &[payload, code], |e| {
+        serde_json::json!({ "offer": null, "check": null, "error": e }).to_string()
+    })
+    .await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::import_offer(payload, code);
+}
+
+pub async fn evaluate_expression(expression: &str, ty: &str, witness_data: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("evaluate_expression", &[expression, ty, witness_data], |e| {
+        serde_json::json!({ "evaluation": null, "error": e }).to_string()
+    })
+    .await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::evaluate_expression(expression, ty, witness_data);
+}
+
+pub async fn encode_value(ty: &str, json_value: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("encode_value", &[ty, json_value], |e| {
+        serde_json::json!({ "encoded": null, "error": e }).to_string()
+    })
+    .await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::encode_value(ty, json_value);
+}
+
+pub async fn decode_value(ty: &str, bits_hex: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("decode_value", &[ty, bits_hex], |e| {
+        serde_json::json!({ "value": null, "error": e }).to_string()
+    })
+    .await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::decode_value(ty, bits_hex);
+}
+
+pub async fn benchmark(code: &str, witness_data: &str, iterations: u32) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward_values("benchmark", &[code.into(), witness_data.into(), iterations.into()], |e| {
+        serde_json::json!({ "report": null, "error": e }).to_string()
+    })
+    .await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::benchmark(code, witness_data, iterations);
+}
+
+pub async fn run_program(code: &str, witness_data: &str, mock_json: &str, limits_json: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("run_program", &[code, witness_data, mock_json, limits_json], |e| {
+        serde_json::json!({ "run": null, "error": e }).to_string()
+    })
+    .await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::run_program(code, witness_data, mock_json, limits_json);
+}
+
+pub async fn trace_program(code: &str, witness_data: &str, mock_json: &str, limits_json: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("trace_program", &[code, witness_data, mock_json, limits_json], |e| {
+        serde_json::json!({ "trace": null, "text": null, "error": e }).to_string()
+    })
+    .await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::trace_program(code, witness_data, mock_json, limits_json);
+}
+
+pub async fn estimate_witness_size(code: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("estimate_witness_size", &[code], |e| {
+        serde_json::json!({ "estimate": null, "error": e }).to_string()
+    })
+    .await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::estimate_witness_size(code);
+}
+
+pub async fn random_witness(code: &str, seed: &str, sighash: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("random_witness", &[code, seed, sighash], |e| {
+        serde_json::json!({ "witness": null, "error": e }).to_string()
+    })
+    .await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::random_witness(code, seed, sighash);
+}
+
+pub async fn minimize_program(code: &str, failure_json: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("minimize_program", &[code, failure_json], |e| {
+        serde_json::json!({ "reduction": null, "error": e }).to_string()
+    })
+    .await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::minimize_program(code, failure_json);
+}
+
+pub async fn run_tests(code: &str, tests_json: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("run_tests", &[code, tests_json], |e| {
+        serde_json::json!({ "results": [], "passed": 0, "failed": 0, "error": e }).to_string()
+    })
+    .await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::run_tests(code, tests_json);
+}
+
+pub async fn triage_failure(code: &str, witness_data: &str, error: &str, env_json: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("triage_failure", &[code, witness_data, error, env_json], |_| {
+        serde_json::json!({ "causes": [] }).to_string()
+    })
+    .await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::triage_failure(code, witness_data, error, env_json);
+}
+
+pub async fn coverage(code: &str, tests_json: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("coverage", &[code, tests_json], |e| {
+        serde_json::json!({ "report": null, "error": e }).to_string()
+    })
+    .await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::coverage(code, tests_json);
+}
+
+pub async fn pruning_report(code: &str, witness_data: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("pruning_report", &[code, witness_data], |e| {
+        serde_json::json!({ "report": null, "error": e }).to_string()
+    })
+    .await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::pruning_report(code, witness_data);
+}
+
+pub async fn compilation_report(code: &str, witness_data: &str, network: &str, taproot_json: &str) -> String {
+    #[cfg(feature = "lazy-core")]
+    return forward("compilation_report", &[code, witness_data, network, taproot_json], |e| {
+        serde_json::json!({ "report": null, "html": null, "error": e }).to_string()
+    })
+    .await;
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::compilation_report(code, witness_data, network, taproot_json);
+}
+
+/// The CMR of `code`, with parameter values from `args` or, when that is
+/// blank, from the source itself.
+pub async fn cmr_of(code: &str, args: &str) -> Result<Cmr, String> {
+    #[cfg(feature = "lazy-core")]
+    return crate::address::parse_cmr(&core::call("cmr_of", &[code.into(), args.into()]).await?);
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::wasm_api::compile_cmr(code, args);
+}
+
+/// The bytes of the program's [`Artifact`](crate::artifact::Artifact), as
+/// `encode_artifact` returns them.
+pub async fn encode_artifact(code: &str, witness_data: &str) -> Result<Vec<u8>, String> {
+    #[cfg(feature = "lazy-core")]
+    return core::call_value("encode_artifact", &[code.into(), witness_data.into()])
+        .await
+        .map(|bytes| js_sys::Uint8Array::new(&bytes).to_vec());
+    #[cfg(not(feature = "lazy-core"))]
+    return crate::artifact::build(code, witness_data).map(|artifact| artifact.to_bytes());
+}
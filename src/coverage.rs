@@ -0,0 +1,606 @@
+//! Branch and jet coverage of a test spec.
+//!
+//! Every test case the program accepts is run with a trace (see
+//! [`crate::trace`]), which records the branch each `case` node took and
+//! every jet called. The source map (see [`crate::source_map`]) gives the
+//! lines of the calls each branch of a `case` runs, and the `match` whose
+//! arms hold those lines on either side is the one the `case` implements.
+//! Helpers are inlined where `main` calls them first, so a branch that only
+//! calls helpers is still placed. A jet counts where its arm was taken and
+//! the trace shows a call to it.
+//!
+//! Branches whose code makes no call the source map knows of cannot be
+//! placed; their arms are counted as unknown, never as uncovered.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::dag::{DagLike, MaxSharing};
+use simplicityhl::simplicity::jet::Elements;
+use simplicityhl::simplicity::node::{Commit, Inner};
+
+use crate::diagnostics::Diagnostic;
+use crate::options::CompileOptions;
+use crate::sanity::{qualified_names, strip_comment};
+use crate::source_map;
+use crate::spend_paths::{inline_calls, Inlined};
+use crate::test_runner::{self, TestCase};
+use crate::trace::Trace;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BranchCoverage {
+    pub line: usize,
+    /// The matched expression, e.g. `witness::PATH`.
+    pub scrutinee: String,
+    /// The arm's pattern, e.g. `Left(sig)`.
+    pub arm: String,
+    /// Accepted cases that took this arm.
+    pub hits: usize,
+    /// Accepted cases for which the arm taken could not be determined.
+    pub unknown: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct JetCoverage {
+    pub line: usize,
+    pub jet: String,
+    pub hits: usize,
+    pub unknown: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct CoverageReport {
+    /// Cases the program accepted; rejected runs stop early and are not counted.
+    pub cases: usize,
+    pub branches: Vec<BranchCoverage>,
+    pub jets: Vec<JetCoverage>,
+    /// Lines of arms no accepted case reached.
+    pub uncovered_lines: Vec<usize>,
+}
+
+impl CoverageReport {
+    /// Editor warnings for the arms no case executed.
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.branches
+            .iter()
+            .filter(|b| b.hits == 0 && b.unknown == 0)
+            .map(|b| Diagnostic::warning(b.line, 1, format!("Arm `{}` is never executed by the tests", b.arm)))
+            .collect()
+    }
+}
+
+/// An arm, as (match index, arm index).
+pub(crate) type ArmRef = (usize, usize);
+
+pub(crate) struct Arm {
+    pub(crate) line: usize,
+    pub(crate) pattern: String,
+    /// `Left`, `Right`, `Some`, `None`, `true` or `false`.
+    pub(crate) kind: String,
+}
+
+pub(crate) struct Match {
+    pub(crate) scrutinee: String,
+    /// The arm the match is nested in.
+    pub(crate) parent: Option<ArmRef>,
+    pub(crate) arms: Vec<Arm>,
+}
+
+pub(crate) struct Jet {
+    pub(crate) line: usize,
+    pub(crate) name: String,
+    pub(crate) parent: Option<ArmRef>,
+}
+
+/// An open `match` block: its index, the brace depth inside it, and the arm
+/// being read.
+struct Frame {
+    index: usize,
+    depth: usize,
+    arm: Option<usize>,
+}
+
+/// Where the arms and jets of a program sit. Matches are in source order,
+/// so a match always comes after the one it is nested in.
+pub(crate) struct Layout {
+    pub(crate) matches: Vec<Match>,
+    pub(crate) jets: Vec<Jet>,
+    /// `let NAME = witness::WITNESS` bindings, as (NAME, WITNESS).
+    pub(crate) aliases: Vec<(String, String)>,
+    /// The innermost arm each line belongs to, by 0-based line index.
+    pub(crate) line_parents: Vec<Option<ArmRef>>,
+}
+
+pub(crate) fn layout(code: &str) -> Layout {
+    let mut layout = Layout {
+        matches: Vec::new(),
+        jets: Vec::new(),
+        aliases: Vec::new(),
+        line_parents: Vec::new(),
+    };
+    let mut frames: Vec<Frame> = Vec::new();
+    let mut depth = 0usize;
+
+    for (index, line) in code.lines().enumerate() {
+        let line = strip_comment(line);
+        let trimmed = line.trim();
+
+        if let Some(rest) = trimmed.strip_prefix("let ") {
+            if let Some((binding, value)) = rest.split_once('=') {
+                let name = binding.split(':').next().unwrap_or_default().trim();
+                if let Some(witness) = value.trim().trim_end_matches(';').trim().strip_prefix("witness::") {
+                    layout.aliases.push((name.to_string(), witness.to_string()));
+                }
+            }
+        }
+
+        // An arm starts at the top level of the innermost match.
+        let mut body = trimmed;
+        if let Some(frame) = frames.last_mut().filter(|f| f.depth == depth) {
+            if let Some((pattern, rest)) = trimmed.split_once("=>") {
+                let pattern = pattern.trim();
+                let kind = pattern.split('(').next().unwrap_or_default().trim();
+                let arms = &mut layout.matches[frame.index].arms;
+                frame.arm = Some(arms.len());
+                arms.push(Arm {
+                    line: index + 1,
+                    pattern: pattern.to_string(),
+                    kind: kind.to_string(),
+                });
+                body = rest;
+            }
+        }
+        let parent = frames.last().and_then(|f| f.arm.map(|arm| (f.index, arm)));
+        layout.line_parents.push(parent);
+
+        for name in qualified_names(body, "jet::") {
+            layout.jets.push(Jet {
+                line: index + 1,
+                name: name.to_string(),
+                parent,
+            });
+        }
+
+        for c in line.chars() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        while frames.last().is_some_and(|f| f.depth > depth) {
+            frames.pop();
+        }
+
+        let opens_match = body
+            .find("match ")
+            .filter(|&at| body.ends_with('{') && !body[..at].ends_with(|c: char| c.is_alphanumeric() || c == '_'));
+        if let Some(at) = opens_match {
+            let scrutinee = body[at + "match ".len()..body.len() - 1].trim();
+            frames.push(Frame {
+                index: layout.matches.len(),
+                depth,
+                arm: None,
+            });
+            layout.matches.push(Match {
+                scrutinee: scrutinee.to_string(),
+                parent,
+                arms: Vec::new(),
+            });
+        }
+    }
+    layout
+}
+
+/// Whether an arm of kind `kind` runs on the left branch of its `case`.
+fn on_left(kind: &str) -> Option<bool> {
+    match kind {
+        "Left" | "None" | "false" => Some(true),
+        "Right" | "Some" | "true" => Some(false),
+        _ => None,
+    }
+}
+
+/// `arm` and every arm it is nested in.
+fn arm_chain(layout: &Layout, arm: ArmRef) -> Vec<ArmRef> {
+    let mut chain = vec![arm];
+    while let Some(parent) = layout.matches[chain[chain.len() - 1].0].parent {
+        chain.push(parent);
+    }
+    chain
+}
+
+/// Where the source's lines went in the inlined code.
+struct Placement<'a> {
+    layout: &'a Layout,
+    /// 1-based source line → 0-based lines of the inlined code.
+    copies: HashMap<usize, Vec<usize>>,
+}
+
+impl<'a> Placement<'a> {
+    fn new(inlined: &Inlined, layout: &'a Layout) -> Self {
+        let mut copies: HashMap<usize, Vec<usize>> = HashMap::new();
+        for index in 0..inlined.origins.len() {
+            copies.entry(inlined.source_line(index)).or_default().push(index);
+        }
+        Placement { layout, copies }
+    }
+
+    /// Whether each of the 1-based source `lines` has a copy inside `arm`.
+    fn within(&self, lines: &[usize], arm: ArmRef) -> bool {
+        let inside = |index: usize| {
+            let parent = self.layout.line_parents.get(index).copied().flatten();
+            parent.is_some_and(|parent| arm_chain(self.layout, parent).contains(&arm))
+        };
+        lines
+            .iter()
+            .all(|line| self.copies.get(line).is_some_and(|copies| copies.iter().any(|&index| inside(index))))
+    }
+
+    /// The left and right arm of the match a `case` implements, from the
+    /// source lines its branches run. Of the matches that fit, the one no
+    /// other nests in wins; none or several leave the `case` unplaced.
+    fn place(&self, left: &[usize], right: &[usize]) -> Option<(ArmRef, ArmRef)> {
+        if left.is_empty() && right.is_empty() {
+            return None;
+        }
+        let fits: Vec<(ArmRef, ArmRef)> = self
+            .layout
+            .matches
+            .iter()
+            .enumerate()
+            .filter_map(|(index, m)| {
+                let side = |left: bool| m.arms.iter().position(|arm| on_left(&arm.kind) == Some(left));
+                let arms = ((index, side(true)?), (index, side(false)?));
+                (self.within(left, arms.0) && self.within(right, arms.1)).then_some(arms)
+            })
+            .collect();
+        let innermost: Vec<&(ArmRef, ArmRef)> = fits
+            .iter()
+            .filter(|(outer, _)| {
+                !fits.iter().any(|(inner, _)| {
+                    inner.0 != outer.0
+                        && self.layout.matches[inner.0]
+                            .parent
+                            .is_some_and(|p| arm_chain(self.layout, p).iter().any(|arm| arm.0 == outer.0))
+                })
+            })
+            .collect();
+        match innermost.as_slice() {
+            [only] => Some(**only),
+            _ => None,
+        }
+    }
+}
+
+/// The arms each `case` node of `code` runs on its left and right branch,
+/// by the node's CMR, with parameter values read from `args_source`.
+fn case_arms(
+    code: &str,
+    args_source: &str,
+    placement: &Placement,
+) -> Result<HashMap<String, (ArmRef, ArmRef)>, String> {
+    let map = source_map::build_with_args(code, args_source)?;
+    let compiled = CompileOptions::default().compile_source(code, args_source)?;
+    let lines = |cmr: String| -> Vec<usize> {
+        let mut lines: Vec<usize> = map.ranges_of(&cmr).iter().flat_map(|range| range.line..=range.end_line).collect();
+        lines.sort_unstable();
+        lines.dedup();
+        lines
+    };
+    let mut cases = HashMap::new();
+    for item in compiled.commit().post_order_iter::<MaxSharing<Commit<Elements>>>() {
+        if let Inner::Case(left, right) = item.node.inner() {
+            if let Some(arms) = placement.place(&lines(left.cmr().to_string()), &lines(right.cmr().to_string())) {
+                cases.insert(item.node.cmr().to_string(), arms);
+            }
+        }
+    }
+    Ok(cases)
+}
+
+/// The arms `trace` took, with the arms they are nested in.
+fn taken_arms(layout: &Layout, cases: &HashMap<String, (ArmRef, ArmRef)>, trace: &Trace) -> HashSet<ArmRef> {
+    let mut taken = HashSet::new();
+    for step in &trace.steps {
+        match (step.operation.as_str(), cases.get(&step.cmr)) {
+            ("case-left", Some(&(left, _))) => taken.extend(arm_chain(layout, left)),
+            ("case-right", Some(&(_, right))) => taken.extend(arm_chain(layout, right)),
+            _ => {}
+        }
+    }
+    taken
+}
+
+/// The 1-based lines of `code` its run may have reached, going by the run's
+/// `trace`, in the order the run reaches them: helpers appear where `main`
+/// calls them, once per call. Each comes with whether the trace shows it was
+/// reached; lines in arms of matches that could not be placed, or in any arm
+/// when the trace was cut short, are only not ruled out. Lines `main` never
+/// reaches are left out.
+pub(crate) fn reached_lines(code: &str, trace: &Trace) -> Vec<(usize, bool)> {
+    let inlined = inline_calls(code);
+    let layout = layout(&inlined.code);
+    let placement = Placement::new(&inlined, &layout);
+    let cases = case_arms(code, code, &placement).unwrap_or_default();
+    let placed: HashSet<usize> = cases.values().map(|(left, _)| left.0).collect();
+    let taken = taken_arms(&layout, &cases, trace);
+    (0..inlined.origins.len())
+        .filter_map(|index| {
+            let chain = layout.line_parents.get(index).copied().flatten().map(|parent| arm_chain(&layout, parent));
+            let chain = chain.unwrap_or_default();
+            if trace.truncated {
+                return Some((inlined.source_line(index), chain.is_empty()));
+            }
+            if chain.iter().any(|arm| placed.contains(&arm.0) && !taken.contains(arm)) {
+                return None;
+            }
+            let certain = chain.iter().all(|arm| taken.contains(arm));
+            Some((inlined.source_line(index), certain))
+        })
+        .collect()
+}
+
+/// Coverage of `code` by the cases in `tests` that it accepts.
+pub fn coverage(code: &str, tests: &[TestCase]) -> CoverageReport {
+    let inlined = inline_calls(code);
+    let layout = layout(&inlined.code);
+    let placement = Placement::new(&inlined, &layout);
+
+    // A helper inlined twice shares its entries between the copies.
+    let mut report = CoverageReport::default();
+    let mut branch_entries = Vec::new();
+    for m in &layout.matches {
+        for arm in &m.arms {
+            let line = inlined.source_line(arm.line - 1);
+            let entry = report
+                .branches
+                .iter()
+                .position(|b| b.line == line && b.arm == arm.pattern)
+                .unwrap_or_else(|| {
+                    report.branches.push(BranchCoverage {
+                        line,
+                        scrutinee: m.scrutinee.clone(),
+                        arm: arm.pattern.clone(),
+                        hits: 0,
+                        unknown: 0,
+                    });
+                    report.branches.len() - 1
+                });
+            branch_entries.push(entry);
+        }
+    }
+    let mut jet_entries = Vec::new();
+    for jet in &layout.jets {
+        let line = inlined.source_line(jet.line - 1);
+        let entry = report
+            .jets
+            .iter()
+            .position(|j| j.line == line && j.jet == jet.name)
+            .unwrap_or_else(|| {
+                report.jets.push(JetCoverage {
+                    line,
+                    jet: jet.name.clone(),
+                    hits: 0,
+                    unknown: 0,
+                });
+                report.jets.len() - 1
+            });
+        jet_entries.push(entry);
+    }
+
+    // Parameter values can differ per case, and with them the CMRs.
+    let mut programs: HashMap<&str, HashMap<String, (ArmRef, ArmRef)>> = HashMap::new();
+    for case in tests {
+        let Ok(trace) = test_runner::trace_case(code, case) else {
+            continue;
+        };
+        if !trace.accepted {
+            continue;
+        }
+        report.cases += 1;
+        let args_source = case.args.as_deref().filter(|a| !a.trim().is_empty()).unwrap_or(code);
+        let cases = programs
+            .entry(args_source)
+            .or_insert_with(|| case_arms(code, args_source, &placement).unwrap_or_default());
+        let placed: HashSet<usize> = cases.values().map(|(left, _)| left.0).collect();
+
+        let taken = taken_arms(&layout, cases, &trace);
+        let jets_run: HashSet<&str> = trace
+            .steps
+            .iter()
+            .filter(|step| step.operation == "jet")
+            .filter_map(|step| step.detail.as_deref())
+            .collect();
+
+        // Parents come before the matches nested in them. A placed match
+        // whose arm is missing from a complete trace did not take it.
+        let mut states: HashMap<ArmRef, Option<bool>> = HashMap::new();
+        let mut arm_states = Vec::new();
+        for (index, m) in layout.matches.iter().enumerate() {
+            let reached = m.parent.map_or(Some(true), |parent| states[&parent]);
+            for arm_index in 0..m.arms.len() {
+                let arm = (index, arm_index);
+                let state = if taken.contains(&arm) {
+                    Some(true)
+                } else {
+                    match reached {
+                        Some(false) => Some(false),
+                        Some(true) if !trace.truncated && placed.contains(&index) => Some(false),
+                        _ => None,
+                    }
+                };
+                states.insert(arm, state);
+                arm_states.push(state);
+            }
+        }
+
+        // An entry shared by several copies counts once per case.
+        let mut hit = HashSet::new();
+        let mut unknown = HashSet::new();
+        for (&entry, state) in branch_entries.iter().zip(arm_states) {
+            match state {
+                Some(true) => {
+                    hit.insert(entry);
+                }
+                Some(false) => {}
+                None => {
+                    unknown.insert(entry);
+                }
+            }
+        }
+        for entry in 0..report.branches.len() {
+            if hit.contains(&entry) {
+                report.branches[entry].hits += 1;
+            } else if unknown.contains(&entry) {
+                report.branches[entry].unknown += 1;
+            }
+        }
+
+        let mut hit = HashSet::new();
+        let mut unknown = HashSet::new();
+        for (jet, &entry) in layout.jets.iter().zip(&jet_entries) {
+            match jet.parent.map_or(Some(true), |parent| states[&parent]) {
+                Some(true) if jets_run.contains(jet.name.as_str()) => {
+                    hit.insert(entry);
+                }
+                Some(_) => {}
+                None => {
+                    unknown.insert(entry);
+                }
+            }
+        }
+        for entry in 0..report.jets.len() {
+            if hit.contains(&entry) {
+                report.jets[entry].hits += 1;
+            } else if unknown.contains(&entry) {
+                report.jets[entry].unknown += 1;
+            }
+        }
+    }
+
+    report.uncovered_lines = report
+        .branches
+        .iter()
+        .filter(|b| b.hits == 0 && b.unknown == 0)
+        .map(|b| b.line)
+        .collect();
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use simplicityhl::simplicity::elements::secp256k1_zkp::{Keypair, Message, Secp256k1, SecretKey};
+
+    use super::*;
+    use crate::hex;
+    use crate::limits::ExecLimits;
+    use crate::mock_tx::{self, MockTx};
+    use crate::test_runner::TestEnv;
+    use crate::wizards::htlc;
+
+    const LASTWILL: &str = include_str!("../contract/lastwill.simf");
+    const LASTWILL_WITNESS: &str = include_str!("../contract/lastwill.wit");
+
+    fn case(witness: serde_json::Value, env: TestEnv) -> TestCase {
+        TestCase {
+            name: "case".to_string(),
+            witness,
+            args: None,
+            env,
+            expect: Default::default(),
+        }
+    }
+
+    /// A signature by secret key `secret` of what `sig_all_hash` returns
+    /// when `code` spends from `mock`.
+    fn sign(code: &str, mock: &MockTx, secret: u8) -> String {
+        let cmr = CompileOptions::default().compile_source(code, code).unwrap().commit().cmr();
+        let run = mock_tx::run(code, "", mock, cmr, &ExecLimits::default()).unwrap();
+        let digest: [u8; 32] = hex::decode(&run.sig_all_hash).unwrap().try_into().unwrap();
+        let mut key = [0u8; 32];
+        key[31] = secret;
+        let secp = Secp256k1::new();
+        let keypair = Keypair::from_secret_key(&secp, &SecretKey::from_slice(&key).unwrap());
+        let signature = secp.sign_schnorr_no_aux_rand(&Message::from_digest(digest), &keypair);
+        format!("0x{}", hex::encode(signature.as_ref()))
+    }
+
+    fn branch<'a>(report: &'a CoverageReport, arm: &str) -> &'a BranchCoverage {
+        report.branches.iter().find(|b| b.arm == arm).unwrap()
+    }
+
+    fn jet<'a>(report: &'a CoverageReport, name: &str) -> &'a JetCoverage {
+        report.jets.iter().find(|j| j.jet == name).unwrap()
+    }
+
+    #[test]
+    fn arms_calling_helpers_are_placed_from_the_trace() {
+        let code = "fn small(x: u32) {\n    assert!(jet::lt_32(x, 10));\n}\n\nfn seven(y: u32) {\n    assert!(jet::eq_32(y, 7));\n}\n\nfn main() {\n    match witness::CHOICE {\n        Left(x: u32) => small(x),\n        Right(y: u32) => seven(y),\n    }\n}";
+        let witness = |value: &str| serde_json::json!({ "CHOICE": { "value": value, "type": "Either<u32, u32>" } });
+        let tests = [
+            case(witness("Left(3)"), TestEnv::default()),
+            // Rejected, so not counted.
+            case(witness("Right(8)"), TestEnv::default()),
+        ];
+        let report = coverage(code, &tests);
+        assert_eq!(report.cases, 1);
+        assert_eq!((branch(&report, "Left(x: u32)").hits, branch(&report, "Left(x: u32)").unknown), (1, 0));
+        assert_eq!((branch(&report, "Right(y: u32)").hits, branch(&report, "Right(y: u32)").unknown), (0, 0));
+        assert_eq!(report.uncovered_lines, [12]);
+        assert_eq!(jet(&report, "lt_32").hits, 1);
+        assert_eq!(jet(&report, "eq_32").hits, 0);
+    }
+
+    #[test]
+    fn lastwill_cold_spend_covers_the_cold_arm_only() {
+        let mock = MockTx::default();
+        let mut witness: serde_json::Value = serde_json::from_str(LASTWILL_WITNESS).unwrap();
+        // Bob's key is twice the generator.
+        witness["INHERIT_OR_NOT"]["value"] = format!("Right(Left({}))", sign(LASTWILL, &mock, 2)).into();
+        let env = TestEnv {
+            mock: Some(mock),
+            ..TestEnv::default()
+        };
+        let report = coverage(LASTWILL, &[case(witness, env)]);
+        assert_eq!(report.cases, 1);
+        assert_eq!(branch(&report, "Right(cold_or_hot: Either<Signature, Signature>)").hits, 1);
+        assert_eq!(branch(&report, "Left(cold_sig: Signature)").hits, 1);
+        for arm in ["Left(inheritor_sig: Signature)", "Right(hot_sig: Signature)"] {
+            assert_eq!((branch(&report, arm).hits, branch(&report, arm).unknown), (0, 0), "{arm}");
+        }
+        assert_eq!(report.uncovered_lines.len(), 2);
+        assert_eq!(jet(&report, "bip_0340_verify").hits, 1);
+        assert_eq!(jet(&report, "check_lock_distance").hits, 0);
+        assert_eq!(jet(&report, "output_is_fee").hits, 0);
+    }
+
+    #[test]
+    fn htlc_refund_leaves_the_claim_uncovered() {
+        let code = htlc::example();
+        let mock = MockTx {
+            lock_time: 1000,
+            ..MockTx::default()
+        };
+        let signature = sign(&code, &mock, 1);
+        let witness = serde_json::json!({
+            "COMPLETE_OR_REFUND": {
+                "value": format!("Right({signature})"),
+                "type": "Either<(u256, Signature), Signature>",
+            }
+        });
+        let env = TestEnv {
+            mock: Some(mock),
+            ..TestEnv::default()
+        };
+        let report = coverage(&code, &[case(witness, env)]);
+        assert_eq!(report.cases, 1);
+        assert_eq!(branch(&report, "Right(sig: Signature)").hits, 1);
+        let claim = branch(&report, "Left(secret_and_sig: (u256, Signature))");
+        assert_eq!((claim.hits, claim.unknown), (0, 0));
+        assert_eq!(report.uncovered_lines, [claim.line]);
+        assert_eq!(jet(&report, "check_lock_height").hits, 1);
+        assert_eq!(jet(&report, "eq_256").hits, 0);
+        assert_eq!(jet(&report, "sha_256_ctx_8_init").hits, 0);
+    }
+}
@@ -0,0 +1,161 @@
+//! Opt-in local crash log.
+//!
+//! When the user turns it on, panics, uncaught JavaScript errors and
+//! error-level log entries are written to localStorage as they happen, so
+//! they survive the page dying. Nothing is ever sent anywhere: the log only
+//! leaves the browser when the user exports it and attaches it to a bug
+//! report themselves. Turning the log off deletes it.
+
+use serde::{Deserialize, Serialize};
+
+use crate::logging::{self, Level};
+use crate::storage;
+
+const ENABLED_KEY: &str = "crash-log-enabled";
+const LOG_KEY: &str = "crash-log";
+
+/// Reports kept before the oldest are dropped.
+pub const MAX_REPORTS: usize = 20;
+/// Longest message kept per report; panics can carry whole programs.
+const MAX_MESSAGE_CHARS: usize = 2000;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CrashKind {
+    Panic,
+    /// An uncaught JavaScript error or unhandled promise rejection.
+    Uncaught,
+    /// An error-level entry of the structured log.
+    Logged,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CrashReport {
+    pub kind: CrashKind,
+    pub message: String,
+    /// `file:line:column` of a panic.
+    #[serde(default)]
+    pub location: Option<String>,
+    /// Milliseconds since the Unix epoch.
+    pub unix_ms: f64,
+    pub version: String,
+}
+
+impl CrashReport {
+    pub fn new(kind: CrashKind, message: &str, location: Option<String>) -> Self {
+        CrashReport {
+            kind,
+            message: message.chars().take(MAX_MESSAGE_CHARS).collect(),
+            location,
+            unix_ms: js_sys::Date::now(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+}
+
+pub fn is_enabled() -> bool {
+    storage::load(ENABLED_KEY).as_deref() == Some("1")
+}
+
+/// Turn the log on or off; off also deletes what was recorded.
+pub fn set_enabled(enabled: bool) {
+    if enabled {
+        storage::save(ENABLED_KEY, "1");
+    } else {
+        storage::remove(ENABLED_KEY);
+        storage::remove(LOG_KEY);
+    }
+}
+
+pub fn reports() -> Vec<CrashReport> {
+    storage::load(LOG_KEY)
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Store `report` if the log is on.
+pub fn record(report: CrashReport) {
+    if !is_enabled() {
+        return;
+    }
+    let mut reports = reports();
+    reports.push(report);
+    let excess = reports.len().saturating_sub(MAX_REPORTS);
+    reports.drain(..excess);
+    if let Ok(json) = serde_json::to_string(&reports) {
+        storage::save(LOG_KEY, &json);
+    }
+}
+
+pub fn clear() {
+    storage::remove(LOG_KEY);
+}
+
+/// What to attach to a bug report: the app version, the browser, the
+/// recorded crashes and the warnings and errors of this session's log.
+pub fn export_text() -> String {
+    let user_agent = web_sys::window()
+        .and_then(|w| w.navigator().user_agent().ok())
+        .unwrap_or_default();
+    let mut text = format!(
+        "Simplicity WASM {}\nBrowser: {user_agent}\n\n",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    let reports = reports();
+    text.push_str(&format!("Crash reports ({}):\n", reports.len()));
+    for report in &reports {
+        text.push_str(&format!(
+            "- {} {:?} (v{}){}\n  {}\n",
+            logging::iso_time(report.unix_ms),
+            report.kind,
+            report.version,
+            report.location.as_ref().map(|l| format!(" at {l}")).unwrap_or_default(),
+            report.message.replace('\n', "\n  ")
+        ));
+    }
+
+    let entries: Vec<_> = logging::entries().into_iter().filter(|e| e.level >= Level::Warn).collect();
+    text.push_str(&format!("\nWarnings and errors this session ({}):\n", entries.len()));
+    text.push_str(&logging::to_text(&entries));
+    text
+}
+
+/// Record panics and uncaught errors from now on, while the log is on.
+/// Panics still reach the browser console as before.
+#[cfg(feature = "ui")]
+pub fn install() {
+    use wasm_bindgen::prelude::*;
+    use wasm_bindgen::JsCast;
+
+    std::panic::set_hook(Box::new(|info| {
+        console_error_panic_hook::hook(info);
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Panic".to_string());
+        let location = info.location().map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+        record(CrashReport::new(CrashKind::Panic, &message, location));
+    }));
+
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let on_error = Closure::<dyn Fn(JsValue)>::new(|event: JsValue| {
+        // `ErrorEvent.message`, or the rejection `reason`.
+        let message = ["message", "reason"]
+            .into_iter()
+            .filter_map(|field| js_sys::Reflect::get(&event, &JsValue::from_str(field)).ok())
+            .find(|value| !value.is_undefined())
+            .map(|value| value.as_string().unwrap_or_else(|| format!("{value:?}")))
+            .unwrap_or_else(|| "Unknown error".to_string());
+        record(CrashReport::new(CrashKind::Uncaught, &message, None));
+    });
+    for event in ["error", "unhandledrejection"] {
+        let _ = window.add_event_listener_with_callback(event, on_error.as_ref().unchecked_ref());
+    }
+    on_error.forget();
+    logging::debug(logging::Category::Ui, "Crash log hooks installed");
+}
@@ -0,0 +1,84 @@
+//! Output descriptors for compiled programs.
+//!
+//! A contract output is written as `eltr(KEY,sim(CMR))`: the Elements
+//! taproot descriptor with internal key `KEY` and a single Simplicity leaf
+//! identified by its CMR. Wallets watching the descriptor derive the same
+//! script as [`taproot_address`](crate::address::taproot_address) when `KEY`
+//! is the key the address was built with. The `#` suffix is the BIP-380
+//! checksum, so typos are caught on import.
+
+use simplicityhl::simplicity::elements::secp256k1_zkp::XOnlyPublicKey;
+use simplicityhl::simplicity::Cmr;
+
+use crate::address::UNSPENDABLE_INTERNAL_KEY;
+
+const INPUT_CHARSET: &str =
+    "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+const CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+fn polymod(mut c: u64, value: u64) -> u64 {
+    let top = c >> 35;
+    c = ((c & 0x7_ffff_ffff) << 5) ^ value;
+    for (bit, generator) in [
+        0xf5dee51989,
+        0xa9fdca3312,
+        0x1bab10e32d,
+        0x3706b1677a,
+        0x644d626ffd,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        if top >> bit & 1 == 1 {
+            c ^= generator;
+        }
+    }
+    c
+}
+
+/// The BIP-380 checksum of `descriptor`.
+pub fn checksum(descriptor: &str) -> Result<String, String> {
+    let mut c = 1u64;
+    let mut class = 0u64;
+    let mut class_count = 0;
+    for ch in descriptor.chars() {
+        let position = INPUT_CHARSET
+            .find(ch)
+            .ok_or_else(|| format!("Invalid character `{ch}` in descriptor"))? as u64;
+        c = polymod(c, position & 31);
+        class = class * 3 + (position >> 5);
+        class_count += 1;
+        if class_count == 3 {
+            c = polymod(c, class);
+            class = 0;
+            class_count = 0;
+        }
+    }
+    if class_count > 0 {
+        c = polymod(c, class);
+    }
+    for _ in 0..8 {
+        c = polymod(c, 0);
+    }
+    c ^= 1;
+    Ok((0..8)
+        .map(|i| CHECKSUM_CHARSET[(c >> (5 * (7 - i)) & 31) as usize] as char)
+        .collect())
+}
+
+/// The descriptor of the output locking funds to `cmr` under `internal_key`.
+pub fn descriptor(cmr: Cmr, internal_key: &XOnlyPublicKey) -> String {
+    let body = format!("eltr({internal_key},sim({cmr}))");
+    let checksum = checksum(&body).expect("hex and punctuation are in the charset");
+    format!("{body}#{checksum}")
+}
+
+/// Parse `internal_key` as x-only hex, defaulting to the unspendable key
+/// when it is blank.
+pub fn parse_internal_key(internal_key: &str) -> Result<XOnlyPublicKey, String> {
+    let internal_key = internal_key.trim();
+    let internal_key = if internal_key.is_empty() { UNSPENDABLE_INTERNAL_KEY } else { internal_key };
+    internal_key
+        .parse()
+        .map_err(|e| format!("Invalid internal key: {e}"))
+}
@@ -0,0 +1,146 @@
+//! Hidden developer console showing the structured log.
+//!
+//! Ctrl+Shift+L (Cmd+Shift+L on macOS) shows and hides it; the choice is
+//! remembered.
+
+use std::time::Duration;
+
+use leptos::prelude::*;
+
+use crate::app::download_text;
+use crate::i18n::{self, t, Msg};
+use crate::logging::{self, Category, Entry, Level};
+use crate::storage;
+
+const VISIBLE_KEY: &str = "dev-console";
+
+/// How often the open console looks for new entries.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[component]
+pub fn DevConsole() -> impl IntoView {
+    let lang = i18n::use_lang();
+    let (visible, set_visible) = signal(storage::load(VISIBLE_KEY).as_deref() == Some("1"));
+    let (entries, set_entries) = signal(Vec::<Entry>::new());
+    let (min_level, set_min_level) = signal(Level::Debug);
+    let (category, set_category) = signal(None::<Category>);
+    let seen = StoredValue::new(None::<u64>);
+
+    let toggle = move || {
+        let shown = !visible.get_untracked();
+        set_visible.set(shown);
+        storage::save(VISIBLE_KEY, if shown { "1" } else { "0" });
+    };
+
+    let keydown = window_event_listener(leptos::ev::keydown, move |ev| {
+        if (ev.ctrl_key() || ev.meta_key()) && ev.shift_key() && ev.key().eq_ignore_ascii_case("l") {
+            ev.prevent_default();
+            toggle();
+        }
+    });
+    on_cleanup(move || keydown.remove());
+
+    if let Ok(poll) = set_interval_with_handle(
+        move || {
+            let revision = logging::revision();
+            if visible.get_untracked() && seen.get_value() != Some(revision) {
+                seen.set_value(Some(revision));
+                set_entries.set(logging::entries());
+            }
+        },
+        POLL_INTERVAL,
+    ) {
+        on_cleanup(move || poll.clear());
+    }
+
+    let shown = move || {
+        let (min_level, category) = (min_level.get(), category.get());
+        entries.with(|entries| {
+            entries
+                .iter()
+                .filter(|e| e.level >= min_level && category.map_or(true, |c| e.category == c))
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+    };
+
+    let export = move |_| download_text("simplicity-log.txt", &logging::to_text(&shown()));
+
+    let rows = move || {
+        shown()
+            .into_iter()
+            .rev()
+            .map(|entry| {
+                view! {
+                    <tr class=format!("dev-log-{}", entry.level.as_str())>
+                        <td>{entry.time()}</td>
+                        <td>{entry.level.as_str()}</td>
+                        <td>{entry.category.as_str()}</td>
+                        <td class="dev-log-message">{entry.message}</td>
+                    </tr>
+                }
+            })
+            .collect_view()
+    };
+
+    let level_options = move |selected: Level| {
+        Level::ALL
+            .into_iter()
+            .map(|level| view! { <option value=level.as_str() selected=level == selected>{level.as_str()}</option> })
+            .collect_view()
+    };
+
+    view! {
+        <Show when=move || visible.get()>
+            <div class="section tool-section dev-console">
+                <label>{t(Msg::DeveloperConsole)}</label>
+                <div class="button-group">
+                    <select
+                        aria-label=t(Msg::LowestLevelShown)
+                        on:change=move |ev| {
+                            if let Ok(level) = event_target_value(&ev).parse() {
+                                set_min_level.set(level);
+                            }
+                        }
+                    >
+                        {level_options(min_level.get_untracked())}
+                    </select>
+                    <select
+                        aria-label=t(Msg::CategoryShown)
+                        on:change=move |ev| {
+                            let value = event_target_value(&ev);
+                            set_category.set(Category::ALL.into_iter().find(|c| c.as_str() == value));
+                        }
+                    >
+                        <option value="">{t(Msg::AllCategories)}</option>
+                        {Category::ALL
+                            .into_iter()
+                            .map(|c| view! { <option value=c.as_str()>{c.as_str()}</option> })
+                            .collect_view()}
+                    </select>
+                    <label class="dev-console-level">
+                        {t(Msg::BrowserConsole)}
+                        <select on:change=move |ev| {
+                            if let Ok(level) = event_target_value(&ev).parse() {
+                                logging::set_console_level(level);
+                            }
+                        }>
+                            {level_options(logging::console_level())}
+                        </select>
+                    </label>
+                    <button class="secondary" on:click=export>{t(Msg::ExportLog)}</button>
+                    <button class="danger" on:click=move |_| logging::clear()>{t(Msg::ClearLog)}</button>
+                    <button class="secondary" on:click=move |_| toggle()>{t(Msg::Close)}</button>
+                </div>
+                <p class="drop-zone-hint">
+                    {move || i18n::format(i18n::text(lang.get(), Msg::LogEntriesKept), &[&entries.with(Vec::len).to_string()])}
+                </p>
+                <div class="dev-log">
+                    <table class="bulk-table">
+                        <tbody>{rows}</tbody>
+                    </table>
+                </div>
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,135 @@
+//! Line-anchored compiler diagnostics.
+//!
+//! SimplicityHL reports errors as [`RichError`]s while parsing, but the
+//! compile step flattens them into rendered strings. Both forms are turned
+//! into [`Diagnostic`]s here so the editor gutter can place markers.
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::error::RichError;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// An edit that resolves a diagnostic: replace bytes `start..end` of the
+/// source with `replacement`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    /// What the edit does, e.g. "Insert `;`".
+    pub title: String,
+    pub start: usize,
+    pub end: usize,
+    pub replacement: String,
+}
+
+impl Fix {
+    /// `code` with the edit made, or `None` if the range does not fit it.
+    pub fn apply(&self, code: &str) -> Option<String> {
+        let valid = self.start <= self.end
+            && self.end <= code.len()
+            && code.is_char_boundary(self.start)
+            && code.is_char_boundary(self.end);
+        valid.then(|| format!("{}{}{}", &code[..self.start], self.replacement, &code[self.end..]))
+    }
+}
+
+/// A message attached to a source range. Lines and columns are 1-based.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    pub message: String,
+    /// A suggested edit, for diagnostics with an obvious fix.
+    #[serde(default)]
+    pub fix: Option<Fix>,
+}
+
+impl Diagnostic {
+    pub fn error(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Diagnostic {
+            severity: Severity::Error,
+            line,
+            column,
+            end_line: line,
+            end_column: column,
+            message: message.into(),
+            fix: None,
+        }
+    }
+
+    pub fn warning(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Diagnostic {
+            severity: Severity::Warning,
+            ..Diagnostic::error(line, column, message)
+        }
+    }
+
+    pub fn from_rich_error(error: &RichError) -> Self {
+        let span = error.span();
+        Diagnostic {
+            severity: Severity::Error,
+            line: span.start.line.get(),
+            column: span.start.col.get(),
+            end_line: span.end.line.get(),
+            end_column: span.end.col.get(),
+            message: error.error().to_string(),
+            fix: None,
+        }
+    }
+
+    /// Recover a diagnostic from a rendered [`RichError`].
+    ///
+    /// The rendering looks like
+    ///
+    /// ```text
+    ///   |
+    /// 3 |     let x: u8 = y;
+    ///   |                 ^ Variable `y` is not defined
+    /// ```
+    ///
+    /// Errors without a source excerpt are attached to line 1.
+    pub fn from_rendered(text: &str) -> Self {
+        let mut first_line = None;
+        let mut last_line = None;
+        let mut underline = None;
+
+        for row in text.lines() {
+            let Some((number, rest)) = row.split_once(" |") else {
+                continue;
+            };
+            let number = number.trim();
+            if number.is_empty() {
+                if let Some(start) = rest.find('^') {
+                    underline = Some((start, &rest[start..]));
+                }
+            } else if let Ok(line) = number.parse::<usize>() {
+                first_line.get_or_insert(line);
+                last_line = Some(line);
+            }
+        }
+
+        match (first_line, underline) {
+            (Some(line), Some((offset, marked))) => {
+                let carets = marked.chars().take_while(|&c| c == '^').count();
+                let message = marked[carets..].trim();
+                Diagnostic {
+                    severity: Severity::Error,
+                    line,
+                    // The renderer pads one space after the gutter bar.
+                    column: offset.max(1),
+                    end_line: last_line.unwrap_or(line),
+                    end_column: offset.max(1) + carets.saturating_sub(1),
+                    message: message.to_string(),
+                    fix: None,
+                }
+            }
+            _ => Diagnostic::error(1, 1, text.trim()),
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Line-number gutter with per-line diagnostic markers.
+//!
+//! The gutter sits beside the code textarea and follows its vertical scroll
+//! offset. Clicking a marker toggles the diagnostic messages for that line,
+//! each with an explanation if it is a common error and a button applying
+//! its fix if it has one.
+
+use leptos::prelude::*;
+
+use crate::diagnostics::{Diagnostic, Fix, Severity};
+use crate::error_explain_panel::LearnMore;
+
+fn line_severity(diagnostics: &[Diagnostic], line: usize) -> Option<Severity> {
+    let mut found = None;
+    for d in diagnostics.iter().filter(|d| d.line == line) {
+        if d.severity == Severity::Error {
+            return Some(Severity::Error);
+        }
+        found = Some(d.severity);
+    }
+    found
+}
+
+#[component]
+pub fn Gutter(
+    #[prop(into)] code: Signal<String>,
+    #[prop(into)] diagnostics: Signal<Vec<Diagnostic>>,
+    /// Current `scrollTop` of the textarea, in pixels.
+    #[prop(into)]
+    scroll_top: Signal<f64>,
+    /// Called with the fix of a diagnostic whose "Apply fix" was clicked.
+    on_fix: Callback<Fix>,
+) -> impl IntoView {
+    let (selected_line, set_selected_line) = signal::<Option<usize>>(None);
+    let line_count = Memo::new(move |_| code.with(|c| c.split('\n').count().max(1)));
+
+    // Forget the selection once the diagnostics for that line disappear.
+    Effect::new(move |_| {
+        let diagnostics = diagnostics.get();
+        if let Some(line) = selected_line.get_untracked() {
+            if !diagnostics.iter().any(|d| d.line == line) {
+                set_selected_line.set(None);
+            }
+        }
+    });
+
+    let rows = move || {
+        let diagnostics = diagnostics.get();
+        (1..=line_count.get())
+            .map(|line| {
+                let marker = line_severity(&diagnostics, line).map(|severity| {
+                    let (class, icon, label) = match severity {
+                        Severity::Error => ("gutter-marker error", "●", "error"),
+                        Severity::Warning => ("gutter-marker warning", "▲", "warning"),
+                    };
+                    view! {
+                        <button
+                            class=class
+                            title=format!("Show {label} on line {line}")
+                            aria-label=format!("Show {label} on line {line}")
+                            on:click=move |_| {
+                                set_selected_line.update(|s| {
+                                    *s = if *s == Some(line) { None } else { Some(line) };
+                                });
+                            }
+                        >
+                            {icon}
+                        </button>
+                    }
+                });
+                view! {
+                    <div class="gutter-line">
+                        <span class="gutter-marker-slot">{marker}</span>
+                        <span class="gutter-number" aria-hidden="true">{line}</span>
+                    </div>
+                }
+            })
+            .collect_view()
+    };
+
+    let detail = move || {
+        selected_line.get().map(|line| {
+            let messages = diagnostics
+                .get()
+                .into_iter()
+                .filter(|d| d.line == line)
+                .map(|d| {
+                    let class = match d.severity {
+                        Severity::Error => "gutter-detail-item error",
+                        Severity::Warning => "gutter-detail-item warning",
+                    };
+                    let fix = d.fix.map(|fix| {
+                        let title = fix.title.clone();
+                        view! {
+                            <button class="secondary quick-fix" on:click=move |_| on_fix.run(fix.clone())>
+                                {format!("Apply fix: {title}")}
+                            </button>
+                        }
+                    });
+                    view! {
+                        <li class=class>
+                            {format!("Line {}:{} — {}", d.line, d.column, d.message)}
+                            {fix}
+                            <LearnMore message=d.message />
+                        </li>
+                    }
+                })
+                .collect_view();
+            view! { <ul class="gutter-detail" role="status">{messages}</ul> }
+        })
+    };
+
+    view! {
+        <div class="gutter">
+            <div
+                class="gutter-lines"
+                style=move || format!("transform: translateY(-{}px);", scroll_top.get())
+            >
+                {rows}
+            </div>
+        </div>
+        {detail}
+    }
+}
@@ -0,0 +1,71 @@
+//! Undo/redo history for the code editor.
+//!
+//! The native textarea undo stack is lost as soon as the value is replaced
+//! from Rust (template insertion, file drops, clearing), so the editor keeps
+//! its own snapshots. Consecutive keystrokes are grouped into one step.
+
+/// Keystrokes closer together than this are undone as a single step.
+const GROUP_WINDOW_MS: f64 = 1000.0;
+/// Oldest snapshots are dropped beyond this many undo steps.
+const MAX_ENTRIES: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditKind {
+    /// Text typed or pasted by the user into the textarea.
+    Typing,
+    /// A whole-buffer change made by the app; always its own undo step.
+    Programmatic,
+}
+
+#[derive(Debug, Default)]
+pub struct History {
+    undo: Vec<String>,
+    redo: Vec<String>,
+    /// Kind and time of the last recorded edit, used for grouping.
+    last_edit: Option<(EditKind, f64)>,
+}
+
+impl History {
+    /// Record that the buffer is about to change away from `before`.
+    ///
+    /// `now_ms` is any monotonic millisecond clock, e.g. `Date.now()`.
+    pub fn record(&mut self, before: &str, kind: EditKind, now_ms: f64) {
+        let extends_group = kind == EditKind::Typing
+            && matches!(
+                self.last_edit,
+                Some((EditKind::Typing, at)) if now_ms - at < GROUP_WINDOW_MS
+            );
+        if !extends_group {
+            self.undo.push(before.to_string());
+            if self.undo.len() > MAX_ENTRIES {
+                self.undo.remove(0);
+            }
+        }
+        self.redo.clear();
+        self.last_edit = Some((kind, now_ms));
+    }
+
+    /// Step back, returning the text to restore.
+    pub fn undo(&mut self, current: &str) -> Option<String> {
+        let previous = self.undo.pop()?;
+        self.redo.push(current.to_string());
+        self.last_edit = None;
+        Some(previous)
+    }
+
+    /// Re-apply the last undone step, returning the text to restore.
+    pub fn redo(&mut self, current: &str) -> Option<String> {
+        let next = self.redo.pop()?;
+        self.undo.push(current.to_string());
+        self.last_edit = None;
+        Some(next)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
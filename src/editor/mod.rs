@@ -0,0 +1,11 @@
+//! Editor building blocks layered around the plain code textarea.
+
+#[cfg(feature = "ui")]
+pub mod gutter;
+pub mod history;
+#[cfg(feature = "ui")]
+pub mod overlay;
+pub mod pairs;
+pub mod search;
+#[cfg(feature = "ui")]
+pub mod search_bar;
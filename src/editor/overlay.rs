@@ -0,0 +1,56 @@
+//! Highlights drawn over the code textarea.
+//!
+//! A textarea cannot style parts of its text, so a copy of the buffer is
+//! laid out on top of it with transparent text, and only the highlighted
+//! ranges get a background. The copy follows the textarea's scroll offsets
+//! and never takes pointer events.
+
+use leptos::prelude::*;
+
+/// A highlighted byte range of the buffer and its CSS class.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mark {
+    pub start: usize,
+    pub end: usize,
+    pub class: &'static str,
+}
+
+#[component]
+pub fn Overlay(
+    #[prop(into)] code: Signal<String>,
+    /// Non-overlapping ranges, in any order.
+    #[prop(into)]
+    marks: Signal<Vec<Mark>>,
+    #[prop(into)] scroll_top: Signal<f64>,
+    #[prop(into)] scroll_left: Signal<f64>,
+) -> impl IntoView {
+    let segments = move || {
+        let mut marks = marks.get();
+        marks.sort_by_key(|m| m.start);
+        code.with(|code| {
+            let mut views = Vec::new();
+            let mut at = 0;
+            for mark in marks {
+                let (start, end) = (mark.start.max(at), mark.end.min(code.len()));
+                if start >= end || !code.is_char_boundary(start) || !code.is_char_boundary(end) {
+                    continue;
+                }
+                views.push(view! { <span>{code[at..start].to_string()}</span> }.into_any());
+                views.push(view! { <mark class=mark.class>{code[start..end].to_string()}</mark> }.into_any());
+                at = end;
+            }
+            views.push(view! { <span>{code[at..].to_string()}</span> }.into_any());
+            views
+        })
+    };
+
+    view! {
+        <div class="editor-overlay" aria-hidden="true">
+            <pre
+                style=move || format!("transform: translate(-{}px, -{}px);", scroll_left.get(), scroll_top.get())
+            >
+                {segments}
+            </pre>
+        </div>
+    }
+}
@@ -0,0 +1,197 @@
+//! Bracket matching, auto-indent and auto-closing pairs.
+//!
+//! Everything here works on the buffer text and byte offsets, so the
+//! textarea only reports keys and selections and applies the [`Edit`]s.
+//! Brackets inside comments and strings are ignored when matching.
+
+use serde::{Deserialize, Serialize};
+
+use crate::storage;
+
+const SETTINGS_KEY: &str = "editor.settings";
+const PAIRS: [(char, char); 4] = [('{', '}'), ('(', ')'), ('[', ']'), ('"', '"')];
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(default)]
+pub struct EditorSettings {
+    pub match_brackets: bool,
+    pub auto_indent: bool,
+    pub auto_close: bool,
+}
+
+impl Default for EditorSettings {
+    fn default() -> Self {
+        EditorSettings {
+            match_brackets: true,
+            auto_indent: true,
+            auto_close: true,
+        }
+    }
+}
+
+impl EditorSettings {
+    pub fn load() -> Self {
+        storage::load(SETTINGS_KEY)
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Ok(json) = serde_json::to_string(self) {
+            storage::save(SETTINGS_KEY, &json);
+        }
+    }
+}
+
+/// The buffer after a keystroke, with the cursor as a byte offset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+    pub text: String,
+    pub cursor: usize,
+}
+
+/// Byte offset of a UTF-16 offset, as textarea selections report them.
+pub fn byte_offset(text: &str, utf16: u32) -> usize {
+    let mut units = 0;
+    for (at, c) in text.char_indices() {
+        if units >= utf16 as usize {
+            return at;
+        }
+        units += c.len_utf16();
+    }
+    text.len()
+}
+
+/// UTF-16 offset of a byte offset, for setting textarea selections.
+pub fn utf16_offset(text: &str, byte: usize) -> u32 {
+    text[..byte.min(text.len())].encode_utf16().count() as u32
+}
+
+/// Whether each byte of `code` is program text rather than a comment or string.
+fn code_mask(code: &str) -> Vec<bool> {
+    let bytes = code.as_bytes();
+    let mut mask = vec![true; bytes.len()];
+    let mut at = 0;
+    while at < bytes.len() {
+        let end = match (bytes[at], bytes.get(at + 1)) {
+            (b'/', Some(b'/')) => code[at..].find('\n').map_or(bytes.len(), |n| at + n),
+            (b'/', Some(b'*')) => code[at + 2..].find("*/").map_or(bytes.len(), |n| at + n + 4),
+            (b'"', _) => code[at + 1..].find('"').map_or(bytes.len(), |n| at + n + 2),
+            _ => {
+                at += 1;
+                continue;
+            }
+        };
+        mask[at..end].iter_mut().for_each(|m| *m = false);
+        at = end;
+    }
+    mask
+}
+
+/// The bracket next to `cursor` and its partner, as byte offsets. The
+/// bracket just before the cursor wins over the one just after it.
+pub fn matching_bracket(code: &str, cursor: usize) -> Option<(usize, usize)> {
+    let mask = code_mask(code);
+    let bytes = code.as_bytes();
+    let is_bracket = |at: usize| mask.get(at) == Some(&true) && b"{}()[]".contains(&bytes[at]);
+    let at = [cursor.checked_sub(1), Some(cursor)]
+        .into_iter()
+        .flatten()
+        .find(|&at| at < bytes.len() && is_bracket(at))?;
+
+    let c = char::from(bytes[at]);
+    let (open, close) = PAIRS.iter().copied().find(|&(open, close)| c == open || c == close)?;
+    let forward = c == open;
+    let (open, close) = (open as u8, close as u8);
+    let mut depth = 0usize;
+    let mut step = |i: usize| {
+        if !mask[i] {
+            return false;
+        }
+        let (deeper, shallower) = if forward { (open, close) } else { (close, open) };
+        if bytes[i] == deeper {
+            depth += 1;
+        } else if bytes[i] == shallower {
+            depth -= 1;
+            return depth == 0;
+        }
+        false
+    };
+    let partner = if forward {
+        (at..bytes.len()).find(|&i| step(i))
+    } else {
+        (0..=at).rev().find(|&i| step(i))
+    }?;
+    Some((at, partner))
+}
+
+/// The leading whitespace of the line containing `at`.
+fn line_indent(code: &str, at: usize) -> &str {
+    let start = code[..at].rfind('\n').map_or(0, |n| n + 1);
+    let line = &code[start..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+/// Enter at `start..end`: keep the line's indentation, indent one level of
+/// `indent_width` spaces after an opening bracket, and put a closing bracket
+/// right after the cursor on its own line.
+pub fn newline(code: &str, start: usize, end: usize, indent_width: usize) -> Edit {
+    let indent = line_indent(code, start).to_string();
+    let before = code[..start].trim_end_matches([' ', '\t']);
+    let opens = before.ends_with(['{', '(', '[']);
+    let closes = code[end..].starts_with(['}', ')', ']']);
+
+    let mut inserted = format!("\n{indent}");
+    if opens {
+        inserted.push_str(&" ".repeat(indent_width));
+    }
+    let cursor = start + inserted.len();
+    if opens && closes {
+        inserted.push_str(&format!("\n{indent}"));
+    }
+    Edit {
+        text: format!("{}{inserted}{}", &code[..start], &code[end..]),
+        cursor,
+    }
+}
+
+/// Typing `c` over `start..end`, or `None` to let the textarea handle it.
+/// Openers insert their closer (around the selection, if any), and typing a
+/// closer or quote that is already next to the cursor steps over it.
+pub fn type_char(code: &str, start: usize, end: usize, c: char) -> Option<Edit> {
+    let next = code[end..].chars().next();
+    let previous = code[..start].chars().next_back();
+    if start == end && next == Some(c) && PAIRS.iter().any(|&(_, close)| close == c) {
+        return Some(Edit {
+            text: code.to_string(),
+            cursor: end + c.len_utf8(),
+        });
+    }
+    let &(open, close) = PAIRS.iter().find(|&&(open, _)| open == c)?;
+    let selection = &code[start..end];
+    if selection.is_empty() {
+        // Only close where nothing would be swallowed by the pair.
+        let free = next.map_or(true, |n| n.is_whitespace() || ")]},;".contains(n));
+        let quote_inside_word = open == '"' && previous.is_some_and(|p| p.is_alphanumeric());
+        if !free || quote_inside_word {
+            return None;
+        }
+    }
+    Some(Edit {
+        text: format!("{}{open}{selection}{close}{}", &code[..start], &code[end..]),
+        cursor: end + open.len_utf8(),
+    })
+}
+
+/// Backspace between an empty pair removes both halves.
+pub fn backspace(code: &str, start: usize, end: usize) -> Option<Edit> {
+    if start != end {
+        return None;
+    }
+    let previous = code[..start].chars().next_back()?;
+    let next = code[end..].chars().next()?;
+    PAIRS.iter().any(|&pair| pair == (previous, next)).then(|| Edit {
+        text: format!("{}{}", &code[..start - previous.len_utf8()], &code[end + next.len_utf8()..]),
+        cursor: start - previous.len_utf8(),
+    })
+}
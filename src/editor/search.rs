@@ -0,0 +1,85 @@
+//! Find and replace over the code buffer.
+//!
+//! Queries are literal text unless regex mode is on, in which case they use
+//! the `regex` crate's syntax and replacements may refer to groups as `$1`
+//! or `${name}`. Matches are byte ranges into the buffer; empty matches are
+//! skipped since they cannot be highlighted or usefully replaced.
+
+use std::ops::Range;
+
+use regex::{Regex, RegexBuilder};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchOptions {
+    pub regex: bool,
+    pub case_sensitive: bool,
+}
+
+fn pattern(query: &str, options: SearchOptions) -> Result<Regex, String> {
+    let source = if options.regex { query.to_string() } else { regex::escape(query) };
+    RegexBuilder::new(&source)
+        .case_insensitive(!options.case_sensitive)
+        .multi_line(true)
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+/// Every non-empty match of `query` in `code`, in order.
+pub fn find_all(code: &str, query: &str, options: SearchOptions) -> Result<Vec<Range<usize>>, String> {
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let pattern = pattern(query, options)?;
+    Ok(pattern.find_iter(code).filter(|m| !m.is_empty()).map(|m| m.range()).collect())
+}
+
+/// The text that replaces the match at `range`.
+fn expansion(pattern: &Regex, code: &str, range: &Range<usize>, replacement: &str, options: SearchOptions) -> String {
+    if !options.regex {
+        return replacement.to_string();
+    }
+    let mut expanded = String::new();
+    let captures = pattern
+        .captures_at(code, range.start)
+        .filter(|c| c.get(0).map(|m| m.range()) == Some(range.clone()));
+    if let Some(captures) = captures {
+        captures.expand(replacement, &mut expanded);
+    }
+    expanded
+}
+
+/// `code` with the match at `range` replaced.
+pub fn replace_one(
+    code: &str,
+    range: Range<usize>,
+    query: &str,
+    replacement: &str,
+    options: SearchOptions,
+) -> Result<String, String> {
+    let pattern = pattern(query, options)?;
+    let expanded = expansion(&pattern, code, &range, replacement, options);
+    Ok(format!("{}{expanded}{}", &code[..range.start], &code[range.end..]))
+}
+
+/// `code` with every match replaced, and how many there were.
+pub fn replace_all(
+    code: &str,
+    query: &str,
+    replacement: &str,
+    options: SearchOptions,
+) -> Result<(String, usize), String> {
+    let ranges = find_all(code, query, options)?;
+    if ranges.is_empty() {
+        return Ok((code.to_string(), 0));
+    }
+    let pattern = pattern(query, options)?;
+    let mut replaced = String::with_capacity(code.len());
+    let mut at = 0;
+    for range in &ranges {
+        replaced.push_str(&code[at..range.start]);
+        replaced.push_str(&expansion(&pattern, code, range, replacement, options));
+        at = range.end;
+    }
+    replaced.push_str(&code[at..]);
+    Ok((replaced, ranges.len()))
+}
@@ -0,0 +1,214 @@
+//! Find/replace bar above the code editor (Ctrl+F, Ctrl+H).
+
+use leptos::html::{Input, Textarea};
+use leptos::prelude::*;
+
+use super::overlay::Mark;
+use super::pairs::utf16_offset;
+use super::search::{self, SearchOptions};
+use crate::i18n::{self, t, Msg};
+
+#[component]
+pub fn SearchBar(
+    #[prop(into)] code: Signal<String>,
+    open: RwSignal<bool>,
+    /// Show the replace field as well.
+    replacing: RwSignal<bool>,
+    /// Receives the highlights for every match.
+    marks: WriteSignal<Vec<Mark>>,
+    textarea: NodeRef<Textarea>,
+    /// Called with the whole buffer after a replacement.
+    on_replace: Callback<String>,
+    /// Editor line height in pixels, for scrolling matches into view.
+    #[prop(into)]
+    line_height: Signal<u32>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let (query, set_query) = signal(String::new());
+    let (replacement, set_replacement) = signal(String::new());
+    let options = RwSignal::new(SearchOptions::default());
+    let (current, set_current) = signal(0usize);
+    let (note, set_note) = signal::<Option<String>>(None);
+    let find_input = NodeRef::<Input>::new();
+
+    let found = Memo::new(move |_| code.with(|code| search::find_all(code, &query.get(), options.get())));
+    let matches = move || found.with(|f| f.clone().unwrap_or_default());
+
+    Effect::new(move |_| {
+        if !open.get() {
+            marks.set(Vec::new());
+            return;
+        }
+        let current = current.get();
+        marks.set(
+            matches()
+                .into_iter()
+                .enumerate()
+                .map(|(index, range)| Mark {
+                    start: range.start,
+                    end: range.end,
+                    class: if index == current { "search-current" } else { "search-match" },
+                })
+                .collect(),
+        );
+    });
+
+    Effect::new(move |_| {
+        if open.get() {
+            if let Some(input) = find_input.get() {
+                let _ = input.focus();
+                input.select();
+            }
+        }
+    });
+
+    // Select match `index` in the textarea and scroll it into view.
+    let reveal = move |index: usize| {
+        let all = matches();
+        if all.is_empty() {
+            return;
+        }
+        let index = index % all.len();
+        set_current.set(index);
+        let range = &all[index];
+        let Some(textarea) = textarea.get_untracked() else {
+            return;
+        };
+        code.with_untracked(|code| {
+            let _ = textarea.set_selection_range(utf16_offset(code, range.start), utf16_offset(code, range.end));
+            let line = code[..range.start].matches('\n').count() as i32;
+            textarea.set_scroll_top((line * line_height.get_untracked() as i32 - textarea.client_height() / 2).max(0));
+        });
+    };
+    let step = move |forward: bool| {
+        let count = matches().len().max(1);
+        let index = current.get_untracked();
+        reveal(if forward { index + 1 } else { index + count - 1 });
+    };
+
+    let replace_current = move |_| {
+        let Some(range) = matches().get(current.get_untracked()).cloned() else {
+            return;
+        };
+        let replaced = code.with_untracked(|code| {
+            search::replace_one(code, range, &query.get_untracked(), &replacement.get_untracked(), options.get_untracked())
+        });
+        match replaced {
+            Ok(text) => on_replace.run(text),
+            Err(e) => set_note.set(Some(e)),
+        }
+    };
+
+    let replace_all = move |_| {
+        let replaced = code.with_untracked(|code| {
+            search::replace_all(code, &query.get_untracked(), &replacement.get_untracked(), options.get_untracked())
+        });
+        match replaced {
+            Ok((text, count)) => {
+                let lang = lang.get_untracked();
+                set_note.set(Some(match count {
+                    1 => i18n::text(lang, Msg::ReplacedOne).to_string(),
+                    _ => i18n::format(i18n::text(lang, Msg::ReplacedMany), &[&count.to_string()]),
+                }));
+                if count > 0 {
+                    // One buffer change, so one undo step.
+                    on_replace.run(text);
+                }
+            }
+            Err(e) => set_note.set(Some(e)),
+        }
+    };
+
+    let status = move || match found.get() {
+        Err(e) => e,
+        Ok(all) if all.is_empty() => i18n::text(lang.get(), Msg::NoMatches).to_string(),
+        Ok(all) => {
+            let shown = (current.get().min(all.len() - 1) + 1).to_string();
+            i18n::format(i18n::text(lang.get(), Msg::MatchOf), &[&shown, &all.len().to_string()])
+        }
+    };
+
+    let close = move || {
+        open.set(false);
+        set_note.set(None);
+        if let Some(textarea) = textarea.get_untracked() {
+            let _ = textarea.focus();
+        }
+    };
+
+    view! {
+        <Show when=move || open.get()>
+            <div class="search-bar" role="search">
+                <div class="search-row">
+                    <input
+                        type="text"
+                        aria-label=t(Msg::Find)
+                        placeholder=t(Msg::Find)
+                        spellcheck="false"
+                        node_ref=find_input
+                        prop:value=move || query.get()
+                        on:input=move |ev| {
+                            set_query.set(event_target_value(&ev));
+                            set_current.set(0);
+                            set_note.set(None);
+                        }
+                        on:keydown=move |ev: web_sys::KeyboardEvent| match ev.key().as_str() {
+                            "Enter" => {
+                                ev.prevent_default();
+                                step(!ev.shift_key());
+                            }
+                            "Escape" => close(),
+                            _ => {}
+                        }
+                    />
+                    <span class="search-count" role="status">{status}</span>
+                    <button class="secondary" title=t(Msg::PreviousMatch) on:click=move |_| step(false)>
+                        "↑"
+                    </button>
+                    <button class="secondary" title=t(Msg::NextMatch) on:click=move |_| step(true)>
+                        "↓"
+                    </button>
+                    <label class="editor-option" title=t(Msg::RegularExpression)>
+                        <input
+                            type="checkbox"
+                            prop:checked=move || options.with(|o| o.regex)
+                            on:change=move |ev| options.update(|o| o.regex = event_target_checked(&ev))
+                        />
+                        ".*"
+                    </label>
+                    <label class="editor-option" title=t(Msg::MatchCase)>
+                        <input
+                            type="checkbox"
+                            prop:checked=move || options.with(|o| o.case_sensitive)
+                            on:change=move |ev| options.update(|o| o.case_sensitive = event_target_checked(&ev))
+                        />
+                        "Aa"
+                    </label>
+                    <button class="secondary" title=t(Msg::CloseEscape) on:click=move |_| close()>
+                        "✕"
+                    </button>
+                </div>
+                <Show when=move || replacing.get()>
+                    <div class="search-row">
+                        <input
+                            type="text"
+                            aria-label=t(Msg::ReplaceWith)
+                            placeholder=t(Msg::ReplaceWith)
+                            spellcheck="false"
+                            prop:value=move || replacement.get()
+                            on:input=move |ev| set_replacement.set(event_target_value(&ev))
+                            on:keydown=move |ev: web_sys::KeyboardEvent| {
+                                if ev.key() == "Escape" {
+                                    close();
+                                }
+                            }
+                        />
+                        <button class="secondary" on:click=replace_current>{t(Msg::Replace)}</button>
+                        <button class="secondary" on:click=replace_all>{t(Msg::ReplaceAll)}</button>
+                    </div>
+                </Show>
+                {move || note.get().map(|note| view! { <p class="reminder-note">{note}</p> })}
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,148 @@
+//! Embedding the playground in other pages.
+//!
+//! Two entry points are offered once the module is initialised:
+//!
+//! ```html
+//! <simplicity-playground code="fn main() {}" readonly hide-witness></simplicity-playground>
+//! ```
+//!
+//! ```js
+//! mount_playground("#demo", { code: "fn main() {}", readonly: false, hide_witness: true });
+//! ```
+//!
+//! Each instance renders into its own shadow root, so the playground styles
+//! and the host page's styles do not leak into each other. The host element
+//! receives `compiled` and `error` `CustomEvent`s whose `detail` is the
+//! compile result object.
+//!
+//! Pages that embed the playground should not also get the full-page app;
+//! auto-mounting on `<body>` is skipped when the document contains a
+//! `<simplicity-playground>` element or `<body data-simplicity-embed>`.
+
+use leptos::prelude::*;
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::app::App;
+use crate::logging::{self, Category};
+
+pub const ELEMENT_NAME: &str = "simplicity-playground";
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct PlaygroundOptions {
+    pub code: Option<String>,
+    pub readonly: bool,
+    pub hide_witness: bool,
+}
+
+impl PlaygroundOptions {
+    fn from_js(options: &JsValue) -> Result<Self, JsValue> {
+        if options.is_undefined() || options.is_null() {
+            return Ok(Self::default());
+        }
+        let json = js_sys::JSON::stringify(options)?
+            .as_string()
+            .unwrap_or_default();
+        serde_json::from_str(&json).map_err(|e| JsValue::from_str(&format!("Invalid playground options: {e}")))
+    }
+}
+
+#[wasm_bindgen(inline_js = r#"
+export function define_playground_element(name, mount) {
+    if (customElements.get(name)) {
+        return;
+    }
+    customElements.define(name, class extends HTMLElement {
+        connectedCallback() {
+            if (this.shadowRoot) {
+                return;
+            }
+            const code = this.getAttribute('code');
+            mount(this, {
+                code: code !== null ? code : (this.textContent.trim() || null),
+                readonly: this.hasAttribute('readonly'),
+                hide_witness: this.hasAttribute('hide-witness'),
+            });
+        }
+    });
+}
+"#)]
+extern "C" {
+    fn define_playground_element(name: &str, mount: &JsValue);
+}
+
+/// Render a playground into a shadow root attached to `host`.
+fn mount_into(host: web_sys::HtmlElement, options: PlaygroundOptions) -> Result<(), JsValue> {
+    let shadow = host.attach_shadow(&web_sys::ShadowRootInit::new(web_sys::ShadowRootMode::Open))?;
+    let document = web_sys::window()
+        .and_then(|w| w.document())
+        .ok_or_else(|| JsValue::from_str("No document available"))?;
+    let container: web_sys::HtmlElement = document.create_element("div")?.unchecked_into();
+    shadow.append_child(&container)?;
+
+    let events: web_sys::Element = host.into();
+    leptos::mount::mount_to(container, move || {
+        view! {
+            <App
+                initial_code=options.code.clone()
+                readonly=options.readonly
+                hide_witness=options.hide_witness
+                events=events.clone()
+            />
+        }
+    })
+    .forget();
+    Ok(())
+}
+
+/// Mount a playground into the first element matching `selector`.
+///
+/// `options` is an object with optional `code`, `readonly` and
+/// `hide_witness` fields.
+#[wasm_bindgen]
+pub fn mount_playground(selector: &str, options: JsValue) -> Result<(), JsValue> {
+    let options = PlaygroundOptions::from_js(&options)?;
+    let host = web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.query_selector(selector).ok().flatten())
+        .ok_or_else(|| JsValue::from_str(&format!("No element matches `{selector}`")))?;
+    mount_into(host.unchecked_into(), options)
+}
+
+/// Register the `<simplicity-playground>` custom element.
+pub fn define_element() {
+    let mount = Closure::<dyn Fn(web_sys::HtmlElement, JsValue)>::new(|host, options: JsValue| {
+        let result = PlaygroundOptions::from_js(&options).and_then(|options| mount_into(host, options));
+        if let Err(e) = result {
+            logging::error(Category::Ui, format!("Failed to mount playground: {e:?}"));
+        }
+    });
+    define_playground_element(ELEMENT_NAME, &mount.into_js_value());
+}
+
+/// Whether the full-page app should mount itself on `<body>`.
+pub fn should_auto_mount() -> bool {
+    let Some(document) = web_sys::window().and_then(|w| w.document()) else {
+        return false;
+    };
+    let embedded = document.query_selector(ELEMENT_NAME).ok().flatten().is_some()
+        || document
+            .body()
+            .is_some_and(|body| body.has_attribute("data-simplicity-embed"));
+    !embedded
+}
+
+/// Fire a `CustomEvent` named `name` on `target` with `detail_json` parsed
+/// as its detail.
+pub fn dispatch(target: &web_sys::Element, name: &str, detail_json: &str) {
+    let detail = js_sys::JSON::parse(detail_json).unwrap_or(JsValue::NULL);
+    let init = web_sys::CustomEventInit::new();
+    init.set_detail(&detail);
+    init.set_bubbles(true);
+    init.set_composed(true);
+    if let Ok(event) = web_sys::CustomEvent::new_with_event_init_dict(name, &init) {
+        let _ = target.dispatch_event(&event);
+    }
+}
@@ -0,0 +1,175 @@
+//! Longer explanations of common SimplicityHL errors.
+//!
+//! Compiler messages say in one line what went wrong. The catalogue keys the
+//! common ones by fragments of their text and says why they happen and how
+//! they are usually fixed, for the "Learn more" block under a diagnostic.
+//! Messages are matched case-insensitively; an entry applies when all of its
+//! fragments appear, and the first that applies wins, so specific entries
+//! come before general ones.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Explanation {
+    /// Stable name of the entry.
+    pub key: &'static str,
+    /// Lower-case fragments that must all appear in the message.
+    pub fragments: &'static [&'static str],
+    pub title: &'static str,
+    pub explanation: &'static str,
+    pub fix: &'static str,
+}
+
+pub const CATALOGUE: &[Explanation] = &[
+    Explanation {
+        key: "missing-param-module",
+        fragments: &["required module `param` is missing"],
+        title: "The program has no `param` module",
+        explanation: "Parameters are declared in `mod param { ... }`, and the compiler reads their values from there. \
+            A program without parameters still needs the module, even if it is empty.",
+        fix: "Add `mod param {}` at the top of the program, or declare each parameter inside it with a value: \
+            `mod param { const KEY: Pubkey = 0x...; }`.",
+    },
+    Explanation {
+        key: "parameter-missing",
+        fragments: &["parameter `", "missing"],
+        title: "A parameter has no value",
+        explanation: "Every `param::NAME` used in the program must be given a value before the program can be \
+            compiled, because the value becomes part of the program and of its CMR.",
+        fix: "Give the parameter a value in `mod param`, or fill it in from the template or parameter fields.",
+    },
+    Explanation {
+        key: "undefined-function",
+        fragments: &["was called but not defined"],
+        title: "Call to a function that does not exist",
+        explanation: "SimplicityHL only knows functions defined in the same file and the built-in jets; there are \
+            no imports. Function names are case-sensitive.",
+        fix: "Check the spelling, define the function with `fn name(...) -> Type { ... }`, or call a jet as \
+            `jet::name(...)`.",
+    },
+    Explanation {
+        key: "undefined-jet",
+        fragments: &["jet `", "does not exist"],
+        title: "Unknown jet",
+        explanation: "Jets are the built-in operations of Simplicity, such as `jet::sha_256_ctx_8_init` or \
+            `jet::bip_0340_verify`. Names are fixed by the Simplicity specification and written in snake case.",
+        fix: "Apply the suggested fix if the name is a typo; otherwise check the exact name in the Simplicity \
+            jet list. Arithmetic jets carry their width, as in `jet::add_32`.",
+    },
+    Explanation {
+        key: "undefined-variable",
+        fragments: &["is not defined"],
+        title: "Use of a name that is not in scope",
+        explanation: "A variable exists from its `let` to the end of the enclosing block. Parameters are \
+            reached as `param::NAME` and witness values as `witness::NAME`, never by their bare name.",
+        fix: "Check the spelling, move the `let` before the use, or add the `param::` or `witness::` prefix.",
+    },
+    Explanation {
+        key: "type-mismatch",
+        fragments: &["expected expression of type"],
+        title: "An expression has the wrong type",
+        explanation: "SimplicityHL never converts between types implicitly: a `u8` is not a `u32`, and a \
+            function's result must match the type it declares. Integer literals take the type the context \
+            expects, so the mismatch is usually in a variable, a function result or a jet's output.",
+        fix: "Annotate the `let` with the intended type, or convert explicitly, e.g. with `<u8>::into(x)` or a \
+            widening jet such as `jet::left_pad_low_8_32`.",
+    },
+    Explanation {
+        key: "argument-count",
+        fragments: &["expected", "arguments, found"],
+        title: "Wrong number of arguments",
+        explanation: "Functions and jets take a fixed number of arguments. A jet taking a pair or a tuple \
+            still takes its parts as separate arguments.",
+        fix: "Compare the call with the function's signature, or with the jet's type in the Simplicity jet list.",
+    },
+    Explanation {
+        key: "main-signature",
+        fragments: &["main function"],
+        title: "`main` has the wrong shape",
+        explanation: "Every program needs exactly one `fn main()`. It takes no parameters and returns \
+            nothing: its inputs are parameters and witness values, and it succeeds by running to the end.",
+        fix: "Declare it as `fn main() { ... }` and read inputs through `param::` and `witness::`.",
+    },
+    Explanation {
+        key: "redefined",
+        fragments: &["defined multiple times"],
+        title: "Two definitions share a name",
+        explanation: "Function, type alias and module item names must be unique within the program.",
+        fix: "Rename or remove one of the definitions.",
+    },
+    Explanation {
+        key: "witness-type",
+        fragments: &["witness `", "type"],
+        title: "A witness value has the wrong type",
+        explanation: "Each witness has the type of its first use in the program, and every value given for it \
+            must have that type. A value that is the wrong size, such as a 33-byte public key for a `u256`, \
+            does not fit.",
+        fix: "Check the `type` of the value in the witness JSON against the program, or use the witness form, \
+            which takes the types from the program.",
+    },
+    Explanation {
+        key: "witness-outside-main",
+        fragments: &["outside of the `main` function"],
+        title: "Witness read outside `main`",
+        explanation: "Witness values can only be read in `main`, so each has a single place in the program \
+            and a single type.",
+        fix: "Read the witness in `main` and pass it to the function as an argument.",
+    },
+    Explanation {
+        key: "core-target",
+        fragments: &["elements-only"],
+        title: "Elements jet used with the core target",
+        explanation: "The core target leaves out the jets that read the transaction or check timelocks, so the \
+            program stays portable to chains other than Liquid.",
+        fix: "Switch the target back to Elements in the compiler options, or rewrite the check without the jet.",
+    },
+    Explanation {
+        key: "budget-exceeded",
+        fragments: &["budget exceeded"],
+        title: "The program is over its execution limits",
+        explanation: "Before a run, the program's memory and cost are bounded from its types; a program whose \
+            bounds exceed the execution limits is refused without being started. A run that outlasts the \
+            timeout is stopped.",
+        fix: "Raise the limits under Execution limits in the Mock Transaction section, or reduce the work the \
+            program does, e.g. fewer loop iterations or smaller arrays.",
+    },
+    Explanation {
+        key: "invalid-witness-json",
+        fragments: &["invalid json witness data"],
+        title: "The witness is not valid JSON",
+        explanation: "Witness data is a JSON object mapping each witness name to `{\"value\": ..., \"type\": ...}`.",
+        fix: "Check for trailing commas and unquoted names, or clear the witness and use the witness form.",
+    },
+    Explanation {
+        key: "witness-error",
+        fragments: &["witness error"],
+        title: "The witness does not satisfy the program",
+        explanation: "Every witness the program reads must be given a value of its type, and no others. A \
+            missing or extra name, or a value of the wrong type, stops the program from being satisfied.",
+        fix: "Compare the witness names and types with the program; the witness form lists exactly the ones it \
+            reads.",
+    },
+    Explanation {
+        key: "execution-failed",
+        fragments: &["execution error"],
+        title: "The program rejected the transaction",
+        explanation: "The program ran and an `assert!`, a `panic!`, a failed `unwrap` or a jet such as a \
+            signature check stopped it. On chain, this spend would be invalid.",
+        fix: "Trace the run in the Mock Transaction section and use Show in Source to find the failing call; \
+            signatures must be made over the sighash of this exact transaction.",
+    },
+    Explanation {
+        key: "parse-error",
+        fragments: &["parse error"],
+        title: "The source does not parse",
+        explanation: "The text is not valid SimplicityHL. Common causes are a missing `;` after a statement, a \
+            `let` without a type where one is needed, or an unclosed bracket.",
+        fix: "Look at the marked position and the line before it; the last expression of a block has no `;`.",
+    },
+];
+
+/// The catalogue entry for `message`, if it is a known error.
+pub fn explain(message: &str) -> Option<&'static Explanation> {
+    let message = message.to_lowercase();
+    CATALOGUE
+        .iter()
+        .find(|entry| entry.fragments.iter().all(|fragment| message.contains(fragment)))
+}
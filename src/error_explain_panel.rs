@@ -0,0 +1,24 @@
+//! "Learn more" block explaining a known error under its message.
+
+use leptos::prelude::*;
+
+use crate::error_explain;
+use crate::i18n::{self, t, Msg};
+
+/// Nothing for messages the catalogue does not know.
+#[component]
+pub fn LearnMore(#[prop(into)] message: String) -> impl IntoView {
+    let lang = i18n::use_lang();
+    error_explain::explain(&message).map(|entry| {
+        view! {
+            <details class="learn-more" data-error=entry.key>
+                <summary>{move || i18n::format(i18n::text(lang.get(), Msg::LearnMore), &[entry.title])}</summary>
+                <p>{entry.explanation}</p>
+                <p>
+                    <strong>{t(Msg::HowToFix)}</strong>
+                    {entry.fix}
+                </p>
+            </details>
+        }
+    })
+}
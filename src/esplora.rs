@@ -0,0 +1,165 @@
+//! Minimal client for the Esplora REST API.
+//!
+//! Requests go straight from the browser to the configured explorer; nothing
+//! is proxied. Errors are returned as display strings, like the rest of the
+//! app.
+//!
+//! Each network uses its public Blockstream explorer unless an endpoint has
+//! been configured with [`set_endpoint`], e.g. a local Esplora for a regtest
+//! Elements node.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+use crate::address::Network;
+use crate::logging::{self, Category};
+use crate::storage;
+
+const ENDPOINTS_KEY: &str = "esplora.endpoints";
+
+/// Public Blockstream explorer for `network`, if there is one.
+pub fn default_base_url(network: Network) -> Option<&'static str> {
+    match network {
+        Network::Liquid => Some("https://blockstream.info/liquid/api"),
+        Network::LiquidTestnet => Some("https://blockstream.info/liquidtestnet/api"),
+        Network::Elements => None,
+    }
+}
+
+fn configured_endpoints() -> BTreeMap<String, String> {
+    storage::load(ENDPOINTS_KEY)
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// The configured endpoint for `network`, if one was set.
+pub fn configured_endpoint(network: Network) -> Option<String> {
+    configured_endpoints().remove(network.as_str())
+}
+
+/// Use `url` as the explorer for `network`; `None` or a blank URL restores
+/// the default.
+pub fn set_endpoint(network: Network, url: Option<&str>) {
+    let mut endpoints = configured_endpoints();
+    match url.map(str::trim).filter(|url| !url.is_empty()) {
+        Some(url) => endpoints.insert(network.as_str().to_string(), url.trim_end_matches('/').to_string()),
+        None => endpoints.remove(network.as_str()),
+    };
+    if let Ok(json) = serde_json::to_string(&endpoints) {
+        storage::save(ENDPOINTS_KEY, &json);
+    }
+}
+
+/// The explorer to use for `network`: the configured endpoint, else the
+/// public default.
+pub fn base_url(network: Network) -> Option<String> {
+    configured_endpoint(network).or_else(|| default_base_url(network).map(str::to_string))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct TxStatus {
+    pub confirmed: bool,
+    pub block_height: Option<u32>,
+    pub block_time: Option<u64>,
+}
+
+/// An output paying to an address, as listed by `/address/:addr/utxo`.
+///
+/// `value` and `asset` are absent for confidential outputs.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Utxo {
+    pub txid: String,
+    pub vout: u32,
+    pub status: TxStatus,
+    pub value: Option<u64>,
+    pub asset: Option<String>,
+}
+
+/// An output as listed in `/tx/:txid`. `value` and `asset` are absent for
+/// confidential outputs.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TxOut {
+    pub scriptpubkey: String,
+    pub scriptpubkey_address: Option<String>,
+    pub value: Option<u64>,
+    pub asset: Option<String>,
+}
+
+/// The parts of `/tx/:txid` the app uses.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Transaction {
+    pub txid: String,
+    pub locktime: u32,
+    pub vout: Vec<TxOut>,
+    pub status: TxStatus,
+}
+
+async fn get_text(url: &str) -> Result<String, String> {
+    let window = web_sys::window().ok_or("No window available")?;
+    fetch_text(window.fetch_with_str(url), url).await
+}
+
+async fn post_text(url: &str, body: &str) -> Result<String, String> {
+    let window = web_sys::window().ok_or("No window available")?;
+    let init = web_sys::RequestInit::new();
+    init.set_method("POST");
+    init.set_body(&JsValue::from_str(body));
+    fetch_text(window.fetch_with_str_and_init(url, &init), url).await
+}
+
+async fn fetch_text(request: js_sys::Promise, url: &str) -> Result<String, String> {
+    logging::debug(Category::Network, format!("Requesting {url}"));
+    let response = JsFuture::from(request).await.map_err(|e| {
+        let message = format!("Request to {url} failed: {e:?}");
+        logging::warn(Category::Network, message.clone());
+        message
+    })?;
+    let response: web_sys::Response = response
+        .dyn_into()
+        .map_err(|_| "Explorer returned an unexpected response".to_string())?;
+    let text = JsFuture::from(response.text().map_err(|e| format!("{e:?}"))?)
+        .await
+        .map_err(|e| format!("{e:?}"))?
+        .as_string()
+        .unwrap_or_default();
+    if !response.ok() {
+        logging::warn(Category::Network, format!("{url} answered {}", response.status()));
+        return Err(format!("Explorer error {}: {}", response.status(), text.trim()));
+    }
+    Ok(text)
+}
+
+/// List unspent outputs paying to `address`.
+pub async fn address_utxos(base_url: &str, address: &str) -> Result<Vec<Utxo>, String> {
+    let text = get_text(&format!("{}/address/{address}/utxo", base_url.trim_end_matches('/'))).await?;
+    serde_json::from_str(&text).map_err(|e| format!("Invalid explorer response: {e}"))
+}
+
+/// Height of the current chain tip.
+pub async fn tip_height(base_url: &str) -> Result<u32, String> {
+    let text = get_text(&format!("{}/blocks/tip/height", base_url.trim_end_matches('/'))).await?;
+    text.trim().parse().map_err(|e| format!("Invalid explorer response: {e}"))
+}
+
+/// Look up a transaction by id.
+pub async fn transaction(base_url: &str, txid: &str) -> Result<Transaction, String> {
+    let text = get_text(&format!("{}/tx/{txid}", base_url.trim_end_matches('/'))).await?;
+    serde_json::from_str(&text).map_err(|e| format!("Invalid explorer response: {e}"))
+}
+
+/// Raw hex of a transaction.
+pub async fn transaction_hex(base_url: &str, txid: &str) -> Result<String, String> {
+    get_text(&format!("{}/tx/{txid}/hex", base_url.trim_end_matches('/')))
+        .await
+        .map(|hex| hex.trim().to_string())
+}
+
+/// Broadcast a finalized transaction and return its txid.
+pub async fn broadcast(base_url: &str, tx_hex: &str) -> Result<String, String> {
+    post_text(&format!("{}/tx", base_url.trim_end_matches('/')), tx_hex.trim())
+        .await
+        .map(|txid| txid.trim().to_string())
+}
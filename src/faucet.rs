@@ -0,0 +1,66 @@
+//! Test-coin faucet requests for funding freshly derived contracts.
+//!
+//! A faucet is configured as a URL template in which `{address}` is replaced
+//! by the contract address. Public faucets rarely send CORS headers, so the
+//! request is made in `no-cors` mode and its response cannot be read;
+//! whether it worked is judged by the output appearing on the explorer.
+
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::address::Network;
+use crate::storage;
+
+const FAUCET_KEY_PREFIX: &str = "faucet.";
+
+/// Public faucet for `network`, if there is one. Mainnet never has one.
+pub fn default_template(network: Network) -> Option<&'static str> {
+    match network {
+        Network::LiquidTestnet => Some("https://liquidtestnet.com/faucet?address={address}&action=lbtc"),
+        Network::Liquid | Network::Elements => None,
+    }
+}
+
+/// The configured faucet for `network`, else its default.
+pub fn template(network: Network) -> Option<String> {
+    storage::load(&format!("{FAUCET_KEY_PREFIX}{}", network.as_str()))
+        .or_else(|| default_template(network).map(str::to_string))
+}
+
+/// Use `template` as the faucet for `network`; a blank template restores the
+/// default.
+pub fn set_template(network: Network, template: &str) {
+    let key = format!("{FAUCET_KEY_PREFIX}{}", network.as_str());
+    match template.trim() {
+        "" => storage::remove(&key),
+        template => storage::save(&key, template),
+    }
+}
+
+/// Fill the `{address}` placeholder of `template`.
+pub fn faucet_url(template: &str, address: &str) -> Result<String, String> {
+    if !template.contains("{address}") {
+        return Err("The faucet URL must contain an {address} placeholder".to_string());
+    }
+    let encoded = String::from(js_sys::encode_uri_component(address));
+    Ok(template.replace("{address}", &encoded))
+}
+
+/// Ask the faucet behind `template` to pay `address`.
+pub async fn request_funds(template: &str, address: &str) -> Result<(), String> {
+    let url = faucet_url(template, address)?;
+    let window = web_sys::window().ok_or("No window available")?;
+    let init = web_sys::RequestInit::new();
+    init.set_mode(web_sys::RequestMode::NoCors);
+    let response = JsFuture::from(window.fetch_with_str_and_init(&url, &init))
+        .await
+        .map_err(|e| format!("Faucet request to {url} failed: {e:?}"))?;
+    let response: web_sys::Response = response
+        .dyn_into()
+        .map_err(|_| "Faucet returned an unexpected response".to_string())?;
+    // Opaque responses report status 0; anything readable must be a success.
+    if response.type_() != web_sys::ResponseType::Opaque && !response.ok() {
+        return Err(format!("Faucet error {}", response.status()));
+    }
+    Ok(())
+}
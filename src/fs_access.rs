@@ -0,0 +1,99 @@
+//! Native Open and Save dialogs through the File System Access API.
+//!
+//! The handle of the file last opened or saved is remembered for the rest of
+//! the visit, so "Save" writes back to the same `.simf` file without asking
+//! again. Browsers without the API (Firefox, Safari) report
+//! [`supported`] as false and keep drag-and-drop as the only way in.
+
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::logging::js_error;
+
+#[wasm_bindgen(inline_js = r#"
+let current = null;
+const types = [{ description: 'SimplicityHL source', accept: { 'text/plain': ['.simf'] } }];
+async function cancellable(action) {
+    try {
+        return await action();
+    } catch (e) {
+        if (e.name === 'AbortError') return null;
+        throw e;
+    }
+}
+async function write(handle, text) {
+    const writable = await handle.createWritable();
+    await writable.write(text);
+    await writable.close();
+    current = handle;
+    return handle.name;
+}
+export function fs_supported() {
+    return 'showOpenFilePicker' in window && 'showSaveFilePicker' in window;
+}
+export function fs_open() {
+    return cancellable(async () => {
+        const [handle] = await window.showOpenFilePicker({ types });
+        const file = await handle.getFile();
+        const text = await file.text();
+        current = handle;
+        return JSON.stringify({ name: handle.name, text });
+    });
+}
+export function fs_save_as(text, suggested_name) {
+    return cancellable(async () => {
+        const handle = await window.showSaveFilePicker({ types, suggestedName: suggested_name });
+        return write(handle, text);
+    });
+}
+export async function fs_save(text) {
+    if (current && await current.requestPermission({ mode: 'readwrite' }) === 'granted') {
+        return write(current, text);
+    }
+    return null;
+}
+"#)]
+extern "C" {
+    fn fs_supported() -> bool;
+    fn fs_open() -> js_sys::Promise;
+    fn fs_save_as(text: &str, suggested_name: &str) -> js_sys::Promise;
+    fn fs_save(text: &str) -> js_sys::Promise;
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OpenedFile {
+    pub name: String,
+    pub text: String,
+}
+
+/// Whether the browser offers native file dialogs.
+pub fn supported() -> bool {
+    fs_supported()
+}
+
+/// Ask for a file and read it; `None` when the user cancels.
+pub async fn open() -> Result<Option<OpenedFile>, String> {
+    let json = JsFuture::from(fs_open()).await.map_err(js_error)?;
+    match json.as_string() {
+        Some(json) => serde_json::from_str(&json).map(Some).map_err(|e| e.to_string()),
+        None => Ok(None),
+    }
+}
+
+/// Ask where to write `text` and remember the choice. Returns the file name,
+/// or `None` when the user cancels.
+pub async fn save_as(text: &str, suggested_name: &str) -> Result<Option<String>, String> {
+    let name = JsFuture::from(fs_save_as(text, suggested_name)).await.map_err(js_error)?;
+    Ok(name.as_string())
+}
+
+/// Write `text` back to the remembered file, or ask for one as
+/// [`save_as`] does when there is none or write access was refused.
+pub async fn save(text: &str, suggested_name: &str) -> Result<Option<String>, String> {
+    let name = JsFuture::from(fs_save(text)).await.map_err(js_error)?;
+    match name.as_string() {
+        Some(name) => Ok(Some(name)),
+        None => save_as(text, suggested_name).await,
+    }
+}
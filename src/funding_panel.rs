@@ -0,0 +1,169 @@
+//! "Fund contract" flow: faucet request, then a watch on the explorer until
+//! the funding output confirms.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::address::Network;
+use crate::esplora::{self, Utxo};
+use crate::faucet;
+use crate::i18n::{self, t, Lang, Msg};
+use crate::invoice::format_btc;
+
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FundingState {
+    Idle,
+    Requesting,
+    /// Watching the explorer for an output at the address.
+    Waiting,
+    Funded(Utxo),
+}
+
+/// Check the explorer once and move the flow forward.
+fn poll(
+    address: String,
+    network: Network,
+    lang: Lang,
+    state: RwSignal<FundingState>,
+    set_error: WriteSignal<Option<String>>,
+) {
+    let Some(base_url) = esplora::base_url(network) else {
+        set_error.set(Some(i18n::text(lang, Msg::NoExplorer).to_string()));
+        return;
+    };
+    spawn_local(async move {
+        match esplora::address_utxos(&base_url, &address).await {
+            Ok(utxos) => {
+                // Prefer a confirmed output; otherwise show the first one seen.
+                let best = utxos
+                    .iter()
+                    .find(|u| u.status.confirmed)
+                    .or_else(|| utxos.first())
+                    .cloned();
+                if let Some(utxo) = best {
+                    state.set(FundingState::Funded(utxo));
+                }
+            }
+            Err(e) => set_error.set(Some(e)),
+        }
+    });
+}
+
+#[component]
+pub fn FundingPanel(
+    /// Address of the most recently compiled program.
+    #[prop(into)]
+    address: Signal<Option<String>>,
+    #[prop(into)] network: Signal<Network>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let state = RwSignal::new(FundingState::Idle);
+    let (template, set_template) = signal(faucet::template(network.get_untracked()).unwrap_or_default());
+    let (error, set_error) = signal::<Option<String>>(None);
+
+    Effect::new(move |_| {
+        set_template.set(faucet::template(network.get()).unwrap_or_default());
+        address.track();
+        state.set(FundingState::Idle);
+    });
+
+    if let Ok(handle) = set_interval_with_handle(
+        move || {
+            let watching = match state.get_untracked() {
+                FundingState::Waiting => true,
+                FundingState::Funded(utxo) => !utxo.status.confirmed,
+                FundingState::Idle | FundingState::Requesting => false,
+            };
+            if let (true, Some(address)) = (watching, address.get_untracked()) {
+                poll(address, network.get_untracked(), lang.get_untracked(), state, set_error);
+            }
+        },
+        POLL_INTERVAL,
+    ) {
+        on_cleanup(move || handle.clear());
+    }
+
+    let request = move |_| {
+        let Some(address) = address.get_untracked() else {
+            return;
+        };
+        let template = template.get_untracked();
+        faucet::set_template(network.get_untracked(), &template);
+        set_error.set(None);
+        state.set(FundingState::Requesting);
+        spawn_local(async move {
+            match faucet::request_funds(&template, &address).await {
+                Ok(()) => state.set(FundingState::Waiting),
+                Err(e) => {
+                    set_error.set(Some(e));
+                    state.set(FundingState::Idle);
+                }
+            }
+        });
+    };
+
+    let watch = move |_| {
+        let Some(address) = address.get_untracked() else {
+            return;
+        };
+        set_error.set(None);
+        state.set(FundingState::Waiting);
+        poll(address, network.get_untracked(), lang.get_untracked(), state, set_error);
+    };
+
+    let status = move || match state.get() {
+        FundingState::Idle => None,
+        FundingState::Requesting => Some(view! { <p class="network-status">{t(Msg::RequestingFunds)}</p> }.into_any()),
+        FundingState::Waiting => Some(view! { <p class="network-status">{t(Msg::WaitingForFunding)}</p> }.into_any()),
+        FundingState::Funded(utxo) => {
+            let (class, label) = if utxo.status.confirmed {
+                ("status-badge paid", Msg::Confirmed)
+            } else {
+                ("status-badge pending", Msg::Unconfirmed)
+            };
+            let amount = utxo.value.map_or_else(|| i18n::text(lang.get(), Msg::Confidential).to_string(), format_btc);
+            Some(
+                view! {
+                    <div class="output-group">
+                        <div class="section-header">
+                            <span class="output-label">{t(Msg::FundingOutpoint)}</span>
+                            <span class=class>{t(label)}</span>
+                        </div>
+                        <div class="output-box">{format!("{}:{}", utxo.txid, utxo.vout)}</div>
+                        <p class="network-status">{i18n::format(i18n::text(lang.get(), Msg::AmountIs), &[&amount])}</p>
+                    </div>
+                }
+                .into_any(),
+            )
+        }
+    };
+
+    let busy = move || matches!(state.get(), FundingState::Requesting | FundingState::Waiting);
+
+    view! {
+        <div class="funding-panel">
+            <label>{t(Msg::FundContract)}</label>
+            <div class="invoice-form">
+                <input
+                    type="url"
+                    placeholder=t(Msg::FaucetUrlPlaceholder)
+                    prop:value=move || template.get()
+                    on:input=move |ev| set_template.set(event_target_value(&ev))
+                />
+                <button
+                    on:click=request
+                    disabled=move || busy() || address.get().is_none() || template.get().trim().is_empty()
+                >
+                    {t(Msg::RequestTestCoins)}
+                </button>
+                <button class="secondary" on:click=watch disabled=move || busy() || address.get().is_none()>
+                    {t(Msg::WaitForFunding)}
+                </button>
+            </div>
+            {move || error.get().map(|e| view! { <p class="tool-error">{e}</p> })}
+            {status}
+        </div>
+    }
+}
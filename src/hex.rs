@@ -0,0 +1,26 @@
+//! Hex encoding for byte strings shown to and pasted by users.
+
+/// Decode hex, with or without a `0x` prefix.
+pub fn decode(hex: &str) -> Result<Vec<u8>, String> {
+    let hex = hex.trim();
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return Err("Hex must have an even number of digits".to_string());
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            let digits = &hex[i..i + 2];
+            // `from_str_radix` on its own also takes a sign, as in `+f`.
+            if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(format!("Invalid hex: `{digits}`"));
+            }
+            u8::from_str_radix(digits, 16).map_err(|_| format!("Invalid hex: `{digits}`"))
+        })
+        .collect()
+}
+
+/// Lower-case hex without a prefix.
+pub fn encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
@@ -0,0 +1,240 @@
+//! Byte- and bit-level view of an encoded program.
+//!
+//! The dump shows the program as it goes on the wire, without witness data:
+//! offset, hex and ASCII columns, then every node of the bit stream with
+//! where it starts, its bits and what they encode. The annotation comes from
+//! decoding the bytes again: a length prefix, then one entry per node, each a
+//! code prefix (`00000` comp, `11` jet, `10` word, ...) followed by relative
+//! child references as encoded naturals, jet codes, word values or hashes.
+//! If the decoder ever disagrees with the encoder the dump is still shown and
+//! the annotation is replaced by the reason.
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::jet::{Elements, Jet};
+use simplicityhl::simplicity::BitIter;
+
+use crate::options::CompileOptions;
+
+/// Bytes per row of the dump.
+const ROW_BYTES: usize = 16;
+/// Bits printed per node before eliding the rest.
+const MAX_SHOWN_BITS: usize = 64;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct NodeSpan {
+    /// Position in the encoding; children always come before parents.
+    pub index: usize,
+    /// First bit of the node, counted from the start of the program.
+    pub bit_start: usize,
+    pub bit_len: usize,
+    /// The node's bits, elided after [`MAX_SHOWN_BITS`].
+    pub bits: String,
+    /// Combinator name: `comp`, `jet`, `word`, ...
+    pub kind: String,
+    /// Children, jet name or value, e.g. `%3 %5` or `add_32`.
+    pub detail: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct HexDump {
+    pub bytes: Vec<u8>,
+    /// Bits taken by the node count before the first node.
+    pub header_bits: usize,
+    pub nodes: Vec<NodeSpan>,
+    /// Why the bytes could not be annotated, if they could not.
+    pub annotation_error: Option<String>,
+}
+
+/// Reads bits and remembers them for display.
+struct Reader<I: Iterator<Item = u8>> {
+    bits: BitIter<I>,
+    shown: String,
+}
+
+impl<I: Iterator<Item = u8>> Reader<I> {
+    fn position(&self) -> usize {
+        self.bits.n_total_read()
+    }
+
+    fn bit(&mut self) -> Result<bool, String> {
+        let bit = self.bits.next().ok_or("Unexpected end of program")?;
+        self.shown.push(if bit { '1' } else { '0' });
+        Ok(bit)
+    }
+
+    fn bits(&mut self, n: usize) -> Result<u64, String> {
+        (0..n).try_fold(0u64, |acc, _| Ok((acc << 1) | u64::from(self.bit()?)))
+    }
+
+    /// A positive natural: `0` is 1, otherwise `1`, the bit length minus
+    /// one as a natural, then the bits of `n` below its leading one.
+    fn natural(&mut self) -> Result<usize, String> {
+        if !self.bit()? {
+            return Ok(1);
+        }
+        let len = self.natural()?;
+        if len >= usize::BITS as usize {
+            return Err(format!("Natural of {len} bits is too large"));
+        }
+        Ok((1usize << len) | self.bits(len)? as usize)
+    }
+
+    fn hex(&mut self, n_bytes: usize) -> Result<String, String> {
+        (0..n_bytes).map(|_| self.bits(8).map(|b| format!("{b:02x}"))).collect()
+    }
+}
+
+/// The nodes of an encoded program and the length of its header.
+pub fn annotate(bytes: &[u8]) -> Result<(usize, Vec<NodeSpan>), String> {
+    let mut reader = Reader {
+        bits: BitIter::new(bytes.iter().copied()),
+        shown: String::new(),
+    };
+    let count = reader.natural()?;
+    if count > bytes.len() * 8 {
+        return Err(format!("Node count {count} exceeds the program size"));
+    }
+    let header_bits = reader.position();
+    let mut nodes = Vec::with_capacity(count);
+
+    for index in 0..count {
+        reader.shown.clear();
+        let bit_start = reader.position();
+        let child = |reader: &mut Reader<_>| -> Result<String, String> {
+            let offset = reader.natural()?;
+            index
+                .checked_sub(offset)
+                .map(|child| format!("%{child}"))
+                .ok_or_else(|| format!("Node {index} refers {offset} nodes back, before the start"))
+        };
+        let (kind, detail) = match (reader.bit()?, reader.bit()?) {
+            (false, false) => {
+                let kind = ["comp", "case", "pair", "disconnect", "injl", "injr", "take", "drop"][reader.bits(3)? as usize];
+                let left = child(&mut reader)?;
+                let detail = if matches!(kind, "comp" | "case" | "pair" | "disconnect") {
+                    format!("{left} {}", child(&mut reader)?)
+                } else {
+                    left
+                };
+                (kind, detail)
+            }
+            (false, true) => match reader.bit()? {
+                false => match reader.bits(2)? {
+                    0 => ("iden", String::new()),
+                    1 => ("unit", String::new()),
+                    2 => ("fail", format!("entropy {}", reader.hex(64)?)),
+                    _ => return Err(format!("Reserved code at bit {bit_start}")),
+                },
+                true => match reader.bit()? {
+                    false => ("hidden", format!("#{}", reader.hex(32)?)),
+                    true => ("witness", String::new()),
+                },
+            },
+            (true, false) => {
+                let depth = reader.natural()?;
+                if depth > 32 {
+                    return Err(format!("Word of depth {depth} at bit {bit_start}"));
+                }
+                let width = 1usize << (depth - 1);
+                let value = if width < 8 {
+                    format!("0b{:0width$b}", reader.bits(width)?)
+                } else {
+                    format!("0x{}", reader.hex(width / 8)?)
+                };
+                ("word", format!("{width} bits = {value}"))
+            }
+            (true, true) => {
+                let start = reader.position();
+                let jet = Elements::decode(&mut reader.bits).map_err(|e| format!("Bad jet code at bit {start}: {e}"))?;
+                // The library reads the jet code, so only its length is shown.
+                let read = reader.position() - start;
+                reader.shown.push_str(&format!("<{read} jet bits>"));
+                ("jet", jet.to_string())
+            }
+        };
+        let bit_len = reader.position() - bit_start;
+        let mut bits = std::mem::take(&mut reader.shown);
+        if bits.chars().count() > MAX_SHOWN_BITS {
+            bits = bits.chars().take(MAX_SHOWN_BITS).collect::<String>() + "…";
+        }
+        nodes.push(NodeSpan {
+            index,
+            bit_start,
+            bit_len,
+            bits,
+            kind: kind.to_string(),
+            detail,
+        });
+    }
+    Ok((header_bits, nodes))
+}
+
+impl HexDump {
+    /// Dump `bytes`, annotated when they decode.
+    pub fn new(bytes: Vec<u8>) -> Self {
+        match annotate(&bytes) {
+            Ok((header_bits, nodes)) => HexDump {
+                bytes,
+                header_bits,
+                nodes,
+                annotation_error: None,
+            },
+            Err(e) => HexDump {
+                bytes,
+                annotation_error: Some(e),
+                ..HexDump::default()
+            },
+        }
+    }
+
+    /// The nodes that start in row `row`.
+    fn nodes_starting_in(&self, row: usize) -> impl Iterator<Item = &NodeSpan> {
+        let bits = row * ROW_BYTES * 8..(row + 1) * ROW_BYTES * 8;
+        self.nodes.iter().filter(move |node| bits.contains(&node.bit_start))
+    }
+
+    /// The hex rows, then the node table.
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![format!("-- {} bytes, {} nodes", self.bytes.len(), self.nodes.len())];
+        for (row, chunk) in self.bytes.chunks(ROW_BYTES).enumerate() {
+            let hex: Vec<String> = chunk.iter().map(|b| format!("{b:02x}")).collect();
+            let ascii: String = chunk
+                .iter()
+                .map(|&b| if b.is_ascii_graphic() { b as char } else { '.' })
+                .collect();
+            let starts: Vec<String> = self.nodes_starting_in(row).map(|n| format!("%{}", n.index)).collect();
+            let mut line = format!("{:08x}  {:<48} |{ascii:<16}|", row * ROW_BYTES, hex.join(" "));
+            if !starts.is_empty() {
+                line.push_str(&format!("  {}", starts.join(" ")));
+            }
+            lines.push(line);
+        }
+
+        lines.push(String::new());
+        match &self.annotation_error {
+            Some(e) => lines.push(format!("-- structure not annotated: {e}")),
+            None => {
+                lines.push(format!("-- header: node count, bits 0..{}", self.header_bits));
+                for node in &self.nodes {
+                    lines.push(format!(
+                        "%{:<5} {:>4}.{} +{:<5} {:<10} {:<24} {}",
+                        node.index,
+                        node.bit_start / 8,
+                        node.bit_start % 8,
+                        node.bit_len,
+                        node.kind,
+                        node.detail,
+                        node.bits
+                    ));
+                }
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+/// Compile `code` and dump its encoding without witness data.
+pub fn dump(code: &str) -> Result<HexDump, String> {
+    let compiled = CompileOptions::default().compile_source(code, code)?;
+    Ok(HexDump::new(compiled.commit().to_vec_without_witness()))
+}
@@ -0,0 +1,132 @@
+//! Collapsible "Hex dump" section with the annotated program encoding.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::api_types::HexDumpResult;
+use crate::app::{download_bytes, download_text};
+use crate::compiler;
+use crate::hexdump::HexDump;
+use crate::i18n::{self, t, Msg};
+use crate::worker_pool;
+
+#[component]
+pub fn HexDumpPanel(
+    #[prop(into)] code: Signal<String>,
+    /// Witness the exported artifact is satisfied with.
+    #[prop(into)]
+    witness: Signal<String>,
+    /// CMR of the last successful compile; a new one invalidates the dump.
+    #[prop(into)]
+    cmr: Signal<Option<String>>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let (open, set_open) = signal(false);
+    let (dump, set_dump) = signal::<Option<Result<(HexDump, String), String>>>(None);
+    let (export_error, set_export_error) = signal(None::<String>);
+
+    // Only decode while the section is open.
+    Effect::new(move |_| {
+        let _ = cmr.get();
+        if !open.get() {
+            set_dump.set(None);
+            return;
+        }
+        let code = code.get_untracked();
+        spawn_local(async move {
+            let json = compiler::hex_dump(&code).await;
+            set_dump.set(Some(match serde_json::from_str::<HexDumpResult>(&json) {
+                Ok(HexDumpResult { dump: Some(dump), text: Some(text), .. }) => Ok((dump, text)),
+                Ok(HexDumpResult { error, .. }) => Err(error.unwrap_or_default()),
+                Err(e) => Err(e.to_string()),
+            }));
+        });
+    });
+
+    let export_artifact = move |_| {
+        let (code, witness) = (code.get_untracked(), witness.get_untracked());
+        spawn_local(async move {
+            match worker_pool::build_artifact(&code, &witness).await {
+                Ok(artifact) => {
+                    set_export_error.set(None);
+                    download_bytes("simplicity-artifact.bin", "application/octet-stream", &artifact.to_bytes());
+                }
+                Err(e) => set_export_error.set(Some(e)),
+            }
+        });
+    };
+
+    let nodes = move |dump: HexDump| {
+        if let Some(e) = dump.annotation_error {
+            let text = i18n::format(i18n::text(lang.get_untracked(), Msg::StructureNotAnnotated), &[&e]);
+            return view! { <p class="tool-error">{text}</p> }.into_any();
+        }
+        let rows = dump
+            .nodes
+            .into_iter()
+            .map(|node| {
+                let index = node.index;
+                view! {
+                    <tr>
+                        <td>{format!("%{index}")}</td>
+                        <td>{format!("{}.{}", node.bit_start / 8, node.bit_start % 8)}</td>
+                        <td>{node.bit_len}</td>
+                        <td>{node.kind}</td>
+                        <td>{node.detail}</td>
+                        <td class="hexdump-bits">{node.bits}</td>
+                    </tr>
+                }
+            })
+            .collect_view();
+        view! {
+            <table class="bulk-table hexdump-nodes">
+                <thead>
+                    <tr>
+                        <th>{t(Msg::Node)}</th>
+                        <th title=t(Msg::NodeStartTitle)>{t(Msg::At)}</th>
+                        <th>{t(Msg::Bits)}</th>
+                        <th>{t(Msg::Kind)}</th>
+                        <th>{t(Msg::Detail)}</th>
+                        <th>{t(Msg::Encoding)}</th>
+                    </tr>
+                </thead>
+                <tbody>{rows}</tbody>
+            </table>
+        }
+        .into_any()
+    };
+
+    view! {
+        <details
+            class="manifest-editor"
+            on:toggle=move |ev| set_open.set(event_target::<web_sys::Element>(&ev).has_attribute("open"))
+        >
+            <summary>{t(Msg::HexDump)}</summary>
+            {move || match dump.get() {
+                None => view! { <p class="reminder-note">{t(Msg::EncodingProgram)}</p> }.into_any(),
+                Some(Ok((dump, text))) => {
+                    let export = text.clone();
+                    view! {
+                        <pre class="output-box listing">{text.split("\n\n").next().unwrap_or_default().to_string()}</pre>
+                        {nodes(dump)}
+                        <div class="button-group">
+                            <button class="secondary" on:click=move |_| download_text("simplicity-hexdump.txt", &export)>
+                                {t(Msg::ExportDump)}
+                            </button>
+                            <button
+                                class="secondary"
+                                title=t(Msg::ArtifactTitle)
+                                on:click=export_artifact
+                            >
+                                {t(Msg::ExportArtifact)}
+                            </button>
+                        </div>
+                        {move || export_error.get().map(|e| view! { <p class="tool-error">{e}</p> })}
+                    }
+                    .into_any()
+                }
+                Some(Err(e)) => view! { <p class="tool-error">{e}</p> }.into_any(),
+            }}
+        </details>
+    }
+}
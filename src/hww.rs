@@ -0,0 +1,276 @@
+//! Ledger public keys over WebHID.
+//!
+//! APDUs are framed into 64-byte HID reports (channel `0x0101`, tag `0x05`,
+//! big-endian sequence numbers, the first report carrying the total length)
+//! and exchanged with the device through `navigator.hid`. Private keys stay
+//! on the device; the page only sees public keys.
+//!
+//! Public keys are read with the Bitcoin app's `GET_EXTENDED_PUBKEY`, for
+//! use as `Pubkey` values in contracts. No shipped Ledger or Jade app signs
+//! a bare 32-byte sighash, which is what `jet::sig_all_hash` commits a
+//! Simplicity spend to, so signatures are not made here. Jade talks over
+//! Web Serial rather than WebHID and is not supported.
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+use simplicityhl::simplicity::hashes::{sha256d, Hash};
+
+use crate::hex;
+use crate::logging::js_error;
+
+/// Ledger's USB vendor id.
+pub const LEDGER_VENDOR_ID: u16 = 0x2c97;
+
+const CHANNEL: u16 = 0x0101;
+const TAG_APDU: u8 = 0x05;
+const REPORT_SIZE: usize = 64;
+
+/// Class byte of the Bitcoin app (v2 protocol).
+pub const CLA_BITCOIN: u8 = 0xe1;
+const INS_GET_EXTENDED_PUBKEY: u8 = 0x00;
+
+const SW_OK: u16 = 0x9000;
+
+#[wasm_bindgen(inline_js = r#"
+export async function hid_open(vendor_id) {
+    if (!navigator.hid) {
+        throw new Error('WebHID is not available in this browser');
+    }
+    const [device] = await navigator.hid.requestDevice({ filters: [{ vendorId: vendor_id }] });
+    if (!device) {
+        throw new Error('No device selected');
+    }
+    if (!device.opened) {
+        await device.open();
+    }
+    if (!device.__reports) {
+        device.__reports = [];
+        device.__waiters = [];
+        device.addEventListener('inputreport', (event) => {
+            const report = new Uint8Array(event.data.buffer);
+            const waiter = device.__waiters.shift();
+            if (waiter) {
+                waiter(report);
+            } else {
+                device.__reports.push(report);
+            }
+        });
+    }
+    return device;
+}
+export function hid_send(device, report) {
+    return device.sendReport(0, report);
+}
+export function hid_receive(device) {
+    const report = device.__reports.shift();
+    if (report) {
+        return Promise.resolve(report);
+    }
+    return new Promise((resolve) => device.__waiters.push(resolve));
+}
+"#)]
+extern "C" {
+    #[wasm_bindgen(catch)]
+    fn hid_open(vendor_id: u16) -> Result<js_sys::Promise, JsValue>;
+    #[wasm_bindgen(catch)]
+    fn hid_send(device: &JsValue, report: &[u8]) -> Result<js_sys::Promise, JsValue>;
+    fn hid_receive(device: &JsValue) -> js_sys::Promise;
+}
+
+/// Split `apdu` into HID reports.
+pub fn frame(apdu: &[u8]) -> Vec<[u8; REPORT_SIZE]> {
+    let mut payload = Vec::with_capacity(apdu.len() + 2);
+    payload.extend_from_slice(&(apdu.len() as u16).to_be_bytes());
+    payload.extend_from_slice(apdu);
+
+    payload
+        .chunks(REPORT_SIZE - 5)
+        .enumerate()
+        .map(|(sequence, chunk)| {
+            let mut report = [0u8; REPORT_SIZE];
+            report[..2].copy_from_slice(&CHANNEL.to_be_bytes());
+            report[2] = TAG_APDU;
+            report[3..5].copy_from_slice(&(sequence as u16).to_be_bytes());
+            report[5..5 + chunk.len()].copy_from_slice(chunk);
+            report
+        })
+        .collect()
+}
+
+/// Reassembles a response from HID reports.
+#[derive(Debug, Default)]
+pub struct Unframer {
+    expected: Option<usize>,
+    data: Vec<u8>,
+    sequence: u16,
+}
+
+impl Unframer {
+    /// Add a report; returns the full response once it is complete.
+    pub fn push(&mut self, report: &[u8]) -> Result<Option<Vec<u8>>, String> {
+        if report.len() < 5 || report[..2] != CHANNEL.to_be_bytes() || report[2] != TAG_APDU {
+            return Err("Unexpected HID report from device".to_string());
+        }
+        if report[3..5] != self.sequence.to_be_bytes() {
+            return Err("HID reports arrived out of order".to_string());
+        }
+        self.sequence += 1;
+        let mut body = &report[5..];
+        if self.expected.is_none() {
+            if body.len() < 2 {
+                return Err("Truncated HID report".to_string());
+            }
+            self.expected = Some(u16::from_be_bytes([body[0], body[1]]) as usize);
+            body = &body[2..];
+        }
+        let expected = self.expected.unwrap_or_default();
+        let take = body.len().min(expected - self.data.len());
+        self.data.extend_from_slice(&body[..take]);
+        Ok((self.data.len() == expected).then(|| std::mem::take(&mut self.data)))
+    }
+}
+
+/// An opened Ledger device.
+pub struct Ledger {
+    device: JsValue,
+}
+
+/// BIP-32 path like `m/86'/1'/0'/0/0` as child numbers.
+pub fn parse_path(path: &str) -> Result<Vec<u32>, String> {
+    let path = path.trim();
+    let rest = path.strip_prefix("m/").or_else(|| (path == "m").then_some("")).unwrap_or(path);
+    rest.split('/')
+        .filter(|s| !s.is_empty())
+        .map(|step| {
+            let (number, hardened) = match step.strip_suffix(['\'', 'h']) {
+                Some(number) => (number, true),
+                None => (step, false),
+            };
+            let index: u32 = number.parse().map_err(|_| format!("Invalid path step `{step}`"))?;
+            if index >= 1 << 31 {
+                return Err(format!("Path step `{step}` is out of range"));
+            }
+            Ok(if hardened { index | 1 << 31 } else { index })
+        })
+        .collect()
+}
+
+fn encode_path(path: &[u32]) -> Vec<u8> {
+    let mut data = vec![path.len() as u8];
+    for step in path {
+        data.extend_from_slice(&step.to_be_bytes());
+    }
+    data
+}
+
+/// Decode base58check `text`, returning the payload without its checksum.
+fn decode_base58check(text: &str) -> Result<Vec<u8>, String> {
+    const ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+    let mut bytes: Vec<u8> = Vec::new();
+    for c in text.bytes() {
+        let mut carry = ALPHABET
+            .iter()
+            .position(|&a| a == c)
+            .ok_or_else(|| format!("Invalid base58 character `{}`", c as char))? as u32;
+        for byte in bytes.iter_mut().rev() {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.insert(0, carry as u8);
+            carry >>= 8;
+        }
+    }
+    let zeros = text.bytes().take_while(|&c| c == b'1').count();
+    let mut out = vec![0u8; zeros];
+    out.extend(bytes);
+
+    let Some((payload, checksum)) = out.split_last_chunk::<4>() else {
+        return Err("Base58check data is too short for its checksum".to_string());
+    };
+    if sha256d::Hash::hash(payload)[..4] != checksum[..] {
+        return Err("Base58check checksum mismatch".to_string());
+    }
+    Ok(payload.to_vec())
+}
+
+impl Ledger {
+    /// Ask the user to pick a Ledger and open it.
+    pub async fn connect() -> Result<Self, String> {
+        let device = JsFuture::from(hid_open(LEDGER_VENDOR_ID).map_err(js_error)?)
+            .await
+            .map_err(js_error)?;
+        Ok(Ledger { device })
+    }
+
+    /// Send one APDU and return the response data, failing on any status
+    /// word other than `0x9000`.
+    pub async fn exchange(&self, cla: u8, ins: u8, p1: u8, p2: u8, data: &[u8]) -> Result<Vec<u8>, String> {
+        if data.len() > 255 {
+            return Err("APDU data is longer than 255 bytes".to_string());
+        }
+        let mut apdu = vec![cla, ins, p1, p2, data.len() as u8];
+        apdu.extend_from_slice(data);
+        for report in frame(&apdu) {
+            JsFuture::from(hid_send(&self.device, &report).map_err(js_error)?)
+                .await
+                .map_err(js_error)?;
+        }
+
+        let mut unframer = Unframer::default();
+        let response = loop {
+            let report = JsFuture::from(hid_receive(&self.device)).await.map_err(js_error)?;
+            if let Some(response) = unframer.push(&js_sys::Uint8Array::new(&report).to_vec())? {
+                break response;
+            }
+        };
+        let Some((status, body)) = response.split_last_chunk::<2>().map(|(b, s)| (u16::from_be_bytes(*s), b)) else {
+            return Err("Empty response from device".to_string());
+        };
+        if status != SW_OK {
+            return Err(format!("Device refused the request (status {status:04x})"));
+        }
+        Ok(body.to_vec())
+    }
+
+    /// The x-only public key at `path`, as hex.
+    pub async fn xonly_pubkey(&self, path: &str) -> Result<String, String> {
+        let mut data = vec![0u8]; // do not display on the device
+        data.extend(encode_path(&parse_path(path)?));
+        let xpub = self
+            .exchange(CLA_BITCOIN, INS_GET_EXTENDED_PUBKEY, 0, 0, &data)
+            .await?;
+        let xpub = String::from_utf8(xpub).map_err(|_| "Device returned a non-text key".to_string())?;
+        // version 4 | depth 1 | fingerprint 4 | child 4 | chain code 32 | key 33
+        let raw = decode_base58check(&xpub)?;
+        if raw.len() != 78 {
+            return Err("Device returned a malformed extended key".to_string());
+        }
+        Ok(hex::encode(&raw[46..78]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The master key of BIP-32 test vector 1.
+    const XPUB: &str = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+
+    #[test]
+    fn extended_keys_decode_with_their_checksum() {
+        let raw = decode_base58check(XPUB).unwrap();
+        assert_eq!(raw.len(), 78);
+        assert_eq!(
+            hex::encode(&raw[45..]),
+            "0339a36013301597daef41fbe593a02cc513d0b55527ec2df1050e2e8ff49c85c2"
+        );
+
+        let mut corrupted = XPUB.to_string();
+        corrupted.replace_range(20..21, if &XPUB[20..21] == "a" { "b" } else { "a" });
+        assert_eq!(decode_base58check(&corrupted).unwrap_err(), "Base58check checksum mismatch");
+        assert!(decode_base58check("1").is_err());
+    }
+}
@@ -0,0 +1,80 @@
+//! Public keys from a hardware wallet, for use in contracts.
+
+use std::rc::Rc;
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::hww::Ledger;
+use crate::i18n::{self, t, Msg};
+
+#[component]
+pub fn HwwPanel() -> impl IntoView {
+    let lang = i18n::use_lang();
+    let ledger = StoredValue::new_local(None::<Rc<Ledger>>);
+    let (connected, set_connected) = signal(false);
+    let (path, set_path) = signal("m/86'/1'/0'/0/0".to_string());
+    let (pubkey, set_pubkey) = signal::<Option<String>>(None);
+    let (status, set_status) = signal::<Option<Result<String, String>>>(None);
+    let (busy, set_busy) = signal(false);
+
+    let connect = move |_| {
+        set_busy.set(true);
+        spawn_local(async move {
+            match Ledger::connect().await {
+                Ok(device) => {
+                    ledger.set_value(Some(Rc::new(device)));
+                    set_connected.set(true);
+                    set_status.set(Some(Ok(i18n::text(lang.get_untracked(), Msg::LedgerConnected).to_string())));
+                }
+                Err(e) => set_status.set(Some(Err(e))),
+            }
+            set_busy.set(false);
+        });
+    };
+
+    let read_pubkey = move |_| {
+        let Some(device) = ledger.get_value() else {
+            return;
+        };
+        let path = path.get_untracked();
+        set_busy.set(true);
+        spawn_local(async move {
+            match device.xonly_pubkey(&path).await {
+                Ok(key) => set_pubkey.set(Some(key)),
+                Err(e) => set_status.set(Some(Err(e))),
+            }
+            set_busy.set(false);
+        });
+    };
+
+    view! {
+        <div class="hww-panel">
+            <label>{t(Msg::HardwareWallet)}</label>
+            <div class="signature-form">
+                <input
+                    placeholder=t(Msg::DerivationPath)
+                    prop:value=move || path.get()
+                    on:input=move |ev| set_path.set(event_target_value(&ev))
+                />
+            </div>
+            <div class="button-group">
+                <button class="secondary" on:click=connect disabled=move || busy.get()>
+                    {move || i18n::text(lang.get(), if connected.get() { Msg::Reconnect } else { Msg::ConnectLedger })}
+                </button>
+                <button class="secondary" on:click=read_pubkey disabled=move || busy.get() || !connected.get()>
+                    {t(Msg::GetPublicKey)}
+                </button>
+            </div>
+            {move || {
+                pubkey.get().map(|key| view! { <div class="output-box">{key}</div> })
+            }}
+            {move || {
+                status.get().map(|status| match status {
+                    Ok(message) => view! { <p class="manifest-status">{message}</p> }.into_any(),
+                    Err(e) => view! { <p class="manifest-status error">{e}</p> }.into_any(),
+                })
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,1458 @@
+//! UI message catalog and language selection.
+//!
+//! Every label, button, hint and status message of the UI, the shell and
+//! each panel alike, is a [`Msg`]; each language maps messages to text, and
+//! a message a translation lacks falls back to English, so catalogs can grow
+//! one string at a time. The language is the one saved from the picker, else
+//! the browser's preferred language when it is supported, else English. Messages with `{}` take arguments in
+//! order, filled in by [`format`].
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::storage;
+
+const LANGUAGE_KEY: &str = "language";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Lang {
+    #[default]
+    En,
+    Es,
+}
+
+impl Lang {
+    pub const ALL: [Lang; 2] = [Lang::En, Lang::Es];
+
+    /// BCP 47 primary language subtag, as used in `<html lang>`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::En => "en",
+            Lang::Es => "es",
+        }
+    }
+
+    /// The language's name in itself, for the picker.
+    pub fn name(self) -> &'static str {
+        match self {
+            Lang::En => "English",
+            Lang::Es => "Español",
+        }
+    }
+
+    /// The saved choice, else the browser language, else English.
+    pub fn load() -> Lang {
+        storage::load(LANGUAGE_KEY)
+            .and_then(|code| code.parse().ok())
+            .or_else(Lang::detect)
+            .unwrap_or_default()
+    }
+
+    pub fn save(self) {
+        storage::save(LANGUAGE_KEY, self.code());
+    }
+
+    /// The first of the browser's preferred languages this catalog has.
+    pub fn detect() -> Option<Lang> {
+        let navigator = web_sys::window()?.navigator();
+        let preferred = navigator.languages();
+        preferred
+            .iter()
+            .filter_map(|l| l.as_string())
+            .chain(navigator.language())
+            .find_map(|tag| tag.parse().ok())
+    }
+}
+
+impl FromStr for Lang {
+    type Err = String;
+
+    /// Accepts a language tag such as `es-AR`; only the language counts.
+    fn from_str(tag: &str) -> Result<Self, Self::Err> {
+        let primary = tag.split(['-', '_']).next().unwrap_or_default().to_ascii_lowercase();
+        Lang::ALL
+            .into_iter()
+            .find(|l| l.code() == primary)
+            .ok_or_else(|| format!("Unsupported language `{tag}`"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Msg {
+    Title,
+    Tagline,
+    Language,
+    InstallApp,
+    InstallAppTitle,
+    SkipToResults,
+    RestorePrompt,
+    Restore,
+    Discard,
+    CodeLabel,
+    CodeDropHint,
+    CodeDropLabel,
+    ChooseFile,
+    DropHintEditBelow,
+    CodePlaceholder,
+    Compile,
+    Compiling,
+    LoadingCompiler,
+    JetTarget,
+    JetTargetTitle,
+    TargetJets,
+    InsertTemplate,
+    Undo,
+    UndoTitle,
+    Redo,
+    RedoTitle,
+    Open,
+    Save,
+    SaveTo,
+    SaveAs,
+    Clear,
+    ExportSession,
+    ExportProject,
+    ImportSession,
+    Editing,
+    EditorSettings,
+    MatchBrackets,
+    AutoIndent,
+    AutoClose,
+    WitnessLabel,
+    WitnessDropHint,
+    WitnessDropHintBelow,
+    WitnessDropLabel,
+    ChooseWitnessFile,
+    WitnessPlaceholder,
+    EditAsJson,
+    EditAsForm,
+    ClearWitness,
+    ErrorHeading,
+    ResultsRegion,
+    ResultsPlaceholder,
+    CompileSuccess,
+    CompileAnnounced,
+    CmrLabel,
+    AddressLabel,
+    Network,
+    DescriptorLabel,
+    CodeBase64Label,
+    CompiledAtLabel,
+    WitnessInfoLabel,
+    SpendingConditionsLabel,
+    JetUsageLabel,
+    ExportSummary,
+    ExportReport,
+    PrintReport,
+    ViewerLink,
+    ViewerLinkTitle,
+    ViewerNote,
+    FooterRuntime,
+    FooterPrivacy,
+    CodeEmpty,
+    InvalidResponse,
+    UnsupportedFile,
+    ArgumentsApplied,
+    CrashLogOptIn,
+    Settings,
+    ExportDebugLog,
+    // The shell.
+    Untitled,
+    WitnessProcessed,
+    NoWitnessData,
+    NoExampleNamed,
+    NoSavedContract,
+    ReportFailed,
+    ReportFailedWith,
+    ReportNeedsAddress,
+    OpenFileFailed,
+    SaveFileFailed,
+    OpenedFromUrl,
+    // Shared by several panels.
+    CompileFirst,
+    OutputKeyLabel,
+    Apply,
+    Verify,
+    GenerateKey,
+    LineMessage,
+    OpenButton,
+    Close,
+    Remove,
+    Running,
+    // Taproot panel.
+    TaprootSettings,
+    InternalKeyPlaceholder,
+    ExtraLeavesPlaceholder,
+    UseDefaultTree,
+    GeneratedInternalKey,
+    OutputKeyParity,
+    MerkleRootLabel,
+    ControlBlockLabel,
+    VerifyControlBlock,
+    ControlBlockPlaceholder,
+    AddressOrOutputKey,
+    DecodeScriptPubkey,
+    ScriptPubkeyPlaceholder,
+    CommitsToLeaf,
+    NotSimplicityLeaf,
+    NotThisProgram,
+    ThisProgramsOutput,
+    OutputOfProgram,
+    NotCompiledHere,
+    ScriptType,
+    // Error explain panel.
+    LearnMore,
+    HowToFix,
+    // Triage panel.
+    OnLine,
+    TriageScore,
+    LikelyCauses,
+    // Outline panel.
+    OutlineParameter,
+    OutlineFunction,
+    OutlineTypeAlias,
+    OutlineWitness,
+    OutlineItemTitle,
+    Outline,
+    OutlineEmpty,
+    // Spend paths panel.
+    NoConditions,
+    PathNumber,
+    MorePaths,
+    Estimating,
+    EstimateWitnessSize,
+    // Sanity panel.
+    KeysSaved,
+    ReviewBeforeSharing,
+    ShowAddressAnyway,
+    MyKeys,
+    ManifestPlaceholder,
+    SaveKeys,
+    // Pruning panel.
+    PruningSummary,
+    Side,
+    Nodes,
+    PrunedSubtreeCmr,
+    PruningReport,
+    // Recent panel.
+    History,
+    HistoryEmpty,
+    // Signature panel.
+    ValidSignature,
+    SignatureInvalid,
+    NoSignatureValues,
+    ValidFor,
+    MatchesNoKey,
+    SignatureVerification,
+    PubkeyPlaceholder,
+    SighashMessagePlaceholder,
+    SignaturePlaceholder,
+    VerifySignature,
+    VerifyWitnessSignatures,
+    WitnessSignaturesHint,
+    // Template panel.
+    HexKeyOrLabel,
+    HexBytes,
+    Templates,
+    Template,
+    CreateProgram,
+    // Remote panel.
+    OpenFromUrl,
+    RemoteUrlLabel,
+    Fetching,
+    OpenUrl,
+    RemoteHint,
+    // Developer console.
+    DeveloperConsole,
+    LowestLevelShown,
+    CategoryShown,
+    AllCategories,
+    BrowserConsole,
+    ExportLog,
+    ClearLog,
+    LogEntriesKept,
+    // Listing panel.
+    ShowInSource,
+    LowLevelOutput,
+    BuildingListing,
+    ClickNodeHint,
+    // Witness sets panel.
+    WitnessSets,
+    AddWitnessSet,
+    WitnessSetName,
+    RunAllSets,
+    // Multisig panel.
+    AddedSignatureForKey,
+    SignaturesReady,
+    SignaturesOfRequired,
+    KeysHaveSigned,
+    Signed,
+    Waiting,
+    MultisigSigning,
+    SighashPlaceholder,
+    CosignerSignaturePlaceholder,
+    AddSignature,
+    MultisigHint,
+    // Address book panel.
+    AddressBook,
+    ContactLabelPlaceholder,
+    Kind,
+    XonlyKeyPlaceholder,
+    HashPlaceholder,
+    Address,
+    NoteOptional,
+    AddContact,
+    AddressBookHint,
+    // Funding panel.
+    NoExplorer,
+    RequestingFunds,
+    WaitingForFunding,
+    Confirmed,
+    Unconfirmed,
+    Confidential,
+    FundingOutpoint,
+    AmountIs,
+    FundContract,
+    FaucetUrlPlaceholder,
+    RequestTestCoins,
+    WaitForFunding,
+    // Expression playground.
+    Program,
+    ExpressionPlayground,
+    TypePlaceholder,
+    WitnessBindings,
+    Evaluating,
+    Evaluate,
+    ClearHistory,
+    ReplHint,
+    // Hex dump panel.
+    StructureNotAnnotated,
+    Node,
+    NodeStartTitle,
+    At,
+    Bits,
+    Detail,
+    Encoding,
+    HexDump,
+    EncodingProgram,
+    ExportDump,
+    ArtifactTitle,
+    ExportArtifact,
+    // Minimize panel.
+    NoReduction,
+    ReductionSummary,
+    ReductionExhausted,
+    OpenInEditor,
+    Download,
+    MinimizeFailure,
+    FailureToKeep,
+    CompileError,
+    FailedMockRun,
+    ErrorToKeep,
+    ErrorToKeepPlaceholder,
+    Shrinking,
+    Shrink,
+    MinimizeHint,
+    // Vault panel.
+    LabelNamesContact,
+    PrivateKey,
+    PublicKey,
+    Reveal,
+    KeyVault,
+    Passphrase,
+    VaultLabelPlaceholder,
+    KeyToImport,
+    Generate,
+    Import,
+    VaultHint,
+    PrivateKeyLabel,
+    Hide,
+    // Reminder panel.
+    HeightMustBeNumber,
+    PickDateTime,
+    AtHeight,
+    ReminderNumber,
+    TimelockReminders,
+    ReminderLabelPlaceholder,
+    AtBlockHeight,
+    AtDate,
+    BlockHeight,
+    Note,
+    AddReminder,
+    Upcoming,
+    RefreshTips,
+    // Settings panel.
+    SettingsTitle,
+    EditorFontSize,
+    TabWidth,
+    CompileWhileTyping,
+    Theme,
+    DefaultNetwork,
+    CompileWorkers,
+    Automatic,
+    SettingsHint,
+    ResetToDefaults,
+    // Snapshot panel.
+    SavedRevision,
+    UnchangedSinceRevision,
+    RestoreRevision,
+    Snapshots,
+    SnapshotOf,
+    Contract,
+    FilterByTag,
+    AllTags,
+    DeleteHistory,
+    NoRevisions,
+    DiffFromRevision,
+    DiffFrom,
+    DiffToRevision,
+    DiffTo,
+    // Benchmark panel.
+    PhaseCompile,
+    PhaseSatisfy,
+    PhaseExecute,
+    IterationTime,
+    TimePerIteration,
+    PhaseMs,
+    Median,
+    Mean,
+    Min,
+    Max,
+    Performance,
+    RunBenchmark,
+    BenchmarkHint,
+    // Bulk panel.
+    SkippedFile,
+    SourceNotCompiled,
+    Pass,
+    Fail,
+    ErrorResult,
+    Derived,
+    Line,
+    Subject,
+    ExpectedAddress,
+    Result,
+    Details,
+    ExportReportCsv,
+    BulkVerification,
+    BulkDropText,
+    BulkCmrHint,
+    Sources,
+    Verifying,
+    VerifyAll,
+    ClearSources,
+    // Jet usage panel.
+    JetCostsConsensus,
+    JetCostsCustom,
+    JetCostsHint,
+    JetCostTable,
+    ApplyCosts,
+    ConsensusCosts,
+    AllJetsCost,
+    AllJetsCostConsensus,
+    ConsensusCost,
+    CostWas,
+    Jet,
+    Uses,
+    CostWu,
+    TotalWu,
+    // Registry panel.
+    ShowOnlyTag,
+    IndexingContracts,
+    IndexedRevisions,
+    IndexedRevisionsFailed,
+    EditTags,
+    TagsLabel,
+    SaveTags,
+    Cancel,
+    OpenEntry,
+    Registry,
+    SearchRegistry,
+    RegistrySearchPlaceholder,
+    IndexSnapshots,
+    RegistryEmpty,
+    NoRegistryMatch,
+    // Invoice panel.
+    AmountPositive,
+    AssetIdHex,
+    ExpiryPositive,
+    FundingUri,
+    Pending,
+    PaidTx,
+    PaidUnconfirmedTx,
+    Expired,
+    InvoiceNumber,
+    Show,
+    FundingRequest,
+    AmountSat,
+    AssetIdPlaceholder,
+    ExpiresInHours,
+    Label,
+    CreateInvoice,
+    Invoices,
+    CheckPayments,
+    // Network panel.
+    NoExplorerEndpoint,
+    NoUnspentOutputs,
+    ConfirmedAtHeight,
+    TxStatusLocktime,
+    EsploraUrl,
+    SaveEndpoint,
+    UseDefault,
+    ContractUtxos,
+    LookUpUtxos,
+    Transaction,
+    Fetch,
+    BroadcastLabel,
+    FinalizedTxHex,
+    Broadcast,
+    Broadcasted,
+    // Offer panel.
+    AppliedOfferedParams,
+    OfferDoesNotCompile,
+    OfferCmrDiffers,
+    OfferCmrMatches,
+    ParamsMatch,
+    ParamsDiffer,
+    TypeUnknown,
+    IsOfferedContract,
+    NotOfferedContract,
+    SourceHashMatches,
+    SourceHashDiffers,
+    SpendMustProvide,
+    ApplyOfferedParams,
+    ContractOffer,
+    ExportOffer,
+    OfferString,
+    OfferHint,
+    ReceivedOffer,
+    ReceivedOfferPlaceholder,
+    CheckOffer,
+    // MuSig2 panel.
+    StartSessionFirst,
+    ImportedMessages,
+    NonceIn,
+    WaitingForNonce,
+    AggregatedSignature,
+    MusigSigning,
+    ParticipantKeys,
+    ParticipantKeysPlaceholder,
+    AggregatedKey,
+    YourPrivateKey,
+    YourPublicKey,
+    SighashToSign,
+    StartSession,
+    CreatePartial,
+    SendToParticipants,
+    ReceivedRoundMessages,
+    RoundMessagesPlaceholder,
+    ImportMessages,
+    MusigHint,
+    // Test panel.
+    CoverageSummary,
+    CoverageUnknown,
+    PassedFailed,
+    ContractTests,
+    RunningTests,
+    RunTests,
+    Coverage,
+    AddCaseFromWitness,
+    // Verify panel.
+    NotInputIndex,
+    EditorDoesNotCompile,
+    EditorIsOnChain,
+    EditorDifferentCmr,
+    LeafCommitsTo,
+    TransactionTerm,
+    TxInput,
+    OnChain,
+    Compiled,
+    Witness,
+    ByteCount,
+    ExtractedProgram,
+    SourceDoesNotCompile,
+    SourceDifferentCmr,
+    SourceAttestedCmr,
+    SignedBy,
+    SignatureByFails,
+    AttestationHolds,
+    AttestationFails,
+    SameCompiler,
+    OtherCompiler,
+    SourceMatchesCmr,
+    SourceDiffersCmr,
+    Claimed,
+    CmrVerification,
+    ClaimedCmrPlaceholder,
+    SourceToVerify,
+    SourcePlaceholder,
+    CompilingShort,
+    VerifySource,
+    UseEditorCode,
+    VerifyCmrHint,
+    Attestation,
+    SigningKeyPlaceholder,
+    AttestationPlaceholder,
+    AttestEditorCode,
+    CheckAttestation,
+    AttestationHint,
+    OnChainSpend,
+    SpendTxPlaceholder,
+    InputIndexPlaceholder,
+    CompareWithEditor,
+    OnChainHint,
+    // Mock transaction panel.
+    Txid,
+    Vout,
+    Sequence,
+    Asset,
+    ValueSat,
+    ScriptPubkey,
+    AllZeros,
+    ContractAddress,
+    Empty,
+    SpendsInput,
+    TraceSteps,
+    TraceStepsCut,
+    Accepted,
+    Rejected,
+    RunJetCost,
+    RunJetCostCustom,
+    ExecutionTrace,
+    ClickStepHint,
+    JetsOverBudget,
+    ExportTraceJson,
+    ExportTraceText,
+    ProgramAccepts,
+    ShowFailureTitle,
+    ShowFailureInSource,
+    MockTransaction,
+    Version,
+    LockTime,
+    Spent,
+    Value,
+    Output,
+    AddInput,
+    AddOutput,
+    Reset,
+    RunOnMock,
+    TraceExecution,
+    ExecutionLimits,
+    Cells,
+    Frames,
+    CostMwu,
+    TimeoutMs,
+    Defaults,
+    LimitsHint,
+    MockTxHint,
+    // Hardware wallet panel.
+    LedgerConnected,
+    HardwareWallet,
+    DerivationPath,
+    Reconnect,
+    ConnectLedger,
+    GetPublicKey,
+    // Parameter preview.
+    ParameterValue,
+    Set,
+    ParamNotDeclared,
+    // Split pane.
+    ResizePanes,
+    // Witness wizard.
+    HexOrLabelPlaceholder,
+    HexBytesPlaceholder,
+    BitsEncoding,
+    PasteEncodedHex,
+    PickDateInstead,
+    OrHours,
+    Type,
+    InspectBytes,
+    NoWitness,
+    KeyNumber,
+    PublicKeyTitle,
+    PrivateKeyTitle,
+    RandomWitness,
+    Seed,
+    SeedPlaceholder,
+    SighashHexLabel,
+    SighashOptionalPlaceholder,
+    RandomWitnessHint,
+    NoWitnessValues,
+    // Search bar.
+    ReplacedOne,
+    ReplacedMany,
+    NoMatches,
+    MatchOf,
+    Find,
+    PreviousMatch,
+    NextMatch,
+    RegularExpression,
+    MatchCase,
+    CloseEscape,
+    ReplaceWith,
+    Replace,
+    ReplaceAll,
+    // Wizard panel.
+    HexOrLabelShort,
+    ProgramLabel,
+    CmrShortLabel,
+    OpenInEditorButton,
+    ContractWizards,
+    ContractType,
+    StepOf,
+    Back,
+    GenerateCompile,
+    Next,
+}
+
+fn en(msg: Msg) -> &'static str {
+    match msg {
+        Msg::Title => "Simplicity WASM Compiler",
+        Msg::Tagline => "Compile Simplicity smart contracts directly in your browser",
+        Msg::Language => "Language",
+        Msg::InstallApp => "⬇️ Install App",
+        Msg::InstallAppTitle => "Install for offline use",
+        Msg::SkipToResults => "Skip to results",
+        Msg::RestorePrompt => "Restore unsaved work from {}?",
+        Msg::Restore => "↩️ Restore",
+        Msg::Discard => "Discard",
+        Msg::CodeLabel => "SimplicityHL Code",
+        Msg::CodeDropHint => "Drag here to import a .simf file, .json session or .zip project",
+        Msg::CodeDropLabel => "Import a .simf file, .json session or .zip project",
+        Msg::ChooseFile => "📂 Choose file…",
+        Msg::DropHintEditBelow => "or edit directly below",
+        Msg::CodePlaceholder => "Enter Simplicity code here...",
+        Msg::Compile => "🔨 Compile",
+        Msg::Compiling => "⏳ Compiling…",
+        Msg::LoadingCompiler => "⏳ Loading compiler…",
+        Msg::JetTarget => "Jet target",
+        Msg::JetTargetTitle => "Jets the program may use",
+        Msg::TargetJets => "{} jets",
+        Msg::InsertTemplate => "📋 Insert Template",
+        Msg::Undo => "↶ Undo",
+        Msg::UndoTitle => "Undo (Ctrl+Z)",
+        Msg::Redo => "↷ Redo",
+        Msg::RedoTitle => "Redo (Ctrl+Shift+Z)",
+        Msg::Open => "📂 Open…",
+        Msg::Save => "💾 Save",
+        Msg::SaveTo => "Save to {}",
+        Msg::SaveAs => "Save As…",
+        Msg::Clear => "🗑️ Clear",
+        Msg::ExportSession => "💾 Export Session",
+        Msg::ExportProject => "🗜️ Export Project (.zip)",
+        Msg::ImportSession => "📂 Import Session",
+        Msg::Editing => "Editing {}",
+        Msg::EditorSettings => "⚙️ Editor",
+        Msg::MatchBrackets => "Highlight matching brackets",
+        Msg::AutoIndent => "Auto-indent new lines",
+        Msg::AutoClose => "Auto-close brackets and quotes",
+        Msg::WitnessLabel => "Witness Data",
+        Msg::WitnessDropHint => "Drag a witness or arguments file here",
+        Msg::WitnessDropHintBelow => "or paste witness data below",
+        Msg::WitnessDropLabel => "Import a witness or arguments file",
+        Msg::ChooseWitnessFile => "📂 Choose witness file…",
+        Msg::WitnessPlaceholder => "Witness data will appear here...",
+        Msg::EditAsJson => "{ } Edit as JSON",
+        Msg::EditAsForm => "📝 Edit as Form",
+        Msg::ClearWitness => "🗑️ Clear Witness",
+        Msg::ErrorHeading => "⚠️ Error:",
+        Msg::ResultsRegion => "Compilation results",
+        Msg::ResultsPlaceholder => "Compilation results will appear here",
+        Msg::CompileSuccess => "✅ Compilation Successful!",
+        Msg::CompileAnnounced => "Compilation successful. CMR {}",
+        Msg::CmrLabel => "CMR (Commitment Merkle Root):",
+        Msg::AddressLabel => "Address:",
+        Msg::Network => "Network",
+        Msg::DescriptorLabel => "Descriptor:",
+        Msg::CodeBase64Label => "Code (Base64):",
+        Msg::CompiledAtLabel => "Compiled At:",
+        Msg::WitnessInfoLabel => "Witness Information:",
+        Msg::SpendingConditionsLabel => "Spending Conditions:",
+        Msg::JetUsageLabel => "Jet Usage:",
+        Msg::ExportSummary => "📝 Export Text Summary",
+        Msg::ExportReport => "📄 Export Report (HTML)",
+        Msg::PrintReport => "🖨️ Print Report (PDF)",
+        Msg::ViewerLink => "🔗 Viewer Link",
+        Msg::ViewerLinkTitle => "Open a read-only page with this program's results, to share",
+        Msg::ViewerNote => "Viewing a published contract: the results below were compiled in your browser from the source shown. Editing is turned off.",
+        Msg::FooterRuntime => "This is a WebAssembly-based Simplicity compiler running entirely in your browser.",
+        Msg::FooterPrivacy => "No data is sent to any server.",
+        Msg::CodeEmpty => "Code is empty",
+        Msg::InvalidResponse => "Invalid response from compiler",
+        Msg::UnsupportedFile => "Only .simf files, .args arguments, .json sessions and .zip projects are supported",
+        Msg::ArgumentsApplied => "Set {} parameter values from {}",
+        Msg::CrashLogOptIn => "Keep a local crash log to attach to bug reports (never sent anywhere)",
+        Msg::ExportDebugLog => "🐞 Export Debug Log",
+        Msg::Settings => "⚙️ Settings",
+        // The shell.
+        Msg::Untitled => "Untitled",
+        Msg::WitnessProcessed => "Witness processed successfully",
+        Msg::NoWitnessData => "No witness data provided",
+        Msg::NoExampleNamed => "No example named `{}`",
+        Msg::NoSavedContract => "No saved contract named `{}`",
+        Msg::ReportFailed => "Could not build the report",
+        Msg::ReportFailedWith => "Could not build the report: {}",
+        Msg::ReportNeedsAddress => "The report includes the address; acknowledge the sanity findings first",
+        Msg::OpenFileFailed => "Could not open file: {}",
+        Msg::SaveFileFailed => "Could not save file: {}",
+        Msg::OpenedFromUrl => "Opened {} from a URL.",
+        // Shared by several panels.
+        Msg::CompileFirst => "Compile a program first",
+        Msg::OutputKeyLabel => "Output key:",
+        Msg::Apply => "✔ Apply",
+        Msg::Verify => "🔍 Verify",
+        Msg::GenerateKey => "🎲 Generate Key",
+        Msg::LineMessage => "Line {}: {}",
+        Msg::OpenButton => "📂 Open",
+        Msg::Close => "Close",
+        Msg::Remove => "Remove",
+        Msg::Running => "Running…",
+        // Taproot panel.
+        Msg::TaprootSettings => "Taproot Settings",
+        Msg::InternalKeyPlaceholder => "Internal key (hex or @label; blank for the unspendable point)",
+        Msg::ExtraLeavesPlaceholder => "Extra tapscript leaves, one hex script per line",
+        Msg::UseDefaultTree => "↺ Use Default Tree",
+        Msg::GeneratedInternalKey => "Private key for the generated internal key; keep it (e.g. in the Key Vault) to spend by key path: ",
+        Msg::OutputKeyParity => "{} (parity {})",
+        Msg::MerkleRootLabel => "Merkle root:",
+        Msg::ControlBlockLabel => "Control block:",
+        Msg::VerifyControlBlock => "Verify a control block:",
+        Msg::ControlBlockPlaceholder => "Control block (hex)",
+        Msg::AddressOrOutputKey => "Address or output key",
+        Msg::DecodeScriptPubkey => "Decode a script pubkey:",
+        Msg::ScriptPubkeyPlaceholder => "Script pubkey (hex)",
+        Msg::CommitsToLeaf => "✓ Commits to the Simplicity leaf (path of {} hashes)",
+        Msg::NotSimplicityLeaf => "✗ Not a Simplicity leaf version",
+        Msg::NotThisProgram => "✗ Does not commit to this program",
+        Msg::ThisProgramsOutput => "✓ This program's output",
+        Msg::OutputOfProgram => "Output of the program with CMR {}",
+        Msg::NotCompiledHere => "Not a program compiled on this page",
+        Msg::ScriptType => "Type: {}",
+        // Error explain panel.
+        Msg::LearnMore => "Learn more: {}",
+        Msg::HowToFix => "How to fix: ",
+        // Triage panel.
+        Msg::OnLine => " (line {})",
+        Msg::TriageScore => "score {}",
+        Msg::LikelyCauses => "Likely causes",
+        // Outline panel.
+        Msg::OutlineParameter => "parameter",
+        Msg::OutlineFunction => "function",
+        Msg::OutlineTypeAlias => "type alias",
+        Msg::OutlineWitness => "witness",
+        Msg::OutlineItemTitle => "{} on line {}",
+        Msg::Outline => "🗂️ Outline",
+        Msg::OutlineEmpty => "Functions, parameters and witnesses will be listed here.",
+        // Spend paths panel.
+        Msg::NoConditions => "No conditions: anyone who can choose this path can spend",
+        Msg::PathNumber => "Path {}",
+        Msg::MorePaths => "Only the first paths are listed; the program has more.",
+        Msg::Estimating => "Estimating…",
+        Msg::EstimateWitnessSize => "⚖️ Estimate Witness Size",
+        // Sanity panel.
+        Msg::KeysSaved => "Saved {} key(s). Compile again to re-check.",
+        Msg::ReviewBeforeSharing => "Review before sharing this address:",
+        Msg::ShowAddressAnyway => "I understand, show the address",
+        Msg::MyKeys => "My keys ({})",
+        Msg::ManifestPlaceholder => "label: x-only public key hex, one per line",
+        Msg::SaveKeys => "💾 Save Keys",
+        // Pruning panel.
+        Msg::PruningSummary => "{} of {} nodes kept, {} branch(es) pruned, {} kept. Program {} → {} bytes plus {} witness bytes; saves {} WU.",
+        Msg::Side => "Side",
+        Msg::Nodes => "Nodes",
+        Msg::PrunedSubtreeCmr => "Pruned subtree CMR",
+        Msg::PruningReport => "✂️ Pruning Report",
+        // Recent panel.
+        Msg::History => "🕘 History",
+        Msg::HistoryEmpty => "Compiled programs will be listed here.",
+        // Signature panel.
+        Msg::ValidSignature => "✓ Valid BIP-340 signature",
+        Msg::SignatureInvalid => "✗ Signature does not verify",
+        Msg::NoSignatureValues => "The witness has no values typed as signatures.",
+        Msg::ValidFor => "valid for {}",
+        Msg::MatchesNoKey => "matches no program key",
+        Msg::SignatureVerification => "Signature Verification",
+        Msg::PubkeyPlaceholder => "Public key (x-only hex)",
+        Msg::SighashMessagePlaceholder => "Message / sighash (32-byte hex)",
+        Msg::SignaturePlaceholder => "Signature (64-byte hex)",
+        Msg::VerifySignature => "🔏 Verify",
+        Msg::VerifyWitnessSignatures => "Verify All Witness Signatures",
+        Msg::WitnessSignaturesHint => "Witness signatures are checked against the sighash above and every key the program uses.",
+        // Template panel.
+        Msg::HexKeyOrLabel => "0x… (32 bytes) or @label",
+        Msg::HexBytes => "0x… ({} bytes)",
+        Msg::Templates => "🧩 Templates",
+        Msg::Template => "Template",
+        Msg::CreateProgram => "✨ Create Program",
+        // Remote panel.
+        Msg::OpenFromUrl => "🌐 Open from URL",
+        Msg::RemoteUrlLabel => "URL of a .simf file or manifest",
+        Msg::Fetching => "Fetching…",
+        Msg::OpenUrl => "🌐 Open",
+        Msg::RemoteHint => "A raw .simf file, a file page on GitHub, GitLab or Gitea, or a JSON manifest listing several files.",
+        // Developer console.
+        Msg::DeveloperConsole => "Developer Console",
+        Msg::LowestLevelShown => "Lowest level shown",
+        Msg::CategoryShown => "Category shown",
+        Msg::AllCategories => "all categories",
+        Msg::BrowserConsole => "Browser console: ",
+        Msg::ExportLog => "📝 Export Log",
+        Msg::ClearLog => "🗑️ Clear",
+        Msg::LogEntriesKept => "{} entries kept, newest first. Source code is only logged at debug level.",
+        // Listing panel.
+        Msg::ShowInSource => "Show in source",
+        Msg::LowLevelOutput => "Low-level output",
+        Msg::BuildingListing => "Building listing…",
+        Msg::ClickNodeHint => "Click a node to highlight the source it was compiled from.",
+        // Witness sets panel.
+        Msg::WitnessSets => "Witness sets",
+        Msg::AddWitnessSet => "Add a witness set",
+        Msg::WitnessSetName => "Name of the witness set",
+        Msg::RunAllSets => "▶ Run All Sets",
+        // Multisig panel.
+        Msg::AddedSignatureForKey => "Added the signature for key {}",
+        Msg::SignaturesReady => "{} of {} required signatures: ready to spend",
+        Msg::SignaturesOfRequired => "{} of {} required signatures",
+        Msg::KeysHaveSigned => "{} of {} keys have signed",
+        Msg::Signed => "signed",
+        Msg::Waiting => "waiting",
+        Msg::MultisigSigning => "Multisig Signing",
+        Msg::SighashPlaceholder => "Sighash every co-signer signs (32-byte hex)",
+        Msg::CosignerSignaturePlaceholder => "A co-signer's signature (64-byte hex)",
+        Msg::AddSignature => "✍️ Add Signature",
+        Msg::MultisigHint => "Each signature is checked against every key and written into the witness in its key's slot; keys without a signature stay None.",
+        // Address book panel.
+        Msg::AddressBook => "Address Book",
+        Msg::ContactLabelPlaceholder => "Label (e.g. carol)",
+        Msg::Kind => "Kind",
+        Msg::XonlyKeyPlaceholder => "x-only public key (hex)",
+        Msg::HashPlaceholder => "Hash (32-byte hex)",
+        Msg::Address => "Address",
+        Msg::NoteOptional => "Note (optional)",
+        Msg::AddContact => "📇 Add",
+        Msg::AddressBookHint => "Write @label in the program, the witness or a template field to use an entry; fields that take one suggest labels as you type.",
+        // Funding panel.
+        Msg::NoExplorer => "No explorer for this network; configure one in the Network panel",
+        Msg::RequestingFunds => "⏳ Requesting funds…",
+        Msg::WaitingForFunding => "⏳ Waiting for the funding transaction to appear…",
+        Msg::Confirmed => "Confirmed",
+        Msg::Unconfirmed => "Unconfirmed",
+        Msg::Confidential => "confidential",
+        Msg::FundingOutpoint => "Funding outpoint:",
+        Msg::AmountIs => "Amount: {}",
+        Msg::FundContract => "Fund Contract",
+        Msg::FaucetUrlPlaceholder => "Faucet URL with {address}",
+        Msg::RequestTestCoins => "🚰 Request Test Coins",
+        Msg::WaitForFunding => "👀 Wait for Funding",
+        // Expression playground.
+        Msg::Program => "Program",
+        Msg::ExpressionPlayground => "Expression Playground",
+        Msg::TypePlaceholder => "Type, e.g. (bool, u32)",
+        Msg::WitnessBindings => "Witness bindings",
+        Msg::Evaluating => "Evaluating…",
+        Msg::Evaluate => "▶ Evaluate",
+        Msg::ClearHistory => "Clear History",
+        Msg::ReplHint => "Enter evaluates, Shift+Enter adds a line. Give the type to see the value as a SimplicityHL literal.",
+        // Hex dump panel.
+        Msg::StructureNotAnnotated => "Structure not annotated: {}",
+        Msg::Node => "Node",
+        Msg::NodeStartTitle => "Byte.bit where the node starts",
+        Msg::At => "At",
+        Msg::Bits => "Bits",
+        Msg::Detail => "Detail",
+        Msg::Encoding => "Encoding",
+        Msg::HexDump => "Hex dump",
+        Msg::EncodingProgram => "Encoding program…",
+        Msg::ExportDump => "📝 Export Dump",
+        Msg::ArtifactTitle => "CMR, program length, program and witness bytes in one file",
+        Msg::ExportArtifact => "📦 Export Program + Witness (.bin)",
+        // Minimize panel.
+        Msg::NoReduction => "No reduction",
+        Msg::ReductionSummary => "{} of {} lines kept after {} tries, still failing with: {}",
+        Msg::ReductionExhausted => "Stopped at the limit on tries; open the result and shrink it again to go further.",
+        Msg::OpenInEditor => "↩ Open in Editor",
+        Msg::Download => "⬇️ Download",
+        Msg::MinimizeFailure => "✂️ Minimize Failure",
+        Msg::FailureToKeep => "Failure to keep",
+        Msg::CompileError => "Compile error",
+        Msg::FailedMockRun => "Failed run on the mock transaction",
+        Msg::ErrorToKeep => "Error message to keep",
+        Msg::ErrorToKeepPlaceholder => "Error message to keep (blank: the current one)",
+        Msg::Shrinking => "Shrinking…",
+        Msg::Shrink => "✂️ Shrink",
+        Msg::MinimizeHint => "Removes items, statements and blocks while the program still fails with the same message.",
+        // Vault panel.
+        Msg::LabelNamesContact => "`{}` already names an address book entry",
+        Msg::PrivateKey => "🔒 private",
+        Msg::PublicKey => "public",
+        Msg::Reveal => "Reveal",
+        Msg::KeyVault => "Key Vault",
+        Msg::Passphrase => "Passphrase",
+        Msg::VaultLabelPlaceholder => "Label (e.g. alice)",
+        Msg::KeyToImport => "Key to import (hex)",
+        Msg::Generate => "🎲 Generate",
+        Msg::Import => "📥 Import",
+        Msg::VaultHint => "Write @label in the program or witness to use a key. With a passphrase, imported keys are private and stored encrypted; without one, public.",
+        Msg::PrivateKeyLabel => "Private key @{}:",
+        Msg::Hide => "Hide",
+        // Reminder panel.
+        Msg::HeightMustBeNumber => "Block height must be a whole number",
+        Msg::PickDateTime => "Pick a date and time",
+        Msg::AtHeight => "height {}",
+        Msg::ReminderNumber => "Reminder #{}",
+        Msg::TimelockReminders => "Timelock Reminders",
+        Msg::ReminderLabelPlaceholder => "Label (e.g. timeout path opens)",
+        Msg::AtBlockHeight => "At block height",
+        Msg::AtDate => "At date",
+        Msg::BlockHeight => "Block height",
+        Msg::Note => "Note",
+        Msg::AddReminder => "⏰ Add Reminder",
+        Msg::Upcoming => "Upcoming:",
+        Msg::RefreshTips => "🔄 Refresh Tips",
+        // Settings panel.
+        Msg::SettingsTitle => "Settings",
+        Msg::EditorFontSize => "Editor font size (px)",
+        Msg::TabWidth => "Tab width (spaces)",
+        Msg::CompileWhileTyping => "Compile while typing",
+        Msg::Theme => "Theme",
+        Msg::DefaultNetwork => "Default network",
+        Msg::CompileWorkers => "Compile workers",
+        Msg::Automatic => "automatic",
+        Msg::SettingsHint => "The default network applies from the next visit. Workers are used for bulk verification with the lazy-loaded compiler.",
+        Msg::ResetToDefaults => "Reset to Defaults",
+        // Snapshot panel.
+        Msg::SavedRevision => "Saved revision {} of {}",
+        Msg::UnchangedSinceRevision => "{} is unchanged since its last revision",
+        Msg::RestoreRevision => "↩ Restore",
+        Msg::Snapshots => "📸 Snapshots",
+        Msg::SnapshotOf => "📸 Snapshot {}",
+        Msg::Contract => "Contract",
+        Msg::FilterByTag => "Filter by tag",
+        Msg::AllTags => "All tags",
+        Msg::DeleteHistory => "🗑️ Delete History",
+        Msg::NoRevisions => "Saved revisions will be listed here.",
+        Msg::DiffFromRevision => "Diff from revision",
+        Msg::DiffFrom => "Diff from…",
+        Msg::DiffToRevision => "Diff to revision",
+        Msg::DiffTo => "…to",
+        // Benchmark panel.
+        Msg::PhaseCompile => "Compile",
+        Msg::PhaseSatisfy => "Satisfy",
+        Msg::PhaseExecute => "Execute",
+        Msg::IterationTime => "Iteration {}: {} {} ms",
+        Msg::TimePerIteration => "Time per iteration by phase",
+        Msg::PhaseMs => "Phase (ms)",
+        Msg::Median => "Median",
+        Msg::Mean => "Mean",
+        Msg::Min => "Min",
+        Msg::Max => "Max",
+        Msg::Performance => "Performance",
+        Msg::RunBenchmark => "⏱ Run Benchmark",
+        Msg::BenchmarkHint => "Compiles, satisfies with the current witness and executes the program once per iteration. The page is busy while it runs.",
+        // Bulk panel.
+        Msg::SkippedFile => "Skipped `{}`: only .csv and .simf files are used",
+        Msg::SourceNotCompiled => "Source was not compiled",
+        Msg::Pass => "PASS",
+        Msg::Fail => "FAIL",
+        Msg::ErrorResult => "ERROR",
+        Msg::Derived => "derived {}",
+        Msg::Line => "Line",
+        Msg::Subject => "Subject",
+        Msg::ExpectedAddress => "Expected address",
+        Msg::Result => "Result",
+        Msg::Details => "Details",
+        Msg::ExportReportCsv => "📥 Export Report (CSV)",
+        Msg::BulkVerification => "Bulk Address Verification",
+        Msg::BulkDropText => "Drop a .csv address list and the .simf files it names",
+        Msg::BulkCmrHint => "Rows may also give a CMR instead of a file name",
+        Msg::Sources => "Sources: {}",
+        Msg::Verifying => "Verifying…",
+        Msg::VerifyAll => "✅ Verify All",
+        Msg::ClearSources => "🗑️ Clear Sources",
+        // Jet usage panel.
+        Msg::JetCostsConsensus => "Jet costs: consensus",
+        Msg::JetCostsCustom => "Jet costs: custom table",
+        Msg::JetCostsHint => "Milliweight per call by jet name, e.g. {\"sha_256_block\": 900}. Jets left out keep their consensus cost. The table applies to this analysis and to traced runs; budgets are still checked with consensus costs.",
+        Msg::JetCostTable => "Jet cost table (JSON)",
+        Msg::ApplyCosts => "Apply Costs",
+        Msg::ConsensusCosts => "Consensus Costs",
+        Msg::AllJetsCost => "All jets: {} WU",
+        Msg::AllJetsCostConsensus => "All jets: {} WU (consensus: {} WU)",
+        Msg::ConsensusCost => "Consensus: {} WU",
+        Msg::CostWas => "{} (was {})",
+        Msg::Jet => "Jet",
+        Msg::Uses => "Uses",
+        Msg::CostWu => "Cost (WU)",
+        Msg::TotalWu => "Total (WU)",
+        // Registry panel.
+        Msg::ShowOnlyTag => "Show only this tag",
+        Msg::IndexingContracts => "Indexing saved contracts…",
+        Msg::IndexedRevisions => "Indexed {} saved revisions",
+        Msg::IndexedRevisionsFailed => "Indexed {} saved revisions; {} did not compile",
+        Msg::EditTags => "Edit tags",
+        Msg::TagsLabel => "Tags, separated by commas",
+        Msg::SaveTags => "Save Tags",
+        Msg::Cancel => "Cancel",
+        Msg::OpenEntry => "↩ Open",
+        Msg::Registry => "🗂️ Registry",
+        Msg::SearchRegistry => "Search the registry",
+        Msg::RegistrySearchPlaceholder => "Name, CMR prefix or tag:mainnet",
+        Msg::IndexSnapshots => "📥 Index Snapshots",
+        Msg::RegistryEmpty => "Saved programs will be listed here by CMR.",
+        Msg::NoRegistryMatch => "No saved program matches.",
+        // Invoice panel.
+        Msg::AmountPositive => "Amount must be a positive number of satoshis",
+        Msg::AssetIdHex => "Asset id must be 64 hex characters",
+        Msg::ExpiryPositive => "Expiry must be a positive number of hours",
+        Msg::FundingUri => "Funding URI:",
+        Msg::Pending => "Pending",
+        Msg::PaidTx => "Paid · {}…",
+        Msg::PaidUnconfirmedTx => "Paid (unconfirmed) · {}…",
+        Msg::Expired => "Expired",
+        Msg::InvoiceNumber => "Invoice #{}",
+        Msg::Show => "Show",
+        Msg::FundingRequest => "Funding Request",
+        Msg::AmountSat => "Amount (sat)",
+        Msg::AssetIdPlaceholder => "Asset id (default L-BTC)",
+        Msg::ExpiresInHours => "Expires in (hours)",
+        Msg::Label => "Label",
+        Msg::CreateInvoice => "🧾 Create Invoice",
+        Msg::Invoices => "Invoices:",
+        Msg::CheckPayments => "🔄 Check Payments",
+        // Network panel.
+        Msg::NoExplorerEndpoint => "No explorer for this network; configure an endpoint first",
+        Msg::NoUnspentOutputs => "No unspent outputs at this address.",
+        Msg::ConfirmedAtHeight => "Confirmed at height {}",
+        Msg::TxStatusLocktime => "{} · locktime {}",
+        Msg::EsploraUrl => "Esplora API URL",
+        Msg::SaveEndpoint => "💾 Save Endpoint",
+        Msg::UseDefault => "Use Default",
+        Msg::ContractUtxos => "Contract UTXOs:",
+        Msg::LookUpUtxos => "🔍 Look Up UTXOs",
+        Msg::Transaction => "Transaction:",
+        Msg::Fetch => "Fetch",
+        Msg::BroadcastLabel => "Broadcast:",
+        Msg::FinalizedTxHex => "Finalized transaction hex",
+        Msg::Broadcast => "📡 Broadcast",
+        Msg::Broadcasted => "Broadcast: {}",
+        // Offer panel.
+        Msg::AppliedOfferedParams => "Applied {} parameter value(s); check the offer again",
+        Msg::OfferDoesNotCompile => "The editor's code does not compile: {}",
+        Msg::OfferCmrDiffers => "The code compiles to {}, not {}",
+        Msg::OfferCmrMatches => "The code compiles to the offered CMR {}",
+        Msg::ParamsMatch => "Parameter values match",
+        Msg::ParamsDiffer => "Parameters differ: {}",
+        Msg::TypeUnknown => "type unknown",
+        Msg::IsOfferedContract => "This is the offered contract",
+        Msg::NotOfferedContract => "This is not the offered contract",
+        Msg::SourceHashMatches => "Source hash matches",
+        Msg::SourceHashDiffers => "Source hash differs",
+        Msg::SpendMustProvide => "A spend must provide:",
+        Msg::ApplyOfferedParams => "Apply Offered Parameters",
+        Msg::ContractOffer => "Contract Offer",
+        Msg::ExportOffer => "📨 Export Offer",
+        Msg::OfferString => "Offer string",
+        Msg::OfferHint => "The offer holds the source hash, CMR, parameter values and required witness fields, not the source. Send it over any channel; the counterparty checks it against their copy.",
+        Msg::ReceivedOffer => "Received offer",
+        Msg::ReceivedOfferPlaceholder => "Paste a received offer (simoffer1…) to check the editor's code against it",
+        Msg::CheckOffer => "Check Offer",
+        // MuSig2 panel.
+        Msg::StartSessionFirst => "Start a session first",
+        Msg::ImportedMessages => "Imported {} message(s)",
+        Msg::NonceIn => "nonce in",
+        Msg::WaitingForNonce => "waiting for nonce",
+        Msg::AggregatedSignature => "Aggregated signature",
+        Msg::MusigSigning => "MuSig2 Signing",
+        Msg::ParticipantKeys => "Participant public keys",
+        Msg::ParticipantKeysPlaceholder => "Every participant's compressed public key (33-byte hex), one per line, your own included",
+        Msg::AggregatedKey => "Aggregated key for the contract: ",
+        Msg::YourPrivateKey => "Your private key (32-byte hex)",
+        Msg::YourPublicKey => "Your public key: ",
+        Msg::SighashToSign => "Sighash to sign (32-byte hex)",
+        Msg::StartSession => "🤝 Start Session",
+        Msg::CreatePartial => "Create Partial Signature",
+        Msg::SendToParticipants => "Send to the other participants",
+        Msg::ReceivedRoundMessages => "Received round messages",
+        Msg::RoundMessagesPlaceholder => "Paste the others' musig2-nonce / musig2-partial messages, one per line",
+        Msg::ImportMessages => "Import Messages",
+        Msg::MusigHint => "Round 1: everyone sends their nonce. Round 2, once all nonces are in: everyone sends a partial signature. Never restart a session with a nonce already used for a signature.",
+        // Test panel.
+        Msg::CoverageSummary => "{} of {} arms and {} of {} jet calls executed by {} accepted case(s)",
+        Msg::CoverageUnknown => "; {} arm(s) match on computed values and could not be followed",
+        Msg::PassedFailed => "{} passed, {} failed",
+        Msg::ContractTests => "Contract Tests",
+        Msg::RunningTests => "⏳ Running…",
+        Msg::RunTests => "▶ Run Tests",
+        Msg::Coverage => "📊 Coverage",
+        Msg::AddCaseFromWitness => "➕ Add Case from Witness",
+        // Verify panel.
+        Msg::NotInputIndex => "`{}` is not an input index",
+        Msg::EditorDoesNotCompile => "The editor code does not compile: {}",
+        Msg::EditorIsOnChain => "✓ The editor code is the program spent on chain",
+        Msg::EditorDifferentCmr => "✗ The editor code compiles to a different CMR",
+        Msg::LeafCommitsTo => "The leaf script commits to {}, not the program's CMR",
+        Msg::TransactionTerm => "Transaction",
+        Msg::TxInput => "{}, input {}",
+        Msg::OnChain => "On chain",
+        Msg::Compiled => "Compiled",
+        Msg::Witness => "Witness",
+        Msg::ByteCount => "{} bytes",
+        Msg::ExtractedProgram => "Extracted program and witness",
+        Msg::SourceDoesNotCompile => "Source does not compile: {}",
+        Msg::SourceDifferentCmr => "Source compiles to a different CMR: {}",
+        Msg::SourceAttestedCmr => "Source compiles to the attested CMR",
+        Msg::SignedBy => "Signed by {}",
+        Msg::SignatureByFails => "Signature by {} does not verify",
+        Msg::AttestationHolds => "Attestation holds",
+        Msg::AttestationFails => "Attestation does not hold",
+        Msg::SameCompiler => "Same compiler versions",
+        Msg::OtherCompiler => "Attested with other compiler versions",
+        Msg::SourceMatchesCmr => "✓ The source compiles to this CMR",
+        Msg::SourceDiffersCmr => "✗ The source compiles to a different CMR",
+        Msg::Claimed => "Claimed",
+        Msg::CmrVerification => "CMR Verification",
+        Msg::ClaimedCmrPlaceholder => "Claimed CMR (64 hex characters)",
+        Msg::SourceToVerify => "Source to verify",
+        Msg::SourcePlaceholder => "Paste the contract source, parameter values included",
+        Msg::CompilingShort => "Compiling…",
+        Msg::VerifySource => "🔎 Verify",
+        Msg::UseEditorCode => "Use Editor Code",
+        Msg::VerifyCmrHint => "The source is compiled with its own parameter values and compared root for root.",
+        Msg::Attestation => "Reproducibility Attestation",
+        Msg::SigningKeyPlaceholder => "Signing key (optional, 32-byte hex; never stored)",
+        Msg::AttestationPlaceholder => "Paste an attestation JSON to check it against the source above",
+        Msg::AttestEditorCode => "📜 Attest Editor Code",
+        Msg::CheckAttestation => "Check Attestation",
+        Msg::AttestationHint => "An attestation records the source hash, compiler versions, CMR and time, signed with the key if one is given.",
+        Msg::OnChainSpend => "On-chain Spend",
+        Msg::SpendTxPlaceholder => "Paste the hex of a transaction that spends a Simplicity output",
+        Msg::InputIndexPlaceholder => "Input index (blank for the first Simplicity input)",
+        Msg::CompareWithEditor => "⛓️ Compare with Editor Code",
+        Msg::OnChainHint => "The program and witness are read from the input's witness stack and decoded; the decoded program's CMR is compared with the editor code's.",
+        // Mock transaction panel.
+        Msg::Txid => "Txid",
+        Msg::Vout => "Vout",
+        Msg::Sequence => "Sequence",
+        Msg::Asset => "Asset",
+        Msg::ValueSat => "Value (sat)",
+        Msg::ScriptPubkey => "Script pubkey",
+        Msg::AllZeros => "all zeros",
+        Msg::ContractAddress => "contract address",
+        Msg::Empty => "empty",
+        Msg::SpendsInput => "Program spends input {}",
+        Msg::TraceSteps => "{} steps, {}",
+        Msg::TraceStepsCut => "{} steps (cut short), {}",
+        Msg::Accepted => "accepted",
+        Msg::Rejected => "rejected",
+        Msg::RunJetCost => "Jets called in this run cost {} WU.",
+        Msg::RunJetCostCustom => "Jets called in this run cost {} WU with the custom cost table ({} WU at consensus costs).",
+        Msg::ExecutionTrace => "Execution trace: {}",
+        Msg::ClickStepHint => "Click a step to highlight its source.",
+        Msg::JetsOverBudget => "The jets alone exceed the cost limit.",
+        Msg::ExportTraceJson => "Export Trace (JSON)",
+        Msg::ExportTraceText => "Export Trace (Text)",
+        Msg::ProgramAccepts => "The program accepts this transaction",
+        Msg::ShowFailureTitle => "Trace the run and highlight the source of the step that failed",
+        Msg::ShowFailureInSource => "📍 Show in Source",
+        Msg::MockTransaction => "Mock Transaction",
+        Msg::Version => "Version",
+        Msg::LockTime => "Lock time",
+        Msg::Spent => "Spent",
+        Msg::Value => "Value",
+        Msg::Output => "Output",
+        Msg::AddInput => "+ Input",
+        Msg::AddOutput => "+ Output",
+        Msg::Reset => "Reset",
+        Msg::RunOnMock => "▶ Run on Mock Transaction",
+        Msg::TraceExecution => "🧵 Trace Execution",
+        Msg::ExecutionLimits => "Execution limits",
+        Msg::Cells => "Cells",
+        Msg::Frames => "Frames",
+        Msg::CostMwu => "Cost (mWU)",
+        Msg::TimeoutMs => "Timeout (ms)",
+        Msg::Defaults => "Defaults",
+        Msg::LimitsHint => "Programs whose bounds exceed a budget are refused before they run. The timeout applies when the compiler runs in the background.",
+        Msg::MockTxHint => "Runs the program with the current witness as the spender of the selected input. Assets are ids or commitments in hex; inputs without a script spend from the contract's own address.",
+        // Hardware wallet panel.
+        Msg::LedgerConnected => "Ledger connected",
+        Msg::HardwareWallet => "Hardware Wallet",
+        Msg::DerivationPath => "Derivation path",
+        Msg::Reconnect => "🔌 Reconnect",
+        Msg::ConnectLedger => "🔌 Connect Ledger",
+        Msg::GetPublicKey => "🔑 Get Public Key",
+        // Parameter preview.
+        Msg::ParameterValue => "Parameter value",
+        Msg::Set => "Set",
+        Msg::ParamNotDeclared => "Not declared in mod param; it has no value yet.",
+        // Split pane.
+        Msg::ResizePanes => "Resize code and results panes",
+        // Witness wizard.
+        Msg::HexOrLabelPlaceholder => "0x… (32 bytes) or @label",
+        Msg::HexBytesPlaceholder => "0x… ({} bytes)",
+        Msg::BitsEncoding => "{} bits: 0x{}",
+        Msg::PasteEncodedHex => "Paste encoded hex to decode",
+        Msg::PickDateInstead => "Pick a date instead of a height",
+        Msg::OrHours => "or hours",
+        Msg::Type => "Type",
+        Msg::InspectBytes => "Inspect bytes",
+        Msg::NoWitness => "No witness",
+        Msg::KeyNumber => "Key {}",
+        Msg::PublicKeyTitle => "Public key",
+        Msg::PrivateKeyTitle => "Private key",
+        Msg::RandomWitness => "🎲 Random Witness",
+        Msg::Seed => "Seed",
+        Msg::SeedPlaceholder => "Seed, e.g. demo-1",
+        Msg::SighashHexLabel => "Sighash to sign, as hex",
+        Msg::SighashOptionalPlaceholder => "Sighash to sign (hex, optional)",
+        Msg::RandomWitnessHint => "The same seed always gives the same witness. Signatures are made with keys derived from the seed.",
+        Msg::NoWitnessValues => "The program reads no witness values.",
+        // Search bar.
+        Msg::ReplacedOne => "Replaced 1 match",
+        Msg::ReplacedMany => "Replaced {} matches",
+        Msg::NoMatches => "No matches",
+        Msg::MatchOf => "{} of {}",
+        Msg::Find => "Find",
+        Msg::PreviousMatch => "Previous match (Shift+Enter)",
+        Msg::NextMatch => "Next match (Enter)",
+        Msg::RegularExpression => "Regular expression",
+        Msg::MatchCase => "Match case",
+        Msg::CloseEscape => "Close (Escape)",
+        Msg::ReplaceWith => "Replace with",
+        Msg::Replace => "Replace",
+        Msg::ReplaceAll => "Replace All",
+        // Wizard panel.
+        Msg::HexOrLabelShort => "0x… or @label",
+        Msg::ProgramLabel => "Program:",
+        Msg::CmrShortLabel => "CMR:",
+        Msg::OpenInEditorButton => "📝 Open in Editor",
+        Msg::ContractWizards => "🧙 Contract Wizards",
+        Msg::ContractType => "Contract type",
+        Msg::StepOf => "Step {} of {}: {}",
+        Msg::Back => "← Back",
+        Msg::GenerateCompile => "🔨 Generate & Compile",
+        Msg::Next => "Next →",
+    }
+}
+
+fn es(msg: Msg) -> Option<&'static str> {
+    Some(match msg {
+        Msg::Title => "Compilador Simplicity WASM",
+        Msg::Tagline => "Compila contratos inteligentes Simplicity directamente en tu navegador",
+        Msg::Language => "Idioma",
+        Msg::InstallApp => "⬇️ Instalar aplicación",
+        Msg::InstallAppTitle => "Instalar para usarla sin conexión",
+        Msg::SkipToResults => "Saltar a los resultados",
+        Msg::RestorePrompt => "¿Restaurar el trabajo sin guardar del {}?",
+        Msg::Restore => "↩️ Restaurar",
+        Msg::Discard => "Descartar",
+        Msg::CodeLabel => "Código SimplicityHL",
+        Msg::CodeDropHint => "Arrastra aquí un archivo .simf, una sesión .json o un proyecto .zip",
+        Msg::CodeDropLabel => "Importar un archivo .simf, una sesión .json o un proyecto .zip",
+        Msg::ChooseFile => "📂 Elegir archivo…",
+        Msg::DropHintEditBelow => "o edita directamente abajo",
+        Msg::CodePlaceholder => "Escribe aquí el código Simplicity...",
+        Msg::Compile => "🔨 Compilar",
+        Msg::Compiling => "⏳ Compilando…",
+        Msg::LoadingCompiler => "⏳ Cargando el compilador…",
+        Msg::JetTarget => "Conjunto de jets",
+        Msg::JetTargetTitle => "Jets que el programa puede usar",
+        Msg::TargetJets => "jets {}",
+        Msg::InsertTemplate => "📋 Insertar plantilla",
+        Msg::Undo => "↶ Deshacer",
+        Msg::UndoTitle => "Deshacer (Ctrl+Z)",
+        Msg::Redo => "↷ Rehacer",
+        Msg::RedoTitle => "Rehacer (Ctrl+Shift+Z)",
+        Msg::Open => "📂 Abrir…",
+        Msg::Save => "💾 Guardar",
+        Msg::SaveTo => "Guardar en {}",
+        Msg::SaveAs => "Guardar como…",
+        Msg::Clear => "🗑️ Borrar",
+        Msg::ExportSession => "💾 Exportar sesión",
+        Msg::ExportProject => "🗜️ Exportar proyecto (.zip)",
+        Msg::ImportSession => "📂 Importar sesión",
+        Msg::Editing => "Editando {}",
+        Msg::EditorSettings => "⚙️ Editor",
+        Msg::MatchBrackets => "Resaltar el paréntesis correspondiente",
+        Msg::AutoIndent => "Sangría automática en líneas nuevas",
+        Msg::AutoClose => "Cerrar paréntesis y comillas automáticamente",
+        Msg::WitnessLabel => "Datos del testigo",
+        Msg::WitnessDropHint => "Arrastra aquí un archivo de testigo o de argumentos",
+        Msg::WitnessDropHintBelow => "o pega los datos del testigo abajo",
+        Msg::WitnessDropLabel => "Importar un archivo de testigo o de argumentos",
+        Msg::ChooseWitnessFile => "📂 Elegir archivo de testigo…",
+        Msg::WitnessPlaceholder => "Los datos del testigo aparecerán aquí...",
+        Msg::EditAsJson => "{ } Editar como JSON",
+        Msg::EditAsForm => "📝 Editar como formulario",
+        Msg::ClearWitness => "🗑️ Borrar testigo",
+        Msg::ErrorHeading => "⚠️ Error:",
+        Msg::ResultsRegion => "Resultados de la compilación",
+        Msg::ResultsPlaceholder => "Los resultados de la compilación aparecerán aquí",
+        Msg::CompileSuccess => "✅ ¡Compilación correcta!",
+        Msg::CompileAnnounced => "Compilación correcta. CMR {}",
+        Msg::CmrLabel => "CMR (raíz de Merkle de compromiso):",
+        Msg::AddressLabel => "Dirección:",
+        Msg::Network => "Red",
+        Msg::DescriptorLabel => "Descriptor:",
+        Msg::CodeBase64Label => "Código (Base64):",
+        Msg::CompiledAtLabel => "Compilado el:",
+        Msg::WitnessInfoLabel => "Información del testigo:",
+        Msg::SpendingConditionsLabel => "Condiciones de gasto:",
+        Msg::JetUsageLabel => "Uso de jets:",
+        Msg::ExportSummary => "📝 Exportar resumen de texto",
+        Msg::ExportReport => "📄 Exportar informe (HTML)",
+        Msg::PrintReport => "🖨️ Imprimir informe (PDF)",
+        Msg::ViewerLink => "🔗 Enlace de visor",
+        Msg::ViewerLinkTitle => "Abrir una página de solo lectura con los resultados de este programa, para compartir",
+        Msg::ViewerNote => "Viendo un contrato publicado: los resultados de abajo se compilaron en tu navegador a partir del código mostrado. La edición está desactivada.",
+        Msg::FooterRuntime => "Este es un compilador Simplicity basado en WebAssembly que se ejecuta por completo en tu navegador.",
+        Msg::FooterPrivacy => "No se envía ningún dato a ningún servidor.",
+        Msg::CodeEmpty => "El código está vacío",
+        Msg::InvalidResponse => "Respuesta no válida del compilador",
+        Msg::UnsupportedFile => "Solo se admiten archivos .simf, argumentos .args, sesiones .json y proyectos .zip",
+        Msg::ArgumentsApplied => "Se fijaron {} valores de parámetros desde {}",
+        Msg::CrashLogOptIn => "Guardar un registro local de fallos para adjuntar a informes de errores (nunca se envía)",
+        Msg::ExportDebugLog => "🐞 Exportar registro de depuración",
+        Msg::Settings => "⚙️ Ajustes",
+        _ => return None,
+    })
+}
+
+/// The text of `msg` in `lang`, in English when not yet translated.
+pub fn text(lang: Lang, msg: Msg) -> &'static str {
+    match lang {
+        Lang::En => None,
+        Lang::Es => es(msg),
+    }
+    .unwrap_or_else(|| en(msg))
+}
+
+/// `template` with each `{}` replaced by the next of `args`.
+pub fn format(template: &str, args: &[&str]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut rest = template;
+    while let Some(at) = rest.find("{}") {
+        out.push_str(&rest[..at]);
+        out.push_str(args.next().copied().unwrap_or_default());
+        rest = &rest[at + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The language signal the app provides as context.
+#[cfg(feature = "ui")]
+pub fn use_lang() -> leptos::prelude::RwSignal<Lang> {
+    use leptos::prelude::*;
+    use_context::<RwSignal<Lang>>().unwrap_or_else(|| RwSignal::new(Lang::default()))
+}
+
+/// `msg` in the current language, updating when the language changes.
+#[cfg(feature = "ui")]
+pub fn t(msg: Msg) -> impl Fn() -> &'static str + Copy + Send + Sync + 'static {
+    use leptos::prelude::*;
+    let lang = use_lang();
+    move || text(lang.get(), msg)
+}
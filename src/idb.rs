@@ -0,0 +1,95 @@
+//! The playground's IndexedDB database.
+//!
+//! Every store lives in one database, and its schema is declared here once:
+//! opening the database at a new version creates whichever stores and
+//! indexes are missing, so each store's module only names them. Values
+//! cross to JavaScript as JSON.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::logging::js_error;
+
+#[wasm_bindgen(inline_js = r#"
+const STORES = {
+    recent: { options: { keyPath: 'id', autoIncrement: true }, indexes: {} },
+    registry: { options: { keyPath: 'cmr' }, indexes: { tags: { keyPath: 'tags', multiEntry: true } } },
+};
+function open_db() {
+    return new Promise((resolve, reject) => {
+        const request = indexedDB.open('simplicity-wasm', 3);
+        request.onupgradeneeded = () => {
+            const db = request.result;
+            for (const [name, { options, indexes }] of Object.entries(STORES)) {
+                const store = db.objectStoreNames.contains(name)
+                    ? request.transaction.objectStore(name)
+                    : db.createObjectStore(name, options);
+                for (const [index, { keyPath, multiEntry }] of Object.entries(indexes)) {
+                    if (!store.indexNames.contains(index)) {
+                        store.createIndex(index, keyPath, { multiEntry });
+                    }
+                }
+            }
+        };
+        request.onsuccess = () => resolve(request.result);
+        request.onerror = () => reject(request.error);
+    });
+}
+async function run(name, mode, action) {
+    const db = await open_db();
+    return new Promise((resolve, reject) => {
+        const store = db.transaction(name, mode).objectStore(name);
+        const request = action(store);
+        request.onsuccess = () => resolve(request.result);
+        request.onerror = () => reject(request.error);
+    });
+}
+export function idb_put(name, json) {
+    const value = JSON.parse(json);
+    // A missing key lets an auto-increment store pick one.
+    const key = STORES[name].options.keyPath;
+    if (value[key] === null || value[key] === undefined) {
+        delete value[key];
+    }
+    return run(name, 'readwrite', store => store.put(value));
+}
+export async function idb_all(name) {
+    return JSON.stringify(await run(name, 'readonly', store => store.getAll()));
+}
+export async function idb_by_index(name, index, key) {
+    return JSON.stringify(await run(name, 'readonly', store => store.index(index).getAll(key)));
+}
+export function idb_delete(name, key) {
+    return run(name, 'readwrite', store => store.delete(key));
+}
+"#)]
+extern "C" {
+    fn idb_put(name: &str, json: &str) -> js_sys::Promise;
+    fn idb_all(name: &str) -> js_sys::Promise;
+    fn idb_by_index(name: &str, index: &str, key: &str) -> js_sys::Promise;
+    fn idb_delete(name: &str, key: JsValue) -> js_sys::Promise;
+}
+
+/// Add `value` to `store`, replacing the value with the same key.
+pub async fn put<T: Serialize>(store: &str, value: &T) -> Result<(), String> {
+    let json = serde_json::to_string(value).map_err(|e| e.to_string())?;
+    JsFuture::from(idb_put(store, &json)).await.map(|_| ()).map_err(js_error)
+}
+
+/// Every value in `store`, in key order.
+pub async fn all<T: DeserializeOwned>(store: &str) -> Result<Vec<T>, String> {
+    let json = JsFuture::from(idb_all(store)).await.map_err(js_error)?;
+    serde_json::from_str(&json.as_string().unwrap_or_default()).map_err(|e| e.to_string())
+}
+
+/// The values in `store` whose `index` holds `key`.
+pub async fn by_index<T: DeserializeOwned>(store: &str, index: &str, key: &str) -> Result<Vec<T>, String> {
+    let json = JsFuture::from(idb_by_index(store, index, key)).await.map_err(js_error)?;
+    serde_json::from_str(&json.as_string().unwrap_or_default()).map_err(|e| e.to_string())
+}
+
+pub async fn delete(store: &str, key: impl Into<JsValue>) -> Result<(), String> {
+    JsFuture::from(idb_delete(store, key.into())).await.map(|_| ()).map_err(js_error)
+}
@@ -0,0 +1,130 @@
+//! Funding requests for contract addresses.
+//!
+//! An [`Invoice`] bundles the address, amount, asset and expiry into a
+//! wallet-readable URI and QR code. Invoices are kept in localStorage and
+//! marked paid once the explorer lists a matching output.
+
+use serde::{Deserialize, Serialize};
+
+use crate::address::Network;
+use crate::esplora::Utxo;
+use crate::storage;
+use crate::timestamp::Stamp;
+
+const INVOICES_KEY: &str = "invoices";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase", tag = "state")]
+pub enum InvoiceStatus {
+    Pending,
+    Paid { txid: String, confirmed: bool },
+    Expired,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Invoice {
+    pub address: String,
+    pub network: Network,
+    /// Requested amount in satoshis.
+    pub amount_sat: u64,
+    /// Asset id in hex; `None` means the network's policy asset (L-BTC).
+    pub asset: Option<String>,
+    /// Unix time in milliseconds after which the request should not be paid.
+    pub expires_at_ms: Option<u64>,
+    pub label: String,
+    pub created: Stamp,
+    pub status: InvoiceStatus,
+}
+
+/// Format satoshis as a decimal BTC amount with eight places.
+pub fn format_btc(amount_sat: u64) -> String {
+    format!("{}.{:08}", amount_sat / 100_000_000, amount_sat % 100_000_000)
+}
+
+/// Percent-encode a URI query value.
+fn encode_query(value: &str) -> String {
+    String::from(js_sys::encode_uri_component(value))
+}
+
+impl Invoice {
+    /// BIP-21 style URI understood by Liquid wallets.
+    pub fn to_uri(&self) -> String {
+        let mut uri = format!(
+            "{}:{}?amount={}",
+            self.network.uri_scheme(),
+            self.address,
+            format_btc(self.amount_sat)
+        );
+        if let Some(asset) = &self.asset {
+            uri.push_str(&format!("&assetid={asset}"));
+        }
+        if !self.label.is_empty() {
+            uri.push_str(&format!("&label={}", encode_query(&self.label)));
+        }
+        if let Some(expires) = self.expires_at_ms {
+            // BIP-21 has no expiry field; this follows the BOLT-11-like `exp`
+            // convention of seconds since the epoch.
+            uri.push_str(&format!("&exp={}", expires / 1000));
+        }
+        uri
+    }
+
+    /// Render the URI as an SVG QR code.
+    pub fn qr_svg(&self) -> Result<String, String> {
+        use qrcode::render::svg;
+
+        let code = qrcode::QrCode::new(self.to_uri())
+            .map_err(|e| format!("QR encoding failed: {e}"))?;
+        Ok(code
+            .render::<svg::Color>()
+            .min_dimensions(200, 200)
+            .quiet_zone(true)
+            .build())
+    }
+
+    pub fn is_expired(&self, now_ms: u64) -> bool {
+        self.expires_at_ms.is_some_and(|t| now_ms >= t)
+    }
+
+    /// Find an output that settles this invoice.
+    ///
+    /// Confidential outputs hide their amount and asset, so any of them is
+    /// accepted as a possible payment.
+    pub fn find_payment<'a>(&self, utxos: &'a [Utxo]) -> Option<&'a Utxo> {
+        utxos.iter().find(|utxo| match (utxo.value, &utxo.asset) {
+            (Some(value), asset) => {
+                let wanted = self.asset.as_deref().or(self.network.policy_asset());
+                value >= self.amount_sat
+                    && match (wanted, asset) {
+                        (Some(wanted), Some(got)) => wanted.eq_ignore_ascii_case(got),
+                        _ => true,
+                    }
+            }
+            (None, _) => true,
+        })
+    }
+
+    /// Move the status forward given the explorer's view of the address.
+    pub fn update_status(&mut self, utxos: &[Utxo], now_ms: u64) {
+        if let Some(utxo) = self.find_payment(utxos) {
+            self.status = InvoiceStatus::Paid {
+                txid: utxo.txid.clone(),
+                confirmed: utxo.status.confirmed,
+            };
+        } else if self.is_expired(now_ms) {
+            self.status = InvoiceStatus::Expired;
+        }
+    }
+}
+
+pub fn load_invoices() -> Vec<Invoice> {
+    storage::load(INVOICES_KEY)
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_invoices(invoices: &[Invoice]) {
+    if let Ok(json) = serde_json::to_string(invoices) {
+        storage::save(INVOICES_KEY, &json);
+    }
+}
@@ -0,0 +1,250 @@
+//! Funding request form and payment monitor.
+//!
+//! Pending invoices are re-checked against the explorer on an interval;
+//! invoices on networks without an explorer only track expiry.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::address::Network;
+use crate::esplora;
+use crate::i18n::{self, t, Msg};
+use crate::invoice::{format_btc, load_invoices, save_invoices, Invoice, InvoiceStatus};
+use crate::timestamp::Stamp;
+
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+const MS_PER_HOUR: f64 = 3_600_000.0;
+
+fn is_asset_id(value: &str) -> bool {
+    value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Re-check every pending invoice and persist any status change.
+fn refresh(invoices: RwSignal<Vec<Invoice>>, set_monitor_error: WriteSignal<Option<String>>) {
+    let pending: Vec<(usize, String, Network)> = invoices.with_untracked(|list| {
+        list.iter()
+            .enumerate()
+            .filter(|(_, invoice)| invoice.status == InvoiceStatus::Pending)
+            .map(|(index, invoice)| (index, invoice.address.clone(), invoice.network))
+            .collect()
+    });
+
+    for (index, address, network) in pending {
+        let apply = {
+            let address = address.clone();
+            move |utxos: &[esplora::Utxo]| {
+                let now = js_sys::Date::now() as u64;
+                invoices.update(|list| {
+                    // The list may have been edited while the request was in flight.
+                    if let Some(invoice) = list.get_mut(index).filter(|i| i.address == address) {
+                        invoice.update_status(utxos, now);
+                    }
+                });
+                invoices.with_untracked(|list| save_invoices(list));
+            }
+        };
+
+        let Some(base_url) = esplora::base_url(network) else {
+            apply(&[]);
+            continue;
+        };
+        spawn_local(async move {
+            match esplora::address_utxos(&base_url, &address).await {
+                Ok(utxos) => {
+                    set_monitor_error.set(None);
+                    apply(&utxos);
+                }
+                Err(e) => set_monitor_error.set(Some(e)),
+            }
+        });
+    }
+}
+
+#[component]
+pub fn InvoicePanel(
+    /// Address of the most recently compiled program.
+    #[prop(into)]
+    address: Signal<Option<String>>,
+    #[prop(into)] network: Signal<Network>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let invoices = RwSignal::new(load_invoices());
+    let (amount, set_amount) = signal(String::new());
+    let (asset, set_asset) = signal(String::new());
+    let (expiry_hours, set_expiry_hours) = signal("24".to_string());
+    let (label, set_label) = signal(String::new());
+    let (form_error, set_form_error) = signal::<Option<String>>(None);
+    let (monitor_error, set_monitor_error) = signal::<Option<String>>(None);
+    let (shown, set_shown) = signal::<Option<usize>>(None);
+
+    if let Ok(handle) =
+        set_interval_with_handle(move || refresh(invoices, set_monitor_error), POLL_INTERVAL)
+    {
+        on_cleanup(move || handle.clear());
+    }
+
+    let create = move |_| {
+        let Some(address) = address.get_untracked() else {
+            return;
+        };
+        let amount_sat = match amount.get_untracked().trim().parse::<u64>() {
+            Ok(value) if value > 0 => value,
+            _ => {
+                set_form_error.set(Some(i18n::text(lang.get_untracked(), Msg::AmountPositive).to_string()));
+                return;
+            }
+        };
+        let asset = asset.get_untracked().trim().to_lowercase();
+        let asset = if asset.is_empty() {
+            None
+        } else if is_asset_id(&asset) {
+            Some(asset)
+        } else {
+            set_form_error.set(Some(i18n::text(lang.get_untracked(), Msg::AssetIdHex).to_string()));
+            return;
+        };
+        let now = js_sys::Date::now();
+        let expires_at_ms = match expiry_hours.get_untracked().trim() {
+            "" => None,
+            hours => match hours.parse::<f64>() {
+                Ok(hours) if hours > 0.0 => Some((now + hours * MS_PER_HOUR) as u64),
+                _ => {
+                    set_form_error.set(Some(i18n::text(lang.get_untracked(), Msg::ExpiryPositive).to_string()));
+                    return;
+                }
+            },
+        };
+
+        set_form_error.set(None);
+        invoices.update(|list| {
+            list.push(Invoice {
+                address,
+                network: network.get_untracked(),
+                amount_sat,
+                asset,
+                expires_at_ms,
+                label: label.get_untracked().trim().to_string(),
+                created: Stamp::now(),
+                status: InvoiceStatus::Pending,
+            });
+            set_shown.set(Some(list.len() - 1));
+        });
+        invoices.with_untracked(|list| save_invoices(list));
+    };
+
+    let remove = move |index: usize| {
+        invoices.update(|list| {
+            if index < list.len() {
+                list.remove(index);
+            }
+        });
+        invoices.with_untracked(|list| save_invoices(list));
+        set_shown.set(None);
+    };
+
+    let detail = move || {
+        let invoice = shown.get().and_then(|i| invoices.with(|list| list.get(i).cloned()))?;
+        let uri = invoice.to_uri();
+        let qr = match invoice.qr_svg() {
+            Ok(svg) => view! { <div class="invoice-qr" inner_html=svg></div> }.into_any(),
+            Err(e) => view! { <p class="tool-error">{e}</p> }.into_any(),
+        };
+        Some(view! {
+            <div class="invoice-detail">
+                {qr}
+                <span class="output-label">{t(Msg::FundingUri)}</span>
+                <div class="output-box">
+                    <a href=uri.clone()>{uri}</a>
+                </div>
+            </div>
+        })
+    };
+
+    let rows = move || {
+        invoices
+            .get()
+            .into_iter()
+            .enumerate()
+            .map(|(index, invoice)| {
+                let lang = lang.get();
+                let (class, status) = match &invoice.status {
+                    InvoiceStatus::Pending => ("status-badge pending", i18n::text(lang, Msg::Pending).to_string()),
+                    InvoiceStatus::Paid { txid, confirmed } => (
+                        "status-badge paid",
+                        i18n::format(
+                            i18n::text(lang, if *confirmed { Msg::PaidTx } else { Msg::PaidUnconfirmedTx }),
+                            &[&txid[..txid.len().min(12)]],
+                        ),
+                    ),
+                    InvoiceStatus::Expired => ("status-badge expired", i18n::text(lang, Msg::Expired).to_string()),
+                };
+                let label = if invoice.label.is_empty() {
+                    i18n::format(i18n::text(lang, Msg::InvoiceNumber), &[&invoice.created.sequence.to_string()])
+                } else {
+                    invoice.label.clone()
+                };
+                view! {
+                    <li class="invoice-row">
+                        <span class="invoice-label" title=invoice.address.clone()>{label}</span>
+                        <span class="invoice-amount">{format_btc(invoice.amount_sat)}</span>
+                        <span class=class>{status}</span>
+                        <button class="secondary" on:click=move |_| set_shown.set(Some(index))>
+                            {t(Msg::Show)}
+                        </button>
+                        <button class="danger" on:click=move |_| remove(index)>
+                            {t(Msg::Remove)}
+                        </button>
+                    </li>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <div class="invoice-panel">
+            <label>{t(Msg::FundingRequest)}</label>
+            <div class="invoice-form">
+                <input
+                    type="number"
+                    min="1"
+                    placeholder=t(Msg::AmountSat)
+                    prop:value=move || amount.get()
+                    on:input=move |ev| set_amount.set(event_target_value(&ev))
+                />
+                <input
+                    type="text"
+                    placeholder=t(Msg::AssetIdPlaceholder)
+                    prop:value=move || asset.get()
+                    on:input=move |ev| set_asset.set(event_target_value(&ev))
+                />
+                <input
+                    type="number"
+                    min="0"
+                    step="any"
+                    placeholder=t(Msg::ExpiresInHours)
+                    prop:value=move || expiry_hours.get()
+                    on:input=move |ev| set_expiry_hours.set(event_target_value(&ev))
+                />
+                <input
+                    type="text"
+                    placeholder=t(Msg::Label)
+                    prop:value=move || label.get()
+                    on:input=move |ev| set_label.set(event_target_value(&ev))
+                />
+                <button on:click=create disabled=move || address.get().is_none()>
+                    {t(Msg::CreateInvoice)}
+                </button>
+            </div>
+            {move || form_error.get().map(|e| view! { <p class="tool-error">{e}</p> })}
+            {detail}
+            <div class="section-header">
+                <span class="output-label">{t(Msg::Invoices)}</span>
+                <button class="secondary" on:click=move |_| refresh(invoices, set_monitor_error)>
+                    {t(Msg::CheckPayments)}
+                </button>
+            </div>
+            {move || monitor_error.get().map(|e| view! { <p class="tool-error">{e}</p> })}
+            <ul class="invoice-list">{rows}</ul>
+        </div>
+    }
+}
@@ -0,0 +1,107 @@
+//! Alternative jet costs, for weighing proposed consensus changes.
+//!
+//! A cost table maps jet names to a cost of one call in milliweight, as
+//! passed from JavaScript:
+//!
+//! ```json
+//! {"sha_256_block": 900, "bip_0340_verify": 45000}
+//! ```
+//!
+//! Jets left out keep their consensus cost, so a table only lists what it
+//! changes; an empty table is the consensus one. The jet usage analysis and
+//! the costs of traced runs read the table. Static budgets are still
+//! checked with consensus costs, since they are what the chain enforces.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::jet::{Elements, Jet};
+
+use crate::quick_fix::similar_jets;
+use crate::storage;
+use crate::trace::Trace;
+
+const COSTS_KEY: &str = "jet-costs";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct CostTable {
+    /// Jet name → cost of one call, in milliweight.
+    pub costs: BTreeMap<String, u64>,
+}
+
+/// The consensus cost of the jet named `name`, if there is one.
+pub fn consensus_cost(name: &str) -> Option<u64> {
+    Elements::ALL
+        .iter()
+        .find(|jet| jet.to_string() == name)
+        .map(|jet| u64::from(u32::from(jet.cost())))
+}
+
+impl CostTable {
+    pub fn load() -> Self {
+        storage::load(COSTS_KEY)
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if self.costs.is_empty() {
+            storage::remove(COSTS_KEY);
+        } else if let Ok(json) = serde_json::to_string(self) {
+            storage::save(COSTS_KEY, &json);
+        }
+    }
+
+    /// Read a table from JSON; a blank string gives the consensus table.
+    /// Unknown jet names are refused, with the nearest real ones.
+    pub fn parse(json: &str) -> Result<Self, String> {
+        if json.trim().is_empty() {
+            return Ok(CostTable::default());
+        }
+        let table: CostTable = serde_json::from_str(json).map_err(|e| format!("Invalid jet cost table: {}", e))?;
+        for name in table.costs.keys() {
+            if consensus_cost(name).is_none() {
+                let similar = similar_jets(name, 1);
+                return Err(match similar.first() {
+                    Some(similar) => format!("Unknown jet `{name}` in the cost table; did you mean `{similar}`?"),
+                    None => format!("Unknown jet `{name}` in the cost table"),
+                });
+            }
+        }
+        Ok(table)
+    }
+
+    pub fn is_consensus(&self) -> bool {
+        self.costs.is_empty()
+    }
+
+    /// The cost of one call of `jet`, in milliweight.
+    pub fn cost(&self, jet: &Elements) -> u64 {
+        self.costs
+            .get(&jet.to_string())
+            .copied()
+            .unwrap_or_else(|| u64::from(u32::from(jet.cost())))
+    }
+
+    /// The cost of the jet named `name`, if it is a jet.
+    pub fn cost_of(&self, name: &str) -> Option<u64> {
+        self.costs.get(name).copied().or_else(|| consensus_cost(name))
+    }
+}
+
+/// Jet costs of the calls made in `trace`, in milliweight: under `table`,
+/// and under consensus costs.
+pub fn run_cost(trace: &Trace, table: &CostTable) -> (u64, u64) {
+    let jets = trace
+        .steps
+        .iter()
+        .filter(|step| step.operation == "jet")
+        .filter_map(|step| step.detail.as_deref());
+    let (mut custom, mut consensus) = (0, 0);
+    for name in jets {
+        custom += table.cost_of(name).unwrap_or(0);
+        consensus += consensus_cost(name).unwrap_or(0);
+    }
+    (custom, consensus)
+}
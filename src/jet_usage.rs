@@ -0,0 +1,55 @@
+//! Which jets a program calls and what they cost.
+//!
+//! Counts are references in the shared DAG: a jet used from three places is
+//! counted three times, however often those places run. Costs are in
+//! milliweight units (1000 mWU = 1 WU), the unit the Bit Machine's budget is
+//! checked in, and come from a [`CostTable`]: the consensus costs unless it
+//! overrides them.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::dag::{DagLike, MaxSharing};
+use simplicityhl::simplicity::jet::{Elements, Jet};
+use simplicityhl::simplicity::node::{Commit, Inner};
+use simplicityhl::simplicity::CommitNode;
+
+use crate::jet_costs::CostTable;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct JetUsage {
+    pub count: usize,
+    /// Cost of one call, in milliweight.
+    pub cost: u64,
+    /// `count` × `cost`.
+    pub total_cost: u64,
+    /// Cost of one call under consensus rules, when the table changes it.
+    #[serde(default)]
+    pub consensus_cost: Option<u64>,
+}
+
+/// Usage of every jet in `program`, by jet name, costed by `costs`.
+pub fn histogram(program: &CommitNode<Elements>, costs: &CostTable) -> BTreeMap<String, JetUsage> {
+    let nodes: Vec<_> = program.post_order_iter::<MaxSharing<Commit<Elements>>>().collect();
+    let jet = |index: usize| match nodes[index].node.inner() {
+        Inner::Jet(jet) => Some(*jet),
+        _ => None,
+    };
+
+    let mut usage: BTreeMap<String, JetUsage> = BTreeMap::new();
+    let references = nodes
+        .iter()
+        .flat_map(|item| [item.left_index, item.right_index])
+        .flatten()
+        // The root is referenced by the program itself.
+        .chain(std::iter::once(nodes.len() - 1));
+    for jet in references.filter_map(jet) {
+        let entry = usage.entry(jet.to_string()).or_default();
+        entry.count += 1;
+        let consensus = u64::from(u32::from(jet.cost()));
+        entry.cost = costs.cost(&jet);
+        entry.consensus_cost = (entry.cost != consensus).then_some(consensus);
+        entry.total_cost += entry.cost;
+    }
+    usage
+}
@@ -0,0 +1,152 @@
+//! Jet usage table for the last successful compile, with an editor for a
+//! custom jet cost table.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::api_types::AnalysisResult;
+use crate::compiler;
+use crate::i18n::{self, t, Msg};
+use crate::jet_costs::CostTable;
+
+/// Milliweight as weight units with up to three decimals.
+pub(crate) fn weight(milliweight: u64) -> String {
+    format!("{:.3}", milliweight as f64 / 1000.0)
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+#[component]
+pub fn JetUsagePanel(
+    #[prop(into)] code: Signal<String>,
+    /// CMR of the last successful compile; the table follows it.
+    #[prop(into)]
+    cmr: Signal<Option<String>>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let (analysis, set_analysis) = signal::<Option<AnalysisResult>>(None);
+    let costs = RwSignal::new(CostTable::load());
+    let (costs_text, set_costs_text) = signal(costs.with_untracked(|c| {
+        if c.is_consensus() {
+            String::new()
+        } else {
+            serde_json::to_string_pretty(c).unwrap_or_default()
+        }
+    }));
+    let (costs_error, set_costs_error) = signal::<Option<String>>(None);
+
+    Effect::new(move |_| {
+        if cmr.get().is_none() {
+            set_analysis.set(None);
+            return;
+        }
+        let code = code.get_untracked();
+        let costs_json = costs.with(|c| serde_json::to_string(c).unwrap_or_default());
+        spawn_local(async move {
+            let json = compiler::analyze_program(&code, &costs_json).await;
+            set_analysis.set(serde_json::from_str::<AnalysisResult>(&json).ok().filter(|a| a.error.is_none()));
+        });
+    });
+
+    let apply_costs = move |_| match CostTable::parse(&costs_text.get_untracked()) {
+        Ok(table) => {
+            table.save();
+            set_costs_error.set(None);
+            costs.set(table);
+        }
+        Err(e) => set_costs_error.set(Some(e)),
+    };
+
+    let reset_costs = move |_| {
+        CostTable::default().save();
+        set_costs_text.set(String::new());
+        set_costs_error.set(None);
+        costs.set(CostTable::default());
+    };
+
+    let editor = view! {
+        <details class="manifest-editor">
+            <summary>
+                {move || {
+                    let msg = if costs.with(CostTable::is_consensus) { Msg::JetCostsConsensus } else { Msg::JetCostsCustom };
+                    i18n::text(lang.get(), msg)
+                }}
+            </summary>
+            <p class="drop-zone-hint">
+                {t(Msg::JetCostsHint)}
+            </p>
+            <textarea
+                class="network-tx-input"
+                spellcheck="false"
+                aria-label=t(Msg::JetCostTable)
+                prop:value=move || costs_text.get()
+                on:input=move |ev| set_costs_text.set(event_target_value(&ev))
+            ></textarea>
+            <div class="button-group">
+                <button class="secondary" on:click=apply_costs>{t(Msg::ApplyCosts)}</button>
+                <button class="secondary" on:click=reset_costs>{t(Msg::ConsensusCosts)}</button>
+            </div>
+            {move || costs_error.get().map(|e| view! { <p class="tool-error">{e}</p> })}
+        </details>
+    };
+
+    let table = move || {
+        let analysis = analysis.get().filter(|a| !a.jets.is_empty())?;
+        let consensus_total: u64 = analysis
+            .jets
+            .values()
+            .map(|usage| usage.consensus_cost.unwrap_or(usage.cost) * usage.count as u64)
+            .sum();
+        let lang = lang.get();
+        let total = if consensus_total == analysis.total_cost {
+            i18n::format(i18n::text(lang, Msg::AllJetsCost), &[&weight(analysis.total_cost)])
+        } else {
+            let template = i18n::text(lang, Msg::AllJetsCostConsensus);
+            i18n::format(template, &[&weight(analysis.total_cost), &weight(consensus_total)])
+        };
+        let mut jets: Vec<_> = analysis.jets.into_iter().collect();
+        jets.sort_by(|(_, a), (_, b)| b.total_cost.cmp(&a.total_cost));
+        let rows = jets
+            .into_iter()
+            .map(|(name, usage)| {
+                view! {
+                    <tr>
+                        <td class="bulk-address">{name}</td>
+                        <td>{usage.count}</td>
+                        <td title=usage
+                            .consensus_cost
+                            .map(|c| i18n::format(i18n::text(lang, Msg::ConsensusCost), &[&weight(c)]))>
+                            {match usage.consensus_cost {
+                                Some(consensus) => {
+                                    i18n::format(i18n::text(lang, Msg::CostWas), &[&weight(usage.cost), &weight(consensus)])
+                                }
+                                None => weight(usage.cost),
+                            }}
+                        </td>
+                        <td>{weight(usage.total_cost)}</td>
+                    </tr>
+                }
+            })
+            .collect_view();
+        Some(view! {
+            <table class="bulk-table">
+                <thead>
+                    <tr>
+                        <th>{t(Msg::Jet)}</th>
+                        <th>{t(Msg::Uses)}</th>
+                        <th>{t(Msg::CostWu)}</th>
+                        <th>{t(Msg::TotalWu)}</th>
+                    </tr>
+                </thead>
+                <tbody>{rows}</tbody>
+            </table>
+            <p class="reminder-note">{total}</p>
+        })
+    };
+
+    view! {
+        {table}
+        {editor}
+    }
+}
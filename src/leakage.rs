@@ -0,0 +1,101 @@
+//! Warnings for secrets written into the program.
+//!
+//! Spending reveals the program, so every constant in it becomes public the
+//! moment the contract is spent; a secret belongs in the witness. The check
+//! is a heuristic over the source: a `0x` literal of 16 bytes or more is
+//! flagged when its line names something secret (`secret`, `preimage`,
+//! `private`, `seed`, `password`, a `sk`/`priv` name part), or when it is fed
+//! straight into a SHA-256 context, where it can only be a preimage.
+//!
+//! A finding is silenced by `// allow(secret-constant)` at the end of its
+//! line or alone on the line above, for constants that are public on purpose.
+
+use crate::diagnostics::Diagnostic;
+use crate::sanity::strip_comment;
+
+/// The comment that silences a finding.
+pub const ALLOW: &str = "allow(secret-constant)";
+
+/// Shortest literal, in bytes, that can hold a secret worth flagging.
+const MIN_BYTES: usize = 16;
+
+const SECRET_WORDS: [&str; 5] = ["secret", "preimage", "private", "seed", "password"];
+
+/// Name parts that mean "secret key" on their own, e.g. `alice_sk`.
+const SECRET_PARTS: [&str; 3] = ["sk", "priv", "privkey"];
+
+/// Every `0x` literal of at least [`MIN_BYTES`] bytes on `line`, as
+/// (1-based column, length in characters).
+fn long_literals(line: &str) -> Vec<(usize, usize)> {
+    let mut literals = Vec::new();
+    let mut offset = 0;
+    while let Some(at) = line[offset..].find("0x") {
+        let start = offset + at;
+        let digits = line[start + 2..]
+            .find(|c: char| !c.is_ascii_hexdigit() && c != '_')
+            .unwrap_or(line.len() - start - 2);
+        let hex_digits = line[start + 2..start + 2 + digits].chars().filter(|c| *c != '_').count();
+        if hex_digits >= MIN_BYTES * 2 {
+            literals.push((line[..start].chars().count() + 1, digits + 2));
+        }
+        offset = start + 2 + digits;
+    }
+    literals
+}
+
+/// Why `line` makes its literals look secret, if it does.
+fn secret_reason(line: &str) -> Option<String> {
+    let lower = line.to_ascii_lowercase();
+    if lower.contains("sha_256_ctx_8_add") {
+        return Some("it is hashed, so it is a preimage".to_string());
+    }
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .filter(|w| !w.is_empty())
+        .collect();
+    // `preimage_hash` and the like name the public half.
+    if let Some(word) = SECRET_WORDS.iter().find(|s| words.iter().any(|w| w.contains(*s) && !w.contains("hash"))) {
+        return Some(format!("its line mentions `{word}`"));
+    }
+    words
+        .iter()
+        .find(|w| w.split('_').any(|part| SECRET_PARTS.contains(&part)))
+        .map(|w| format!("`{w}` reads like a secret key"))
+}
+
+fn allowed(line: &str) -> bool {
+    line.split_once("//").is_some_and(|(_, comment)| comment.contains(ALLOW))
+}
+
+/// Warnings for constants in `code` that look like secrets.
+pub fn check(code: &str) -> Vec<Diagnostic> {
+    let lines: Vec<&str> = code.lines().collect();
+    let mut findings = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let above_allows = index
+            .checked_sub(1)
+            .map(|above| lines[above].trim())
+            .is_some_and(|above| above.starts_with("//") && above.contains(ALLOW));
+        if allowed(line) || above_allows {
+            continue;
+        }
+        let code_part = strip_comment(line);
+        let Some(reason) = secret_reason(code_part) else {
+            continue;
+        };
+        for (column, len) in long_literals(code_part) {
+            findings.push(Diagnostic {
+                end_column: column + len,
+                ..Diagnostic::warning(
+                    index + 1,
+                    column,
+                    format!(
+                        "This constant looks like a secret ({reason}). Spending reveals the program, \
+                         so pass it as a witness instead, or mark it `// {ALLOW}` if it is public."
+                    ),
+                )
+            });
+        }
+    }
+    findings
+}
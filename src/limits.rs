@@ -0,0 +1,115 @@
+//! Budgets for running programs on the Bit Machine, as passed from
+//! JavaScript.
+//!
+//! ```json
+//! {"max_cells": 5242880, "max_frames": 100000, "max_cost": 4000050000, "timeout_ms": 10000}
+//! ```
+//!
+//! Every field is optional. Simplicity programs always terminate, and the
+//! type checker bounds the memory and work of a program before it runs, so
+//! cells, frames and cost (the Bit Machine's step budget, in milliweight) are
+//! checked against those static bounds: a program over budget is refused
+//! without being started, and one within budget cannot exceed it. The Bit
+//! Machine cannot be interrupted once it runs, so `timeout_ms` is enforced
+//! by whoever runs it off the page; the playground does so when the core is
+//! loaded separately (`lazy-core`), by running on a worker it stops when
+//! time is up.
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::jet::Elements;
+use simplicityhl::simplicity::{Cost, RedeemNode};
+
+use crate::storage;
+
+const LIMITS_KEY: &str = "exec-limits";
+
+fn default_max_cells() -> usize {
+    // Liquid's consensus limit.
+    5 * 1024 * 1024
+}
+
+fn default_max_frames() -> usize {
+    100_000
+}
+
+fn default_max_cost() -> u32 {
+    // Liquid's consensus limit: a whole block's weight.
+    4_000_050_000
+}
+
+fn default_timeout_ms() -> u32 {
+    10_000
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExecLimits {
+    /// Bits of read and write frames beyond the program's input and output.
+    #[serde(default = "default_max_cells")]
+    pub max_cells: usize,
+    /// Frames on the read and write stacks at once.
+    #[serde(default = "default_max_frames")]
+    pub max_frames: usize,
+    /// Execution cost, in milliweight.
+    #[serde(default = "default_max_cost")]
+    pub max_cost: u32,
+    /// Wall-clock time, for runners that can stop the machine.
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u32,
+}
+
+impl Default for ExecLimits {
+    fn default() -> Self {
+        ExecLimits {
+            max_cells: default_max_cells(),
+            max_frames: default_max_frames(),
+            max_cost: default_max_cost(),
+            timeout_ms: default_timeout_ms(),
+        }
+    }
+}
+
+impl ExecLimits {
+    pub fn load() -> Self {
+        storage::load(LIMITS_KEY)
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Ok(json) = serde_json::to_string(self) {
+            storage::save(LIMITS_KEY, &json);
+        }
+    }
+
+    /// Read limits from JSON; a blank string gives the defaults.
+    pub fn parse(json: &str) -> Result<Self, String> {
+        if json.trim().is_empty() {
+            return Ok(ExecLimits::default());
+        }
+        serde_json::from_str(json).map_err(|e| format!("Invalid execution limits: {}", e))
+    }
+
+    /// Refuse `program` if its bounds exceed a budget.
+    pub fn check(&self, program: &RedeemNode<Elements>) -> Result<(), String> {
+        let bounds = program.bounds();
+        if bounds.extra_cells > self.max_cells {
+            return Err(format!(
+                "Budget exceeded: the program needs up to {} cells, over the limit of {}",
+                bounds.extra_cells, self.max_cells
+            ));
+        }
+        if bounds.extra_frames > self.max_frames {
+            return Err(format!(
+                "Budget exceeded: the program needs up to {} frames, over the limit of {}",
+                bounds.extra_frames, self.max_frames
+            ));
+        }
+        if bounds.cost > Cost::from_milliweight(self.max_cost) {
+            return Err(format!(
+                "Budget exceeded: the program may cost up to {}, over the limit of {} mWU",
+                bounds.cost, self.max_cost
+            ));
+        }
+        Ok(())
+    }
+}
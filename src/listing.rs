@@ -0,0 +1,42 @@
+//! Combinator listing of a compiled program.
+//!
+//! One line per node after maximal sharing, children first, in the spirit
+//! of Simplicity's human-readable encoding: `%3 = comp %1 %2 : 1 → 2^32`.
+//! The last node is the root and is named `main`.
+
+use simplicityhl::simplicity::dag::{DagLike, MaxSharing};
+use simplicityhl::simplicity::jet::Elements;
+use simplicityhl::simplicity::node::{Commit, Inner};
+use simplicityhl::simplicity::CommitNode;
+
+/// The listing of `program`.
+pub fn to_text(program: &CommitNode<Elements>) -> String {
+    let mut lines = vec![format!("-- CMR {}", program.cmr())];
+    let mut root = 0;
+    for item in program.post_order_iter::<MaxSharing<Commit<Elements>>>() {
+        let child = |index: Option<usize>| index.map(|i| format!("%{i}")).unwrap_or_default();
+        let (left, right) = (child(item.left_index), child(item.right_index));
+        let combinator = match item.node.inner() {
+            Inner::Iden => "iden".to_string(),
+            Inner::Unit => "unit".to_string(),
+            Inner::InjL(_) => format!("injl {left}"),
+            Inner::InjR(_) => format!("injr {left}"),
+            Inner::Take(_) => format!("take {left}"),
+            Inner::Drop(_) => format!("drop {left}"),
+            Inner::Comp(..) => format!("comp {left} {right}"),
+            Inner::Case(..) => format!("case {left} {right}"),
+            Inner::AssertL(_, cmr) => format!("assertl {left} #{cmr}"),
+            Inner::AssertR(cmr, _) => format!("assertr #{cmr} {left}"),
+            Inner::Pair(..) => format!("pair {left} {right}"),
+            Inner::Disconnect(..) => format!("disconnect {left} {right}"),
+            Inner::Witness(_) => "witness".to_string(),
+            Inner::Fail(_) => "fail".to_string(),
+            Inner::Jet(jet) => format!("jet_{jet}"),
+            Inner::Word(word) => format!("const {word}"),
+        };
+        lines.push(format!("%{} = {combinator} : {}", item.index, item.node.arrow()));
+        root = item.index;
+    }
+    lines.push(format!("main = %{root}"));
+    lines.join("\n")
+}
@@ -0,0 +1,111 @@
+//! Collapsible "Low-level output" section with the combinator listing.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::api_types::{ListingResult, SourceMapResult};
+use crate::compiler;
+use crate::i18n::{t, Msg};
+use crate::source_map::{SourceMap, SourceRange};
+
+/// `text` line by line. Clicking a line `ranges` has source ranges for
+/// (by 0-based line number) highlights them; used for listings and traces.
+pub(crate) fn mapped_lines(
+    text: &str,
+    ranges: impl Fn(usize) -> Option<Vec<SourceRange>>,
+    on_source: Callback<Vec<SourceRange>>,
+) -> Vec<AnyView> {
+    text.lines()
+        .enumerate()
+        .map(|(number, line)| match ranges(number).filter(|ranges| !ranges.is_empty()) {
+            Some(ranges) => view! {
+                <span
+                    class="listing-line mapped"
+                    title=t(Msg::ShowInSource)
+                    on:click=move |_| on_source.run(ranges.clone())
+                >
+                    {line.to_string()}
+                    "\n"
+                </span>
+            }
+            .into_any(),
+            None => view! { <span class="listing-line">{line.to_string()}"\n"</span> }.into_any(),
+        })
+        .collect()
+}
+
+/// The source map of `code`, or `None` if it cannot be built.
+pub(crate) async fn load_source_map(code: &str) -> Option<SourceMap> {
+    serde_json::from_str::<SourceMapResult>(&compiler::source_map(code).await)
+        .ok()
+        .and_then(|result| result.map)
+}
+
+#[component]
+pub fn ListingPanel(
+    #[prop(into)] code: Signal<String>,
+    /// CMR of the last successful compile; a new one invalidates the listing.
+    #[prop(into)]
+    cmr: Signal<Option<String>>,
+    /// Called with the source ranges of a clicked node.
+    on_source: Callback<Vec<SourceRange>>,
+) -> impl IntoView {
+    let (open, set_open) = signal(false);
+    let (listing, set_listing) = signal::<Option<Result<(String, Option<SourceMap>), String>>>(None);
+
+    // The listing can be long; only build it while the section is open.
+    Effect::new(move |_| {
+        let _ = cmr.get();
+        if !open.get() {
+            set_listing.set(None);
+            return;
+        }
+        let code = code.get_untracked();
+        spawn_local(async move {
+            let json = compiler::to_simplicity_text(&code).await;
+            let result = match serde_json::from_str::<ListingResult>(&json) {
+                Ok(ListingResult { text: Some(text), .. }) => Ok(text),
+                Ok(ListingResult { error, .. }) => Err(error.unwrap_or_default()),
+                Err(e) => Err(e.to_string()),
+            };
+            // Without a source map the listing is still worth showing.
+            let map = match &result {
+                Ok(_) => load_source_map(&code).await,
+                Err(_) => None,
+            };
+            set_listing.set(Some(result.map(|text| (text, map))));
+        });
+    });
+
+    view! {
+        <details
+            class="manifest-editor"
+            on:toggle=move |ev| set_open.set(event_target::<web_sys::Element>(&ev).has_attribute("open"))
+        >
+            <summary>{t(Msg::LowLevelOutput)}</summary>
+            {move || match listing.get() {
+                None => view! { <p class="reminder-note">{t(Msg::BuildingListing)}</p> }.into_any(),
+                Some(Ok((text, map))) => {
+                    let mapped = map.is_some();
+                    // The first line is the CMR; node `%i` is on line `i + 1`.
+                    let lines = mapped_lines(
+                        &text,
+                        |number| Some(map.as_ref()?.nodes.get(number.checked_sub(1)?)?.ranges.clone()),
+                        on_source,
+                    );
+                    view! {
+                        <pre class="output-box listing">{lines}</pre>
+                        {mapped
+                            .then(|| {
+                                view! {
+                                    <p class="drop-zone-hint">{t(Msg::ClickNodeHint)}</p>
+                                }
+                            })}
+                    }
+                    .into_any()
+                }
+                Some(Err(e)) => view! { <p class="tool-error">{e}</p> }.into_any(),
+            }}
+        </details>
+    }
+}
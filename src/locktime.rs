@@ -0,0 +1,176 @@
+//! Encodings of the values the timelock jets compare against.
+//!
+//! `check_lock_height` and `check_lock_time` take absolute `u32` lock times,
+//! which the transaction's `nLockTime` must reach; values below
+//! [`LOCKTIME_THRESHOLD`] are block heights and the rest UNIX times.
+//! `check_lock_distance` and `check_lock_duration` take relative `u16` locks
+//! that the input's `nSequence` must encode (BIP-68): a number of blocks, or a
+//! number of 512-second intervals with the type flag set.
+
+use serde::{Deserialize, Serialize};
+
+/// Lock times at or above this are UNIX timestamps, below it block heights.
+pub const LOCKTIME_THRESHOLD: u32 = 500_000_000;
+/// BIP-68 flag marking a relative lock as time-based.
+pub const SEQUENCE_TYPE_FLAG: u32 = 1 << 22;
+/// Granularity of time-based relative locks, in seconds.
+pub const DURATION_UNIT_SECS: u64 = 512;
+/// Liquid's target block interval, for rough conversions between blocks and time.
+pub const BLOCK_INTERVAL_SECS: u64 = 60;
+
+/// An encoded lock, with the `nSequence` a spend needs for relative locks.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct EncodedLock {
+    pub value: u32,
+    #[serde(default)]
+    pub sequence: Option<u32>,
+    pub description: String,
+}
+
+/// Absolute lock at block `height`.
+pub fn lock_height(height: u64) -> Result<EncodedLock, String> {
+    if height >= u64::from(LOCKTIME_THRESHOLD) {
+        return Err(format!("Block heights must be below {LOCKTIME_THRESHOLD}"));
+    }
+    Ok(EncodedLock {
+        value: height as u32,
+        sequence: None,
+        description: describe_lock_time(height as u32),
+    })
+}
+
+/// Absolute lock at UNIX time `unix_secs`.
+pub fn lock_time(unix_secs: u64) -> Result<EncodedLock, String> {
+    if unix_secs < u64::from(LOCKTIME_THRESHOLD) {
+        return Err("Times before November 1985 encode as block heights".to_string());
+    }
+    let value = u32::try_from(unix_secs).map_err(|_| "Times after February 2106 do not fit in a lock time".to_string())?;
+    Ok(EncodedLock {
+        value,
+        sequence: None,
+        description: describe_lock_time(value),
+    })
+}
+
+/// Relative lock of `blocks` blocks.
+pub fn lock_distance(blocks: u64) -> Result<EncodedLock, String> {
+    let value = u16::try_from(blocks).map_err(|_| format!("At most {} blocks", u16::MAX))?;
+    Ok(EncodedLock {
+        value: u32::from(value),
+        sequence: Some(u32::from(value)),
+        description: format!("{value} blocks (≈ {})", describe_seconds(u64::from(value) * BLOCK_INTERVAL_SECS)),
+    })
+}
+
+/// Relative lock of at least `seconds`, rounded up to whole 512-second units.
+pub fn lock_duration(seconds: u64) -> Result<EncodedLock, String> {
+    let units = seconds.div_ceil(DURATION_UNIT_SECS);
+    let units = u16::try_from(units)
+        .map_err(|_| format!("At most {} seconds", u64::from(u16::MAX) * DURATION_UNIT_SECS))?;
+    Ok(duration(units))
+}
+
+/// Relative lock of `units` 512-second intervals.
+pub fn duration(units: u16) -> EncodedLock {
+    EncodedLock {
+        value: u32::from(units),
+        sequence: Some(SEQUENCE_TYPE_FLAG | u32::from(units)),
+        description: describe_duration(units),
+    }
+}
+
+/// "2d 3h"-style rendering of a span of seconds.
+pub fn describe_seconds(seconds: u64) -> String {
+    let (days, hours, minutes) = (seconds / 86_400, seconds % 86_400 / 3_600, seconds % 3_600 / 60);
+    match (days, hours, minutes) {
+        (0, 0, minutes) => format!("{minutes}m"),
+        (0, hours, minutes) => format!("{hours}h {minutes}m"),
+        (days, hours, _) => format!("{days}d {hours}h"),
+    }
+}
+
+/// What a `Duration` of `units` waits for.
+pub fn describe_duration(units: u16) -> String {
+    let seconds = u64::from(units) * DURATION_UNIT_SECS;
+    format!("{units} × 512 s = {}", describe_seconds(seconds))
+}
+
+/// What an absolute lock time means.
+pub fn describe_lock_time(value: u32) -> String {
+    if value < LOCKTIME_THRESHOLD {
+        format!("block height {value}")
+    } else {
+        let date = js_sys::Date::new(&(f64::from(value) * 1000.0).into());
+        format!("UNIX time {value} ({})", String::from(date.to_iso_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LASTWILL: &str = include_str!("../contract/lastwill.simf");
+
+    /// The literal `name` is bound to in `code`, as in `let name: Ty = 25920;`.
+    fn declared(code: &str, name: &str) -> u64 {
+        let line = code
+            .lines()
+            .find(|line| line.trim_start().starts_with(&format!("let {name}:")))
+            .unwrap();
+        line.split('=').nth(1).unwrap().trim().trim_end_matches(';').parse().unwrap()
+    }
+
+    #[test]
+    fn lastwill_inheritance_delay_is_a_block_distance() {
+        let blocks = declared(LASTWILL, "days_180");
+        let lock = lock_distance(blocks).unwrap();
+        assert_eq!(lock.value, 25920);
+        // Without the type flag, BIP-68 counts blocks.
+        assert_eq!(lock.sequence, Some(25920));
+        // One-minute blocks: 25920 of them are 18 days, not 180.
+        assert_eq!(lock.description, "25920 blocks (≈ 18d 0h)");
+
+        let lock = lock_duration(180 * 86_400).unwrap();
+        assert_eq!(lock.value, 30375);
+        assert_eq!(lock.sequence, Some(SEQUENCE_TYPE_FLAG | 30375));
+        assert_eq!(lock.description, "30375 × 512 s = 180d 0h");
+    }
+
+    #[test]
+    fn htlc_timeout_is_an_absolute_height() {
+        let timeout = declared(&crate::wizards::htlc::example(), "timeout");
+        let lock = lock_height(timeout).unwrap();
+        assert_eq!(lock.value, 1000);
+        assert_eq!(lock.sequence, None);
+        assert_eq!(lock.description, "block height 1000");
+        assert!(lock_height(u64::from(LOCKTIME_THRESHOLD) - 1).is_ok());
+        assert!(lock_height(u64::from(LOCKTIME_THRESHOLD)).is_err());
+    }
+
+    #[test]
+    fn durations_round_up_to_whole_units() {
+        assert_eq!(lock_duration(0).unwrap().value, 0);
+        assert_eq!(lock_duration(1).unwrap().value, 1);
+        assert_eq!(lock_duration(512).unwrap().value, 1);
+        assert_eq!(lock_duration(513).unwrap().value, 2);
+        let longest = u64::from(u16::MAX) * DURATION_UNIT_SECS;
+        assert_eq!(lock_duration(longest).unwrap().value, u32::from(u16::MAX));
+        assert!(lock_duration(longest + 1).is_err());
+    }
+
+    #[test]
+    fn out_of_range_locks_are_refused() {
+        assert!(lock_time(u64::from(LOCKTIME_THRESHOLD) - 1).unwrap_err().contains("block heights"));
+        assert!(lock_time(u64::from(u32::MAX) + 1).unwrap_err().contains("2106"));
+        assert_eq!(lock_distance(u64::from(u16::MAX)).unwrap().value, u32::from(u16::MAX));
+        assert_eq!(lock_distance(u64::from(u16::MAX) + 1).unwrap_err(), "At most 65535 blocks");
+    }
+
+    #[test]
+    fn spans_are_described_by_their_largest_units() {
+        assert_eq!(describe_seconds(59), "0m");
+        assert_eq!(describe_seconds(3_600), "1h 0m");
+        assert_eq!(describe_seconds(90_061), "1d 1h");
+        assert_eq!(describe_duration(1), "1 × 512 s = 8m");
+    }
+}
@@ -0,0 +1,215 @@
+//! Structured log for the playground.
+//!
+//! Every message has a [`Level`] and a [`Category`] and is kept in a ring
+//! buffer of the last [`CAPACITY`] entries, which the developer console
+//! (Ctrl+Shift+L) shows. Messages at or above the console level, `info` by
+//! default, are also written to the browser console. User source code and
+//! compiler output are only ever logged at `debug`, so they stay out of the
+//! browser console unless asked for. Errors also go to the crash log, when
+//! the user has turned it on.
+
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::crash_log::{self, CrashKind, CrashReport};
+use crate::storage;
+
+/// Entries kept before the oldest are dropped.
+pub const CAPACITY: usize = 500;
+
+const LEVEL_KEY: &str = "log-level";
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = console, js_name = debug)]
+    fn console_debug(s: &str);
+    #[wasm_bindgen(js_namespace = console, js_name = info)]
+    fn console_info(s: &str);
+    #[wasm_bindgen(js_namespace = console, js_name = warn)]
+    fn console_warn(s: &str);
+    #[wasm_bindgen(js_namespace = console, js_name = error)]
+    fn console_error(s: &str);
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    pub const ALL: [Level; 4] = [Level::Debug, Level::Info, Level::Warn, Level::Error];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Level::Debug => "debug",
+            Level::Info => "info",
+            Level::Warn => "warn",
+            Level::Error => "error",
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Level::ALL
+            .into_iter()
+            .find(|l| l.as_str() == s)
+            .ok_or_else(|| format!("Unknown log level `{s}`"))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Category {
+    Ui,
+    Compiler,
+    Worker,
+    Network,
+}
+
+impl Category {
+    pub const ALL: [Category; 4] = [Category::Ui, Category::Compiler, Category::Worker, Category::Network];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Category::Ui => "ui",
+            Category::Compiler => "compiler",
+            Category::Worker => "worker",
+            Category::Network => "network",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Entry {
+    /// Milliseconds since the Unix epoch.
+    pub unix_ms: f64,
+    pub level: Level,
+    pub category: Category,
+    pub message: String,
+}
+
+/// ISO-8601 UTC form of a time in milliseconds since the Unix epoch.
+pub fn iso_time(unix_ms: f64) -> String {
+    js_sys::Date::new(&JsValue::from_f64(unix_ms)).to_iso_string().into()
+}
+
+/// The message of an error thrown by JavaScript: the value itself when it
+/// is a string, else its `message` property, else its debug form.
+pub fn js_error(e: JsValue) -> String {
+    e.as_string()
+        .or_else(|| js_sys::Reflect::get(&e, &JsValue::from_str("message")).ok()?.as_string())
+        .unwrap_or_else(|| format!("{e:?}"))
+}
+
+impl Entry {
+    pub fn time(&self) -> String {
+        iso_time(self.unix_ms)
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {:<5} [{}] {}", self.time(), self.level.as_str(), self.category.as_str(), self.message)
+    }
+}
+
+thread_local! {
+    static ENTRIES: RefCell<VecDeque<Entry>> = const { RefCell::new(VecDeque::new()) };
+    /// Bumped on every entry so viewers can tell when to redraw.
+    static REVISION: Cell<u64> = const { Cell::new(0) };
+    static CONSOLE_LEVEL: Cell<Option<Level>> = const { Cell::new(None) };
+}
+
+/// Lowest level written to the browser console.
+pub fn console_level() -> Level {
+    CONSOLE_LEVEL.with(|level| {
+        level.get().unwrap_or_else(|| {
+            let loaded = storage::load(LEVEL_KEY)
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(Level::Info);
+            level.set(Some(loaded));
+            loaded
+        })
+    })
+}
+
+pub fn set_console_level(level: Level) {
+    CONSOLE_LEVEL.with(|l| l.set(Some(level)));
+    storage::save(LEVEL_KEY, level.as_str());
+}
+
+pub fn log(level: Level, category: Category, message: impl Into<String>) {
+    let entry = Entry {
+        unix_ms: js_sys::Date::now(),
+        level,
+        category,
+        message: message.into(),
+    };
+    if level >= console_level() {
+        let line = format!("[{}] {}", category.as_str(), entry.message);
+        match level {
+            Level::Debug => console_debug(&line),
+            Level::Info => console_info(&line),
+            Level::Warn => console_warn(&line),
+            Level::Error => console_error(&line),
+        }
+    }
+    if level == Level::Error {
+        crash_log::record(CrashReport::new(CrashKind::Logged, &entry.message, None));
+    }
+    ENTRIES.with_borrow_mut(|entries| {
+        if entries.len() == CAPACITY {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    });
+    REVISION.with(|r| r.set(r.get() + 1));
+}
+
+pub fn debug(category: Category, message: impl Into<String>) {
+    log(Level::Debug, category, message);
+}
+
+pub fn info(category: Category, message: impl Into<String>) {
+    log(Level::Info, category, message);
+}
+
+pub fn warn(category: Category, message: impl Into<String>) {
+    log(Level::Warn, category, message);
+}
+
+pub fn error(category: Category, message: impl Into<String>) {
+    log(Level::Error, category, message);
+}
+
+/// Retained entries, oldest first.
+pub fn entries() -> Vec<Entry> {
+    ENTRIES.with_borrow(|entries| entries.iter().cloned().collect())
+}
+
+/// Changes whenever an entry is added or the log is cleared.
+pub fn revision() -> u64 {
+    REVISION.with(Cell::get)
+}
+
+pub fn clear() {
+    ENTRIES.with_borrow_mut(VecDeque::clear);
+    REVISION.with(|r| r.set(r.get() + 1));
+}
+
+/// One line per entry, for exporting.
+pub fn to_text(entries: &[Entry]) -> String {
+    entries.iter().map(|e| format!("{e}\n")).collect()
+}
@@ -0,0 +1,82 @@
+//! The user's key manifest: public keys they control.
+//!
+//! Keys are x-only public keys in hex. The manifest is edited as plain text,
+//! one key per line with an optional `label:` prefix, and persisted in
+//! localStorage.
+
+use serde::{Deserialize, Serialize};
+
+use crate::storage;
+
+const MANIFEST_KEY: &str = "manifest.keys";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ManifestKey {
+    pub label: String,
+    /// Lower-case hex of the 32-byte x-only key.
+    pub pubkey: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyManifest {
+    pub keys: Vec<ManifestKey>,
+}
+
+/// Normalise a 32-byte hex key, accepting an optional `0x` prefix.
+pub fn normalize_pubkey(hex: &str) -> Option<String> {
+    let hex = hex.trim();
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    (hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit())).then(|| hex.to_ascii_lowercase())
+}
+
+impl KeyManifest {
+    pub fn load() -> Self {
+        storage::load(MANIFEST_KEY)
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Ok(json) = serde_json::to_string(self) {
+            storage::save(MANIFEST_KEY, &json);
+        }
+    }
+
+    /// Parse the text form. Blank lines and `#` comments are skipped.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut keys = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (label, hex) = match line.rsplit_once(':') {
+                Some((label, hex)) => (label.trim().to_string(), hex),
+                None => (String::new(), line),
+            };
+            let pubkey = normalize_pubkey(hex)
+                .ok_or_else(|| format!("Line {}: expected a 64-character hex public key", index + 1))?;
+            keys.push(ManifestKey { label, pubkey });
+        }
+        Ok(KeyManifest { keys })
+    }
+
+    pub fn to_text(&self) -> String {
+        self.keys
+            .iter()
+            .map(|k| {
+                if k.label.is_empty() {
+                    k.pubkey.clone()
+                } else {
+                    format!("{}: {}", k.label, k.pubkey)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn find(&self, pubkey: &str) -> Option<&ManifestKey> {
+        let pubkey = normalize_pubkey(pubkey)?;
+        self.keys.iter().find(|k| k.pubkey == pubkey)
+    }
+}
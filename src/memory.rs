@@ -0,0 +1,158 @@
+//! Heap accounting for the compiler.
+//!
+//! WebAssembly memory only ever grows: pages the allocator once asked for
+//! stay with the module until the page is closed, so the heap settles at the
+//! largest amount ever live at once. What matters is therefore the peak of
+//! each compile, not the total it allocated. The global allocator here wraps
+//! the system one and counts live bytes and their high-water mark, and
+//! [`track_compile`] records the peak above the baseline for every compile.
+//! It wraps the compiler proper; expanding keys, parsing the arguments and
+//! satisfying a witness happen outside it and only show in `peak_bytes`.
+//!
+//! A compile is meant to keep nothing once its entry point returns: the
+//! compiled program is dropped before the result is serialized, and the
+//! compile cache holds only CMRs and error messages. The allocator then
+//! hands the freed space to the next compile, so the heap is bounded by the
+//! largest compile rather than growing with the number of them, and reusing
+//! buffers across compiles would not lower it. The ignored test below
+//! measures this; the counters are process-wide, so run it alone:
+//!
+//! ```text
+//! cargo test --lib memory -- --ignored --test-threads=1 --nocapture
+//! ```
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+/// Bytes per WebAssembly memory page.
+const PAGE_BYTES: usize = 64 * 1024;
+
+struct Counting;
+
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static COMPILES: AtomicUsize = AtomicUsize::new(0);
+static LAST_COMPILE_PEAK: AtomicUsize = AtomicUsize::new(0);
+static MAX_COMPILE_PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let live = LIVE.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(live, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            if new_size >= layout.size() {
+                let live = LIVE.fetch_add(new_size - layout.size(), Ordering::Relaxed) + new_size - layout.size();
+                PEAK.fetch_max(live, Ordering::Relaxed);
+            } else {
+                LIVE.fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+            }
+        }
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Size of the WebAssembly memory, which never shrinks.
+    pub heap_bytes: usize,
+    /// Bytes currently allocated.
+    pub live_bytes: usize,
+    /// Most bytes ever allocated at once.
+    pub peak_bytes: usize,
+    pub compiles: usize,
+    /// Bytes the last compile allocated above what was live before it.
+    pub last_compile_peak_bytes: usize,
+    /// The largest such peak of any compile.
+    pub max_compile_peak_bytes: usize,
+}
+
+fn heap_bytes() -> usize {
+    #[cfg(target_arch = "wasm32")]
+    return core::arch::wasm32::memory_size(0) * PAGE_BYTES;
+    #[cfg(not(target_arch = "wasm32"))]
+    return PEAK.load(Ordering::Relaxed).div_ceil(PAGE_BYTES) * PAGE_BYTES;
+}
+
+pub fn stats() -> MemoryStats {
+    MemoryStats {
+        heap_bytes: heap_bytes(),
+        live_bytes: LIVE.load(Ordering::Relaxed),
+        peak_bytes: PEAK.load(Ordering::Relaxed),
+        compiles: COMPILES.load(Ordering::Relaxed),
+        last_compile_peak_bytes: LAST_COMPILE_PEAK.load(Ordering::Relaxed),
+        max_compile_peak_bytes: MAX_COMPILE_PEAK.load(Ordering::Relaxed),
+    }
+}
+
+/// Run the compile `f`, recording how far it pushed the heap above where it
+/// started. The global peak is restored afterwards, so it still reports the
+/// all-time high.
+pub fn track_compile<T>(f: impl FnOnce() -> T) -> T {
+    let baseline = LIVE.load(Ordering::Relaxed);
+    let peak_before = PEAK.swap(baseline, Ordering::Relaxed);
+    let result = f();
+    let compile_peak = PEAK.load(Ordering::Relaxed).saturating_sub(baseline);
+    PEAK.fetch_max(peak_before, Ordering::Relaxed);
+
+    COMPILES.fetch_add(1, Ordering::Relaxed);
+    LAST_COMPILE_PEAK.store(compile_peak, Ordering::Relaxed);
+    MAX_COMPILE_PEAK.fetch_max(compile_peak, Ordering::Relaxed);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hexdump;
+    use crate::options::CompileOptions;
+
+    const LASTWILL: &str = include_str!("../contract/lastwill.simf");
+    const ROUNDS: usize = 50;
+
+    #[test]
+    #[ignore = "the counters are process-wide; run alone"]
+    fn repeated_compiles_do_not_grow_the_heap() {
+        let run = || {
+            CompileOptions::default().compile_source(LASTWILL, LASTWILL).unwrap();
+            hexdump::dump(LASTWILL).unwrap();
+        };
+        // The first run also fills one-off tables, such as compiled patterns.
+        run();
+        let mut peaks = Vec::with_capacity(ROUNDS);
+        let before = stats();
+        for _ in 0..ROUNDS {
+            run();
+            peaks.push(stats().last_compile_peak_bytes);
+        }
+        let after = stats();
+        println!(
+            "live {} -> {} bytes, compile peaks {}..{} bytes, all-time peak {} bytes over {} compiles",
+            before.live_bytes,
+            after.live_bytes,
+            peaks.iter().min().unwrap(),
+            peaks.iter().max().unwrap(),
+            after.peak_bytes,
+            after.compiles,
+        );
+        assert_eq!(after.live_bytes, before.live_bytes);
+        assert!(peaks.iter().all(|&peak| peak == peaks[0]));
+    }
+}
@@ -0,0 +1,100 @@
+//! The metadata header at the top of a contract.
+//!
+//! A run of `//!` lines at the start of the source describes the contract:
+//!
+//! ```text
+//! //! name: Hash time lock
+//! //! version: 1.2.0
+//! //! author: Alice <alice@example.com>
+//! //! license: MIT
+//! //! description: Pays Bob with the preimage, or refunds Alice
+//! //!   after block 3,000,000.
+//! ```
+//!
+//! Keys are case-insensitive and may come in any order; a line without a
+//! known key continues the field above it, and unknown keys are ignored.
+//! The header is a comment, so it changes nothing in the compiled program.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Metadata {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub license: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl Metadata {
+    /// Field labels and values, in header order, for the fields that are set.
+    pub fn fields(&self) -> Vec<(&'static str, &str)> {
+        [
+            ("Name", &self.name),
+            ("Version", &self.version),
+            ("Author", &self.author),
+            ("License", &self.license),
+            ("Description", &self.description),
+        ]
+        .into_iter()
+        .filter_map(|(label, value)| Some((label, value.as_deref()?)))
+        .collect()
+    }
+
+    fn field(&mut self, key: &str) -> Option<&mut Option<String>> {
+        match key.trim().to_ascii_lowercase().as_str() {
+            "name" => Some(&mut self.name),
+            "version" => Some(&mut self.version),
+            "author" => Some(&mut self.author),
+            "license" => Some(&mut self.license),
+            "description" => Some(&mut self.description),
+            _ => None,
+        }
+    }
+}
+
+/// The header of `code`, if it starts with one that sets any field.
+pub fn parse(code: &str) -> Option<Metadata> {
+    let mut metadata = Metadata::default();
+    // The key the previous line set, which continuation lines add to.
+    let mut last: Option<String> = None;
+    let header = code
+        .lines()
+        .map(str::trim)
+        .skip_while(|line| line.is_empty())
+        .map_while(|line| line.strip_prefix("//!"));
+    for line in header {
+        let line = line.trim();
+        let keyed = line
+            .split_once(':')
+            .filter(|(key, _)| metadata.field(key).is_some())
+            .map(|(key, value)| (key.trim().to_ascii_lowercase(), value.trim()));
+        let (key, text) = match keyed {
+            Some((key, value)) => {
+                if let Some(field) = metadata.field(&key) {
+                    *field = None;
+                }
+                (key, value)
+            }
+            None => match &last {
+                Some(key) => (key.clone(), line),
+                None => continue,
+            },
+        };
+        if let Some(field) = metadata.field(&key) {
+            if !text.is_empty() {
+                *field = Some(match field.take() {
+                    Some(before) => format!("{before} {text}"),
+                    None => text.to_string(),
+                });
+            }
+        }
+        last = Some(key);
+    }
+    (metadata != Metadata::default()).then_some(metadata)
+}
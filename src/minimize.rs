@@ -0,0 +1,270 @@
+//! Shrinking a failing program to a small reproducer, for bug reports.
+//!
+//! A failure is a compile error or a failed run on a mock transaction, and
+//! is kept as long as its message is: the reducer removes pieces of the
+//! source and keeps each removal after which the program still fails with
+//! that message. Pieces are whole items first, then statements and blocks,
+//! each a line or a line opening a block with everything up to the line
+//! closing it, so most candidates still parse. Removals are tried in
+//! halves, then quarters and so on (delta debugging), and passes repeat
+//! until none helps.
+//!
+//! Every candidate is compiled, so the number of tries is capped; a
+//! reduction that hits the cap can be shrunk further by running it again.
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::Cmr;
+
+use crate::diagnostics::Diagnostic;
+use crate::limits::ExecLimits;
+use crate::mock_tx::{self, MockTx};
+use crate::options::CompileOptions;
+use crate::sanity::strip_comment;
+
+/// Most candidates compiled in one reduction.
+const MAX_TESTS: usize = 500;
+
+/// The failure a reduction keeps. A blank `message` means the failure of
+/// the original program.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase", tag = "kind")]
+pub enum Failure {
+    /// Compiling fails with an error containing `message`.
+    Compile {
+        #[serde(default)]
+        message: String,
+    },
+    /// The program compiles, but a run with `witness` on `mock` fails with
+    /// an error containing `message`.
+    Run {
+        #[serde(default)]
+        witness: serde_json::Value,
+        #[serde(default)]
+        mock: MockTx,
+        #[serde(default)]
+        message: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Reduction {
+    pub source: String,
+    /// The message the reduced program still fails with.
+    pub message: String,
+    pub original_lines: usize,
+    pub lines: usize,
+    /// Candidates compiled.
+    pub tests: usize,
+    /// Stopped at the cap on candidates; running again may shrink it more.
+    pub exhausted: bool,
+}
+
+fn compile(code: &str) -> Result<Cmr, String> {
+    let compiled = CompileOptions::default().compile_source(code, code)?;
+    Ok(compiled.commit().cmr())
+}
+
+/// The message of a rendered error, without the source excerpt, whose line
+/// numbers change as the program shrinks.
+fn message_of(error: &str) -> String {
+    let message = Diagnostic::from_rendered(error).message;
+    if message.is_empty() {
+        error.lines().next().unwrap_or_default().to_string()
+    } else {
+        message
+    }
+}
+
+impl Failure {
+    /// The error of `code` if it fails this way, whatever the message.
+    fn error(&self, code: &str) -> Option<String> {
+        match self {
+            Failure::Compile { .. } => compile(code).err(),
+            Failure::Run { witness, mock, .. } => {
+                let cmr = compile(code).ok()?;
+                let witness = if witness.is_null() { String::new() } else { witness.to_string() };
+                let run = mock_tx::run(code, &witness, mock, cmr, &ExecLimits::default()).ok()?;
+                if run.accepted {
+                    None
+                } else {
+                    Some(run.error.unwrap_or_default())
+                }
+            }
+        }
+    }
+
+    fn message(&self) -> &str {
+        match self {
+            Failure::Compile { message } | Failure::Run { message, .. } => message,
+        }
+    }
+
+    fn holds(&self, code: &str) -> bool {
+        self.error(code).is_some_and(|error| error.contains(self.message()))
+    }
+}
+
+/// Spans of lines removed together, as `start..end`: a line, or a line
+/// opening a block and everything up to the line closing it. Lines that
+/// only close a block belong to the span that opened it. With `top_level`,
+/// only spans starting outside any block.
+fn spans(lines: &[String], top_level: bool) -> Vec<(usize, usize)> {
+    let balance: Vec<i32> = lines
+        .iter()
+        .map(|line| {
+            strip_comment(line).chars().fold(0, |balance, c| match c {
+                '{' | '(' | '[' => balance + 1,
+                '}' | ')' | ']' => balance - 1,
+                _ => balance,
+            })
+        })
+        .collect();
+    let mut spans = Vec::new();
+    let mut depth = 0;
+    for (start, (line, &change)) in lines.iter().zip(&balance).enumerate() {
+        let outer = depth;
+        depth += change;
+        if change < 0 || line.trim().is_empty() || (top_level && outer > 0) {
+            continue;
+        }
+        let mut open = change;
+        let mut end = start + 1;
+        while open > 0 && end < lines.len() {
+            open += balance[end];
+            end += 1;
+        }
+        spans.push((start, end));
+    }
+    spans
+}
+
+struct Reducer<'a> {
+    failure: &'a Failure,
+    lines: Vec<String>,
+    kept: Vec<bool>,
+    tests: usize,
+}
+
+impl Reducer<'_> {
+    fn source(&self, kept: &[bool]) -> String {
+        let lines: Vec<&str> = self
+            .lines
+            .iter()
+            .zip(kept)
+            .filter(|(_, &keep)| keep)
+            .map(|(line, _)| line.as_str())
+            .collect();
+        lines.join("\n")
+    }
+
+    fn exhausted(&self) -> bool {
+        self.tests >= MAX_TESTS
+    }
+
+    /// Remove `removed` if the failure holds without it.
+    fn try_remove(&mut self, removed: &[usize]) -> bool {
+        if self.exhausted() || removed.iter().all(|&line| !self.kept[line]) {
+            return false;
+        }
+        let mut kept = self.kept.clone();
+        for &line in removed {
+            kept[line] = false;
+        }
+        self.tests += 1;
+        if self.failure.holds(&self.source(&kept)) {
+            self.kept = kept;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Delta debugging over `spans`: remove them in ever smaller chunks.
+    fn ddmin(&mut self, spans: Vec<(usize, usize)>) {
+        let mut units: Vec<Vec<usize>> = spans.into_iter().map(|(start, end)| (start..end).collect()).collect();
+        let mut chunks = 2;
+        while !units.is_empty() && !self.exhausted() {
+            units.retain(|unit| unit.iter().any(|&line| self.kept[line]));
+            if units.is_empty() {
+                break;
+            }
+            let size = units.len().div_ceil(chunks.min(units.len()));
+            let mut removed_at = None;
+            for start in (0..units.len()).step_by(size) {
+                let end = (start + size).min(units.len());
+                let lines: Vec<usize> = units[start..end].iter().flatten().copied().collect();
+                if self.try_remove(&lines) {
+                    removed_at = Some((start, end));
+                    break;
+                }
+            }
+            match removed_at {
+                Some((start, end)) => {
+                    units.drain(start..end);
+                    chunks = (chunks - 1).max(2);
+                }
+                None if chunks >= units.len() => break,
+                None => chunks = (chunks * 2).min(units.len()),
+            }
+        }
+    }
+
+    fn kept_lines(&self) -> usize {
+        self.kept.iter().filter(|&&keep| keep).count()
+    }
+}
+
+/// Shrink `code`, which fails as `failure` describes, to a smaller program
+/// that fails the same way.
+pub fn minimize(code: &str, failure: &Failure) -> Result<Reduction, String> {
+    let error = failure.error(code).ok_or_else(|| match failure {
+        Failure::Compile { .. } => "The program compiles, so there is no compile error to keep".to_string(),
+        Failure::Run { .. } => "The program does not fail on the mock transaction".to_string(),
+    })?;
+    let mut failure = failure.clone();
+    match &mut failure {
+        Failure::Compile { message } | Failure::Run { message, .. } => {
+            if message.trim().is_empty() {
+                *message = message_of(&error);
+            } else if !error.contains(message.as_str()) {
+                return Err(format!("The program fails, but not with `{message}`: {}", message_of(&error)));
+            }
+        }
+    }
+
+    let lines: Vec<String> = code.lines().map(str::to_string).collect();
+    let mut reducer = Reducer {
+        failure: &failure,
+        kept: vec![true; lines.len()],
+        lines,
+        tests: 0,
+    };
+
+    // Blank lines and comments first, in one go.
+    let noise: Vec<usize> = reducer
+        .lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| strip_comment(line).trim().is_empty())
+        .map(|(index, _)| index)
+        .collect();
+    reducer.try_remove(&noise);
+
+    loop {
+        let before = reducer.kept_lines();
+        reducer.ddmin(spans(&reducer.lines, true));
+        reducer.ddmin(spans(&reducer.lines, false));
+        if reducer.kept_lines() == before || reducer.exhausted() {
+            break;
+        }
+    }
+
+    Ok(Reduction {
+        source: reducer.source(&reducer.kept),
+        message: failure.message().to_string(),
+        original_lines: code.lines().count(),
+        lines: reducer.kept_lines(),
+        tests: reducer.tests,
+        exhausted: reducer.exhausted(),
+    })
+}
@@ -0,0 +1,122 @@
+//! "Minimize Failure" drawer: shrink the program to a small one that fails
+//! the same way, ready to attach to a bug report.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::api_types::MinimizeResult;
+use crate::app::download_text;
+use crate::compiler;
+use crate::i18n::{self, t, Msg};
+use crate::minimize::{Failure, Reduction};
+use crate::mock_tx_panel::load_mock;
+
+#[component]
+pub fn MinimizePanel(
+    #[prop(into)] code: Signal<String>,
+    #[prop(into)] witness: Signal<String>,
+    /// Called with the reduced source to open it in the editor.
+    on_open: Callback<String>,
+) -> impl IntoView {
+    // Keep a compile error, or a failed run on the mock transaction.
+    let lang = i18n::use_lang();
+    let (run, set_run) = signal(false);
+    let (message, set_message) = signal(String::new());
+    let (busy, set_busy) = signal(false);
+    let (reduction, set_reduction) = signal::<Option<Result<Reduction, String>>>(None);
+
+    let shrink = move |_| {
+        let message = message.get_untracked().trim().to_string();
+        let failure = if run.get_untracked() {
+            Failure::Run {
+                witness: serde_json::from_str(&witness.get_untracked()).unwrap_or_default(),
+                mock: load_mock(),
+                message,
+            }
+        } else {
+            Failure::Compile { message }
+        };
+        let Ok(failure_json) = serde_json::to_string(&failure) else {
+            return;
+        };
+        let code = code.get_untracked();
+        let no_reduction = i18n::text(lang.get_untracked(), Msg::NoReduction);
+        set_busy.set(true);
+        set_reduction.set(None);
+        spawn_local(async move {
+            let json = compiler::minimize_program(&code, &failure_json).await;
+            set_reduction.set(Some(match serde_json::from_str::<MinimizeResult>(&json) {
+                Ok(MinimizeResult { reduction: Some(reduction), .. }) => Ok(reduction),
+                Ok(MinimizeResult { error, .. }) => Err(error.unwrap_or_else(|| no_reduction.to_string())),
+                Err(e) => Err(e.to_string()),
+            }));
+            set_busy.set(false);
+        });
+    };
+
+    let result = move || {
+        reduction.get().map(|reduction| match reduction {
+            Ok(reduction) => {
+                let source = reduction.source.clone();
+                let download = reduction.source.clone();
+                let summary = i18n::format(
+                    i18n::text(lang.get(), Msg::ReductionSummary),
+                    &[
+                        &reduction.lines.to_string(),
+                        &reduction.original_lines.to_string(),
+                        &reduction.tests.to_string(),
+                        &reduction.message,
+                    ],
+                );
+                view! {
+                    <p class="reminder-note">{summary}</p>
+                    {reduction.exhausted.then(|| {
+                        view! {
+                            <p class="reminder-note">{t(Msg::ReductionExhausted)}</p>
+                        }
+                    })}
+                    <pre>{reduction.source}</pre>
+                    <div class="button-group">
+                        <button on:click=move |_| on_open.run(source.clone())>{t(Msg::OpenInEditor)}</button>
+                        <button class="secondary" on:click=move |_| download_text("reproducer.simf", &download)>
+                            {t(Msg::Download)}
+                        </button>
+                    </div>
+                }
+                .into_any()
+            }
+            Err(e) => view! { <p class="tool-error">{e}</p> }.into_any(),
+        })
+    };
+
+    view! {
+        <details class="manifest-editor">
+            <summary>{t(Msg::MinimizeFailure)}</summary>
+            <div class="button-group">
+                <select
+                    aria-label=t(Msg::FailureToKeep)
+                    on:change=move |ev| set_run.set(event_target_value(&ev) == "run")
+                >
+                    <option value="compile" selected=move || !run.get()>{t(Msg::CompileError)}</option>
+                    <option value="run" selected=move || run.get()>{t(Msg::FailedMockRun)}</option>
+                </select>
+                <input
+                    type="text"
+                    class="registry-search"
+                    spellcheck="false"
+                    aria-label=t(Msg::ErrorToKeep)
+                    placeholder=t(Msg::ErrorToKeepPlaceholder)
+                    prop:value=move || message.get()
+                    on:input=move |ev| set_message.set(event_target_value(&ev))
+                />
+                <button disabled=move || busy.get() on:click=shrink>
+                    {move || i18n::text(lang.get(), if busy.get() { Msg::Shrinking } else { Msg::Shrink })}
+                </button>
+            </div>
+            <p class="drop-zone-hint">
+                {t(Msg::MinimizeHint)}
+            </p>
+            {result}
+        </details>
+    }
+}
@@ -0,0 +1,226 @@
+//! Mock transactions for the introspection jets.
+//!
+//! A mock transaction is written out field by field (version, lock time,
+//! inputs with the outputs they spend, and outputs) rather than as hex, so a
+//! covenant can be run against the transaction it is meant to constrain
+//! without building and signing one. The program spends input `input_index`;
+//! inputs that give no `script_pubkey` spend from the contract's own address.
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::elements::confidential::Nonce;
+use simplicityhl::simplicity::elements::{
+    LockTime, OutPoint, Script, Sequence, Transaction, TxIn, TxOut, TxOutWitness, Txid,
+};
+use simplicityhl::simplicity::hashes::Hash;
+use simplicityhl::simplicity::Cmr;
+
+use crate::address::{taproot_address, Network};
+use crate::assets::{self, NewIssuance};
+use crate::hex;
+use crate::limits::ExecLimits;
+use crate::sighash::{elements_env, SighashUtxo};
+use crate::taproot::TaprootConfig;
+use crate::test_runner::{self, TestCase, TestEnv, TestOutput};
+
+const DEFAULT_VALUE: u64 = 100_000;
+
+/// An input of the mock transaction, with the output it spends.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MockInput {
+    /// Hex; all zeros by default.
+    #[serde(default)]
+    pub txid: Option<String>,
+    #[serde(default)]
+    pub vout: u32,
+    /// Defaults to `0xfffffffe`, which enables lock times.
+    #[serde(default)]
+    pub sequence: Option<u32>,
+    /// Asset id or commitment of the spent output; the policy asset by default.
+    #[serde(default)]
+    pub asset: Option<String>,
+    /// Value of the spent output, in satoshis.
+    #[serde(default)]
+    pub value: u64,
+    /// Confidential amount commitment in hex; replaces `value`.
+    #[serde(default)]
+    pub value_commitment: Option<String>,
+    /// Script of the spent output, hex; the contract's address by default.
+    #[serde(default)]
+    pub script_pubkey: Option<String>,
+    /// Issuance made by this input.
+    #[serde(default)]
+    pub issuance: Option<NewIssuance>,
+}
+
+impl Default for MockInput {
+    fn default() -> Self {
+        MockInput {
+            txid: None,
+            vout: 0,
+            sequence: None,
+            asset: None,
+            value: DEFAULT_VALUE,
+            value_commitment: None,
+            script_pubkey: None,
+            issuance: None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MockTx {
+    #[serde(default = "default_version")]
+    pub version: u32,
+    #[serde(default)]
+    pub lock_time: u32,
+    pub inputs: Vec<MockInput>,
+    pub outputs: Vec<TestOutput>,
+    /// The input the program spends.
+    #[serde(default)]
+    pub input_index: u32,
+}
+
+fn default_version() -> u32 {
+    2
+}
+
+impl Default for MockTx {
+    /// One input of the default value, paid back to one output.
+    fn default() -> Self {
+        MockTx {
+            version: default_version(),
+            lock_time: 0,
+            inputs: vec![MockInput::default()],
+            outputs: vec![TestOutput {
+                value: DEFAULT_VALUE,
+                ..TestOutput::default()
+            }],
+            input_index: 0,
+        }
+    }
+}
+
+/// A run of a program on a mock transaction.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MockRun {
+    pub accepted: bool,
+    /// Why the program rejected the transaction, if it did.
+    pub error: Option<String>,
+    pub txid: String,
+    /// What `jet::sig_all_hash` returns on this transaction, hex.
+    pub sig_all_hash: String,
+}
+
+impl MockTx {
+    /// The transaction and the outputs it spends, for a program with CMR `cmr`.
+    pub fn build(&self, cmr: Cmr) -> Result<(Transaction, Vec<SighashUtxo>), String> {
+        if self.inputs.is_empty() {
+            return Err("The transaction needs at least one input".to_string());
+        }
+        let network = Network::LiquidTestnet;
+        let policy_asset = network.policy_asset().expect("testnet has a policy asset");
+        let own_script = taproot_address(cmr, network).script_pubkey();
+
+        let mut input = Vec::with_capacity(self.inputs.len());
+        let mut utxos = Vec::with_capacity(self.inputs.len());
+        for (index, mock) in self.inputs.iter().enumerate() {
+            let error = |e: String| format!("Input {index}: {e}");
+            let txid = match &mock.txid {
+                Some(txid) => Txid::from_str(txid.trim()).map_err(|e| error(format!("invalid txid: {e}")))?,
+                None => Txid::all_zeros(),
+            };
+            let asset = mock.asset.clone().unwrap_or_else(|| policy_asset.to_string());
+            // Parsed here so a bad asset is blamed on its input.
+            assets::parse_asset(&asset).map_err(error)?;
+            let script_pubkey = match &mock.script_pubkey {
+                Some(script) => Script::from(hex::decode(script).map_err(error)?),
+                None => own_script.clone(),
+            };
+            input.push(TxIn {
+                previous_output: OutPoint::new(txid, mock.vout),
+                is_pegin: false,
+                script_sig: Script::new(),
+                sequence: Sequence(mock.sequence.unwrap_or(0xffff_fffe)),
+                asset_issuance: match &mock.issuance {
+                    Some(issuance) => issuance.to_issuance().map_err(error)?,
+                    None => Default::default(),
+                },
+                witness: Default::default(),
+            });
+            utxos.push(SighashUtxo {
+                address: None,
+                script_pubkey: Some(hex::encode(script_pubkey.as_bytes())),
+                asset,
+                value: mock.value,
+                value_commitment: mock.value_commitment.clone(),
+            });
+        }
+
+        let output = self
+            .outputs
+            .iter()
+            .enumerate()
+            .map(|(index, output)| {
+                let error = |e: String| format!("Output {index}: {e}");
+                Ok(TxOut {
+                    asset: assets::parse_asset(output.asset.as_deref().unwrap_or(policy_asset)).map_err(error)?,
+                    value: assets::parse_value(output.value, output.value_commitment.as_deref()).map_err(error)?,
+                    nonce: Nonce::Null,
+                    script_pubkey: match &output.script_pubkey {
+                        Some(script) => Script::from(hex::decode(script).map_err(error)?),
+                        None => Script::new(),
+                    },
+                    witness: TxOutWitness::default(),
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let tx = Transaction {
+            version: self.version,
+            lock_time: LockTime::from_consensus(self.lock_time),
+            input,
+            output,
+        };
+        Ok((tx, utxos))
+    }
+}
+
+/// What `jet::sig_all_hash` returns when the program with CMR `cmr` spends
+/// input `input_index` of `mock`, hex.
+pub fn sig_all_hash(mock: &MockTx, cmr: Cmr) -> Result<String, String> {
+    let (tx, utxos) = mock.build(cmr)?;
+    let env = elements_env(tx, mock.input_index, &utxos, cmr, &TaprootConfig::default())?;
+    Ok(hex::encode(&env.c_tx_env().sighash_all().to_byte_array()))
+}
+
+/// Run `code` with `witness_json` as the spender of input `input_index` of
+/// `mock`, if it is within `limits`. `cmr` is the program's CMR.
+pub fn run(code: &str, witness_json: &str, mock: &MockTx, cmr: Cmr, limits: &ExecLimits) -> Result<MockRun, String> {
+    let txid = mock.build(cmr)?.0.txid().to_string();
+    let sig_all_hash = sig_all_hash(mock, cmr)?;
+
+    let witness = if witness_json.trim().is_empty() {
+        serde_json::json!({})
+    } else {
+        serde_json::from_str(witness_json).map_err(|e| format!("Invalid JSON witness data: {e}"))?
+    };
+    let case = TestCase {
+        name: "mock transaction".to_string(),
+        witness,
+        args: None,
+        env: TestEnv {
+            mock: Some(mock.clone()),
+            ..TestEnv::default()
+        },
+        expect: Default::default(),
+    };
+    let outcome = test_runner::run_with_limits(code, std::slice::from_ref(&case), limits).remove(0);
+    Ok(MockRun {
+        accepted: outcome.passed,
+        error: outcome.error,
+        txid,
+        sig_all_hash,
+    })
+}
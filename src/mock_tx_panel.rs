@@ -0,0 +1,456 @@
+//! Mock transaction editor: run the program against hand-written inputs and
+//! outputs to exercise its introspection jets, and export the run's trace.
+//! Trace steps and failures lead back to the source they were compiled from.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::api_types::{MockRunResult, TraceResult};
+use crate::app::download_text;
+use crate::i18n::{self, t, Msg};
+use crate::jet_costs::{self, CostTable};
+use crate::jet_usage_panel::weight;
+use crate::limits::ExecLimits;
+use crate::listing_panel::{load_source_map, mapped_lines};
+use crate::mock_tx::{MockInput, MockRun, MockTx};
+use crate::source_map::{SourceMap, SourceRange};
+use crate::storage;
+use crate::test_runner::TestOutput;
+use crate::trace::Trace;
+use crate::worker_pool;
+
+const MOCK_TX_KEY: &str = "mock_tx";
+
+pub(crate) fn load_mock() -> MockTx {
+    storage::load(MOCK_TX_KEY)
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn save_mock(mock: &MockTx) {
+    if let Ok(json) = serde_json::to_string(mock) {
+        storage::save(MOCK_TX_KEY, &json);
+    }
+}
+
+/// `None` for a blank field.
+fn optional(text: String) -> Option<String> {
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// A table cell editing one field of the mock transaction.
+fn cell(
+    label: Msg,
+    placeholder: impl Fn() -> &'static str + Send + Sync + 'static,
+    value: impl Fn() -> String + Send + Sync + 'static,
+    edit: impl Fn(String) + 'static,
+) -> impl IntoView {
+    view! {
+        <td>
+            <input
+                type="text"
+                spellcheck="false"
+                aria-label=t(label)
+                placeholder=placeholder
+                prop:value=value
+                on:input=move |ev| edit(event_target_value(&ev))
+            />
+        </td>
+    }
+}
+
+#[component]
+pub fn MockTxPanel(
+    #[prop(into)] code: Signal<String>,
+    #[prop(into)] witness: Signal<String>,
+    /// Called with the source ranges of a trace step or failure.
+    on_source: Callback<Vec<SourceRange>>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let mock = RwSignal::new(load_mock());
+    let limits = RwSignal::new(ExecLimits::load());
+    let (busy, set_busy) = signal(false);
+    let (outcome, set_outcome) = signal::<Option<Result<MockRun, String>>>(None);
+    let (traced, set_traced) = signal::<Option<Result<(Trace, String, Option<SourceMap>), String>>>(None);
+
+    Effect::new(move |_| mock.with(save_mock));
+    Effect::new(move |_| limits.with(ExecLimits::save));
+
+    // Rows are rebuilt only when one is added or removed, so typing keeps focus.
+    let input_count = Memo::new(move |_| mock.with(|m| m.inputs.len()));
+    let output_count = Memo::new(move |_| mock.with(|m| m.outputs.len()));
+
+    let input_field = move |index: usize, get: fn(&MockInput) -> String, set: fn(&mut MockInput, String)| {
+        (
+            move || mock.with(|m| m.inputs.get(index).map(get).unwrap_or_default()),
+            move |text: String| mock.update(|m| m.inputs.get_mut(index).into_iter().for_each(|i| set(i, text.clone()))),
+        )
+    };
+    let output_field = move |index: usize, get: fn(&TestOutput) -> String, set: fn(&mut TestOutput, String)| {
+        (
+            move || mock.with(|m| m.outputs.get(index).map(get).unwrap_or_default()),
+            move |text: String| mock.update(|m| m.outputs.get_mut(index).into_iter().for_each(|o| set(o, text.clone()))),
+        )
+    };
+
+    let input_rows = move || {
+        (0..input_count.get())
+            .map(|index| {
+                let (txid, set_txid) = input_field(index, |i| i.txid.clone().unwrap_or_default(), |i, t| i.txid = optional(t));
+                let (vout, set_vout) = input_field(index, |i| i.vout.to_string(), |i, t| {
+                    if let Ok(n) = t.trim().parse() {
+                        i.vout = n;
+                    }
+                });
+                let (sequence, set_sequence) = input_field(
+                    index,
+                    |i| i.sequence.map(|s| s.to_string()).unwrap_or_default(),
+                    |i, t| i.sequence = t.trim().parse().ok(),
+                );
+                let (asset, set_asset) = input_field(index, |i| i.asset.clone().unwrap_or_default(), |i, t| i.asset = optional(t));
+                let (value, set_value) = input_field(index, |i| i.value.to_string(), |i, t| {
+                    if let Ok(n) = t.trim().parse() {
+                        i.value = n;
+                    }
+                });
+                let (script, set_script) = input_field(
+                    index,
+                    |i| i.script_pubkey.clone().unwrap_or_default(),
+                    |i, t| i.script_pubkey = optional(t),
+                );
+                view! {
+                    <tr>
+                        <td>
+                            <input
+                                type="radio"
+                                name="mock-spent-input"
+                                aria-label=move || {
+                                    i18n::format(i18n::text(lang.get(), Msg::SpendsInput), &[&index.to_string()])
+                                }
+                                prop:checked=move || mock.with(|m| m.input_index as usize == index)
+                                on:change=move |_| mock.update(|m| m.input_index = index as u32)
+                            />
+                        </td>
+                        {cell(Msg::Txid, t(Msg::AllZeros), txid, set_txid)}
+                        {cell(Msg::Vout, || "0", vout, set_vout)}
+                        {cell(Msg::Sequence, || "0xfffffffe", sequence, set_sequence)}
+                        {cell(Msg::Asset, || "L-BTC", asset, set_asset)}
+                        {cell(Msg::ValueSat, || "0", value, set_value)}
+                        {cell(Msg::ScriptPubkey, t(Msg::ContractAddress), script, set_script)}
+                        <td>
+                            <button
+                                class="danger"
+                                disabled=move || input_count.get() == 1
+                                on:click=move |_| {
+                                    mock.update(|m| {
+                                        m.inputs.remove(index);
+                                        m.input_index = m.input_index.min(m.inputs.len() as u32 - 1);
+                                    })
+                                }
+                            >
+                                {t(Msg::Remove)}
+                            </button>
+                        </td>
+                    </tr>
+                }
+            })
+            .collect_view()
+    };
+
+    let output_rows = move || {
+        (0..output_count.get())
+            .map(|index| {
+                let (asset, set_asset) = output_field(index, |o| o.asset.clone().unwrap_or_default(), |o, t| o.asset = optional(t));
+                let (value, set_value) = output_field(index, |o| o.value.to_string(), |o, t| {
+                    if let Ok(n) = t.trim().parse() {
+                        o.value = n;
+                    }
+                });
+                let (script, set_script) = output_field(
+                    index,
+                    |o| o.script_pubkey.clone().unwrap_or_default(),
+                    |o, t| o.script_pubkey = optional(t),
+                );
+                view! {
+                    <tr>
+                        <td>{index}</td>
+                        {cell(Msg::Asset, || "L-BTC", asset, set_asset)}
+                        {cell(Msg::ValueSat, || "0", value, set_value)}
+                        {cell(Msg::ScriptPubkey, t(Msg::Empty), script, set_script)}
+                        <td>
+                            <button class="danger" on:click=move |_| mock.update(|m| { m.outputs.remove(index); })>
+                                {t(Msg::Remove)}
+                            </button>
+                        </td>
+                    </tr>
+                }
+            })
+            .collect_view()
+    };
+
+    let run = move |_| {
+        let (code, witness) = (code.get_untracked(), witness.get_untracked());
+        let json = mock.with_untracked(|m| serde_json::to_string(m).unwrap_or_default());
+        let limits = limits.get_untracked();
+        set_busy.set(true);
+        spawn_local(async move {
+            let result = worker_pool::run_program(&code, &witness, &json, &limits).await;
+            set_outcome.set(Some(match serde_json::from_str::<MockRunResult>(&result) {
+                Ok(MockRunResult { run: Some(run), .. }) => Ok(run),
+                Ok(MockRunResult { error, .. }) => Err(error.unwrap_or_default()),
+                Err(e) => Err(e.to_string()),
+            }));
+            set_busy.set(false);
+        });
+    };
+
+    // Record a trace, and with `show_failure` highlight where a rejected
+    // run failed.
+    let record_trace = move |show_failure: bool| {
+        let (code, witness) = (code.get_untracked(), witness.get_untracked());
+        let json = mock.with_untracked(|m| serde_json::to_string(m).unwrap_or_default());
+        let limits = limits.get_untracked();
+        set_busy.set(true);
+        spawn_local(async move {
+            let result = worker_pool::trace_program(&code, &witness, &json, &limits).await;
+            let result = match serde_json::from_str::<TraceResult>(&result) {
+                Ok(TraceResult { trace: Some(trace), text: Some(text), .. }) => Ok((trace, text)),
+                Ok(TraceResult { error, .. }) => Err(error.unwrap_or_default()),
+                Err(e) => Err(e.to_string()),
+            };
+            let map = match &result {
+                Ok(_) => load_source_map(&code).await,
+                Err(_) => None,
+            };
+            if let (true, Ok((trace, _)), Some(map)) = (show_failure, &result, &map) {
+                if let Some(step) = trace.failed_step() {
+                    on_source.run(map.ranges_of(&step.cmr).to_vec());
+                }
+            }
+            set_traced.set(Some(result.map(|(trace, text)| (trace, text, map))));
+            set_busy.set(false);
+        });
+    };
+
+    // One number input per budget; anything that does not parse is ignored.
+    let limit_field = move |label: Msg, get: fn(&ExecLimits) -> String, set: fn(&mut ExecLimits, &str)| {
+        view! {
+            <label class="limit-field">
+                {t(label)}
+                <input
+                    type="number"
+                    min="1"
+                    prop:value=move || limits.with(get)
+                    on:input=move |ev| limits.update(|l| set(l, &event_target_value(&ev)))
+                />
+            </label>
+        }
+    };
+
+    let trace_view = move || {
+        traced.get().map(|traced| match traced {
+            Err(e) => view! { <p class="tool-error" role="alert">{e}</p> }.into_any(),
+            Ok((trace, text, map)) => {
+                let lang = lang.get();
+                let summary = i18n::format(
+                    i18n::text(lang, if trace.truncated { Msg::TraceStepsCut } else { Msg::TraceSteps }),
+                    &[
+                        &trace.steps.len().to_string(),
+                        i18n::text(lang, if trace.accepted { Msg::Accepted } else { Msg::Rejected }),
+                    ],
+                );
+                let (cost, consensus) = jet_costs::run_cost(&trace, &CostTable::load());
+                let over_budget = cost > u64::from(limits.with_untracked(|l| l.max_cost));
+                let cost = if cost == consensus {
+                    i18n::format(i18n::text(lang, Msg::RunJetCost), &[&weight(cost)])
+                } else {
+                    i18n::format(i18n::text(lang, Msg::RunJetCostCustom), &[&weight(cost), &weight(consensus)])
+                };
+                let json = serde_json::to_string_pretty(&trace).unwrap_or_default();
+                // Steps follow the header lines, one per line.
+                let header = text.lines().count().saturating_sub(trace.steps.len() + usize::from(trace.truncated));
+                let lines = mapped_lines(
+                    &text,
+                    |number| {
+                        let step = trace.steps.get(number.checked_sub(header)?)?;
+                        Some(map.as_ref()?.ranges_of(&step.cmr).to_vec())
+                    },
+                    on_source,
+                );
+                view! {
+                    <details>
+                        <summary>{i18n::format(i18n::text(lang, Msg::ExecutionTrace), &[&summary])}</summary>
+                        <pre class="output-box listing">{lines}</pre>
+                        {map.is_some().then(|| view! { <p class="drop-zone-hint">{t(Msg::ClickStepHint)}</p> })}
+                    </details>
+                    <p class="reminder-note">{cost}</p>
+                    {over_budget.then(|| view! { <p class="tool-error">{t(Msg::JetsOverBudget)}</p> })}
+                    <div class="button-group">
+                        <button class="secondary" on:click=move |_| download_text("simplicity-trace.json", &json)>
+                            {t(Msg::ExportTraceJson)}
+                        </button>
+                        <button class="secondary" on:click=move |_| download_text("simplicity-trace.txt", &text)>
+                            {t(Msg::ExportTraceText)}
+                        </button>
+                    </div>
+                }
+                .into_any()
+            }
+        })
+    };
+
+    let results = move || {
+        outcome.get().map(|outcome| match outcome {
+            Err(e) => view! { <p class="tool-error" role="alert">{e}</p> }.into_any(),
+            Ok(run) => {
+                let (class, mark, text) = if run.accepted {
+                    ("status-badge paid", "✓", i18n::text(lang.get(), Msg::ProgramAccepts).to_string())
+                } else {
+                    ("status-badge expired", "✗", run.error.clone().unwrap_or_default())
+                };
+                let rejected = !run.accepted;
+                view! {
+                    <ul class="signature-list">
+                        <li class="signature-row">
+                            <span class=class>{mark}</span>
+                            {text}
+                            {rejected
+                                .then(|| {
+                                    view! {
+                                        <button
+                                            class="secondary"
+                                            title=t(Msg::ShowFailureTitle)
+                                            on:click=move |_| record_trace(true)
+                                            disabled=move || busy.get()
+                                        >
+                                            {t(Msg::ShowFailureInSource)}
+                                        </button>
+                                    }
+                                })}
+                        </li>
+                    </ul>
+                    <dl class="verify-cmrs">
+                        <dt>{t(Msg::Txid)}</dt>
+                        <dd>{run.txid}</dd>
+                        <dt>"sig_all_hash"</dt>
+                        <dd>{run.sig_all_hash}</dd>
+                    </dl>
+                }
+                .into_any()
+            }
+        })
+    };
+
+    view! {
+        <div class="mock-tx-panel">
+            <label>{t(Msg::MockTransaction)}</label>
+            <div class="invoice-form">
+                <input
+                    type="number"
+                    min="0"
+                    aria-label=t(Msg::Version)
+                    placeholder=t(Msg::Version)
+                    prop:value=move || mock.with(|m| m.version.to_string())
+                    on:input=move |ev| {
+                        if let Ok(n) = event_target_value(&ev).parse() {
+                            mock.update(|m| m.version = n);
+                        }
+                    }
+                />
+                <input
+                    type="number"
+                    min="0"
+                    aria-label=t(Msg::LockTime)
+                    placeholder=t(Msg::LockTime)
+                    prop:value=move || mock.with(|m| m.lock_time.to_string())
+                    on:input=move |ev| {
+                        if let Ok(n) = event_target_value(&ev).parse() {
+                            mock.update(|m| m.lock_time = n);
+                        }
+                    }
+                />
+            </div>
+            <table class="bulk-table mock-tx-table">
+                <thead>
+                    <tr>
+                        <th>{t(Msg::Spent)}</th>
+                        <th>{t(Msg::Txid)}</th>
+                        <th>{t(Msg::Vout)}</th>
+                        <th>{t(Msg::Sequence)}</th>
+                        <th>{t(Msg::Asset)}</th>
+                        <th>{t(Msg::Value)}</th>
+                        <th>{t(Msg::ScriptPubkey)}</th>
+                        <th></th>
+                    </tr>
+                </thead>
+                <tbody>{input_rows}</tbody>
+            </table>
+            <table class="bulk-table mock-tx-table">
+                <thead>
+                    <tr>
+                        <th>{t(Msg::Output)}</th>
+                        <th>{t(Msg::Asset)}</th>
+                        <th>{t(Msg::Value)}</th>
+                        <th>{t(Msg::ScriptPubkey)}</th>
+                        <th></th>
+                    </tr>
+                </thead>
+                <tbody>{output_rows}</tbody>
+            </table>
+            <div class="button-group">
+                <button class="secondary" on:click=move |_| mock.update(|m| m.inputs.push(MockInput::default()))>
+                    {t(Msg::AddInput)}
+                </button>
+                <button class="secondary" on:click=move |_| mock.update(|m| m.outputs.push(TestOutput::default()))>
+                    {t(Msg::AddOutput)}
+                </button>
+                <button class="secondary" on:click=move |_| mock.set(MockTx::default())>
+                    {t(Msg::Reset)}
+                </button>
+                <button on:click=run disabled=move || busy.get()>
+                    {move || i18n::text(lang.get(), if busy.get() { Msg::Running } else { Msg::RunOnMock })}
+                </button>
+                <button class="secondary" on:click=move |_| record_trace(false) disabled=move || busy.get()>
+                    {t(Msg::TraceExecution)}
+                </button>
+            </div>
+            <details>
+                <summary>{t(Msg::ExecutionLimits)}</summary>
+                <div class="invoice-form">
+                    {limit_field(Msg::Cells, |l| l.max_cells.to_string(), |l, t| {
+                        if let Ok(n) = t.trim().parse() {
+                            l.max_cells = n;
+                        }
+                    })}
+                    {limit_field(Msg::Frames, |l| l.max_frames.to_string(), |l, t| {
+                        if let Ok(n) = t.trim().parse() {
+                            l.max_frames = n;
+                        }
+                    })}
+                    {limit_field(Msg::CostMwu, |l| l.max_cost.to_string(), |l, t| {
+                        if let Ok(n) = t.trim().parse() {
+                            l.max_cost = n;
+                        }
+                    })}
+                    {limit_field(Msg::TimeoutMs, |l| l.timeout_ms.to_string(), |l, t| {
+                        if let Ok(n) = t.trim().parse() {
+                            l.timeout_ms = n;
+                        }
+                    })}
+                    <button class="secondary" on:click=move |_| limits.set(ExecLimits::default())>
+                        {t(Msg::Defaults)}
+                    </button>
+                </div>
+                <p class="drop-zone-hint">
+                    {t(Msg::LimitsHint)}
+                </p>
+            </details>
+            <p class="drop-zone-hint">
+                {t(Msg::MockTxHint)}
+            </p>
+            {results}
+            {trace_view}
+        </div>
+    }
+}
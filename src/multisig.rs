@@ -0,0 +1,158 @@
+//! Collecting signatures for k-of-n multisig programs.
+//!
+//! A SimplicityHL multisig reads one witness of type
+//! `[Option<Signature>; N]`, one slot per key of a `[Pubkey; N]` array in the
+//! program, and counts the `Some` slots that verify. Co-signers sign the same
+//! `sig_all_hash` independently; each signature is checked against every key
+//! and lands in the slot of the key it verifies under, so the order they
+//! arrive in does not matter. Once enough slots are filled the witness value,
+//! e.g. `[Some(0x..), None, Some(0x..)]`, is written into the witness JSON.
+
+use serde_json::{Map, Value};
+
+use crate::sanity::strip_comment;
+use crate::sigverify;
+use crate::witness_form;
+
+/// The multisig a program spends with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Multisig {
+    /// Name of the `[Option<Signature>; N]` witness.
+    pub witness: String,
+    /// Lower-case hex of each key, in slot order.
+    pub keys: Vec<String>,
+    /// Signatures required, from a `let threshold: u8 = K;` binding.
+    pub threshold: Option<usize>,
+}
+
+/// `N` of `[<element>; N]`.
+fn array_len(ty: &str, element: &str) -> Option<usize> {
+    let inner = ty.trim().strip_prefix('[')?.strip_suffix(']')?;
+    let (elem, len) = inner.rsplit_once(';')?;
+    (elem.trim() == element).then(|| len.trim().parse().ok()).flatten()
+}
+
+/// The 32-byte `0x` literals in `text`, in order.
+fn key_literals(text: &str) -> Vec<String> {
+    let mut keys = Vec::new();
+    let mut rest = text;
+    while let Some(at) = rest.find("0x") {
+        let tail = &rest[at + 2..];
+        let end = tail.find(|c: char| !c.is_ascii_hexdigit()).unwrap_or(tail.len());
+        if end == 64 {
+            keys.push(tail[..end].to_ascii_lowercase());
+        }
+        rest = &tail[end..];
+    }
+    keys
+}
+
+/// The keys of the first `[Pubkey; n]` array literal in `code`.
+fn find_keys(code: &str, n: usize) -> Option<Vec<String>> {
+    let code: String = code.lines().map(strip_comment).collect::<Vec<_>>().join("\n");
+    let declared = format!("[Pubkey; {n}]");
+    let mut rest = code.as_str();
+    while let Some(at) = rest.find(&declared) {
+        rest = &rest[at + declared.len()..];
+        let Some(body) = rest.trim_start().strip_prefix('=').map(str::trim_start) else {
+            continue;
+        };
+        let Some(body) = body.strip_prefix('[') else {
+            continue;
+        };
+        let keys = key_literals(&body[..body.find(']')?]);
+        if keys.len() == n {
+            return Some(keys);
+        }
+    }
+    None
+}
+
+fn find_threshold(code: &str) -> Option<usize> {
+    code.lines().find_map(|line| {
+        let rest = strip_comment(line).trim().strip_prefix("let threshold:")?;
+        let (_, value) = rest.split_once('=')?;
+        value.trim().trim_end_matches(';').trim().parse().ok()
+    })
+}
+
+/// The multisig `code` spends with, if it reads a signature array witness
+/// and writes its keys out as a `[Pubkey; N]` literal.
+pub fn detect(code: &str) -> Option<Multisig> {
+    witness_form::fields(code, "{}").into_iter().find_map(|field| {
+        let n = array_len(&field.ty, "Option<Signature>")?;
+        Some(Multisig {
+            witness: field.name,
+            keys: find_keys(code, n)?,
+            threshold: find_threshold(code),
+        })
+    })
+}
+
+/// Signatures already in the witness value of `multisig`, by slot.
+pub fn collected(multisig: &Multisig, witness_json: &str) -> Vec<Option<String>> {
+    let witness = serde_json::from_str::<Value>(witness_json).unwrap_or_default();
+    let value = witness
+        .get(&multisig.witness)
+        .and_then(|e| e.get("value"))
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let slots: Vec<Option<String>> = value
+        .trim()
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .split(',')
+        .map(|slot| {
+            let slot = slot.trim().strip_prefix("Some(")?.strip_suffix(')')?;
+            let hex = slot.trim().strip_prefix("0x")?;
+            (hex.len() == 128).then(|| hex.to_ascii_lowercase())
+        })
+        .collect();
+    if slots.len() == multisig.keys.len() {
+        slots
+    } else {
+        vec![None; multisig.keys.len()]
+    }
+}
+
+/// Put `signature` over `sighash` into the slot of the key it verifies
+/// under. Returns that slot.
+pub fn add(multisig: &Multisig, slots: &mut [Option<String>], sighash: &str, signature: &str) -> Result<usize, String> {
+    let signature = signature.trim().trim_start_matches("0x").to_ascii_lowercase();
+    for (slot, key) in multisig.keys.iter().enumerate() {
+        if sigverify::verify_bip340(key, sighash, &signature)? {
+            slots[slot] = Some(signature);
+            return Ok(slot);
+        }
+    }
+    Err("The signature verifies under none of the program's keys for this sighash".to_string())
+}
+
+/// The witness value for `slots`, e.g. `[Some(0x..), None]`.
+pub fn witness_value(slots: &[Option<String>]) -> String {
+    let slots: Vec<String> = slots
+        .iter()
+        .map(|slot| match slot {
+            Some(signature) => format!("Some(0x{signature})"),
+            None => "None".to_string(),
+        })
+        .collect();
+    format!("[{}]", slots.join(", "))
+}
+
+/// `witness_json` with the signature array of `multisig` set to `slots`.
+/// Other entries are kept.
+pub fn write_witness(multisig: &Multisig, slots: &[Option<String>], witness_json: &str) -> String {
+    let mut witness = match serde_json::from_str::<Value>(witness_json) {
+        Ok(Value::Object(map)) => map,
+        _ => Map::new(),
+    };
+    witness.insert(
+        multisig.witness.clone(),
+        serde_json::json!({
+            "value": witness_value(slots),
+            "type": format!("[Option<Signature>; {}]", multisig.keys.len()),
+        }),
+    );
+    serde_json::to_string_pretty(&Value::Object(witness)).unwrap_or_default()
+}
@@ -0,0 +1,128 @@
+//! Signing progress for multisig programs: collect co-signers' signatures
+//! into the witness one at a time.
+
+use leptos::prelude::*;
+
+use crate::i18n::{self, t, Msg};
+use crate::multisig;
+use crate::address_book;
+
+#[component]
+pub fn MultisigPanel(
+    #[prop(into)] code: Signal<String>,
+    witness: ReadSignal<String>,
+    set_witness: WriteSignal<String>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let (sighash, set_sighash) = signal(String::new());
+    let (signature, set_signature) = signal(String::new());
+    let (note, set_note) = signal::<Option<Result<String, String>>>(None);
+
+    // Keys may be vault or address book labels; they are compared as the compiler sees them.
+    let detected = Memo::new(move |_| multisig::detect(&address_book::expand_all(&code.get())));
+    let slots = Memo::new(move |_| {
+        detected
+            .get()
+            .map(|m| multisig::collected(&m, &witness.get()))
+            .unwrap_or_default()
+    });
+
+    let add = move |_| {
+        let Some(multisig) = detected.get_untracked() else {
+            return;
+        };
+        let mut slots = slots.get_untracked();
+        match multisig::add(&multisig, &mut slots, &sighash.get_untracked(), &signature.get_untracked()) {
+            Ok(slot) => {
+                set_witness.set(multisig::write_witness(&multisig, &slots, &witness.get_untracked()));
+                set_signature.set(String::new());
+                let added = i18n::text(lang.get_untracked(), Msg::AddedSignatureForKey);
+                set_note.set(Some(Ok(i18n::format(added, &[&(slot + 1).to_string()]))));
+            }
+            Err(e) => set_note.set(Some(Err(e))),
+        }
+    };
+
+    let clear = move |slot: usize| {
+        if let Some(multisig) = detected.get_untracked() {
+            let mut slots = slots.get_untracked();
+            slots[slot] = None;
+            set_witness.set(multisig::write_witness(&multisig, &slots, &witness.get_untracked()));
+        }
+    };
+
+    let progress = move || {
+        detected.get().map(|m| {
+            let signed = slots.get().iter().filter(|s| s.is_some()).count();
+            let (msg, of) = match m.threshold {
+                Some(k) if signed >= k => (Msg::SignaturesReady, k),
+                Some(k) => (Msg::SignaturesOfRequired, k),
+                None => (Msg::KeysHaveSigned, m.keys.len()),
+            };
+            i18n::format(i18n::text(lang.get(), msg), &[&signed.to_string(), &of.to_string()])
+        })
+    };
+
+    let rows = move || {
+        detected.get().map(|m| {
+            let slots = slots.get();
+            m.keys
+                .into_iter()
+                .zip(slots)
+                .enumerate()
+                .map(|(slot, (key, signature))| {
+                    let signed = signature.is_some();
+                    let (class, mark) = if signed { ("status-badge paid", "✓") } else { ("status-badge expired", "…") };
+                    view! {
+                        <li class="signature-row">
+                            <span class=class>{mark}</span>
+                            <code>{format!("{}…{}", &key[..8], &key[56..])}</code>
+                            {t(if signed { Msg::Signed } else { Msg::Waiting })}
+                            <Show when=move || signed>
+                                <button class="secondary" on:click=move |_| clear(slot)>
+                                    {t(Msg::Remove)}
+                                </button>
+                            </Show>
+                        </li>
+                    }
+                })
+                .collect_view()
+        })
+    };
+
+    view! {
+        <Show when=move || detected.with(Option::is_some)>
+            <div class="signature-panel">
+                <label>{t(Msg::MultisigSigning)}</label>
+                <p class="bulk-summary" role="status">{progress}</p>
+                <ul class="signature-list">{rows}</ul>
+                <div class="signature-form">
+                    <input
+                        placeholder=t(Msg::SighashPlaceholder)
+                        prop:value=move || sighash.get()
+                        on:input=move |ev| set_sighash.set(event_target_value(&ev))
+                    />
+                    <input
+                        placeholder=t(Msg::CosignerSignaturePlaceholder)
+                        prop:value=move || signature.get()
+                        on:input=move |ev| set_signature.set(event_target_value(&ev))
+                    />
+                </div>
+                <div class="button-group">
+                    <button on:click=add disabled=move || signature.get().trim().is_empty()>
+                        {t(Msg::AddSignature)}
+                    </button>
+                </div>
+                <p class="drop-zone-hint">
+                    {t(Msg::MultisigHint)}
+                </p>
+                {move || {
+                    note.get().map(|note| match note {
+                        Ok(text) => view! { <p class="reminder-note">{text}</p> }.into_any(),
+                        Err(e) => view! { <p class="tool-error">{e}</p> }.into_any(),
+                    })
+                }}
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,590 @@
+//! MuSig2 signing sessions for aggregated-key contracts.
+//!
+//! Keys are aggregated in sorted order, so every participant derives the same
+//! x-only key from the same list whatever order they typed it in; that key
+//! goes into the contract as an ordinary `Pubkey` and the final signature is
+//! an ordinary BIP-340 signature over the `sig_all_hash`.
+//!
+//! Signing takes two rounds of messages, exchanged over any channel:
+//!
+//! 1. every participant publishes a public nonce,
+//!    `musig2-nonce:<pubkey>:<nonce>`;
+//! 2. once all nonces are in, every participant publishes a partial
+//!    signature, `musig2-partial:<pubkey>:<partial signature>`.
+//!
+//! Partial signatures are checked as they are imported, so a bad one is
+//! blamed on its signer. The secret nonce never leaves the [`Session`] and is
+//! consumed by signing: a nonce used twice leaks the secret key.
+//!
+//! The protocol is BIP-327 without tweaks, written out on the secp256k1
+//! scalar and point operations; the pinned `secp256k1-zkp` has no MuSig2
+//! module. Zero scalars and the point at infinity, which the library types
+//! cannot hold, are `None`.
+
+use simplicityhl::simplicity::elements::secp256k1_zkp::{
+    constants, schnorr, Keypair, Message, Parity, PublicKey, Scalar, Secp256k1, SecretKey, XOnlyPublicKey,
+};
+use simplicityhl::simplicity::hashes::{sha256, Hash, HashEngine};
+
+use crate::hex;
+
+const NONCE_PREFIX: &str = "musig2-nonce";
+const PARTIAL_PREFIX: &str = "musig2-partial";
+
+/// A scalar modulo the curve order; `None` is zero.
+type Num = Option<SecretKey>;
+/// A curve point; `None` is the point at infinity.
+type Point = Option<PublicKey>;
+
+fn tagged_hash(tag: &str, parts: &[&[u8]]) -> [u8; 32] {
+    let tag = sha256::Hash::hash(tag.as_bytes());
+    let mut engine = sha256::Hash::engine();
+    engine.input(tag.as_ref());
+    engine.input(tag.as_ref());
+    for part in parts {
+        engine.input(part);
+    }
+    sha256::Hash::from_engine(engine).to_byte_array()
+}
+
+/// A hash as a scalar. Hashes are below twice the order, so one
+/// subtraction reduces them.
+fn reduce(mut bytes: [u8; 32]) -> Num {
+    if bytes >= constants::CURVE_ORDER {
+        let mut borrow = 0;
+        for (byte, order) in bytes.iter_mut().zip(constants::CURVE_ORDER).rev() {
+            let (difference, under) = byte.overflowing_sub(order);
+            let (difference, under_again) = difference.overflowing_sub(borrow);
+            *byte = difference;
+            borrow = u8::from(under || under_again);
+        }
+    }
+    SecretKey::from_slice(&bytes).ok()
+}
+
+fn one() -> SecretKey {
+    SecretKey::from_slice(&Scalar::ONE.to_be_bytes()).expect("one is a valid scalar")
+}
+
+fn add(a: Num, b: Num) -> Num {
+    match (a, b) {
+        (None, x) | (x, None) => x,
+        // Fails only when the sum is zero.
+        (Some(a), Some(b)) => a.add_tweak(&Scalar::from(b)).ok(),
+    }
+}
+
+fn mul(a: Num, b: Num) -> Num {
+    a.zip(b).and_then(|(a, b)| a.mul_tweak(&Scalar::from(b)).ok())
+}
+
+fn point_add(p: Point, q: Point) -> Point {
+    match (p, q) {
+        (None, x) | (x, None) => x,
+        // Fails only when the sum is the point at infinity.
+        (Some(p), Some(q)) => p.combine(&q).ok(),
+    }
+}
+
+fn point_mul(p: Point, k: Num) -> Point {
+    p.zip(k).and_then(|(p, k)| p.mul_tweak(&Secp256k1::new(), &Scalar::from(k)).ok())
+}
+
+fn base_mul(k: Num) -> Point {
+    k.map(|k| PublicKey::from_secret_key(&Secp256k1::new(), &k))
+}
+
+fn has_even_y(point: &PublicKey) -> bool {
+    point.x_only_public_key().1 == Parity::Even
+}
+
+/// A point as 33 bytes, with the point at infinity as zeros.
+fn point_bytes(point: Point) -> [u8; 33] {
+    point.map_or([0; 33], |p| p.serialize())
+}
+
+/// The aggregate of a key list, with what is needed to recompute each
+/// key's coefficient.
+struct KeyAgg {
+    list_hash: [u8; 32],
+    second_key: Option<PublicKey>,
+    key: PublicKey,
+}
+
+impl KeyAgg {
+    fn new(keys: &[PublicKey]) -> Result<KeyAgg, String> {
+        let serialized: Vec<[u8; 33]> = keys.iter().map(PublicKey::serialize).collect();
+        let parts: Vec<&[u8]> = serialized.iter().map(|k| &k[..]).collect();
+        let mut agg = KeyAgg {
+            list_hash: tagged_hash("KeyAgg list", &parts),
+            second_key: keys.iter().find(|k| **k != keys[0]).copied(),
+            key: keys[0],
+        };
+        agg.key = keys
+            .iter()
+            .fold(None, |sum, key| point_add(sum, point_mul(Some(*key), agg.coefficient(key))))
+            .ok_or("The participant keys cancel out")?;
+        Ok(agg)
+    }
+
+    fn coefficient(&self, key: &PublicKey) -> Num {
+        if Some(*key) == self.second_key {
+            return Some(one());
+        }
+        reduce(tagged_hash("KeyAgg coefficient", &[&self.list_hash, &key.serialize()]))
+    }
+
+    fn x_only(&self) -> XOnlyPublicKey {
+        self.key.x_only_public_key().0
+    }
+
+    /// The factor that makes the aggregate key's y coordinate even.
+    fn parity_factor(&self) -> Num {
+        let one = one();
+        Some(if has_even_y(&self.key) { one } else { one.negate() })
+    }
+}
+
+/// A participant's public nonce, two points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PubNonce([PublicKey; 2]);
+
+impl PubNonce {
+    fn serialize(&self) -> [u8; 66] {
+        let mut bytes = [0; 66];
+        bytes[..33].copy_from_slice(&self.0[0].serialize());
+        bytes[33..].copy_from_slice(&self.0[1].serialize());
+        bytes
+    }
+
+    fn from_slice(bytes: &[u8]) -> Result<PubNonce, String> {
+        if bytes.len() != 66 {
+            return Err(format!("expected 66 bytes, got {}", bytes.len()));
+        }
+        let point = |bytes: &[u8]| PublicKey::from_slice(bytes).map_err(|e| e.to_string());
+        Ok(PubNonce([point(&bytes[..33])?, point(&bytes[33..])?]))
+    }
+}
+
+/// A participant's partial signature, a scalar below the curve order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartialSignature([u8; 32]);
+
+impl PartialSignature {
+    fn from_slice(bytes: &[u8]) -> Result<PartialSignature, String> {
+        let bytes: [u8; 32] = bytes
+            .try_into()
+            .map_err(|_| format!("expected 32 bytes, got {}", bytes.len()))?;
+        Scalar::from_be_bytes(bytes).map_err(|_| "not below the curve order".to_string())?;
+        Ok(PartialSignature(bytes))
+    }
+
+    fn num(&self) -> Num {
+        SecretKey::from_slice(&self.0).ok()
+    }
+}
+
+/// The values every participant derives from the aggregated nonces.
+struct SigningValues {
+    /// The nonce coefficient `b`.
+    nonce_coefficient: Num,
+    /// The final nonce `R`.
+    nonce: PublicKey,
+    /// The BIP-340 challenge `e`.
+    challenge: Num,
+}
+
+impl SigningValues {
+    fn new(agg: &KeyAgg, nonces: &[PubNonce], digest: &[u8; 32]) -> SigningValues {
+        let sum = |j: usize| nonces.iter().fold(None, |sum, nonce| point_add(sum, Some(nonce.0[j])));
+        let (first, second) = (sum(0), sum(1));
+        let agg_key = agg.x_only().serialize();
+        let nonce_coefficient = reduce(tagged_hash(
+            "MuSig/noncecoef",
+            &[&point_bytes(first), &point_bytes(second), &agg_key, digest],
+        ));
+        let nonce = point_add(first, point_mul(second, nonce_coefficient)).unwrap_or_else(|| {
+            // BIP-327 substitutes the generator for the point at infinity.
+            PublicKey::from_secret_key(&Secp256k1::new(), &one())
+        });
+        let challenge = reduce(tagged_hash(
+            "BIP0340/challenge",
+            &[&nonce.x_only_public_key().0.serialize(), &agg_key, digest],
+        ));
+        SigningValues {
+            nonce_coefficient,
+            nonce,
+            challenge,
+        }
+    }
+
+    /// Negate nonces when the final nonce has an odd y coordinate.
+    fn nonce_factor(&self) -> Num {
+        let one = one();
+        Some(if has_even_y(&self.nonce) { one } else { one.negate() })
+    }
+}
+
+/// A compressed public key, 33 bytes of hex. MuSig2 needs the key's parity,
+/// so x-only keys are not enough.
+pub fn parse_pubkey(text: &str) -> Result<PublicKey, String> {
+    let text = text.trim();
+    let bytes = hex::decode(text)?;
+    if bytes.len() != 33 {
+        return Err(format!("`{text}` is not a compressed public key (33 bytes)"));
+    }
+    PublicKey::from_slice(&bytes).map_err(|e| format!("Invalid public key `{text}`: {e}"))
+}
+
+/// The compressed public key of `secret`, for sharing with the other
+/// participants.
+pub fn own_pubkey(secret: &str) -> Option<String> {
+    let secret = SecretKey::from_slice(&hex::decode(secret).ok()?).ok()?;
+    Some(hex::encode(&PublicKey::from_secret_key(&Secp256k1::new(), &secret).serialize()))
+}
+
+/// The participants' keys, sorted and without duplicates, and their
+/// aggregate.
+pub fn aggregate(pubkeys: &[String]) -> Result<(Vec<PublicKey>, XOnlyPublicKey), String> {
+    let mut keys = pubkeys
+        .iter()
+        .filter(|k| !k.trim().is_empty())
+        .map(|k| parse_pubkey(k))
+        .collect::<Result<Vec<_>, _>>()?;
+    keys.sort_by_key(|k| k.serialize());
+    keys.dedup();
+    if keys.len() < 2 {
+        return Err("MuSig2 needs at least two distinct participant keys".to_string());
+    }
+    let agg = KeyAgg::new(&keys)?;
+    Ok((keys, agg.x_only()))
+}
+
+/// A round message as read from a participant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundMessage {
+    Nonce(PublicKey, PubNonce),
+    Partial(PublicKey, PartialSignature),
+}
+
+impl RoundMessage {
+    pub fn encode(&self) -> String {
+        match self {
+            RoundMessage::Nonce(key, nonce) => {
+                format!("{NONCE_PREFIX}:{}:{}", hex::encode(&key.serialize()), hex::encode(&nonce.serialize()))
+            }
+            RoundMessage::Partial(key, partial) => {
+                format!("{PARTIAL_PREFIX}:{}:{}", hex::encode(&key.serialize()), hex::encode(&partial.0))
+            }
+        }
+    }
+
+    pub fn decode(text: &str) -> Result<RoundMessage, String> {
+        let text: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+        let mut parts = text.splitn(3, ':');
+        let (Some(kind), Some(key), Some(payload)) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(format!("Round messages look like `{NONCE_PREFIX}:<pubkey>:<nonce>`"));
+        };
+        let key = parse_pubkey(key)?;
+        let payload = hex::decode(payload)?;
+        match kind {
+            NONCE_PREFIX => PubNonce::from_slice(&payload)
+                .map(|nonce| RoundMessage::Nonce(key, nonce))
+                .map_err(|e| format!("Invalid nonce: {e}")),
+            PARTIAL_PREFIX => PartialSignature::from_slice(&payload)
+                .map(|partial| RoundMessage::Partial(key, partial))
+                .map_err(|e| format!("Invalid partial signature: {e}")),
+            _ => Err(format!("Unknown round message `{kind}`")),
+        }
+    }
+
+    fn pubkey(&self) -> PublicKey {
+        match self {
+            RoundMessage::Nonce(key, _) | RoundMessage::Partial(key, _) => *key,
+        }
+    }
+}
+
+/// One participant's view of a signing session.
+pub struct Session {
+    keypair: Keypair,
+    keys: Vec<PublicKey>,
+    agg: KeyAgg,
+    digest: [u8; 32],
+    sec_nonce: Option<[SecretKey; 2]>,
+    nonces: Vec<Option<PubNonce>>,
+    partials: Vec<Option<PartialSignature>>,
+}
+
+impl Session {
+    /// Sign `sighash` (32 bytes of hex) with `secret` among `pubkeys`,
+    /// which must include the secret's own key.
+    pub fn new(secret: &str, pubkeys: &[String], sighash: &str) -> Result<Session, String> {
+        let secp = Secp256k1::new();
+        let secret = SecretKey::from_slice(&hex::decode(secret)?).map_err(|e| format!("Invalid private key: {e}"))?;
+        let keypair = Keypair::from_secret_key(&secp, &secret);
+        let (keys, _) = aggregate(pubkeys)?;
+        if !keys.contains(&keypair.public_key()) {
+            return Err("Your key is not one of the participants".to_string());
+        }
+        let digest: [u8; 32] = hex::decode(sighash)?
+            .try_into()
+            .map_err(|_| "A sighash is 32 bytes (64 hex digits)".to_string())?;
+        Ok(Session {
+            keypair,
+            agg: KeyAgg::new(&keys)?,
+            nonces: vec![None; keys.len()],
+            partials: vec![None; keys.len()],
+            keys,
+            digest,
+            sec_nonce: None,
+        })
+    }
+
+    /// The participants' keys, in aggregation order.
+    pub fn keys(&self) -> &[PublicKey] {
+        &self.keys
+    }
+
+    pub fn aggregate_key(&self) -> XOnlyPublicKey {
+        self.agg.x_only()
+    }
+
+    fn own_index(&self) -> usize {
+        let own = self.keypair.public_key();
+        self.keys.iter().position(|k| *k == own).expect("checked in new")
+    }
+
+    fn index_of(&self, key: &PublicKey) -> Result<usize, String> {
+        self.keys
+            .iter()
+            .position(|k| k == key)
+            .ok_or_else(|| format!("{} is not a participant", hex::encode(&key.serialize())))
+    }
+
+    /// Round 1: this participant's nonce message. The nonce is made once per
+    /// session from `session_id`, which must be fresh randomness.
+    pub fn nonce_message(&mut self, session_id: [u8; 32]) -> Result<String, String> {
+        let own = self.own_index();
+        if self.nonces[own].is_none() {
+            // BIP-327 NonceGen, with the secret key, aggregate key and
+            // message mixed in and no extra input.
+            let aux = tagged_hash("MuSig/aux", &[&session_id]);
+            let mut rand = self.keypair.secret_bytes();
+            rand.iter_mut().zip(aux).for_each(|(byte, aux)| *byte ^= aux);
+            let pubkey = self.keypair.public_key().serialize();
+            let agg_key = self.agg.x_only().serialize();
+            let nonce = |i: u8| {
+                reduce(tagged_hash(
+                    "MuSig/nonce",
+                    &[
+                        &rand,
+                        &[33],
+                        &pubkey,
+                        &[32],
+                        &agg_key,
+                        &[1],
+                        &32u64.to_be_bytes(),
+                        &self.digest,
+                        &0u32.to_be_bytes(),
+                        &[i],
+                    ],
+                ))
+                .ok_or_else(|| "Could not make a nonce; try again".to_string())
+            };
+            let sec_nonce = [nonce(0)?, nonce(1)?];
+            let secp = Secp256k1::new();
+            self.nonces[own] = Some(PubNonce(sec_nonce.map(|k| PublicKey::from_secret_key(&secp, &k))));
+            self.sec_nonce = Some(sec_nonce);
+        }
+        let nonce = self.nonces[own].expect("just made");
+        Ok(RoundMessage::Nonce(self.keypair.public_key(), nonce).encode())
+    }
+
+    fn signing_values(&self) -> Result<SigningValues, String> {
+        let nonces = self
+            .nonces
+            .iter()
+            .map(|n| n.ok_or_else(|| "Not every participant's nonce is in yet".to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(SigningValues::new(&self.agg, &nonces, &self.digest))
+    }
+
+    /// BIP-327 PartialSigVerify: `s·G = b-weighted nonce + e·a·g·P`.
+    fn partial_verifies(&self, values: &SigningValues, key: &PublicKey, nonce: &PubNonce, partial: Num) -> bool {
+        let [first, second] = nonce.0;
+        let nonce = point_add(Some(first), point_mul(Some(second), values.nonce_coefficient));
+        let nonce = point_mul(nonce, values.nonce_factor());
+        let factor = mul(values.challenge, mul(self.agg.coefficient(key), self.agg.parity_factor()));
+        base_mul(partial) == point_add(nonce, point_mul(Some(*key), factor))
+    }
+
+    /// Read another participant's round message. Returns their position.
+    pub fn import(&mut self, text: &str) -> Result<usize, String> {
+        let message = RoundMessage::decode(text)?;
+        let index = self.index_of(&message.pubkey())?;
+        if index == self.own_index() {
+            return Err("That is your own message".to_string());
+        }
+        match message {
+            RoundMessage::Nonce(_, nonce) => {
+                if self.partials.iter().any(Option::is_some) {
+                    return Err("Signing has started; nonces can no longer change".to_string());
+                }
+                self.nonces[index] = Some(nonce);
+            }
+            RoundMessage::Partial(key, partial) => {
+                let nonce = self.nonces[index].ok_or("Import this participant's nonce first")?;
+                if !self.partial_verifies(&self.signing_values()?, &key, &nonce, partial.num()) {
+                    return Err("The partial signature does not verify for this participant".to_string());
+                }
+                self.partials[index] = Some(partial);
+            }
+        }
+        Ok(index)
+    }
+
+    /// Round 2: this participant's partial signature message. Needs every
+    /// nonce, and uses up the secret nonce.
+    pub fn partial_message(&mut self) -> Result<String, String> {
+        let own = self.own_index();
+        if self.partials[own].is_none() {
+            let values = self.signing_values()?;
+            let [first, second] = self.sec_nonce.take().ok_or("Publish your nonce first")?;
+            let nonce = mul(
+                add(Some(first), mul(values.nonce_coefficient, Some(second))),
+                values.nonce_factor(),
+            );
+            let secret = mul(Some(self.keypair.secret_key()), self.agg.parity_factor());
+            let key = self.keypair.public_key();
+            let partial = add(nonce, mul(values.challenge, mul(self.agg.coefficient(&key), secret)));
+            let partial = PartialSignature(partial.map_or([0; 32], |s| s.secret_bytes()));
+            if !self.partial_verifies(&values, &key, &self.nonces[own].expect("published"), partial.num()) {
+                return Err("Could not sign: the partial signature does not verify".to_string());
+            }
+            self.partials[own] = Some(partial);
+        }
+        let partial = self.partials[own].expect("just made");
+        Ok(RoundMessage::Partial(self.keypair.public_key(), partial).encode())
+    }
+
+    /// Which participants' nonces and partial signatures are in, by key.
+    pub fn progress(&self) -> Vec<(String, bool, bool)> {
+        self.keys
+            .iter()
+            .zip(self.nonces.iter().zip(&self.partials))
+            .map(|(key, (nonce, partial))| (hex::encode(&key.serialize()), nonce.is_some(), partial.is_some()))
+            .collect()
+    }
+
+    /// The aggregated BIP-340 signature, once every partial signature is in.
+    pub fn signature(&self) -> Result<String, String> {
+        let partials = self
+            .partials
+            .iter()
+            .map(|p| p.ok_or_else(|| "Not every partial signature is in yet".to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let values = self.signing_values()?;
+        let sum = partials.iter().fold(None, |sum, partial| add(sum, partial.num()));
+        let mut bytes = [0; 64];
+        bytes[..32].copy_from_slice(&values.nonce.x_only_public_key().0.serialize());
+        bytes[32..].copy_from_slice(&sum.map_or([0; 32], |s| s.secret_bytes()));
+        let signature = schnorr::Signature::from_slice(&bytes).map_err(|e| e.to_string())?;
+        Secp256k1::verification_only()
+            .verify_schnorr(&signature, &Message::from_digest(self.digest), &self.aggregate_key())
+            .map_err(|_| "The aggregated signature does not verify".to_string())?;
+        Ok(hex::encode(signature.as_ref()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGHASH: &str = "f95466d086770e689964664219266fe5ed215c92ae20bab5c9d79addddf3c0cf";
+
+    fn secret(byte: u8) -> String {
+        hex::encode(&[byte; 32])
+    }
+
+    fn pubkeys(bytes: &[u8]) -> Vec<String> {
+        bytes.iter().map(|b| own_pubkey(&secret(*b)).unwrap()).collect()
+    }
+
+    /// Runs both rounds among `sessions`, delivering every message to
+    /// every other participant.
+    fn exchange(sessions: &mut [Session]) {
+        let nonces: Vec<String> =
+            sessions.iter_mut().enumerate().map(|(i, s)| s.nonce_message([i as u8; 32]).unwrap()).collect();
+        for (i, session) in sessions.iter_mut().enumerate() {
+            for (j, nonce) in nonces.iter().enumerate() {
+                if i != j {
+                    session.import(nonce).unwrap();
+                }
+            }
+        }
+        let partials: Vec<String> = sessions.iter_mut().map(|s| s.partial_message().unwrap()).collect();
+        for (i, session) in sessions.iter_mut().enumerate() {
+            for (j, partial) in partials.iter().enumerate() {
+                if i != j {
+                    session.import(partial).unwrap();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn key_aggregation_matches_bip327() {
+        let keys: Vec<PublicKey> = [
+            "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            "03DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            "023590A94E768F8E1815C2F24B4D80A8E3149316C3518CE7B7AD338368D038CA66",
+        ]
+        .iter()
+        .map(|k| parse_pubkey(k).unwrap())
+        .collect();
+        let cases: [(&[usize], &str); 4] = [
+            (&[0, 1, 2], "90539eede565f5d054f32cc0c220126889ed1e5d193baf15aef344fe59d4610c"),
+            (&[2, 1, 0], "6204de8b083426dc6eaf9502d27024d53fc826bf7d2012148a0575435df54b2b"),
+            (&[0, 0, 0], "b436e3bad62b8cd409969a224731c193d051162d8c5ae8b109306127da3aa935"),
+            (&[0, 0, 1, 1], "69bc22bfa5d106306e48a20679de1d7389386124d07571d0d872686028c26a3e"),
+        ];
+        for (indices, expected) in cases {
+            let list: Vec<PublicKey> = indices.iter().map(|i| keys[*i]).collect();
+            assert_eq!(hex::encode(&KeyAgg::new(&list).unwrap().x_only().serialize()), expected);
+        }
+    }
+
+    #[test]
+    fn three_participants_make_a_valid_signature() {
+        let keys = pubkeys(&[1, 2, 3]);
+        let mut sessions: Vec<Session> =
+            [1, 2, 3].iter().map(|b| Session::new(&secret(*b), &keys, SIGHASH).unwrap()).collect();
+        exchange(&mut sessions);
+
+        let signature = sessions[0].signature().unwrap();
+        assert!(sessions.iter().all(|s| s.signature().unwrap() == signature));
+        let reversed: Vec<String> = keys.iter().rev().cloned().collect();
+        assert_eq!(aggregate(&reversed).unwrap().1, sessions[0].aggregate_key());
+    }
+
+    #[test]
+    fn bad_partial_signatures_are_blamed() {
+        let keys = pubkeys(&[1, 2]);
+        let mut alice = Session::new(&secret(1), &keys, SIGHASH).unwrap();
+        let mut bob = Session::new(&secret(2), &keys, SIGHASH).unwrap();
+        alice.import(&bob.nonce_message([2; 32]).unwrap()).unwrap();
+        bob.import(&alice.nonce_message([1; 32]).unwrap()).unwrap();
+
+        let partial = bob.partial_message().unwrap();
+        let last = partial.chars().last().unwrap();
+        let tampered = format!("{}{}", &partial[..partial.len() - 1], if last == '0' { '1' } else { '0' });
+        let error = alice.import(&tampered).unwrap_err();
+        assert!(error.contains("does not verify"), "{error}");
+        assert!(alice.signature().is_err());
+
+        alice.import(&partial).unwrap();
+        alice.partial_message().unwrap();
+        alice.signature().unwrap();
+    }
+}
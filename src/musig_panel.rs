@@ -0,0 +1,187 @@
+//! MuSig2 signing: aggregate participants' keys, then exchange nonces and
+//! partial signatures by copy and paste.
+
+use leptos::prelude::*;
+
+use crate::i18n::{self, t, Msg};
+use crate::musig::{self, Session};
+
+#[component]
+pub fn MusigPanel() -> impl IntoView {
+    let lang = i18n::use_lang();
+    let (pubkeys, set_pubkeys) = signal(String::new());
+    let (secret, set_secret) = signal(String::new());
+    let (sighash, set_sighash) = signal(String::new());
+    let (received, set_received) = signal(String::new());
+    let (outgoing, set_outgoing) = signal::<Option<String>>(None);
+    let (error, set_error) = signal::<Option<String>>(None);
+    let (note, set_note) = signal::<Option<String>>(None);
+    // The secret nonce lives only here, for the page's lifetime.
+    let session = StoredValue::new_local(None::<Session>);
+    // Bumped on every change to the session, which is not itself reactive.
+    let (revision, set_revision) = signal(0u32);
+
+    let keys = move || pubkeys.get().lines().map(str::to_string).collect::<Vec<_>>();
+    let aggregate = Memo::new(move |_| musig::aggregate(&keys()).map(|(_, key)| key.to_string()));
+
+    let report = move |result: Result<String, String>| match result {
+        Ok(message) => {
+            set_outgoing.set(Some(message));
+            set_error.set(None);
+            set_revision.update(|r| *r += 1);
+        }
+        Err(e) => set_error.set(Some(e)),
+    };
+
+    let start = move |_| {
+        let started = Session::new(&secret.get_untracked(), &keys(), &sighash.get_untracked()).and_then(|mut s| {
+            let message = s.nonce_message(rand::random())?;
+            session.set_value(Some(s));
+            Ok(message)
+        });
+        // The secret key is not needed after the nonce is made.
+        if started.is_ok() {
+            set_secret.set(String::new());
+        }
+        report(started);
+    };
+
+    let import = move |_| {
+        let text = received.get_untracked();
+        let lang = lang.get_untracked();
+        let mut result = Err(i18n::text(lang, Msg::StartSessionFirst).to_string());
+        session.update_value(|s| {
+            if let Some(s) = s {
+                result = text
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(|line| s.import(line))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(|imported| i18n::format(i18n::text(lang, Msg::ImportedMessages), &[&imported.len().to_string()]));
+            }
+        });
+        match result {
+            Ok(imported) => {
+                set_received.set(String::new());
+                set_error.set(None);
+                set_note.set(Some(imported));
+                set_revision.update(|r| *r += 1);
+            }
+            Err(e) => set_error.set(Some(e)),
+        }
+    };
+
+    let sign = move |_| {
+        let mut result = Err(i18n::text(lang.get_untracked(), Msg::StartSessionFirst).to_string());
+        session.update_value(|s| {
+            if let Some(s) = s {
+                result = s.partial_message();
+            }
+        });
+        report(result);
+    };
+
+    let progress = move || {
+        revision.track();
+        session.with_value(|s| {
+            s.as_ref().map(|s| {
+                let rows = s
+                    .progress()
+                    .into_iter()
+                    .map(|(key, nonce, partial)| {
+                        let (class, mark, state) = match (nonce, partial) {
+                            (_, true) => ("status-badge paid", "✓", Msg::Signed),
+                            (true, false) => ("status-badge paid", "…", Msg::NonceIn),
+                            (false, false) => ("status-badge expired", "…", Msg::WaitingForNonce),
+                        };
+                        view! {
+                            <li class="signature-row">
+                                <span class=class>{mark}</span>
+                                <code>{key}</code>
+                                {t(state)}
+                            </li>
+                        }
+                    })
+                    .collect_view();
+                let signature = s.signature().ok();
+                view! {
+                    <ul class="signature-list">{rows}</ul>
+                    {signature.map(|sig| {
+                        view! {
+                            <label>{t(Msg::AggregatedSignature)}</label>
+                            <pre class="output-box listing">{sig}</pre>
+                        }
+                    })}
+                }
+            })
+        })
+    };
+
+    view! {
+        <div class="signature-panel">
+            <label>{t(Msg::MusigSigning)}</label>
+            <textarea
+                spellcheck="false"
+                aria-label=t(Msg::ParticipantKeys)
+                placeholder=t(Msg::ParticipantKeysPlaceholder)
+                prop:value=move || pubkeys.get()
+                on:input=move |ev| set_pubkeys.set(event_target_value(&ev))
+            />
+            {move || match aggregate.get() {
+                Ok(key) => {
+                    view! { <p class="reminder-note">{t(Msg::AggregatedKey)} <code>{key}</code></p> }.into_any()
+                }
+                Err(e) if !pubkeys.get().trim().is_empty() => view! { <p class="tool-error">{e}</p> }.into_any(),
+                Err(_) => ().into_any(),
+            }}
+            <div class="signature-form">
+                <input
+                    type="password"
+                    autocomplete="off"
+                    placeholder=t(Msg::YourPrivateKey)
+                    prop:value=move || secret.get()
+                    on:input=move |ev| set_secret.set(event_target_value(&ev))
+                />
+                {move || {
+                    musig::own_pubkey(&secret.get())
+                        .map(|key| view! { <p class="drop-zone-hint">{t(Msg::YourPublicKey)} <code>{key}</code></p> })
+                }}
+                <input
+                    placeholder=t(Msg::SighashToSign)
+                    prop:value=move || sighash.get()
+                    on:input=move |ev| set_sighash.set(event_target_value(&ev))
+                />
+            </div>
+            <div class="button-group">
+                <button on:click=start>{t(Msg::StartSession)}</button>
+                <button class="secondary" on:click=sign>{t(Msg::CreatePartial)}</button>
+            </div>
+            {move || {
+                outgoing.get().map(|message| {
+                    view! {
+                        <label>{t(Msg::SendToParticipants)}</label>
+                        <pre class="output-box listing">{message}</pre>
+                    }
+                })
+            }}
+            <textarea
+                spellcheck="false"
+                aria-label=t(Msg::ReceivedRoundMessages)
+                placeholder=t(Msg::RoundMessagesPlaceholder)
+                prop:value=move || received.get()
+                on:input=move |ev| set_received.set(event_target_value(&ev))
+            />
+            <div class="button-group">
+                <button class="secondary" on:click=import disabled=move || received.get().trim().is_empty()>
+                    {t(Msg::ImportMessages)}
+                </button>
+            </div>
+            <p class="drop-zone-hint">
+                {t(Msg::MusigHint)}
+            </p>
+            {move || note.get().map(|n| view! { <p class="reminder-note">{n}</p> })}
+            {move || error.get().map(|e| view! { <p class="tool-error">{e}</p> })}
+            {progress}
+        </div>
+    }
+}
@@ -0,0 +1,250 @@
+//! Network and explorer settings, UTXO lookup and broadcasting.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::address::Network;
+use crate::esplora::{self, Transaction, Utxo};
+use crate::i18n::{self, t, Lang, Msg};
+use crate::invoice::format_btc;
+
+fn format_value(value: Option<u64>, lang: Lang) -> String {
+    value.map_or_else(|| i18n::text(lang, Msg::Confidential).to_string(), format_btc)
+}
+
+#[component]
+pub fn NetworkPanel(
+    network: RwSignal<Network>,
+    /// Address of the most recently compiled program.
+    #[prop(into)]
+    address: Signal<Option<String>>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let (endpoint, set_endpoint) = signal(esplora::configured_endpoint(network.get_untracked()).unwrap_or_default());
+    let (utxos, set_utxos) = signal::<Option<Vec<Utxo>>>(None);
+    let (txid, set_txid) = signal(String::new());
+    let (transaction, set_transaction) = signal::<Option<Transaction>>(None);
+    let (tx_hex, set_tx_hex) = signal(String::new());
+    let (broadcast_txid, set_broadcast_txid) = signal::<Option<String>>(None);
+    let (busy, set_busy) = signal(false);
+    let (error, set_error) = signal::<Option<String>>(None);
+
+    // The network can also be switched from the address box.
+    Effect::new(move |_| {
+        set_endpoint.set(esplora::configured_endpoint(network.get()).unwrap_or_default());
+        set_utxos.set(None);
+        set_transaction.set(None);
+    });
+
+    let save_endpoint = move |_| {
+        esplora::set_endpoint(network.get_untracked(), Some(&endpoint.get_untracked()));
+        set_endpoint.set(esplora::configured_endpoint(network.get_untracked()).unwrap_or_default());
+    };
+
+    let reset_endpoint = move |_| {
+        esplora::set_endpoint(network.get_untracked(), None);
+        set_endpoint.set(String::new());
+    };
+
+    // The explorer for the selected network, reporting when there is none.
+    let explorer = move || {
+        let base_url = esplora::base_url(network.get_untracked());
+        set_error.set(
+            base_url
+                .is_none()
+                .then(|| i18n::text(lang.get_untracked(), Msg::NoExplorerEndpoint).to_string()),
+        );
+        base_url
+    };
+
+    let lookup_utxos = move |_| {
+        let (Some(address), Some(base_url)) = (address.get_untracked(), explorer()) else {
+            return;
+        };
+        set_busy.set(true);
+        spawn_local(async move {
+            match esplora::address_utxos(&base_url, &address).await {
+                Ok(list) => set_utxos.set(Some(list)),
+                Err(e) => set_error.set(Some(e)),
+            }
+            set_busy.set(false);
+        });
+    };
+
+    let fetch_transaction = move |_| {
+        let Some(base_url) = explorer() else {
+            return;
+        };
+        let txid = txid.get_untracked().trim().to_string();
+        set_busy.set(true);
+        spawn_local(async move {
+            match esplora::transaction(&base_url, &txid).await {
+                Ok(tx) => set_transaction.set(Some(tx)),
+                Err(e) => set_error.set(Some(e)),
+            }
+            set_busy.set(false);
+        });
+    };
+
+    let broadcast = move |_| {
+        let Some(base_url) = explorer() else {
+            return;
+        };
+        let hex = tx_hex.get_untracked();
+        set_busy.set(true);
+        spawn_local(async move {
+            match esplora::broadcast(&base_url, &hex).await {
+                Ok(txid) => set_broadcast_txid.set(Some(txid)),
+                Err(e) => set_error.set(Some(e)),
+            }
+            set_busy.set(false);
+        });
+    };
+
+    let utxo_rows = move || {
+        utxos.get().map(|utxos| {
+            if utxos.is_empty() {
+                return view! { <p class="signature-empty">{t(Msg::NoUnspentOutputs)}</p> }.into_any();
+            }
+            let rows = utxos
+                .into_iter()
+                .map(|utxo| {
+                    let (class, status) = if utxo.status.confirmed {
+                        ("status-badge paid", Msg::Confirmed)
+                    } else {
+                        ("status-badge pending", Msg::Unconfirmed)
+                    };
+                    view! {
+                        <li class="invoice-row">
+                            <span class="invoice-label">{format!("{}:{}", utxo.txid, utxo.vout)}</span>
+                            <span class="invoice-amount">{format_value(utxo.value, lang.get())}</span>
+                            <span class=class>{t(status)}</span>
+                        </li>
+                    }
+                })
+                .collect_view();
+            view! { <ul class="invoice-list">{rows}</ul> }.into_any()
+        })
+    };
+
+    let transaction_view = move || {
+        transaction.get().map(|tx| {
+            let outputs = tx
+                .vout
+                .into_iter()
+                .enumerate()
+                .map(|(index, out)| {
+                    view! {
+                        <li class="invoice-row">
+                            <span>{format!("#{index}")}</span>
+                            <span class="invoice-label">
+                                {out.scriptpubkey_address.unwrap_or(out.scriptpubkey)}
+                            </span>
+                            <span class="invoice-amount">{format_value(out.value, lang.get())}</span>
+                        </li>
+                    }
+                })
+                .collect_view();
+            let lang = lang.get();
+            let status = match tx.status.block_height {
+                Some(height) if tx.status.confirmed => {
+                    i18n::format(i18n::text(lang, Msg::ConfirmedAtHeight), &[&height.to_string()])
+                }
+                _ => i18n::text(lang, Msg::Unconfirmed).to_string(),
+            };
+            let status = i18n::format(i18n::text(lang, Msg::TxStatusLocktime), &[&status, &tx.locktime.to_string()]);
+            view! {
+                <p class="network-status">{status}</p>
+                <ul class="invoice-list">{outputs}</ul>
+            }
+        })
+    };
+
+    view! {
+        <div class="network-panel">
+            <label>{t(Msg::Network)}</label>
+            <div class="invoice-form">
+                <select
+                    aria-label=t(Msg::Network)
+                    on:change=move |ev| {
+                        if let Ok(n) = event_target_value(&ev).parse::<Network>() {
+                            network.set(n);
+                        }
+                    }
+                >
+                    {Network::ALL
+                        .into_iter()
+                        .map(|n| {
+                            view! {
+                                <option value=n.as_str() selected=move || network.get() == n>
+                                    {n.as_str()}
+                                </option>
+                            }
+                        })
+                        .collect_view()}
+                </select>
+                <input
+                    type="url"
+                    placeholder=move || {
+                        esplora::default_base_url(network.get()).unwrap_or(i18n::text(lang.get(), Msg::EsploraUrl)).to_string()
+                    }
+                    prop:value=move || endpoint.get()
+                    on:input=move |ev| set_endpoint.set(event_target_value(&ev))
+                />
+                <button class="secondary" on:click=save_endpoint>{t(Msg::SaveEndpoint)}</button>
+                <button class="secondary" on:click=reset_endpoint>{t(Msg::UseDefault)}</button>
+            </div>
+            {move || error.get().map(|e| view! { <p class="tool-error">{e}</p> })}
+
+            <div class="section-header">
+                <span class="output-label">{t(Msg::ContractUtxos)}</span>
+                <button
+                    class="secondary"
+                    on:click=lookup_utxos
+                    disabled=move || busy.get() || address.get().is_none()
+                >
+                    {t(Msg::LookUpUtxos)}
+                </button>
+            </div>
+            {utxo_rows}
+
+            <div class="section-header">
+                <span class="output-label">{t(Msg::Transaction)}</span>
+            </div>
+            <div class="invoice-form">
+                <input
+                    type="text"
+                    placeholder="txid"
+                    prop:value=move || txid.get()
+                    on:input=move |ev| set_txid.set(event_target_value(&ev))
+                />
+                <button class="secondary" on:click=fetch_transaction disabled=move || busy.get()>
+                    {t(Msg::Fetch)}
+                </button>
+            </div>
+            {transaction_view}
+
+            <div class="section-header">
+                <span class="output-label">{t(Msg::BroadcastLabel)}</span>
+            </div>
+            <textarea
+                class="network-tx-input"
+                spellcheck="false"
+                placeholder=t(Msg::FinalizedTxHex)
+                prop:value=move || tx_hex.get()
+                on:input=move |ev| set_tx_hex.set(event_target_value(&ev))
+            />
+            <div class="button-group">
+                <button on:click=broadcast disabled=move || busy.get() || tx_hex.get().trim().is_empty()>
+                    {t(Msg::Broadcast)}
+                </button>
+            </div>
+            {move || {
+                broadcast_txid
+                    .get()
+                    .map(|txid| i18n::format(i18n::text(lang.get(), Msg::Broadcasted), &[&txid]))
+                    .map(|text| view! { <p class="network-status">{text}</p> })
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,200 @@
+//! Contract offers: a contract setup packed into one pasteable string.
+//!
+//! An offer names the SHA-256 of the source, the CMR it compiles to, the
+//! values of its `mod param` block and the witness fields a spend must fill,
+//! so two parties can agree on a contract over any text channel, be it chat,
+//! e-mail or a Nostr note. The string is `simoffer1` followed by the
+//! base64url JSON of the offer and a 4-byte checksum, which catches offers
+//! cut short or mangled on the way. It carries no source: the counterparty
+//! brings their own copy, applies the parameters, and [`check`] confirms it
+//! is the same contract.
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::hashes::{sha256, Hash};
+use simplicityhl::simplicity::Cmr;
+
+use crate::attestation::source_hash;
+use crate::param_values;
+use crate::witness_form;
+
+/// Format version written by this build.
+pub const OFFER_VERSION: u32 = 1;
+
+/// What every offer string starts with.
+pub const OFFER_PREFIX: &str = "simoffer1";
+
+const CHECKSUM_LEN: usize = 4;
+
+const BASE64URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OfferParam {
+    pub name: String,
+    pub ty: String,
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OfferWitness {
+    pub name: String,
+    /// SimplicityHL type, empty when the source does not spell it out.
+    pub ty: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Offer {
+    pub version: u32,
+    /// SHA-256 of the UTF-8 source with its parameter values, as hex.
+    pub source_sha256: String,
+    pub cmr: String,
+    pub params: Vec<OfferParam>,
+    /// Witness values a spend of the contract must provide.
+    pub witnesses: Vec<OfferWitness>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OfferCheck {
+    pub source_matches: bool,
+    pub cmr_matches: bool,
+    pub actual_cmr: Option<String>,
+    pub compile_error: Option<String>,
+    /// Parameters of the offer the source declares with another value, or
+    /// does not declare.
+    pub differing_params: Vec<String>,
+}
+
+impl OfferCheck {
+    /// The source is the offered contract.
+    pub fn is_valid(&self) -> bool {
+        self.source_matches && self.cmr_matches
+    }
+}
+
+/// The offer for `code`, which compiles to `cmr`.
+pub fn create(code: &str, cmr: Cmr) -> Offer {
+    Offer {
+        version: OFFER_VERSION,
+        source_sha256: source_hash(code),
+        cmr: cmr.to_string(),
+        params: param_values::params(code)
+            .into_iter()
+            .map(|p| OfferParam {
+                name: p.name,
+                ty: p.ty,
+                value: p.value,
+            })
+            .collect(),
+        witnesses: witness_form::fields(code, "{}")
+            .into_iter()
+            .map(|f| OfferWitness { name: f.name, ty: f.ty })
+            .collect(),
+    }
+}
+
+fn checksum(bytes: &[u8]) -> [u8; CHECKSUM_LEN] {
+    let hash = sha256::Hash::hash(bytes).to_byte_array();
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Unpadded base64url.
+fn base64url_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, &b)| n | (u32::from(b) << (16 - 8 * i)));
+        for i in 0..=chunk.len() {
+            out.push(BASE64URL[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+        }
+    }
+    out
+}
+
+fn base64url_decode(text: &str) -> Result<Vec<u8>, String> {
+    let digits = text
+        .bytes()
+        .map(|c| {
+            BASE64URL
+                .iter()
+                .position(|&d| d == c)
+                .map(|d| d as u32)
+                .ok_or_else(|| format!("`{}` is not part of an offer", c as char))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if digits.len() % 4 == 1 {
+        return Err("The offer is cut short".to_string());
+    }
+    let mut bytes = Vec::with_capacity(digits.len() * 3 / 4);
+    for chunk in digits.chunks(4) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, &d)| n | (d << (18 - 6 * i)));
+        for i in 0..chunk.len() - 1 {
+            bytes.push((n >> (16 - 8 * i)) as u8);
+        }
+    }
+    Ok(bytes)
+}
+
+impl Offer {
+    /// The offer as one string for pasting into a message.
+    pub fn encode(&self) -> String {
+        let mut bytes = serde_json::to_vec(self).unwrap_or_default();
+        let sum = checksum(&bytes);
+        bytes.extend_from_slice(&sum);
+        format!("{OFFER_PREFIX}{}", base64url_encode(&bytes))
+    }
+
+    /// Read an offer string. Whitespace is ignored, so offers broken over
+    /// lines by a mail client still read.
+    pub fn decode(text: &str) -> Result<Offer, String> {
+        let text: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+        let body = text
+            .strip_prefix(OFFER_PREFIX)
+            .ok_or_else(|| format!("Offers start with `{OFFER_PREFIX}`"))?;
+        let bytes = base64url_decode(body)?;
+        if bytes.len() <= CHECKSUM_LEN {
+            return Err("The offer is cut short".to_string());
+        }
+        let (json, sum) = bytes.split_at(bytes.len() - CHECKSUM_LEN);
+        if checksum(json)[..] != *sum {
+            return Err("The offer was changed or cut short on the way: its checksum does not match".to_string());
+        }
+        let offer: Offer = serde_json::from_slice(json).map_err(|e| format!("Invalid offer: {e}"))?;
+        if offer.version > OFFER_VERSION {
+            return Err(format!(
+                "Offer version {} is newer than this playground supports ({OFFER_VERSION})",
+                offer.version
+            ));
+        }
+        Ok(offer)
+    }
+
+    /// Arguments JSON with the offered parameter values, in the form
+    /// [`param_values::apply_arguments`] reads.
+    pub fn arguments_json(&self) -> String {
+        let entries: serde_json::Map<String, serde_json::Value> = self
+            .params
+            .iter()
+            .map(|p| (p.name.clone(), serde_json::json!({ "value": p.value, "type": p.ty })))
+            .collect();
+        serde_json::Value::Object(entries).to_string()
+    }
+}
+
+/// Check that `code` is the offered contract, compiling it with `compile`.
+pub fn check(offer: &Offer, code: &str, compile: impl Fn(&str) -> Result<Cmr, String>) -> OfferCheck {
+    let (actual_cmr, compile_error) = match compile(code) {
+        Ok(cmr) => (Some(cmr.to_string()), None),
+        Err(e) => (None, Some(e)),
+    };
+    let declared = param_values::params(code);
+    OfferCheck {
+        source_matches: source_hash(code) == offer.source_sha256,
+        cmr_matches: actual_cmr.as_deref() == Some(offer.cmr.as_str()),
+        actual_cmr,
+        compile_error,
+        differing_params: offer
+            .params
+            .iter()
+            .filter(|p| !declared.iter().any(|d| d.name == p.name && d.value == p.value))
+            .map(|p| p.name.clone())
+            .collect(),
+    }
+}
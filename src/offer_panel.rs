@@ -0,0 +1,178 @@
+//! Contract offers: export the editor's contract as an offer string, and
+//! check a received offer against the editor's code.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::api_types::{OfferImportResult, OfferResult};
+use crate::compiler;
+use crate::i18n::{self, t, Msg};
+use crate::offer::{Offer, OfferCheck};
+use crate::param_values;
+
+#[component]
+pub fn OfferPanel(
+    #[prop(into)] code: Signal<String>,
+    /// Called with the code once the offered parameter values are applied.
+    on_apply: Callback<String>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let (payload, set_payload) = signal(None::<Result<String, String>>);
+    let (received, set_received) = signal(String::new());
+    let (imported, set_imported) = signal(None::<Result<(Offer, OfferCheck), String>>);
+    let (note, set_note) = signal(None::<String>);
+
+    // The verdict is about the code and offer it was made for.
+    Effect::new(move |_| {
+        code.track();
+        received.track();
+        set_imported.set(None);
+    });
+
+    let export = move |_| {
+        let code = code.get_untracked();
+        spawn_local(async move {
+            let json = compiler::export_offer(&code).await;
+            set_payload.set(Some(match serde_json::from_str::<OfferResult>(&json) {
+                Ok(OfferResult { payload: Some(payload), .. }) => Ok(payload),
+                Ok(OfferResult { error, .. }) => Err(error.unwrap_or_default()),
+                Err(e) => Err(e.to_string()),
+            }));
+        });
+    };
+
+    let import = move || {
+        let (received, code) = (received.get_untracked(), code.get_untracked());
+        spawn_local(async move {
+            let json = compiler::import_offer(&received, &code).await;
+            set_imported.set(Some(match serde_json::from_str::<OfferImportResult>(&json) {
+                Ok(OfferImportResult { offer: Some(offer), check: Some(check), .. }) => Ok((offer, check)),
+                Ok(OfferImportResult { error, .. }) => Err(error.unwrap_or_default()),
+                Err(e) => Err(e.to_string()),
+            }));
+        });
+    };
+
+    let apply = move |offer: &Offer| match param_values::apply_arguments(&code.get_untracked(), &offer.arguments_json()) {
+        Ok((code, count)) => {
+            let applied = i18n::text(lang.get_untracked(), Msg::AppliedOfferedParams);
+            set_note.set(Some(i18n::format(applied, &[&count.to_string()])));
+            on_apply.run(code);
+        }
+        Err(e) => set_note.set(Some(e)),
+    };
+
+    let verdict = move || {
+        imported.get().map(|imported| match imported {
+            Err(e) => view! { <p class="tool-error" role="alert">{e}</p> }.into_any(),
+            Ok((offer, check)) => {
+                let row = |ok: bool, text: String| {
+                    let (class, mark) = if ok { ("status-badge paid", "✓") } else { ("status-badge expired", "✗") };
+                    view! {
+                        <li class="signature-row">
+                            <span class=class>{mark}</span>
+                            {text}
+                        </li>
+                    }
+                };
+                let lang = lang.get();
+                let text = |msg| i18n::text(lang, msg);
+                let cmr = match (&check.actual_cmr, &check.compile_error) {
+                    (_, Some(e)) => i18n::format(text(Msg::OfferDoesNotCompile), &[e]),
+                    (Some(actual), _) if !check.cmr_matches => {
+                        i18n::format(text(Msg::OfferCmrDiffers), &[actual, &offer.cmr])
+                    }
+                    _ => i18n::format(text(Msg::OfferCmrMatches), &[&offer.cmr]),
+                };
+                let params = if check.differing_params.is_empty() {
+                    text(Msg::ParamsMatch).to_string()
+                } else {
+                    i18n::format(text(Msg::ParamsDiffer), &[&check.differing_params.join(", ")])
+                };
+                let witnesses = offer
+                    .witnesses
+                    .iter()
+                    .map(|w| {
+                        let ty = if w.ty.is_empty() { text(Msg::TypeUnknown).to_string() } else { w.ty.clone() };
+                        view! { <li><code>{format!("witness::{}", w.name)}</code>{format!(": {ty}")}</li> }
+                    })
+                    .collect_view();
+                let needs_params = !check.differing_params.is_empty();
+                let offer = StoredValue::new(offer);
+                view! {
+                    <p class="bulk-summary" role="status">
+                        {text(if check.is_valid() { Msg::IsOfferedContract } else { Msg::NotOfferedContract })}
+                    </p>
+                    <ul class="signature-list">
+                        {row(
+                            check.source_matches,
+                            text(match check.source_matches {
+                                true => Msg::SourceHashMatches,
+                                false => Msg::SourceHashDiffers,
+                            })
+                            .to_string(),
+                        )}
+                        {row(check.cmr_matches, cmr)}
+                        {row(!needs_params, params)}
+                    </ul>
+                    <p class="drop-zone-hint">{text(Msg::SpendMustProvide)}</p>
+                    <ul class="spend-path-notes">{witnesses}</ul>
+                    <Show when=move || needs_params>
+                        <div class="button-group">
+                            <button class="secondary" on:click=move |_| offer.with_value(|offer| apply(offer))>
+                                {text(Msg::ApplyOfferedParams)}
+                            </button>
+                        </div>
+                    </Show>
+                }
+                .into_any()
+            }
+        })
+    };
+
+    view! {
+        <div class="verify-panel">
+            <label>{t(Msg::ContractOffer)}</label>
+            <div class="button-group">
+                <button on:click=export>{t(Msg::ExportOffer)}</button>
+            </div>
+            {move || {
+                payload.get().map(|payload| match payload {
+                    Ok(payload) => view! {
+                        <textarea
+                            id="offer-payload"
+                            readonly=true
+                            spellcheck="false"
+                            aria-label=t(Msg::OfferString)
+                            prop:value=payload
+                            on:focus=|ev| event_target::<web_sys::HtmlTextAreaElement>(&ev).select()
+                        />
+                    }
+                    .into_any(),
+                    Err(e) => view! { <p class="tool-error">{e}</p> }.into_any(),
+                })
+            }}
+            <p class="drop-zone-hint">
+                {t(Msg::OfferHint)}
+            </p>
+            <textarea
+                spellcheck="false"
+                aria-label=t(Msg::ReceivedOffer)
+                placeholder=t(Msg::ReceivedOfferPlaceholder)
+                prop:value=move || received.get()
+                on:input=move |ev| set_received.set(event_target_value(&ev))
+            />
+            <div class="button-group">
+                <button
+                    class="secondary"
+                    on:click=move |_| import()
+                    disabled=move || received.get().trim().is_empty()
+                >
+                    {t(Msg::CheckOffer)}
+                </button>
+            </div>
+            {move || note.get().map(|n| view! { <p class="reminder-note">{n}</p> })}
+            {verdict}
+        </div>
+    }
+}
@@ -0,0 +1,121 @@
+//! Programs and witnesses taken from transactions on chain.
+//!
+//! An input that spends a Simplicity leaf carries, on its witness stack, the
+//! encoded witness values, the encoded program, the leaf script (the CMR)
+//! and the control block, optionally followed by an annex. [`extract`] reads
+//! them back and decodes the program with its witness, so the CMR it
+//! computes is the one the chain checked; [`compare`] puts that CMR next to
+//! the CMR of local source, closing the loop between the playground and a
+//! spend seen on a block explorer.
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::elements::encode::deserialize;
+use simplicityhl::simplicity::elements::taproot::ControlBlock;
+use simplicityhl::simplicity::elements::Transaction;
+use simplicityhl::simplicity::jet::Elements;
+use simplicityhl::simplicity::{leaf_version, BitIter, Cmr, RedeemNode};
+
+use crate::hex;
+
+/// First byte of a taproot annex.
+const ANNEX_TAG: u8 = 0x50;
+
+/// The Simplicity spend in one input of a transaction.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OnChainSpend {
+    pub txid: String,
+    pub input_index: u32,
+    /// Encoded program, hex.
+    pub program: String,
+    /// Encoded witness values, hex.
+    pub witness: String,
+    /// CMR of the decoded program.
+    pub cmr: String,
+    /// CMR committed to by the leaf script; equal to `cmr` in any valid spend.
+    pub leaf_cmr: String,
+    pub control_block: String,
+}
+
+/// An on-chain spend next to the CMR of local source.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SpendComparison {
+    pub spend: OnChainSpend,
+    /// CMR the local source compiles to.
+    pub local_cmr: Option<String>,
+    /// Why the local source did not compile.
+    pub compile_error: Option<String>,
+    pub matches: bool,
+}
+
+/// The witness stack items of a Simplicity leaf spend, without the annex:
+/// witness, program, leaf script and control block.
+fn simplicity_items(stack: &[Vec<u8>]) -> Option<[&[u8]; 4]> {
+    let stack = match stack.split_last() {
+        Some((last, rest)) if stack.len() >= 2 && last.first() == Some(&ANNEX_TAG) => rest,
+        _ => stack,
+    };
+    let [.., witness, program, script, control] = stack else {
+        return None;
+    };
+    let leaf = ControlBlock::from_slice(control).ok()?.leaf_version == leaf_version();
+    (leaf && script.len() == 32).then_some([witness.as_slice(), program.as_slice(), script.as_slice(), control.as_slice()])
+}
+
+/// The Simplicity spend in input `input_index` of `tx_hex`, or in its first
+/// input that spends a Simplicity leaf.
+pub fn extract(tx_hex: &str, input_index: Option<u32>) -> Result<OnChainSpend, String> {
+    let tx: Transaction = deserialize(&hex::decode(tx_hex)?).map_err(|e| format!("Invalid transaction: {e}"))?;
+    let (index, [witness, program, script, control]) = match input_index {
+        Some(index) => {
+            let input = tx
+                .input
+                .get(index as usize)
+                .ok_or_else(|| format!("The transaction has no input {index}"))?;
+            let items = simplicity_items(&input.witness.script_witness)
+                .ok_or_else(|| format!("Input {index} does not spend a Simplicity leaf"))?;
+            (index, items)
+        }
+        None => tx
+            .input
+            .iter()
+            .enumerate()
+            .find_map(|(index, input)| Some((index as u32, simplicity_items(&input.witness.script_witness)?)))
+            .ok_or("No input of the transaction spends a Simplicity leaf")?,
+    };
+    let program_bits = BitIter::from(program.iter().copied());
+    let witness_bits = BitIter::from(witness.iter().copied());
+    let redeemed = RedeemNode::<Elements>::decode(program_bits, witness_bits)
+        .map_err(|e| format!("Input {index}: the program does not decode: {e}"))?;
+    let leaf_cmr: [u8; 32] = script.try_into().expect("checked to be 32 bytes");
+    Ok(OnChainSpend {
+        txid: tx.txid().to_string(),
+        input_index: index,
+        program: hex::encode(program),
+        witness: hex::encode(witness),
+        cmr: redeemed.cmr().to_string(),
+        leaf_cmr: Cmr::from_byte_array(leaf_cmr).to_string(),
+        control_block: hex::encode(control),
+    })
+}
+
+/// Compile `code` with `compile` and compare its CMR with the CMR of `spend`.
+pub fn compare(
+    code: &str,
+    spend: OnChainSpend,
+    compile: impl Fn(&str) -> Result<Cmr, String>,
+) -> SpendComparison {
+    match compile(code) {
+        Ok(cmr) => SpendComparison {
+            matches: cmr.to_string() == spend.cmr,
+            local_cmr: Some(cmr.to_string()),
+            compile_error: None,
+            spend,
+        },
+        Err(e) => SpendComparison {
+            spend,
+            local_cmr: None,
+            compile_error: Some(e),
+            matches: false,
+        },
+    }
+}
@@ -0,0 +1,181 @@
+//! Settings for a compile, as passed from JavaScript.
+//!
+//! ```json
+//! {"debug_symbols": false, "target": "elements"}
+//! ```
+//!
+//! Every field is optional. SimplicityHL 0.3 has no optimization levels:
+//! programs are always shared maximally, so there is nothing to tune there.
+//!
+//! The compiler always resolves jets against the Elements family, which
+//! contains every core jet. The `core` target compiles the same way and then
+//! rejects programs calling a jet outside the core set, so they stay
+//! portable to non-Elements environments.
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::parse::ParseFromStr;
+use simplicityhl::simplicity::dag::{DagLike, MaxSharing};
+use simplicityhl::simplicity::jet::{Core, Elements};
+use simplicityhl::simplicity::node::{Commit, Inner};
+use simplicityhl::simplicity::CommitNode;
+use simplicityhl::{Arguments, CompiledProgram};
+
+use crate::bip32;
+use crate::memory;
+use crate::sanity::{qualified_names, strip_comment};
+use crate::storage;
+
+const OPTIONS_KEY: &str = "compile-options";
+
+/// The jet family a program is compiled against.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Target {
+    /// Core jets plus the Elements transaction jets, as on Liquid.
+    #[default]
+    Elements,
+    /// Core jets only: arithmetic, hashes and signatures.
+    Core,
+}
+
+impl Target {
+    pub const ALL: [Target; 2] = [Target::Elements, Target::Core];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Target::Elements => "elements",
+            Target::Core => "core",
+        }
+    }
+}
+
+impl FromStr for Target {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Target::ALL
+            .into_iter()
+            .find(|t| t.as_str() == s)
+            .ok_or_else(|| format!("Unknown target `{s}`"))
+    }
+}
+
+/// Fail with the source lines of the jets in `program` that are not core jets.
+fn check_core_jets(code: &str, program: &CommitNode<Elements>) -> Result<(), String> {
+    let mut outside: Vec<String> = Vec::new();
+    for item in program.post_order_iter::<MaxSharing<Commit<Elements>>>() {
+        if let Inner::Jet(jet) = item.node.inner() {
+            let name = jet.to_string();
+            if Core::from_str(&name).is_err() && !outside.contains(&name) {
+                outside.push(name);
+            }
+        }
+    }
+    if outside.is_empty() {
+        return Ok(());
+    }
+    let jets: Vec<String> = outside
+        .iter()
+        .map(|name| {
+            let line = code
+                .lines()
+                .position(|line| qualified_names(strip_comment(line), "jet::").contains(&name.as_str()));
+            match line {
+                Some(index) => format!("`jet::{name}` (line {})", index + 1),
+                None => format!("`jet::{name}`"),
+            }
+        })
+        .collect();
+    Err(format!(
+        "{} {} Elements-only; the core target has no transaction or timelock jets",
+        jets.join(", "),
+        if jets.len() == 1 { "is" } else { "are" }
+    ))
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompileOptions {
+    /// Keep `dbg!` calls and the source spans debuggers map them back to.
+    #[serde(default)]
+    pub debug_symbols: bool,
+    #[serde(default)]
+    pub target: Target,
+}
+
+impl CompileOptions {
+    pub fn load() -> Self {
+        storage::load(OPTIONS_KEY)
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Ok(json) = serde_json::to_string(self) {
+            storage::save(OPTIONS_KEY, &json);
+        }
+    }
+
+    /// Read options from JSON; a blank string gives the defaults.
+    pub fn parse(json: &str) -> Result<Self, String> {
+        if json.trim().is_empty() {
+            return Ok(CompileOptions::default());
+        }
+        serde_json::from_str(json).map_err(|e| format!("Invalid compiler options: {}", e))
+    }
+
+    /// Compile `code` with `args` under these options.
+    pub fn compile(&self, code: &str, args: Arguments) -> Result<CompiledProgram, String> {
+        let compiled = memory::track_compile(|| CompiledProgram::new(code, args, self.debug_symbols))?;
+        if self.target == Target::Core {
+            check_core_jets(code, &compiled.commit())?;
+        }
+        Ok(compiled)
+    }
+
+    /// Compile `code` with the parameter values in `args_source`, which is
+    /// `code` itself unless the values come from elsewhere. Key derivations
+    /// are resolved in both first; every entry point compiles through here,
+    /// so they all accept what the compile button accepts.
+    pub fn compile_source(&self, code: &str, args_source: &str) -> Result<CompiledProgram, String> {
+        let code = &expand_keys(code)?;
+        let args_source = &expand_keys(args_source)?;
+        let args = Arguments::parse_from_str(args_source).map_err(|e| format!("Parse error: {}", e))?;
+        self.compile(code, args).map_err(|e| format!("Compilation error: {}", e))
+    }
+
+    /// Distinguishes cache entries compiled under these options; empty for
+    /// the defaults, so their entries are shared with plain compiles.
+    pub fn cache_tag(&self) -> String {
+        if *self == CompileOptions::default() {
+            String::new()
+        } else {
+            serde_json::to_string(self).unwrap_or_default()
+        }
+    }
+}
+
+/// `text` with every `xpub.../path` replaced by the key it derives; see
+/// [`bip32`]. Offsets are kept, so diagnostics, quick fixes and source maps
+/// of the expanded text still point into `text`.
+pub fn expand_keys(text: &str) -> Result<String, String> {
+    bip32::expand_in_place(text).map_err(|e| format!("Key derivation error: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XPUB: &str = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+
+    #[test]
+    fn expanded_keys_keep_offsets() {
+        let text = format!("let key: Pubkey = {XPUB}/0/5; // owner");
+        let expanded = expand_keys(&text).unwrap();
+        let key = bip32::derive_key(XPUB, "0/5").unwrap().pubkey;
+        assert!(expanded.contains(&format!("0x{key}")));
+        assert_eq!(expanded.len(), text.len());
+        assert_eq!(expanded.find("// owner"), text.find("// owner"));
+    }
+}
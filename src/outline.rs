@@ -0,0 +1,136 @@
+//! Document outline: parameters, functions, type aliases and witnesses.
+//!
+//! The outline is read from the source text rather than the compiler's
+//! parse tree, so it stays available while the program is half-written and
+//! does not compile. Parameters and witnesses are listed where they are
+//! declared in a `mod param` or `mod witness` block, or else where the
+//! program first reads them.
+
+use serde::{Deserialize, Serialize};
+
+use crate::sanity::{qualified_names, strip_comment};
+use crate::witness_form;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ItemKind {
+    Parameter,
+    Function,
+    Type,
+    Witness,
+}
+
+impl ItemKind {
+    pub fn icon(self) -> &'static str {
+        match self {
+            ItemKind::Parameter => "⚙",
+            ItemKind::Function => "ƒ",
+            ItemKind::Type => "τ",
+            ItemKind::Witness => "✎",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OutlineItem {
+    pub kind: ItemKind,
+    pub name: String,
+    /// Signature, type or value, e.g. `(pk: Pubkey) -> u256`.
+    pub detail: String,
+    pub line: usize,
+}
+
+/// `NAME: Type = value` from a `const` line.
+fn constant(line: &str) -> Option<(String, String)> {
+    let rest = line.trim().strip_prefix("const ")?.trim_end_matches(';');
+    let (name, detail) = rest.split_once(':')?;
+    Some((name.trim().to_string(), detail.trim().to_string()))
+}
+
+/// The outline of `code`, grouped by kind and in source order within each.
+pub fn outline(code: &str) -> Vec<OutlineItem> {
+    let mut items: Vec<OutlineItem> = Vec::new();
+    // The `mod` block being read, with the depth inside it.
+    let mut module: Option<(ItemKind, usize)> = None;
+    let mut depth = 0usize;
+
+    for (index, line) in code.lines().enumerate() {
+        let line = strip_comment(line);
+        let trimmed = line.trim();
+        let mut push = |kind, name: &str, detail: String| {
+            items.push(OutlineItem {
+                kind,
+                name: name.to_string(),
+                detail,
+                line: index + 1,
+            })
+        };
+
+        match module {
+            Some((kind, _)) => {
+                if let Some((name, detail)) = constant(trimmed) {
+                    push(kind, &name, detail);
+                }
+            }
+            None if depth == 0 => {
+                if let Some(rest) = trimmed.strip_prefix("fn ") {
+                    let (name, signature) = rest.split_at(rest.find('(').unwrap_or(rest.len()));
+                    push(ItemKind::Function, name.trim(), signature.trim_end_matches('{').trim().to_string());
+                } else if let Some(rest) = trimmed.strip_prefix("type ") {
+                    if let Some((name, ty)) = rest.split_once('=') {
+                        push(ItemKind::Type, name.trim(), ty.trim().trim_end_matches(';').trim().to_string());
+                    }
+                } else if trimmed.starts_with("mod param") {
+                    module = Some((ItemKind::Parameter, depth + 1));
+                } else if trimmed.starts_with("mod witness") {
+                    module = Some((ItemKind::Witness, depth + 1));
+                }
+            }
+            None => {}
+        }
+
+        for c in line.chars() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        if module.is_some_and(|(_, inside)| depth < inside) {
+            module = None;
+        }
+    }
+
+    // Names read but never declared.
+    let witness_types = witness_form::fields(code, "");
+    for (index, line) in code.lines().enumerate() {
+        let line = strip_comment(line);
+        for (kind, prefix) in [(ItemKind::Parameter, "param::"), (ItemKind::Witness, "witness::")] {
+            for name in qualified_names(line, prefix) {
+                if items.iter().any(|i| i.kind == kind && i.name == name) {
+                    continue;
+                }
+                let detail = match kind {
+                    ItemKind::Witness => witness_types
+                        .iter()
+                        .find(|f| f.name == name)
+                        .map(|f| f.ty.clone())
+                        .unwrap_or_default(),
+                    _ => "no value set".to_string(),
+                };
+                items.push(OutlineItem {
+                    kind,
+                    name: name.to_string(),
+                    detail,
+                    line: index + 1,
+                });
+            }
+        }
+    }
+
+    let rank = |kind: ItemKind| [ItemKind::Parameter, ItemKind::Type, ItemKind::Function, ItemKind::Witness]
+        .iter()
+        .position(|&k| k == kind);
+    items.sort_by_key(|item| (rank(item.kind), item.line));
+    items
+}
@@ -0,0 +1,61 @@
+//! Clickable outline of the program beside the editor.
+
+use leptos::prelude::*;
+
+use crate::i18n::{self, t, Msg};
+use crate::outline::{self, ItemKind};
+
+#[component]
+pub fn OutlinePanel(
+    #[prop(into)] code: Signal<String>,
+    /// Called with the 1-based line of the chosen item.
+    on_select: Callback<usize>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let items = Memo::new(move |_| code.with(|code| outline::outline(code)));
+
+    let rows = move || {
+        let lang = lang.get();
+        items
+            .get()
+            .into_iter()
+            .map(|item| {
+                let line = item.line;
+                let kind = i18n::text(
+                    lang,
+                    match item.kind {
+                        ItemKind::Parameter => Msg::OutlineParameter,
+                        ItemKind::Function => Msg::OutlineFunction,
+                        ItemKind::Type => Msg::OutlineTypeAlias,
+                        ItemKind::Witness => Msg::OutlineWitness,
+                    },
+                );
+                view! {
+                    <li>
+                        <button
+                            class="outline-item"
+                            title=i18n::format(i18n::text(lang, Msg::OutlineItemTitle), &[kind, &line.to_string()])
+                            on:click=move |_| on_select.run(line)
+                        >
+                            <span class="outline-icon" aria-label=kind>{item.kind.icon()}</span>
+                            <span class="outline-name">{item.name}</span>
+                            <span class="outline-detail">{item.detail}</span>
+                        </button>
+                    </li>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <details class="manifest-editor" open=true>
+            <summary>{t(Msg::Outline)}</summary>
+            <Show
+                when=move || !items.with(Vec::is_empty)
+                fallback=|| view! { <p class="signature-empty">{t(Msg::OutlineEmpty)}</p> }
+            >
+                <ul class="outline">{rows}</ul>
+            </Show>
+        </details>
+    }
+}
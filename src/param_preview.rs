@@ -0,0 +1,77 @@
+//! Value of the parameter under the pointer, editable in place.
+
+use leptos::prelude::*;
+
+use crate::i18n::{t, Msg};
+use crate::param_values;
+
+#[component]
+pub fn ParamPreview(
+    #[prop(into)] code: Signal<String>,
+    /// The parameter last hovered or under the caret; cleared by closing.
+    name: RwSignal<Option<String>>,
+    /// Called with the whole buffer after a value was changed.
+    on_change: Callback<String>,
+) -> impl IntoView {
+    let (draft, set_draft) = signal(String::new());
+    let (error, set_error) = signal::<Option<String>>(None);
+    let param = Memo::new(move |_| {
+        let name = name.get()?;
+        code.with(|code| param_values::params(code).into_iter().find(|p| p.name == name))
+    });
+
+    // Start every edit from the configured value.
+    Effect::new(move |_| {
+        set_draft.set(param.with(|p| p.as_ref().map(|p| p.value.clone()).unwrap_or_default()));
+        set_error.set(None);
+    });
+
+    let apply = move || {
+        let Some(name) = name.get_untracked() else {
+            return;
+        };
+        match code.with_untracked(|code| param_values::set_value(code, &name, &draft.get_untracked())) {
+            Ok(updated) => {
+                set_error.set(None);
+                on_change.run(updated);
+            }
+            Err(e) => set_error.set(Some(e)),
+        }
+    };
+
+    view! {
+        {move || {
+            let shown = name.get()?;
+            Some(view! {
+                <div class="param-preview" role="status">
+                    <span class="witness-field-name">{format!("param::{shown}")}</span>
+                    {move || match param.get() {
+                        Some(param) => view! {
+                            <span class="witness-field-type">{param.ty}</span>
+                            <input
+                                type="text"
+                                spellcheck="false"
+                                aria-label=t(Msg::ParameterValue)
+                                prop:value=move || draft.get()
+                                on:input=move |ev| set_draft.set(event_target_value(&ev))
+                                on:keydown=move |ev: web_sys::KeyboardEvent| match ev.key().as_str() {
+                                    "Enter" => apply(),
+                                    "Escape" => name.set(None),
+                                    _ => {}
+                                }
+                            />
+                            <button class="secondary" on:click=move |_| apply()>{t(Msg::Set)}</button>
+                        }
+                        .into_any(),
+                        None => view! {
+                            <span class="witness-field-hint">{t(Msg::ParamNotDeclared)}</span>
+                        }
+                        .into_any(),
+                    }}
+                    <button class="secondary" title=t(Msg::Close) on:click=move |_| name.set(None)>"✕"</button>
+                    {move || error.get().map(|e| view! { <span class="witness-field-error">{e}</span> })}
+                </div>
+            })
+        }}
+    }
+}
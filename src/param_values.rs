@@ -0,0 +1,151 @@
+//! Values of `mod param` constants, for previewing and editing in place.
+//!
+//! Parameters are configured in the program's own `mod param` block, which
+//! is also what compiles read their arguments from, so editing a value here
+//! rewrites that block and nothing else. Argument files from the command
+//! line workflow (`{"NAME": {"value": ..., "type": ...}}`, like witness files)
+//! are applied the same way.
+
+use serde_json::Value;
+
+use crate::bip32;
+use crate::sanity::{qualified_names, strip_comment};
+use crate::witness_form::InputKind;
+
+/// What a dropped values file holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValuesFile {
+    Witness,
+    Arguments,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParamValue {
+    pub name: String,
+    pub ty: String,
+    pub value: String,
+    /// 0-based index of the `const` line.
+    pub line: usize,
+}
+
+/// The constants declared in the `mod param` block of `code`.
+pub fn params(code: &str) -> Vec<ParamValue> {
+    let mut params = Vec::new();
+    let mut inside = false;
+    for (index, line) in code.lines().enumerate() {
+        let line = strip_comment(line).trim();
+        if line.starts_with("mod param") {
+            inside = !line.ends_with('}');
+            continue;
+        }
+        if !inside {
+            continue;
+        }
+        if line.starts_with('}') {
+            inside = false;
+            continue;
+        }
+        let Some(rest) = line.strip_prefix("const ") else {
+            continue;
+        };
+        let Some((name, rest)) = rest.split_once(':') else {
+            continue;
+        };
+        let Some((ty, value)) = rest.split_once('=') else {
+            continue;
+        };
+        params.push(ParamValue {
+            name: name.trim().to_string(),
+            ty: ty.trim().to_string(),
+            value: value.trim().trim_end_matches(';').trim().to_string(),
+            line: index,
+        });
+    }
+    params
+}
+
+/// The name in the `param::NAME` reference covering byte `offset`, if any.
+pub fn param_at(code: &str, offset: usize) -> Option<String> {
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let offset = offset.min(code.len());
+    if !code.is_char_boundary(offset) {
+        return None;
+    }
+    let start = code[..offset].rfind(|c: char| !is_ident(c) && c != ':').map_or(0, |i| i + 1);
+    let end = code[offset..].find(|c: char| !is_ident(c) && c != ':').map_or(code.len(), |i| offset + i);
+    let name = code[start..end].strip_prefix("param::")?;
+    (!name.is_empty() && !name.contains(':')).then(|| name.to_string())
+}
+
+/// `code` with parameter `name` set to `value`, checked against its type.
+pub fn set_value(code: &str, name: &str, value: &str) -> Result<String, String> {
+    let param = params(code)
+        .into_iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| format!("`{name}` is not declared in `mod param`"))?;
+    let value = value.trim();
+    if !(param.ty == "Pubkey" && bip32::is_xpub_path(value)) {
+        InputKind::of(&param.ty).validate(value)?;
+    }
+
+    let lines: Vec<&str> = code.split('\n').collect();
+    let line = lines[param.line];
+    let indent = &line[..line.len() - line.trim_start().len()];
+    let comment = line.split_once("//").map(|(_, c)| format!(" //{c}")).unwrap_or_default();
+    let replaced = format!("{indent}const {name}: {} = {value};{comment}", param.ty);
+    let mut lines: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+    lines[param.line] = replaced;
+    Ok(lines.join("\n"))
+}
+
+/// Whether `file_name` with contents `json` holds witness values or
+/// arguments for `code`. The extension decides when it is `.args` or `.wit`;
+/// otherwise a file naming only parameters of `code`, and no witness it
+/// reads, is taken as arguments.
+pub fn classify(file_name: &str, json: &str, code: &str) -> ValuesFile {
+    let name = file_name.to_ascii_lowercase();
+    if name.ends_with(".args") || name.ends_with(".args.json") {
+        return ValuesFile::Arguments;
+    }
+    if name.ends_with(".wit") || name.ends_with(".wit.json") {
+        return ValuesFile::Witness;
+    }
+    let Ok(Value::Object(entries)) = serde_json::from_str::<Value>(json) else {
+        return ValuesFile::Witness;
+    };
+    let declared = params(code);
+    let reads_witness =
+        |name: &str| code.lines().any(|line| qualified_names(strip_comment(line), "witness::").contains(&name));
+    let all_params = !entries.is_empty()
+        && entries
+            .keys()
+            .all(|name| declared.iter().any(|p| &p.name == name) && !reads_witness(name));
+    if all_params {
+        ValuesFile::Arguments
+    } else {
+        ValuesFile::Witness
+    }
+}
+
+/// `code` with the `mod param` values set from the argument file `json`.
+/// Returns the new code and how many values were set.
+pub fn apply_arguments(code: &str, json: &str) -> Result<(String, usize), String> {
+    let entries = match serde_json::from_str::<Value>(json) {
+        Ok(Value::Object(entries)) => entries,
+        Ok(_) => return Err("Expected an object mapping names to {\"value\", \"type\"}".to_string()),
+        Err(e) => return Err(format!("Invalid JSON arguments: {}", e)),
+    };
+    let declared = params(code);
+    let mut code = code.to_string();
+    for (name, entry) in &entries {
+        let text = |key: &str| entry.get(key).and_then(Value::as_str);
+        let value = text("value").ok_or_else(|| format!("`{name}` has no string \"value\""))?;
+        if let (Some(ty), Some(param)) = (text("type"), declared.iter().find(|p| &p.name == name)) {
+            if ty.trim() != param.ty {
+                return Err(format!("`{name}` is declared as `{}`, but the file gives a `{ty}`", param.ty));
+            }
+        }
+        code = set_value(&code, name, value).map_err(|e| format!("`{name}`: {e}"))?;
+    }
+    Ok((code, entries.len()))
+}
@@ -0,0 +1,233 @@
+//! JavaScript plugins that extend the playground.
+//!
+//! A plugin is an object passed to `register_plugin` once the module is
+//! initialised. Every hook is optional:
+//!
+//! ```js
+//! register_plugin({
+//!     name: "house-style",
+//!     // Before each compile: the source to compile instead. May return a Promise.
+//!     transformSource(source) { return source.replaceAll("@treasury", "0x79be..."); },
+//!     // After each compile, with the compile result object and the compiled source.
+//!     afterCompile(result, source) { console.log(result.cmr); },
+//!     // A section under the results, redrawn after each compile.
+//!     panel: { title: "Audit notes", render(element, result) { element.textContent = result.cmr ?? ""; } },
+//! });
+//! ```
+//!
+//! Transforms run in registration order, each on the output of the one
+//! before, on the source as written with its `@label`s still in it. What
+//! they return is the program: it is what gets compiled, what every panel
+//! analyses and what exports and addresses come from, while the editor keeps
+//! the source as written. Diagnostics are shown only while the transforms
+//! keep the line count, since their lines would not match the editor
+//! otherwise. A hook that throws or returns the wrong type is logged to the
+//! developer console and skipped, so a broken plugin never stops a compile.
+//! Registering a name again replaces that plugin.
+
+use std::cell::RefCell;
+
+use leptos::prelude::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::logging::{self, Category};
+
+struct Plugin {
+    name: String,
+    object: JsValue,
+}
+
+thread_local! {
+    static PLUGINS: RefCell<Vec<Plugin>> = const { RefCell::new(Vec::new()) };
+    /// The last compile result, for panels drawn after it arrived.
+    static LAST_RESULT: RefCell<JsValue> = RefCell::new(JsValue::NULL);
+    /// Notified when a plugin is registered or a compile finishes.
+    static CHANGED: ArcTrigger = ArcTrigger::new();
+    /// Notified when a plugin is registered, so the program is transformed again.
+    static REGISTERED: ArcTrigger = ArcTrigger::new();
+}
+
+fn changed() {
+    CHANGED.with(|trigger| trigger.notify());
+}
+
+fn get(object: &JsValue, key: &str) -> Option<JsValue> {
+    js_sys::Reflect::get(object, &JsValue::from_str(key))
+        .ok()
+        .filter(|value| !value.is_undefined() && !value.is_null())
+}
+
+fn method(object: &JsValue, key: &str) -> Option<js_sys::Function> {
+    get(object, key)?.dyn_into().ok()
+}
+
+/// Each plugin's name and its `key` method, if it has one.
+fn hooks(key: &str) -> Vec<(String, JsValue, js_sys::Function)> {
+    PLUGINS.with(|plugins| {
+        plugins
+            .borrow()
+            .iter()
+            .filter_map(|p| Some((p.name.clone(), p.object.clone(), method(&p.object, key)?)))
+            .collect()
+    })
+}
+
+fn warn(name: &str, hook: &str, error: impl std::fmt::Debug) {
+    logging::warn(Category::Ui, format!("Plugin `{name}` failed in {hook}: {error:?}"));
+}
+
+/// Add `plugin`, or replace the registered plugin with the same `name`.
+#[wasm_bindgen]
+pub fn register_plugin(plugin: JsValue) -> Result<(), JsValue> {
+    if !plugin.is_object() {
+        return Err(JsValue::from_str("A plugin must be an object"));
+    }
+    let name = get(&plugin, "name")
+        .and_then(|name| name.as_string())
+        .filter(|name| !name.trim().is_empty())
+        .ok_or_else(|| JsValue::from_str("A plugin needs a `name`"))?;
+    PLUGINS.with(|plugins| {
+        let mut plugins = plugins.borrow_mut();
+        plugins.retain(|p| p.name != name);
+        plugins.push(Plugin { name: name.clone(), object: plugin });
+    });
+    logging::info(Category::Ui, format!("Registered plugin `{name}`"));
+    REGISTERED.with(|trigger| trigger.notify());
+    changed();
+    Ok(())
+}
+
+/// Names of the registered plugins, in registration order.
+#[wasm_bindgen]
+pub fn plugin_names() -> Vec<String> {
+    PLUGINS.with(|plugins| plugins.borrow().iter().map(|p| p.name.clone()).collect())
+}
+
+/// Subscribe the running effect to plugin registrations.
+pub fn track_registrations() {
+    REGISTERED.with(|trigger| trigger.track());
+}
+
+/// Whether any plugin transforms the source.
+pub fn has_transforms() -> bool {
+    !hooks("transformSource").is_empty()
+}
+
+/// `source` as the plugins' transforms leave it.
+pub async fn transform_source(source: String) -> String {
+    let mut source = source;
+    for (name, object, transform) in hooks("transformSource") {
+        let mut value = match transform.call1(&object, &JsValue::from_str(&source)) {
+            Ok(value) => value,
+            Err(e) => {
+                warn(&name, "transformSource", e);
+                continue;
+            }
+        };
+        if let Some(promise) = value.dyn_ref::<js_sys::Promise>() {
+            match JsFuture::from(promise.clone()).await {
+                Ok(resolved) => value = resolved,
+                Err(e) => {
+                    warn(&name, "transformSource", e);
+                    continue;
+                }
+            }
+        }
+        match value.as_string() {
+            Some(transformed) => source = transformed,
+            None => warn(&name, "transformSource", "it did not return a string"),
+        }
+    }
+    source
+}
+
+/// Hand `result_json`, the result of compiling `source`, to the plugins and
+/// redraw their panels.
+pub fn after_compile(result_json: &str, source: &str) {
+    let result = js_sys::JSON::parse(result_json).unwrap_or(JsValue::NULL);
+    for (name, object, hook) in hooks("afterCompile") {
+        if let Err(e) = hook.call2(&object, &result, &JsValue::from_str(source)) {
+            warn(&name, "afterCompile", e);
+        }
+    }
+    LAST_RESULT.with(|last| *last.borrow_mut() = result);
+    changed();
+}
+
+/// Bring the panels in `container` in line with the plugins, and redraw
+/// them with the last result. Existing sections are kept, so they stay open
+/// or closed across compiles.
+fn draw_panels(container: &web_sys::Element) -> Result<(), JsValue> {
+    let document = web_sys::window()
+        .and_then(|w| w.document())
+        .ok_or_else(|| JsValue::from_str("No document"))?;
+    let result = LAST_RESULT.with(|last| last.borrow().clone());
+    let panels: Vec<(String, JsValue, js_sys::Function)> = PLUGINS.with(|plugins| {
+        plugins
+            .borrow()
+            .iter()
+            .filter_map(|p| {
+                let panel = get(&p.object, "panel")?;
+                let render = method(&panel, "render")?;
+                Some((p.name.clone(), panel, render))
+            })
+            .collect()
+    });
+
+    // Drop the sections of plugins that are gone or replaced without a panel.
+    let children = container.children();
+    for index in (0..children.length()).rev() {
+        if let Some(child) = children.item(index) {
+            let owner = child.get_attribute("data-plugin").unwrap_or_default();
+            if !panels.iter().any(|(name, ..)| *name == owner) {
+                child.remove();
+            }
+        }
+    }
+
+    for (name, panel, render) in panels {
+        let selector = format!("[data-plugin=\"{}\"]", name.replace('\\', "\\\\").replace('"', "\\\""));
+        let section = match container.query_selector(&selector)? {
+            Some(section) => section,
+            None => {
+                let section = document.create_element("details")?;
+                section.set_class_name("manifest-editor");
+                section.set_attribute("data-plugin", &name)?;
+                let summary = document.create_element("summary")?;
+                let title = get(&panel, "title").and_then(|t| t.as_string()).unwrap_or_else(|| name.clone());
+                summary.set_text_content(Some(&format!("🧩 {title}")));
+                section.append_child(&summary)?;
+                section.append_child(&document.create_element("div")?)?;
+                container.append_child(&section)?;
+                section
+            }
+        };
+        let Some(body) = section.last_element_child() else {
+            continue;
+        };
+        body.set_inner_html("");
+        if let Err(e) = render.call2(&panel, &body, &result) {
+            warn(&name, "panel.render", e);
+        }
+    }
+    Ok(())
+}
+
+/// Sections drawn by the plugins' `panel` hooks.
+#[component]
+pub fn PluginPanels() -> impl IntoView {
+    let container = NodeRef::<leptos::html::Div>::new();
+
+    Effect::new(move |_| {
+        CHANGED.with(|trigger| trigger.track());
+        if let Some(container) = container.get() {
+            if let Err(e) = draw_panels(&container) {
+                logging::warn(Category::Ui, format!("Could not draw plugin panels: {e:?}"));
+            }
+        }
+    });
+
+    view! { <div class="plugin-panels" node_ref=container></div> }
+}
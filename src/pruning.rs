@@ -0,0 +1,93 @@
+//! What redeeming with a particular witness prunes from a program.
+//!
+//! Satisfying a program replaces every `case` whose branch the witness does
+//! not take with an assertion that commits to the unused branch by its CMR
+//! alone. The unused subtree, including any witness data it would need, is
+//! left out of the transaction. On Liquid the program and witness are
+//! witness data, so every byte pruned saves one weight unit.
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::dag::{DagLike, MaxSharing};
+use simplicityhl::simplicity::jet::Elements;
+use simplicityhl::simplicity::node::{Commit, Inner, Redeem};
+use simplicityhl::simplicity::{Cmr, CommitNode, RedeemNode};
+use simplicityhl::WitnessValues;
+
+use crate::options::CompileOptions;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PrunedBranch {
+    /// CMR of the removed subtree.
+    pub cmr: String,
+    /// Which side of its `case` the subtree was on: `left` or `right`.
+    pub side: String,
+    /// Distinct nodes in the subtree.
+    pub nodes: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct PruningReport {
+    pub commit_nodes: usize,
+    pub redeem_nodes: usize,
+    /// `case` nodes left with both branches.
+    pub kept_cases: usize,
+    pub pruned: Vec<PrunedBranch>,
+    /// Encoded program without witness data, before pruning.
+    pub commit_bytes: usize,
+    /// Encoded program after pruning.
+    pub redeem_bytes: usize,
+    pub witness_bytes: usize,
+    /// Weight units saved by pruning.
+    pub saved_weight: usize,
+}
+
+/// Distinct nodes under the commit node with CMR `cmr`, if there is one.
+fn subtree_size(program: &CommitNode<Elements>, cmr: Cmr) -> Option<usize> {
+    program
+        .post_order_iter::<MaxSharing<Commit<Elements>>>()
+        .find(|item| item.node.cmr() == cmr)
+        .map(|item| item.node.post_order_iter::<MaxSharing<Commit<Elements>>>().count())
+}
+
+/// Compare `committed` with its redemption `redeemed`.
+pub fn report(committed: &CommitNode<Elements>, redeemed: &RedeemNode<Elements>) -> PruningReport {
+    let (program, witness) = redeemed.to_vec_with_witness();
+    let commit_bytes = committed.to_vec_without_witness().len();
+    let mut report = PruningReport {
+        commit_nodes: committed.post_order_iter::<MaxSharing<Commit<Elements>>>().count(),
+        commit_bytes,
+        redeem_bytes: program.len(),
+        witness_bytes: witness.len(),
+        saved_weight: commit_bytes.saturating_sub(program.len()),
+        ..PruningReport::default()
+    };
+
+    for item in redeemed.post_order_iter::<MaxSharing<Redeem<Elements>>>() {
+        report.redeem_nodes += 1;
+        let (side, cmr) = match item.node.inner() {
+            Inner::Case(..) => {
+                report.kept_cases += 1;
+                continue;
+            }
+            Inner::AssertL(_, cmr) => ("right", *cmr),
+            Inner::AssertR(cmr, _) => ("left", *cmr),
+            _ => continue,
+        };
+        report.pruned.push(PrunedBranch {
+            cmr: cmr.to_string(),
+            side: side.to_string(),
+            nodes: subtree_size(committed, cmr).unwrap_or(0),
+        });
+    }
+    report
+}
+
+/// Compile `code`, satisfy it with `witness_json` and report the pruning.
+pub fn analyze(code: &str, witness_json: &str) -> Result<PruningReport, String> {
+    let compiled = CompileOptions::default().compile_source(code, code)?;
+    let witness = serde_json::from_str::<WitnessValues>(witness_json)
+        .map_err(|e| format!("Invalid JSON witness data: {}", e))?;
+    let committed = compiled.commit();
+    let satisfied = compiled.satisfy(witness).map_err(|e| format!("Witness error: {}", e))?;
+    Ok(report(&committed, satisfied.redeem()))
+}
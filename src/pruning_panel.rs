@@ -0,0 +1,95 @@
+//! Pruning report for the current witness.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::api_types::PruningResult;
+use crate::compiler;
+use crate::i18n::{self, t, Msg};
+
+#[component]
+pub fn PruningPanel(
+    #[prop(into)] code: Signal<String>,
+    #[prop(into)] witness: Signal<String>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let (result, set_result) = signal::<Option<PruningResult>>(None);
+    let (busy, set_busy) = signal(false);
+
+    let analyze = move |_| {
+        let code = code.get_untracked();
+        let witness = witness.get_untracked();
+        set_busy.set(true);
+        spawn_local(async move {
+            let json = compiler::pruning_report(&code, &witness).await;
+            set_result.set(Some(serde_json::from_str::<PruningResult>(&json).unwrap_or_else(|e| PruningResult {
+                report: None,
+                error: Some(e.to_string()),
+            })));
+            set_busy.set(false);
+        });
+    };
+
+    let report = move || {
+        let result = result.get()?;
+        if let Some(e) = result.error {
+            return Some(view! { <p class="tool-error">{e}</p> }.into_any());
+        }
+        let report = result.report?;
+        let summary = i18n::format(
+            i18n::text(lang.get(), Msg::PruningSummary),
+            &[
+                &report.redeem_nodes.to_string(),
+                &report.commit_nodes.to_string(),
+                &report.pruned.len().to_string(),
+                &report.kept_cases.to_string(),
+                &report.commit_bytes.to_string(),
+                &report.redeem_bytes.to_string(),
+                &report.witness_bytes.to_string(),
+                &report.saved_weight.to_string(),
+            ],
+        );
+        let rows = report
+            .pruned
+            .into_iter()
+            .map(|branch| {
+                view! {
+                    <tr>
+                        <td>{branch.side}</td>
+                        <td>{branch.nodes}</td>
+                        <td class="bulk-address">{branch.cmr}</td>
+                    </tr>
+                }
+            })
+            .collect_view();
+        Some(
+            view! {
+                <p class="bulk-summary" role="status">{summary}</p>
+                <table class="bulk-table">
+                    <thead>
+                        <tr>
+                            <th>{t(Msg::Side)}</th>
+                            <th>{t(Msg::Nodes)}</th>
+                            <th>{t(Msg::PrunedSubtreeCmr)}</th>
+                        </tr>
+                    </thead>
+                    <tbody>{rows}</tbody>
+                </table>
+            }
+            .into_any(),
+        )
+    };
+
+    view! {
+        <div class="button-group">
+            <button
+                class="secondary"
+                on:click=analyze
+                disabled=move || busy.get() || witness.get().trim().is_empty()
+            >
+                {t(Msg::PruningReport)}
+            </button>
+        </div>
+        {report}
+    }
+}
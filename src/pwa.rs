@@ -0,0 +1,111 @@
+//! Offline support and installation as an app.
+//!
+//! `sw.js`, copied next to `index.html` by Trunk, caches the page and every
+//! same-origin asset it fetches, so once loaded the compiler keeps working
+//! without a network. It is registered with the crate version, which names
+//! its cache: a new release starts a fresh cache and drops the old one. The
+//! page loads its WASM before the worker takes control, so after
+//! registration it sends the worker the assets already fetched, plus the
+//! separately built core under `lazy-core`, to cache up front.
+//!
+//! Browsers that can install the app fire `beforeinstallprompt`; the event is
+//! held back so the header can offer an Install button instead of the
+//! browser's own banner. Only the full-page app registers; embedded
+//! playgrounds leave the host page's worker and install flow alone.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::logging::{self, js_error, Category};
+
+#[wasm_bindgen(inline_js = r#"
+let deferred = null;
+let listener = null;
+function notify() {
+    if (listener) listener(deferred !== null);
+}
+export function install_listen() {
+    window.addEventListener('beforeinstallprompt', (event) => {
+        event.preventDefault();
+        deferred = event;
+        notify();
+    });
+    window.addEventListener('appinstalled', () => {
+        deferred = null;
+        notify();
+    });
+}
+export function install_on_change(callback) {
+    listener = callback;
+    notify();
+}
+export async function install_prompt() {
+    const event = deferred;
+    if (event === null) return false;
+    deferred = null;
+    notify();
+    event.prompt();
+    return (await event.userChoice).outcome === 'accepted';
+}
+export async function sw_register(url, extra) {
+    if (!('serviceWorker' in navigator)) return false;
+    await navigator.serviceWorker.register(url);
+    const registration = await navigator.serviceWorker.ready;
+    const urls = performance.getEntriesByType('resource')
+        .map((entry) => entry.name)
+        .concat([location.href], extra)
+        .map((u) => new URL(u, document.baseURI))
+        .filter((u) => u.origin === location.origin)
+        .map((u) => u.href);
+    registration.active.postMessage({ type: 'precache', urls });
+    return true;
+}
+"#)]
+extern "C" {
+    fn install_listen();
+    fn install_on_change(callback: &Closure<dyn FnMut(bool)>);
+    fn install_prompt() -> js_sys::Promise;
+    fn sw_register(url: &str, extra: &js_sys::Array) -> js_sys::Promise;
+}
+
+/// Assets fetched on demand rather than at load, to cache ahead of use.
+fn deferred_assets() -> js_sys::Array {
+    let assets = js_sys::Array::new();
+    #[cfg(feature = "lazy-core")]
+    {
+        let core = crate::compiler::core_url();
+        if let Some(stem) = core.strip_suffix(".js") {
+            assets.push(&JsValue::from_str(&format!("{stem}_bg.wasm")));
+        }
+        assets.push(&JsValue::from_str(&core));
+    }
+    assets
+}
+
+/// Register the service worker and start listening for install offers.
+pub fn register() {
+    install_listen();
+    spawn_local(async {
+        let url = format!("sw.js?v={}", env!("CARGO_PKG_VERSION"));
+        if let Err(e) = JsFuture::from(sw_register(&url, &deferred_assets())).await {
+            logging::warn(Category::Worker, format!("Service worker registration failed: {}", js_error(e)));
+        }
+    });
+}
+
+/// Whether the browser currently offers to install the app.
+pub fn install_available() -> ReadSignal<bool> {
+    let (available, set_available) = signal(false);
+    let callback = Closure::<dyn FnMut(bool)>::new(move |offered| set_available.set(offered));
+    install_on_change(&callback);
+    callback.forget();
+    available
+}
+
+/// Show the browser's install dialog; `true` if the user accepted.
+pub async fn install() -> Result<bool, String> {
+    let accepted = JsFuture::from(install_prompt()).await.map_err(js_error)?;
+    Ok(accepted.as_bool().unwrap_or(false))
+}
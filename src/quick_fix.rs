@@ -0,0 +1,183 @@
+//! Suggested edits for diagnostics with an obvious fix.
+//!
+//! [`attach`] looks at compiler diagnostics for two mistakes that can be
+//! repaired without guessing at intent: a statement missing its `;`, and a
+//! jet name one or two typos away from a real jet. Jet names are long and
+//! easy to mistype, so an unknown jet's message also lists the nearest real
+//! ones, by edit distance. [`unused_witnesses`]
+//! adds warnings, with fixes, for witness values bound to a variable that is
+//! never read; the witness would still have to be supplied to spend.
+
+use simplicityhl::simplicity::jet::Elements;
+
+use crate::diagnostics::{Diagnostic, Fix, Severity};
+use crate::sanity::{qualified_names, strip_comment};
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Byte offset of the start of each line of `code`.
+fn line_starts(code: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(code.match_indices('\n').map(|(at, _)| at + 1))
+        .collect()
+}
+
+/// Edits needed to turn `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substituted = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substituted.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Jets whose names are within a few typos of `name`, nearest first, at
+/// most `count` of them.
+pub fn similar_jets(name: &str, count: usize) -> Vec<String> {
+    let limit = (name.len() / 4).max(2);
+    let mut close: Vec<(usize, String)> = Elements::ALL
+        .iter()
+        .map(|jet| jet.to_string())
+        .map(|candidate| (edit_distance(name, &candidate), candidate))
+        .filter(|(distance, _)| *distance <= limit)
+        .collect();
+    close.sort();
+    close.into_iter().take(count).map(|(_, candidate)| candidate).collect()
+}
+
+/// The name in "Jet `name` does not exist".
+fn unknown_jet(message: &str) -> Option<&str> {
+    let at = message.find("Jet `").or_else(|| message.find("jet `"))? + "jet `".len();
+    let name = &message[at..at + message[at..].find('`')?];
+    message[at..].contains("does not exist").then_some(name)
+}
+
+/// "`jet::a`", "`jet::a` or `jet::b`", "`jet::a`, `jet::b` or `jet::c`".
+fn did_you_mean(names: &[String]) -> String {
+    let quoted: Vec<String> = names.iter().map(|name| format!("`jet::{name}`")).collect();
+    match quoted.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} or {last}", rest.join(", ")),
+        None => String::new(),
+    }
+}
+
+/// Replace the misspelt jet in "Jet `name` does not exist".
+fn jet_fix(code: &str, diagnostic: &Diagnostic) -> Option<Fix> {
+    let name = unknown_jet(&diagnostic.message)?;
+    let replacement = similar_jets(name, 1).pop()?;
+    // Prefer the use on the diagnostic's line, if the error points at one.
+    let starts = line_starts(code);
+    let line_start = starts.get(diagnostic.line.saturating_sub(1)).copied().unwrap_or(0);
+    let call = format!("jet::{name}");
+    let found = code[line_start..]
+        .match_indices(&call)
+        .map(|(offset, _)| line_start + offset)
+        .chain(code.match_indices(&call).map(|(offset, _)| offset))
+        .find(|&offset| !code[offset + call.len()..].starts_with(is_ident_char))?;
+    let start = found + "jet::".len();
+    Some(Fix {
+        title: format!("Replace with `jet::{replacement}`"),
+        start,
+        end: start + name.len(),
+        replacement,
+    })
+}
+
+/// Add the `;` missing after the statement before a parse error.
+fn semicolon_fix(code: &str, diagnostic: &Diagnostic) -> Option<Fix> {
+    let message = diagnostic.message.to_lowercase();
+    if !(message.contains("expected") || message.contains("parse")) {
+        return None;
+    }
+    let starts = line_starts(code);
+    let lines: Vec<&str> = code.split('\n').collect();
+    // The last line with code before the one the parser stopped at.
+    let index = (0..diagnostic.line.saturating_sub(1).min(lines.len()))
+        .rev()
+        .find(|&i| !strip_comment(lines[i]).trim().is_empty())?;
+    let statement = strip_comment(lines[index]).trim_end();
+    let trimmed = statement.trim_start();
+    let looks_like_statement = trimmed.starts_with("let ") || trimmed.ends_with(')');
+    if !looks_like_statement || statement.ends_with([';', '{', '}', '(', ',']) {
+        return None;
+    }
+    let at = starts[index] + statement.len();
+    Some(Fix {
+        title: "Insert `;`".to_string(),
+        start: at,
+        end: at,
+        replacement: ";".to_string(),
+    })
+}
+
+/// Give each error in `diagnostics` of `code` a fix, where one is obvious,
+/// and name the jets an unknown jet may have meant.
+pub fn attach(code: &str, diagnostics: &mut [Diagnostic]) {
+    for diagnostic in diagnostics.iter_mut().filter(|d| d.severity == Severity::Error && d.fix.is_none()) {
+        diagnostic.fix = jet_fix(code, diagnostic).or_else(|| semicolon_fix(code, diagnostic));
+        let similar = unknown_jet(&diagnostic.message).map(|name| similar_jets(name, 3)).unwrap_or_default();
+        if !similar.is_empty() {
+            let message = diagnostic.message.trim_end_matches('.');
+            diagnostic.message = format!("{message}; did you mean {}?", did_you_mean(&similar));
+        }
+    }
+}
+
+/// Warnings for `let name = witness::NAME;` bindings whose variable is never
+/// read, each with a fix that removes the line.
+pub fn unused_witnesses(code: &str) -> Vec<Diagnostic> {
+    let starts = line_starts(code);
+    let lines: Vec<&str> = code.split('\n').collect();
+    let mut findings = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        let statement = strip_comment(line).trim();
+        let Some(binding) = statement.strip_prefix("let ") else {
+            continue;
+        };
+        let Some(witness) = qualified_names(statement, "witness::").first().copied() else {
+            continue;
+        };
+        let name: String = binding.trim_start().chars().take_while(|&c| is_ident_char(c)).collect();
+        if name.is_empty() || name.starts_with('_') || !statement.ends_with(';') {
+            continue;
+        }
+        let used = lines[index + 1..].iter().any(|later| {
+            let later = strip_comment(later);
+            later.match_indices(name.as_str()).any(|(at, _)| {
+                let before = later[..at].chars().next_back();
+                let after = later[at + name.len()..].chars().next();
+                !before.is_some_and(|c| is_ident_char(c) || c == ':') && !after.is_some_and(is_ident_char)
+            })
+        });
+        if used {
+            continue;
+        }
+        let end = starts.get(index + 1).copied().unwrap_or(code.len());
+        findings.push(Diagnostic {
+            fix: Some(Fix {
+                title: "Remove the unused binding".to_string(),
+                start: starts[index],
+                end,
+                replacement: String::new(),
+            }),
+            ..Diagnostic::warning(
+                index + 1,
+                1,
+                format!(
+                    "`{name}` holds `witness::{witness}` but is never used; spenders must still supply the witness."
+                ),
+            )
+        });
+    }
+    findings
+}
@@ -0,0 +1,90 @@
+//! History of recently compiled programs, kept in IndexedDB.
+//!
+//! Every successful compile records the source with its CMR, newest first
+//! and at most [`MAX_RECENT`] entries. Recompiling the same source moves its
+//! entry to the top instead of adding another. The history is separate from
+//! explicit saves: it is a safety net, not a file list.
+
+use serde::{Deserialize, Serialize};
+
+use crate::idb;
+use crate::metadata;
+use crate::timestamp::Stamp;
+
+/// Entries kept; older ones are dropped as new ones arrive.
+pub const MAX_RECENT: usize = 20;
+
+const STORE: &str = "recent";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RecentEntry {
+    /// IndexedDB key; absent until stored.
+    #[serde(default)]
+    pub id: Option<u32>,
+    pub name: String,
+    pub timestamp: Stamp,
+    pub cmr: String,
+    pub source: String,
+}
+
+impl RecentEntry {
+    pub fn new(source: &str, cmr: &str) -> Self {
+        RecentEntry {
+            id: None,
+            name: program_name(source, cmr),
+            timestamp: Stamp::now(),
+            cmr: cmr.to_string(),
+            source: source.to_string(),
+        }
+    }
+}
+
+/// The name in the metadata header of `source`, or else its first line
+/// comment, if it has one.
+pub(crate) fn comment_name(source: &str) -> Option<String> {
+    if let Some(name) = metadata::parse(source).and_then(|m| m.name) {
+        return Some(name);
+    }
+    source
+        .lines()
+        .map(str::trim)
+        .find_map(|line| line.strip_prefix("//"))
+        .map(|comment| comment.trim_start_matches('/').trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+/// The first line comment of `source`, or a name from the CMR.
+fn program_name(source: &str, cmr: &str) -> String {
+    comment_name(source).unwrap_or_else(|| format!("Program {}", &cmr[..cmr.len().min(8)]))
+}
+
+/// Every entry, newest first.
+pub async fn list() -> Result<Vec<RecentEntry>, String> {
+    let mut entries: Vec<RecentEntry> = idb::all(STORE).await?;
+    entries.sort_by(|a, b| b.timestamp.unix_ms.cmp(&a.timestamp.unix_ms));
+    Ok(entries)
+}
+
+pub async fn delete(id: u32) -> Result<(), String> {
+    idb::delete(STORE, id).await
+}
+
+/// Record `entry`, replacing an older entry with the same source and
+/// dropping the oldest beyond [`MAX_RECENT`].
+pub async fn record(entry: RecentEntry) -> Result<(), String> {
+    let existing = list().await?;
+    for stale in existing.iter().filter(|e| e.source == entry.source) {
+        if let Some(id) = stale.id {
+            delete(id).await?;
+        }
+    }
+    idb::put(STORE, &entry).await?;
+
+    let kept = existing.iter().filter(|e| e.source != entry.source);
+    for old in kept.skip(MAX_RECENT - 1) {
+        if let Some(id) = old.id {
+            delete(id).await?;
+        }
+    }
+    Ok(())
+}
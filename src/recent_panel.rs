@@ -0,0 +1,97 @@
+//! History drawer listing recently compiled programs.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::i18n::{t, Msg};
+use crate::recent::{self, RecentEntry};
+
+#[component]
+pub fn RecentPanel(
+    /// CMR of the last successful compile; the list reloads when it changes.
+    #[prop(into)]
+    cmr: Signal<Option<String>>,
+    /// Called with the source of the entry to restore.
+    on_restore: Callback<String>,
+) -> impl IntoView {
+    let (open, set_open) = signal(false);
+    let (entries, set_entries) = signal::<Vec<RecentEntry>>(Vec::new());
+    let (error, set_error) = signal::<Option<String>>(None);
+
+    let reload = move || {
+        spawn_local(async move {
+            match recent::list().await {
+                Ok(list) => {
+                    set_error.set(None);
+                    set_entries.set(list);
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    Effect::new(move |_| {
+        let _ = cmr.get();
+        if open.get() {
+            reload();
+        }
+    });
+
+    let remove = move |id: u32| {
+        spawn_local(async move {
+            if let Err(e) = recent::delete(id).await {
+                set_error.set(Some(e));
+            }
+            reload();
+        });
+    };
+
+    let rows = move || {
+        entries
+            .get()
+            .into_iter()
+            .map(|entry| {
+                let source = entry.source.clone();
+                let id = entry.id;
+                view! {
+                    <li class="signature-row">
+                        <span>{entry.name}</span>
+                        <span class="reminder-note">
+                            {format!("{} · {}…", entry.timestamp.to_locale_string(), &entry.cmr[..entry.cmr.len().min(12)])}
+                        </span>
+                        <button class="secondary" on:click=move |_| on_restore.run(source.clone())>
+                            {t(Msg::Restore)}
+                        </button>
+                        <button
+                            class="danger"
+                            disabled=id.is_none()
+                            on:click=move |_| {
+                                if let Some(id) = id {
+                                    remove(id);
+                                }
+                            }
+                        >
+                            "🗑️"
+                        </button>
+                    </li>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <details
+            class="manifest-editor"
+            on:toggle=move |ev| set_open.set(event_target::<web_sys::Element>(&ev).has_attribute("open"))
+        >
+            <summary>{t(Msg::History)}</summary>
+            {move || error.get().map(|e| view! { <p class="tool-error">{e}</p> })}
+            <Show
+                when=move || !entries.with(Vec::is_empty)
+                fallback=|| view! { <p class="signature-empty">{t(Msg::HistoryEmpty)}</p> }
+            >
+                <ul class="signature-list">{rows}</ul>
+            </Show>
+        </details>
+    }
+}
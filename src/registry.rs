@@ -0,0 +1,154 @@
+//! Saved programs indexed by CMR, kept in IndexedDB.
+//!
+//! Every save of a contract, to a file or as a snapshot, records its source
+//! under the CMR it compiles to, so a root seen on chain leads back to the
+//! source that commits to it. Entries are keyed by CMR: saving a source that
+//! compiles to a CMR already present renames the entry and keeps its tags.
+//! Unlike the history of recent compiles, nothing is dropped; entries go
+//! when they are deleted.
+//!
+//! Entries carry free-form tags, such as `mainnet`, `audited` or `v2`, kept
+//! lower-case with dashes for spaces so that `Audited` and `audited` are one
+//! tag. The store indexes them, for listing a tag's entries directly.
+
+use serde::{Deserialize, Serialize};
+
+use crate::idb;
+use crate::timestamp::Stamp;
+
+const STORE: &str = "registry";
+const TAGS_INDEX: &str = "tags";
+
+/// Shortest hex term read as a CMR prefix rather than part of a name.
+const MIN_CMR_PREFIX: usize = 4;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RegistryEntry {
+    pub cmr: String,
+    /// The contract the source was saved as.
+    pub name: String,
+    /// The snapshot revision, for saves that recorded one.
+    #[serde(default)]
+    pub revision: Option<u32>,
+    pub source: String,
+    /// When the source was last saved.
+    pub saved: Stamp,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl RegistryEntry {
+    pub fn new(name: &str, revision: Option<u32>, source: &str, cmr: &str) -> Self {
+        RegistryEntry {
+            cmr: cmr.to_string(),
+            name: name.to_string(),
+            revision,
+            source: source.to_string(),
+            saved: Stamp::now(),
+            tags: Vec::new(),
+        }
+    }
+
+    /// Whether the entry matches every term of `query`. A term is a tag as
+    /// `tag:NAME` or `#NAME`, a CMR prefix of at least four hex digits, or
+    /// part of the name; case is ignored throughout.
+    pub fn matches(&self, query: &str) -> bool {
+        let name = self.name.to_lowercase();
+        query.split_whitespace().map(str::to_lowercase).all(|term| {
+            if let Some(tag) = term.strip_prefix("tag:").or_else(|| term.strip_prefix('#')) {
+                return self.tags.iter().any(|t| t.to_lowercase() == tag);
+            }
+            let hex = term.len() >= MIN_CMR_PREFIX && term.chars().all(|c| c.is_ascii_hexdigit());
+            (hex && self.cmr.starts_with(&term)) || name.contains(&term)
+        })
+    }
+}
+
+/// `tag` in its stored form, or `None` if it is blank.
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let words: Vec<&str> = tag.trim().trim_start_matches('#').split_whitespace().collect();
+    (!words.is_empty()).then(|| words.join("-").to_lowercase())
+}
+
+/// The tags in `text`, separated by commas, without repeats.
+pub fn parse_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in text.split(',').filter_map(normalize_tag) {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+/// Every tag used in `entries`, sorted.
+pub fn all_tags(entries: &[RegistryEntry]) -> Vec<String> {
+    let mut tags: Vec<String> = entries.iter().flat_map(|entry| entry.tags.iter().cloned()).collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+/// The tags of every entry saved as `name`, sorted.
+pub fn contract_tags(entries: &[RegistryEntry], name: &str) -> Vec<String> {
+    let mut tags: Vec<String> = entries
+        .iter()
+        .filter(|entry| entry.name == name)
+        .flat_map(|entry| entry.tags.iter().cloned())
+        .collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+/// The entries of `entries` matching `query`, exact CMR matches first.
+pub fn search<'a>(entries: &'a [RegistryEntry], query: &str) -> Vec<&'a RegistryEntry> {
+    let exact = query.trim().to_lowercase();
+    let mut found: Vec<&RegistryEntry> = entries.iter().filter(|entry| entry.matches(query)).collect();
+    found.sort_by_key(|entry| entry.cmr != exact);
+    found
+}
+
+/// Every entry, most recently saved first.
+pub async fn list() -> Result<Vec<RegistryEntry>, String> {
+    let mut entries: Vec<RegistryEntry> = idb::all(STORE).await?;
+    entries.sort_by(|a, b| b.saved.unix_ms.cmp(&a.saved.unix_ms));
+    Ok(entries)
+}
+
+/// Record `entry`, keeping the tags of the entry it replaces.
+pub async fn register(mut entry: RegistryEntry) -> Result<(), String> {
+    if let Some(existing) = list().await?.into_iter().find(|e| e.cmr == entry.cmr) {
+        for tag in existing.tags {
+            if !entry.tags.contains(&tag) {
+                entry.tags.push(tag);
+            }
+        }
+    }
+    idb::put(STORE, &entry).await
+}
+
+/// The entries tagged `tag`, most recently saved first.
+pub async fn tagged(tag: &str) -> Result<Vec<RegistryEntry>, String> {
+    let Some(tag) = normalize_tag(tag) else {
+        return Ok(Vec::new());
+    };
+    let mut entries: Vec<RegistryEntry> = idb::by_index(STORE, TAGS_INDEX, &tag).await?;
+    entries.sort_by(|a, b| b.saved.unix_ms.cmp(&a.saved.unix_ms));
+    Ok(entries)
+}
+
+/// Replace the tags of the entry with `cmr`.
+pub async fn set_tags(cmr: &str, tags: &[String]) -> Result<(), String> {
+    let mut entry = list()
+        .await?
+        .into_iter()
+        .find(|e| e.cmr == cmr)
+        .ok_or_else(|| format!("No saved program has CMR {cmr}"))?;
+    entry.tags = parse_tags(&tags.join(","));
+    idb::put(STORE, &entry).await
+}
+
+pub async fn delete(cmr: &str) -> Result<(), String> {
+    idb::delete(STORE, cmr).await
+}
@@ -0,0 +1,265 @@
+//! Registry drawer: saved programs searchable by name, CMR prefix and tag,
+//! with their tags edited in place.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::address_book;
+use crate::api_types::CompileResult;
+use crate::compiler;
+use crate::i18n::{self, t, Msg};
+use crate::registry::{self, RegistryEntry};
+use crate::snapshots;
+
+/// Compile `source`, as saved under `name`, and record it under its CMR.
+pub(crate) async fn register_saved(name: &str, revision: Option<u32>, source: &str) -> Result<String, String> {
+    let expanded = address_book::expand_all(source);
+    let result = compiler::compile_simplicity(&expanded).await;
+    let cmr = match serde_json::from_str::<CompileResult>(&result).map_err(|e| e.to_string())? {
+        CompileResult { cmr: Some(cmr), .. } => cmr,
+        CompileResult { error, .. } => return Err(error.unwrap_or_else(|| "No CMR".to_string())),
+    };
+    registry::register(RegistryEntry::new(name, revision, source, &cmr)).await?;
+    Ok(cmr)
+}
+
+/// `tags` as chips; clicking one calls `on_pick` with it.
+pub(crate) fn tag_chips(tags: &[String], on_pick: Callback<String>) -> impl IntoView {
+    tags.iter()
+        .map(|tag| {
+            let picked = tag.clone();
+            view! {
+                <button class="registry-tag" title=t(Msg::ShowOnlyTag) on:click=move |_| on_pick.run(picked.clone())>
+                    {format!("#{tag}")}
+                </button>
+            }
+        })
+        .collect_view()
+}
+
+#[component]
+pub fn RegistryPanel(
+    /// Bumped whenever a save is registered; the list reloads.
+    #[prop(into)]
+    saves: Signal<u32>,
+    /// Called with the source of the entry to open.
+    on_restore: Callback<String>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let (open, set_open) = signal(false);
+    let (entries, set_entries) = signal::<Vec<RegistryEntry>>(Vec::new());
+    let (query, set_query) = signal(String::new());
+    let (note, set_note) = signal::<Option<String>>(None);
+    let (error, set_error) = signal::<Option<String>>(None);
+
+    let reload = move || {
+        spawn_local(async move {
+            match registry::list().await {
+                Ok(list) => {
+                    set_error.set(None);
+                    set_entries.set(list);
+                }
+                Err(e) => set_error.set(Some(e)),
+            }
+        });
+    };
+
+    Effect::new(move |_| {
+        let _ = saves.get();
+        if open.get() {
+            reload();
+        }
+    });
+
+    // Saves from before the registry existed are only in the snapshots.
+    let index_snapshots = move |_| {
+        let lang = lang.get_untracked();
+        set_note.set(Some(i18n::text(lang, Msg::IndexingContracts).to_string()));
+        spawn_local(async move {
+            let (mut indexed, mut failed) = (0, 0);
+            for contract in snapshots::load() {
+                for revision in &contract.revisions {
+                    match register_saved(&contract.name, Some(revision.number), &revision.source).await {
+                        Ok(_) => indexed += 1,
+                        Err(_) => failed += 1,
+                    }
+                }
+            }
+            let indexed = indexed.to_string();
+            set_note.set(Some(match failed {
+                0 => i18n::format(i18n::text(lang, Msg::IndexedRevisions), &[&indexed]),
+                _ => i18n::format(i18n::text(lang, Msg::IndexedRevisionsFailed), &[&indexed, &failed.to_string()]),
+            }));
+            reload();
+        });
+    };
+
+    let remove = move |cmr: String| {
+        spawn_local(async move {
+            if let Err(e) = registry::delete(&cmr).await {
+                set_error.set(Some(e));
+            }
+            reload();
+        });
+    };
+
+    // The entry whose tags are being edited, and the text of its tag field.
+    let (editing, set_editing) = signal::<Option<String>>(None);
+    let (tag_text, set_tag_text) = signal(String::new());
+
+    let save_tags = move |cmr: String| {
+        let tags = registry::parse_tags(&tag_text.get_untracked());
+        spawn_local(async move {
+            if let Err(e) = registry::set_tags(&cmr, &tags).await {
+                set_error.set(Some(e));
+            }
+            set_editing.set(None);
+            reload();
+        });
+    };
+
+    let filter_by = Callback::new(move |tag: String| set_query.set(format!("tag:{tag}")));
+
+    let rows = move || {
+        let query = query.get();
+        entries.with(|entries| {
+            registry::search(entries, &query)
+                .into_iter()
+                .cloned()
+                .map(|entry| {
+                    let source = entry.source.clone();
+                    let cmr = entry.cmr.clone();
+                    let name = match entry.revision {
+                        Some(number) => format!("{} r{number}", entry.name),
+                        None => entry.name.clone(),
+                    };
+                    let tag_editor = {
+                        let (cmr, tags) = (cmr.clone(), entry.tags.join(", "));
+                        let edit_cmr = cmr.clone();
+                        move || {
+                            if editing.get().as_deref() != Some(cmr.as_str()) {
+                                let (cmr, tags) = (cmr.clone(), tags.clone());
+                                return view! {
+                                    <button
+                                        class="secondary"
+                                        title=t(Msg::EditTags)
+                                        on:click=move |_| {
+                                            set_tag_text.set(tags.clone());
+                                            set_editing.set(Some(cmr.clone()));
+                                        }
+                                    >
+                                        "🏷️"
+                                    </button>
+                                }
+                                .into_any();
+                            }
+                            let cmr = edit_cmr.clone();
+                            view! {
+                                <input
+                                    type="text"
+                                    class="registry-search"
+                                    spellcheck="false"
+                                    aria-label=t(Msg::TagsLabel)
+                                    placeholder="mainnet, audited, v2"
+                                    prop:value=move || tag_text.get()
+                                    on:input=move |ev| set_tag_text.set(event_target_value(&ev))
+                                />
+                                <button on:click=move |_| save_tags(cmr.clone())>{t(Msg::SaveTags)}</button>
+                                <button class="secondary" on:click=move |_| set_editing.set(None)>{t(Msg::Cancel)}</button>
+                            }
+                            .into_any()
+                        }
+                    };
+                    view! {
+                        <li class="signature-row">
+                            <span>{name}</span>
+                            {tag_chips(&entry.tags, filter_by)}
+                            <span class="reminder-note" title=entry.cmr.clone()>
+                                {format!("{} · {}…", entry.saved.to_locale_string(), &entry.cmr[..entry.cmr.len().min(16)])}
+                            </span>
+                            <button class="secondary" on:click=move |_| on_restore.run(source.clone())>
+                                {t(Msg::OpenEntry)}
+                            </button>
+                            {tag_editor}
+                            <button class="danger" on:click=move |_| remove(cmr.clone())>
+                                "🗑️"
+                            </button>
+                        </li>
+                    }
+                })
+                .collect_view()
+        })
+    };
+
+    let tag_options = move || {
+        let current = query.get();
+        entries.with(|entries| {
+            registry::all_tags(entries)
+                .into_iter()
+                .map(|tag| {
+                    let selected = current.trim() == format!("tag:{tag}");
+                    view! { <option value=tag.clone() selected=selected>{format!("#{tag}")}</option> }
+                })
+                .collect_view()
+        })
+    };
+
+    let empty = move || {
+        let query = query.get();
+        entries.with(|entries| registry::search(entries, &query).is_empty())
+    };
+
+    view! {
+        <details
+            class="manifest-editor"
+            on:toggle=move |ev| set_open.set(event_target::<web_sys::Element>(&ev).has_attribute("open"))
+        >
+            <summary>{t(Msg::Registry)}</summary>
+            <div class="button-group">
+                <input
+                    type="search"
+                    class="registry-search"
+                    spellcheck="false"
+                    aria-label=t(Msg::SearchRegistry)
+                    placeholder=t(Msg::RegistrySearchPlaceholder)
+                    prop:value=move || query.get()
+                    on:input=move |ev| set_query.set(event_target_value(&ev))
+                />
+                <select
+                    aria-label=t(Msg::FilterByTag)
+                    on:change=move |ev| {
+                        let tag = event_target_value(&ev);
+                        if tag.is_empty() {
+                            set_query.set(String::new());
+                        } else {
+                            filter_by.run(tag);
+                        }
+                    }
+                >
+                    <option value="">{t(Msg::AllTags)}</option>
+                    {tag_options}
+                </select>
+                <button class="secondary" on:click=index_snapshots>
+                    {t(Msg::IndexSnapshots)}
+                </button>
+            </div>
+            {move || note.get().map(|note| view! { <p class="reminder-note">{note}</p> })}
+            {move || error.get().map(|e| view! { <p class="tool-error">{e}</p> })}
+            <Show
+                when=move || !empty()
+                fallback=move || {
+                    view! {
+                        <p class="signature-empty">
+                            {move || {
+                                let empty = query.with(|q| q.trim().is_empty());
+                                i18n::text(lang.get(), if empty { Msg::RegistryEmpty } else { Msg::NoRegistryMatch })
+                            }}
+                        </p>
+                    }
+                }
+            >
+                <ul class="signature-list">{rows}</ul>
+            </Show>
+        </details>
+    }
+}
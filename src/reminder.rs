@@ -0,0 +1,128 @@
+//! Timelock reminders for funded contracts.
+//!
+//! A [`Reminder`] records when something happens to a contract address: a
+//! timeout path opening at a block height, a key that should be rotated by a
+//! date. Time remaining is computed from the chain tip, so funds are not
+//! forgotten past the window in which the intended path can still be used.
+//! Reminders are kept in localStorage.
+
+use serde::{Deserialize, Serialize};
+
+use crate::address::Network;
+use crate::storage;
+use crate::timestamp::Stamp;
+
+const REMINDERS_KEY: &str = "reminders";
+
+/// Target block interval on Liquid, used to turn blocks into wall time.
+pub const BLOCK_INTERVAL_SECS: i64 = 60;
+
+/// Reminders due within this many seconds are flagged as upcoming.
+pub const DUE_SOON_SECS: i64 = 7 * 24 * 60 * 60;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase", tag = "kind")]
+pub enum Deadline {
+    /// Absolute block height.
+    Height { height: u32 },
+    /// Unix time in milliseconds.
+    Time { unix_ms: u64 },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Reminder {
+    pub address: String,
+    pub network: Network,
+    pub label: String,
+    pub deadline: Deadline,
+    pub note: String,
+    pub created: Stamp,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Urgency {
+    Passed,
+    Soon,
+    Later,
+    /// Height deadline while the chain tip is unknown.
+    Unknown,
+}
+
+impl Reminder {
+    /// Seconds until the deadline (negative once passed), if it can be told.
+    pub fn seconds_remaining(&self, tip_height: Option<u32>, now_ms: u64) -> Option<i64> {
+        match self.deadline {
+            Deadline::Height { height } => {
+                tip_height.map(|tip| (i64::from(height) - i64::from(tip)) * BLOCK_INTERVAL_SECS)
+            }
+            Deadline::Time { unix_ms } => Some((unix_ms as i64 - now_ms as i64) / 1000),
+        }
+    }
+
+    pub fn urgency(&self, tip_height: Option<u32>, now_ms: u64) -> Urgency {
+        match self.seconds_remaining(tip_height, now_ms) {
+            None => Urgency::Unknown,
+            Some(s) if s <= 0 => Urgency::Passed,
+            Some(s) if s <= DUE_SOON_SECS => Urgency::Soon,
+            Some(_) => Urgency::Later,
+        }
+    }
+
+    /// Human-readable time remaining, e.g. `in 2d 3h (~3060 blocks)`.
+    pub fn describe_remaining(&self, tip_height: Option<u32>, now_ms: u64) -> String {
+        let Some(seconds) = self.seconds_remaining(tip_height, now_ms) else {
+            return "waiting for chain tip".to_string();
+        };
+        let blocks = match (self.deadline, tip_height) {
+            (Deadline::Height { height }, Some(tip)) => {
+                format!(" ({} blocks)", (i64::from(height) - i64::from(tip)).abs())
+            }
+            _ => String::new(),
+        };
+        if seconds <= 0 {
+            format!("passed {} ago{blocks}", format_duration(-seconds))
+        } else {
+            format!("in {}{blocks}", format_duration(seconds))
+        }
+    }
+}
+
+fn format_duration(seconds: i64) -> String {
+    let days = seconds / 86_400;
+    let hours = seconds % 86_400 / 3600;
+    let minutes = seconds % 3600 / 60;
+    match (days, hours) {
+        (0, 0) => format!("{minutes}m"),
+        (0, _) => format!("{hours}h {minutes}m"),
+        _ => format!("{days}d {hours}h"),
+    }
+}
+
+/// Indices of `reminders` ordered soonest first; reminders whose time cannot
+/// be told yet go last.
+pub fn dashboard_order(
+    reminders: &[Reminder],
+    tip_height: impl Fn(Network) -> Option<u32>,
+    now_ms: u64,
+) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..reminders.len()).collect();
+    order.sort_by_key(|&i| {
+        let r = &reminders[i];
+        r.seconds_remaining(tip_height(r.network), now_ms)
+            .map_or((1, 0), |s| (0, s))
+    });
+    order
+}
+
+pub fn load_reminders() -> Vec<Reminder> {
+    storage::load(REMINDERS_KEY)
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_reminders(reminders: &[Reminder]) {
+    if let Ok(json) = serde_json::to_string(reminders) {
+        storage::save(REMINDERS_KEY, &json);
+    }
+}
@@ -0,0 +1,211 @@
+//! Reminder form and expiry dashboard.
+//!
+//! Chain tips are refreshed from the explorer on an interval; height
+//! reminders on networks without a public explorer stay undated.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::address::Network;
+use crate::esplora;
+use crate::i18n::{self, t, Msg};
+use crate::reminder::{dashboard_order, load_reminders, save_reminders, Deadline, Reminder, Urgency};
+use crate::timestamp::{format_local, Stamp};
+
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Fetch the tip of every network a reminder is on.
+fn refresh(
+    reminders: RwSignal<Vec<Reminder>>,
+    tips: RwSignal<Vec<(Network, u32)>>,
+    set_now: WriteSignal<u64>,
+    set_monitor_error: WriteSignal<Option<String>>,
+) {
+    set_now.set(js_sys::Date::now() as u64);
+    let mut networks: Vec<Network> = reminders.with_untracked(|list| list.iter().map(|r| r.network).collect());
+    networks.sort_by_key(|n| n.as_str());
+    networks.dedup();
+
+    for network in networks {
+        let Some(base_url) = esplora::base_url(network) else {
+            continue;
+        };
+        spawn_local(async move {
+            match esplora::tip_height(&base_url).await {
+                Ok(height) => {
+                    set_monitor_error.set(None);
+                    tips.update(|tips| {
+                        tips.retain(|(n, _)| *n != network);
+                        tips.push((network, height));
+                    });
+                }
+                Err(e) => set_monitor_error.set(Some(e)),
+            }
+        });
+    }
+}
+
+#[component]
+pub fn ReminderPanel(
+    /// Address of the most recently compiled program.
+    #[prop(into)]
+    address: Signal<Option<String>>,
+    #[prop(into)] network: Signal<Network>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let reminders = RwSignal::new(load_reminders());
+    let tips = RwSignal::new(Vec::<(Network, u32)>::new());
+    let (now, set_now) = signal(js_sys::Date::now() as u64);
+    let (label, set_label) = signal(String::new());
+    let (by_height, set_by_height) = signal(true);
+    let (deadline, set_deadline) = signal(String::new());
+    let (note, set_note) = signal(String::new());
+    let (form_error, set_form_error) = signal::<Option<String>>(None);
+    let (monitor_error, set_monitor_error) = signal::<Option<String>>(None);
+
+    refresh(reminders, tips, set_now, set_monitor_error);
+    if let Ok(handle) = set_interval_with_handle(
+        move || refresh(reminders, tips, set_now, set_monitor_error),
+        POLL_INTERVAL,
+    ) {
+        on_cleanup(move || handle.clear());
+    }
+
+    let tip_of = move |network: Network| {
+        tips.with(|tips| tips.iter().find(|(n, _)| *n == network).map(|(_, h)| *h))
+    };
+
+    let add = move |_| {
+        let Some(address) = address.get_untracked() else {
+            return;
+        };
+        let value = deadline.get_untracked();
+        let deadline = if by_height.get_untracked() {
+            match value.trim().parse::<u32>() {
+                Ok(height) => Deadline::Height { height },
+                Err(_) => {
+                    set_form_error.set(Some(i18n::text(lang.get_untracked(), Msg::HeightMustBeNumber).to_string()));
+                    return;
+                }
+            }
+        } else {
+            let unix_ms = js_sys::Date::parse(&value);
+            if unix_ms.is_nan() {
+                set_form_error.set(Some(i18n::text(lang.get_untracked(), Msg::PickDateTime).to_string()));
+                return;
+            }
+            Deadline::Time { unix_ms: unix_ms as u64 }
+        };
+
+        set_form_error.set(None);
+        reminders.update(|list| {
+            list.push(Reminder {
+                address,
+                network: network.get_untracked(),
+                label: label.get_untracked().trim().to_string(),
+                deadline,
+                note: note.get_untracked().trim().to_string(),
+                created: Stamp::now(),
+            })
+        });
+        reminders.with_untracked(|list| save_reminders(list));
+        refresh(reminders, tips, set_now, set_monitor_error);
+    };
+
+    let remove = move |index: usize| {
+        reminders.update(|list| {
+            if index < list.len() {
+                list.remove(index);
+            }
+        });
+        reminders.with_untracked(|list| save_reminders(list));
+    };
+
+    let rows = move || {
+        let now = now.get();
+        let list = reminders.get();
+        dashboard_order(&list, tip_of, now)
+            .into_iter()
+            .map(|index| {
+                let reminder = list[index].clone();
+                let tip = tip_of(reminder.network);
+                let class = match reminder.urgency(tip, now) {
+                    Urgency::Passed => "status-badge expired",
+                    Urgency::Soon => "status-badge pending",
+                    Urgency::Later | Urgency::Unknown => "status-badge paid",
+                };
+                let when = match reminder.deadline {
+                    Deadline::Height { height } => i18n::format(i18n::text(lang.get(), Msg::AtHeight), &[&height.to_string()]),
+                    Deadline::Time { unix_ms } => format_local(unix_ms),
+                };
+                let label = if reminder.label.is_empty() {
+                    i18n::format(i18n::text(lang.get(), Msg::ReminderNumber), &[&reminder.created.sequence.to_string()])
+                } else {
+                    reminder.label.clone()
+                };
+                view! {
+                    <li class="invoice-row">
+                        <span class="invoice-label" title=reminder.address.clone()>
+                            {label}
+                            <span class="reminder-note">{reminder.note.clone()}</span>
+                        </span>
+                        <span class="invoice-amount">{when}</span>
+                        <span class=class>{reminder.describe_remaining(tip, now)}</span>
+                        <button class="danger" on:click=move |_| remove(index)>
+                            {t(Msg::Remove)}
+                        </button>
+                    </li>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <div class="reminder-panel">
+            <label>{t(Msg::TimelockReminders)}</label>
+            <div class="invoice-form">
+                <input
+                    type="text"
+                    placeholder=t(Msg::ReminderLabelPlaceholder)
+                    prop:value=move || label.get()
+                    on:input=move |ev| set_label.set(event_target_value(&ev))
+                />
+                <select on:change=move |ev| {
+                    set_by_height.set(event_target_value(&ev) == "height");
+                    set_deadline.set(String::new());
+                }>
+                    <option value="height" selected=move || by_height.get()>{t(Msg::AtBlockHeight)}</option>
+                    <option value="time" selected=move || !by_height.get()>{t(Msg::AtDate)}</option>
+                </select>
+                <input
+                    type=move || if by_height.get() { "number" } else { "datetime-local" }
+                    min="0"
+                    placeholder=t(Msg::BlockHeight)
+                    prop:value=move || deadline.get()
+                    on:input=move |ev| set_deadline.set(event_target_value(&ev))
+                />
+                <input
+                    type="text"
+                    placeholder=t(Msg::Note)
+                    prop:value=move || note.get()
+                    on:input=move |ev| set_note.set(event_target_value(&ev))
+                />
+                <button on:click=add disabled=move || address.get().is_none()>
+                    {t(Msg::AddReminder)}
+                </button>
+            </div>
+            {move || form_error.get().map(|e| view! { <p class="tool-error">{e}</p> })}
+            <div class="section-header">
+                <span class="output-label">{t(Msg::Upcoming)}</span>
+                <button
+                    class="secondary"
+                    on:click=move |_| refresh(reminders, tips, set_now, set_monitor_error)
+                >
+                    {t(Msg::RefreshTips)}
+                </button>
+            </div>
+            {move || monitor_error.get().map(|e| view! { <p class="tool-error">{e}</p> })}
+            <ul class="invoice-list">{rows}</ul>
+        </div>
+    }
+}
@@ -0,0 +1,151 @@
+//! Contract sources fetched from a URL, such as a file in a Git repository.
+//!
+//! A URL names either a source file or a manifest listing several:
+//!
+//! ```json
+//! {"name": "Treasury contracts", "files": [{"path": "vault.simf", "name": "Vault"}, {"path": "htlc/htlc.simf"}]}
+//! ```
+//!
+//! Manifest paths are relative to the manifest. Links to a file's page on
+//! GitHub, GitLab, Codeberg or Gitea are rewritten to the raw file, since
+//! the page itself is HTML. The request goes straight from the browser, so
+//! the host must allow it with CORS headers; the raw file hosts of the big
+//! forges do, and the error says so when a host does not.
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::logging::{self, Category};
+
+/// Largest response read; contracts are a few kilobytes.
+const MAX_BYTES: usize = 1024 * 1024;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ManifestFile {
+    /// Relative to the manifest, or an absolute URL.
+    pub path: String,
+    /// Shown instead of the file name.
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+impl ManifestFile {
+    /// The name to show: `name`, or else the last part of the path.
+    pub fn label(&self) -> &str {
+        self.name
+            .as_deref()
+            .unwrap_or_else(|| self.path.rsplit('/').next().unwrap_or(&self.path))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Manifest {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub files: Vec<ManifestFile>,
+}
+
+/// What a URL held.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    Source { url: String, text: String },
+    /// `url` is the manifest's, which its paths are relative to.
+    Manifest { url: String, manifest: Manifest },
+}
+
+/// `url` checked to be HTTPS (or plain HTTP to this machine), with links to
+/// a forge's file page turned into links to the raw file.
+pub fn raw_url(url: &str) -> Result<String, String> {
+    let url = url.trim();
+    let local = ["http://localhost", "http://127.0.0.1", "http://[::1]"]
+        .iter()
+        .any(|prefix| url.strip_prefix(prefix).is_some_and(|rest| rest.is_empty() || rest.starts_with([':', '/'])));
+    let Some(rest) = url.strip_prefix("https://") else {
+        return if local {
+            Ok(url.to_string())
+        } else {
+            Err("Only https:// URLs can be opened".to_string())
+        };
+    };
+    let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+    let raw = match host {
+        // github.com/OWNER/REPO/blob/REF/PATH
+        "github.com" => match path.split_once("/blob/") {
+            Some((repo, file)) => format!("https://raw.githubusercontent.com/{repo}/{file}"),
+            None => url.to_string(),
+        },
+        // HOST/OWNER/REPO/-/blob/REF/PATH on GitLab, HOST/OWNER/REPO/src/branch/REF/PATH on Gitea.
+        _ if path.contains("/-/blob/") => url.replacen("/-/blob/", "/-/raw/", 1),
+        _ if path.contains("/src/branch/") || path.contains("/src/commit/") || path.contains("/src/tag/") => {
+            url.replacen("/src/", "/raw/", 1)
+        }
+        _ => url.to_string(),
+    };
+    Ok(raw)
+}
+
+/// `path` resolved against the manifest at `base`.
+pub fn resolve(base: &str, path: &str) -> Result<String, String> {
+    let url = web_sys::Url::new_with_base(path, base).map_err(|_| format!("Invalid path in manifest: {path}"))?;
+    raw_url(&url.href())
+}
+
+/// Read `text`, fetched from `url`, as a manifest if it is one.
+pub fn classify(url: &str, text: String) -> Result<Fetched, String> {
+    let looks_like_json = text.trim_start().starts_with('{');
+    if !looks_like_json {
+        return Ok(Fetched::Source { url: url.to_string(), text });
+    }
+    let manifest: Manifest = serde_json::from_str(&text).map_err(|e| format!("Invalid manifest: {e}"))?;
+    if manifest.files.is_empty() {
+        return Err("The manifest lists no files".to_string());
+    }
+    Ok(Fetched::Manifest { url: url.to_string(), manifest })
+}
+
+async fn fetch_text(url: &str) -> Result<String, String> {
+    let window = web_sys::window().ok_or("No window available")?;
+    let init = web_sys::RequestInit::new();
+    init.set_mode(web_sys::RequestMode::Cors);
+    logging::debug(Category::Network, format!("Requesting {url}"));
+    let response = JsFuture::from(window.fetch_with_str_and_init(url, &init)).await.map_err(|e| {
+        logging::warn(Category::Network, format!("Request to {url} failed: {e:?}"));
+        // Browsers report a CORS refusal as a bare network error.
+        format!(
+            "Could not fetch {url}. The host may not allow requests from other sites (CORS); \
+             link to the raw file, e.g. on raw.githubusercontent.com, or download it and open it as a file"
+        )
+    })?;
+    let response: web_sys::Response = response
+        .dyn_into()
+        .map_err(|_| "The server returned an unexpected response".to_string())?;
+    if !response.ok() {
+        logging::warn(Category::Network, format!("{url} answered {}", response.status()));
+        return Err(match response.status() {
+            404 => format!("Not found: {url}. Private repositories cannot be read without signing in"),
+            status => format!("The server answered {status} for {url}"),
+        });
+    }
+    let text = JsFuture::from(response.text().map_err(|e| format!("{e:?}"))?)
+        .await
+        .map_err(|e| format!("{e:?}"))?
+        .as_string()
+        .unwrap_or_default();
+    if text.len() > MAX_BYTES {
+        return Err(format!("{url} is larger than {} KiB", MAX_BYTES / 1024));
+    }
+    Ok(text)
+}
+
+/// Fetch `url`: a source file, or a manifest of them.
+pub async fn fetch(url: &str) -> Result<Fetched, String> {
+    let url = raw_url(url)?;
+    let text = fetch_text(&url).await?;
+    classify(&url, text)
+}
+
+/// Fetch one file of the manifest at `base`.
+pub async fn fetch_file(base: &str, file: &ManifestFile) -> Result<String, String> {
+    fetch_text(&resolve(base, &file.path)?).await
+}
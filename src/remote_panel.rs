@@ -0,0 +1,111 @@
+//! "Open from URL" drawer: load a contract, or pick one from a manifest,
+//! from a remote repository.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::i18n::{self, t, Msg};
+use crate::remote::{self, Fetched, Manifest, ManifestFile};
+use crate::storage;
+
+const LAST_URL_KEY: &str = "remote.last-url";
+
+#[component]
+pub fn RemotePanel(
+    /// Called with the name and source of the file to open.
+    on_open: Callback<(String, String)>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let (url, set_url) = signal(storage::load(LAST_URL_KEY).unwrap_or_default());
+    let (busy, set_busy) = signal(false);
+    let (error, set_error) = signal::<Option<String>>(None);
+    // The manifest last fetched, with the URL its paths are relative to.
+    let (manifest, set_manifest) = signal::<Option<(String, Manifest)>>(None);
+
+    let open_url = move |_| {
+        let requested = url.get_untracked();
+        storage::save(LAST_URL_KEY, requested.trim());
+        set_busy.set(true);
+        set_error.set(None);
+        set_manifest.set(None);
+        spawn_local(async move {
+            match remote::fetch(&requested).await {
+                Ok(Fetched::Source { url, text }) => {
+                    let name = url.rsplit('/').next().unwrap_or(&url).to_string();
+                    on_open.run((name, text));
+                }
+                Ok(Fetched::Manifest { url, manifest }) => set_manifest.set(Some((url, manifest))),
+                Err(e) => set_error.set(Some(e)),
+            }
+            set_busy.set(false);
+        });
+    };
+
+    let open_file = move |base: String, file: ManifestFile| {
+        set_busy.set(true);
+        set_error.set(None);
+        spawn_local(async move {
+            match remote::fetch_file(&base, &file).await {
+                Ok(text) => on_open.run((file.label().to_string(), text)),
+                Err(e) => set_error.set(Some(e)),
+            }
+            set_busy.set(false);
+        });
+    };
+
+    let files = move || {
+        manifest.get().map(|(base, manifest)| {
+            let rows = manifest
+                .files
+                .into_iter()
+                .map(|file| {
+                    let base = base.clone();
+                    let path = file.path.clone();
+                    let label = file.label().to_string();
+                    view! {
+                        <li class="signature-row">
+                            <span>{label}</span>
+                            <span class="reminder-note">{path}</span>
+                            <button
+                                class="secondary"
+                                disabled=move || busy.get()
+                                on:click=move |_| open_file(base.clone(), file.clone())
+                            >
+                                {t(Msg::OpenButton)}
+                            </button>
+                        </li>
+                    }
+                })
+                .collect_view();
+            view! {
+                {manifest.name.map(|name| view! { <p class="reminder-note">{name}</p> })}
+                <ul class="signature-list">{rows}</ul>
+            }
+        })
+    };
+
+    view! {
+        <details class="manifest-editor">
+            <summary>{t(Msg::OpenFromUrl)}</summary>
+            <div class="button-group">
+                <input
+                    type="url"
+                    class="registry-search"
+                    spellcheck="false"
+                    aria-label=t(Msg::RemoteUrlLabel)
+                    placeholder="https://github.com/org/contracts/blob/main/vault.simf"
+                    prop:value=move || url.get()
+                    on:input=move |ev| set_url.set(event_target_value(&ev))
+                />
+                <button disabled=move || busy.get() || url.with(|u| u.trim().is_empty()) on:click=open_url>
+                    {move || i18n::text(lang.get(), if busy.get() { Msg::Fetching } else { Msg::OpenUrl })}
+                </button>
+            </div>
+            <p class="drop-zone-hint">
+                {t(Msg::RemoteHint)}
+            </p>
+            {move || error.get().map(|e| view! { <p class="tool-error">{e}</p> })}
+            {files}
+        </details>
+    }
+}
@@ -0,0 +1,116 @@
+//! Evaluate a single SimplicityHL expression on the Bit Machine.
+//!
+//! The expression is wrapped in a `main` that passes it through `dbg!`, then
+//! compiled with debug symbols and run against the same synthetic
+//! transaction the test runner uses, so transaction jets answer too. The
+//! value is captured from the debug call. With a type it is shown as a
+//! SimplicityHL literal; without one the compiler has to infer it, and the
+//! raw Simplicity value is all there is to show.
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::parse::ParseFromStr;
+use simplicityhl::simplicity::bit_machine::ExecTracker;
+use simplicityhl::simplicity::ffi::ffi::UWORD;
+use simplicityhl::simplicity::jet::Elements;
+use simplicityhl::simplicity::{BitMachine, Cmr, Ihr};
+use simplicityhl::str::AliasName;
+use simplicityhl::types::{AliasedType, ResolvedType};
+use simplicityhl::value::StructuralValue;
+use simplicityhl::{Arguments, WitnessValues};
+
+use crate::options::{self, CompileOptions};
+use crate::sighash::elements_env;
+use crate::taproot::TaprootConfig;
+use crate::test_runner::{synthetic_tx, TestEnv};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Evaluation {
+    /// The value as a SimplicityHL literal, when the type is known.
+    pub value: Option<String>,
+    /// The value as the Bit Machine produced it.
+    pub raw: String,
+    /// The program the expression was wrapped in.
+    pub program: String,
+}
+
+/// Keeps the value of the one `dbg!` call the wrapper makes.
+#[derive(Default)]
+struct Capture {
+    value: Option<simplicityhl::simplicity::Value>,
+}
+
+impl ExecTracker<Elements> for Capture {
+    fn track_left(&mut self, _: Ihr) {}
+
+    fn track_right(&mut self, _: Ihr) {}
+
+    fn track_jet_call(&mut self, _: &Elements, _: &[UWORD], _: &[UWORD], _: bool) {}
+
+    fn track_dbg_call(&mut self, _: &Cmr, value: simplicityhl::simplicity::Value) {
+        self.value.get_or_insert(value);
+    }
+
+    fn is_track_debug_enabled(&self) -> bool {
+        true
+    }
+}
+
+/// `expression` inside a program that runs it through `dbg!`.
+pub fn wrap(expression: &str, ty: &str) -> String {
+    let expression = expression.trim().trim_end_matches(';');
+    let binding = if ty.trim().is_empty() {
+        "result".to_string()
+    } else {
+        format!("result: {}", ty.trim())
+    };
+    format!("fn main() {{\n    let {binding} = dbg!({expression});\n}}\n")
+}
+
+/// The SimplicityHL type `ty`, with builtin aliases resolved.
+pub(crate) fn resolve(ty: &str) -> Result<ResolvedType, String> {
+    let aliased = AliasedType::parse_from_str(ty).map_err(|e| format!("Invalid type: {}", e))?;
+    aliased
+        .resolve_builtin()
+        .map_err(|name: AliasName| format!("Unknown type `{name}`"))
+}
+
+/// Evaluate `expression`, of type `ty` if given, with witness values from
+/// `witness_json` for any `witness::NAME` it reads.
+pub fn evaluate(expression: &str, ty: &str, witness_json: &str) -> Result<Evaluation, String> {
+    if expression.trim().is_empty() {
+        return Err("Enter an expression".to_string());
+    }
+    let resolved = if ty.trim().is_empty() { None } else { Some(resolve(ty.trim())?) };
+    let program = options::expand_keys(&wrap(expression, ty))?;
+    let options = CompileOptions {
+        debug_symbols: true,
+        ..CompileOptions::default()
+    };
+    let compiled = options.compile(&program, Arguments::default())?;
+    let witness = if witness_json.trim().is_empty() {
+        WitnessValues::default()
+    } else {
+        serde_json::from_str::<WitnessValues>(witness_json).map_err(|e| format!("Invalid JSON witness data: {}", e))?
+    };
+    let cmr = compiled.commit().cmr();
+    let satisfied = compiled.satisfy(witness).map_err(|e| format!("Witness error: {}", e))?;
+    let (tx, utxo) = synthetic_tx(&TestEnv::default(), cmr)?;
+    let env = elements_env(tx, 0, &[utxo], cmr, &TaprootConfig::default())?;
+
+    let redeem = satisfied.redeem();
+    let mut machine = BitMachine::for_program(redeem).map_err(|e| format!("Execution error: {e}"))?;
+    let mut capture = Capture::default();
+    machine
+        .exec_with_tracker(redeem, &env, &mut capture)
+        .map_err(|e| format!("Execution error: {e}"))?;
+    let raw = capture.value.ok_or("The expression produced no value")?;
+
+    let value = resolved.and_then(|ty| {
+        simplicityhl::value::Value::reconstruct(&StructuralValue::from(raw.clone()), &ty).map(|v| v.to_string())
+    });
+    Ok(Evaluation {
+        value,
+        raw: raw.to_string(),
+        program,
+    })
+}
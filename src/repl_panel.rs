@@ -0,0 +1,135 @@
+//! Expression playground: evaluate one expression at a time and keep a
+//! history of the results.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::api_types::EvaluationResult;
+use crate::compiler;
+use crate::i18n::{self, t, Msg};
+use crate::repl::Evaluation;
+
+/// Evaluations kept in the history, newest first.
+const MAX_HISTORY: usize = 20;
+
+#[derive(Clone)]
+struct Entry {
+    expression: String,
+    ty: String,
+    outcome: Result<Evaluation, String>,
+}
+
+#[component]
+pub fn ReplPanel() -> impl IntoView {
+    let lang = i18n::use_lang();
+    let (expression, set_expression) = signal(String::new());
+    let (ty, set_ty) = signal(String::new());
+    let (witness, set_witness) = signal(String::new());
+    let (busy, set_busy) = signal(false);
+    let (history, set_history) = signal(Vec::<Entry>::new());
+
+    let evaluate = move || {
+        let (expression, ty, witness) = (expression.get_untracked(), ty.get_untracked(), witness.get_untracked());
+        if expression.trim().is_empty() || busy.get_untracked() {
+            return;
+        }
+        set_busy.set(true);
+        spawn_local(async move {
+            let json = compiler::evaluate_expression(&expression, &ty, &witness).await;
+            let outcome = match serde_json::from_str::<EvaluationResult>(&json) {
+                Ok(EvaluationResult { evaluation: Some(evaluation), .. }) => Ok(evaluation),
+                Ok(EvaluationResult { error, .. }) => Err(error.unwrap_or_default()),
+                Err(e) => Err(e.to_string()),
+            };
+            set_history.update(|history| {
+                history.insert(0, Entry { expression, ty, outcome });
+                history.truncate(MAX_HISTORY);
+            });
+            set_busy.set(false);
+        });
+    };
+
+    let entries = move || {
+        history
+            .get()
+            .into_iter()
+            .map(|entry| {
+                let input = if entry.ty.trim().is_empty() {
+                    entry.expression
+                } else {
+                    format!("{} : {}", entry.expression, entry.ty.trim())
+                };
+                let result = match entry.outcome {
+                    Ok(evaluation) => {
+                        let program = evaluation.program;
+                        view! {
+                            <div class="repl-value">{evaluation.value.unwrap_or(evaluation.raw)}</div>
+                            <details>
+                                <summary>{t(Msg::Program)}</summary>
+                                <pre class="output-box listing">{program}</pre>
+                            </details>
+                        }
+                        .into_any()
+                    }
+                    Err(e) => view! { <div class="tool-error">{e}</div> }.into_any(),
+                };
+                view! {
+                    <li class="repl-entry">
+                        <code class="repl-input">{format!("» {input}")}</code>
+                        {result}
+                    </li>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <div class="repl-panel">
+            <label for="repl-expression">{t(Msg::ExpressionPlayground)}</label>
+            <div class="repl-form">
+                <textarea
+                    id="repl-expression"
+                    spellcheck="false"
+                    rows="2"
+                    placeholder="jet::add_32(40, 2)"
+                    prop:value=move || expression.get()
+                    on:input=move |ev| set_expression.set(event_target_value(&ev))
+                    on:keydown=move |ev: web_sys::KeyboardEvent| {
+                        if ev.key() == "Enter" && !ev.shift_key() {
+                            ev.prevent_default();
+                            evaluate();
+                        }
+                    }
+                />
+                <input
+                    class="repl-type"
+                    placeholder=t(Msg::TypePlaceholder)
+                    spellcheck="false"
+                    prop:value=move || ty.get()
+                    on:input=move |ev| set_ty.set(event_target_value(&ev))
+                />
+            </div>
+            <details>
+                <summary>{t(Msg::WitnessBindings)}</summary>
+                <textarea
+                    spellcheck="false"
+                    placeholder=r#"{"X": {"value": "0x01", "type": "u8"}}"#
+                    prop:value=move || witness.get()
+                    on:input=move |ev| set_witness.set(event_target_value(&ev))
+                />
+            </details>
+            <div class="button-group">
+                <button on:click=move |_| evaluate() disabled=move || busy.get()>
+                    {move || i18n::text(lang.get(), if busy.get() { Msg::Evaluating } else { Msg::Evaluate })}
+                </button>
+                <button class="secondary" on:click=move |_| set_history.set(Vec::new())>
+                    {t(Msg::ClearHistory)}
+                </button>
+            </div>
+            <p class="drop-zone-hint">
+                {t(Msg::ReplHint)}
+            </p>
+            <ul class="repl-history" aria-live="polite">{entries}</ul>
+        </div>
+    }
+}
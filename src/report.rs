@@ -0,0 +1,209 @@
+//! Standalone compilation reports for audit records.
+//!
+//! A report gathers what a reviewer signs off on: the source as compiled,
+//! its CMR and AMR, the address on the chosen network, the structure and jet
+//! costs of the program, and its spending conditions. [`Report::to_html`]
+//! renders it as a single HTML file with inline styles and no scripts or
+//! external resources, so it opens the same anywhere and prints cleanly to
+//! PDF from the browser's print dialog.
+//!
+//! The AMR commits to the witness values as well as the program, so it is
+//! only reported when a witness is given and satisfies the program.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::WitnessValues;
+
+use crate::address::Network;
+use crate::annotations::{self, Annotations};
+use crate::jet_costs::CostTable;
+use crate::jet_usage::{self, JetUsage};
+use crate::metadata::{self, Metadata};
+use crate::options::CompileOptions;
+use crate::recent::comment_name;
+use crate::spend_paths::{self, SpendConditions};
+use crate::summary::ProgramSummary;
+use crate::taproot::TaprootConfig;
+use crate::timestamp::Stamp;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    /// The metadata name or first line comment of the source, or
+    /// "Untitled contract".
+    pub name: String,
+    pub generated: Stamp,
+    pub source: String,
+    pub cmr: String,
+    pub amr: Option<String>,
+    /// Why there is no AMR: no witness, or the witness was rejected.
+    pub amr_note: Option<String>,
+    pub network: Network,
+    pub address: String,
+    pub summary: ProgramSummary,
+    pub jets: BTreeMap<String, JetUsage>,
+    /// Sum of the jets' total costs, in milliweight.
+    pub total_cost: u64,
+    pub spend: SpendConditions,
+    /// `///` comments on the functions and witnesses of the source.
+    #[serde(default)]
+    pub annotations: Annotations,
+    /// The `//!` header of the source.
+    #[serde(default)]
+    pub metadata: Option<Metadata>,
+}
+
+/// Compile `code` and collect its report, with the address of the tree
+/// `config` describes. An empty `witness_json` leaves the AMR out; an
+/// unusable one is noted instead of failing the report.
+pub fn build(code: &str, witness_json: &str, network: Network, config: &TaprootConfig) -> Result<Report, String> {
+    let compiled = CompileOptions::default().compile_source(code, code)?;
+    let committed = compiled.commit();
+    let cmr = committed.cmr();
+    // Reports record what the chain charges, whatever table is loaded.
+    let jets = jet_usage::histogram(&committed, &CostTable::default());
+
+    let (amr, amr_note) = if witness_json.trim().is_empty() {
+        (None, Some("No witness was given.".to_string()))
+    } else {
+        match serde_json::from_str::<WitnessValues>(witness_json)
+            .map_err(|e| format!("Invalid JSON witness data: {}", e))
+            .and_then(|witness| compiled.satisfy(witness).map_err(|e| format!("Witness error: {}", e)))
+        {
+            Ok(satisfied) => (Some(satisfied.redeem().amr().to_string()), None),
+            Err(e) => (None, Some(e)),
+        }
+    };
+
+    Ok(Report {
+        name: comment_name(code).unwrap_or_else(|| "Untitled contract".to_string()),
+        generated: Stamp::now(),
+        source: code.to_string(),
+        cmr: cmr.to_string(),
+        amr,
+        amr_note,
+        network,
+        address: config.address(cmr, network)?.to_string(),
+        summary: ProgramSummary::from_commit(&committed, code),
+        total_cost: jets.values().map(|usage| usage.total_cost).sum(),
+        jets,
+        spend: spend_paths::analyze(code),
+        annotations: annotations::extract(code),
+        metadata: metadata::parse(code),
+    })
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+const STYLE: &str = "
+body { font-family: -apple-system, 'Segoe UI', Roboto, sans-serif; color: #222; max-width: 60em; margin: 2em auto; padding: 0 1em; }
+h1 { font-size: 1.6em; margin-bottom: 0.2em; }
+h2 { font-size: 1.2em; border-bottom: 1px solid #ccc; padding-bottom: 0.2em; margin-top: 1.6em; }
+.meta { color: #666; }
+dl { display: grid; grid-template-columns: max-content 1fr; gap: 0.3em 1em; }
+dt { font-weight: 600; }
+dd { margin: 0; font-family: Menlo, Monaco, 'Courier New', monospace; word-break: break-all; }
+dd.doc { font-family: inherit; word-break: normal; }
+pre { background: #f6f6f6; border: 1px solid #ddd; padding: 0.8em; white-space: pre-wrap; word-break: break-word; font-size: 0.85em; }
+table { border-collapse: collapse; width: 100%; }
+th, td { text-align: left; padding: 0.3em 0.6em; border-bottom: 1px solid #e4e4e4; }
+td.number, th.number { text-align: right; }
+@media print { body { margin: 0; max-width: none; } h2 { break-after: avoid; } pre { break-inside: auto; } }
+";
+
+impl Report {
+    /// The report as one self-contained HTML document.
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        let mut push = |s: &str| html.push_str(s);
+
+        push("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        push(&format!("<title>Compilation report: {}</title>\n", escape(&self.name)));
+        push(&format!("<style>{STYLE}</style>\n</head>\n<body>\n"));
+        push(&format!("<h1>{}</h1>\n", escape(&self.name)));
+        push(&format!(
+            "<p class=\"meta\">Simplicity compilation report · generated {} (sequence #{}) · simplicity-wasm {}</p>\n",
+            escape(&self.generated.utc),
+            self.generated.sequence,
+            env!("CARGO_PKG_VERSION"),
+        ));
+
+        if let Some(metadata) = &self.metadata {
+            push("<h2>About</h2>\n<dl>\n");
+            for (label, value) in metadata.fields() {
+                push(&format!("<dt>{label}</dt><dd class=\"doc\">{}</dd>\n", escape(value)));
+            }
+            push("</dl>\n");
+        }
+
+        push("<h2>Identity</h2>\n<dl>\n");
+        push(&format!("<dt>CMR</dt><dd>{}</dd>\n", escape(&self.cmr)));
+        match (&self.amr, &self.amr_note) {
+            (Some(amr), _) => push(&format!("<dt>AMR</dt><dd>{}</dd>\n", escape(amr))),
+            (None, note) => push(&format!(
+                "<dt>AMR</dt><dd>not available{}</dd>\n",
+                note.as_deref().map(|n| format!(": {}", escape(n))).unwrap_or_default()
+            )),
+        }
+        push(&format!(
+            "<dt>Address ({})</dt><dd>{}</dd>\n</dl>\n",
+            self.network.as_str(),
+            escape(&self.address)
+        ));
+
+        push("<h2>Spending conditions</h2>\n");
+        push(&format!("<pre>{}</pre>\n", escape(&self.spend.to_text())));
+
+        if !self.annotations.is_empty() {
+            push("<h2>Documentation</h2>\n<dl>\n");
+            for (name, doc) in &self.annotations.functions {
+                push(&format!("<dt>fn {}</dt><dd class=\"doc\">{}</dd>\n", escape(name), escape(doc)));
+            }
+            for (name, doc) in &self.annotations.witnesses {
+                push(&format!("<dt>witness::{}</dt><dd class=\"doc\">{}</dd>\n", escape(name), escape(doc)));
+            }
+            push("</dl>\n");
+        }
+
+        push("<h2>Program</h2>\n");
+        push(&format!("<p>{}</p>\n", escape(&self.summary.to_text()).replace('\n', "<br>\n")));
+
+        push("<h2>Cost analysis</h2>\n");
+        if self.jets.is_empty() {
+            push("<p>The program calls no jets.</p>\n");
+        } else {
+            push("<table>\n<thead><tr><th>Jet</th><th class=\"number\">Calls</th><th class=\"number\">Cost (mWU)</th><th class=\"number\">Total (mWU)</th></tr></thead>\n<tbody>\n");
+            for (name, usage) in &self.jets {
+                push(&format!(
+                    "<tr><td>{}</td><td class=\"number\">{}</td><td class=\"number\">{}</td><td class=\"number\">{}</td></tr>\n",
+                    escape(name),
+                    usage.count,
+                    usage.cost,
+                    usage.total_cost
+                ));
+            }
+            push(&format!(
+                "</tbody>\n<tfoot><tr><th>Total</th><th></th><th></th><th class=\"number\">{}</th></tr></tfoot>\n</table>\n",
+                self.total_cost
+            ));
+        }
+
+        push("<h2>Source</h2>\n");
+        push(&format!("<pre>{}</pre>\n", escape(&self.source)));
+        push("</body>\n</html>\n");
+        html
+    }
+}
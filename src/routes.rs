@@ -0,0 +1,99 @@
+//! Client-side routes, kept in the URL fragment.
+//!
+//! `#/editor`, `#/debug`, `#/examples/<slug>` and `#/saved/<name>` name a
+//! place in the playground, so it can be bookmarked and the browser's back
+//! and forward buttons move between places. `#/view/<source>` carries a
+//! whole program and opens it in viewer mode, for publishing. The fragment
+//! never reaches the server, so any static host serves every route without
+//! rewrite rules.
+
+use std::fmt;
+
+/// Where the playground is.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Route {
+    /// The editor, the page's default.
+    #[default]
+    Editor,
+    /// The results pane: compile output, diagnostics and triage.
+    Debug,
+    /// The template form with the template of this slug chosen.
+    Example(String),
+    /// The latest revision of the saved contract with this name.
+    Saved(String),
+    /// This program, compiled and shown read-only.
+    View(String),
+}
+
+/// Lowercase words joined by dashes: `Pay to public key` → `pay-to-public-key`.
+pub fn slug(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+impl Route {
+    /// Route of a path such as `/examples/hash-lock`; `None` when the path
+    /// names no route. Segments are percent-decoded.
+    pub fn parse(path: &str) -> Option<Route> {
+        let path = path.trim_start_matches('#').trim_matches('/');
+        let (head, rest) = path.split_once('/').unwrap_or((path, ""));
+        let rest = js_sys::decode_uri_component(rest).ok().map(String::from).unwrap_or_default();
+        match (head, rest.is_empty()) {
+            ("" | "editor", true) => Some(Route::Editor),
+            ("debug", true) => Some(Route::Debug),
+            ("examples", false) => Some(Route::Example(rest)),
+            ("saved", false) => Some(Route::Saved(rest)),
+            ("view", false) => Some(Route::View(rest)),
+            _ => None,
+        }
+    }
+
+    /// The fragment for this route, with its leading `#`.
+    pub fn to_hash(&self) -> String {
+        format!("#{self}")
+    }
+}
+
+impl fmt::Display for Route {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let encode = |s: &str| String::from(js_sys::encode_uri_component(s));
+        match self {
+            Route::Editor => write!(f, "/editor"),
+            Route::Debug => write!(f, "/debug"),
+            Route::Example(slug) => write!(f, "/examples/{}", encode(slug)),
+            Route::Saved(name) => write!(f, "/saved/{}", encode(name)),
+            Route::View(source) => write!(f, "/view/{}", encode(source)),
+        }
+    }
+}
+
+/// The route in the address bar, or [`Route::Editor`] when there is none.
+#[cfg(feature = "ui")]
+pub fn current() -> Route {
+    web_sys::window()
+        .and_then(|w| w.location().hash().ok())
+        .and_then(|hash| Route::parse(&hash))
+        .unwrap_or_default()
+}
+
+/// Go to `route`, adding a history entry unless it is already current.
+#[cfg(feature = "ui")]
+pub fn navigate(route: &Route) {
+    if current() != *route {
+        if let Some(window) = web_sys::window() {
+            let _ = window.location().set_hash(&route.to_hash());
+        }
+    }
+}
+
+/// Show `route` in the address bar in place of the current entry, without
+/// notifying listeners.
+#[cfg(feature = "ui")]
+pub fn replace(route: &Route) {
+    if let Some(history) = web_sys::window().and_then(|w| w.history().ok()) {
+        let _ = history.replace_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(&route.to_hash()));
+    }
+}
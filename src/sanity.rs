@@ -0,0 +1,214 @@
+//! Pre-share sanity pass over a program's public keys.
+//!
+//! An address only commits to keys written into the program. Keys read from
+//! the witness are chosen by whoever spends, and keys nobody in the manifest
+//! controls mean the funds can only move with someone else's signature.
+//! Both are easy to get wrong and costly to discover after funding.
+//!
+//! Keys are found by scanning the source: a `0x` literal of 64 hex digits,
+//! or a `witness::`/`param::` name, counts as a key when its line mentions
+//! `Pubkey` or the name looks like one (`*_PK`, `*PUBLIC_KEY*`, `*PUBKEY*`).
+//!
+//! The pass also reports spend paths no witness can satisfy (see
+//! [`satisfiable`](crate::satisfiable)), since an unspendable address is the
+//! costliest mistake of all, and constants that look like secrets (see
+//! [`leakage`](crate::leakage)), which the first spend makes public.
+
+use serde::{Deserialize, Serialize};
+
+use crate::diagnostics::Diagnostic;
+use crate::leakage;
+use crate::manifest::KeyManifest;
+use crate::satisfiable;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase", tag = "kind", content = "name")]
+pub enum KeySource {
+    /// Hex written directly into the program; committed by the address.
+    Literal,
+    /// Supplied at spend time; not committed by the address.
+    Witness(String),
+    /// Compile-time parameter; committed by the address.
+    Param(String),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct KeyRef {
+    pub source: KeySource,
+    /// Lower-case hex, when the value is known.
+    pub pubkey: Option<String>,
+    pub line: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct SanityReport {
+    pub keys: Vec<KeyRef>,
+    /// Committed keys that appear in the manifest.
+    pub own_keys: Vec<String>,
+    pub findings: Vec<Diagnostic>,
+}
+
+impl SanityReport {
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn looks_like_key_name(name: &str) -> bool {
+    let upper = name.to_ascii_uppercase();
+    upper == "PK"
+        || upper.ends_with("_PK")
+        || upper.contains("PUBLIC_KEY")
+        || upper.contains("PUBKEY")
+}
+
+/// Every `prefix::NAME` on `line`.
+pub(crate) fn qualified_names<'a>(line: &'a str, prefix: &str) -> Vec<&'a str> {
+    let mut names = Vec::new();
+    let mut rest = line;
+    while let Some(at) = rest.find(prefix) {
+        let preceded_by_ident = rest[..at].chars().next_back().is_some_and(is_ident_char);
+        let tail = &rest[at + prefix.len()..];
+        let end = tail.find(|c: char| !is_ident_char(c)).unwrap_or(tail.len());
+        if !preceded_by_ident && end > 0 {
+            names.push(&tail[..end]);
+        }
+        rest = &tail[end..];
+    }
+    names
+}
+
+/// Every 32-byte `0x` literal on `line`.
+fn hex_literals(line: &str) -> Vec<String> {
+    let mut literals = Vec::new();
+    let mut rest = line;
+    while let Some(at) = rest.find("0x") {
+        let tail = &rest[at + 2..];
+        let end = tail.find(|c: char| !c.is_ascii_hexdigit()).unwrap_or(tail.len());
+        if end == 64 {
+            literals.push(tail[..end].to_ascii_lowercase());
+        }
+        rest = &tail[end..];
+    }
+    literals
+}
+
+/// Look up `name` in witness JSON of the form `{"NAME": {"value": "0x.."}}`.
+fn witness_value(witness: &serde_json::Value, name: &str) -> Option<String> {
+    let value = witness.get(name)?.get("value")?.as_str()?;
+    crate::manifest::normalize_pubkey(value)
+}
+
+pub(crate) fn strip_comment(line: &str) -> &str {
+    line.split_once("//").map_or(line, |(code, _)| code)
+}
+
+/// Collect the public keys a program uses, with where they come from.
+pub fn find_keys(code: &str, witness_json: &str) -> Vec<KeyRef> {
+    let witness = serde_json::from_str::<serde_json::Value>(witness_json).unwrap_or_default();
+    let mut keys = Vec::new();
+
+    for (index, line) in code.lines().enumerate() {
+        let line_number = index + 1;
+        let line = strip_comment(line);
+        let typed_as_key = line.contains("Pubkey");
+
+        for name in qualified_names(line, "witness::") {
+            if typed_as_key || looks_like_key_name(name) {
+                keys.push(KeyRef {
+                    pubkey: witness_value(&witness, name),
+                    source: KeySource::Witness(name.to_string()),
+                    line: line_number,
+                });
+            }
+        }
+        for name in qualified_names(line, "param::") {
+            if typed_as_key || looks_like_key_name(name) {
+                keys.push(KeyRef {
+                    source: KeySource::Param(name.to_string()),
+                    pubkey: None,
+                    line: line_number,
+                });
+            }
+        }
+        let lower = line.to_ascii_lowercase();
+        if typed_as_key || lower.contains("pk") || lower.contains("key") {
+            for pubkey in hex_literals(line) {
+                keys.push(KeyRef {
+                    source: KeySource::Literal,
+                    pubkey: Some(pubkey),
+                    line: line_number,
+                });
+            }
+        }
+    }
+    keys
+}
+
+/// Run the sanity pass for `code` compiled with `witness_json`.
+pub fn check(code: &str, witness_json: &str, manifest: &KeyManifest) -> SanityReport {
+    let keys = find_keys(code, witness_json);
+    let mut findings = satisfiable::check(code);
+    findings.extend(leakage::check(code));
+
+    for key in &keys {
+        if let KeySource::Witness(name) = &key.source {
+            findings.push(Diagnostic::error(
+                key.line,
+                1,
+                format!(
+                    "`witness::{name}` is used as a public key but comes from the witness. \
+                     The address does not commit to it, so any spender can supply their own key."
+                ),
+            ));
+        }
+    }
+
+    let committed: Vec<&KeyRef> = keys
+        .iter()
+        .filter(|k| !matches!(k.source, KeySource::Witness(_)))
+        .collect();
+    let own_keys: Vec<String> = committed
+        .iter()
+        .filter_map(|k| k.pubkey.as_deref())
+        .filter(|pk| manifest.find(pk).is_some())
+        .map(str::to_string)
+        .collect();
+    let has_unknown_values = committed.iter().any(|k| k.pubkey.is_none());
+
+    if let Some(first) = committed.first() {
+        if manifest.keys.is_empty() {
+            findings.push(Diagnostic::warning(
+                first.line,
+                1,
+                format!(
+                    "Your key manifest is empty, so ownership of the {} committed key(s) cannot be checked.",
+                    committed.len()
+                ),
+            ));
+        } else if own_keys.is_empty() && !has_unknown_values {
+            findings.push(Diagnostic::error(
+                first.line,
+                1,
+                "None of the keys this program commits to are in your manifest; \
+                 every spend path requires third-party keys.",
+            ));
+        }
+    } else if !code.contains("bip_0340_verify") && !code.contains("checksig") {
+        findings.push(Diagnostic::warning(
+            1,
+            1,
+            "The program checks no signatures; anyone who can satisfy it can spend.",
+        ));
+    }
+
+    SanityReport {
+        keys,
+        own_keys,
+        findings,
+    }
+}
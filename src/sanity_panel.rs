@@ -0,0 +1,86 @@
+//! Sanity findings shown in place of the address until acknowledged, plus an
+//! editor for the key manifest they are checked against.
+
+use leptos::prelude::*;
+
+use crate::diagnostics::Severity;
+use crate::i18n::{self, t, Msg};
+use crate::manifest::KeyManifest;
+use crate::sanity::SanityReport;
+
+#[component]
+pub fn SanityPanel(
+    #[prop(into)] report: Signal<Option<SanityReport>>,
+    /// Set once the user has read the findings and wants the address anyway.
+    acknowledged: RwSignal<bool>,
+    manifest: RwSignal<KeyManifest>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let (manifest_text, set_manifest_text) = signal(manifest.with_untracked(KeyManifest::to_text));
+    let (manifest_status, set_manifest_status) = signal::<Option<Result<String, String>>>(None);
+
+    let save_manifest = move |_| match KeyManifest::parse(&manifest_text.get_untracked()) {
+        Ok(parsed) => {
+            parsed.save();
+            set_manifest_status.set(Some(Ok(i18n::format(
+                i18n::text(lang.get_untracked(), Msg::KeysSaved),
+                &[&parsed.keys.len().to_string()],
+            ))));
+            manifest.set(parsed);
+        }
+        Err(e) => set_manifest_status.set(Some(Err(e))),
+    };
+
+    let findings = move || {
+        let report = report.get().filter(|r| !r.is_clean())?;
+        if acknowledged.get() {
+            return None;
+        }
+        let items = report
+            .findings
+            .into_iter()
+            .map(|d| {
+                let class = match d.severity {
+                    Severity::Error => "sanity-finding error",
+                    Severity::Warning => "sanity-finding warning",
+                };
+                let text = i18n::format(i18n::text(lang.get(), Msg::LineMessage), &[&d.line.to_string(), &d.message]);
+                view! { <li class=class>{text}</li> }
+            })
+            .collect_view();
+        Some(view! {
+            <div class="sanity-findings" role="alert">
+                <strong>{t(Msg::ReviewBeforeSharing)}</strong>
+                <ul>{items}</ul>
+                <button class="secondary" on:click=move |_| acknowledged.set(true)>
+                    {t(Msg::ShowAddressAnyway)}
+                </button>
+            </div>
+        })
+    };
+
+    view! {
+        {findings}
+        <details class="manifest-editor">
+            <summary>
+                {move || i18n::format(i18n::text(lang.get(), Msg::MyKeys), &[&manifest.with(|m| m.keys.len()).to_string()])}
+            </summary>
+            <textarea
+                class="manifest-input"
+                spellcheck="false"
+                placeholder=t(Msg::ManifestPlaceholder)
+                prop:value=move || manifest_text.get()
+                on:input=move |ev| set_manifest_text.set(event_target_value(&ev))
+            />
+            <div class="button-group">
+                <button class="secondary" on:click=save_manifest>{t(Msg::SaveKeys)}</button>
+            </div>
+            {move || {
+                manifest_status.get().map(|status| match status {
+                    Ok(message) => view! { <p class="manifest-status">{message}</p> }.into_any(),
+                    Err(e) => view! { <p class="manifest-status error">{e}</p> }.into_any(),
+                })
+            }}
+        </details>
+    }
+}
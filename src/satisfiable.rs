@@ -0,0 +1,206 @@
+//! Static detection of spend paths that can never succeed.
+//!
+//! A path fails for every witness when it reaches `assert!(false)` or
+//! `panic!`, asserts two different literals equal, asserts one value equal
+//! to two different numbers, or mixes lock kinds the transaction cannot
+//! satisfy together: a lock time is either a height or a UNIX time, and a
+//! relative lock either counts blocks or 512-second units. When every path
+//! fails the program is unspendable and funds sent to it are lost.
+//!
+//! Paths are read from `main` with helper calls inlined, so a helper only
+//! counts on the paths that call it, and one nobody calls on none.
+
+use crate::coverage::layout;
+use crate::diagnostics::Diagnostic;
+use crate::sanity::strip_comment;
+use crate::spend_paths::{arm_paths, call_argument, inline_calls, split_arguments, Inlined};
+use crate::triage::resolve_number;
+
+/// The first reason the lines of one path fail for every witness, as
+/// (1-based source line, reason).
+fn failure(inlined: &Inlined, lines: &[&str], included: &[usize]) -> Option<(usize, String)> {
+    // Value asserted equal to a number, with the line it happened on.
+    let mut pinned: Vec<(&str, u64, usize)> = Vec::new();
+    let mut locks: Vec<(&str, usize)> = Vec::new();
+
+    for &index in included {
+        let line = strip_comment(lines[index]);
+        let compact: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+        if compact.contains("assert!(false)") || compact.contains("panic!(") {
+            return Some((inlined.source_line(index), "it always aborts here".to_string()));
+        }
+
+        for jet in ["check_lock_height", "check_lock_time", "check_lock_distance", "check_lock_duration"] {
+            if line.contains(&format!("jet::{jet}(")) {
+                locks.push((jet, inlined.source_line(index)));
+            }
+        }
+
+        if !compact.starts_with("assert!(jet::eq_") {
+            continue;
+        }
+        let Some(name) = compact["assert!(jet::".len()..].split('(').next() else {
+            continue;
+        };
+        let Some(arguments) = call_argument(line, name) else {
+            continue;
+        };
+        let [left, right] = split_arguments(arguments)[..] else {
+            continue;
+        };
+        let (operand, n) = match (resolve_number(lines, index, left), resolve_number(lines, index, right)) {
+            (Some(a), Some(b)) if a != b => {
+                return Some((inlined.source_line(index), format!("it asserts {a} equals {b}")));
+            }
+            (Some(n), None) => (right, n),
+            (None, Some(n)) => (left, n),
+            _ => continue,
+        };
+        match pinned.iter().find(|(o, _, _)| *o == operand) {
+            Some(&(_, m, at)) if m != n => {
+                let reason = format!("`{operand}` must equal both {m} (line {at}) and {n}");
+                return Some((inlined.source_line(index), reason));
+            }
+            Some(_) => {}
+            None => pinned.push((operand, n, inlined.source_line(index))),
+        }
+    }
+
+    let find = |jet: &str| locks.iter().find(|(j, _)| *j == jet).map(|&(_, line)| line);
+    for (height, time, what) in [
+        ("check_lock_height", "check_lock_time", "the lock time cannot be both a block height and a UNIX time"),
+        ("check_lock_distance", "check_lock_duration", "a relative lock cannot count both blocks and time"),
+    ] {
+        if let (Some(a), Some(b)) = (find(height), find(time)) {
+            return Some((a.max(b), format!("`{height}` and `{time}` together: {what}")));
+        }
+    }
+    None
+}
+
+/// Warnings for the spend paths of `code` no witness can satisfy, or an
+/// error per path when none can.
+pub fn check(code: &str) -> Vec<Diagnostic> {
+    let inlined = inline_calls(code);
+    let layout = layout(&inlined.code);
+    let lines: Vec<&str> = inlined.code.lines().collect();
+    let mut truncated = false;
+    let paths = arm_paths(&layout, None, &mut truncated);
+
+    let failures: Vec<Option<(usize, String)>> = paths
+        .iter()
+        .map(|arms| {
+            let included: Vec<usize> = (0..lines.len())
+                .filter(|&index| layout.line_parents[index].map_or(true, |parent| arms.contains(&parent)))
+                .collect();
+            failure(&inlined, &lines, &included)
+        })
+        .collect();
+
+    let unspendable = !truncated && failures.iter().all(Option::is_some);
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    for (index, failure) in failures.into_iter().enumerate() {
+        let Some((line, reason)) = failure else {
+            continue;
+        };
+        // Paths share their unconditional lines; report each line once.
+        if diagnostics.iter().any(|d| d.line == line) {
+            continue;
+        }
+        diagnostics.push(if unspendable {
+            Diagnostic::error(
+                line,
+                1,
+                format!("No witness can satisfy this program: {reason}. Funds sent to its address are lost."),
+            )
+        } else {
+            Diagnostic::warning(line, 1, format!("Spend path {} can never succeed: {reason}.", index + 1))
+        });
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::Severity;
+
+    const HELPERS: &str = "fn by_height() {
+    jet::check_lock_height(800000);
+}
+
+fn by_time() {
+    jet::check_lock_time(1700000000);
+}
+
+fn two_outputs() {
+    assert!(jet::eq_32(jet::num_outputs(), 2));
+}
+
+fn three_outputs() {
+    assert!(jet::eq_32(jet::num_outputs(), 3));
+}
+
+fn never_called() {
+    assert!(false);
+}
+";
+
+    fn program(main: &str) -> String {
+        format!("{HELPERS}\nfn main() {{\n{main}\n}}")
+    }
+
+    #[test]
+    fn uncalled_helpers_are_ignored() {
+        assert!(check(&program("    by_height();")).is_empty());
+    }
+
+    #[test]
+    fn helpers_count_on_their_arm_only() {
+        let code = program(
+            "    match witness::PATH {
+        Left(a: ()) => by_height(),
+        Right(b: ()) => by_time(),
+    }",
+        );
+        assert!(check(&code).is_empty());
+
+        let code = program(
+            "    match witness::PATH {
+        Left(a: ()) => two_outputs(),
+        Right(b: ()) => three_outputs(),
+    }",
+        );
+        assert!(check(&code).is_empty());
+
+        let code = program(
+            "    match witness::PATH {
+        Left(a: ()) => never_called(),
+        Right(b: ()) => by_time(),
+    }",
+        );
+        let findings = check(&code);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Warning);
+        assert_eq!(findings[0].line, 18);
+    }
+
+    #[test]
+    fn contradictions_across_helpers_are_found() {
+        let findings = check(&program("    by_height();\n    by_time();"));
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert_eq!(findings[0].line, 6);
+
+        let findings = check(&program("    two_outputs();\n    three_outputs();"));
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.contains("must equal both 2 (line 10) and 3"));
+    }
+
+    #[test]
+    fn bundled_contracts_are_satisfiable() {
+        assert!(check(include_str!("../contract/lastwill.simf")).is_empty());
+        assert!(check(include_str!("../contract/simple.simf")).is_empty());
+        assert!(check(&crate::wizards::htlc::example()).is_empty());
+    }
+}
@@ -0,0 +1,170 @@
+//! JSON Schemas for witness and argument files, and a validator for them.
+//!
+//! Both formats map a SimplicityHL identifier to `{"value": ..., "type": ...}`
+//! with the value written as a SimplicityHL literal in a string. The
+//! published schemas (draft 2020-12) describe that shape and the value syntax
+//! of the common types; [`validate`] checks the same rules and also integer
+//! widths and lock-time ranges, which a pattern cannot express. Errors carry
+//! a JSON Pointer to the offending member, e.g. `/PREIMAGE/value`.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::witness_form::InputKind;
+
+const IDENTIFIER: &str = "^[A-Za-z_][A-Za-z0-9_]*$";
+
+/// Value patterns by type, for the published schema.
+const VALUE_PATTERNS: &[(&[&str], &str)] = &[
+    (&["bool"], "^(true|false)$"),
+    (
+        &["u1", "u2", "u4", "u8", "u16", "u32", "u64", "Height", "Lock", "Distance", "Duration", "Time"],
+        "^[0-9]+$",
+    ),
+    (&["u128"], "^0x[0-9a-fA-F]{32}$"),
+    (&["u256", "Pubkey", "Message", "Scalar", "Fe", "Ctx8"], "^0x[0-9a-fA-F]{64}$"),
+    (&["Signature", "Message64"], "^0x[0-9a-fA-F]{128}$"),
+];
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SchemaError {
+    /// JSON Pointer to the offending member; empty for the whole document.
+    pub pointer: String,
+    pub message: String,
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.pointer.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.pointer, self.message)
+        }
+    }
+}
+
+fn schema(id: &str, title: &str, description: &str) -> Value {
+    let typed_values: Vec<Value> = VALUE_PATTERNS
+        .iter()
+        .map(|(types, pattern)| {
+            json!({
+                "if": { "properties": { "type": { "enum": types } } },
+                "then": { "properties": { "value": { "pattern": pattern } } }
+            })
+        })
+        .collect();
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$id": id,
+        "title": title,
+        "description": description,
+        "type": "object",
+        "propertyNames": { "pattern": IDENTIFIER },
+        "additionalProperties": {
+            "type": "object",
+            "required": ["value", "type"],
+            "properties": {
+                "value": {
+                    "type": "string",
+                    "description": "SimplicityHL literal, e.g. `0x…`, `42`, `true` or `Left(0x01)`."
+                },
+                "type": {
+                    "type": "string",
+                    "description": "SimplicityHL type, e.g. `u256`, `Signature` or `Either<u8, bool>`."
+                }
+            },
+            "additionalProperties": false,
+            "allOf": typed_values
+        }
+    })
+}
+
+/// Schema of the witness JSON read by `compile_with_witness` and friends.
+pub fn witness_schema() -> Value {
+    schema(
+        "simplicity-wasm/witness.schema.json",
+        "SimplicityHL witness values",
+        "Values for the `witness::NAME` expressions of a program, by name.",
+    )
+}
+
+/// Schema of parameter values in JSON form, as serialized by SimplicityHL.
+pub fn arguments_schema() -> Value {
+    schema(
+        "simplicity-wasm/arguments.schema.json",
+        "SimplicityHL arguments",
+        "Values for the `param::NAME` expressions of a program, by name.",
+    )
+}
+
+/// `token` escaped as a JSON Pointer segment.
+fn pointer_segment(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Every way `json` breaks the witness/arguments schema, by entry name.
+pub fn validate(json: &str) -> Vec<SchemaError> {
+    let error = |pointer: String, message: &str| SchemaError {
+        pointer,
+        message: message.to_string(),
+    };
+    let document = match serde_json::from_str::<Value>(json) {
+        Ok(document) => document,
+        Err(e) => {
+            return vec![error(
+                String::new(),
+                &format!("Invalid JSON at line {}, column {}: {}", e.line(), e.column(), e),
+            )]
+        }
+    };
+    let Value::Object(entries) = document else {
+        return vec![error(String::new(), "Expected an object mapping names to {\"value\", \"type\"}")];
+    };
+
+    let mut errors = Vec::new();
+    for (name, entry) in &entries {
+        let pointer = format!("/{}", pointer_segment(name));
+        if !is_identifier(name) {
+            errors.push(error(pointer.clone(), "Not a valid SimplicityHL identifier"));
+        }
+        let Value::Object(fields) = entry else {
+            errors.push(error(pointer, "Expected an object with \"value\" and \"type\""));
+            continue;
+        };
+        for key in fields.keys().filter(|key| !matches!(key.as_str(), "value" | "type")) {
+            errors.push(error(format!("{pointer}/{}", pointer_segment(key)), "Unknown property"));
+        }
+        let text = |key: &str| match fields.get(key) {
+            None => Err(error(pointer.clone(), &format!("Missing \"{key}\""))),
+            Some(Value::String(text)) => Ok(text.as_str()),
+            Some(_) => Err(error(format!("{pointer}/{key}"), "Expected a string")),
+        };
+        let (value, ty) = (text("value"), text("type"));
+        match (value, ty) {
+            (Ok(value), Ok(ty)) => {
+                if let Err(message) = InputKind::of(ty).validate(value) {
+                    errors.push(error(format!("{pointer}/value"), &message));
+                }
+            }
+            (value, ty) => errors.extend([value.err(), ty.err()].into_iter().flatten()),
+        }
+    }
+    errors
+}
+
+/// The errors of [`validate`] as one message, or `Ok` when there are none.
+pub fn check(json: &str) -> Result<(), String> {
+    let errors = validate(json);
+    if errors.is_empty() {
+        return Ok(());
+    }
+    Err(errors.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n"))
+}
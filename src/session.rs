@@ -0,0 +1,145 @@
+//! Versioned snapshots of the whole playground state.
+//!
+//! A session file holds everything needed to reproduce a compile: the code,
+//! the witness and any named witness sets, the compiler options, the network and taproot tree the
+//! address is derived under, and the contract tests. Parameter values live
+//! in the code's `mod param` block, so they travel with it. The results of
+//! the last compile are recorded alongside, so whoever imports the session
+//! can check their build reproduces them.
+//!
+//! The same state also packs into a ZIP archive with one file per buffer,
+//! for backups and for sharing with people who edit the files directly.
+
+use serde::{Deserialize, Serialize};
+
+use crate::address::Network;
+use crate::metadata::Metadata;
+use crate::options::CompileOptions;
+use crate::taproot::TaprootConfig;
+use crate::timestamp::Stamp;
+use crate::witness_sets::WitnessSets;
+use crate::zip::{self, Entry};
+
+/// Format version written by this build. Older versions are read as far as
+/// their fields go; newer ones are refused.
+pub const SESSION_VERSION: u32 = 1;
+
+/// Files of a project archive besides the session itself. Each is also
+/// editable on its own and overrides the session's copy on import.
+const ARCHIVE_CODE: &str = "contract.simf";
+const ARCHIVE_WITNESS: &str = "witness.json";
+const ARCHIVE_TESTS: &str = "tests.json";
+const ARCHIVE_SESSION: &str = "session.json";
+
+/// What the last compile produced.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionResults {
+    #[serde(default)]
+    pub cmr: Option<String>,
+    #[serde(default)]
+    pub address: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default)]
+    pub compiled_at: Option<Stamp>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    pub version: u32,
+    pub code: String,
+    #[serde(default)]
+    pub witness: String,
+    #[serde(default)]
+    pub options: CompileOptions,
+    #[serde(default)]
+    pub network: Network,
+    #[serde(default)]
+    pub taproot: TaprootConfig,
+    /// Contract test spec, see [`test_runner`](crate::test_runner).
+    #[serde(default)]
+    pub tests: String,
+    /// Named witnesses; `witness` is the active one's.
+    #[serde(default)]
+    pub witness_sets: WitnessSets,
+    #[serde(default)]
+    pub results: SessionResults,
+    /// The code's `//!` header, so the file says what it holds without
+    /// reading the source.
+    #[serde(default)]
+    pub metadata: Option<Metadata>,
+    /// When the session was exported.
+    #[serde(default)]
+    pub exported_at: Stamp,
+}
+
+impl Session {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    pub fn parse(json: &str) -> Result<Session, String> {
+        let value = serde_json::from_str::<serde_json::Value>(json).map_err(|e| format!("Invalid session file: {e}"))?;
+        match value.get("version").and_then(|v| v.as_u64()) {
+            None => return Err("Not a session file: no `version` field".to_string()),
+            Some(version) if version > u64::from(SESSION_VERSION) => {
+                return Err(format!(
+                    "Session version {version} is newer than this playground supports ({SESSION_VERSION})"
+                ))
+            }
+            Some(_) => {}
+        }
+        serde_json::from_value(value).map_err(|e| format!("Invalid session file: {e}"))
+    }
+
+    /// The session as a ZIP archive: the source, witness and tests as their
+    /// own files, and `session.json` with the settings and results.
+    pub fn to_archive(&self) -> Vec<u8> {
+        zip::pack(&[
+            Entry::new(ARCHIVE_CODE, self.code.as_bytes()),
+            Entry::new(ARCHIVE_WITNESS, self.witness.as_bytes()),
+            Entry::new(ARCHIVE_TESTS, self.tests.as_bytes()),
+            Entry::new(ARCHIVE_SESSION, self.to_json().as_bytes()),
+        ])
+    }
+
+    /// Read a project archive. `session.json` may be missing, as in an
+    /// archive of hand-picked files; the source is then the first `.simf`.
+    pub fn from_archive(bytes: &[u8]) -> Result<Session, String> {
+        let entries = zip::unpack(bytes)?;
+        let file_name = |entry: &Entry| entry.name.rsplit('/').next().unwrap_or_default().to_string();
+        let find = |name: &str| entries.iter().find(|e| file_name(e) == name);
+
+        let mut session = match find(ARCHIVE_SESSION) {
+            Some(entry) => Session::parse(&entry.text()?)?,
+            None => serde_json::from_value(serde_json::json!({ "version": SESSION_VERSION, "code": "" }))
+                .map_err(|e| e.to_string())?,
+        };
+        match find(ARCHIVE_CODE).or_else(|| entries.iter().find(|e| e.name.ends_with(".simf"))) {
+            Some(entry) => session.code = entry.text()?,
+            None if session.code.is_empty() => return Err("The archive holds no .simf source".to_string()),
+            None => {}
+        }
+        if let Some(entry) = find(ARCHIVE_WITNESS) {
+            session.witness = entry.text()?;
+        }
+        if let Some(entry) = find(ARCHIVE_TESTS) {
+            session.tests = entry.text()?;
+        }
+        Ok(session)
+    }
+
+    /// One line on where the session came from and what it recorded.
+    pub fn describe(&self) -> String {
+        let recorded = match (&self.results.cmr, &self.results.error) {
+            (Some(cmr), _) => format!("recorded CMR {cmr}"),
+            (None, Some(error)) => format!("recorded error: {error}"),
+            (None, None) => "no recorded results".to_string(),
+        };
+        let name = match self.metadata.as_ref().and_then(|m| m.name.as_deref()) {
+            Some(name) => format!(" of {name}"),
+            None => String::new(),
+        };
+        format!("Imported session{name} exported {}; {recorded}. Compile to reproduce.", self.exported_at.utc)
+    }
+}
@@ -0,0 +1,150 @@
+//! Playground preferences, persisted in localStorage.
+//!
+//! Stored settings outlive the code that wrote them, so reading is lenient:
+//! each field is read on its own and falls back to its default when it is
+//! missing or no longer parses, unknown fields are ignored, and values are
+//! clamped to what the current version accepts. A bad or renamed field
+//! therefore resets only itself, never the rest. [`SETTINGS_VERSION`] is
+//! bumped when a field changes meaning, with the conversion in [`migrate`].
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::address::Network;
+use crate::storage;
+
+pub const SETTINGS_VERSION: u32 = 1;
+
+const SETTINGS_KEY: &str = "settings";
+
+pub const FONT_SIZES: std::ops::RangeInclusive<u32> = 10..=24;
+pub const TAB_WIDTHS: std::ops::RangeInclusive<u32> = 1..=8;
+/// Most workers a batch compile starts, however many cores are reported.
+pub const MAX_WORKERS: u32 = 8;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    /// Follow the operating system.
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::System, Theme::Light, Theme::Dark];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Theme::System => "system",
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Settings {
+    pub version: u32,
+    /// Editor font size in pixels.
+    pub font_size: u32,
+    /// Spaces per indentation level, and the width of a tab character.
+    pub tab_width: u32,
+    /// Compile shortly after every edit instead of on the Compile button.
+    pub auto_compile: bool,
+    pub theme: Theme,
+    /// Network selected when the page opens; sessions still bring their own.
+    pub default_network: Network,
+    /// Workers for batch compiles; 0 uses one per logical core.
+    pub workers: u32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            version: SETTINGS_VERSION,
+            font_size: 13,
+            tab_width: 4,
+            auto_compile: false,
+            theme: Theme::default(),
+            default_network: Network::default(),
+            workers: 0,
+        }
+    }
+}
+
+/// `map[name]`, or `default` when it is missing or does not parse.
+fn field<T: DeserializeOwned>(map: &Map<String, Value>, name: &str, default: T) -> T {
+    map.get(name)
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+        .unwrap_or(default)
+}
+
+/// Bring settings written by an older version up to [`SETTINGS_VERSION`].
+/// Version 0 is anything written without a version field.
+fn migrate(mut map: Map<String, Value>) -> Map<String, Value> {
+    let version = map.get("version").and_then(Value::as_u64).unwrap_or(0);
+    if version < 1 {
+        // Nothing was stored before version 1 under this key.
+        map.insert("version".to_string(), Value::from(1));
+    }
+    map
+}
+
+impl Settings {
+    /// Read settings from JSON, keeping every field that still makes sense.
+    pub fn from_json(json: &str) -> Self {
+        let Ok(Value::Object(map)) = serde_json::from_str::<Value>(json) else {
+            return Settings::default();
+        };
+        let map = migrate(map);
+        let default = Settings::default();
+        Settings {
+            version: SETTINGS_VERSION,
+            font_size: field(&map, "font_size", default.font_size),
+            tab_width: field(&map, "tab_width", default.tab_width),
+            auto_compile: field(&map, "auto_compile", default.auto_compile),
+            theme: field(&map, "theme", default.theme),
+            default_network: field(&map, "default_network", default.default_network),
+            workers: field(&map, "workers", default.workers),
+        }
+        .clamped()
+    }
+
+    pub fn load() -> Self {
+        storage::load(SETTINGS_KEY)
+            .map(|json| Settings::from_json(&json))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Ok(json) = serde_json::to_string(self) {
+            storage::save(SETTINGS_KEY, &json);
+        }
+    }
+
+    /// These settings with every number in its accepted range.
+    pub fn clamped(mut self) -> Self {
+        self.font_size = self.font_size.clamp(*FONT_SIZES.start(), *FONT_SIZES.end());
+        self.tab_width = self.tab_width.clamp(*TAB_WIDTHS.start(), *TAB_WIDTHS.end());
+        self.workers = self.workers.min(MAX_WORKERS);
+        self
+    }
+
+    /// Editor line height in pixels, in proportion to the font size.
+    pub fn line_height(&self) -> u32 {
+        (self.font_size * 18).div_ceil(13)
+    }
+
+    /// CSS custom properties for the editor, for a `style` attribute.
+    pub fn editor_style(&self) -> String {
+        format!(
+            "--sw-editor-font-size: {}px; --sw-editor-line-height: {}px; --sw-tab-size: {};",
+            self.font_size,
+            self.line_height(),
+            self.tab_width
+        )
+    }
+}
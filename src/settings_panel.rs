@@ -0,0 +1,162 @@
+//! The Settings dialog. Every change applies and is saved at once.
+
+use leptos::prelude::*;
+
+use crate::address::Network;
+use crate::i18n::{self, t, Msg};
+use crate::settings::{Settings, Theme, FONT_SIZES, MAX_WORKERS, TAB_WIDTHS};
+
+/// Apply `change` to the settings and save them.
+fn update(settings: RwSignal<Settings>, change: impl FnOnce(&mut Settings)) {
+    settings.update(|s| {
+        change(s);
+        *s = s.clone().clamped();
+        s.save();
+    });
+}
+
+#[component]
+pub fn SettingsDialog(settings: RwSignal<Settings>, open: RwSignal<bool>) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let close = move || open.set(false);
+
+    view! {
+        <Show when=move || open.get()>
+            <div class="settings-backdrop" on:click=move |_| close()>
+                <div
+                    class="settings-dialog"
+                    role="dialog"
+                    aria-modal="true"
+                    aria-labelledby="settings-title"
+                    on:click=|ev| ev.stop_propagation()
+                    on:keydown=move |ev: web_sys::KeyboardEvent| {
+                        if ev.key() == "Escape" {
+                            close();
+                        }
+                    }
+                >
+                    <h2 id="settings-title">{t(Msg::SettingsTitle)}</h2>
+                    <div class="settings-grid">
+                        <label for="settings-font-size">{t(Msg::EditorFontSize)}</label>
+                        <input
+                            id="settings-font-size"
+                            type="number"
+                            min=*FONT_SIZES.start()
+                            max=*FONT_SIZES.end()
+                            prop:value=move || settings.with(|s| s.font_size.to_string())
+                            on:change=move |ev| {
+                                if let Ok(n) = event_target_value(&ev).parse() {
+                                    update(settings, |s| s.font_size = n);
+                                }
+                            }
+                        />
+
+                        <label for="settings-tab-width">{t(Msg::TabWidth)}</label>
+                        <input
+                            id="settings-tab-width"
+                            type="number"
+                            min=*TAB_WIDTHS.start()
+                            max=*TAB_WIDTHS.end()
+                            prop:value=move || settings.with(|s| s.tab_width.to_string())
+                            on:change=move |ev| {
+                                if let Ok(n) = event_target_value(&ev).parse() {
+                                    update(settings, |s| s.tab_width = n);
+                                }
+                            }
+                        />
+
+                        <label for="settings-auto-compile">{t(Msg::CompileWhileTyping)}</label>
+                        <input
+                            id="settings-auto-compile"
+                            type="checkbox"
+                            prop:checked=move || settings.with(|s| s.auto_compile)
+                            on:change=move |ev| {
+                                let on = event_target_checked(&ev);
+                                update(settings, |s| s.auto_compile = on);
+                            }
+                        />
+
+                        <label for="settings-theme">{t(Msg::Theme)}</label>
+                        <select
+                            id="settings-theme"
+                            on:change=move |ev| {
+                                let value = event_target_value(&ev);
+                                if let Some(theme) = Theme::ALL.into_iter().find(|t| t.as_str() == value) {
+                                    update(settings, |s| s.theme = theme);
+                                }
+                            }
+                        >
+                            {Theme::ALL
+                                .into_iter()
+                                .map(|theme| {
+                                    view! {
+                                        <option value=theme.as_str() selected=move || settings.with(|s| s.theme == theme)>
+                                            {theme.as_str()}
+                                        </option>
+                                    }
+                                })
+                                .collect_view()}
+                        </select>
+
+                        <label for="settings-network">{t(Msg::DefaultNetwork)}</label>
+                        <select
+                            id="settings-network"
+                            on:change=move |ev| {
+                                if let Ok(network) = event_target_value(&ev).parse::<Network>() {
+                                    update(settings, |s| s.default_network = network);
+                                }
+                            }
+                        >
+                            {Network::ALL
+                                .into_iter()
+                                .map(|n| {
+                                    view! {
+                                        <option value=n.as_str() selected=move || settings.with(|s| s.default_network == n)>
+                                            {n.as_str()}
+                                        </option>
+                                    }
+                                })
+                                .collect_view()}
+                        </select>
+
+                        <label for="settings-workers">{t(Msg::CompileWorkers)}</label>
+                        <select
+                            id="settings-workers"
+                            on:change=move |ev| {
+                                if let Ok(n) = event_target_value(&ev).parse() {
+                                    update(settings, |s| s.workers = n);
+                                }
+                            }
+                        >
+                            {(0..=MAX_WORKERS)
+                                .map(|n| {
+                                    let label = match n {
+                                        0 => i18n::text(lang.get(), Msg::Automatic).to_string(),
+                                        _ => n.to_string(),
+                                    };
+                                    view! {
+                                        <option value=n.to_string() selected=move || settings.with(|s| s.workers == n)>
+                                            {label}
+                                        </option>
+                                    }
+                                })
+                                .collect_view()}
+                        </select>
+                    </div>
+                    <p class="drop-zone-hint">
+                        {t(Msg::SettingsHint)}
+                    </p>
+                    <div class="button-group">
+                        <button
+                            class="secondary"
+                            on:click=move |_| update(settings, |s| *s = Settings::default())
+                        >
+                            {t(Msg::ResetToDefaults)}
+                        </button>
+                        <button on:click=move |_| close()>{t(Msg::Close)}</button>
+                    </div>
+                </div>
+            </div>
+        </Show>
+    }
+}
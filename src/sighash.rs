@@ -0,0 +1,115 @@
+//! The signature hash Simplicity's `jet::sig_all_hash` computes.
+//!
+//! It commits to the whole spending transaction, every output it spends, the
+//! input index, the tapleaf (CMR and control block) and the chain's genesis
+//! block, but not to the witness. A transaction can therefore be built with
+//! placeholder signatures, hashed here, signed elsewhere and rebuilt with the
+//! real signatures without changing the hash.
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::elements::encode::deserialize;
+use simplicityhl::simplicity::elements::{Address, BlockHash, Script, Transaction};
+use simplicityhl::simplicity::hashes::Hash;
+use simplicityhl::simplicity::jet::elements::{ElementsEnv, ElementsUtxo};
+use simplicityhl::simplicity::Cmr;
+
+use crate::address::{parse_cmr, Network};
+use crate::assets;
+use crate::hex;
+use crate::taproot::TaprootConfig;
+
+/// An output spent by the transaction, in input order.
+///
+/// Give either `address` or `script_pubkey` (hex). The network, and with it
+/// the genesis hash, is taken from the first `address`; outputs given only
+/// by script are assumed to be on Liquid testnet.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SighashUtxo {
+    #[serde(default)]
+    pub address: Option<String>,
+    #[serde(default)]
+    pub script_pubkey: Option<String>,
+    /// Asset id, or confidential asset commitment, in hex.
+    pub asset: String,
+    #[serde(default)]
+    pub value: u64,
+    /// Confidential amount commitment in hex; replaces `value`.
+    #[serde(default)]
+    pub value_commitment: Option<String>,
+}
+
+/// The transaction environment a program spending input `input_index` of
+/// `tx` through the leaf with CMR `cmr`, in the tree `config` describes,
+/// runs in.
+pub(crate) fn elements_env(
+    tx: Transaction,
+    input_index: u32,
+    utxos: &[SighashUtxo],
+    cmr: Cmr,
+    config: &TaprootConfig,
+) -> Result<ElementsEnv<Arc<Transaction>>, String> {
+    if input_index as usize >= tx.input.len() {
+        return Err(format!("Input {input_index} does not exist; the transaction has {}", tx.input.len()));
+    }
+    if utxos.len() != tx.input.len() {
+        return Err(format!(
+            "Got {} spent outputs for {} inputs; give one per input, in order",
+            utxos.len(),
+            tx.input.len()
+        ));
+    }
+
+    let mut network = None;
+    let mut spent = Vec::with_capacity(utxos.len());
+    for (index, utxo) in utxos.iter().enumerate() {
+        let script_pubkey = match (&utxo.address, &utxo.script_pubkey) {
+            (Some(address), _) => {
+                let address = Address::from_str(address).map_err(|e| format!("Output {index}: invalid address: {e}"))?;
+                network = network.or(Network::of_address(&address));
+                address.script_pubkey()
+            }
+            (None, Some(script)) => Script::from(hex::decode(script)?),
+            (None, None) => return Err(format!("Output {index}: give an address or script_pubkey")),
+        };
+        spent.push(ElementsUtxo {
+            script_pubkey,
+            asset: assets::parse_asset(&utxo.asset).map_err(|e| format!("Output {index}: {e}"))?,
+            value: assets::parse_value(utxo.value, utxo.value_commitment.as_deref())
+                .map_err(|e| format!("Output {index}: {e}"))?,
+        });
+    }
+
+    let network = network.unwrap_or_default();
+    let genesis = network
+        .genesis_hash()
+        .ok_or_else(|| format!("The genesis hash of `{}` chains is not known", network.as_str()))?;
+    let genesis = BlockHash::from_str(genesis).expect("constant is a valid hash");
+
+    Ok(ElementsEnv::new(
+        Arc::new(tx),
+        spent,
+        input_index,
+        cmr,
+        config.control_block(cmr)?,
+        None,
+        genesis,
+    ))
+}
+
+/// Compute the `sig_all_hash` of input `input_index` of `tx_hex`, which spends
+/// `utxos` through the leaf with CMR `cmr` in the tree `config` describes.
+/// Returns 32 bytes as hex.
+pub fn compute(
+    tx_hex: &str,
+    input_index: u32,
+    utxos: &[SighashUtxo],
+    cmr: &str,
+    config: &TaprootConfig,
+) -> Result<String, String> {
+    let tx: Transaction = deserialize(&hex::decode(tx_hex)?).map_err(|e| format!("Invalid transaction: {e}"))?;
+    let env = elements_env(tx, input_index, utxos, parse_cmr(cmr)?, config)?;
+    Ok(hex::encode(&env.c_tx_env().sighash_all().to_byte_array()))
+}
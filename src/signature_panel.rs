@@ -0,0 +1,104 @@
+//! Standalone BIP-340 verifier plus a check of the current witness.
+
+use leptos::prelude::*;
+
+use crate::i18n::{self, t, Msg};
+use crate::sigverify::{self, SignatureCheck};
+
+#[component]
+pub fn SignaturePanel(
+    #[prop(into)] code: Signal<String>,
+    #[prop(into)] witness: Signal<String>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let (pubkey, set_pubkey) = signal(String::new());
+    let (message, set_message) = signal(String::new());
+    let (signature, set_signature) = signal(String::new());
+    let (verdict, set_verdict) = signal::<Option<Result<bool, String>>>(None);
+    let (checks, set_checks) = signal::<Option<Result<Vec<SignatureCheck>, String>>>(None);
+
+    let verify_one = move |_| {
+        set_verdict.set(Some(sigverify::verify_bip340(
+            &pubkey.get_untracked(),
+            &message.get_untracked(),
+            &signature.get_untracked(),
+        )));
+    };
+
+    let verify_witness = move |_| {
+        set_checks.set(Some(sigverify::verify_witness(
+            &code.get_untracked(),
+            &witness.get_untracked(),
+            &message.get_untracked(),
+        )));
+    };
+
+    let verdict_view = move || {
+        verdict.get().map(|verdict| match verdict {
+            Ok(true) => view! { <p class="status-badge paid">{t(Msg::ValidSignature)}</p> }.into_any(),
+            Ok(false) => view! { <p class="status-badge expired">{t(Msg::SignatureInvalid)}</p> }.into_any(),
+            Err(e) => view! { <p class="tool-error">{e}</p> }.into_any(),
+        })
+    };
+
+    let checks_view = move || {
+        checks.get().map(|checks| match checks {
+            Err(e) => view! { <p class="tool-error">{e}</p> }.into_any(),
+            Ok(checks) if checks.is_empty() => {
+                view! { <p class="signature-empty">{t(Msg::NoSignatureValues)}</p> }.into_any()
+            }
+            Ok(checks) => {
+                let rows = checks
+                    .into_iter()
+                    .map(|check| {
+                        let (class, result) = match &check.valid_for {
+                            Some(key) => ("status-badge paid", i18n::format(i18n::text(lang.get(), Msg::ValidFor), &[key])),
+                            None => ("status-badge expired", i18n::text(lang.get(), Msg::MatchesNoKey).to_string()),
+                        };
+                        view! {
+                            <li class="signature-row">
+                                <code>{check.name}</code>
+                                <span class=class>{result}</span>
+                            </li>
+                        }
+                    })
+                    .collect_view();
+                view! { <ul class="signature-list">{rows}</ul> }.into_any()
+            }
+        })
+    };
+
+    view! {
+        <div class="signature-panel">
+            <label>{t(Msg::SignatureVerification)}</label>
+            <div class="signature-form">
+                <input
+                    placeholder=t(Msg::PubkeyPlaceholder)
+                    prop:value=move || pubkey.get()
+                    on:input=move |ev| set_pubkey.set(event_target_value(&ev))
+                />
+                <input
+                    placeholder=t(Msg::SighashMessagePlaceholder)
+                    prop:value=move || message.get()
+                    on:input=move |ev| set_message.set(event_target_value(&ev))
+                />
+                <input
+                    placeholder=t(Msg::SignaturePlaceholder)
+                    prop:value=move || signature.get()
+                    on:input=move |ev| set_signature.set(event_target_value(&ev))
+                />
+            </div>
+            <div class="button-group">
+                <button on:click=verify_one>{t(Msg::VerifySignature)}</button>
+                <button class="secondary" on:click=verify_witness>
+                    {t(Msg::VerifyWitnessSignatures)}
+                </button>
+            </div>
+            <p class="drop-zone-hint">
+                {t(Msg::WitnessSignaturesHint)}
+            </p>
+            {verdict_view}
+            {checks_view}
+        </div>
+    }
+}
@@ -0,0 +1,101 @@
+//! BIP-340 signature checks, run before attempting a full spend.
+//!
+//! A wrong signature only shows up as a failed `bip_0340_verify` jet during
+//! execution, with nothing saying which signature or which key. Checking each
+//! witness signature against the keys the program uses and the sighash the
+//! user computed pins that down up front.
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::elements::secp256k1_zkp::{schnorr, Message, Secp256k1, XOnlyPublicKey};
+
+use crate::hex;
+use crate::sanity::find_keys;
+
+fn strip_hex(hex: &str) -> &str {
+    let hex = hex.trim();
+    hex.strip_prefix("0x").unwrap_or(hex)
+}
+
+fn parse_message(sighash: &str) -> Result<Message, String> {
+    let digest: [u8; 32] = hex::decode(sighash)
+        .map_err(|e| format!("Invalid sighash: {e}"))?
+        .try_into()
+        .map_err(|_| "A sighash is 32 bytes (64 hex digits)".to_string())?;
+    Ok(Message::from_digest(digest))
+}
+
+/// Whether `signature` is a valid BIP-340 signature by `pubkey` over the
+/// 32-byte `message`. All three are hex, with or without a `0x` prefix.
+pub fn verify_bip340(pubkey: &str, message: &str, signature: &str) -> Result<bool, String> {
+    let pubkey = XOnlyPublicKey::from_str(strip_hex(pubkey)).map_err(|e| format!("Invalid public key: {e}"))?;
+    let signature =
+        schnorr::Signature::from_str(strip_hex(signature)).map_err(|e| format!("Invalid signature: {e}"))?;
+    let message = parse_message(message)?;
+    Ok(Secp256k1::verification_only()
+        .verify_schnorr(&signature, &message, &pubkey)
+        .is_ok())
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SignatureCheck {
+    /// Witness name the signature was read from.
+    pub name: String,
+    pub signature: String,
+    /// The program key the signature verifies under, if any.
+    pub valid_for: Option<String>,
+}
+
+/// Every 64-byte `0x` payload in the witness values typed as signatures.
+///
+/// Values like `Left(0x..)` that select a branch are searched too.
+pub fn witness_signatures(witness_json: &str) -> Vec<(String, String)> {
+    let witness = serde_json::from_str::<serde_json::Value>(witness_json).unwrap_or_default();
+    let Some(entries) = witness.as_object() else {
+        return Vec::new();
+    };
+    let mut signatures = Vec::new();
+    for (name, entry) in entries {
+        let ty = entry.get("type").and_then(|t| t.as_str()).unwrap_or_default();
+        let Some(value) = entry.get("value").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        if !ty.contains("Signature") {
+            continue;
+        }
+        let mut rest = value;
+        while let Some(at) = rest.find("0x") {
+            let tail = &rest[at + 2..];
+            let end = tail.find(|c: char| !c.is_ascii_hexdigit()).unwrap_or(tail.len());
+            if end == 128 {
+                signatures.push((name.clone(), tail[..end].to_ascii_lowercase()));
+            }
+            rest = &tail[end..];
+        }
+    }
+    signatures
+}
+
+/// Check every witness signature against every known key in the program.
+pub fn verify_witness(code: &str, witness_json: &str, sighash: &str) -> Result<Vec<SignatureCheck>, String> {
+    parse_message(sighash)?;
+    let mut keys: Vec<String> = find_keys(code, witness_json)
+        .into_iter()
+        .filter_map(|k| k.pubkey)
+        .collect();
+    keys.sort();
+    keys.dedup();
+
+    Ok(witness_signatures(witness_json)
+        .into_iter()
+        .map(|(name, signature)| SignatureCheck {
+            valid_for: keys
+                .iter()
+                .find(|key| verify_bip340(key, sighash, &signature) == Ok(true))
+                .cloned(),
+            name,
+            signature,
+        })
+        .collect())
+}
@@ -0,0 +1,241 @@
+//! Revision history of saved contracts, with diffs and restore. Contracts
+//! can be narrowed to those with a registry tag.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::i18n::{self, t, Msg};
+use crate::logging::{self, Category};
+use crate::registry::{self, RegistryEntry};
+use crate::registry_panel::{register_saved, tag_chips};
+use crate::snapshots::{self, Change, Contract};
+
+#[component]
+pub fn SnapshotPanel(
+    #[prop(into)] code: Signal<String>,
+    /// Name the current code is saved under.
+    #[prop(into)]
+    name: Signal<String>,
+    /// Bumped whenever a save records a revision; the list reloads.
+    #[prop(into)]
+    saves: Signal<u32>,
+    /// Called with the source of the revision to restore.
+    on_restore: Callback<String>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let contracts = RwSignal::new(Vec::<Contract>::new());
+    let (selected, set_selected) = signal::<Option<String>>(None);
+    // Revision numbers to diff, older first.
+    let (from, set_from) = signal::<Option<u32>>(None);
+    let (to, set_to) = signal::<Option<u32>>(None);
+    let (note, set_note) = signal::<Option<String>>(None);
+
+    // Registry entries, for the tags of each contract's revisions.
+    let registered = RwSignal::new(Vec::<RegistryEntry>::new());
+    let (tag, set_tag) = signal(String::new());
+    // Contracts with a revision tagged `tag`, while one is chosen.
+    let (tagged, set_tagged) = signal::<Option<Vec<String>>>(None);
+
+    Effect::new(move |_| {
+        let _ = saves.get();
+        contracts.set(snapshots::load());
+        spawn_local(async move {
+            match registry::list().await {
+                Ok(entries) => registered.set(entries),
+                Err(e) => logging::warn(Category::Ui, format!("Could not read the registry: {e}")),
+            }
+        });
+    });
+
+    Effect::new(move |_| {
+        let tag = tag.get();
+        if tag.is_empty() {
+            set_tagged.set(None);
+            return;
+        }
+        spawn_local(async move {
+            match registry::tagged(&tag).await {
+                Ok(entries) => set_tagged.set(Some(entries.into_iter().map(|e| e.name).collect())),
+                Err(e) => logging::warn(Category::Ui, format!("Could not read the registry: {e}")),
+            }
+        });
+    });
+
+    // The selected contract, or the one being edited.
+    let contract = Memo::new(move |_| {
+        let wanted = selected.get().unwrap_or_else(|| name.get());
+        contracts.with(|list| list.iter().find(|c| c.name == wanted).cloned())
+    });
+
+    let snapshot = move |_| {
+        let name = name.get_untracked();
+        let source = code.get_untracked();
+        let lang = lang.get_untracked();
+        set_note.set(Some(match snapshots::commit(&name, &source) {
+            Some(number) => {
+                let name = name.clone();
+                spawn_local(async move {
+                    if let Err(e) = register_saved(&name, Some(number), &source).await {
+                        logging::warn(Category::Ui, format!("Could not register {name}: {e}"));
+                    }
+                });
+                i18n::format(i18n::text(lang, Msg::SavedRevision), &[&number.to_string(), &name])
+            }
+            None => i18n::format(i18n::text(lang, Msg::UnchangedSinceRevision), &[&name]),
+        }));
+        set_selected.set(None);
+        contracts.set(snapshots::load());
+    };
+
+    let remove = move |_| {
+        if let Some(contract) = contract.get_untracked() {
+            snapshots::delete(&contract.name);
+            set_selected.set(None);
+            contracts.set(snapshots::load());
+        }
+    };
+
+    let source_of = move |number: Option<u32>| {
+        contract.with(|c| {
+            let c = c.as_ref()?;
+            c.revisions.iter().find(|r| Some(r.number) == number).map(|r| r.source.clone())
+        })
+    };
+
+    let diff = move || {
+        let (old, new) = (source_of(from.get())?, source_of(to.get())?);
+        let lines = snapshots::diff(&old, &new)
+            .into_iter()
+            .map(|line| {
+                let class = match line.change {
+                    Change::Same => "",
+                    Change::Added => "diff-added",
+                    Change::Removed => "diff-removed",
+                };
+                view! { <span class=class>{line.to_text()}"\n"</span> }
+            })
+            .collect_view();
+        Some(view! { <pre class="output-box listing">{lines}</pre> })
+    };
+
+    let revision_options = move |chosen: ReadSignal<Option<u32>>| {
+        contract
+            .get()
+            .map(|c| c.revisions)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|r| {
+                view! {
+                    <option value=r.number.to_string() selected=move || chosen.get() == Some(r.number)>
+                        {format!("r{} · {}", r.number, r.timestamp.to_locale_string())}
+                    </option>
+                }
+            })
+            .collect_view()
+    };
+
+    let rows = move || {
+        contract
+            .get()
+            .map(|c| c.revisions)
+            .unwrap_or_default()
+            .into_iter()
+            .rev()
+            .map(|revision| {
+                let source = revision.source.clone();
+                view! {
+                    <li class="signature-row">
+                        <span>{format!("r{}", revision.number)}</span>
+                        <span class="reminder-note">{revision.timestamp.to_locale_string()}</span>
+                        <button class="secondary" on:click=move |_| on_restore.run(source.clone())>
+                            {t(Msg::RestoreRevision)}
+                        </button>
+                    </li>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <details class="manifest-editor">
+            <summary>{t(Msg::Snapshots)}</summary>
+            <div class="button-group">
+                <button class="secondary" on:click=snapshot>
+                    {move || i18n::format(i18n::text(lang.get(), Msg::SnapshotOf), &[&name.get()])}
+                </button>
+                <select
+                    aria-label=t(Msg::Contract)
+                    on:change=move |ev| {
+                        set_selected.set(Some(event_target_value(&ev)));
+                        set_from.set(None);
+                        set_to.set(None);
+                    }
+                >
+                    {move || {
+                        let current = selected.get().unwrap_or_else(|| name.get());
+                        let tagged = tagged.get();
+                        contracts
+                            .get()
+                            .into_iter()
+                            .filter(|c| {
+                                c.name == current || tagged.as_ref().map_or(true, |names| names.contains(&c.name))
+                            })
+                            .map(|c| {
+                                let selected = c.name == current;
+                                view! { <option value=c.name.clone() selected=selected>{c.name}</option> }
+                            })
+                            .collect_view()
+                    }}
+                </select>
+                <select aria-label=t(Msg::FilterByTag) on:change=move |ev| set_tag.set(event_target_value(&ev))>
+                    <option value="">{t(Msg::AllTags)}</option>
+                    {move || {
+                        let current = tag.get();
+                        registered.with(|entries| {
+                            registry::all_tags(entries)
+                                .into_iter()
+                                .map(|t| {
+                                    let selected = t == current;
+                                    view! { <option value=t.clone() selected=selected>{format!("#{t}")}</option> }
+                                })
+                                .collect_view()
+                        })
+                    }}
+                </select>
+                <button class="danger" disabled=move || contract.with(Option::is_none) on:click=remove>
+                    {t(Msg::DeleteHistory)}
+                </button>
+            </div>
+            {move || {
+                let name = contract.with(|c| c.as_ref().map(|c| c.name.clone()))?;
+                let tags = registered.with(|entries| registry::contract_tags(entries, &name));
+                (!tags.is_empty())
+                    .then(|| view! { <p class="reminder-note">{tag_chips(&tags, Callback::new(move |t| set_tag.set(t)))}</p> })
+            }}
+            {move || note.get().map(|note| view! { <p class="reminder-note">{note}</p> })}
+            <Show
+                when=move || contract.with(Option::is_some)
+                fallback=|| view! { <p class="signature-empty">{t(Msg::NoRevisions)}</p> }
+            >
+                <ul class="signature-list">{rows}</ul>
+                <div class="button-group">
+                    <select
+                        aria-label=t(Msg::DiffFromRevision)
+                        on:change=move |ev| set_from.set(event_target_value(&ev).parse().ok())
+                    >
+                        <option value="">{t(Msg::DiffFrom)}</option>
+                        {move || revision_options(from)}
+                    </select>
+                    <select
+                        aria-label=t(Msg::DiffToRevision)
+                        on:change=move |ev| set_to.set(event_target_value(&ev).parse().ok())
+                    >
+                        <option value="">{t(Msg::DiffTo)}</option>
+                        {move || revision_options(to)}
+                    </select>
+                </div>
+                {diff}
+            </Show>
+        </details>
+    }
+}
@@ -0,0 +1,151 @@
+//! Immutable revisions of saved contracts, with line diffs between them.
+//!
+//! Every save of a contract appends a numbered revision unless the source
+//! is unchanged; revisions are never edited, and restoring one loads it
+//! into the editor rather than rewriting history. Contracts are told apart
+//! by name: the file they were saved to, or their first comment line.
+
+use serde::{Deserialize, Serialize};
+
+use crate::storage;
+use crate::timestamp::Stamp;
+
+const SNAPSHOTS_KEY: &str = "snapshots";
+
+/// Past this many line pairs the diff gives up on alignment and shows the
+/// whole of both revisions.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Revision {
+    /// 1 for the first revision of a contract, counting up.
+    pub number: u32,
+    pub timestamp: Stamp,
+    pub source: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Contract {
+    pub name: String,
+    /// Oldest first.
+    pub revisions: Vec<Revision>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Change {
+    Same,
+    Added,
+    Removed,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DiffLine {
+    pub change: Change,
+    pub text: String,
+}
+
+impl DiffLine {
+    /// The line in unified-diff form: ` `, `+` or `-` and the text.
+    pub fn to_text(&self) -> String {
+        let marker = match self.change {
+            Change::Same => ' ',
+            Change::Added => '+',
+            Change::Removed => '-',
+        };
+        format!("{marker} {}", self.text)
+    }
+}
+
+pub fn load() -> Vec<Contract> {
+    storage::load(SNAPSHOTS_KEY)
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+fn save(contracts: &[Contract]) {
+    if let Ok(json) = serde_json::to_string(contracts) {
+        storage::save(SNAPSHOTS_KEY, &json);
+    }
+}
+
+/// Record `source` as the next revision of `name`. Returns the new revision
+/// number, or `None` when the source matches the latest revision.
+pub fn commit(name: &str, source: &str) -> Option<u32> {
+    let mut contracts = load();
+    let index = match contracts.iter().position(|c| c.name == name) {
+        Some(index) => index,
+        None => {
+            contracts.push(Contract {
+                name: name.to_string(),
+                revisions: Vec::new(),
+            });
+            contracts.len() - 1
+        }
+    };
+    let revisions = &mut contracts[index].revisions;
+    if revisions.last().is_some_and(|r| r.source == source) {
+        return None;
+    }
+    let number = revisions.last().map_or(1, |r| r.number + 1);
+    revisions.push(Revision {
+        number,
+        timestamp: Stamp::now(),
+        source: source.to_string(),
+    });
+    save(&contracts);
+    Some(number)
+}
+
+/// Forget `name` and all its revisions.
+pub fn delete(name: &str) {
+    let mut contracts = load();
+    contracts.retain(|c| c.name != name);
+    save(&contracts);
+}
+
+/// Line diff turning `old` into `new`, from their longest common subsequence.
+pub fn diff(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let line = |change, text: &str| DiffLine {
+        change,
+        text: text.to_string(),
+    };
+    if old.len().saturating_mul(new.len()) > MAX_DIFF_CELLS {
+        return old
+            .iter()
+            .map(|text| line(Change::Removed, text))
+            .chain(new.iter().map(|text| line(Change::Added, text)))
+            .collect();
+    }
+
+    // common[i][j]: length of the longest common subsequence of old[i..] and new[j..].
+    let mut common = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut lines = Vec::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(line(Change::Same, old[i]));
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || common[i][j + 1] >= common[i + 1][j]) {
+            lines.push(line(Change::Added, new[j]));
+            j += 1;
+        } else {
+            lines.push(line(Change::Removed, old[i]));
+            i += 1;
+        }
+    }
+    lines
+}
@@ -0,0 +1,190 @@
+//! Where each node of a compiled program comes from in its SimplicityHL
+//! source.
+//!
+//! With debug symbols the compiler marks every call it can report on
+//! (`assert!`, `panic!`, jets, `unwrap`s and `dbg!`) with an `assertl` whose
+//! pruned branch is a fresh CMR, and records the text of the call under that
+//! CMR. [`build`] compiles the program that way, finds each call's text in
+//! the source, and hands the span down to every node the call is made of.
+//! The program without debug symbols (the one that is deployed, listed and
+//! run) shares every node that encloses no call with the debug build, so
+//! those nodes take their spans by CMR; the nodes that enclose calls take
+//! the spans of their children.
+//!
+//! A node shared between several calls, such as a jet called in more than
+//! one place, maps to every one of them.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::dag::{DagLike, MaxSharing};
+use simplicityhl::simplicity::jet::Elements;
+use simplicityhl::simplicity::node::{Commit, Inner};
+use simplicityhl::simplicity::{Cmr, CommitNode};
+use simplicityhl::CompiledProgram;
+
+use crate::options::{self, CompileOptions};
+use crate::sanity::strip_comment;
+
+/// A byte range of the source. Lines and columns are 1-based; columns
+/// count characters.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SourceRange {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+/// The source of one node of the program.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct NodeSource {
+    /// Index of the node in the combinator listing (`%index`).
+    pub index: usize,
+    pub cmr: String,
+    /// Empty for nodes no call accounts for, such as the glue around
+    /// `main`'s statements.
+    pub ranges: Vec<SourceRange>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SourceMap {
+    /// CMR of the program.
+    pub cmr: String,
+    /// Every node, in listing order, children first.
+    pub nodes: Vec<NodeSource>,
+}
+
+impl SourceMap {
+    /// The ranges of the node with `cmr`, if the program has one.
+    pub fn ranges_of(&self, cmr: &str) -> &[SourceRange] {
+        self.nodes
+            .iter()
+            .find(|node| node.cmr == cmr)
+            .map_or(&[], |node| &node.ranges)
+    }
+}
+
+/// Every place `text` appears in the code of `code`, outside comments and
+/// ignoring whitespace, as the compiler collapses it in recorded calls.
+fn locate(code: &str, text: &str) -> Vec<SourceRange> {
+    // Non-whitespace characters of the code with their byte offsets, lines
+    // and columns.
+    let mut chars = Vec::new();
+    let mut offset = 0;
+    for (line_index, line) in code.split('\n').enumerate() {
+        for (column, (at, c)) in strip_comment(line).char_indices().enumerate() {
+            if !c.is_whitespace() {
+                chars.push((offset + at, c, line_index + 1, column + 1));
+            }
+        }
+        offset += line.len() + 1;
+    }
+    let needle: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    if needle.is_empty() || needle.len() > chars.len() {
+        return Vec::new();
+    }
+    (0..=chars.len() - needle.len())
+        .filter(|&i| chars[i..i + needle.len()].iter().map(|&(_, c, ..)| c).eq(needle.iter().copied()))
+        .map(|i| {
+            let (start, _, line, column) = chars[i];
+            let (last, c, end_line, last_column) = chars[i + needle.len() - 1];
+            SourceRange {
+                start,
+                end: last + c.len_utf8(),
+                line,
+                column,
+                end_line,
+                end_column: last_column + 1,
+            }
+        })
+        .collect()
+}
+
+/// Add `more` to `ranges`, leaving out any it already has.
+fn merge(ranges: &mut Vec<SourceRange>, more: &[SourceRange]) {
+    for range in more {
+        if !ranges.contains(range) {
+            ranges.push(*range);
+        }
+    }
+}
+
+/// Spans of the nodes of `compiled`, a build with debug symbols, by CMR.
+fn debug_spans(code: &str, compiled: &CompiledProgram) -> HashMap<Cmr, Vec<SourceRange>> {
+    let program = compiled.commit();
+    let symbols = compiled.debug_symbols();
+    let items: Vec<_> = program.post_order_iter::<MaxSharing<Commit<Elements>>>().collect();
+
+    // The marker of a call and the `comp` that runs the call before it own
+    // the call's span.
+    let marker = |node: &CommitNode<Elements>| match node.inner() {
+        Inner::AssertL(_, cmr) => symbols.get(cmr).map(|call| locate(code, call.text())),
+        _ => None,
+    };
+    let mut own: Vec<Option<Vec<SourceRange>>> = items.iter().map(|item| marker(&item.node)).collect();
+    for (index, item) in items.iter().enumerate() {
+        if let (Inner::Comp(..), Some(right)) = (item.node.inner(), item.right_index) {
+            if own[index].is_none() {
+                own[index] = own[right].clone();
+            }
+        }
+    }
+
+    // Parents come after their children, so walking backwards reaches every
+    // parent of a node before the node itself.
+    let mut inherited: Vec<Vec<SourceRange>> = vec![Vec::new(); items.len()];
+    let mut spans = HashMap::new();
+    for (index, item) in items.iter().enumerate().rev() {
+        let ranges = own[index].clone().unwrap_or_else(|| std::mem::take(&mut inherited[index]));
+        for child in [item.left_index, item.right_index].into_iter().flatten() {
+            merge(&mut inherited[child], &ranges);
+        }
+        spans.insert(item.node.cmr(), ranges);
+    }
+    spans
+}
+
+/// The source map of `code`, with parameter values read from the source.
+pub fn build(code: &str) -> Result<SourceMap, String> {
+    build_with_args(code, code)
+}
+
+/// The source map of `code`, with parameter values read from `args_source`.
+pub(crate) fn build_with_args(code: &str, args_source: &str) -> Result<SourceMap, String> {
+    // Debug spans are located in the text they were compiled from.
+    let code = &options::expand_keys(code)?;
+    let debug = CompileOptions {
+        debug_symbols: true,
+        ..CompileOptions::default()
+    }
+    .compile_source(code, args_source)?;
+    let compiled = CompileOptions::default().compile_source(code, args_source)?;
+
+    let spans = debug_spans(code, &debug);
+    let program = compiled.commit();
+    let mut nodes: Vec<NodeSource> = Vec::new();
+    for item in program.post_order_iter::<MaxSharing<Commit<Elements>>>() {
+        let ranges = match spans.get(&item.node.cmr()) {
+            Some(ranges) => ranges.clone(),
+            None => {
+                let mut ranges = Vec::new();
+                for child in [item.left_index, item.right_index].into_iter().flatten() {
+                    merge(&mut ranges, &nodes[child].ranges);
+                }
+                ranges
+            }
+        };
+        nodes.push(NodeSource {
+            index: item.index,
+            cmr: item.node.cmr().to_string(),
+            ranges,
+        });
+    }
+    Ok(SourceMap {
+        cmr: program.cmr().to_string(),
+        nodes,
+    })
+}
@@ -0,0 +1,337 @@
+//! Transactions spending a contract's taproot output.
+//!
+//! The spend goes through the script path of the Simplicity leaf, in the
+//! tree a [`TaprootConfig`] describes. Its input witness stack is, in order: the encoded witness values, the
+//! encoded program, the leaf script (the CMR) and the control block. No annex
+//! is attached; Simplicity does not need one.
+//!
+//! Outputs may carry any asset the input brings: the spent output's own, or
+//! the asset and reissuance tokens of an issuance the input makes. Elements
+//! requires inputs and outputs to balance exactly per asset, so the fee is an
+//! explicit output in the policy asset and whatever is left of an asset goes
+//! to the output marked `change`.
+//!
+//! Outputs to confidential addresses are blinded by default: their asset and
+//! amount become commitments with surjection and range proofs, readable only
+//! with the address's blinding key. The last blinded output takes the
+//! blinding factor that balances the commitments. Proofs grow the
+//! transaction, so the fee is settled by building until it covers the size.
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::elements::confidential::{
+    Asset, AssetBlindingFactor, Nonce, Value, ValueBlindingFactor,
+};
+use simplicityhl::simplicity::elements::encode::serialize_hex;
+use simplicityhl::simplicity::elements::secp256k1_zkp::Secp256k1;
+use simplicityhl::simplicity::elements::{
+    Address, AssetId, LockTime, OutPoint, Script, Sequence, Transaction, TxIn, TxInWitness, TxOut, TxOutSecrets,
+    TxOutWitness, Txid,
+};
+use simplicityhl::simplicity::Cmr;
+use simplicityhl::WitnessValues;
+
+use crate::address::{leaf_script, Network};
+use crate::assets::{self, IssuedIds, NewIssuance};
+use crate::options::CompileOptions;
+use crate::taproot::TaprootConfig;
+
+/// The output being spent.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SpendUtxo {
+    pub txid: String,
+    pub vout: u32,
+    /// Explicit value in satoshis.
+    pub value: u64,
+    /// Asset id in hex; defaults to the policy asset of the outputs' network.
+    /// Confidential outputs have to be unblinded first.
+    #[serde(default)]
+    pub asset: Option<String>,
+    /// New asset issued by the spending input.
+    #[serde(default)]
+    pub issuance: Option<NewIssuance>,
+    /// Input sequence; defaults to `0xfffffffe`, which enables lock times.
+    #[serde(default)]
+    pub sequence: Option<u32>,
+    /// Transaction lock time; defaults to 0.
+    #[serde(default)]
+    pub lock_time: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SpendOutput {
+    pub address: String,
+    /// Ignored for the change output, which gets the remainder.
+    #[serde(default)]
+    pub amount_sat: u64,
+    /// Asset id in hex; defaults to the asset of the spent output.
+    #[serde(default)]
+    pub asset: Option<String>,
+    #[serde(default)]
+    pub change: bool,
+    /// Blind the asset and amount; defaults to whether the address is
+    /// confidential. `false` pays a confidential address in the clear.
+    #[serde(default)]
+    pub confidential: Option<bool>,
+}
+
+/// Blinding factors of a blinded output, in hex. The receiver's wallet
+/// recovers them with the blinding key; they are returned for the sender's
+/// records and for unblinding without it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BlindedOutput {
+    pub index: usize,
+    pub asset_blinder: String,
+    pub value_blinder: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SpendTx {
+    pub tx_hex: String,
+    pub txid: String,
+    pub fee_sat: u64,
+    pub vsize: usize,
+    pub change_sat: Option<u64>,
+    /// Ids of the asset and token the input issued, if it issued any.
+    #[serde(default)]
+    pub issued: Option<IssuedIds>,
+    /// Outputs that were blinded, in output order.
+    #[serde(default)]
+    pub blinded: Vec<BlindedOutput>,
+}
+
+/// Compile `code`, satisfy it with `witness_json` and encode it for the
+/// witness stack. Returns the CMR, program bytes and witness bytes.
+pub fn satisfy(code: &str, witness_json: &str) -> Result<(Cmr, Vec<u8>, Vec<u8>), String> {
+    let compiled = CompileOptions::default().compile_source(code, code)?;
+    let witness = serde_json::from_str::<WitnessValues>(witness_json)
+        .map_err(|e| format!("Invalid JSON witness data: {}", e))?;
+    let cmr = compiled.commit().cmr();
+    let satisfied = compiled.satisfy(witness).map_err(|e| format!("Witness error: {}", e))?;
+    let (program, witness) = satisfied.redeem().to_vec_with_witness();
+    Ok((cmr, program, witness))
+}
+
+fn explicit_output(script_pubkey: Script, amount_sat: u64, asset: AssetId) -> TxOut {
+    TxOut {
+        asset: Asset::Explicit(asset),
+        value: Value::Explicit(amount_sat),
+        nonce: Nonce::Null,
+        script_pubkey,
+        witness: TxOutWitness::default(),
+    }
+}
+
+/// Build the complete spend of `utxo`, locked in the tree `config`
+/// describes, by the program `code`, paying `outputs` at `fee_rate` sat/vB.
+pub fn build(
+    code: &str,
+    witness_json: &str,
+    utxo: &SpendUtxo,
+    outputs: &[SpendOutput],
+    fee_rate: f64,
+    config: &TaprootConfig,
+) -> Result<SpendTx, String> {
+    if outputs.is_empty() {
+        return Err("At least one output is required".to_string());
+    }
+    if outputs.iter().filter(|o| o.change).count() > 1 {
+        return Err("Only one output can be marked as change".to_string());
+    }
+    if utxo.asset.as_deref().is_some_and(|asset| asset.trim().len() == 66) {
+        return Err("The UTXO's asset is confidential; give its unblinded asset id and value".to_string());
+    }
+    if !(fee_rate.is_finite() && fee_rate >= 0.0) {
+        return Err("Fee rate must be a non-negative number of sat/vB".to_string());
+    }
+
+    let addresses = outputs
+        .iter()
+        .map(|o| Address::from_str(&o.address).map_err(|e| format!("Invalid output address `{}`: {e}", o.address)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let blind = outputs
+        .iter()
+        .zip(&addresses)
+        .map(|(o, a)| match o.confidential {
+            Some(true) if a.blinding_pubkey.is_none() => {
+                Err(format!("Output address `{}` is not confidential, so it cannot be blinded to", o.address))
+            }
+            confidential => Ok(confidential.unwrap_or(a.blinding_pubkey.is_some())),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let policy_asset = Network::of_address(&addresses[0]).and_then(Network::policy_asset);
+    let asset = match &utxo.asset {
+        Some(asset) => assets::asset_id(asset)?,
+        None => assets::asset_id(policy_asset.ok_or("The UTXO asset is required on this network")?)?,
+    };
+    // Elements fees are paid in the policy asset; without one, in the UTXO's.
+    let fee_asset = policy_asset.map(assets::asset_id).transpose()?.unwrap_or(asset);
+    let output_assets = outputs
+        .iter()
+        .map(|o| o.asset.as_deref().map_or(Ok(asset), assets::asset_id))
+        .collect::<Result<Vec<_>, _>>()?;
+    let txid = Txid::from_str(&utxo.txid).map_err(|e| format!("Invalid txid: {e}"))?;
+    let prevout = OutPoint::new(txid, utxo.vout);
+
+    // What the input brings, per asset, in the order Elements lists the
+    // input's assets for surjection proofs.
+    let mut available = vec![(asset, utxo.value)];
+    let (asset_issuance, issued) = match &utxo.issuance {
+        Some(issuance) => {
+            let (issued_asset, token) = issuance.ids(prevout)?;
+            for (id, amount) in [(issued_asset, issuance.asset_amount), (token, issuance.token_amount)] {
+                if amount > 0 {
+                    available.push((id, amount));
+                }
+            }
+            let ids = IssuedIds {
+                asset: issued_asset.to_string(),
+                token: token.to_string(),
+            };
+            (issuance.to_issuance()?, Some(ids))
+        }
+        None => (Default::default(), None),
+    };
+
+    let (cmr, program, witness) = satisfy(code, witness_json)?;
+    let control_block = config.control_block(cmr)?;
+    let input = TxIn {
+        previous_output: prevout,
+        is_pegin: false,
+        script_sig: Script::new(),
+        sequence: Sequence(utxo.sequence.unwrap_or(0xffff_fffe)),
+        asset_issuance,
+        witness: TxInWitness {
+            script_witness: vec![witness, program, leaf_script(cmr).to_bytes(), control_block.serialize()],
+            ..Default::default()
+        },
+    };
+
+    let change_index = outputs.iter().position(|o| o.change);
+    // The amount of every output once `fee_sat` is paid, and the change.
+    let balance = |fee_sat: u64| -> Result<(Vec<u64>, Option<u64>), String> {
+        let mut amounts: Vec<u64> = outputs.iter().map(|o| o.amount_sat).collect();
+        let mut spent: Vec<(AssetId, u64)> = vec![(fee_asset, fee_sat)];
+        for (index, (o, &asset)) in outputs.iter().zip(&output_assets).enumerate() {
+            if Some(index) != change_index {
+                spent.push((asset, o.amount_sat));
+            }
+        }
+        let total = |list: &[(AssetId, u64)], asset: AssetId| -> Result<u64, String> {
+            list.iter()
+                .filter(|(a, _)| *a == asset)
+                .try_fold(0u64, |sum, (_, n)| sum.checked_add(*n))
+                .ok_or_else(|| "Output amounts overflow".to_string())
+        };
+        let mut assets_seen: Vec<AssetId> = Vec::new();
+        for &(asset, _) in available.iter().chain(&spent) {
+            if !assets_seen.contains(&asset) {
+                assets_seen.push(asset);
+            }
+        }
+
+        let mut change_sat = None;
+        for asset in assets_seen {
+            let (have, want) = (total(&available, asset)?, total(&spent, asset)?);
+            let fee = if asset == fee_asset { " and fee" } else { "" };
+            if want > have {
+                return Err(format!("Outputs{fee} need {want} sat of asset {asset} but the input brings {have} sat"));
+            }
+            match change_index.filter(|&index| output_assets[index] == asset) {
+                Some(index) => {
+                    amounts[index] = have - want;
+                    change_sat = Some(have - want);
+                }
+                None if have != want => {
+                    return Err(format!(
+                        "The input brings {have} sat of asset {asset} but outputs{fee} take {want} sat; mark an output of that asset as change"
+                    ))
+                }
+                None => {}
+            }
+        }
+        Ok((amounts, change_sat))
+    };
+
+    let secp = Secp256k1::new();
+    // The input is explicit, so its blinding factors are zero.
+    let spent_secrets: Vec<TxOutSecrets> = available
+        .iter()
+        .map(|&(asset, value)| TxOutSecrets::new(asset, AssetBlindingFactor::zero(), value, ValueBlindingFactor::zero()))
+        .collect();
+    let last_blinded = blind.iter().rposition(|&b| b);
+    let assemble = |amounts: &[u64], fee_sat: u64| -> Result<(Transaction, Vec<BlindedOutput>), String> {
+        let mut output: Vec<TxOut> = Vec::with_capacity(outputs.len() + 1);
+        let mut secrets: Vec<TxOutSecrets> = Vec::new();
+        let mut blinded = Vec::new();
+        for (index, address) in addresses.iter().enumerate() {
+            let (amount, asset) = (amounts[index], output_assets[index]);
+            if !blind[index] {
+                output.push(explicit_output(address.script_pubkey(), amount, asset));
+                continue;
+            }
+            let (txout, abf, vbf, _) = if Some(index) == last_blinded {
+                let blinder = address.blinding_pubkey.ok_or("Blinded outputs need a confidential address")?;
+                let others: Vec<&TxOutSecrets> = secrets.iter().collect();
+                TxOut::new_last_confidential(
+                    &mut rand::thread_rng(),
+                    &secp,
+                    amount,
+                    asset,
+                    address.script_pubkey(),
+                    blinder,
+                    &spent_secrets,
+                    &others,
+                )
+            } else {
+                TxOut::new_not_last_confidential(
+                    &mut rand::thread_rng(),
+                    &secp,
+                    amount,
+                    address.clone(),
+                    asset,
+                    &spent_secrets,
+                )
+            }
+            .map_err(|e| format!("Could not blind output {index}: {e}"))?;
+            secrets.push(TxOutSecrets::new(asset, abf, amount, vbf));
+            blinded.push(BlindedOutput {
+                index,
+                asset_blinder: abf.to_string(),
+                value_blinder: vbf.to_string(),
+            });
+            output.push(txout);
+        }
+        output.push(TxOut::new_fee(fee_sat, fee_asset));
+        let tx = Transaction {
+            version: 2,
+            lock_time: LockTime::from_consensus(utxo.lock_time.unwrap_or(0)),
+            input: vec![input.clone()],
+            output,
+        };
+        Ok((tx, blinded))
+    };
+
+    // Explicit amounts have a fixed size, so the second round settles an
+    // unblinded fee; proof sizes vary a little with the amounts they hide.
+    let mut fee_sat = 0;
+    loop {
+        let (amounts, change_sat) = balance(fee_sat)?;
+        let (tx, blinded) = assemble(&amounts, fee_sat)?;
+        let vsize = tx.weight().div_ceil(4);
+        let needed = (vsize as f64 * fee_rate).ceil() as u64;
+        if needed <= fee_sat {
+            return Ok(SpendTx {
+                tx_hex: serialize_hex(&tx),
+                txid: tx.txid().to_string(),
+                fee_sat,
+                vsize,
+                change_sat,
+                issued,
+                blinded,
+            });
+        }
+        fee_sat = needed;
+    }
+}
@@ -0,0 +1,497 @@
+//! The distinct ways a program can be satisfied, in words.
+//!
+//! Every combination of `match` arms is a spend path. The conditions of a
+//! path are read off the jets it runs: signature checks, hash preimages and
+//! timelocks. Arithmetic and introspection jets are not described, so a path
+//! may demand more than its summary says; a path with no conditions at all is
+//! spendable by anyone who can pick it. Doc comments on the functions a path
+//! calls and the witnesses it reads are attached to it as notes.
+//!
+//! Helper functions count where `main` calls them: each call is replaced by
+//! the helper's body, with its parameters renamed to the caller's arguments,
+//! so a helper called from one arm adds to that arm's paths only and a
+//! helper nobody calls adds to none.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::annotations::{self, defined_function};
+use crate::coverage::{layout, ArmRef, Layout};
+use crate::locktime;
+use crate::sanity::{qualified_names, strip_comment};
+use crate::triage::resolve_number;
+
+/// Paths listed before the summary gives up; arms multiply quickly.
+const MAX_PATHS: usize = 64;
+/// Deepest chain of helper calls inlined.
+const MAX_INLINE_DEPTH: usize = 8;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SpendPath {
+    /// The arm taken at each match on the path, e.g. "`witness::PATH` is `Left(sig)`".
+    pub choices: Vec<String>,
+    pub conditions: Vec<String>,
+    /// Doc comments on what the path runs, e.g. "`fn refund`: returns the funds".
+    #[serde(default)]
+    pub notes: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpendConditions {
+    pub paths: Vec<SpendPath>,
+    /// More than [`MAX_PATHS`] paths exist; only the first are listed.
+    pub truncated: bool,
+}
+
+impl SpendConditions {
+    /// One line per path: "path 1: signature by `param::ALICE`".
+    pub fn to_text(&self) -> String {
+        let mut text = self
+            .paths
+            .iter()
+            .enumerate()
+            .map(|(index, path)| {
+                let conditions = if path.conditions.is_empty() {
+                    "no conditions".to_string()
+                } else {
+                    path.conditions.join(" + ")
+                };
+                let mut line = format!("path {}: {conditions}", index + 1);
+                for note in &path.notes {
+                    line.push_str(&format!("\n    {note}"));
+                }
+                line
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        if self.truncated {
+            text.push_str(&format!("\n(only the first {MAX_PATHS} paths are listed)"));
+        }
+        text
+    }
+}
+
+/// The text between the parentheses of the call to `jet::name` on `line`.
+pub(crate) fn call_argument<'a>(line: &'a str, name: &str) -> Option<&'a str> {
+    let start = line.find(&format!("jet::{name}("))? + "jet::(".len() + name.len();
+    let mut depth = 0usize;
+    for (offset, c) in line[start..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return Some(&line[start..start + offset]),
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    Some(&line[start..])
+}
+
+/// The arguments of a call, split at top-level commas.
+pub(crate) fn split_arguments(arguments: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0usize, 0);
+    for (at, c) in arguments.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(arguments[start..at].trim());
+                start = at + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(arguments[start..].trim());
+    parts
+}
+
+/// A top-level function: its parameter names and the lines of its body,
+/// each as (0-based source line, text).
+struct Function {
+    name: String,
+    params: Vec<String>,
+    /// Source lines from the signature to the closing brace, exclusive.
+    lines: (usize, usize),
+    body: Vec<(usize, String)>,
+}
+
+/// The names of the parameters declared in `signature`.
+fn parameters(signature: &str) -> Vec<String> {
+    let Some(start) = signature.find('(') else {
+        return Vec::new();
+    };
+    let mut depth = 0usize;
+    let mut end = signature.len();
+    for (offset, c) in signature[start + 1..].char_indices() {
+        match c {
+            '(' | '[' | '<' => depth += 1,
+            ')' if depth == 0 => {
+                end = start + 1 + offset;
+                break;
+            }
+            ')' | ']' | '>' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    split_arguments(&signature[start + 1..end])
+        .into_iter()
+        .filter_map(|param| param.split(':').next())
+        .map(|name| name.trim().trim_start_matches("mut ").trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// The top-level functions of `lines`.
+fn functions(lines: &[&str]) -> Vec<Function> {
+    let mut functions = Vec::new();
+    let mut open: Option<(Function, String, bool)> = None;
+    let mut depth = 0usize;
+    for (index, line) in lines.iter().enumerate() {
+        let line = strip_comment(line);
+        if depth == 0 && open.is_none() {
+            if let Some(name) = defined_function(line) {
+                let function = Function {
+                    name: name.to_string(),
+                    params: Vec::new(),
+                    lines: (index, index + 1),
+                    body: Vec::new(),
+                };
+                open = Some((function, String::new(), false));
+            }
+        }
+        for c in line.chars() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        let Some((function, signature, opened)) = open.as_mut() else {
+            continue;
+        };
+        if !*opened {
+            // The signature may span lines; the body starts at its brace.
+            signature.push_str(line.split('{').next().unwrap_or_default());
+            if !line.contains('{') {
+                continue;
+            }
+            *opened = true;
+            function.params = parameters(signature);
+            let inner = &line[line.find('{').map_or(0, |at| at + 1)..];
+            let inner = if depth == 0 { inner.rsplit_once('}').map_or(inner, |(inner, _)| inner) } else { inner };
+            if !inner.trim().is_empty() {
+                function.body.push((index, inner.to_string()));
+            }
+        } else if depth > 0 {
+            function.body.push((index, lines[index].to_string()));
+        } else if let Some((inner, _)) = line.rsplit_once('}').filter(|(inner, _)| !inner.trim().is_empty()) {
+            function.body.push((index, inner.to_string()));
+        }
+        if depth == 0 {
+            if let Some((mut function, _, _)) = open.take() {
+                function.lines.1 = index + 1;
+                functions.push(function);
+            }
+        }
+    }
+    functions
+}
+
+/// The text of each call to the function `name` on `line`, between its
+/// parentheses.
+fn call_arguments<'a>(line: &'a str, name: &str) -> Vec<(usize, &'a str)> {
+    line.match_indices(&format!("{name}("))
+        .filter(|(at, _)| {
+            !line[..*at].chars().next_back().is_some_and(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+        })
+        .map(|(at, call)| {
+            let start = at + call.len();
+            let mut depth = 0usize;
+            let mut end = line.len();
+            for (offset, c) in line[start..].char_indices() {
+                match c {
+                    '(' => depth += 1,
+                    ')' if depth == 0 => {
+                        end = start + offset;
+                        break;
+                    }
+                    ')' => depth -= 1,
+                    _ => {}
+                }
+            }
+            (at, &line[start..end])
+        })
+        .collect()
+}
+
+/// `text` with each identifier in `names` replaced, leaving paths such as
+/// `witness::NAME` and the digits of literals alone.
+fn rename(text: &str, names: &HashMap<&str, &str>) -> String {
+    let mut renamed = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        let end = rest[start..]
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .map_or(rest.len(), |len| start + len);
+        renamed.push_str(&rest[..start]);
+        let word = &rest[start..end];
+        let attached = renamed.ends_with(|c: char| c == ':' || c == '.' || c.is_ascii_digit());
+        match names.get(word) {
+            Some(with) if !attached => renamed.push_str(with),
+            _ => renamed.push_str(word),
+        }
+        rest = &rest[end..];
+    }
+    renamed.push_str(rest);
+    renamed
+}
+
+/// `code` as `main` runs it.
+pub(crate) struct Inlined {
+    /// The lines of `main`, each followed by the bodies of the helpers it
+    /// calls in a block of their own.
+    pub(crate) code: String,
+    /// The 0-based source line each line of `code` came from; the braces
+    /// around an inlined body come from the line calling it.
+    pub(crate) origins: Vec<usize>,
+}
+
+impl Inlined {
+    /// The 1-based source line of 0-based line `index` of the inlined code.
+    pub(crate) fn source_line(&self, index: usize) -> usize {
+        self.origins.get(index).copied().unwrap_or(index) + 1
+    }
+}
+
+/// Push `line`, then the body of each helper it calls, inlined in turn.
+fn emit(
+    line: String,
+    origin: usize,
+    functions: &[Function],
+    stack: &mut Vec<String>,
+    out: &mut Vec<(usize, String)>,
+) {
+    let code = strip_comment(&line).to_string();
+    out.push((origin, line));
+    if stack.len() > MAX_INLINE_DEPTH {
+        return;
+    }
+    let defines = defined_function(&code);
+    let mut calls: Vec<(usize, &Function, &str)> = functions
+        .iter()
+        .filter(|f| defines != Some(f.name.as_str()) && !stack.contains(&f.name))
+        .flat_map(|f| call_arguments(&code, &f.name).into_iter().map(move |(at, args)| (at, f, args)))
+        .collect();
+    calls.sort_by_key(|&(at, _, _)| at);
+    for (_, function, arguments) in calls {
+        let names: HashMap<&str, &str> = function
+            .params
+            .iter()
+            .map(String::as_str)
+            .zip(split_arguments(arguments))
+            .collect();
+        out.push((origin, "{".to_string()));
+        stack.push(function.name.clone());
+        for (body_origin, body_line) in &function.body {
+            emit(rename(body_line, &names), *body_origin, functions, stack, out);
+        }
+        stack.pop();
+        out.push((origin, "}".to_string()));
+    }
+}
+
+/// `main` with every call to a helper followed by the helper's body, its
+/// parameters renamed to the arguments. Helpers `main` never reaches are
+/// left out. Code without a `main` is returned as it is.
+pub(crate) fn inline_calls(code: &str) -> Inlined {
+    let lines: Vec<&str> = code.lines().collect();
+    let functions = functions(&lines);
+    let Some(main) = functions.iter().find(|f| f.name == "main") else {
+        return Inlined {
+            code: code.to_string(),
+            origins: (0..lines.len()).collect(),
+        };
+    };
+    let mut out = Vec::new();
+    let mut stack = vec![main.name.clone()];
+    for (index, line) in lines.iter().enumerate().take(main.lines.1).skip(main.lines.0) {
+        emit(line.to_string(), index, &functions, &mut stack, &mut out);
+    }
+    let (origins, lines): (Vec<usize>, Vec<String>) = out.into_iter().unzip();
+    Inlined {
+        code: lines.join("\n"),
+        origins,
+    }
+}
+
+/// What the call to `jet::name` on line `index` requires of a spend.
+fn describe(lines: &[&str], index: usize, name: &str) -> Option<String> {
+    let line = strip_comment(lines[index]);
+    let argument = call_argument(line, name)?.trim();
+    let number = resolve_number(lines, index, argument);
+    let condition = match name {
+        "bip_0340_verify" => {
+            let key = argument.trim_start_matches('(').split(',').next().unwrap_or_default().trim();
+            format!("signature by `{key}`")
+        }
+        "check_lock_height" => match number {
+            Some(height) => format!("after block {height}"),
+            None => format!("after block height `{argument}`"),
+        },
+        "check_lock_time" => match number.and_then(|n| u32::try_from(n).ok()) {
+            Some(time) => format!("after {}", locktime::describe_lock_time(time)),
+            None => format!("after lock time `{argument}`"),
+        },
+        "check_lock_distance" => match number {
+            Some(blocks) => format!("{blocks} blocks after funding"),
+            None => format!("`{argument}` blocks after funding"),
+        },
+        "check_lock_duration" => match number.and_then(|n| u16::try_from(n).ok()) {
+            Some(units) => format!("{} after funding", locktime::describe_duration(units)),
+            None => format!("`{argument}` × 512 s after funding"),
+        },
+        "sha_256_ctx_8_finalize" => "preimage of a SHA-256 hash".to_string(),
+        _ => return None,
+    };
+    Some(condition)
+}
+
+/// Whether `line` calls the function `name`.
+fn calls(line: &str, name: &str) -> bool {
+    line.match_indices(&format!("{name}(")).any(|(at, _)| {
+        !line[..at].chars().next_back().is_some_and(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+    })
+}
+
+/// Every combination of arms under `scope`, each as the arms it takes.
+pub(crate) fn arm_paths(layout: &Layout, scope: Option<ArmRef>, truncated: &mut bool) -> Vec<Vec<ArmRef>> {
+    let mut paths = vec![Vec::new()];
+    for (index, m) in layout.matches.iter().enumerate().filter(|(_, m)| m.parent == scope) {
+        let mut next = Vec::new();
+        for arm in 0..m.arms.len() {
+            let tails = arm_paths(layout, Some((index, arm)), truncated);
+            for path in &paths {
+                for tail in &tails {
+                    if next.len() == MAX_PATHS {
+                        *truncated = true;
+                        break;
+                    }
+                    let mut path = path.clone();
+                    path.push((index, arm));
+                    path.extend_from_slice(tail);
+                    next.push(path);
+                }
+            }
+        }
+        if !next.is_empty() {
+            paths = next;
+        }
+    }
+    paths
+}
+
+/// The spend paths of `code` and what each requires.
+pub fn analyze(code: &str) -> SpendConditions {
+    let inlined = inline_calls(code);
+    let layout = layout(&inlined.code);
+    let lines: Vec<&str> = inlined.code.lines().collect();
+    let docs = annotations::extract(code);
+    let mut truncated = false;
+    let paths = arm_paths(&layout, None, &mut truncated)
+        .into_iter()
+        .map(|arms| {
+            let choices = arms
+                .iter()
+                .map(|&(index, arm)| {
+                    let m = &layout.matches[index];
+                    format!("`{}` is `{}`", m.scrutinee, m.arms[arm].pattern)
+                })
+                .collect();
+            let mut conditions: Vec<String> = Vec::new();
+            for jet in &layout.jets {
+                if jet.parent.is_some_and(|parent| !arms.contains(&parent)) {
+                    continue;
+                }
+                if let Some(condition) = describe(&lines, jet.line - 1, &jet.name) {
+                    if !conditions.contains(&condition) {
+                        conditions.push(condition);
+                    }
+                }
+            }
+            let mut notes: Vec<String> = Vec::new();
+            for (index, line) in lines.iter().enumerate() {
+                if layout.line_parents.get(index).copied().flatten().is_some_and(|parent| !arms.contains(&parent)) {
+                    continue;
+                }
+                let line = strip_comment(line);
+                let defines = defined_function(line);
+                let functions = docs
+                    .functions
+                    .iter()
+                    .filter(|(name, _)| defines != Some(name.as_str()) && calls(line, name))
+                    .map(|(name, doc)| format!("`fn {name}`: {doc}"));
+                let witnesses = qualified_names(line, "witness::")
+                    .into_iter()
+                    .filter_map(|name| docs.witnesses.get(name).map(|doc| format!("`witness::{name}`: {doc}")));
+                for note in functions.chain(witnesses) {
+                    if !notes.contains(&note) {
+                        notes.push(note);
+                    }
+                }
+            }
+            SpendPath { choices, conditions, notes }
+        })
+        .collect();
+    SpendConditions { paths, truncated }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wizards::htlc;
+
+    const LASTWILL: &str = include_str!("../contract/lastwill.simf");
+
+    fn conditions(code: &str) -> Vec<Vec<String>> {
+        analyze(code).paths.into_iter().map(|path| path.conditions).collect()
+    }
+
+    #[test]
+    fn lastwill_timelock_is_on_the_inherit_path_only() {
+        assert_eq!(
+            conditions(LASTWILL),
+            [
+                vec!["25920 blocks after funding".to_string(), "signature by `alice_pk`".to_string()],
+                vec!["signature by `bob_pk`".to_string()],
+                vec!["signature by `charlie_pk`".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn htlc_refund_needs_no_preimage() {
+        assert_eq!(
+            conditions(&htlc::example()),
+            [
+                vec!["preimage of a SHA-256 hash".to_string(), "signature by `recipient`".to_string()],
+                vec!["after block 1000".to_string(), "signature by `sender`".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn uncalled_helpers_add_nothing() {
+        let code = "fn unused() {\n    jet::check_lock_height(100);\n}\n\nfn main() {\n    let sig: Signature = witness::SIG;\n    jet::bip_0340_verify((param::KEY, jet::sig_all_hash()), sig);\n}";
+        assert_eq!(conditions(code), [vec!["signature by `param::KEY`".to_string()]]);
+    }
+
+    #[test]
+    fn inlined_lines_keep_their_source_line() {
+        let inlined = inline_calls(LASTWILL);
+        let index = inlined.code.lines().position(|line| line.contains("check_lock_distance")).unwrap();
+        let line = LASTWILL.lines().position(|line| line.contains("check_lock_distance")).unwrap();
+        assert_eq!(inlined.source_line(index), line + 1);
+        assert!(!inlined.code.contains("fn recursive_covenant"));
+    }
+}
@@ -0,0 +1,82 @@
+//! "Spending conditions" summary of a compiled program.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::api_types::WitnessSizeResult;
+use crate::compiler;
+use crate::i18n::{self, t, Msg};
+use crate::spend_paths;
+
+#[component]
+pub fn SpendPathsPanel(#[prop(into)] code: Signal<String>) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let analysis = Memo::new(move |_| spend_paths::analyze(&code.get()));
+    let (sizes, set_sizes) = signal::<Option<WitnessSizeResult>>(None);
+    let (busy, set_busy) = signal(false);
+
+    let estimate = move |_| {
+        let code = code.get_untracked();
+        set_busy.set(true);
+        spawn_local(async move {
+            let json = compiler::estimate_witness_size(&code).await;
+            set_sizes.set(Some(serde_json::from_str::<WitnessSizeResult>(&json).unwrap_or_else(|e| {
+                WitnessSizeResult {
+                    estimate: None,
+                    error: Some(e.to_string()),
+                }
+            })));
+            set_busy.set(false);
+        });
+    };
+
+    let size_report = move || {
+        let result = sizes.get()?;
+        if let Some(e) = result.error {
+            return Some(view! { <p class="tool-error">{e}</p> }.into_any());
+        }
+        Some(view! { <pre class="output-box listing">{result.estimate?.to_text()}</pre> }.into_any())
+    };
+
+    let paths = move || {
+        let lang = lang.get();
+        analysis
+            .get()
+            .paths
+            .into_iter()
+            .enumerate()
+            .map(|(index, path)| {
+                let conditions = if path.conditions.is_empty() {
+                    i18n::text(lang, Msg::NoConditions).to_string()
+                } else {
+                    path.conditions.join(" + ")
+                };
+                let class = if path.conditions.is_empty() { "status-badge expired" } else { "status-badge paid" };
+                view! {
+                    <li class="signature-row">
+                        <span class=class>{i18n::format(i18n::text(lang, Msg::PathNumber), &[&(index + 1).to_string()])}</span>
+                        <span>{conditions}</span>
+                        <span class="reminder-note">{path.choices.join(", ")}</span>
+                        {(!path.notes.is_empty()).then(|| {
+                            let notes = path.notes.into_iter().map(|note| view! { <li>{note}</li> }).collect_view();
+                            view! { <ul class="spend-path-notes">{notes}</ul> }
+                        })}
+                    </li>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <ul class="signature-list">{paths}</ul>
+        <Show when=move || analysis.get().truncated>
+            <p class="reminder-note">{t(Msg::MorePaths)}</p>
+        </Show>
+        <div class="button-group">
+            <button class="secondary" disabled=move || busy.get() on:click=estimate>
+                {move || i18n::text(lang.get(), if busy.get() { Msg::Estimating } else { Msg::EstimateWitnessSize })}
+            </button>
+        </div>
+        {size_report}
+    }
+}
@@ -0,0 +1,137 @@
+//! Two-column layout with a draggable divider.
+//!
+//! The divider uses pointer events, so mouse, pen and touch all work, and it
+//! is focusable with arrow-key resizing for keyboard users. The chosen ratio
+//! is persisted in localStorage under the given key.
+
+use leptos::html::Div;
+use leptos::prelude::*;
+
+use crate::i18n::{t, Msg};
+use crate::storage;
+
+/// Smallest share (in percent) either pane may shrink to.
+const MIN_PERCENT: f64 = 15.0;
+const MAX_PERCENT: f64 = 100.0 - MIN_PERCENT;
+const DEFAULT_PERCENT: f64 = 50.0;
+/// Step applied per arrow-key press on the focused divider.
+const KEY_STEP_PERCENT: f64 = 2.0;
+
+#[slot]
+pub struct SplitStart {
+    children: Children,
+}
+
+#[slot]
+pub struct SplitEnd {
+    children: Children,
+}
+
+fn clamp_percent(value: f64) -> f64 {
+    if value.is_finite() {
+        value.clamp(MIN_PERCENT, MAX_PERCENT)
+    } else {
+        DEFAULT_PERCENT
+    }
+}
+
+#[component]
+pub fn SplitPane(
+    /// localStorage key under which the ratio is remembered.
+    #[prop(into)]
+    storage_key: String,
+    split_start: SplitStart,
+    split_end: SplitEnd,
+) -> impl IntoView {
+    let initial = storage::load(&storage_key)
+        .and_then(|v| v.parse::<f64>().ok())
+        .map(clamp_percent)
+        .unwrap_or(DEFAULT_PERCENT);
+    let (percent, set_percent) = signal(initial);
+    let (dragging, set_dragging) = signal(false);
+    let container_ref = NodeRef::<Div>::new();
+
+    let persist = {
+        let storage_key = storage_key.clone();
+        move || storage::save(&storage_key, &format!("{:.1}", percent.get_untracked()))
+    };
+
+    let update_from_pointer = move |client_x: i32| {
+        if let Some(container) = container_ref.get_untracked() {
+            let rect = container.get_bounding_client_rect();
+            if rect.width() > 0.0 {
+                let offset = f64::from(client_x) - rect.left();
+                set_percent.set(clamp_percent(offset / rect.width() * 100.0));
+            }
+        }
+    };
+
+    let on_pointer_down = move |ev: web_sys::PointerEvent| {
+        ev.prevent_default();
+        if let Some(target) = ev
+            .target()
+            .and_then(|t| wasm_bindgen::JsCast::dyn_into::<web_sys::Element>(t).ok())
+        {
+            let _ = target.set_pointer_capture(ev.pointer_id());
+        }
+        set_dragging.set(true);
+    };
+
+    let on_pointer_move = move |ev: web_sys::PointerEvent| {
+        if dragging.get_untracked() {
+            update_from_pointer(ev.client_x());
+        }
+    };
+
+    let on_pointer_up = {
+        let persist = persist.clone();
+        move |_: web_sys::PointerEvent| {
+            if dragging.get_untracked() {
+                set_dragging.set(false);
+                persist();
+            }
+        }
+    };
+
+    let on_key_down = move |ev: web_sys::KeyboardEvent| {
+        let delta = match ev.key().as_str() {
+            "ArrowLeft" => -KEY_STEP_PERCENT,
+            "ArrowRight" => KEY_STEP_PERCENT,
+            "Home" => MIN_PERCENT - percent.get_untracked(),
+            "End" => MAX_PERCENT - percent.get_untracked(),
+            _ => return,
+        };
+        ev.prevent_default();
+        set_percent.set(clamp_percent(percent.get_untracked() + delta));
+        persist();
+    };
+
+    view! {
+        <div
+            class=move || if dragging.get() { "split-pane dragging" } else { "split-pane" }
+            node_ref=container_ref
+            style=move || {
+                let start = percent.get();
+                format!("grid-template-columns: minmax(0, {start}fr) 10px minmax(0, {}fr);", 100.0 - start)
+            }
+        >
+            <div class="split-pane-start">{(split_start.children)()}</div>
+            <div
+                class="split-pane-divider"
+                role="separator"
+                tabindex="0"
+                aria-orientation="vertical"
+                aria-label=t(Msg::ResizePanes)
+                aria-valuemin=MIN_PERCENT
+                aria-valuemax=MAX_PERCENT
+                aria-valuenow=move || format!("{:.0}", percent.get())
+                on:pointerdown=on_pointer_down
+                on:pointermove=on_pointer_move
+                on:pointerup=on_pointer_up.clone()
+                on:pointercancel=on_pointer_up
+                on:keydown=on_key_down
+            />
+            <div class="split-pane-end">{(split_end.children)()}</div>
+        </div>
+    }
+}
@@ -0,0 +1,33 @@
+//! Thin wrapper around `window.localStorage`.
+//!
+//! Storage may be unavailable (private browsing, sandboxed iframes), so every
+//! accessor degrades to a no-op instead of failing.
+
+/// Prefix applied to every key so embedders sharing an origin don't collide.
+const KEY_PREFIX: &str = "simplicity-wasm:";
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+/// Read the value stored under `key`, if any.
+pub fn load(key: &str) -> Option<String> {
+    local_storage()?
+        .get_item(&format!("{KEY_PREFIX}{key}"))
+        .ok()
+        .flatten()
+}
+
+/// Store `value` under `key`, silently ignoring quota or availability errors.
+pub fn save(key: &str, value: &str) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.set_item(&format!("{KEY_PREFIX}{key}"), value);
+    }
+}
+
+/// Remove the value stored under `key`.
+pub fn remove(key: &str) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.remove_item(&format!("{KEY_PREFIX}{key}"));
+    }
+}
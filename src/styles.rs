@@ -0,0 +1,1510 @@
+//! The playground stylesheet.
+//!
+//! Styles are grouped by component and assembled by [`stylesheet`]. Every
+//! colour, font and radius is read from a CSS custom property declared in
+//! [`THEME`], so embedders can restyle the app by overriding those properties
+//! on `:root` (or, when embedded, on the `<simplicity-playground>` host
+//! element) without touching the view code:
+//!
+//! ```css
+//! :root {
+//!     --sw-color-primary: #6f42c1;
+//!     --sw-font-mono: 'JetBrains Mono', monospace;
+//! }
+//! ```
+//!
+//! | Property | Used for |
+//! |---|---|
+//! | `--sw-font-sans`, `--sw-font-mono` | UI text and code |
+//! | `--sw-editor-font-size`, `--sw-editor-line-height`, `--sw-tab-size` | The code editor, set from Settings |
+//! | `--sw-radius`, `--sw-radius-lg` | Controls and cards |
+//! | `--sw-shadow`, `--sw-shadow-raised` | Cards and popovers |
+//! | `--sw-color-bg`, `--sw-color-surface*` | Page, cards and inputs |
+//! | `--sw-color-text*` | Body, secondary and hint text |
+//! | `--sw-color-border*` | Input, output-box and drop-zone borders |
+//! | `--sw-color-primary*`, `--sw-color-focus-*` | Primary buttons, focus and drag highlights |
+//! | `--sw-color-secondary*`, `--sw-color-danger*`, `--sw-color-disabled` | Button variants |
+//! | `--sw-color-{error,success,warning,neutral}-*` | Status boxes, badges and gutter markers |
+
+/// Default values for every custom property.
+///
+/// Declared under `:where()` so they have zero specificity and any embedder
+/// rule setting the same property wins regardless of load order.
+pub const THEME: &str = r#"
+:where(:root, :host) {
+    --sw-font-sans: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif;
+    --sw-font-mono: 'Monaco', 'Courier New', monospace;
+    --sw-radius: 4px;
+    --sw-radius-lg: 8px;
+    --sw-shadow: 0 1px 3px rgba(0,0,0,0.1);
+    --sw-shadow-raised: 0 2px 6px rgba(0,0,0,0.15);
+    --sw-editor-font-size: 13px;
+    --sw-editor-line-height: 18px;
+    --sw-tab-size: 4;
+
+    --sw-color-bg: #f5f5f5;
+    --sw-color-surface: white;
+    --sw-color-surface-alt: #fafafa;
+    --sw-color-surface-sunken: #f0f0f0;
+    --sw-color-text: #333;
+    --sw-color-text-muted: #666;
+    --sw-color-text-subtle: #999;
+    --sw-color-border: #ddd;
+    --sw-color-border-light: #e0e0e0;
+    --sw-color-border-strong: #ccc;
+
+    --sw-color-primary: #007bff;
+    --sw-color-primary-hover: #0056b3;
+    --sw-color-primary-active: #004085;
+    --sw-color-primary-soft: #e7f3ff;
+    --sw-color-primary-tint: #f0f8ff;
+    --sw-color-focus-ring: rgba(0, 123, 255, 0.1);
+    --sw-color-focus-glow: rgba(0, 123, 255, 0.3);
+    --sw-color-secondary: #6c757d;
+    --sw-color-secondary-hover: #5a6268;
+    --sw-color-danger: #dc3545;
+    --sw-color-danger-hover: #c82333;
+    --sw-color-disabled: #adb5bd;
+
+    --sw-color-error-bg: #f8d7da;
+    --sw-color-error-text: #721c24;
+    --sw-color-error-border: #f5c6cb;
+    --sw-color-success-bg: #d4edda;
+    --sw-color-success-text: #155724;
+    --sw-color-success-border: #c3e6cb;
+    --sw-color-warning: #d39e00;
+    --sw-color-warning-bg: #fff3cd;
+    --sw-color-warning-text: #856404;
+    --sw-color-neutral-bg: #e2e3e5;
+    --sw-color-neutral-text: #383d41;
+}
+"#;
+
+/// The dark palette, applied when Settings pick the dark theme, or the
+/// system theme on a system set to dark.
+const DARK_PALETTE: &str = r#"
+    color-scheme: dark;
+    color: var(--sw-color-text);
+    --sw-shadow: 0 1px 3px rgba(0,0,0,0.5);
+    --sw-shadow-raised: 0 2px 6px rgba(0,0,0,0.6);
+
+    --sw-color-bg: #1e1f22;
+    --sw-color-surface: #2b2d31;
+    --sw-color-surface-alt: #25272b;
+    --sw-color-surface-sunken: #202225;
+    --sw-color-text: #e3e5e8;
+    --sw-color-text-muted: #b5bac1;
+    --sw-color-text-subtle: #80848e;
+    --sw-color-border: #3f4147;
+    --sw-color-border-light: #35373c;
+    --sw-color-border-strong: #4e5058;
+
+    --sw-color-primary: #4c9aff;
+    --sw-color-primary-hover: #79b4ff;
+    --sw-color-primary-active: #a3cbff;
+    --sw-color-primary-soft: #1d3350;
+    --sw-color-primary-tint: #1a2a40;
+    --sw-color-focus-ring: rgba(76, 154, 255, 0.2);
+    --sw-color-focus-glow: rgba(76, 154, 255, 0.4);
+
+    --sw-color-error-bg: #4a1f24;
+    --sw-color-error-text: #f5b5bc;
+    --sw-color-error-border: #6b2a31;
+    --sw-color-success-bg: #1d3b27;
+    --sw-color-success-text: #8fd6a4;
+    --sw-color-success-border: #2a5636;
+    --sw-color-warning-bg: #3d3318;
+    --sw-color-warning-text: #f0d27a;
+    --sw-color-neutral-bg: #3a3c42;
+    --sw-color-neutral-text: #d4d6da;
+"#;
+
+/// [`DARK_PALETTE`] under the selectors that turn it on. The page
+/// background outside the app follows it too.
+fn dark() -> String {
+    format!(
+        r#"
+.container[data-theme="dark"] {{{DARK_PALETTE}}}
+
+body:has(.container[data-theme="dark"]) {{
+    background: #1e1f22;
+}}
+
+@media (prefers-color-scheme: dark) {{
+    .container[data-theme="system"] {{{DARK_PALETTE}}}
+
+    body:has(.container[data-theme="system"]) {{
+        background: #1e1f22;
+    }}
+}}
+"#
+    )
+}
+
+/// Reset, page chrome and card sections.
+pub const BASE: &str = r#"
+* {
+    margin: 0;
+    padding: 0;
+    box-sizing: border-box;
+}
+
+body {
+    font-family: var(--sw-font-sans);
+    background: var(--sw-color-bg);
+}
+
+.container {
+    max-width: 1400px;
+    margin: 0 auto;
+    padding: 30px 20px;
+}
+
+.header {
+    margin-bottom: 30px;
+}
+
+.header h1 {
+    font-size: 32px;
+    margin-bottom: 10px;
+    color: var(--sw-color-text);
+}
+
+.header p {
+    color: var(--sw-color-text-muted);
+    font-size: 16px;
+}
+
+.header .language-select {
+    margin-top: 10px;
+}
+
+.section {
+    background: var(--sw-color-surface);
+    padding: 25px;
+    border-radius: var(--sw-radius-lg);
+    box-shadow: var(--sw-shadow);
+}
+
+.section label {
+    font-weight: 600;
+    color: var(--sw-color-text);
+    font-size: 14px;
+    display: block;
+    margin-bottom: 12px;
+}
+
+.button-group {
+    display: flex;
+    gap: 10px;
+    margin-top: 15px;
+    flex-wrap: wrap;
+}
+
+.section-header {
+    display: flex;
+    justify-content: space-between;
+    align-items: center;
+    gap: 10px;
+    margin-bottom: 6px;
+}
+
+.tool-error {
+    color: var(--sw-color-error-text);
+    font-size: 13px;
+    margin-bottom: 10px;
+}
+
+.sr-only {
+    position: absolute;
+    width: 1px;
+    height: 1px;
+    padding: 0;
+    margin: -1px;
+    overflow: hidden;
+    clip: rect(0, 0, 0, 0);
+    white-space: nowrap;
+    border: 0;
+}
+
+.skip-link:not(:focus) {
+    position: absolute;
+    width: 1px;
+    height: 1px;
+    overflow: hidden;
+    clip: rect(0, 0, 0, 0);
+}
+
+.skip-link:focus {
+    display: inline-block;
+    margin-bottom: 10px;
+}
+
+.footer {
+    margin-top: 40px;
+    padding-top: 20px;
+    border-top: 1px solid var(--sw-color-border);
+    color: var(--sw-color-text-muted);
+    font-size: 14px;
+}
+
+.footer p {
+    margin-bottom: 8px;
+}
+
+.footer-crash-log {
+    display: flex;
+    flex-wrap: wrap;
+    align-items: center;
+    gap: 10px;
+    font-size: 13px;
+}
+"#;
+
+/// Buttons and their variants.
+pub const CONTROLS: &str = r#"
+button {
+    padding: 10px 16px;
+    background: var(--sw-color-primary);
+    color: var(--sw-color-surface);
+    border: none;
+    border-radius: var(--sw-radius);
+    cursor: pointer;
+    font-size: 14px;
+    font-weight: 600;
+    transition: background 0.2s;
+    white-space: nowrap;
+}
+
+button:hover {
+    background: var(--sw-color-primary-hover);
+}
+
+button:active {
+    background: var(--sw-color-primary-active);
+}
+
+button.secondary {
+    background: var(--sw-color-secondary);
+}
+
+button.secondary:hover {
+    background: var(--sw-color-secondary-hover);
+}
+
+button:disabled,
+button:disabled:hover {
+    background: var(--sw-color-disabled);
+    cursor: not-allowed;
+}
+
+button.danger {
+    background: var(--sw-color-danger);
+}
+
+button.danger:hover {
+    background: var(--sw-color-danger-hover);
+}
+"#;
+
+/// [`SplitPane`](crate::split_pane::SplitPane) layout and divider.
+pub const SPLIT_PANE: &str = r#"
+.split-pane {
+    display: grid;
+    margin-bottom: 40px;
+}
+
+.split-pane.dragging {
+    user-select: none;
+    cursor: col-resize;
+}
+
+.split-pane-start,
+.split-pane-end {
+    min-width: 0;
+}
+
+.split-pane-divider {
+    cursor: col-resize;
+    touch-action: none;
+    margin: 0 2px;
+    border-radius: 3px;
+    background: transparent;
+    transition: background 0.2s;
+}
+
+.split-pane-divider:hover,
+.split-pane-divider:focus,
+.split-pane.dragging .split-pane-divider {
+    outline: none;
+    background: var(--sw-color-primary);
+}
+"#;
+
+/// Drop zones, textareas and the line-number gutter.
+pub const EDITOR: &str = r#"
+.drop-zone {
+    border: 2px dashed var(--sw-color-border-strong);
+    border-radius: var(--sw-radius-lg);
+    padding: 40px 20px;
+    text-align: center;
+    background: var(--sw-color-surface-alt);
+    cursor: pointer;
+    transition: all 0.2s ease;
+    margin-bottom: 15px;
+    min-height: 100px;
+    display: flex;
+    align-items: center;
+    justify-content: center;
+    flex-direction: column;
+}
+
+.drop-zone:hover {
+    border-color: var(--sw-color-primary);
+    background: var(--sw-color-primary-tint);
+}
+
+.file-picker,
+.bottom-sheet-handle {
+    display: none;
+}
+
+.drop-zone:focus-visible,
+.output-box:focus-visible {
+    outline: 2px solid var(--sw-color-primary);
+    outline-offset: 2px;
+}
+
+.drop-zone.drag-over {
+    border-color: var(--sw-color-primary);
+    background: var(--sw-color-primary-soft);
+    box-shadow: 0 0 8px var(--sw-color-focus-glow);
+}
+
+.drop-zone-icon {
+    font-size: 32px;
+    margin-bottom: 10px;
+}
+
+.drop-zone-text {
+    color: var(--sw-color-text-muted);
+    font-size: 14px;
+    font-weight: 500;
+}
+
+.drop-zone-hint {
+    color: var(--sw-color-text-subtle);
+    font-size: 12px;
+    margin-top: 8px;
+}
+
+textarea {
+    width: 100%;
+    height: 200px;
+    padding: 12px;
+    border: 1px solid var(--sw-color-border);
+    border-radius: var(--sw-radius);
+    font-family: var(--sw-font-mono);
+    font-size: 13px;
+    resize: none;
+    background: var(--sw-color-surface-alt);
+    margin-bottom: 15px;
+}
+
+.editor {
+    position: relative;
+    display: flex;
+    margin-bottom: 15px;
+}
+
+.editor textarea.code-input {
+    height: 300px;
+    margin-bottom: 0;
+    border-top-left-radius: 0;
+    border-bottom-left-radius: 0;
+    font-size: var(--sw-editor-font-size);
+    line-height: var(--sw-editor-line-height);
+    tab-size: var(--sw-tab-size);
+    white-space: pre;
+    overflow: auto;
+}
+
+.gutter {
+    flex: 0 0 auto;
+    min-width: 48px;
+    height: 300px;
+    overflow: hidden;
+    padding: 13px 0;
+    background: var(--sw-color-surface-sunken);
+    border: 1px solid var(--sw-color-border);
+    border-right: none;
+    border-radius: var(--sw-radius) 0 0 var(--sw-radius);
+    font-family: var(--sw-font-mono);
+    font-size: 12px;
+    color: var(--sw-color-text-subtle);
+    user-select: none;
+}
+
+.gutter-line {
+    display: flex;
+    align-items: center;
+    justify-content: flex-end;
+    gap: 4px;
+    height: var(--sw-editor-line-height);
+    padding: 0 8px 0 4px;
+}
+
+.gutter-marker-slot {
+    width: 14px;
+    display: inline-flex;
+    justify-content: center;
+}
+
+button.gutter-marker {
+    padding: 0;
+    width: 14px;
+    height: 14px;
+    line-height: 14px;
+    font-size: 10px;
+    background: transparent;
+}
+
+button.gutter-marker.error {
+    color: var(--sw-color-danger);
+}
+
+button.gutter-marker.warning {
+    color: var(--sw-color-warning);
+}
+
+.gutter-detail {
+    position: absolute;
+    left: 52px;
+    right: 8px;
+    bottom: 8px;
+    z-index: 2;
+    list-style: none;
+    padding: 8px 12px;
+    background: var(--sw-color-surface);
+    border: 1px solid var(--sw-color-border);
+    border-radius: var(--sw-radius);
+    box-shadow: var(--sw-shadow-raised);
+    font-size: 12px;
+}
+
+.gutter-detail-item.error {
+    color: var(--sw-color-error-text);
+}
+
+.gutter-detail-item.warning {
+    color: var(--sw-color-warning-text);
+}
+
+.gutter-detail-item .quick-fix {
+    margin-left: 8px;
+    padding: 2px 8px;
+    font-size: 12px;
+}
+
+.learn-more {
+    margin-top: 4px;
+    color: var(--sw-color-text);
+    font-size: 13px;
+}
+
+.learn-more summary {
+    cursor: pointer;
+    font-weight: 600;
+}
+
+.learn-more p {
+    margin: 4px 0 0;
+}
+
+.editor-text {
+    position: relative;
+    flex: 1 1 auto;
+    min-width: 0;
+}
+
+.editor-overlay {
+    position: absolute;
+    inset: 1px;
+    padding: 12px;
+    overflow: hidden;
+    pointer-events: none;
+}
+
+.editor-overlay pre {
+    margin: 0;
+    font-family: var(--sw-font-mono);
+    font-size: var(--sw-editor-font-size);
+    line-height: var(--sw-editor-line-height);
+    tab-size: var(--sw-tab-size);
+    white-space: pre;
+    color: transparent;
+}
+
+.editor-overlay mark {
+    color: transparent;
+    background: transparent;
+    border-radius: 2px;
+}
+
+.editor-overlay mark.bracket-match {
+    background: var(--sw-color-focus-glow);
+    outline: 1px solid var(--sw-color-primary);
+}
+
+.editor-overlay mark.search-match {
+    background: var(--sw-color-warning-bg);
+}
+
+.editor-overlay mark.source-match {
+    background: var(--sw-color-focus-glow);
+    border-bottom: 2px solid var(--sw-color-primary);
+}
+
+.editor-overlay mark.search-current {
+    background: var(--sw-color-warning);
+    opacity: 0.5;
+}
+
+.search-bar {
+    display: grid;
+    gap: 6px;
+    margin-bottom: 8px;
+    padding: 8px;
+    background: var(--sw-color-surface-sunken);
+    border: 1px solid var(--sw-color-border);
+    border-radius: var(--sw-radius);
+    font-size: 13px;
+}
+
+.search-row {
+    display: flex;
+    align-items: center;
+    gap: 6px;
+}
+
+.search-row input[type="text"] {
+    flex: 1 1 auto;
+    min-width: 0;
+    padding: 6px 8px;
+    border: 1px solid var(--sw-color-border);
+    border-radius: var(--sw-radius);
+    font-family: var(--sw-font-mono);
+    font-size: 13px;
+}
+
+.search-row button {
+    padding: 6px 10px;
+}
+
+.search-count {
+    min-width: 80px;
+    color: var(--sw-color-text-muted);
+    white-space: nowrap;
+}
+
+.outline {
+    list-style: none;
+    max-height: 240px;
+    overflow-y: auto;
+}
+
+button.outline-item {
+    display: flex;
+    align-items: baseline;
+    gap: 8px;
+    width: 100%;
+    padding: 3px 6px;
+    background: transparent;
+    color: var(--sw-color-text);
+    font-family: var(--sw-font-mono);
+    font-size: 12px;
+    text-align: left;
+}
+
+button.outline-item:hover {
+    background: var(--sw-color-primary-tint);
+}
+
+.outline-icon {
+    width: 14px;
+    color: var(--sw-color-primary);
+}
+
+.outline-detail {
+    color: var(--sw-color-text-subtle);
+    overflow: hidden;
+    text-overflow: ellipsis;
+    white-space: nowrap;
+}
+
+.editor-measure {
+    position: absolute;
+    visibility: hidden;
+    font-family: var(--sw-font-mono);
+    font-size: 13px;
+    white-space: pre;
+}
+
+.param-preview {
+    display: flex;
+    flex-wrap: wrap;
+    align-items: center;
+    gap: 8px;
+    margin: -8px 0 15px;
+    padding: 6px 8px;
+    background: var(--sw-color-primary-tint);
+    border: 1px solid var(--sw-color-border);
+    border-radius: var(--sw-radius);
+    font-size: 13px;
+}
+
+.param-preview input {
+    flex: 1 1 160px;
+    padding: 4px 8px;
+    border: 1px solid var(--sw-color-border);
+    border-radius: var(--sw-radius);
+    font-family: var(--sw-font-mono);
+    font-size: 13px;
+}
+
+.param-preview button {
+    padding: 4px 10px;
+}
+
+.editor-option {
+    display: flex;
+    align-items: center;
+    gap: 6px;
+    margin: 4px 0;
+}
+
+textarea:focus {
+    outline: none;
+    border-color: var(--sw-color-primary);
+    background: var(--sw-color-surface);
+    box-shadow: 0 0 0 3px var(--sw-color-focus-ring);
+}
+"#;
+
+/// Error, success and output boxes in the results column.
+pub const RESULTS: &str = r#"
+.error {
+    padding: 15px;
+    background: var(--sw-color-error-bg);
+    color: var(--sw-color-error-text);
+    border: 1px solid var(--sw-color-error-border);
+    border-radius: var(--sw-radius);
+    margin-bottom: 15px;
+}
+
+.error strong {
+    display: block;
+    margin-bottom: 8px;
+}
+
+.error pre {
+    margin: 0;
+    white-space: pre-wrap;
+    word-break: break-word;
+    font-size: 13px;
+    font-family: var(--sw-font-mono);
+}
+
+.success {
+    padding: 15px;
+    background: var(--sw-color-success-bg);
+    color: var(--sw-color-success-text);
+    border: 1px solid var(--sw-color-success-border);
+    border-radius: var(--sw-radius);
+    margin-bottom: 15px;
+}
+
+.success strong {
+    display: block;
+    margin-bottom: 10px;
+}
+
+.output-group {
+    margin-bottom: 20px;
+}
+
+.output-label {
+    font-weight: 600;
+    color: var(--sw-color-success-text);
+    font-size: 12px;
+    text-transform: uppercase;
+    margin-bottom: 6px;
+    display: block;
+}
+
+.output-box {
+    background: var(--sw-color-bg);
+    padding: 12px;
+    border-radius: 3px;
+    word-break: break-all;
+    font-family: var(--sw-font-mono);
+    font-size: 12px;
+    color: var(--sw-color-text);
+    overflow-x: auto;
+    max-height: 150px;
+    overflow-y: auto;
+    border: 1px solid var(--sw-color-border-light);
+}
+
+.output-box.witness {
+    max-height: 200px;
+    white-space: pre-wrap;
+    word-wrap: break-word;
+}
+
+.output-box.listing {
+    max-height: 320px;
+    margin: 0;
+    white-space: pre;
+    word-break: normal;
+}
+
+.output-box.listing .listing-line.mapped {
+    cursor: pointer;
+}
+
+.output-box.listing .listing-line.mapped:hover {
+    background: var(--sw-color-focus-glow);
+}
+
+.output-box.listing .diff-added {
+    background: var(--sw-color-success-bg);
+    color: var(--sw-color-success-text);
+}
+
+.output-box.listing .diff-removed {
+    background: var(--sw-color-error-bg);
+    color: var(--sw-color-error-text);
+}
+
+.empty-state {
+    padding: 40px 30px;
+    background: var(--sw-color-surface-sunken);
+    border-radius: var(--sw-radius);
+    text-align: center;
+    color: var(--sw-color-text-muted);
+    min-height: 200px;
+    display: flex;
+    align-items: center;
+    justify-content: center;
+}
+"#;
+
+/// Address sanity findings and the key manifest editor.
+pub const SANITY: &str = r#"
+.sanity-findings {
+    padding: 12px;
+    margin-bottom: 10px;
+    background: var(--sw-color-warning-bg);
+    color: var(--sw-color-warning-text);
+    border: 1px solid var(--sw-color-warning);
+    border-radius: var(--sw-radius);
+    font-size: 13px;
+}
+
+.sanity-findings ul {
+    margin: 8px 0 12px 18px;
+}
+
+.sanity-finding.error {
+    color: var(--sw-color-error-text);
+}
+
+.manifest-editor {
+    margin-bottom: 10px;
+    font-size: 13px;
+    color: var(--sw-color-text);
+}
+
+.manifest-editor summary {
+    cursor: pointer;
+    margin-bottom: 8px;
+}
+
+.manifest-editor textarea.manifest-input {
+    height: 90px;
+    margin-bottom: 0;
+}
+
+.manifest-status {
+    margin-top: 8px;
+    color: var(--sw-color-success-text);
+}
+
+.manifest-status.error {
+    color: var(--sw-color-error-text);
+}
+"#;
+
+/// Ranked failure causes inside the error box.
+pub const TRIAGE: &str = r#"
+.triage {
+    margin-top: 10px;
+    font-size: 13px;
+}
+
+.triage ol {
+    margin: 6px 0 0 20px;
+}
+
+.triage-cause {
+    margin-bottom: 6px;
+}
+
+.triage-cause.likely strong {
+    color: var(--sw-color-error-text);
+}
+
+.triage-score {
+    margin-left: 8px;
+    font-size: 11px;
+    color: var(--sw-color-text-muted);
+}
+
+.triage-detail {
+    color: var(--sw-color-text);
+}
+"#;
+
+/// Funding request form and invoice list.
+pub const INVOICE: &str = r#"
+.tool-section {
+    margin-bottom: 30px;
+}
+
+.invoice-form {
+    display: grid;
+    grid-template-columns: repeat(auto-fit, minmax(160px, 1fr));
+    gap: 10px;
+    margin-bottom: 15px;
+}
+
+.invoice-form input {
+    padding: 8px 10px;
+    border: 1px solid var(--sw-color-border);
+    border-radius: var(--sw-radius);
+    font-size: 13px;
+}
+
+.invoice-detail {
+    margin-bottom: 20px;
+}
+
+.invoice-qr svg {
+    display: block;
+    margin: 0 auto 12px;
+}
+
+.invoice-list {
+    list-style: none;
+}
+
+.invoice-row {
+    display: flex;
+    align-items: center;
+    gap: 10px;
+    padding: 8px 0;
+    border-bottom: 1px solid var(--sw-color-border-light);
+    font-size: 13px;
+}
+
+.invoice-label {
+    flex: 1;
+    overflow: hidden;
+    text-overflow: ellipsis;
+}
+
+.invoice-amount {
+    font-family: var(--sw-font-mono);
+}
+
+.reminder-note {
+    display: block;
+    font-size: 12px;
+    color: var(--sw-color-text-muted);
+}
+
+.status-badge {
+    padding: 2px 8px;
+    border-radius: 10px;
+    font-size: 12px;
+    font-weight: 600;
+}
+
+.status-badge.pending {
+    background: var(--sw-color-warning-bg);
+    color: var(--sw-color-warning-text);
+}
+
+.status-badge.paid {
+    background: var(--sw-color-success-bg);
+    color: var(--sw-color-success-text);
+}
+
+.status-badge.expired {
+    background: var(--sw-color-neutral-bg);
+    color: var(--sw-color-neutral-text);
+}
+"#;
+
+/// Explorer settings, lookups and broadcast.
+pub const NETWORK: &str = r#"
+.network-panel select {
+    padding: 8px 10px;
+    border: 1px solid var(--sw-color-border);
+    border-radius: var(--sw-radius);
+    font-size: 13px;
+}
+
+.network-tx-input {
+    height: 90px;
+    font-family: var(--sw-font-mono);
+}
+
+.network-status {
+    margin: 8px 0;
+    font-size: 13px;
+    color: var(--sw-color-text);
+    word-break: break-all;
+}
+"#;
+
+/// Signature verifier form and witness results.
+pub const SIGNATURES: &str = r#"
+.signature-form {
+    display: grid;
+    gap: 10px;
+    margin-bottom: 15px;
+}
+
+.signature-form input {
+    padding: 8px 10px;
+    border: 1px solid var(--sw-color-border);
+    border-radius: var(--sw-radius);
+    font-family: var(--sw-font-mono);
+    font-size: 13px;
+}
+
+.signature-list {
+    list-style: none;
+    margin-top: 10px;
+}
+
+.signature-row {
+    display: flex;
+    flex-wrap: wrap;
+    align-items: center;
+    gap: 10px;
+    padding: 6px 0;
+    border-bottom: 1px solid var(--sw-color-border-light);
+    font-size: 13px;
+    word-break: break-all;
+}
+
+.registry-search {
+    flex: 1;
+    min-width: 12em;
+    padding: 6px 8px;
+    border: 1px solid var(--sw-color-border);
+    border-radius: var(--sw-radius);
+    font-size: 13px;
+}
+
+.registry-tag {
+    padding: 1px 8px;
+    border: none;
+    border-radius: 10px;
+    cursor: pointer;
+    background: var(--sw-color-neutral-bg);
+    color: var(--sw-color-neutral-text);
+    font-size: 12px;
+}
+
+.bench-iterations {
+    width: 90px;
+    padding: 6px 8px;
+    border: 1px solid var(--sw-color-border);
+    border-radius: var(--sw-radius);
+}
+
+.bench-chart {
+    display: block;
+    width: 100%;
+    height: 80px;
+    margin-top: 10px;
+    background: var(--sw-color-surface-alt);
+    border: 1px solid var(--sw-color-border-light);
+}
+
+.bench-swatch {
+    display: inline-block;
+    width: 10px;
+    height: 10px;
+    margin-right: 6px;
+    border-radius: 2px;
+}
+
+.bench-compile {
+    fill: var(--sw-color-primary);
+    background: var(--sw-color-primary);
+}
+
+.bench-satisfy {
+    fill: var(--sw-color-warning);
+    background: var(--sw-color-warning);
+}
+
+.bench-execute {
+    fill: var(--sw-color-success-text);
+    background: var(--sw-color-success-text);
+}
+
+.repl-form {
+    display: grid;
+    grid-template-columns: 1fr minmax(120px, 30%);
+    gap: 10px;
+    margin-bottom: 10px;
+}
+
+.repl-form textarea,
+.repl-form input {
+    font-family: var(--sw-font-mono);
+    font-size: 13px;
+}
+
+.repl-history {
+    list-style: none;
+    margin-top: 10px;
+}
+
+.repl-entry {
+    padding: 8px 0;
+    border-bottom: 1px solid var(--sw-color-border-light);
+    font-size: 13px;
+}
+
+.repl-input {
+    display: block;
+    color: var(--sw-color-text-muted);
+    white-space: pre-wrap;
+}
+
+.repl-value {
+    font-family: var(--sw-font-mono);
+    word-break: break-all;
+    margin: 4px 0;
+}
+
+.spend-path-notes {
+    flex-basis: 100%;
+    margin: 0;
+    padding-left: 18px;
+    font-size: 12px;
+    color: var(--sw-color-text-muted);
+    word-break: normal;
+}
+
+.signature-empty {
+    margin-top: 10px;
+    font-size: 13px;
+    color: var(--sw-color-text-muted);
+}
+"#;
+
+/// Form-driven witness editor.
+pub const WITNESS_WIZARD: &str = r#"
+.witness-wizard {
+    display: grid;
+    gap: 8px;
+    margin-bottom: 15px;
+}
+
+.witness-tabs {
+    margin-bottom: 10px;
+}
+
+.witness-tab-strip {
+    display: flex;
+    flex-wrap: wrap;
+    gap: 4px;
+    border-bottom: 1px solid var(--sw-color-border);
+}
+
+.witness-tab {
+    display: inline-flex;
+    align-items: center;
+    gap: 6px;
+    padding: 6px 12px;
+    border: 1px solid var(--sw-color-border);
+    border-bottom: none;
+    border-radius: var(--sw-radius) var(--sw-radius) 0 0;
+    background: var(--sw-color-surface-sunken);
+    color: var(--sw-color-text);
+    font-size: 13px;
+    cursor: pointer;
+}
+
+.witness-tab.active {
+    background: var(--sw-color-surface);
+    font-weight: 600;
+}
+
+.witness-tab-actions {
+    display: flex;
+    flex-wrap: wrap;
+    gap: 8px;
+    margin-top: 8px;
+}
+
+.witness-tab-actions input {
+    flex: 1;
+    min-width: 120px;
+    padding: 6px 8px;
+    border: 1px solid var(--sw-color-border);
+    border-radius: var(--sw-radius);
+    font-size: 13px;
+}
+
+.witness-field {
+    display: grid;
+    grid-template-columns: minmax(120px, 1fr) 110px 2fr;
+    align-items: center;
+    gap: 8px;
+}
+
+.witness-field-name {
+    font-family: var(--sw-font-mono);
+    font-size: 13px;
+    word-break: break-all;
+}
+
+.witness-field input {
+    padding: 6px 8px;
+    border: 1px solid var(--sw-color-border);
+    border-radius: var(--sw-radius);
+    font-family: var(--sw-font-mono);
+    font-size: 13px;
+}
+
+.witness-field input[type="checkbox"] {
+    justify-self: start;
+}
+
+.witness-field-hint {
+    grid-column: 1 / -1;
+    font-size: 12px;
+    color: var(--sw-color-text-muted);
+}
+
+.witness-field-error {
+    grid-column: 1 / -1;
+    font-size: 12px;
+    color: var(--sw-color-danger);
+}
+
+.witness-field-inspect {
+    grid-column: 1 / -1;
+    display: flex;
+    align-items: center;
+    gap: 6px;
+    font-size: 12px;
+    color: var(--sw-color-text-muted);
+}
+
+.witness-field input.witness-field-bytes {
+    grid-column: 1 / -1;
+}
+
+.witness-field-hint code {
+    word-break: break-all;
+}
+
+.schema-errors {
+    margin: 6px 0 0;
+    padding-left: 18px;
+    font-size: 12px;
+    color: var(--sw-color-danger);
+}
+
+.schema-errors:empty {
+    display: none;
+}
+"#;
+
+/// Bulk address and CMR verification results.
+pub const BULK: &str = r#"
+.bulk-summary {
+    margin: 10px 0;
+    font-weight: 600;
+    color: var(--sw-color-text);
+}
+
+.bulk-table {
+    width: 100%;
+    border-collapse: collapse;
+    font-size: 12px;
+}
+
+.bulk-table th,
+.bulk-table td {
+    padding: 6px 8px;
+    text-align: left;
+    border-bottom: 1px solid var(--sw-color-border-light);
+    vertical-align: top;
+}
+
+.bulk-table td.bulk-address {
+    font-family: var(--sw-font-mono);
+    word-break: break-all;
+}
+
+.verify-cmrs {
+    display: grid;
+    grid-template-columns: max-content 1fr;
+    gap: 4px 12px;
+    margin-top: 10px;
+    font-size: 13px;
+}
+
+.verify-cmrs dd {
+    margin: 0;
+    font-family: var(--sw-font-mono);
+    word-break: break-all;
+}
+
+.hexdump-nodes td {
+    font-family: var(--sw-font-mono);
+}
+
+.hexdump-nodes td.hexdump-bits {
+    word-break: break-all;
+}
+
+.dev-log {
+    max-height: 320px;
+    overflow-y: auto;
+    border: 1px solid var(--sw-color-border-light);
+}
+
+.dev-log td {
+    font-family: var(--sw-font-mono);
+    white-space: nowrap;
+}
+
+.dev-log td.dev-log-message {
+    white-space: pre-wrap;
+    word-break: break-all;
+}
+
+.dev-log-debug {
+    color: var(--sw-color-text-subtle);
+}
+
+.dev-log-warn {
+    color: var(--sw-color-warning-text);
+}
+
+.dev-log-error {
+    color: var(--sw-color-error-text);
+}
+
+.dev-console-level {
+    display: flex;
+    align-items: center;
+    gap: 6px;
+    font-size: 13px;
+}
+
+.mock-tx-table input {
+    width: 100%;
+    min-width: 60px;
+    padding: 4px 6px;
+    border: 1px solid var(--sw-color-border);
+    border-radius: var(--sw-radius);
+    font-family: var(--sw-font-mono);
+    font-size: 12px;
+}
+
+.limit-field {
+    display: flex;
+    flex-direction: column;
+    gap: 2px;
+    font-size: 13px;
+}
+
+.limit-field input {
+    width: 120px;
+}
+"#;
+
+/// The Settings dialog.
+pub const SETTINGS: &str = r#"
+.settings-backdrop {
+    position: fixed;
+    inset: 0;
+    z-index: 20;
+    display: flex;
+    align-items: center;
+    justify-content: center;
+    padding: 20px;
+    background: rgba(0, 0, 0, 0.4);
+}
+
+.settings-dialog {
+    width: min(440px, 100%);
+    max-height: 100%;
+    overflow-y: auto;
+    padding: 20px;
+    background: var(--sw-color-surface);
+    color: var(--sw-color-text);
+    border-radius: var(--sw-radius-lg);
+    box-shadow: var(--sw-shadow-raised);
+}
+
+.settings-dialog h2 {
+    margin-bottom: 15px;
+    font-size: 18px;
+}
+
+.settings-grid {
+    display: grid;
+    grid-template-columns: 1fr auto;
+    align-items: center;
+    gap: 10px 16px;
+    margin-bottom: 12px;
+    font-size: 14px;
+}
+
+.settings-grid input[type="number"] {
+    width: 80px;
+    padding: 6px 8px;
+    border: 1px solid var(--sw-color-border);
+    border-radius: var(--sw-radius);
+}
+"#;
+
+/// Narrow-viewport overrides; must come last.
+pub const RESPONSIVE: &str = r#"
+@media (max-width: 768px) {
+    .split-pane {
+        grid-template-columns: 1fr !important;
+        gap: 20px;
+    }
+
+    .split-pane-divider {
+        display: none;
+    }
+
+    .header h1 {
+        font-size: 24px;
+    }
+
+    .button-group {
+        flex-direction: column;
+    }
+
+    button {
+        width: 100%;
+    }
+
+    /* Phones: touch-sized controls, pickers instead of drop zones, and the
+       results in a bottom sheet above the on-screen keyboard. */
+    .container {
+        padding-bottom: 80px;
+    }
+
+    button,
+    select {
+        min-height: 44px;
+    }
+
+    .drop-zone {
+        display: none;
+    }
+
+    .file-picker {
+        display: block;
+        min-height: 48px;
+        margin-bottom: 15px;
+        font-size: 16px;
+    }
+
+    .code-input {
+        max-height: calc(100dvh - var(--sw-keyboard-inset, 0px) - 160px);
+    }
+
+    button.compile-button {
+        position: fixed;
+        right: 16px;
+        bottom: calc(68px + var(--sw-keyboard-inset, 0px));
+        width: auto;
+        z-index: 20;
+        box-shadow: var(--sw-shadow-raised);
+    }
+
+    .bottom-sheet {
+        position: fixed;
+        left: 0;
+        right: 0;
+        bottom: var(--sw-keyboard-inset, 0px);
+        z-index: 10;
+        padding: 0 16px 16px;
+        overflow-y: auto;
+        overscroll-behavior: contain;
+        background: var(--sw-color-surface);
+        border-radius: var(--sw-radius-lg) var(--sw-radius-lg) 0 0;
+        box-shadow: 0 -2px 8px rgba(0,0,0,0.15);
+        transition: height 0.2s ease;
+    }
+
+    .bottom-sheet.peek {
+        height: 56px;
+        overflow: hidden;
+    }
+
+    .bottom-sheet.half {
+        height: 50dvh;
+    }
+
+    .bottom-sheet.full {
+        height: calc(100dvh - var(--sw-keyboard-inset, 0px) - 24px);
+    }
+
+    .bottom-sheet-handle {
+        display: flex;
+        position: sticky;
+        top: 0;
+        flex-direction: column;
+        align-items: center;
+        gap: 6px;
+        min-height: 56px;
+        padding: 8px;
+        background: var(--sw-color-surface);
+        color: var(--sw-color-text);
+        border: none;
+        touch-action: none;
+    }
+
+    .bottom-sheet-grip {
+        width: 40px;
+        height: 4px;
+        border-radius: 2px;
+        background: var(--sw-color-border-strong);
+    }
+}
+"#;
+
+/// The full stylesheet, in cascade order.
+pub fn stylesheet() -> String {
+    [THEME, dark().as_str(), BASE, CONTROLS, SPLIT_PANE, EDITOR, RESULTS, SANITY, TRIAGE, INVOICE, NETWORK, SIGNATURES, WITNESS_WIZARD, BULK, SETTINGS, RESPONSIVE].concat()
+}
@@ -0,0 +1,82 @@
+//! Plain-text summaries of compilation artifacts.
+//!
+//! Every visual artifact the playground renders gets a short textual
+//! counterpart here, so screen-reader users and plain-text exports see the
+//! same information as the graphical panels.
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::dag::{DagLike, MaxSharing};
+use simplicityhl::simplicity::jet::Elements;
+use simplicityhl::simplicity::node::{Commit, Inner};
+use simplicityhl::simplicity::CommitNode;
+
+/// Structural facts about a compiled program's DAG.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProgramSummary {
+    pub cmr: String,
+    /// Number of distinct nodes after maximal sharing.
+    pub node_count: usize,
+    /// Number of `case` nodes, i.e. points where execution branches.
+    pub branch_count: usize,
+    pub jet_count: usize,
+    pub witness_count: usize,
+    pub disconnect_count: usize,
+    pub constant_count: usize,
+    /// Length of the bit-encoded program without witness data.
+    pub program_bytes: usize,
+    /// Length of the SimplicityHL source in UTF-8 bytes.
+    pub source_bytes: usize,
+}
+
+impl ProgramSummary {
+    /// Walk the committed program once and collect its summary.
+    pub fn from_commit(program: &CommitNode<Elements>, source: &str) -> Self {
+        let mut summary = ProgramSummary {
+            cmr: program.cmr().to_string(),
+            program_bytes: program.to_vec_without_witness().len(),
+            source_bytes: source.len(),
+            ..ProgramSummary::default()
+        };
+
+        for item in program.post_order_iter::<MaxSharing<Commit<Elements>>>() {
+            summary.node_count += 1;
+            match item.node.inner() {
+                Inner::Case(..) | Inner::AssertL(..) | Inner::AssertR(..) => {
+                    summary.branch_count += 1
+                }
+                Inner::Jet(_) => summary.jet_count += 1,
+                Inner::Witness(_) => summary.witness_count += 1,
+                Inner::Disconnect(..) => summary.disconnect_count += 1,
+                Inner::Word(_) => summary.constant_count += 1,
+                _ => {}
+            }
+        }
+
+        summary
+    }
+
+    /// Render the summary as a few short sentences.
+    ///
+    /// The wording is meant to be read aloud, so numbers are spelled with
+    /// their units and the CMR comes last.
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![
+            format!(
+                "Program DAG: {} nodes, {} branch points, {} jets, {} witness nodes.",
+                self.node_count, self.branch_count, self.jet_count, self.witness_count
+            ),
+            format!(
+                "Encoded program: {} bytes; source: {} bytes.",
+                self.program_bytes, self.source_bytes
+            ),
+        ];
+        if self.disconnect_count > 0 || self.constant_count > 0 {
+            lines.push(format!(
+                "Also contains {} disconnect nodes and {} constant words.",
+                self.disconnect_count, self.constant_count
+            ));
+        }
+        lines.push(format!("CMR: {}", self.cmr));
+        lines.join("\n")
+    }
+}
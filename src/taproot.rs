@@ -0,0 +1,301 @@
+//! User-configurable taproot trees around the Simplicity leaf.
+//!
+//! By default a program is the only leaf under the unspendable internal key
+//! (see [`address`](crate::address)). A [`TaprootConfig`] can replace the
+//! internal key, which makes the key path spendable by whoever holds it, and
+//! add tapscript leaves next to the program. Leaves are arranged in a
+//! balanced tree, the Simplicity leaf first.
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::elements::secp256k1_zkp::{Secp256k1, XOnlyPublicKey};
+use simplicityhl::simplicity::elements::taproot::{ControlBlock, LeafVersion, TaprootBuilder, TaprootSpendInfo};
+use simplicityhl::simplicity::elements::{Address, Script};
+use simplicityhl::simplicity::hashes::Hash;
+use simplicityhl::simplicity::{leaf_version, Cmr};
+
+use crate::address::{leaf_script, Network, UNSPENDABLE_INTERNAL_KEY};
+use crate::cache;
+use crate::hex;
+use crate::storage;
+
+const TAPROOT_KEY: &str = "taproot";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaprootConfig {
+    /// x-only internal key in hex; the unspendable key when absent.
+    #[serde(default)]
+    pub internal_key: Option<String>,
+    /// Additional tapscript leaves, hex-encoded scripts.
+    #[serde(default)]
+    pub leaves: Vec<String>,
+}
+
+/// Depth of each of `count` leaves in a balanced tree, in depth-first order.
+fn balanced_depths(count: usize) -> Vec<u8> {
+    if count <= 1 {
+        return vec![0; count];
+    }
+    let shallow = usize::BITS - 1 - count.leading_zeros(); // floor(log2)
+    let deep_leaves = 2 * (count - (1 << shallow));
+    let mut depths = vec![shallow as u8 + 1; deep_leaves];
+    depths.resize(count, shallow as u8);
+    depths
+}
+
+impl TaprootConfig {
+    pub fn load() -> Self {
+        storage::load(TAPROOT_KEY)
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Ok(json) = serde_json::to_string(self) {
+            storage::save(TAPROOT_KEY, &json);
+        }
+    }
+
+    /// True for the single-leaf tree under the unspendable key.
+    pub fn is_default(&self) -> bool {
+        self.internal_key.is_none() && self.leaves.is_empty()
+    }
+
+    pub fn internal_key(&self) -> Result<XOnlyPublicKey, String> {
+        let key = self.internal_key.as_deref().unwrap_or(UNSPENDABLE_INTERNAL_KEY).trim();
+        XOnlyPublicKey::from_str(key.strip_prefix("0x").unwrap_or(key))
+            .map_err(|e| format!("Invalid internal key: {e}"))
+    }
+
+    /// The extra leaves as scripts.
+    pub fn leaf_scripts(&self) -> Result<Vec<Script>, String> {
+        self.leaves
+            .iter()
+            .enumerate()
+            .map(|(index, leaf)| {
+                hex::decode(leaf)
+                    .map(Script::from)
+                    .map_err(|e| format!("Leaf {}: {e}", index + 1))
+            })
+            .collect()
+    }
+
+    /// Build the tree holding `cmr` and the extra leaves.
+    pub fn spend_info(&self, cmr: Cmr) -> Result<TaprootSpendInfo, String> {
+        let internal_key = self.internal_key()?;
+        let leaves = std::iter::once((leaf_script(cmr), leaf_version()))
+            .chain(self.leaf_scripts()?.into_iter().map(|s| (s, LeafVersion::default())));
+        let mut builder = TaprootBuilder::new();
+        for ((script, version), depth) in leaves.zip(balanced_depths(self.leaves.len() + 1)) {
+            builder = builder
+                .add_leaf_with_ver(depth, script, version)
+                .map_err(|e| format!("Invalid taproot tree: {e}"))?;
+        }
+        builder
+            .finalize(&Secp256k1::verification_only(), internal_key)
+            .map_err(|_| "Invalid taproot tree: incomplete".to_string())
+    }
+
+    /// The control block for spending through the Simplicity leaf.
+    pub fn control_block(&self, cmr: Cmr) -> Result<ControlBlock, String> {
+        self.spend_info(cmr)?
+            .control_block(&(leaf_script(cmr), leaf_version()))
+            .ok_or_else(|| "The Simplicity leaf is not in the tree".to_string())
+    }
+
+    /// The unconfidential address of the tree.
+    pub fn address(&self, cmr: Cmr, network: Network) -> Result<Address, String> {
+        let info = self.spend_info(cmr)?;
+        Ok(Address::p2tr(
+            &Secp256k1::verification_only(),
+            info.internal_key(),
+            info.merkle_root(),
+            None,
+            network.address_params(),
+        ))
+    }
+}
+
+/// The control block for the single-leaf tree of `cmr` under
+/// `internal_key`. A given `parity` must match the output key's, which
+/// catches keys mixed up between trees.
+pub fn leaf_control_block(internal_key: &str, cmr: Cmr, parity: Option<u8>) -> Result<ControlBlock, String> {
+    let config = TaprootConfig {
+        internal_key: (!internal_key.trim().is_empty()).then(|| internal_key.to_string()),
+        leaves: Vec::new(),
+    };
+    let control_block = config.control_block(cmr)?;
+    let actual = control_block.output_key_parity.to_u8();
+    match parity {
+        Some(parity) if parity != actual => Err(format!(
+            "The output key has parity {actual}, not {parity}; check the internal key"
+        )),
+        _ => Ok(control_block),
+    }
+}
+
+/// What a control block proves about a Simplicity leaf.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ControlBlockCheck {
+    /// The leaf for `cmr` is committed to by the output key.
+    pub valid: bool,
+    /// The leaf version is Simplicity's.
+    pub simplicity_leaf: bool,
+    pub internal_key: String,
+    pub output_key_parity: u8,
+    /// Number of hashes in the Merkle path.
+    pub path_length: usize,
+}
+
+/// The x-only output key of `output`, an address or a key in hex.
+fn output_key(output: &str) -> Result<XOnlyPublicKey, String> {
+    let output = output.trim();
+    if let Ok(address) = Address::from_str(output) {
+        return p2tr_output_key(address.script_pubkey().as_bytes())
+            .ok_or_else(|| "The address is not a taproot address".to_string())?;
+    }
+    XOnlyPublicKey::from_slice(&hex::decode(output)?).map_err(|e| format!("Invalid output key: {e}"))
+}
+
+/// The output key of a pay-to-taproot script.
+fn p2tr_output_key(script: &[u8]) -> Option<Result<XOnlyPublicKey, String>> {
+    match script {
+        [0x51, 0x20, key @ ..] if key.len() == 32 => {
+            Some(XOnlyPublicKey::from_slice(key).map_err(|e| format!("Invalid output key: {e}")))
+        }
+        _ => None,
+    }
+}
+
+/// Check `control_block_hex` proves the leaf for `cmr` is in the tree of
+/// `output`, given as an address or x-only output key.
+pub fn verify_control_block(control_block_hex: &str, cmr: Cmr, output: &str) -> Result<ControlBlockCheck, String> {
+    let control_block = ControlBlock::from_slice(&hex::decode(control_block_hex)?)
+        .map_err(|e| format!("Invalid control block: {e}"))?;
+    let output_key = output_key(output)?;
+    let simplicity_leaf = control_block.leaf_version == leaf_version();
+    let valid = simplicity_leaf
+        && control_block.verify_taproot_commitment(&Secp256k1::verification_only(), &output_key, &leaf_script(cmr));
+    Ok(ControlBlockCheck {
+        valid,
+        simplicity_leaf,
+        internal_key: control_block.internal_key.to_string(),
+        output_key_parity: control_block.output_key_parity.to_u8(),
+        path_length: control_block.merkle_branch.as_inner().len(),
+    })
+}
+
+/// Everything needed to spend from or watch a configured tree.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SpendInfoReport {
+    pub internal_key: String,
+    pub merkle_root: Option<String>,
+    pub output_key: String,
+    pub output_key_parity: u8,
+    pub address: String,
+    /// Control block for the Simplicity leaf, hex.
+    pub control_block: String,
+}
+
+pub fn report(cmr: Cmr, config: &TaprootConfig, network: Network) -> Result<SpendInfoReport, String> {
+    let info = config.spend_info(cmr)?;
+    let (output_key, parity) = (info.output_key(), info.output_key_parity());
+    Ok(SpendInfoReport {
+        internal_key: info.internal_key().to_string(),
+        merkle_root: info.merkle_root().map(|root| hex::encode(&root.to_byte_array())),
+        output_key: output_key.as_inner().to_string(),
+        output_key_parity: parity.to_u8(),
+        address: config.address(cmr, network)?.to_string(),
+        control_block: hex::encode(&config.control_block(cmr)?.serialize()),
+    })
+}
+
+/// The script pubkey of the single-leaf tree of `cmr` under `internal_key`
+/// (hex; empty for the unspendable key).
+pub fn script_pubkey_for(cmr: Cmr, internal_key: &str) -> Result<Script, String> {
+    let config = TaprootConfig {
+        internal_key: (!internal_key.trim().is_empty()).then(|| internal_key.to_string()),
+        leaves: Vec::new(),
+    };
+    let info = config.spend_info(cmr)?;
+    Ok(Script::new_v1_p2tr(&Secp256k1::verification_only(), info.internal_key(), info.merkle_root()))
+}
+
+/// What a script pubkey locks funds to.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ScriptPubkeyInfo {
+    /// `p2tr`, `p2wpkh`, `p2wsh`, `witness` (other versions), `p2pkh`,
+    /// `p2sh`, `fee` (the empty script of fee outputs), `op_return` or `other`.
+    pub kind: String,
+    pub witness_version: Option<u8>,
+    /// Witness program, hex.
+    pub witness_program: Option<String>,
+    /// Taproot output key, hex.
+    pub output_key: Option<String>,
+    /// CMR of a program compiled in this session whose output this is,
+    /// under the unspendable key or the saved taproot settings.
+    pub cmr: Option<String>,
+}
+
+/// The compiled program, if any, whose taproot output key is `output_key`.
+fn known_program(output_key: &XOnlyPublicKey) -> Option<Cmr> {
+    let configs = [TaprootConfig::default(), TaprootConfig::load()];
+    cache::cmrs().into_iter().find(|&cmr| {
+        configs.iter().any(|config| {
+            config
+                .spend_info(cmr)
+                .is_ok_and(|info| info.output_key().as_inner() == output_key)
+        })
+    })
+}
+
+/// Classify `script_hex` and, for taproot outputs, report the output key
+/// and the matching compiled program.
+pub fn decode_script_pubkey(script_hex: &str) -> Result<ScriptPubkeyInfo, String> {
+    let script = hex::decode(script_hex)?;
+    let mut info = ScriptPubkeyInfo {
+        kind: "other".to_string(),
+        witness_version: None,
+        witness_program: None,
+        output_key: None,
+        cmr: None,
+    };
+
+    // Witness outputs: a version opcode (OP_0, OP_1..OP_16) and one push.
+    let version = match script.first() {
+        Some(0x00) => Some(0),
+        Some(&op @ 0x51..=0x60) => Some(op - 0x50),
+        _ => None,
+    };
+    match (version, script.get(1)) {
+        (Some(version), Some(&len)) if (2..=40).contains(&len) && script.len() == 2 + len as usize => {
+            info.witness_version = Some(version);
+            info.witness_program = Some(hex::encode(&script[2..]));
+            info.kind = match (version, len) {
+                (0, 20) => "p2wpkh",
+                (0, 32) => "p2wsh",
+                (1, 32) => "p2tr",
+                _ => "witness",
+            }
+            .to_string();
+        }
+        _ => {
+            info.kind = match script.as_slice() {
+                [] => "fee",
+                [0x6a, ..] => "op_return",
+                [0x76, 0xa9, 0x14, .., 0x88, 0xac] if script.len() == 25 => "p2pkh",
+                [0xa9, 0x14, .., 0x87] if script.len() == 23 => "p2sh",
+                _ => "other",
+            }
+            .to_string();
+        }
+    }
+
+    if let Some(output_key) = p2tr_output_key(&script) {
+        let output_key = output_key?;
+        info.output_key = Some(output_key.to_string());
+        info.cmr = known_program(&output_key).map(|cmr| cmr.to_string());
+    }
+    Ok(info)
+}
@@ -0,0 +1,236 @@
+//! Taproot settings: internal key and extra tapscript leaves.
+
+use leptos::prelude::*;
+use simplicityhl::simplicity::Cmr;
+
+use crate::address::{parse_cmr, Network};
+use crate::address_book;
+use crate::hex;
+use crate::i18n::{self, t, Msg};
+use crate::taproot::{self, TaprootConfig};
+use crate::vault;
+
+#[component]
+pub fn TaprootPanel(
+    config: RwSignal<TaprootConfig>,
+    /// CMR of the most recently compiled program.
+    #[prop(into)]
+    cmr: Signal<Option<String>>,
+    #[prop(into)] network: Signal<Network>,
+    /// The contract address, withheld while sanity findings are
+    /// unacknowledged; the output key it encodes is withheld with it.
+    #[prop(into)]
+    address: Signal<Option<String>>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let initial = config.get_untracked();
+    let (internal_key, set_internal_key) = signal(initial.internal_key.unwrap_or_default());
+    let (leaves, set_leaves) = signal(initial.leaves.join("\n"));
+    let (generated, set_generated) = signal::<Option<String>>(None);
+    let (error, set_error) = signal::<Option<String>>(None);
+    let (check_block, set_check_block) = signal(String::new());
+    let (check_output, set_check_output) = signal(String::new());
+    let (check, set_check) = signal::<Option<Result<taproot::ControlBlockCheck, String>>>(None);
+    let (script, set_script) = signal(String::new());
+    let decoded = Memo::new(move |_| {
+        let script = script.get();
+        (!script.trim().is_empty()).then(|| taproot::decode_script_pubkey(&script))
+    });
+
+    let apply = move |_| {
+        // Vault keys and contacts can be named as `@label`.
+        let key = address_book::expand_all(internal_key.get_untracked().trim());
+        let candidate = TaprootConfig {
+            internal_key: (!key.is_empty()).then_some(key),
+            leaves: leaves
+                .get_untracked()
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect(),
+        };
+        // Validate against any leaf; the program's CMR may not exist yet.
+        match candidate.spend_info(Cmr::from_byte_array([0; 32])) {
+            Ok(_) => {
+                set_error.set(None);
+                candidate.save();
+                config.set(candidate);
+            }
+            Err(e) => set_error.set(Some(e)),
+        }
+    };
+
+    let reset = move |_| {
+        set_internal_key.set(String::new());
+        set_leaves.set(String::new());
+        set_generated.set(None);
+        set_error.set(None);
+        let default = TaprootConfig::default();
+        default.save();
+        config.set(default);
+    };
+
+    let generate = move |_| {
+        let secret_hex = hex::encode(&vault::generate_secret().secret_bytes());
+        if let Ok((_, pubkey)) = vault::parse_secret(&secret_hex) {
+            set_internal_key.set(pubkey.to_string());
+            set_generated.set(Some(secret_hex));
+        }
+    };
+
+    let verify = move |_| {
+        let result = cmr
+            .get_untracked()
+            .ok_or_else(|| i18n::text(lang.get_untracked(), Msg::CompileFirst).to_string())
+            .and_then(|cmr| parse_cmr(&cmr))
+            .and_then(|cmr| {
+                taproot::verify_control_block(&check_block.get_untracked(), cmr, &check_output.get_untracked())
+            });
+        set_check.set(Some(result));
+    };
+
+    let check_view = move || {
+        check.get().map(|check| match check {
+            Ok(check) if check.valid => view! {
+                <p class="status-badge paid">
+                    {i18n::format(i18n::text(lang.get(), Msg::CommitsToLeaf), &[&check.path_length.to_string()])}
+                </p>
+            }
+            .into_any(),
+            Ok(check) if !check.simplicity_leaf => {
+                view! { <p class="status-badge expired">{t(Msg::NotSimplicityLeaf)}</p> }.into_any()
+            }
+            Ok(_) => view! { <p class="status-badge expired">{t(Msg::NotThisProgram)}</p> }.into_any(),
+            Err(e) => view! { <p class="tool-error">{e}</p> }.into_any(),
+        })
+    };
+
+    let decoded_view = move || {
+        decoded.get().map(|decoded| match decoded {
+            Ok(info) => {
+                let lang = lang.get();
+                let matched = match (&info.cmr, cmr.get()) {
+                    (Some(found), Some(current)) if *found == current => {
+                        i18n::text(lang, Msg::ThisProgramsOutput).to_string()
+                    }
+                    (Some(found), _) => i18n::format(i18n::text(lang, Msg::OutputOfProgram), &[found]),
+                    (None, _) => i18n::text(lang, Msg::NotCompiledHere).to_string(),
+                };
+                view! {
+                    <div class="output-group">
+                        <p class="network-status">{i18n::format(i18n::text(lang, Msg::ScriptType), &[&info.kind])}</p>
+                        {info
+                            .output_key
+                            .map(|key| {
+                                view! {
+                                    <span class="output-label">{t(Msg::OutputKeyLabel)}</span>
+                                    <div class="output-box">{key}</div>
+                                    <p class="network-status">{matched}</p>
+                                }
+                            })}
+                    </div>
+                }
+                .into_any()
+            }
+            Err(e) => view! { <p class="tool-error">{e}</p> }.into_any(),
+        })
+    };
+
+    let details = move || {
+        address.get()?;
+        let cmr = parse_cmr(&cmr.get()?).ok()?;
+        Some(match config.with(|c| taproot::report(cmr, c, network.get())) {
+            Ok(report) => view! {
+                <div class="output-group">
+                    <span class="output-label">{t(Msg::OutputKeyLabel)}</span>
+                    <div class="output-box">
+                        {i18n::format(
+                            i18n::text(lang.get(), Msg::OutputKeyParity),
+                            &[&report.output_key, &report.output_key_parity.to_string()],
+                        )}
+                    </div>
+                    <span class="output-label">{t(Msg::MerkleRootLabel)}</span>
+                    <div class="output-box">{report.merkle_root.unwrap_or_default()}</div>
+                    <span class="output-label">{t(Msg::ControlBlockLabel)}</span>
+                    <div class="output-box">{report.control_block}</div>
+                </div>
+            }
+            .into_any(),
+            Err(e) => view! { <p class="tool-error">{e}</p> }.into_any(),
+        })
+    };
+
+    view! {
+        <div class="taproot-panel">
+            <label>{t(Msg::TaprootSettings)}</label>
+            <div class="invoice-form">
+                <input
+                    type="text"
+                    placeholder=t(Msg::InternalKeyPlaceholder)
+                    prop:value=move || internal_key.get()
+                    on:input=move |ev| set_internal_key.set(event_target_value(&ev))
+                />
+                <button class="secondary" on:click=generate>
+                    {t(Msg::GenerateKey)}
+                </button>
+            </div>
+            <textarea
+                rows="3"
+                placeholder=t(Msg::ExtraLeavesPlaceholder)
+                prop:value=move || leaves.get()
+                on:input=move |ev| set_leaves.set(event_target_value(&ev))
+            />
+            <div class="button-group">
+                <button on:click=apply>{t(Msg::Apply)}</button>
+                <button class="secondary" on:click=reset disabled=move || config.with(TaprootConfig::is_default)>
+                    {t(Msg::UseDefaultTree)}
+                </button>
+            </div>
+            {move || {
+                generated
+                    .get()
+                    .map(|secret| {
+                        view! {
+                            <p class="manifest-status">
+                                {t(Msg::GeneratedInternalKey)}
+                                <code>{secret}</code>
+                            </p>
+                        }
+                    })
+            }}
+            {move || error.get().map(|e| view! { <p class="tool-error">{e}</p> })}
+            {details}
+            <div class="section-header">
+                <span class="output-label">{t(Msg::VerifyControlBlock)}</span>
+            </div>
+            <div class="signature-form">
+                <input
+                    placeholder=t(Msg::ControlBlockPlaceholder)
+                    prop:value=move || check_block.get()
+                    on:input=move |ev| set_check_block.set(event_target_value(&ev))
+                />
+                <input
+                    placeholder=t(Msg::AddressOrOutputKey)
+                    prop:value=move || check_output.get()
+                    on:input=move |ev| set_check_output.set(event_target_value(&ev))
+                />
+            </div>
+            <div class="button-group">
+                <button class="secondary" on:click=verify>{t(Msg::Verify)}</button>
+            </div>
+            {check_view}
+            <div class="section-header">
+                <span class="output-label">{t(Msg::DecodeScriptPubkey)}</span>
+            </div>
+            <div class="signature-form">
+                <input
+                    placeholder=t(Msg::ScriptPubkeyPlaceholder)
+                    prop:value=move || script.get()
+                    on:input=move |ev| set_script.set(event_target_value(&ev))
+                />
+            </div>
+            {decoded_view}
+        </div>
+    }
+}
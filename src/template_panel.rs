@@ -0,0 +1,105 @@
+//! Form for instantiating a contract template.
+
+use std::collections::HashMap;
+
+use leptos::prelude::*;
+
+use crate::i18n::{self, t, Msg};
+use crate::templates::{self, Template, TEMPLATES};
+use crate::witness_form::InputKind;
+
+#[component]
+pub fn TemplatePanel(
+    /// Name of the chosen template; `#/examples/<slug>` routes set it.
+    chosen: RwSignal<&'static str>,
+    /// Whether the form is expanded.
+    open: RwSignal<bool>,
+    /// Called with the instantiated source.
+    on_create: Callback<String>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let values = RwSignal::new(HashMap::<String, String>::new());
+    let (errors, set_errors) = signal(Vec::<(String, String)>::new());
+    let template = move || Template::find(chosen.get()).unwrap_or(&TEMPLATES[0]);
+
+    let create = move |_| match template().instantiate(&values.get_untracked()) {
+        Ok(source) => {
+            set_errors.set(Vec::new());
+            on_create.run(source);
+        }
+        Err(errors) => set_errors.set(errors),
+    };
+
+    let fields = move || {
+        template()
+            .placeholders
+            .iter()
+            .map(|placeholder| {
+                let name = placeholder.name;
+                let ty = placeholder.ty;
+                let placeholder_text = match InputKind::of(ty) {
+                    InputKind::Hex(32) => i18n::text(lang.get(), Msg::HexKeyOrLabel).to_string(),
+                    InputKind::Hex(len) => i18n::format(i18n::text(lang.get(), Msg::HexBytes), &[&len.to_string()]),
+                    _ => ty.to_string(),
+                };
+                // Labels from the vault and address book are offered as completions.
+                let list = (InputKind::of(ty) == InputKind::Hex(32)).then_some("address-book");
+                let value = move || values.with(|v| v.get(name).cloned().unwrap_or_default());
+                let error = move || {
+                    let value = value();
+                    let shown = errors.with(|e| e.iter().find(|(n, _)| n == name).map(|(_, e)| e.clone()));
+                    (!value.is_empty()).then(|| templates::validate(ty, &value).err()).flatten().or(shown)
+                };
+                view! {
+                    <div class="witness-field">
+                        <span class="witness-field-name">{placeholder.label}</span>
+                        <span class="witness-field-type">{ty}</span>
+                        <input
+                            type="text"
+                            spellcheck="false"
+                            placeholder=placeholder_text
+                            list=list
+                            prop:value=value
+                            on:input=move |ev| {
+                                let value = event_target_value(&ev);
+                                values.update(|v| {
+                                    v.insert(name.to_string(), value);
+                                });
+                            }
+                        />
+                        {move || error().map(|e| view! { <span class="witness-field-error">{e}</span> })}
+                    </div>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <details
+            class="manifest-editor"
+            prop:open=move || open.get()
+            on:toggle=move |ev| open.set(event_target::<web_sys::HtmlDetailsElement>(&ev).open())
+        >
+            <summary>{t(Msg::Templates)}</summary>
+            <div class="button-group">
+                <select
+                    aria-label=t(Msg::Template)
+                    on:change=move |ev| {
+                        if let Some(template) = Template::find(&event_target_value(&ev)) {
+                            chosen.set(template.name);
+                            set_errors.set(Vec::new());
+                        }
+                    }
+                >
+                    {TEMPLATES
+                        .iter()
+                        .map(|t| view! { <option value=t.name selected=move || chosen.get() == t.name>{t.name}</option> })
+                        .collect_view()}
+                </select>
+                <button on:click=create>{t(Msg::CreateProgram)}</button>
+            </div>
+            <p class="reminder-note">{move || template().description}</p>
+            <div class="witness-wizard">{fields}</div>
+        </details>
+    }
+}
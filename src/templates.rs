@@ -0,0 +1,235 @@
+//! Contract templates with typed placeholders.
+//!
+//! A template is SimplicityHL source with `{{name}}` placeholders. Each
+//! placeholder declares a SimplicityHL type, which picks the form input and
+//! checks the value the same way the witness editor does; public keys may
+//! also be given as vault `@label`s or `xpub.../0/5` derivation paths,
+//! expanded when compiling. Instantiating
+//! substitutes every placeholder and yields complete source.
+
+use std::collections::HashMap;
+
+use crate::bip32;
+use crate::witness_form::InputKind;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placeholder {
+    pub name: &'static str,
+    /// SimplicityHL type of the value, e.g. `Pubkey` or `Height`.
+    pub ty: &'static str,
+    pub label: &'static str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Template {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub placeholders: &'static [Placeholder],
+    pub source: &'static str,
+}
+
+pub const TEMPLATES: &[Template] = &[
+    Template {
+        name: "Empty program",
+        description: "A program that accepts any spend.",
+        placeholders: &[],
+        source: "mod param {}\nfn main() {}",
+    },
+    Template {
+        name: "Pay to public key",
+        description: "Spendable with a signature by one key.",
+        placeholders: &[Placeholder {
+            name: "owner_key",
+            ty: "Pubkey",
+            label: "Owner public key",
+        }],
+        source: r#"// Pay to public key
+fn main() {
+    let owner: Pubkey = {{owner_key}};
+    let msg: u256 = jet::sig_all_hash();
+    jet::bip_0340_verify((owner, msg), witness::OWNER_SIGNATURE);
+}"#,
+    },
+    Template {
+        name: "Hash lock",
+        description: "Spendable by whoever reveals the preimage of a SHA-256 hash.",
+        placeholders: &[Placeholder {
+            name: "hash",
+            ty: "u256",
+            label: "SHA-256 hash of the 32-byte secret",
+        }],
+        source: r#"// Hash lock
+fn main() {
+    let expected: u256 = {{hash}};
+    let ctx: Ctx8 = jet::sha_256_ctx_8_init();
+    let ctx: Ctx8 = jet::sha_256_ctx_8_add_32(ctx, witness::PREIMAGE);
+    let actual: u256 = jet::sha_256_ctx_8_finalize(ctx);
+    assert!(jet::eq_256(expected, actual));
+}"#,
+    },
+    Template {
+        name: "Timelocked refund",
+        description: "The recipient can spend at any time; the sender after a block height.",
+        placeholders: &[
+            Placeholder {
+                name: "recipient_key",
+                ty: "Pubkey",
+                label: "Recipient public key",
+            },
+            Placeholder {
+                name: "sender_key",
+                ty: "Pubkey",
+                label: "Sender public key",
+            },
+            Placeholder {
+                name: "timeout",
+                ty: "Height",
+                label: "Refund block height",
+            },
+        ],
+        source: r#"// Timelocked refund
+fn checksig(pk: Pubkey, sig: Signature) {
+    let msg: u256 = jet::sig_all_hash();
+    jet::bip_0340_verify((pk, msg), sig);
+}
+
+fn main() {
+    let recipient: Pubkey = {{recipient_key}};
+    let sender: Pubkey = {{sender_key}};
+    match witness::SPEND {
+        Left(sig: Signature) => checksig(recipient, sig),
+        Right(sig: Signature) => {
+            let timeout: Height = {{timeout}};
+            jet::check_lock_height(timeout);
+            checksig(sender, sig)
+        },
+    }
+}"#,
+    },
+    Template {
+        name: "2-of-3 multisig",
+        description: "Spendable with signatures by any two of three keys.",
+        placeholders: &[
+            Placeholder {
+                name: "key_1",
+                ty: "Pubkey",
+                label: "First public key",
+            },
+            Placeholder {
+                name: "key_2",
+                ty: "Pubkey",
+                label: "Second public key",
+            },
+            Placeholder {
+                name: "key_3",
+                ty: "Pubkey",
+                label: "Third public key",
+            },
+        ],
+        source: r#"// 2-of-3 multisig
+fn not(bit: bool) -> bool {
+    <u1>::into(jet::complement_1(<bool>::into(bit)))
+}
+
+fn checksig(pk: Pubkey, sig: Signature) {
+    let msg: u256 = jet::sig_all_hash();
+    jet::bip_0340_verify((pk, msg), sig);
+}
+
+fn checksig_add(counter: u8, pk: Pubkey, maybe_sig: Option<Signature>) -> u8 {
+    match maybe_sig {
+        Some(sig: Signature) => {
+            checksig(pk, sig);
+            let (carry, new_counter): (bool, u8) = jet::increment_8(counter);
+            assert!(not(carry));
+            new_counter
+        }
+        None => counter,
+    }
+}
+
+fn main() {
+    let pks: [Pubkey; 3] = [{{key_1}}, {{key_2}}, {{key_3}}];
+    let maybe_sigs: [Option<Signature>; 3] = witness::MAYBE_SIGS;
+    let [pk1, pk2, pk3]: [Pubkey; 3] = pks;
+    let [sig1, sig2, sig3]: [Option<Signature>; 3] = maybe_sigs;
+    let counter: u8 = checksig_add(0, pk1, sig1);
+    let counter: u8 = checksig_add(counter, pk2, sig2);
+    let counter: u8 = checksig_add(counter, pk3, sig3);
+    let threshold: u8 = 2;
+    assert!(jet::eq_8(counter, threshold));
+}"#,
+    },
+    Template {
+        name: "Output covenant",
+        description: "Anyone may spend, but only by paying a fixed amount to a fixed script.",
+        placeholders: &[
+            Placeholder {
+                name: "destination",
+                ty: "u256",
+                label: "SHA-256 of the destination's scriptPubKey",
+            },
+            Placeholder {
+                name: "amount",
+                ty: "u64",
+                label: "Least the destination is paid (sat)",
+            },
+        ],
+        source: r#"// Output covenant: output 0 pays the destination at least the amount
+fn main() {
+    let destination: u256 = {{destination}};
+    let amount: u64 = {{amount}};
+    let script: u256 = unwrap(jet::output_script_hash(0));
+    assert!(jet::eq_256(script, destination));
+    let (_, paid): (Asset1, Amount1) = unwrap(jet::output_amount(0));
+    let paid_value: u64 = unwrap_right::<(u1, u256)>(paid);
+    assert!(jet::le_64(amount, paid_value));
+}"#,
+    },
+];
+
+/// Check `value` for a placeholder of type `ty`.
+pub fn validate(ty: &str, value: &str) -> Result<(), String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err("Enter a value".to_string());
+    }
+    // Vault keys and address book entries stand in for any 32-byte value.
+    if value.starts_with('@') && InputKind::of(ty) == InputKind::Hex(32) {
+        return Ok(());
+    }
+    if ty == "Pubkey" && bip32::is_xpub_path(value) {
+        return Ok(());
+    }
+    InputKind::of(ty).validate(value)
+}
+
+impl Template {
+    pub fn find(name: &str) -> Option<&'static Template> {
+        TEMPLATES.iter().find(|t| t.name == name)
+    }
+
+    /// The template whose [`slug`](crate::routes::slug) is `slug`.
+    pub fn find_slug(slug: &str) -> Option<&'static Template> {
+        TEMPLATES.iter().find(|t| crate::routes::slug(t.name) == slug)
+    }
+
+    /// The source with every placeholder replaced by its value in `values`,
+    /// or the problem with each value that is missing or invalid.
+    pub fn instantiate(&self, values: &HashMap<String, String>) -> Result<String, Vec<(String, String)>> {
+        let mut errors = Vec::new();
+        let mut source = self.source.to_string();
+        for placeholder in self.placeholders {
+            let value = values.get(placeholder.name).map(|v| v.trim()).unwrap_or_default();
+            match validate(placeholder.ty, value) {
+                Ok(()) => source = source.replace(&format!("{{{{{}}}}}", placeholder.name), value),
+                Err(e) => errors.push((placeholder.name.to_string(), e)),
+            }
+        }
+        if errors.is_empty() {
+            Ok(source)
+        } else {
+            Err(errors)
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Contract test spec editor, results and coverage.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::api_types::{CoverageResult, TestRunResult};
+use crate::compiler;
+use crate::diagnostics::Diagnostic;
+use crate::i18n::{self, t, Msg};
+use crate::storage;
+
+const TESTS_KEY: &str = "tests";
+
+/// The saved test spec.
+pub fn load_tests() -> String {
+    storage::load(TESTS_KEY).unwrap_or_default()
+}
+
+pub fn save_tests(text: &str) {
+    storage::save(TESTS_KEY, text);
+}
+
+/// Append a case using `witness_json` to the spec `tests_json`.
+fn add_case(tests_json: &str, witness_json: &str) -> Result<String, String> {
+    let mut tests = if tests_json.trim().is_empty() {
+        Vec::new()
+    } else {
+        serde_json::from_str::<Vec<serde_json::Value>>(tests_json).map_err(|e| format!("Invalid tests JSON: {e}"))?
+    };
+    let witness = if witness_json.trim().is_empty() {
+        serde_json::json!({})
+    } else {
+        serde_json::from_str::<serde_json::Value>(witness_json).map_err(|e| format!("Invalid witness JSON: {e}"))?
+    };
+    tests.push(serde_json::json!({
+        "name": format!("case {}", tests.len() + 1),
+        "witness": witness,
+        "env": {},
+        "expect": "pass",
+    }));
+    serde_json::to_string_pretty(&tests).map_err(|e| e.to_string())
+}
+
+#[component]
+pub fn TestPanel(
+    #[prop(into)] code: Signal<String>,
+    #[prop(into)] witness: Signal<String>,
+    /// The test spec, saved on every edit.
+    tests: RwSignal<String>,
+    /// Receives a warning for every arm the tests never execute.
+    set_diagnostics: WriteSignal<Vec<Diagnostic>>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let (run, set_run) = signal::<Option<TestRunResult>>(None);
+    let (coverage, set_coverage) = signal::<Option<String>>(None);
+    let (error, set_error) = signal::<Option<String>>(None);
+    let (busy, set_busy) = signal(false);
+
+    let edit = move |text: String| {
+        save_tests(&text);
+        tests.set(text);
+    };
+
+    let add = move |_| match add_case(&tests.get_untracked(), &witness.get_untracked()) {
+        Ok(text) => {
+            set_error.set(None);
+            edit(text);
+        }
+        Err(e) => set_error.set(Some(e)),
+    };
+
+    let run_all = move |_| {
+        let code = code.get_untracked();
+        let tests = tests.get_untracked();
+        set_busy.set(true);
+        set_error.set(None);
+        spawn_local(async move {
+            let json = compiler::run_tests(&code, &tests).await;
+            match serde_json::from_str::<TestRunResult>(&json) {
+                Ok(result) => {
+                    set_error.set(result.error.clone());
+                    set_run.set(result.error.is_none().then_some(result));
+                }
+                Err(e) => set_error.set(Some(e.to_string())),
+            }
+            set_busy.set(false);
+        });
+    };
+
+    let measure_coverage = move |_| {
+        let code = code.get_untracked();
+        let tests = tests.get_untracked();
+        let lang = lang.get_untracked();
+        set_busy.set(true);
+        set_error.set(None);
+        spawn_local(async move {
+            let json = compiler::coverage(&code, &tests).await;
+            match serde_json::from_str::<CoverageResult>(&json) {
+                Ok(CoverageResult { report: Some(report), .. }) => {
+                    let arms = report.branches.len();
+                    let covered = report.branches.iter().filter(|b| b.hits > 0).count();
+                    let unknown = report.branches.iter().filter(|b| b.hits == 0 && b.unknown > 0).count();
+                    let jets = report.jets.iter().filter(|j| j.hits > 0).count();
+                    let counts = [covered, arms, jets, report.jets.len(), report.cases].map(|n| n.to_string());
+                    let counts: Vec<&str> = counts.iter().map(String::as_str).collect();
+                    let mut summary = i18n::format(i18n::text(lang, Msg::CoverageSummary), &counts);
+                    if unknown > 0 {
+                        summary.push_str(&i18n::format(i18n::text(lang, Msg::CoverageUnknown), &[&unknown.to_string()]));
+                    }
+                    set_coverage.set(Some(summary));
+                    set_diagnostics.set(report.diagnostics());
+                }
+                Ok(CoverageResult { error, .. }) => set_error.set(error),
+                Err(e) => set_error.set(Some(e.to_string())),
+            }
+            set_busy.set(false);
+        });
+    };
+
+    let results = move || {
+        run.get().map(|run| {
+            let summary = i18n::format(
+                i18n::text(lang.get(), Msg::PassedFailed),
+                &[&run.passed.to_string(), &run.failed.to_string()],
+            );
+            let rows = run
+                .results
+                .into_iter()
+                .map(|outcome| {
+                    let (class, label) = if outcome.passed {
+                        ("status-badge paid", Msg::Pass)
+                    } else {
+                        ("status-badge expired", Msg::Fail)
+                    };
+                    view! {
+                        <li class="signature-row">
+                            <span class=class>{t(label)}</span>
+                            <span>{outcome.name}</span>
+                            <span class="reminder-note">{outcome.error.unwrap_or_default()}</span>
+                        </li>
+                    }
+                })
+                .collect_view();
+            view! {
+                <p class="network-status">{summary}</p>
+                <ul class="signature-list">{rows}</ul>
+            }
+        })
+    };
+
+    view! {
+        <div class="test-panel">
+            <label>{t(Msg::ContractTests)}</label>
+            <textarea
+                spellcheck="false"
+                placeholder=r#"[{"name": "happy path", "witness": {...}, "env": {"lock_time": 0}, "expect": "pass"}]"#
+                prop:value=move || tests.get()
+                on:input=move |ev| edit(event_target_value(&ev))
+            />
+            <div class="button-group">
+                <button on:click=run_all disabled=move || busy.get() || tests.get().trim().is_empty()>
+                    {move || i18n::text(lang.get(), if busy.get() { Msg::RunningTests } else { Msg::RunTests })}
+                </button>
+                <button class="secondary" on:click=measure_coverage disabled=move || busy.get() || tests.get().trim().is_empty()>
+                    {t(Msg::Coverage)}
+                </button>
+                <button class="secondary" on:click=add>
+                    {t(Msg::AddCaseFromWitness)}
+                </button>
+            </div>
+            {move || error.get().map(|e| view! { <p class="tool-error">{e}</p> })}
+            {move || coverage.get().map(|summary| view! { <p class="network-status">{summary}</p> })}
+            {results}
+        </div>
+    }
+}
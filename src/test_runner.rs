@@ -0,0 +1,276 @@
+//! Unit tests for contracts, run on the Bit Machine.
+//!
+//! A test spec is a JSON list of cases. Each case gives a witness, optionally
+//! parameter arguments and a transaction environment, and whether the
+//! program should accept (`"pass"`, the default) or reject (`"fail"`) it:
+//!
+//! ```json
+//! [{"name": "alice spends", "witness": {...}, "env": {"lock_time": 1000}, "expect": "pass"}]
+//! ```
+//!
+//! Without a `tx_hex`, the environment is a one-input transaction spending
+//! `value` of Liquid testnet L-BTC, or of `asset`, from the contract's own
+//! address, with the given lock time and sequence, the issuance made by the
+//! input and the outputs it pays. A `mock` transaction (see [`crate::mock_tx`])
+//! gives every field instead. Signatures over `sig_all_hash` can only pass
+//! against a real `tx_hex` or a mock, since the synthetic transaction is not
+//! known in advance.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::elements::confidential::Nonce;
+use simplicityhl::simplicity::elements::encode::deserialize;
+use simplicityhl::simplicity::elements::{
+    LockTime, OutPoint, Script, Sequence, Transaction, TxIn, TxOut, TxOutWitness, Txid,
+};
+use simplicityhl::simplicity::hashes::Hash;
+use simplicityhl::simplicity::jet::elements::ElementsEnv;
+use simplicityhl::simplicity::jet::Elements;
+use simplicityhl::simplicity::{BitMachine, Cmr, RedeemNode};
+use simplicityhl::WitnessValues;
+
+use crate::address::{taproot_address, Network};
+use crate::assets::{self, NewIssuance};
+use crate::hex;
+use crate::limits::ExecLimits;
+use crate::mock_tx::MockTx;
+use crate::options::CompileOptions;
+use crate::sighash::{elements_env, SighashUtxo};
+use crate::taproot::TaprootConfig;
+use crate::trace::{self, Trace};
+
+const DEFAULT_VALUE: u64 = 100_000;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Expect {
+    #[default]
+    Pass,
+    Fail,
+}
+
+/// The transaction the program runs against.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct TestEnv {
+    #[serde(default)]
+    pub lock_time: Option<u32>,
+    #[serde(default)]
+    pub sequence: Option<u32>,
+    /// Value of the spent output, in satoshis.
+    #[serde(default)]
+    pub value: Option<u64>,
+    /// Asset id or commitment of the spent output; the policy asset by default.
+    #[serde(default)]
+    pub asset: Option<String>,
+    /// Issuance made by the spending input.
+    #[serde(default)]
+    pub issuance: Option<NewIssuance>,
+    /// Outputs of the synthetic transaction; by default one paying the
+    /// spent value and asset back.
+    #[serde(default)]
+    pub outputs: Vec<TestOutput>,
+    /// A transaction written out field by field; replaces the synthetic one.
+    #[serde(default)]
+    pub mock: Option<MockTx>,
+    /// A complete spending transaction, hex; replaces the synthetic one.
+    #[serde(default)]
+    pub tx_hex: Option<String>,
+    #[serde(default)]
+    pub input_index: Option<u32>,
+    /// Outputs spent by `tx_hex`, in input order.
+    #[serde(default)]
+    pub utxos: Vec<SighashUtxo>,
+}
+
+/// An output of the synthetic transaction.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct TestOutput {
+    /// Asset id or commitment; the spent output's asset by default.
+    #[serde(default)]
+    pub asset: Option<String>,
+    #[serde(default)]
+    pub value: u64,
+    /// Confidential amount commitment in hex; replaces `value`.
+    #[serde(default)]
+    pub value_commitment: Option<String>,
+    /// Hex; empty by default.
+    #[serde(default)]
+    pub script_pubkey: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TestCase {
+    pub name: String,
+    #[serde(default)]
+    pub witness: serde_json::Value,
+    /// Parameter values in `mod param { ... }` syntax; read from the code when absent.
+    #[serde(default)]
+    pub args: Option<String>,
+    #[serde(default)]
+    pub env: TestEnv,
+    #[serde(default)]
+    pub expect: Expect,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TestOutcome {
+    pub name: String,
+    pub passed: bool,
+    /// Why the program rejected the case, if it did.
+    pub error: Option<String>,
+}
+
+/// The synthetic transaction for `env`, with the output it spends.
+pub(crate) fn synthetic_tx(env: &TestEnv, cmr: Cmr) -> Result<(Transaction, SighashUtxo), String> {
+    let network = Network::LiquidTestnet;
+    let asset = env
+        .asset
+        .clone()
+        .unwrap_or_else(|| network.policy_asset().expect("testnet has a policy asset").to_string());
+    let value = env.value.unwrap_or(DEFAULT_VALUE);
+    let default_output = TestOutput {
+        asset: None,
+        value,
+        ..TestOutput::default()
+    };
+    let outputs = if env.outputs.is_empty() { std::slice::from_ref(&default_output) } else { &env.outputs };
+    let output = outputs
+        .iter()
+        .enumerate()
+        .map(|(index, output)| {
+            let error = |e: String| format!("Test output {index}: {e}");
+            Ok(TxOut {
+                asset: assets::parse_asset(output.asset.as_deref().unwrap_or(&asset)).map_err(error)?,
+                value: assets::parse_value(output.value, output.value_commitment.as_deref()).map_err(error)?,
+                nonce: Nonce::Null,
+                script_pubkey: match &output.script_pubkey {
+                    Some(script) => Script::from(hex::decode(script).map_err(error)?),
+                    None => Script::new(),
+                },
+                witness: TxOutWitness::default(),
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let tx = Transaction {
+        version: 2,
+        lock_time: LockTime::from_consensus(env.lock_time.unwrap_or(0)),
+        input: vec![TxIn {
+            previous_output: OutPoint::new(Txid::all_zeros(), 0),
+            is_pegin: false,
+            script_sig: Script::new(),
+            sequence: Sequence(env.sequence.unwrap_or(0xffff_fffe)),
+            asset_issuance: match &env.issuance {
+                Some(issuance) => issuance.to_issuance()?,
+                None => Default::default(),
+            },
+            witness: Default::default(),
+        }],
+        output,
+    };
+    let utxo = SighashUtxo {
+        address: Some(taproot_address(cmr, network).to_string()),
+        script_pubkey: None,
+        asset,
+        value,
+        value_commitment: None,
+    };
+    Ok((tx, utxo))
+}
+
+/// Why a case did not run to acceptance.
+enum Failure {
+    /// The case could not be set up: the program or spec is broken.
+    Setup(String),
+    /// The program rejected the witness or environment.
+    Rejected(String),
+}
+
+/// The program of `code` satisfied by `case`, and the environment it runs
+/// in. Programs over `limits` are refused.
+fn prepare(
+    code: &str,
+    case: &TestCase,
+    limits: &ExecLimits,
+) -> Result<(Arc<RedeemNode<Elements>>, ElementsEnv<Arc<Transaction>>), Failure> {
+    let args_source = case.args.as_deref().filter(|a| !a.trim().is_empty()).unwrap_or(code);
+    let compiled = CompileOptions::default().compile_source(code, args_source).map_err(Failure::Setup)?;
+    let cmr = compiled.commit().cmr();
+
+    let witness = match &case.witness {
+        serde_json::Value::Null => serde_json::json!({}),
+        witness => witness.clone(),
+    };
+    let witness = serde_json::from_value::<WitnessValues>(witness)
+        .map_err(|e| Failure::Setup(format!("Invalid JSON witness data: {}", e)))?;
+
+    // Tests spend from the single-leaf tree their synthetic outputs pay to.
+    let tree = TaprootConfig::default();
+    let env = match (&case.env.tx_hex, &case.env.mock) {
+        (Some(tx_hex), _) => hex::decode(tx_hex)
+            .and_then(|bytes| deserialize::<Transaction>(&bytes).map_err(|e| format!("Invalid transaction: {e}")))
+            .and_then(|tx| elements_env(tx, case.env.input_index.unwrap_or(0), &case.env.utxos, cmr, &tree)),
+        (None, Some(mock)) => mock
+            .build(cmr)
+            .and_then(|(tx, utxos)| elements_env(tx, mock.input_index, &utxos, cmr, &tree)),
+        (None, None) => synthetic_tx(&case.env, cmr).and_then(|(tx, utxo)| elements_env(tx, 0, &[utxo], cmr, &tree)),
+    }
+    .map_err(Failure::Setup)?;
+
+    let satisfied = compiled
+        .satisfy(witness)
+        .map_err(|e| Failure::Rejected(format!("Witness error: {}", e)))?;
+    let program = satisfied.redeem();
+    limits.check(program).map_err(Failure::Setup)?;
+    Ok((program.clone(), env))
+}
+
+/// Run one case against `code`. Programs over `limits` are not run.
+fn run_case(code: &str, case: &TestCase, limits: &ExecLimits) -> Result<(), Failure> {
+    let (program, env) = prepare(code, case, limits)?;
+    let mut machine =
+        BitMachine::for_program(&program).map_err(|e| Failure::Rejected(format!("Execution error: {e}")))?;
+    machine
+        .exec(&program, &env)
+        .map(|_| ())
+        .map_err(|e| Failure::Rejected(format!("Execution error: {e}")))
+}
+
+/// The trace of `code` run on `case`, or why it could not be run.
+pub(crate) fn trace_case(code: &str, case: &TestCase) -> Result<Trace, String> {
+    let (program, env) = prepare(code, case, &ExecLimits::default()).map_err(|failure| match failure {
+        Failure::Setup(e) | Failure::Rejected(e) => e,
+    })?;
+    trace::run(&program, &env)
+}
+
+/// Whether `code` runs to completion on `case`, whatever the case expects.
+pub fn accepts(code: &str, case: &TestCase) -> bool {
+    run_case(code, case, &ExecLimits::default()).is_ok()
+}
+
+/// Run every case of `tests` against `code`. Cases that cannot be set up
+/// fail whatever they expect.
+pub fn run(code: &str, tests: &[TestCase]) -> Vec<TestOutcome> {
+    run_with_limits(code, tests, &ExecLimits::default())
+}
+
+/// [`run`] under `limits`. A case whose program is over budget fails
+/// whatever it expects.
+pub fn run_with_limits(code: &str, tests: &[TestCase], limits: &ExecLimits) -> Vec<TestOutcome> {
+    tests
+        .iter()
+        .map(|case| {
+            let (passed, error) = match run_case(code, case, limits) {
+                Ok(()) => (case.expect == Expect::Pass, None),
+                Err(Failure::Rejected(e)) => (case.expect == Expect::Fail, Some(e)),
+                Err(Failure::Setup(e)) => (false, Some(e)),
+            };
+            TestOutcome {
+                name: case.name.clone(),
+                passed,
+                error,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,84 @@
+//! Timestamps attached to compile results and exported artifacts.
+//!
+//! The ISO-8601 UTC string is the canonical form that travels with an
+//! artifact; the locale rendering is only for display. Sequence numbers are
+//! persisted in localStorage so they keep increasing across reloads and tabs,
+//! which lets several parties order artifacts even when clocks disagree.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsValue;
+
+use crate::storage;
+
+const SEQUENCE_KEY: &str = "artifact.sequence";
+
+/// Highest sequence number handed out by this instance. Used when storage is
+/// unavailable, and as a floor when another tab has reset it.
+static LAST_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stamp {
+    /// Monotonic per-browser counter, starting at 1.
+    pub sequence: u64,
+    /// ISO-8601 UTC time, e.g. `2024-05-01T12:34:56.789Z`.
+    pub utc: String,
+    /// Milliseconds since the Unix epoch.
+    pub unix_ms: u64,
+}
+
+fn next_sequence() -> u64 {
+    let stored = storage::load(SEQUENCE_KEY)
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(0);
+    let next = LAST_SEQUENCE.load(Ordering::Relaxed).max(stored) + 1;
+    LAST_SEQUENCE.store(next, Ordering::Relaxed);
+    storage::save(SEQUENCE_KEY, &next.to_string());
+    next
+}
+
+fn user_locale() -> String {
+    web_sys::window()
+        .and_then(|w| w.navigator().language())
+        .unwrap_or_else(|| "en-US".to_string())
+}
+
+fn user_time_zone() -> Option<String> {
+    let format = js_sys::Intl::DateTimeFormat::new(&js_sys::Array::new(), &js_sys::Object::new());
+    js_sys::Reflect::get(&format.resolved_options(), &JsValue::from_str("timeZone"))
+        .ok()
+        .and_then(|v| v.as_string())
+}
+
+/// Render a Unix time in milliseconds in the browser's locale and time zone.
+pub fn format_local(unix_ms: u64) -> String {
+    let date = js_sys::Date::new(&JsValue::from_f64(unix_ms as f64));
+    let local = String::from(date.to_locale_string(&user_locale(), &JsValue::UNDEFINED));
+    match user_time_zone() {
+        Some(zone) => format!("{local} ({zone})"),
+        None => local,
+    }
+}
+
+impl Stamp {
+    /// Take the current time and the next sequence number.
+    pub fn now() -> Self {
+        let date = js_sys::Date::new_0();
+        Stamp {
+            sequence: next_sequence(),
+            utc: String::from(date.to_iso_string()),
+            unix_ms: date.get_time() as u64,
+        }
+    }
+
+    /// Render the time in the browser's locale and time zone.
+    pub fn to_locale_string(&self) -> String {
+        format_local(self.unix_ms)
+    }
+
+    /// One-line header for plain-text exports.
+    pub fn to_text(&self) -> String {
+        format!("Generated: {} (sequence #{})", self.utc, self.sequence)
+    }
+}
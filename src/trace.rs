@@ -0,0 +1,216 @@
+//! Execution traces of a program run on a mock transaction.
+//!
+//! The Bit Machine reports three kinds of event while it runs: the branch
+//! each `case` takes, every jet call with its input and output frames, and
+//! every `dbg!` value. [`record`] runs a program the way
+//! [`mock_tx::run`](crate::mock_tx::run) does and keeps those events, in
+//! order, with the CMR of the node each came from, so a run can be checked
+//! step by step against the Bit Machine's specification. Combinators that
+//! only move data are not reported by the machine and do not appear.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::bit_machine::ExecTracker;
+use simplicityhl::simplicity::dag::{DagLike, MaxSharing};
+use simplicityhl::simplicity::elements::Transaction;
+use simplicityhl::simplicity::ffi::ffi::UWORD;
+use simplicityhl::simplicity::jet::elements::ElementsEnv;
+use simplicityhl::simplicity::jet::{Elements, Jet};
+use simplicityhl::simplicity::node::{Inner, Redeem};
+use simplicityhl::simplicity::{BitMachine, Cmr, Ihr, RedeemNode};
+use simplicityhl::WitnessValues;
+
+use crate::limits::ExecLimits;
+use crate::mock_tx::MockTx;
+use crate::options::CompileOptions;
+use crate::sighash::elements_env;
+use crate::taproot::TaprootConfig;
+
+/// Steps kept before the trace is cut short, so a long loop cannot exhaust
+/// the page's memory.
+pub const MAX_STEPS: usize = 100_000;
+
+/// Bits in one word of a jet's frame buffers.
+const WORD_BITS: usize = 8 * std::mem::size_of::<UWORD>();
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TraceStep {
+    pub index: usize,
+    /// CMR of the node that ran: the `case` for branches, the jet for jet
+    /// calls and the debug node for `dbg!`.
+    pub cmr: String,
+    /// `case-left`, `case-right`, `jet` or `dbg`.
+    pub operation: String,
+    /// The jet's name, or the value passed to `dbg!`.
+    #[serde(default)]
+    pub detail: Option<String>,
+    /// Size of the jet's read frame in bits, rounded up to whole words.
+    #[serde(default)]
+    pub read_frame_bits: Option<usize>,
+    /// Size of the jet's write frame in bits, rounded up to whole words.
+    #[serde(default)]
+    pub write_frame_bits: Option<usize>,
+    /// Whether the jet succeeded; a failed jet ends the run.
+    #[serde(default)]
+    pub success: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Trace {
+    /// CMR of the program.
+    pub cmr: String,
+    pub accepted: bool,
+    /// Why the program rejected the transaction.
+    pub error: Option<String>,
+    pub steps: Vec<TraceStep>,
+    /// Whether steps after [`MAX_STEPS`] were dropped.
+    pub truncated: bool,
+}
+
+/// Collects steps as the Bit Machine reports them.
+struct Recorder {
+    /// CMRs of the program's branching nodes by IHR, which is all the
+    /// machine reports for a branch.
+    branches: HashMap<Ihr, Cmr>,
+    steps: Vec<TraceStep>,
+    truncated: bool,
+}
+
+impl Recorder {
+    fn push(&mut self, cmr: String, operation: &str, detail: Option<String>) -> Option<&mut TraceStep> {
+        if self.steps.len() >= MAX_STEPS {
+            self.truncated = true;
+            return None;
+        }
+        self.steps.push(TraceStep {
+            index: self.steps.len(),
+            cmr,
+            operation: operation.to_string(),
+            detail,
+            read_frame_bits: None,
+            write_frame_bits: None,
+            success: None,
+        });
+        self.steps.last_mut()
+    }
+
+    fn branch(&mut self, ihr: Ihr, operation: &str) {
+        let cmr = self.branches.get(&ihr).map(Cmr::to_string).unwrap_or_default();
+        self.push(cmr, operation, None);
+    }
+}
+
+impl ExecTracker<Elements> for Recorder {
+    fn track_left(&mut self, ihr: Ihr) {
+        self.branch(ihr, "case-left");
+    }
+
+    fn track_right(&mut self, ihr: Ihr) {
+        self.branch(ihr, "case-right");
+    }
+
+    fn track_jet_call(&mut self, jet: &Elements, input_buffer: &[UWORD], output_buffer: &[UWORD], success: bool) {
+        if let Some(step) = self.push(jet.cmr().to_string(), "jet", Some(jet.to_string())) {
+            step.read_frame_bits = Some(input_buffer.len() * WORD_BITS);
+            step.write_frame_bits = Some(output_buffer.len() * WORD_BITS);
+            step.success = Some(success);
+        }
+    }
+
+    fn track_dbg_call(&mut self, cmr: &Cmr, value: simplicityhl::simplicity::Value) {
+        self.push(cmr.to_string(), "dbg", Some(value.to_string()));
+    }
+
+    fn is_track_debug_enabled(&self) -> bool {
+        true
+    }
+}
+
+/// Compile `code`, satisfy it with `witness_json` and run it as the spender
+/// of `mock`'s selected input, recording every step. Programs over `limits`
+/// are not run.
+pub fn record(code: &str, witness_json: &str, mock: &MockTx, limits: &ExecLimits) -> Result<Trace, String> {
+    let compiled = CompileOptions::default().compile_source(code, code)?;
+    let witness = if witness_json.trim().is_empty() {
+        WitnessValues::default()
+    } else {
+        serde_json::from_str::<WitnessValues>(witness_json).map_err(|e| format!("Invalid JSON witness data: {}", e))?
+    };
+    let cmr = compiled.commit().cmr();
+    let (tx, utxos) = mock.build(cmr)?;
+    let env = elements_env(tx, mock.input_index, &utxos, cmr, &TaprootConfig::default())?;
+    let satisfied = compiled.satisfy(witness).map_err(|e| format!("Witness error: {}", e))?;
+    let program = satisfied.redeem();
+    limits.check(program)?;
+    run(program, &env)
+}
+
+/// Run `program` in `env`, recording every step.
+pub(crate) fn run(program: &RedeemNode<Elements>, env: &ElementsEnv<Arc<Transaction>>) -> Result<Trace, String> {
+    let branches = program
+        .post_order_iter::<MaxSharing<Redeem<Elements>>>()
+        .filter(|item| matches!(item.node.inner(), Inner::Case(..) | Inner::AssertL(..) | Inner::AssertR(..)))
+        .map(|item| (item.node.ihr(), item.node.cmr()))
+        .collect();
+    let mut recorder = Recorder {
+        branches,
+        steps: Vec::new(),
+        truncated: false,
+    };
+    let mut machine = BitMachine::for_program(program).map_err(|e| format!("Execution error: {e}"))?;
+    let error = machine
+        .exec_with_tracker(program, env, &mut recorder)
+        .err()
+        .map(|e| format!("Execution error: {e}"));
+    Ok(Trace {
+        cmr: program.cmr().to_string(),
+        accepted: error.is_none(),
+        error,
+        steps: recorder.steps,
+        truncated: recorder.truncated,
+    })
+}
+
+impl Trace {
+    /// The step a rejected run failed at: its failed jet, or else the last
+    /// step recorded, unless recording stopped before the end.
+    pub fn failed_step(&self) -> Option<&TraceStep> {
+        if self.accepted {
+            return None;
+        }
+        let failed = self.steps.iter().rev().find(|step| step.success == Some(false));
+        failed.or(if self.truncated { None } else { self.steps.last() })
+    }
+
+    /// The trace as text, one step per line.
+    pub fn to_text(&self) -> String {
+        let mut out = format!("Trace of {}\n", self.cmr);
+        match &self.error {
+            None => out.push_str("Result: accepted\n\n"),
+            Some(e) => {
+                let _ = writeln!(out, "Result: rejected: {e}\n");
+            }
+        }
+        for step in &self.steps {
+            let short = step.cmr.get(..16).unwrap_or(&step.cmr);
+            let _ = write!(out, "{:>6}  {:<10}  {short}…", step.index, step.operation);
+            if let Some(detail) = &step.detail {
+                let _ = write!(out, "  {detail}");
+            }
+            if let (Some(read), Some(write)) = (step.read_frame_bits, step.write_frame_bits) {
+                let _ = write!(out, "  read {read} bits, write {write} bits");
+            }
+            if step.success == Some(false) {
+                out.push_str("  FAILED");
+            }
+            out.push('\n');
+        }
+        if self.truncated {
+            let _ = writeln!(out, "… stopped recording after {MAX_STEPS} steps");
+        }
+        out
+    }
+}
@@ -0,0 +1,393 @@
+//! Ranked root-cause hints for a failed spend attempt.
+//!
+//! The program is first run again with the witness on a mock transaction
+//! (see [`crate::mock_tx`]). When it fails there too, the trace names the jet
+//! it stopped at, and the source map and the arms the run took narrow that
+//! down to a line; each witness signature is also checked against the mock
+//! transaction's sighash. The mock cannot reproduce the chain, so the source
+//! and witness are read as well for the usual ways a spend fails: witness
+//! values that are missing or malformed, timelocks the chain has not
+//! reached, signatures made over a stale sighash, and asserts on the path
+//! the witness selects. Each finding gets a score and the report lists the
+//! most likely first.
+
+use serde::{Deserialize, Serialize};
+
+use crate::address::parse_cmr;
+use crate::coverage;
+use crate::limits::ExecLimits;
+use crate::mock_tx::{self, MockTx};
+use crate::sanity::{qualified_names, strip_comment};
+use crate::sigverify;
+use crate::source_map;
+use crate::trace::{self, Trace};
+
+/// What is known about the chain the spend would land on.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct TriageEnv {
+    /// Current tip height, if an explorer could be reached.
+    #[serde(default)]
+    pub block_height: Option<u32>,
+    /// The transaction to run the program on again; [`MockTx::default`] if absent.
+    #[serde(default)]
+    pub mock: Option<MockTx>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Cause {
+    /// 0–100; higher is more likely.
+    pub score: u8,
+    pub title: String,
+    pub detail: String,
+    pub line: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct TriageReport {
+    pub causes: Vec<Cause>,
+}
+
+impl TriageReport {
+    fn push(&mut self, score: u8, line: Option<usize>, title: impl Into<String>, detail: impl Into<String>) {
+        self.causes.push(Cause {
+            score,
+            title: title.into(),
+            detail: detail.into(),
+            line,
+        });
+    }
+}
+
+const TIMELOCK_JETS: [(&str, &str); 4] = [
+    ("check_lock_height", "absolute block height"),
+    ("check_lock_time", "absolute median time"),
+    ("check_lock_distance", "relative block distance"),
+    ("check_lock_duration", "relative duration"),
+];
+
+/// The `0x` hex digits in a witness value, e.g. inside `Left(0x..)`.
+fn hex_payload(value: &str) -> Option<&str> {
+    let start = value.find("0x")? + 2;
+    let tail = &value[start..];
+    let end = tail.find(|c: char| !c.is_ascii_hexdigit()).unwrap_or(tail.len());
+    Some(&tail[..end])
+}
+
+/// The numeric value of `argument`, either a literal or a `let` bound to one
+/// earlier in the source.
+pub(crate) fn resolve_number(lines: &[&str], before: usize, argument: &str) -> Option<u64> {
+    let argument = argument.trim();
+    if let Ok(n) = argument.replace('_', "").parse() {
+        return Some(n);
+    }
+    lines[..before].iter().rev().find_map(|line| {
+        let rest = strip_comment(line).trim().strip_prefix("let ")?;
+        let (binding, value) = rest.split_once('=')?;
+        let name = binding.split(':').next()?.trim();
+        (name == argument)
+            .then(|| value.trim().trim_end_matches(';').replace('_', "").parse().ok())
+            .flatten()
+    })
+}
+
+/// What a jet that failed a run was checking.
+fn failed_check(jet: &str) -> String {
+    match jet {
+        "verify" => "Failed assertion".to_string(),
+        "bip_0340_verify" => "Signature check failed".to_string(),
+        _ if TIMELOCK_JETS.iter().any(|(name, _)| *name == jet) => "Timelock not met".to_string(),
+        _ => format!("`jet::{jet}` failed"),
+    }
+}
+
+/// Report where the rejected run in `trace` stopped. Whether a line was found.
+fn locate_failure(report: &mut TriageReport, code: &str, trace: &Trace) -> bool {
+    let error = trace.error.clone().unwrap_or_default();
+    let Some(step) = trace.failed_step().filter(|step| step.operation == "jet") else {
+        report.push(
+            70,
+            None,
+            "The program rejects the mock transaction",
+            format!("On the mock transaction, with this witness, the program fails: {error}"),
+        );
+        return false;
+    };
+    let jet = step.detail.clone().unwrap_or_default();
+    // A jet called in several places maps to all of them. Of the calls the
+    // run reached, in the order it reached them, the failed one is the call
+    // after as many as the trace ran before it.
+    let ranges = source_map::build(code)
+        .map(|map| map.ranges_of(&step.cmr).to_vec())
+        .unwrap_or_default();
+    let calls: Vec<(usize, bool)> = coverage::reached_lines(code, trace)
+        .into_iter()
+        .flat_map(|(line, certain)| {
+            let on_line = ranges.iter().filter(|range| range.line == line).count();
+            std::iter::repeat((line, certain)).take(on_line)
+        })
+        .collect();
+    let earlier = trace.steps[..step.index].iter().filter(|s| s.cmr == step.cmr).count();
+    let mut lines: Vec<usize> = match calls.get(earlier) {
+        Some(&(line, true)) if calls[..earlier].iter().all(|&(_, certain)| certain) => vec![line],
+        _ => calls.iter().map(|&(line, _)| line).collect(),
+    };
+    lines.sort_unstable();
+    lines.dedup();
+    let (score, place) = match lines.as_slice() {
+        [] => (70, String::new()),
+        [line] => (95, format!(" on line {line}")),
+        _ => {
+            let lines: Vec<String> = lines.iter().map(usize::to_string).collect();
+            (80, format!(" on one of lines {}", lines.join(", ")))
+        }
+    };
+    report.push(
+        score,
+        lines.first().copied(),
+        failed_check(&jet),
+        format!("On the mock transaction, with this witness, the program stops at `jet::{jet}`{place}: {error}"),
+    );
+    !lines.is_empty()
+}
+
+/// Check each witness signature against the sighash of the mock transaction.
+fn check_signatures(report: &mut TriageReport, code: &str, witness_json: &str, trace: &Trace, mock: &MockTx) {
+    let Ok(sighash) = parse_cmr(&trace.cmr).and_then(|cmr| mock_tx::sig_all_hash(mock, cmr)) else {
+        return;
+    };
+    let Ok(checks) = sigverify::verify_witness(code, witness_json, &sighash) else {
+        return;
+    };
+    for check in checks.into_iter().filter(|check| check.valid_for.is_none()) {
+        let line = code
+            .lines()
+            .position(|line| qualified_names(strip_comment(line), "witness::").contains(&check.name.as_str()))
+            .map(|index| index + 1);
+        report.push(
+            65,
+            line,
+            format!("Signature in `{}` does not verify", check.name),
+            format!(
+                "It is not a BIP-340 signature by any key in the program over the mock transaction's sighash \
+                 {sighash}. A signature made for another transaction fails the same way."
+            ),
+        );
+    }
+}
+
+/// Triage a failure of `code` with `witness_json`, given the error it produced.
+pub fn triage(code: &str, witness_json: &str, error: &str, env: TriageEnv) -> TriageReport {
+    let mut report = TriageReport::default();
+    let witness = serde_json::from_str::<serde_json::Value>(witness_json).unwrap_or_default();
+    let lines: Vec<&str> = code.lines().collect();
+
+    let compiles = !["Key derivation error", "Parse error", "Compilation error"]
+        .iter()
+        .any(|prefix| error.starts_with(prefix));
+    if !compiles {
+        report.push(
+            100,
+            None,
+            "The program does not compile",
+            "Fix the compiler error first; the witness is never looked at until the program compiles.",
+        );
+    }
+
+    // The run on the mock transaction, where the witness fits the program.
+    let mock = env.mock.clone().unwrap_or_default();
+    let mut located = false;
+    if let Some(trace) = compiles
+        .then(|| trace::record(code, witness_json, &mock, &ExecLimits::default()).ok())
+        .flatten()
+    {
+        if trace.accepted {
+            report.push(
+                45,
+                None,
+                "The program accepts the mock transaction",
+                "On the mock transaction, with this witness, the program succeeds. Look for what differs on \
+                 chain: lock time, sequences, inputs and outputs.",
+            );
+        } else {
+            located = locate_failure(&mut report, code, &trace);
+        }
+        if code.contains("sig_all_hash") {
+            check_signatures(&mut report, code, witness_json, &trace, &mock);
+        }
+    }
+    if error.starts_with("Invalid JSON witness data") {
+        report.push(100, None, "Witness file is not valid JSON", error.to_string());
+    }
+
+    // Witness names used in the program against the ones supplied.
+    let mut used = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        for name in qualified_names(strip_comment(line), "witness::") {
+            if !used.iter().any(|(n, _)| *n == name) {
+                used.push((name, index + 1));
+            }
+        }
+    }
+    for (name, line) in &used {
+        match witness.get(*name) {
+            None => report.push(
+                90,
+                Some(*line),
+                format!("Missing witness value `{name}`"),
+                "The program reads this witness but the witness data does not define it.",
+            ),
+            Some(entry) => {
+                let value = entry.get("value").and_then(|v| v.as_str()).unwrap_or_default();
+                let ty = entry.get("type").and_then(|v| v.as_str()).unwrap_or_default();
+                let hex_len = hex_payload(value).map_or(0, str::len);
+                if ty.contains("Signature") && hex_len != 128 {
+                    report.push(
+                        80,
+                        Some(*line),
+                        format!("Malformed signature in `{name}`"),
+                        format!("A BIP-340 signature is 64 bytes (128 hex digits); this value has {hex_len}."),
+                    );
+                } else if (ty == "Pubkey" || name.ends_with("PUBLIC_KEY") || name.ends_with("_PK")) && hex_len != 64 {
+                    report.push(
+                        70,
+                        Some(*line),
+                        format!("Malformed public key in `{name}`"),
+                        format!("An x-only public key is 32 bytes (64 hex digits); this value has {hex_len}."),
+                    );
+                }
+            }
+        }
+    }
+    if let Some(supplied) = witness.as_object() {
+        for name in supplied.keys() {
+            if !used.iter().any(|(n, _)| n == name) {
+                report.push(
+                    30,
+                    None,
+                    format!("Unused witness value `{name}`"),
+                    "The program never reads this name; check for a typo against the `witness::` names in the source.",
+                );
+            }
+        }
+    }
+
+    // Timelocks, checked against the tip when it is known.
+    for (index, line) in lines.iter().enumerate() {
+        let line = strip_comment(line);
+        for (jet, kind) in TIMELOCK_JETS {
+            let Some(at) = line.find(&format!("{jet}(")) else {
+                continue;
+            };
+            let argument = line[at + jet.len() + 1..].split(')').next().unwrap_or_default();
+            let value = resolve_number(&lines, index, argument);
+            let required = value.map_or_else(|| format!("`{}`", argument.trim()), |v| v.to_string());
+            match (jet, value, env.block_height) {
+                ("check_lock_height", Some(v), Some(tip)) if v > u64::from(tip) => report.push(
+                    85,
+                    Some(index + 1),
+                    "Height lock not reached",
+                    format!("Requires block height {v}; the chain tip is {tip}, {} block(s) to go.", v - u64::from(tip)),
+                ),
+                ("check_lock_height", Some(_), Some(_)) => {}
+                _ => report.push(
+                    50,
+                    Some(index + 1),
+                    format!("Timelock: {kind} {required}"),
+                    if jet.contains("distance") || jet.contains("duration") {
+                        "The input's sequence must encode at least this lock, and the funding output must be that old."
+                    } else {
+                        "The spending transaction's lock time must be at least this value and final on chain."
+                    },
+                ),
+            }
+        }
+    }
+
+    if code.contains("sig_all_hash") {
+        let line = lines.iter().position(|l| l.contains("sig_all_hash")).map(|i| i + 1);
+        report.push(
+            60,
+            line,
+            "Signature over a different sighash",
+            "`sig_all_hash` commits to the whole spending transaction. A signature made before the \
+             inputs, outputs, fee or lock time were final does not verify.",
+        );
+    }
+
+    // The arm each `match witness::NAME` takes; problems elsewhere cannot be the cause.
+    for (index, line) in lines.iter().enumerate() {
+        let line = strip_comment(line);
+        let Some(rest) = line.trim().strip_prefix("match witness::") else {
+            continue;
+        };
+        let name = rest.trim_end_matches('{').trim();
+        let Some(value) = witness.get(name).and_then(|e| e.get("value")).and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let arm = if value.starts_with("Left(") { "Left" } else if value.starts_with("Right(") { "Right" } else { continue };
+        let arm_line = lines[index + 1..]
+            .iter()
+            .position(|l| l.trim_start().starts_with(&format!("{arm}(")))
+            .map(|offset| index + offset + 2);
+        report.push(
+            40,
+            arm_line,
+            format!("Witness `{name}` takes the {arm} arm"),
+            "Only checks on this arm run. Look there for the failing assert, lock or signature.",
+        );
+    }
+
+    let assert_lines: Vec<String> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| {
+            let l = strip_comment(l);
+            l.contains("assert!(") || l.contains("unwrap(")
+        })
+        .map(|(i, _)| (i + 1).to_string())
+        .collect();
+    if !assert_lines.is_empty() && !located {
+        report.push(
+            35,
+            assert_lines.first().and_then(|l| l.parse().ok()),
+            "Failed assertion",
+            format!("`assert!` or `unwrap` on line(s) {} aborts the program when its condition does not hold.", assert_lines.join(", ")),
+        );
+    }
+
+    report.causes.sort_by(|a, b| b.score.cmp(&a.score));
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LASTWILL: &str = include_str!("../contract/lastwill.simf");
+    const LASTWILL_WITNESS: &str = include_str!("../contract/lastwill.wit");
+
+    fn top_cause(report: &TriageReport) -> (&str, Option<usize>, u8) {
+        let cause = &report.causes[0];
+        (cause.title.as_str(), cause.line, cause.score)
+    }
+
+    #[test]
+    fn failed_assert_is_located_by_running_again() {
+        let code = "fn main() {\n    let x: u32 = witness::X;\n    assert!(jet::lt_32(x, 100));\n    assert!(jet::eq_32(x, 7));\n}";
+        let witness = |x: u32| format!(r#"{{"X": {{"value": "{x}", "type": "u32"}}}}"#);
+        let report = triage(code, &witness(8), "Execution error", TriageEnv::default());
+        assert_eq!(top_cause(&report), ("Failed assertion", Some(4), 95));
+        let report = triage(code, &witness(200), "Execution error", TriageEnv::default());
+        assert_eq!(top_cause(&report), ("Failed assertion", Some(3), 95));
+        assert!(report.causes.iter().all(|cause| cause.score != 35));
+    }
+
+    #[test]
+    fn lastwill_stops_at_the_inheritance_timelock() {
+        let report = triage(LASTWILL, LASTWILL_WITNESS, "Execution error", TriageEnv::default());
+        let line = LASTWILL.lines().position(|line| line.contains("jet::check_lock_distance")).unwrap() + 1;
+        assert_eq!(top_cause(&report), ("Timelock not met", Some(line), 95));
+        // Made for another transaction than the default mock.
+        assert!(report.causes.iter().any(|cause| cause.title == "Signature in `INHERIT_OR_NOT` does not verify"));
+    }
+}
@@ -0,0 +1,42 @@
+//! Ranked failure causes shown under the compile error.
+
+use leptos::prelude::*;
+
+use crate::i18n::{self, t, Msg};
+use crate::triage::TriageReport;
+
+#[component]
+pub fn TriagePanel(#[prop(into)] report: Signal<Option<TriageReport>>) -> impl IntoView {
+    let lang = i18n::use_lang();
+    move || {
+        let report = report.get().filter(|r| !r.causes.is_empty())?;
+        let lang = lang.get();
+        let items = report
+            .causes
+            .into_iter()
+            .map(|cause| {
+                let class = if cause.score >= 70 { "triage-cause likely" } else { "triage-cause" };
+                let location = cause
+                    .line
+                    .map(|line| i18n::format(i18n::text(lang, Msg::OnLine), &[&line.to_string()]))
+                    .unwrap_or_default();
+                view! {
+                    <li class=class>
+                        <strong>{cause.title}</strong>
+                        {location}
+                        <span class="triage-score">
+                            {i18n::format(i18n::text(lang, Msg::TriageScore), &[&cause.score.to_string()])}
+                        </span>
+                        <div class="triage-detail">{cause.detail}</div>
+                    </li>
+                }
+            })
+            .collect_view();
+        Some(view! {
+            <div class="triage">
+                <strong>{t(Msg::LikelyCauses)}</strong>
+                <ol>{items}</ol>
+            </div>
+        })
+    }
+}
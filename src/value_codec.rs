@@ -0,0 +1,79 @@
+//! Convert values between SimplicityHL literals and their bit encoding.
+//!
+//! Witness values travel in the compact encoding: the bits of the value
+//! with nothing added for alignment, most significant first. Sum types take
+//! one bit for the branch, then the bits of that branch only. The encoded
+//! form here pads the bits with zeros to whole bytes and says how many of
+//! them count.
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::BitIter;
+use simplicityhl::types::StructuralType;
+use simplicityhl::value::{StructuralValue, Value};
+use simplicityhl::WitnessValues;
+
+use crate::hex;
+use crate::repl::resolve;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Encoded {
+    /// Number of meaningful bits.
+    pub bits: usize,
+    /// The bits padded with zeros to whole bytes, as hex.
+    pub hex: String,
+    /// The bits as `0`s and `1`s.
+    pub binary: String,
+}
+
+/// The literal text of `json_value`: the contents of a JSON string, or the
+/// JSON itself for numbers and booleans. Anything else is taken verbatim.
+fn literal(json_value: &str) -> String {
+    match serde_json::from_str::<serde_json::Value>(json_value) {
+        Ok(serde_json::Value::String(text)) => text,
+        Ok(value @ (serde_json::Value::Number(_) | serde_json::Value::Bool(_))) => value.to_string(),
+        _ => json_value.trim().to_string(),
+    }
+}
+
+/// Parse `value` as a literal of type `ty`, the way witness files are read.
+fn parse(ty: &str, value: &str) -> Result<Value, String> {
+    let json = serde_json::json!({ "VALUE": { "value": value, "type": ty } });
+    let values = serde_json::from_value::<WitnessValues>(json).map_err(|e| e.to_string())?;
+    values
+        .iter()
+        .next()
+        .map(|(_, value)| value.clone())
+        .ok_or_else(|| "No value".to_string())
+}
+
+/// Encode `json_value`, a literal of type `ty`, in its compact bit encoding.
+pub fn encode(ty: &str, json_value: &str) -> Result<Encoded, String> {
+    let value = parse(ty.trim(), &literal(json_value))?;
+    let bits: Vec<bool> = StructuralValue::from(&value).as_ref().iter_compact().collect();
+    let bytes: Vec<u8> = bits
+        .chunks(8)
+        .map(|chunk| chunk.iter().enumerate().fold(0u8, |byte, (i, &bit)| byte | (u8::from(bit) << (7 - i))))
+        .collect();
+    Ok(Encoded {
+        bits: bits.len(),
+        hex: hex::encode(&bytes),
+        binary: bits.iter().map(|&bit| if bit { '1' } else { '0' }).collect(),
+    })
+}
+
+/// Decode the compact encoding in `bits_hex` as a value of type `ty`, as a
+/// SimplicityHL literal. Bits after the value must be zero padding.
+pub fn decode(ty: &str, bits_hex: &str) -> Result<String, String> {
+    let resolved = resolve(ty.trim())?;
+    let bytes = hex::decode(bits_hex)?;
+    let structural = StructuralType::from(&resolved);
+    let mut bits = BitIter::new(bytes.iter().copied());
+    let value = simplicityhl::simplicity::Value::from_compact_bits(&mut bits, structural.as_ref())
+        .map_err(|e| format!("Not enough bits for `{}`: {e}", ty.trim()))?;
+    if bits.any(|bit| bit) {
+        return Err(format!("Bits left over after the `{}` value", ty.trim()));
+    }
+    Value::reconstruct(&StructuralValue::from(value), &resolved)
+        .map(|value| value.to_string())
+        .ok_or_else(|| format!("The bits do not form a `{}` value", ty.trim()))
+}
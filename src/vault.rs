@@ -0,0 +1,170 @@
+//! Named keys kept in localStorage.
+//!
+//! Public keys are stored in the clear. Private keys are encrypted with
+//! AES-GCM-256 under a key derived from the user's passphrase with
+//! PBKDF2-SHA256; salt and nonce are fresh for every secret, and the
+//! passphrase itself is never stored.
+//!
+//! Programs and witness values refer to a stored key as `@label`, which
+//! [`expand`] replaces with the key's x-only public key before compiling.
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::elements::secp256k1_zkp::{Secp256k1, SecretKey, XOnlyPublicKey};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::hex;
+use crate::logging::js_error;
+use crate::storage;
+
+const VAULT_KEY: &str = "vault";
+
+#[wasm_bindgen(inline_js = r#"
+async function derive_key(passphrase, salt) {
+    const material = await crypto.subtle.importKey(
+        'raw', new TextEncoder().encode(passphrase), 'PBKDF2', false, ['deriveKey']);
+    return crypto.subtle.deriveKey(
+        { name: 'PBKDF2', salt, iterations: 210000, hash: 'SHA-256' },
+        material,
+        { name: 'AES-GCM', length: 256 },
+        false,
+        ['encrypt', 'decrypt']);
+}
+export function random_bytes(length) {
+    return crypto.getRandomValues(new Uint8Array(length));
+}
+export async function aes_encrypt(passphrase, salt, iv, plaintext) {
+    const key = await derive_key(passphrase, salt);
+    return new Uint8Array(await crypto.subtle.encrypt({ name: 'AES-GCM', iv }, key, plaintext));
+}
+export async function aes_decrypt(passphrase, salt, iv, ciphertext) {
+    const key = await derive_key(passphrase, salt);
+    return new Uint8Array(await crypto.subtle.decrypt({ name: 'AES-GCM', iv }, key, ciphertext));
+}
+"#)]
+extern "C" {
+    fn random_bytes(length: u32) -> js_sys::Uint8Array;
+    fn aes_encrypt(passphrase: &str, salt: &[u8], iv: &[u8], plaintext: &[u8]) -> js_sys::Promise;
+    fn aes_decrypt(passphrase: &str, salt: &[u8], iv: &[u8], ciphertext: &[u8]) -> js_sys::Promise;
+}
+
+/// A private key encrypted under a passphrase. All fields are hex.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedSecret {
+    pub salt: String,
+    pub iv: String,
+    pub ciphertext: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct VaultEntry {
+    pub label: String,
+    /// x-only public key, hex.
+    pub pubkey: String,
+    /// Absent for keys imported as public keys only.
+    #[serde(default)]
+    pub secret: Option<EncryptedSecret>,
+}
+
+pub fn load_vault() -> Vec<VaultEntry> {
+    storage::load(VAULT_KEY)
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_vault(entries: &[VaultEntry]) {
+    if let Ok(json) = serde_json::to_string(entries) {
+        storage::save(VAULT_KEY, &json);
+    }
+}
+
+/// Labels may contain ASCII letters, digits, `_` and `-`.
+pub fn is_label_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+/// Check `label` is well-formed and not taken.
+pub fn validate_label(label: &str, entries: &[VaultEntry]) -> Result<(), String> {
+    if label.is_empty() || !label.chars().all(is_label_char) {
+        return Err("Labels may only use letters, digits, `_` and `-`".to_string());
+    }
+    if entries.iter().any(|e| e.label == label) {
+        return Err(format!("A key labelled `{label}` already exists"));
+    }
+    Ok(())
+}
+
+/// Parse a 32-byte private key and return it with its x-only public key.
+pub fn parse_secret(secret_hex: &str) -> Result<(SecretKey, XOnlyPublicKey), String> {
+    let secret = SecretKey::from_slice(&hex::decode(secret_hex)?).map_err(|e| format!("Invalid private key: {e}"))?;
+    let (pubkey, _) = secret.x_only_public_key(&Secp256k1::new());
+    Ok((secret, pubkey))
+}
+
+/// Parse an x-only public key given as hex.
+pub fn parse_pubkey(pubkey_hex: &str) -> Result<XOnlyPublicKey, String> {
+    XOnlyPublicKey::from_slice(&hex::decode(pubkey_hex)?).map_err(|e| format!("Invalid x-only public key: {e}"))
+}
+
+/// A fresh private key from the browser's CSPRNG.
+pub fn generate_secret() -> SecretKey {
+    loop {
+        // Fails only for zero or values above the curve order.
+        if let Ok(secret) = SecretKey::from_slice(&random_bytes(32).to_vec()) {
+            return secret;
+        }
+    }
+}
+
+/// Encrypt `secret` under `passphrase`.
+pub async fn encrypt(passphrase: &str, secret: &SecretKey) -> Result<EncryptedSecret, String> {
+    if passphrase.is_empty() {
+        return Err("Enter a passphrase to protect private keys".to_string());
+    }
+    let salt = random_bytes(16).to_vec();
+    let iv = random_bytes(12).to_vec();
+    let ciphertext = JsFuture::from(aes_encrypt(passphrase, &salt, &iv, &secret.secret_bytes()))
+        .await
+        .map_err(js_error)?;
+    Ok(EncryptedSecret {
+        salt: hex::encode(&salt),
+        iv: hex::encode(&iv),
+        ciphertext: hex::encode(&js_sys::Uint8Array::new(&ciphertext).to_vec()),
+    })
+}
+
+/// Decrypt `secret` with `passphrase`, returning the private key as hex.
+pub async fn decrypt(passphrase: &str, secret: &EncryptedSecret) -> Result<String, String> {
+    let salt = hex::decode(&secret.salt)?;
+    let iv = hex::decode(&secret.iv)?;
+    let ciphertext = hex::decode(&secret.ciphertext)?;
+    let plaintext = JsFuture::from(aes_decrypt(passphrase, &salt, &iv, &ciphertext))
+        .await
+        .map_err(|_| "Wrong passphrase or corrupted vault entry".to_string())?;
+    Ok(hex::encode(&js_sys::Uint8Array::new(&plaintext).to_vec()))
+}
+
+/// Replace every `@label` naming a stored key with `0x` and its public key.
+/// Unknown labels are left as written so the compiler reports them.
+pub fn expand(text: &str, entries: &[VaultEntry]) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(at) = rest.find('@') {
+        out.push_str(&rest[..at]);
+        let after = &rest[at + 1..];
+        let end = after.find(|c: char| !is_label_char(c)).unwrap_or(after.len());
+        match entries.iter().find(|e| e.label == after[..end]) {
+            Some(entry) if end > 0 => {
+                out.push_str("0x");
+                out.push_str(&entry.pubkey);
+            }
+            _ => {
+                out.push('@');
+                out.push_str(&after[..end]);
+            }
+        }
+        rest = &after[end..];
+    }
+    out.push_str(rest);
+    out
+}
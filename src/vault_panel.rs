@@ -0,0 +1,204 @@
+//! Key vault: generate, import, reveal and remove stored keys.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::address_book;
+use crate::hex;
+use crate::i18n::{self, t, Msg};
+use crate::vault::{self, load_vault, save_vault, VaultEntry};
+
+#[component]
+pub fn VaultPanel() -> impl IntoView {
+    let lang = i18n::use_lang();
+    let entries = RwSignal::new(load_vault());
+    let (passphrase, set_passphrase) = signal(String::new());
+    let (label, set_label) = signal(String::new());
+    let (key, set_key) = signal(String::new());
+    let (revealed, set_revealed) = signal::<Option<(String, String)>>(None);
+    let (error, set_error) = signal::<Option<String>>(None);
+    let (busy, set_busy) = signal(false);
+
+    let push = move |entry: VaultEntry| {
+        entries.update(|list| list.push(entry));
+        entries.with_untracked(|list| save_vault(list));
+        set_label.set(String::new());
+        set_key.set(String::new());
+    };
+
+    // Encrypt the private key `secret_hex` and store it; the form is cleared once it is saved.
+    let store_secret = move |label: String, secret_hex: String| {
+        let passphrase = passphrase.get_untracked();
+        set_busy.set(true);
+        spawn_local(async move {
+            match vault::parse_secret(&secret_hex) {
+                Ok((secret, pubkey)) => match vault::encrypt(&passphrase, &secret).await {
+                    Ok(encrypted) => push(VaultEntry {
+                        label,
+                        pubkey: pubkey.to_string(),
+                        secret: Some(encrypted),
+                    }),
+                    Err(e) => set_error.set(Some(e)),
+                },
+                Err(e) => set_error.set(Some(e)),
+            }
+            set_busy.set(false);
+        });
+    };
+
+    let checked_label = move || {
+        let label = label.get_untracked().trim().to_string();
+        entries
+            .with_untracked(|list| vault::validate_label(&label, list))
+            .and_then(|()| match address_book::load().iter().any(|c| c.label == label) {
+                true => Err(i18n::format(i18n::text(lang.get_untracked(), Msg::LabelNamesContact), &[&label])),
+                false => Ok(()),
+            })
+            .map(|()| label)
+    };
+
+    let generate = move |_| {
+        set_error.set(None);
+        match checked_label() {
+            Ok(label) => {
+                let secret = vault::generate_secret();
+                store_secret(label, hex::encode(&secret.secret_bytes()));
+            }
+            Err(e) => set_error.set(Some(e)),
+        }
+    };
+
+    let import = move |_| {
+        set_error.set(None);
+        let label = match checked_label() {
+            Ok(label) => label,
+            Err(e) => return set_error.set(Some(e)),
+        };
+        let key = key.get_untracked().trim().to_string();
+        // A 32-byte key could be either kind; without a passphrase it is taken as public.
+        if passphrase.get_untracked().is_empty() {
+            match vault::parse_pubkey(&key) {
+                Ok(pubkey) => push(VaultEntry {
+                    label,
+                    pubkey: pubkey.to_string(),
+                    secret: None,
+                }),
+                Err(e) => set_error.set(Some(e)),
+            }
+        } else {
+            store_secret(label, key);
+        }
+    };
+
+    let reveal = move |index: usize| {
+        let Some(entry) = entries.with_untracked(|list| list.get(index).cloned()) else {
+            return;
+        };
+        let Some(secret) = entry.secret else {
+            return;
+        };
+        let passphrase = passphrase.get_untracked();
+        set_error.set(None);
+        set_busy.set(true);
+        spawn_local(async move {
+            match vault::decrypt(&passphrase, &secret).await {
+                Ok(secret) => set_revealed.set(Some((entry.label, secret))),
+                Err(e) => set_error.set(Some(e)),
+            }
+            set_busy.set(false);
+        });
+    };
+
+    let remove = move |index: usize| {
+        entries.update(|list| {
+            if index < list.len() {
+                list.remove(index);
+            }
+        });
+        entries.with_untracked(|list| save_vault(list));
+        set_revealed.set(None);
+    };
+
+    let rows = move || {
+        entries
+            .get()
+            .into_iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let locked = entry.secret.is_some();
+                view! {
+                    <li class="invoice-row">
+                        <span class="invoice-label" title=entry.pubkey.clone()>
+                            {format!("@{}", entry.label)}
+                        </span>
+                        <span class="invoice-amount">{t(if locked { Msg::PrivateKey } else { Msg::PublicKey })}</span>
+                        <Show when=move || locked>
+                            <button class="secondary" on:click=move |_| reveal(index) disabled=move || busy.get()>
+                                {t(Msg::Reveal)}
+                            </button>
+                        </Show>
+                        <button class="danger" on:click=move |_| remove(index)>
+                            {t(Msg::Remove)}
+                        </button>
+                    </li>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <div class="vault-panel">
+            <label>{t(Msg::KeyVault)}</label>
+            <div class="invoice-form">
+                <input
+                    type="password"
+                    placeholder=t(Msg::Passphrase)
+                    prop:value=move || passphrase.get()
+                    on:input=move |ev| set_passphrase.set(event_target_value(&ev))
+                />
+                <input
+                    type="text"
+                    placeholder=t(Msg::VaultLabelPlaceholder)
+                    prop:value=move || label.get()
+                    on:input=move |ev| set_label.set(event_target_value(&ev))
+                />
+                <input
+                    type="text"
+                    placeholder=t(Msg::KeyToImport)
+                    prop:value=move || key.get()
+                    on:input=move |ev| set_key.set(event_target_value(&ev))
+                />
+                <button on:click=generate disabled=move || busy.get() || passphrase.get().is_empty()>
+                    {t(Msg::Generate)}
+                </button>
+                <button class="secondary" on:click=import disabled=move || busy.get() || key.get().trim().is_empty()>
+                    {t(Msg::Import)}
+                </button>
+            </div>
+            <p class="drop-zone-hint">
+                {t(Msg::VaultHint)}
+            </p>
+            {move || error.get().map(|e| view! { <p class="tool-error">{e}</p> })}
+            {move || {
+                revealed
+                    .get()
+                    .map(|(label, secret)| {
+                        view! {
+                            <div class="output-group">
+                                <div class="section-header">
+                                    <span class="output-label">
+                                        {i18n::format(i18n::text(lang.get(), Msg::PrivateKeyLabel), &[&label])}
+                                    </span>
+                                    <button class="secondary" on:click=move |_| set_revealed.set(None)>
+                                        {t(Msg::Hide)}
+                                    </button>
+                                </div>
+                                <div class="output-box">{secret}</div>
+                            </div>
+                        }
+                    })
+            }}
+            <ul class="invoice-list">{rows}</ul>
+        </div>
+    }
+}
@@ -0,0 +1,330 @@
+//! Verify mode: check pasted source against a claimed CMR or an
+//! attestation, attest the editor's code, and compare it with a spend on
+//! chain.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::api_types::{AttestationCheckResult, AttestationResult, OnChainResult, VerifyCmrResult};
+use crate::app::download_text;
+use crate::attestation::AttestationCheck;
+use crate::cmr_verify::CmrVerification;
+use crate::compiler;
+use crate::i18n::{self, t, Msg};
+use crate::onchain::SpendComparison;
+
+#[component]
+pub fn VerifyPanel(
+    /// The editor's code, offered as the source to verify.
+    #[prop(into)]
+    code: Signal<String>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let (claimed, set_claimed) = signal(String::new());
+    let (source, set_source) = signal(String::new());
+    let (busy, set_busy) = signal(false);
+    let (outcome, set_outcome) = signal::<Option<Result<CmrVerification, String>>>(None);
+    let (secret, set_secret) = signal(String::new());
+    let (attestation, set_attestation) = signal(String::new());
+    let (attest_error, set_attest_error) = signal(None::<String>);
+    let (check, set_check) = signal::<Option<Result<AttestationCheck, String>>>(None);
+    let (spend_tx, set_spend_tx) = signal(String::new());
+    let (spend_input, set_spend_input) = signal(String::new());
+    let (comparison, set_comparison) = signal::<Option<Result<SpendComparison, String>>>(None);
+
+    // A stale verdict next to edited inputs would mislead an auditor.
+    Effect::new(move |_| {
+        claimed.track();
+        source.track();
+        attestation.track();
+        spend_tx.track();
+        spend_input.track();
+        set_outcome.set(None);
+        set_check.set(None);
+        set_comparison.set(None);
+    });
+
+    let verify = move |_| {
+        let (source, claimed) = (source.get_untracked(), claimed.get_untracked());
+        set_busy.set(true);
+        spawn_local(async move {
+            let json = compiler::verify_cmr(&source, &claimed).await;
+            set_outcome.set(Some(match serde_json::from_str::<VerifyCmrResult>(&json) {
+                Ok(VerifyCmrResult { verification: Some(v), .. }) => Ok(v),
+                Ok(VerifyCmrResult { error, .. }) => Err(error.unwrap_or_default()),
+                Err(e) => Err(e.to_string()),
+            }));
+            set_busy.set(false);
+        });
+    };
+
+    let attest = move |_| {
+        let (code, secret) = (code.get_untracked(), secret.get_untracked());
+        spawn_local(async move {
+            let json = compiler::create_attestation(&code, &secret).await;
+            match serde_json::from_str::<AttestationResult>(&json) {
+                Ok(AttestationResult { attestation: Some(attestation), .. }) => {
+                    set_attest_error.set(None);
+                    let json = serde_json::to_string_pretty(&attestation).unwrap_or_default();
+                    download_text("simplicity-attestation.json", &json);
+                }
+                Ok(AttestationResult { error, .. }) => set_attest_error.set(Some(error.unwrap_or_default())),
+                Err(e) => set_attest_error.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    let verify_attestation = move |_| {
+        let (attestation, source) = (attestation.get_untracked(), source.get_untracked());
+        spawn_local(async move {
+            let json = compiler::verify_attestation(&attestation, &source).await;
+            set_check.set(Some(match serde_json::from_str::<AttestationCheckResult>(&json) {
+                Ok(AttestationCheckResult { check: Some(check), .. }) => Ok(check),
+                Ok(AttestationCheckResult { error, .. }) => Err(error.unwrap_or_default()),
+                Err(e) => Err(e.to_string()),
+            }));
+        });
+    };
+
+    let compare_spend = move |_| {
+        let (code, tx_hex) = (code.get_untracked(), spend_tx.get_untracked());
+        let input = spend_input.get_untracked();
+        let input_index = match input.trim() {
+            "" => None,
+            index => match index.parse() {
+                Ok(index) => Some(index),
+                Err(_) => {
+                    let error = i18n::format(i18n::text(lang.get_untracked(), Msg::NotInputIndex), &[index]);
+                    return set_comparison.set(Some(Err(error)));
+                }
+            },
+        };
+        set_busy.set(true);
+        spawn_local(async move {
+            let json = compiler::compare_onchain_spend(&code, &tx_hex, input_index).await;
+            set_comparison.set(Some(match serde_json::from_str::<OnChainResult>(&json) {
+                Ok(OnChainResult { comparison: Some(c), .. }) => Ok(c),
+                Ok(OnChainResult { error, .. }) => Err(error.unwrap_or_default()),
+                Err(e) => Err(e.to_string()),
+            }));
+            set_busy.set(false);
+        });
+    };
+
+    let comparison_view = move || {
+        comparison.get().map(|comparison| match comparison {
+            Err(e) => view! { <p class="tool-error" role="alert">{e}</p> }.into_any(),
+            Ok(c) => {
+                let lang = lang.get();
+                let text = |msg| i18n::text(lang, msg);
+                let verdict = match (&c.compile_error, c.matches) {
+                    (Some(e), _) => {
+                        let error = i18n::format(text(Msg::EditorDoesNotCompile), &[e]);
+                        view! { <p class="tool-error">{error}</p> }.into_any()
+                    }
+                    (None, true) => view! {
+                        <p class="status-badge paid" role="status">{text(Msg::EditorIsOnChain)}</p>
+                    }
+                    .into_any(),
+                    (None, false) => view! {
+                        <p class="status-badge expired" role="status">{text(Msg::EditorDifferentCmr)}</p>
+                    }
+                    .into_any(),
+                };
+                let spend = c.spend;
+                let leaf_mismatch = (spend.leaf_cmr != spend.cmr).then(|| {
+                    view! { <p class="tool-error">{i18n::format(text(Msg::LeafCommitsTo), &[&spend.leaf_cmr])}</p> }
+                });
+                let bytes = |hex: &str| i18n::format(text(Msg::ByteCount), &[&(hex.len() / 2).to_string()]);
+                view! {
+                    {verdict}
+                    {leaf_mismatch}
+                    <dl class="verify-cmrs">
+                        <dt>{text(Msg::TransactionTerm)}</dt>
+                        <dd>{i18n::format(text(Msg::TxInput), &[&spend.txid, &spend.input_index.to_string()])}</dd>
+                        <dt>{text(Msg::OnChain)}</dt>
+                        <dd>{spend.cmr}</dd>
+                        <dt>{text(Msg::Compiled)}</dt>
+                        <dd>{c.local_cmr.unwrap_or_else(|| "—".to_string())}</dd>
+                        <dt>{text(Msg::Program)}</dt>
+                        <dd>{bytes(&spend.program)}</dd>
+                        <dt>{text(Msg::Witness)}</dt>
+                        <dd>{bytes(&spend.witness)}</dd>
+                    </dl>
+                    <details>
+                        <summary>{text(Msg::ExtractedProgram)}</summary>
+                        <pre class="output-box listing">{format!("program: {}\nwitness: {}\ncontrol block: {}", spend.program, spend.witness, spend.control_block)}</pre>
+                    </details>
+                }
+                .into_any()
+            }
+        })
+    };
+
+    let check_view = move || {
+        check.get().map(|check| match check {
+            Err(e) => view! { <p class="tool-error" role="alert">{e}</p> }.into_any(),
+            Ok(check) => {
+                let lang = lang.get();
+                let text = |msg| i18n::text(lang, msg);
+                let row = |ok: bool, text: String| {
+                    let (class, mark) = if ok { ("status-badge paid", "✓") } else { ("status-badge expired", "✗") };
+                    view! {
+                        <li class="signature-row">
+                            <span class=class>{mark}</span>
+                            {text}
+                        </li>
+                    }
+                };
+                let cmr = match (&check.actual_cmr, &check.compile_error) {
+                    (_, Some(e)) => i18n::format(text(Msg::SourceDoesNotCompile), &[e]),
+                    (Some(actual), _) if !check.cmr_matches => i18n::format(text(Msg::SourceDifferentCmr), &[actual]),
+                    _ => text(Msg::SourceAttestedCmr).to_string(),
+                };
+                let signature = check.signature_valid.map(|valid| {
+                    let signer = check.signer.clone().unwrap_or_default();
+                    row(valid, i18n::format(text(if valid { Msg::SignedBy } else { Msg::SignatureByFails }), &[&signer]))
+                });
+                view! {
+                    <p class="bulk-summary" role="status">
+                        {text(if check.is_valid() { Msg::AttestationHolds } else { Msg::AttestationFails })}
+                    </p>
+                    <ul class="signature-list">
+                        {row(
+                            check.source_matches,
+                            text(match check.source_matches {
+                                true => Msg::SourceHashMatches,
+                                false => Msg::SourceHashDiffers,
+                            })
+                            .to_string(),
+                        )}
+                        {row(check.cmr_matches, cmr)}
+                        {row(
+                            check.same_compiler,
+                            text(if check.same_compiler { Msg::SameCompiler } else { Msg::OtherCompiler }).to_string(),
+                        )}
+                        {signature}
+                    </ul>
+                }
+                .into_any()
+            }
+        })
+    };
+
+    let verdict = move || {
+        outcome.get().map(|outcome| match outcome {
+            Ok(v) if v.matches => view! {
+                <p class="status-badge paid" role="status">{t(Msg::SourceMatchesCmr)}</p>
+            }
+            .into_any(),
+            Ok(v) => view! {
+                <p class="status-badge expired" role="status">{t(Msg::SourceDiffersCmr)}</p>
+                <dl class="verify-cmrs">
+                    <dt>{t(Msg::Claimed)}</dt>
+                    <dd>{v.claimed}</dd>
+                    <dt>{t(Msg::Compiled)}</dt>
+                    <dd>{v.actual}</dd>
+                </dl>
+            }
+            .into_any(),
+            Err(e) => view! { <p class="tool-error" role="alert">{e}</p> }.into_any(),
+        })
+    };
+
+    view! {
+        <div class="verify-panel">
+            <label for="verify-cmr">{t(Msg::CmrVerification)}</label>
+            <div class="signature-form">
+                <input
+                    id="verify-cmr"
+                    placeholder=t(Msg::ClaimedCmrPlaceholder)
+                    spellcheck="false"
+                    prop:value=move || claimed.get()
+                    on:input=move |ev| set_claimed.set(event_target_value(&ev))
+                />
+            </div>
+            <textarea
+                spellcheck="false"
+                aria-label=t(Msg::SourceToVerify)
+                placeholder=t(Msg::SourcePlaceholder)
+                prop:value=move || source.get()
+                on:input=move |ev| set_source.set(event_target_value(&ev))
+            />
+            <div class="button-group">
+                <button
+                    on:click=verify
+                    disabled=move || busy.get() || claimed.get().trim().is_empty() || source.get().trim().is_empty()
+                >
+                    {move || i18n::text(lang.get(), if busy.get() { Msg::CompilingShort } else { Msg::VerifySource })}
+                </button>
+                <button class="secondary" on:click=move |_| set_source.set(code.get_untracked())>
+                    {t(Msg::UseEditorCode)}
+                </button>
+            </div>
+            <p class="drop-zone-hint">
+                {t(Msg::VerifyCmrHint)}
+            </p>
+            {verdict}
+
+            <label for="attestation-json">{t(Msg::Attestation)}</label>
+            <div class="signature-form">
+                <input
+                    type="password"
+                    autocomplete="off"
+                    placeholder=t(Msg::SigningKeyPlaceholder)
+                    prop:value=move || secret.get()
+                    on:input=move |ev| set_secret.set(event_target_value(&ev))
+                />
+            </div>
+            <textarea
+                id="attestation-json"
+                spellcheck="false"
+                placeholder=t(Msg::AttestationPlaceholder)
+                prop:value=move || attestation.get()
+                on:input=move |ev| set_attestation.set(event_target_value(&ev))
+            />
+            <div class="button-group">
+                <button on:click=attest>{t(Msg::AttestEditorCode)}</button>
+                <button
+                    class="secondary"
+                    on:click=verify_attestation
+                    disabled=move || attestation.get().trim().is_empty()
+                >
+                    {t(Msg::CheckAttestation)}
+                </button>
+            </div>
+            <p class="drop-zone-hint">
+                {t(Msg::AttestationHint)}
+            </p>
+            {move || attest_error.get().map(|e| view! { <p class="tool-error">{e}</p> })}
+            {check_view}
+
+            <label for="onchain-tx">{t(Msg::OnChainSpend)}</label>
+            <textarea
+                id="onchain-tx"
+                spellcheck="false"
+                placeholder=t(Msg::SpendTxPlaceholder)
+                prop:value=move || spend_tx.get()
+                on:input=move |ev| set_spend_tx.set(event_target_value(&ev))
+            />
+            <div class="signature-form">
+                <input
+                    type="number"
+                    min="0"
+                    placeholder=t(Msg::InputIndexPlaceholder)
+                    prop:value=move || spend_input.get()
+                    on:input=move |ev| set_spend_input.set(event_target_value(&ev))
+                />
+            </div>
+            <div class="button-group">
+                <button on:click=compare_spend disabled=move || busy.get() || spend_tx.get().trim().is_empty()>
+                    {t(Msg::CompareWithEditor)}
+                </button>
+            </div>
+            <p class="drop-zone-hint">
+                {t(Msg::OnChainHint)}
+            </p>
+            {comparison_view}
+        </div>
+    }
+}
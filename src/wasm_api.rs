@@ -1,13 +1,63 @@
+use std::collections::BTreeMap;
+
 use wasm_bindgen::prelude::*;
-use serde::{Serialize, Deserialize};
 use simplicityhl::parse::ParseFromStr;
-use simplicityhl::CompiledProgram;
+use simplicityhl::simplicity::Cmr;
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct CompileResult {
-    pub cmr: Option<String>,
-    pub error: Option<String>,
-}
+use crate::address::{parse_cmr, taproot_address, Network, Tapleaf};
+use crate::api_types::{
+    AddressResult, AnalysisResult, AttestationCheckResult, AttestationResult, BenchmarkResult, BulkResult, CheckResult,
+    CompileResult, ControlBlockCheckResult, ControlBlockResult, CoverageResult, DecodeResult, DeriveKeyResult,
+    DescriptorResult, EncodeResult, EvaluationResult, HexDumpResult, ListingResult, LockResult, MinimizeResult,
+    MockRunResult, OfferImportResult, OfferResult, OnChainResult, PruningResult, RandomWitnessResult, ReportResult,
+    SchemaResult, ScriptPubkeyResult, SighashResult, SignatureResult, SourceMapResult, SpendResult, SummaryResult,
+    TaprootResult, TestRunResult, TraceResult, VerifyCmrResult, WitnessSignaturesResult, WitnessSizeResult,
+};
+use crate::artifact;
+use crate::attestation::{self, Attestation};
+use crate::benchmark;
+use crate::bip32;
+use crate::bulk;
+use crate::cache;
+use crate::cmr_verify;
+use crate::coverage;
+use crate::descriptor;
+use crate::diagnostics::Diagnostic;
+use crate::hex;
+use crate::hexdump;
+use crate::jet_costs::CostTable;
+use crate::jet_usage;
+use crate::limits::ExecLimits;
+use crate::listing;
+use crate::locktime;
+use crate::manifest::KeyManifest;
+use crate::memory;
+use crate::metadata;
+use crate::minimize;
+use crate::mock_tx::{self, MockTx};
+use crate::offer::{self, Offer};
+use crate::onchain;
+use crate::options::{self, CompileOptions};
+use crate::pruning;
+use crate::quick_fix;
+use crate::repl;
+use crate::report;
+use crate::sanity;
+use crate::schema;
+use crate::sighash;
+use crate::sigverify;
+use crate::source_map;
+use crate::spend;
+use crate::spend_paths;
+use crate::summary::ProgramSummary;
+use crate::taproot;
+use crate::test_runner;
+use crate::timestamp::Stamp;
+use crate::trace;
+use crate::triage;
+use crate::value_codec;
+use crate::witness_gen;
+use crate::witness_size;
 
 #[wasm_bindgen]
 pub fn compile_simplicity(code: &str) -> String {
@@ -15,39 +65,71 @@ pub fn compile_simplicity(code: &str) -> String {
         return serde_json::to_string(&CompileResult {
             cmr: None,
             error: Some("Code is empty".to_string()),
+            timestamp: Stamp::now(),
+            leaf: None,
+            metadata: None,
         }).unwrap_or_else(|_| r#"{"cmr":null,"error":"Serialization error"}"#.to_string());
     }
     
-    // Parse arguments
-    match simplicityhl::Arguments::parse_from_str(code) {
-        Err(e) => {
-            let result = CompileResult {
+    let result = match compile_cmr(code, "") {
+        Ok(cmr) => CompileResult {
+            cmr: Some(format!("{}", cmr)),
+            error: None,
+            timestamp: Stamp::now(),
+            leaf: Some(Tapleaf::new(cmr)),
+            metadata: metadata::parse(code),
+        },
+        Err(e) => CompileResult {
+            cmr: None,
+            error: Some(e),
+            timestamp: Stamp::now(),
+            leaf: None,
+            metadata: None,
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"cmr":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Compile under the settings in `options_json`, a [`CompileOptions`]
+/// object such as `{"debug_symbols": true, "target": "elements"}`.
+/// `witness_data`, when given and not blank, is checked and returned as
+/// [`compile_with_witness`] does.
+#[wasm_bindgen]
+pub fn compile_with_options(code: &str, options_json: &str, witness_data: Option<String>) -> String {
+    if code.trim().is_empty() {
+        return compile_simplicity(code);
+    }
+    if let Some(witness_data) = witness_data.filter(|w| !w.trim().is_empty()) {
+        return match CompileOptions::parse(options_json) {
+            Ok(options) => compile_witness_with(code, &witness_data, &options),
+            Err(e) => serde_json::to_string(&CompileResult {
                 cmr: None,
-                error: Some(format!("Parse error: {}", e)),
-            };
-            serde_json::to_string(&result).unwrap_or_else(|_| r#"{"cmr":null,"error":"Serialization error"}"#.to_string())
-        }
-        Ok(args) => {
-            // Compile
-            match CompiledProgram::new(code, args, false) {
-                Err(e) => {
-                    let result = CompileResult {
-                        cmr: None,
-                        error: Some(format!("Compilation error: {}", e)),
-                    };
-                    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"cmr":null,"error":"Serialization error"}"#.to_string())
-                }
-                Ok(compiled) => {
-                    let cmr = compiled.commit().cmr();
-                    let result = CompileResult {
-                        cmr: Some(format!("{}", cmr)),
-                        error: None,
-                    };
-                    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"cmr":null,"error":"Serialization error"}"#.to_string())
-                }
-            }
-        }
+                error: Some(e),
+                timestamp: Stamp::now(),
+                leaf: None,
+                metadata: None,
+            })
+            .unwrap_or_else(|_| r#"{"cmr":null,"error":"Serialization error"}"#.to_string()),
+        };
     }
+
+    let result = match CompileOptions::parse(options_json).and_then(|options| compile_cmr_with(code, "", &options)) {
+        Ok(cmr) => CompileResult {
+            cmr: Some(format!("{}", cmr)),
+            error: None,
+            timestamp: Stamp::now(),
+            leaf: Some(Tapleaf::new(cmr)),
+            metadata: metadata::parse(code),
+        },
+        Err(e) => CompileResult {
+            cmr: None,
+            error: Some(e),
+            timestamp: Stamp::now(),
+            leaf: None,
+            metadata: None,
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"cmr":null,"error":"Serialization error"}"#.to_string())
 }
 
 /// Compile with witness data support
@@ -58,6 +140,9 @@ pub fn compile_with_witness(code: &str, witness_data: &str) -> String {
         return serde_json::to_string(&CompileResult {
             cmr: None,
             error: Some("Code is empty".to_string()),
+            timestamp: Stamp::now(),
+            leaf: None,
+            metadata: None,
         }).unwrap_or_else(|_| r#"{"cmr":null,"error":"Serialization error"}"#.to_string());
     }
     
@@ -65,57 +150,69 @@ pub fn compile_with_witness(code: &str, witness_data: &str) -> String {
         return serde_json::to_string(&CompileResult {
             cmr: None,
             error: Some("Witness data is empty".to_string()),
+            timestamp: Stamp::now(),
+            leaf: None,
+            metadata: None,
         }).unwrap_or_else(|_| r#"{"cmr":null,"error":"Serialization error"}"#.to_string());
     }
-    
+
+    compile_witness_with(code, witness_data, &CompileOptions::default())
+}
+
+/// [`compile_with_witness`] under `options`, for a non-blank witness.
+fn compile_witness_with(code: &str, witness_data: &str, options: &CompileOptions) -> String {
     // First, validate that witness_data is valid JSON
-    match serde_json::from_str::<serde_json::Value>(witness_data) {
+    let witness = match serde_json::from_str::<serde_json::Value>(witness_data) {
+        Ok(witness) => witness,
         Err(e) => {
             let result = CompileResult {
                 cmr: None,
                 error: Some(format!("Invalid JSON witness data: {}", e)),
+                timestamp: Stamp::now(),
+                leaf: None,
+                metadata: None,
             };
             return serde_json::to_string(&result).unwrap_or_else(|_| r#"{"cmr":null,"error":"Serialization error"}"#.to_string());
         }
-        Ok(_) => {} // Valid JSON, continue
-    }
+    };
     
-    // Parse arguments from code
-    match simplicityhl::Arguments::parse_from_str(code) {
+    if let Err(e) = schema::check(witness_data) {
+        let result = CompileResult {
+            cmr: None,
+            error: Some(format!("Invalid witness data:\n{}", e)),
+            timestamp: Stamp::now(),
+            leaf: None,
+            metadata: None,
+        };
+        return serde_json::to_string(&result).unwrap_or_else(|_| r#"{"cmr":null,"error":"Serialization error"}"#.to_string());
+    }
+
+    match compile_cmr_with(code, "", options) {
         Err(e) => {
             let result = CompileResult {
                 cmr: None,
-                error: Some(format!("Parse error: {}", e)),
+                error: Some(e),
+                timestamp: Stamp::now(),
+                leaf: None,
+                metadata: None,
             };
             serde_json::to_string(&result).unwrap_or_else(|_| r#"{"cmr":null,"error":"Serialization error"}"#.to_string())
         }
-        Ok(args) => {
-            // Compile code
-            match CompiledProgram::new(code, args, false) {
-                Err(e) => {
-                    let result = CompileResult {
-                        cmr: None,
-                        error: Some(format!("Compilation error: {}", e)),
-                    };
-                    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"cmr":null,"error":"Serialization error"}"#.to_string())
-                }
-                Ok(compiled) => {
-                    // Get CMR
-                    let cmr = compiled.commit().cmr();
-                    
-                    // Return success with witness data stored
-                    let result = CompileResult {
-                        cmr: Some(format!("{}", cmr)),
-                        error: None,
-                    };
-                    
-                    // Create extended response with witness data
-                    let mut response = serde_json::to_value(&result).unwrap();
-                    response["witness_data"] = serde_json::from_str(witness_data).unwrap_or(serde_json::json!({}));
-                    
-                    serde_json::to_string(&response).unwrap_or_else(|_| r#"{"cmr":null,"error":"Serialization error"}"#.to_string())
-                }
-            }
+        Ok(cmr) => {
+            // Return success with witness data stored
+            let result = CompileResult {
+                cmr: Some(format!("{}", cmr)),
+                error: None,
+                timestamp: Stamp::now(),
+                leaf: Some(Tapleaf::new(cmr)),
+                metadata: metadata::parse(code),
+            };
+            
+            // Create extended response with witness data
+            let mut response = serde_json::to_value(&result).unwrap();
+            response["witness_data"] = witness;
+            
+            serde_json::to_string(&response).unwrap_or_else(|_| r#"{"cmr":null,"error":"Serialization error"}"#.to_string())
         }
     }
 }
@@ -126,14 +223,22 @@ pub fn parse_program(code: &str) -> String {
         return serde_json::to_string(&CompileResult {
             cmr: None,
             error: Some("Code is empty".to_string()),
+            timestamp: Stamp::now(),
+            leaf: None,
+            metadata: None,
         }).unwrap_or_else(|_| r#"{"cmr":null,"error":"Serialization error"}"#.to_string());
     }
     
-    match simplicityhl::Arguments::parse_from_str(code) {
+    match options::expand_keys(code)
+        .and_then(|code| simplicityhl::Arguments::parse_from_str(&code).map_err(|e| format!("Parse error: {}", e)))
+    {
         Err(e) => {
             let result = CompileResult {
                 cmr: None,
-                error: Some(format!("Parse error: {}", e)),
+                error: Some(e),
+                timestamp: Stamp::now(),
+                leaf: None,
+                metadata: None,
             };
             serde_json::to_string(&result).unwrap_or_else(|_| r#"{"cmr":null,"error":"Serialization error"}"#.to_string())
         }
@@ -141,8 +246,988 @@ pub fn parse_program(code: &str) -> String {
             let result = CompileResult {
                 cmr: Some(format!("Parsed successfully: {:?}", args)),
                 error: None,
+                timestamp: Stamp::now(),
+                leaf: None,
+                metadata: None,
             };
             serde_json::to_string(&result).unwrap_or_else(|_| r#"{"cmr":null,"error":"Serialization error"}"#.to_string())
         }
     }
 }
+
+/// Compile the program and describe its structure in plain text.
+///
+/// The `text` field is suitable for an aria-live region or a `.txt` export.
+#[wasm_bindgen]
+pub fn summarize_program(code: &str) -> String {
+    let result = match CompileOptions::default().compile_source(code, code) {
+        Ok(compiled) => {
+            let summary = ProgramSummary::from_commit(&compiled.commit(), code);
+            SummaryResult {
+                text: Some(summary.to_text()),
+                summary: Some(summary),
+                error: None,
+            }
+        }
+        Err(e) => SummaryResult {
+            summary: None,
+            text: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"summary":null,"text":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Compile the program and tally its jets with their costs.
+///
+/// `costs_json` is a cost table, `{"JET": milliweight, ...}`, or blank for
+/// the consensus costs; see [`crate::jet_costs`]. Jets it changes report
+/// their consensus cost alongside.
+#[wasm_bindgen]
+pub fn analyze_program(code: &str, costs_json: &str) -> String {
+    let result = match CostTable::parse(costs_json).and_then(|costs| {
+        Ok((CompileOptions::default().compile_source(code, code)?, costs))
+    }) {
+        Ok((compiled, costs)) => {
+            let jets = jet_usage::histogram(&compiled.commit(), &costs);
+            AnalysisResult {
+                total_cost: jets.values().map(|usage| usage.total_cost).sum(),
+                jets,
+                error: None,
+            }
+        }
+        Err(e) => AnalysisResult {
+            jets: BTreeMap::new(),
+            total_cost: 0,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result)
+        .unwrap_or_else(|_| r#"{"jets":{},"total_cost":0,"error":"Serialization error"}"#.to_string())
+}
+
+/// Compile the program and list its combinators, one node per line.
+#[wasm_bindgen]
+pub fn to_simplicity_text(code: &str) -> String {
+    let result = match CompileOptions::default().compile_source(code, code) {
+        Ok(compiled) => ListingResult {
+            text: Some(listing::to_text(&compiled.commit())),
+            error: None,
+        },
+        Err(e) => ListingResult {
+            text: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"text":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Map every node of the compiled program, in listing order, to the source
+/// ranges it was compiled from.
+#[wasm_bindgen]
+pub fn source_map(code: &str) -> String {
+    let result = match source_map::build(code) {
+        Ok(map) => SourceMapResult {
+            map: Some(map),
+            error: None,
+        },
+        Err(e) => SourceMapResult {
+            map: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"map":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Compile the program and dump its encoding with a bit-level annotation.
+#[wasm_bindgen]
+pub fn hex_dump(code: &str) -> String {
+    let result = match hexdump::dump(code) {
+        Ok(dump) => HexDumpResult {
+            text: Some(dump.to_text()),
+            dump: Some(dump),
+            error: None,
+        },
+        Err(e) => HexDumpResult {
+            dump: None,
+            text: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"dump":null,"text":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Compile the program and report problems anchored to source lines.
+///
+/// Unlike [`compile_simplicity`], the result never carries an `error` field;
+/// an empty `diagnostics` list means the program compiled. Diagnostics with
+/// an obvious repair carry a `fix`: `{"title", "start", "end",
+/// "replacement"}`, replacing source bytes `start..end`.
+#[wasm_bindgen]
+pub fn check_program(code: &str) -> String {
+    let mut diagnostics = match options::expand_keys(code) {
+        Err(e) => vec![Diagnostic::from_rendered(&e)],
+        Ok(expanded) => match simplicityhl::Arguments::parse_from_str(&expanded) {
+            Err(e) => vec![Diagnostic::from_rich_error(&e)],
+            Ok(args) => match CompileOptions::default().compile(&expanded, args) {
+                Err(e) => vec![Diagnostic::from_rendered(&e)],
+                Ok(_) => Vec::new(),
+            },
+        },
+    };
+    quick_fix::attach(code, &mut diagnostics);
+    serde_json::to_string(&CheckResult { diagnostics }).unwrap_or_else(|_| r#"{"diagnostics":[]}"#.to_string())
+}
+
+/// Compile the program and derive the taproot address that locks funds to it.
+///
+/// `network` is one of `liquid`, `liquidtestnet` or `elements`.
+#[wasm_bindgen]
+pub fn contract_address(code: &str, network: &str) -> String {
+    let result = match network
+        .parse::<Network>()
+        .and_then(|network| Ok((compile_cmr(code, "")?, network)))
+    {
+        Ok((cmr, network)) => AddressResult {
+            cmr: Some(cmr.to_string()),
+            address: Some(taproot_address(cmr, network).to_string()),
+            error: None,
+        },
+        Err(e) => AddressResult {
+            cmr: None,
+            address: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"cmr":null,"address":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Check which public keys the program uses before its address is shared.
+///
+/// `manifest_json` is a [`KeyManifest`] listing the keys the caller controls;
+/// pass `{"keys":[]}` to only check for witness-supplied keys.
+#[wasm_bindgen]
+pub fn check_address_sanity(code: &str, witness_data: &str, manifest_json: &str) -> String {
+    let manifest = serde_json::from_str::<KeyManifest>(manifest_json).unwrap_or_default();
+    let report = sanity::check(code, witness_data, &manifest);
+    serde_json::to_string(&report).unwrap_or_else(|_| r#"{"keys":[],"own_keys":[],"findings":[]}"#.to_string())
+}
+
+/// Parse `args` (or, when blank, the arguments embedded in `code`) and
+/// compile just far enough to get the CMR. Outcomes are served from the
+/// [`cache`] when the same input was compiled before.
+pub(crate) fn compile_cmr(code: &str, args: &str) -> Result<Cmr, String> {
+    compile_cmr_with(code, args, &CompileOptions::default())
+}
+
+/// [`compile_cmr`] under `options`.
+pub(crate) fn compile_cmr_with(code: &str, args: &str, options: &CompileOptions) -> Result<Cmr, String> {
+    let key = cache::key(code, args, &options.cache_tag());
+    cache::get_or_compile(key, || compile_uncached(code, args, options))
+}
+
+fn compile_uncached(code: &str, args: &str, options: &CompileOptions) -> Result<Cmr, String> {
+    let args_source = if args.trim().is_empty() { code } else { args };
+    Ok(options.compile_source(code, args_source)?.commit().cmr())
+}
+
+/// Compile the program and return only its CMR as hex.
+///
+/// `args` holds parameter values in `mod param { ... }` syntax; pass an empty
+/// string to read them from `code`, like the other entry points do. Errors
+/// are thrown as strings instead of being wrapped in a result object.
+///
+/// This is the cheapest way to get a CMR: there is no JSON encoding, witness
+/// handling, timestamping or localStorage access. The remaining cost is
+/// parsing and type-checking `code`, which is paid once per distinct
+/// `(code, args)` pair while it stays in the compile cache, so callers
+/// scanning many `args` for one program should still expect time linear in
+/// the program size per new pair.
+#[wasm_bindgen]
+pub fn cmr_of(code: &str, args: &str) -> Result<String, JsValue> {
+    compile_cmr(code, args)
+        .map(|cmr| cmr.to_string())
+        .map_err(|e| JsValue::from_str(&e))
+}
+
+/// Compile the program, satisfy it with `witness_data` and return the
+/// encoding as one binary artifact (a `Uint8Array` in JavaScript): the
+/// 32-byte CMR, the program length as a little-endian `u32`, the program
+/// bytes, then the witness bytes. Errors are thrown as strings.
+///
+/// Unlike the JSON entry points nothing is hex-encoded, so large programs
+/// cross the JavaScript boundary, and a worker's `postMessage`, at their
+/// real size.
+#[wasm_bindgen]
+pub fn encode_artifact(code: &str, witness_data: &str) -> Result<Vec<u8>, JsValue> {
+    artifact::build(code, witness_data)
+        .map(|artifact| artifact.to_bytes())
+        .map_err(|e| JsValue::from_str(&e))
+}
+
+/// Check whether `code` compiles to `claimed_cmr`, e.g. one taken from a
+/// block explorer. `matches` is false when it compiles to another root.
+#[wasm_bindgen]
+pub fn verify_cmr(code: &str, claimed_cmr: &str) -> String {
+    let result = match cmr_verify::verify(code, claimed_cmr, |code| compile_cmr(code, "")) {
+        Ok(verification) => VerifyCmrResult {
+            verification: Some(verification),
+            error: None,
+        },
+        Err(e) => VerifyCmrResult {
+            verification: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"verification":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Extract the program and witness from the Simplicity spend in input
+/// `input_index` of `tx_hex`, or its first such input, and compare the
+/// program's CMR with the CMR `code` compiles to. A failed compile is
+/// reported in `compile_error`, next to what was extracted.
+#[wasm_bindgen]
+pub fn compare_onchain_spend(code: &str, tx_hex: &str, input_index: Option<u32>) -> String {
+    let result = match onchain::extract(tx_hex, input_index) {
+        Ok(spend) => OnChainResult {
+            comparison: Some(onchain::compare(code, spend, |code| compile_cmr(code, ""))),
+            error: None,
+        },
+        Err(e) => OnChainResult {
+            comparison: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"comparison":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Attest that `code` compiles to its CMR with this build, signed with the
+/// hex private key `secret_key` unless it is empty.
+#[wasm_bindgen]
+pub fn create_attestation(code: &str, secret_key: &str) -> String {
+    let result = match compile_cmr(code, "").and_then(|cmr| attestation::create(code, cmr, secret_key)) {
+        Ok(attestation) => AttestationResult {
+            attestation: Some(attestation),
+            error: None,
+        },
+        Err(e) => AttestationResult {
+            attestation: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"attestation":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Re-check an attestation made by `create_attestation` against `code`.
+#[wasm_bindgen]
+pub fn verify_attestation(attestation_json: &str, code: &str) -> String {
+    let result = match serde_json::from_str::<Attestation>(attestation_json)
+        .map_err(|e| format!("Invalid attestation JSON: {}", e))
+        .and_then(|attestation| attestation::verify(&attestation, code, |code| compile_cmr(code, "")))
+    {
+        Ok(check) => AttestationCheckResult {
+            check: Some(check),
+            error: None,
+        },
+        Err(e) => AttestationCheckResult {
+            check: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"check":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Pack the source hash, CMR, parameter values and witness fields of `code`
+/// into one offer string to send to a counterparty.
+#[wasm_bindgen]
+pub fn export_offer(code: &str) -> String {
+    let result = match compile_cmr(code, "").map(|cmr| offer::create(code, cmr)) {
+        Ok(offer) => OfferResult {
+            payload: Some(offer.encode()),
+            offer: Some(offer),
+            error: None,
+        },
+        Err(e) => OfferResult {
+            payload: None,
+            offer: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"payload":null,"offer":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Read an offer string and check `code` against it.
+#[wasm_bindgen]
+pub fn import_offer(payload: &str, code: &str) -> String {
+    let result = match Offer::decode(payload) {
+        Ok(offer) => OfferImportResult {
+            check: Some(offer::check(&offer, code, |code| compile_cmr(code, ""))),
+            offer: Some(offer),
+            error: None,
+        },
+        Err(e) => OfferImportResult {
+            offer: None,
+            check: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"offer":null,"check":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Evaluate one SimplicityHL expression on the Bit Machine. `ty` is the
+/// expression's type, or empty to let the compiler infer it; `witness_data`
+/// gives values for the `witness::NAME` it reads.
+#[wasm_bindgen]
+pub fn evaluate_expression(expression: &str, ty: &str, witness_data: &str) -> String {
+    let result = match repl::evaluate(expression, ty, witness_data) {
+        Ok(evaluation) => EvaluationResult {
+            evaluation: Some(evaluation),
+            error: None,
+        },
+        Err(e) => EvaluationResult {
+            evaluation: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"evaluation":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// The compact bit encoding of `json_value`, a value of type `ty` written as
+/// in witness files: `"0x01"`, `42`, `true` or `"Left(0x01)"`.
+#[wasm_bindgen]
+pub fn encode_value(ty: &str, json_value: &str) -> String {
+    let result = match value_codec::encode(ty, json_value) {
+        Ok(encoded) => EncodeResult {
+            encoded: Some(encoded),
+            error: None,
+        },
+        Err(e) => EncodeResult {
+            encoded: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"encoded":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// The value of type `ty` encoded in `bits_hex`, as a SimplicityHL literal.
+#[wasm_bindgen]
+pub fn decode_value(ty: &str, bits_hex: &str) -> String {
+    let result = match value_codec::decode(ty, bits_hex) {
+        Ok(value) => DecodeResult {
+            value: Some(value),
+            error: None,
+        },
+        Err(e) => DecodeResult {
+            value: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"value":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Time `iterations` rounds of compiling `code`, satisfying it with
+/// `witness_data` and running it on the Bit Machine, in milliseconds.
+#[wasm_bindgen]
+pub fn benchmark(code: &str, witness_data: &str, iterations: u32) -> String {
+    let result = match benchmark::run(code, witness_data, iterations) {
+        Ok(report) => BenchmarkResult {
+            report: Some(report),
+            error: None,
+        },
+        Err(e) => BenchmarkResult {
+            report: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"report":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Run `code`, satisfied with `witness_data`, as the spender of a mock
+/// transaction, so introspection jets see the inputs, outputs, assets, lock
+/// time and version it gives.
+///
+/// `mock_json` is `{"version"?, "lock_time"?, "inputs", "outputs",
+/// "input_index"?}`; see [`mock_tx`] for the fields. A program that rejects
+/// the transaction is not an error: `run.accepted` is false and `run.error`
+/// says why.
+///
+/// `limits_json` is `{"max_cells"?, "max_frames"?, "max_cost"?,
+/// "timeout_ms"?}`, or blank for the defaults; see [`crate::limits`]. A program
+/// whose bounds exceed them is refused with a "Budget exceeded" error
+/// before it runs.
+#[wasm_bindgen]
+pub fn run_program(code: &str, witness_data: &str, mock_json: &str, limits_json: &str) -> String {
+    let result = ExecLimits::parse(limits_json).and_then(|limits| {
+        let mock = serde_json::from_str::<MockTx>(mock_json).map_err(|e| format!("Invalid mock transaction JSON: {}", e))?;
+        mock_tx::run(code, witness_data, &mock, compile_cmr(code, "")?, &limits)
+    });
+    let result = match result {
+        Ok(run) => MockRunResult {
+            run: Some(run),
+            error: None,
+        },
+        Err(e) => MockRunResult {
+            run: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"run":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Run `code` on the mock transaction in `mock_json`, as [`run_program`]
+/// does, and record its execution trace: every `case` branch, jet call and
+/// `dbg!` value, with the node's CMR and the jet's frame sizes. Programs
+/// over `limits_json` are refused as they are there.
+#[wasm_bindgen]
+pub fn trace_program(code: &str, witness_data: &str, mock_json: &str, limits_json: &str) -> String {
+    let result = ExecLimits::parse(limits_json).and_then(|limits| {
+        let mock = serde_json::from_str::<MockTx>(mock_json).map_err(|e| format!("Invalid mock transaction JSON: {}", e))?;
+        trace::record(code, witness_data, &mock, &limits)
+    });
+    let result = match result {
+        Ok(trace) => TraceResult {
+            text: Some(trace.to_text()),
+            trace: Some(trace),
+            error: None,
+        },
+        Err(e) => TraceResult {
+            trace: None,
+            text: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"trace":null,"text":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Verify a CSV list of `subject,expected_address` rows.
+///
+/// Subjects are CMRs or keys of `sources_json`, an object mapping file names
+/// to SimplicityHL source. The `csv` field holds the pass/fail report.
+#[wasm_bindgen]
+pub fn verify_address_list(csv: &str, sources_json: &str) -> String {
+    let sources = if sources_json.trim().is_empty() {
+        Ok(Default::default())
+    } else {
+        serde_json::from_str(sources_json).map_err(|e| format!("Invalid sources JSON: {}", e))
+    };
+    let result = match sources.and_then(|sources| Ok((bulk::parse_rows(csv)?, sources))) {
+        Ok((rows, sources)) => {
+            let report = bulk::verify(rows, &sources, |code| compile_cmr(code, ""));
+            BulkResult {
+                csv: Some(report.to_csv()),
+                report: Some(report),
+                error: None,
+            }
+        }
+        Err(e) => BulkResult {
+            report: None,
+            csv: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"report":null,"csv":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Rank likely causes of a failed spend of `code` with `witness_data`.
+///
+/// `error` is the message the attempt failed with. `env_json` is
+/// `{"block_height"?, "mock"?}`, or blank: the current chain tip, when known,
+/// for checking height locks, and the mock transaction to run the program on
+/// again, the default one if absent. An invalid `env_json` counts as blank.
+#[wasm_bindgen]
+pub fn triage_failure(code: &str, witness_data: &str, error: &str, env_json: &str) -> String {
+    let env = serde_json::from_str::<triage::TriageEnv>(env_json).unwrap_or_default();
+    let report = triage::triage(code, witness_data, error, env);
+    serde_json::to_string(&report).unwrap_or_else(|_| r#"{"causes":[]}"#.to_string())
+}
+
+/// The spend paths of `code` and the signatures, preimages and timelocks
+/// each requires.
+#[wasm_bindgen]
+pub fn spend_conditions(code: &str) -> String {
+    serde_json::to_string(&spend_paths::analyze(code))
+        .unwrap_or_else(|_| r#"{"paths":[],"truncated":false}"#.to_string())
+}
+
+/// Drop every cached compile outcome.
+#[wasm_bindgen]
+pub fn clear_compile_cache() {
+    cache::clear();
+}
+
+/// Entry count and hit/miss counters of the compile cache, as JSON.
+#[wasm_bindgen]
+pub fn compile_cache_stats() -> String {
+    serde_json::to_string(&cache::stats()).unwrap_or_else(|_| r#"{"entries":0,"hits":0,"misses":0}"#.to_string())
+}
+
+/// Heap size, live and peak allocations, and the peak of the last and the
+/// largest compile, in bytes, as JSON. The heap never shrinks; compare it
+/// with `max_compile_peak_bytes` to see what a program costs.
+#[wasm_bindgen]
+pub fn memory_stats() -> String {
+    serde_json::to_string(&memory::stats()).unwrap_or_else(|_| r#"{"heap_bytes":0}"#.to_string())
+}
+
+/// Check a BIP-340 signature. `pubkey`, `message` and `signature` are hex.
+#[wasm_bindgen]
+pub fn verify_signature(pubkey: &str, message: &str, signature: &str) -> String {
+    let result = match sigverify::verify_bip340(pubkey, message, signature) {
+        Ok(valid) => SignatureResult {
+            valid: Some(valid),
+            error: None,
+        },
+        Err(e) => SignatureResult {
+            valid: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"valid":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Check every signature in `witness_data` against the keys `code` uses and
+/// the 32-byte `sighash`.
+#[wasm_bindgen]
+pub fn verify_witness_signatures(code: &str, witness_data: &str, sighash: &str) -> String {
+    let result = match sigverify::verify_witness(code, witness_data, sighash) {
+        Ok(checks) => WitnessSignaturesResult {
+            checks: Some(checks),
+            error: None,
+        },
+        Err(e) => WitnessSignaturesResult {
+            checks: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"checks":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// A taproot tree given as JSON; blank is the single-leaf tree under the
+/// unspendable key.
+fn taproot_config(json: &str) -> Result<taproot::TaprootConfig, String> {
+    if json.trim().is_empty() {
+        return Ok(taproot::TaprootConfig::default());
+    }
+    serde_json::from_str(json).map_err(|e| format!("Invalid taproot config JSON: {}", e))
+}
+
+/// Build a raw transaction spending a contract output through its Simplicity
+/// leaf.
+///
+/// `utxo_json` is `{"txid", "vout", "value", "asset"?, "issuance"?, "sequence"?,
+/// "lock_time"?}` and `outputs_json` a list of `{"address", "amount_sat",
+/// "asset"?, "change"?, "confidential"?}`; the output marked `change` receives
+/// what is left of its asset after the fee. Outputs to confidential addresses
+/// are blinded unless `confidential` is `false`; their blinding factors come
+/// back in `blinded`. An `issuance` of `{"asset_amount", "token_amount",
+/// "contract_hash"?}` makes the new asset and tokens available to outputs;
+/// their ids come back in `issued`. `fee_rate` is in sat/vB. `taproot_json`
+/// is the tree the output is locked in, as for [`taproot_spend_info`], or
+/// blank for the single-leaf tree. The `tx_hex` field is ready to broadcast.
+#[wasm_bindgen]
+pub fn build_spend_tx(
+    program: &str,
+    witness: &str,
+    utxo_json: &str,
+    outputs_json: &str,
+    fee_rate: f64,
+    taproot_json: &str,
+) -> String {
+    let parsed = serde_json::from_str::<spend::SpendUtxo>(utxo_json)
+        .map_err(|e| format!("Invalid UTXO JSON: {}", e))
+        .and_then(|utxo| {
+            let outputs = serde_json::from_str::<Vec<spend::SpendOutput>>(outputs_json)
+                .map_err(|e| format!("Invalid outputs JSON: {}", e))?;
+            Ok((utxo, outputs, taproot_config(taproot_json)?))
+        });
+    let result = match parsed
+        .and_then(|(utxo, outputs, config)| spend::build(program, witness, &utxo, &outputs, fee_rate, &config))
+    {
+        Ok(tx) => SpendResult {
+            tx: Some(tx),
+            error: None,
+        },
+        Err(e) => SpendResult {
+            tx: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"tx":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Compute the hash `jet::sig_all_hash` returns for input `input_index` of
+/// `tx_hex`, for signing outside the page.
+///
+/// `utxos_json` lists every output the transaction spends, in input order,
+/// as `{"address" | "script_pubkey", "asset", "value"}`. `cmr` is the hex CMR
+/// of the program being spent and `taproot_json` its tree, as for
+/// [`build_spend_tx`].
+#[wasm_bindgen]
+pub fn compute_sighash(tx_hex: &str, input_index: u32, utxos_json: &str, cmr: &str, taproot_json: &str) -> String {
+    let result = match serde_json::from_str::<Vec<sighash::SighashUtxo>>(utxos_json)
+        .map_err(|e| format!("Invalid UTXOs JSON: {}", e))
+        .and_then(|utxos| sighash::compute(tx_hex, input_index, &utxos, cmr, &taproot_config(taproot_json)?))
+    {
+        Ok(sighash) => SighashResult {
+            sighash: Some(sighash),
+            error: None,
+        },
+        Err(e) => SighashResult {
+            sighash: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"sighash":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// The output descriptor of the contract with CMR `cmr`, for watch-only
+/// wallets.
+///
+/// `internal_key` is the x-only taproot internal key in hex; pass an empty
+/// string for the unspendable key the contract addresses use.
+#[wasm_bindgen]
+pub fn to_descriptor(cmr: &str, internal_key: &str) -> String {
+    let result = match parse_cmr(cmr)
+        .and_then(|cmr| Ok((cmr, descriptor::parse_internal_key(internal_key)?)))
+    {
+        Ok((cmr, internal_key)) => DescriptorResult {
+            descriptor: Some(descriptor::descriptor(cmr, &internal_key)),
+            error: None,
+        },
+        Err(e) => DescriptorResult {
+            descriptor: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"descriptor":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Build the taproot tree around the program with CMR `cmr` and report its
+/// keys, address and the Simplicity leaf's control block.
+///
+/// `config_json` is `{"internal_key": hex | null, "leaves": [hex, ...]}`;
+/// pass `{}` for the default single leaf under the unspendable key.
+#[wasm_bindgen]
+pub fn taproot_spend_info(cmr: &str, config_json: &str, network: &str) -> String {
+    let result = match parse_cmr(cmr)
+        .and_then(|cmr| Ok((cmr, network.parse::<Network>()?)))
+        .and_then(|(cmr, network)| {
+            let config = serde_json::from_str::<taproot::TaprootConfig>(config_json)
+                .map_err(|e| format!("Invalid taproot config JSON: {}", e))?;
+            taproot::report(cmr, &config, network)
+        })
+    {
+        Ok(spend_info) => TaprootResult {
+            spend_info: Some(spend_info),
+            error: None,
+        },
+        Err(e) => TaprootResult {
+            spend_info: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"spend_info":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// The control block spending the Simplicity leaf with CMR `cmr` out of the
+/// single-leaf tree under `internal_key` (hex; empty for the unspendable
+/// key).
+///
+/// `parity`, when given, is the expected output key parity (0 or 1) and is
+/// checked against the computed one.
+#[wasm_bindgen]
+pub fn control_block(internal_key: &str, cmr: &str, parity: Option<u8>) -> String {
+    let result = match parse_cmr(cmr).and_then(|cmr| taproot::leaf_control_block(internal_key, cmr, parity)) {
+        Ok(control_block) => ControlBlockResult {
+            control_block: Some(hex::encode(&control_block.serialize())),
+            error: None,
+        },
+        Err(e) => ControlBlockResult {
+            control_block: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"control_block":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Check that `control_block_hex` proves the leaf with CMR `cmr` is in the
+/// taproot tree of `output`, a taproot address or x-only output key in hex.
+#[wasm_bindgen]
+pub fn verify_control_block(control_block_hex: &str, cmr: &str, output: &str) -> String {
+    let result = match parse_cmr(cmr).and_then(|cmr| taproot::verify_control_block(control_block_hex, cmr, output)) {
+        Ok(check) => ControlBlockCheckResult {
+            check: Some(check),
+            error: None,
+        },
+        Err(e) => ControlBlockCheckResult {
+            check: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"check":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Classify the output script `script_hex`. Taproot outputs report their
+/// output key and, when it belongs to a program compiled on this page, that
+/// program's CMR.
+#[wasm_bindgen]
+pub fn decode_script_pubkey(script_hex: &str) -> String {
+    let result = match taproot::decode_script_pubkey(script_hex) {
+        Ok(info) => ScriptPubkeyResult {
+            script_pubkey: Some(info),
+            error: None,
+        },
+        Err(e) => ScriptPubkeyResult {
+            script_pubkey: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"script_pubkey":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// The script pubkey (hex) locking funds to the program with CMR `cmr`
+/// under `internal_key` (hex; empty for the unspendable key). Errors are
+/// thrown as strings.
+#[wasm_bindgen]
+pub fn script_pubkey_for(cmr: &str, internal_key: &str) -> Result<String, JsValue> {
+    parse_cmr(cmr)
+        .and_then(|cmr| taproot::script_pubkey_for(cmr, internal_key))
+        .map(|script| hex::encode(script.as_bytes()))
+        .map_err(|e| JsValue::from_str(&e))
+}
+
+/// Derive the public key at `path` (e.g. `0/5`; unhardened steps only) below
+/// the extended public key `xpub`. Programs and arguments may also write
+/// `xpub.../0/5` wherever a `Pubkey` goes; compiling derives it the same way.
+#[wasm_bindgen]
+pub fn derive_key(xpub: &str, path: &str) -> String {
+    let result = match bip32::derive_key(xpub, path) {
+        Ok(key) => DeriveKeyResult {
+            key: Some(key),
+            error: None,
+        },
+        Err(e) => DeriveKeyResult {
+            key: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"key":null,"error":"Serialization error"}"#.to_string())
+}
+
+fn lock_result(result: Result<locktime::EncodedLock, String>) -> String {
+    let result = match result {
+        Ok(lock) => LockResult {
+            lock: Some(lock),
+            error: None,
+        },
+        Err(e) => LockResult {
+            lock: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"lock":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Lock time for `check_lock_height` at block `height`.
+#[wasm_bindgen]
+pub fn lock_time_from_height(height: u32) -> String {
+    lock_result(locktime::lock_height(u64::from(height)))
+}
+
+/// Lock time for `check_lock_time` at `date`, anything `Date.parse`
+/// understands (e.g. `2025-01-31T12:00Z`).
+#[wasm_bindgen]
+pub fn lock_time_from_date(date: &str) -> String {
+    let ms = js_sys::Date::parse(date);
+    lock_result(if ms.is_nan() {
+        Err(format!("Cannot read `{date}` as a date"))
+    } else {
+        locktime::lock_time((ms / 1000.0).max(0.0) as u64)
+    })
+}
+
+/// Distance for `check_lock_distance` of `blocks` blocks, with the input
+/// sequence that satisfies it.
+#[wasm_bindgen]
+pub fn lock_distance_from_blocks(blocks: u32) -> String {
+    lock_result(locktime::lock_distance(u64::from(blocks)))
+}
+
+/// Duration for `check_lock_duration` of at least `seconds`, with the input
+/// sequence that satisfies it.
+#[wasm_bindgen]
+pub fn lock_duration_from_seconds(seconds: u32) -> String {
+    lock_result(locktime::lock_duration(u64::from(seconds)))
+}
+
+/// Run the test cases in `tests_json` against `code` on the Bit Machine.
+///
+/// `tests_json` is a list of `{"name", "witness", "args"?, "env"?, "expect"?}`
+/// cases; see [`test_runner`] for the environment fields.
+#[wasm_bindgen]
+pub fn run_tests(code: &str, tests_json: &str) -> String {
+    let result = match serde_json::from_str::<Vec<test_runner::TestCase>>(tests_json) {
+        Ok(tests) => {
+            let results = test_runner::run(code, &tests);
+            let passed = results.iter().filter(|r| r.passed).count();
+            TestRunResult {
+                failed: results.len() - passed,
+                passed,
+                results,
+                error: None,
+            }
+        }
+        Err(e) => TestRunResult {
+            results: Vec::new(),
+            passed: 0,
+            failed: 0,
+            error: Some(format!("Invalid tests JSON: {}", e)),
+        },
+    };
+    serde_json::to_string(&result)
+        .unwrap_or_else(|_| r#"{"results":[],"passed":0,"failed":0,"error":"Serialization error"}"#.to_string())
+}
+
+/// Which `match` arms and jets of `code` the cases in `tests_json` execute.
+///
+/// Takes the same spec as [`run_tests`]; only the cases the program accepts
+/// count towards coverage.
+#[wasm_bindgen]
+pub fn coverage(code: &str, tests_json: &str) -> String {
+    let result = match serde_json::from_str::<Vec<test_runner::TestCase>>(tests_json) {
+        Ok(tests) => CoverageResult {
+            report: Some(coverage::coverage(code, &tests)),
+            error: None,
+        },
+        Err(e) => CoverageResult {
+            report: None,
+            error: Some(format!("Invalid tests JSON: {}", e)),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"report":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Satisfy `code` with `witness_data` and report which branches the witness
+/// pruned and the weight that saves.
+#[wasm_bindgen]
+pub fn pruning_report(code: &str, witness_data: &str) -> String {
+    let result = match pruning::analyze(code, witness_data) {
+        Ok(report) => PruningResult {
+            report: Some(report),
+            error: None,
+        },
+        Err(e) => PruningResult {
+            report: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"report":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Worst-case witness size and input weight of each spend path, from the
+/// witness types alone, for planning fees before a witness exists.
+#[wasm_bindgen]
+pub fn estimate_witness_size(code: &str) -> String {
+    let result = match witness_size::estimate(code) {
+        Ok(estimate) => WitnessSizeResult {
+            estimate: Some(estimate),
+            error: None,
+        },
+        Err(e) => WitnessSizeResult {
+            estimate: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"estimate":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// A random but reproducible witness for the program: the same `seed`
+/// always gives the same values. Signatures sign `sighash`, 32 bytes as
+/// hex, with keys derived from the seed; a blank sighash signs zeros.
+#[wasm_bindgen]
+pub fn random_witness(code: &str, seed: &str, sighash: &str) -> String {
+    let result = match witness_gen::generate(code, seed, sighash) {
+        Ok(witness) => RandomWitnessResult {
+            witness: Some(witness),
+            error: None,
+        },
+        Err(e) => RandomWitnessResult {
+            witness: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"witness":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Shrink a failing program to a small one that fails the same way, for
+/// bug reports.
+///
+/// `failure_json` is `{"kind": "compile", "message"?}` for a compile error,
+/// or `{"kind": "run", "witness"?, "mock", "message"?}` for a failed run on
+/// a mock transaction. The failure is kept while the error contains
+/// `message`; without one, the original program's error is kept.
+#[wasm_bindgen]
+pub fn minimize_program(code: &str, failure_json: &str) -> String {
+    let result = serde_json::from_str::<minimize::Failure>(failure_json)
+        .map_err(|e| format!("Invalid failure JSON: {}", e))
+        .and_then(|failure| minimize::minimize(code, &failure));
+    let result = match result {
+        Ok(reduction) => MinimizeResult {
+            reduction: Some(reduction),
+            error: None,
+        },
+        Err(e) => MinimizeResult {
+            reduction: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"reduction":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// Compile the program and build an audit report for it.
+///
+/// `witness_data` may be empty; the AMR is only reported when it satisfies
+/// the program. `network` is one of `liquid`, `liquidtestnet` or `elements`,
+/// and `taproot_json` the tree the address is for, as for [`build_spend_tx`].
+#[wasm_bindgen]
+pub fn compilation_report(code: &str, witness_data: &str, network: &str, taproot_json: &str) -> String {
+    let result = match network
+        .parse::<Network>()
+        .and_then(|network| report::build(code, witness_data, network, &taproot_config(taproot_json)?))
+    {
+        Ok(report) => ReportResult {
+            html: Some(report.to_html()),
+            report: Some(report),
+            error: None,
+        },
+        Err(e) => ReportResult {
+            report: None,
+            html: None,
+            error: Some(e),
+        },
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"report":null,"html":null,"error":"Serialization error"}"#.to_string())
+}
+
+/// JSON Schema of the witness format, as a JSON string.
+#[wasm_bindgen]
+pub fn witness_schema() -> String {
+    schema::witness_schema().to_string()
+}
+
+/// JSON Schema of parameter values in JSON form, as a JSON string.
+#[wasm_bindgen]
+pub fn arguments_schema() -> String {
+    schema::arguments_schema().to_string()
+}
+
+/// Validate witness or argument JSON, with a JSON Pointer for each error.
+#[wasm_bindgen]
+pub fn validate_values(json: &str) -> String {
+    let errors = schema::validate(json);
+    let result = SchemaResult {
+        valid: errors.is_empty(),
+        errors,
+    };
+    serde_json::to_string(&result).unwrap_or_else(|_| r#"{"valid":false,"errors":[]}"#.to_string())
+}
@@ -0,0 +1,149 @@
+//! Typed fields for the witness a program reads.
+//!
+//! The field list comes from the `witness::NAME` references in the source.
+//! Types come from the current witness JSON when it names them, otherwise
+//! from an annotated binding such as `let sig: Signature = witness::SIG;`.
+//! Fields whose type cannot be found are edited as raw values. A `///`
+//! comment on the witness in the source becomes the field's description.
+
+use serde_json::{Map, Value};
+
+use crate::annotations;
+use crate::locktime;
+use crate::sanity::{qualified_names, strip_comment};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WitnessField {
+    pub name: String,
+    /// SimplicityHL type, empty when unknown.
+    pub ty: String,
+    /// Value in witness JSON syntax, e.g. `0x01..`, `true` or `42`.
+    pub value: String,
+    /// The `///` comment documenting the witness in the source.
+    pub doc: Option<String>,
+}
+
+/// How a field is edited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    Bool,
+    /// Unsigned integer of this many bits.
+    Number(u32),
+    /// Fixed-size byte string written as hex.
+    Hex(usize),
+    /// A UNIX timestamp, picked as a date.
+    Date,
+    Text,
+}
+
+impl InputKind {
+    pub fn of(ty: &str) -> InputKind {
+        let ty = ty.trim();
+        match ty {
+            "bool" => InputKind::Bool,
+            "u1" | "u2" | "u4" | "u8" | "u16" | "u32" | "u64" => InputKind::Number(ty[1..].parse().unwrap_or(64)),
+            "Height" | "Lock" => InputKind::Number(32),
+            "Distance" | "Duration" => InputKind::Number(16),
+            "Time" => InputKind::Date,
+            "u128" => InputKind::Hex(16),
+            "u256" | "Pubkey" | "Message" | "Scalar" | "Fe" | "Ctx8" => InputKind::Hex(32),
+            "Signature" | "Message64" => InputKind::Hex(64),
+            _ => match byte_array_len(ty) {
+                Some(len) => InputKind::Hex(len),
+                None => InputKind::Text,
+            },
+        }
+    }
+
+    /// Check `value` fits this kind.
+    pub fn validate(self, value: &str) -> Result<(), String> {
+        let value = value.trim();
+        match self {
+            InputKind::Bool if value == "true" || value == "false" => Ok(()),
+            InputKind::Bool => Err("Expected true or false".to_string()),
+            InputKind::Number(bits) => check_number(value, bits),
+            InputKind::Date => {
+                let secs = value.parse().map_err(|_| "Expected a UNIX time in seconds".to_string())?;
+                locktime::lock_time(secs).map(|_| ())
+            }
+            InputKind::Hex(len) => {
+                let digits = value.strip_prefix("0x").ok_or("Hex values start with 0x")?;
+                if digits.len() != 2 * len || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(format!("Expected {len} bytes ({} hex digits)", 2 * len));
+                }
+                Ok(())
+            }
+            _ if value.is_empty() => Err("Enter a value".to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn check_number(value: &str, bits: u32) -> Result<(), String> {
+    let n: u64 = value.parse().map_err(|_| "Expected a whole number".to_string())?;
+    if bits < 64 && n >> bits != 0 {
+        return Err(format!("Does not fit in {bits} bits"));
+    }
+    Ok(())
+}
+
+/// `N` in `[u8; N]`.
+fn byte_array_len(ty: &str) -> Option<usize> {
+    let inner = ty.strip_prefix('[')?.strip_suffix(']')?;
+    let (element, len) = inner.split_once(';')?;
+    (element.trim() == "u8").then(|| len.trim().parse().ok()).flatten()
+}
+
+/// The type annotated on a binding of `witness::name`, if any.
+fn annotated_type(code: &str, name: &str) -> Option<String> {
+    let target = format!("witness::{name}");
+    code.lines().find_map(|line| {
+        let line = strip_comment(line);
+        let rest = line.trim().strip_prefix("let ")?;
+        let (binding, value) = rest.split_once('=')?;
+        if value.trim().trim_end_matches(';').trim() != target {
+            return None;
+        }
+        let (_, ty) = binding.split_once(':')?;
+        Some(ty.trim().to_string())
+    })
+}
+
+/// The witness fields `code` reads, filled from `witness_json`.
+pub fn fields(code: &str, witness_json: &str) -> Vec<WitnessField> {
+    let witness = serde_json::from_str::<Value>(witness_json).unwrap_or_default();
+    let docs = annotations::extract(code);
+    let mut fields: Vec<WitnessField> = Vec::new();
+    for line in code.lines() {
+        for name in qualified_names(strip_comment(line), "witness::") {
+            if fields.iter().any(|f| f.name == name) {
+                continue;
+            }
+            let entry = witness.get(name);
+            let text = |key: &str| entry.and_then(|e| e.get(key)).and_then(Value::as_str).map(str::to_string);
+            fields.push(WitnessField {
+                name: name.to_string(),
+                ty: text("type").or_else(|| annotated_type(code, name)).unwrap_or_default(),
+                value: text("value").unwrap_or_default(),
+                doc: docs.witnesses.get(name).cloned(),
+            });
+        }
+    }
+    fields
+}
+
+/// Witness JSON for `fields`. Entries of `previous_json` the program does
+/// not read are kept, so switching editors never loses data.
+pub fn to_json(fields: &[WitnessField], previous_json: &str) -> String {
+    let mut witness = match serde_json::from_str::<Value>(previous_json) {
+        Ok(Value::Object(map)) => map,
+        _ => Map::new(),
+    };
+    for field in fields {
+        witness.insert(
+            field.name.clone(),
+            serde_json::json!({ "value": field.value, "type": field.ty }),
+        );
+    }
+    serde_json::to_string_pretty(&Value::Object(witness)).unwrap_or_default()
+}
@@ -0,0 +1,251 @@
+//! Reproducible random witnesses, for demos and fuzzing.
+//!
+//! Every value is drawn from a stream of SHA-256 blocks of the seed, so the
+//! same seed and program always give the same witness and a seed is enough
+//! to share an example. Keys come from the seed as well: the first `Pubkey`
+//! witness takes the first key, the next one the second, and likewise for
+//! `Signature` witnesses, which sign the given sighash. A key ring of one
+//! pubkey witness and one signature witness therefore verifies. For each
+//! 32-byte witness the SHA-256 of its value is listed, so it can serve as
+//! a preimage of a hash given as a parameter.
+//!
+//! Types come from the same places as in the witness size estimate. Sum
+//! types take a random side, which may not be the side a spend path needs.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use simplicityhl::simplicity::elements::secp256k1_zkp::{Keypair, Message, Secp256k1, SecretKey};
+use simplicityhl::simplicity::hashes::{sha256, Hash};
+
+use crate::hex;
+use crate::spend;
+use crate::witness_size::{generic_args, split_top, type_aliases, witness_types};
+
+/// Deepest type nesting followed, which also stops alias cycles.
+const MAX_DEPTH: usize = 16;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedKey {
+    pub secret: String,
+    pub pubkey: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct RandomWitness {
+    /// Witness JSON with a value and type for each witness.
+    pub witness_json: String,
+    /// Keys used, in the order the witnesses take them.
+    pub keys: Vec<GeneratedKey>,
+    /// Witness name → SHA-256 of its value, for 32-byte witnesses.
+    pub hashes: BTreeMap<String, String>,
+    /// Witnesses left out and other things to know.
+    pub notes: Vec<String>,
+}
+
+/// The `index`-th key of `seed`.
+fn keypair(seed: &str, index: usize) -> Keypair {
+    let secp = Secp256k1::new();
+    let mut attempt = 0u32;
+    loop {
+        let digest = sha256::Hash::hash(format!("{seed}/key/{index}/{attempt}").as_bytes()).to_byte_array();
+        // Fails only for zero or values above the curve order.
+        if let Ok(secret) = SecretKey::from_slice(&digest) {
+            return Keypair::from_secret_key(&secp, &secret);
+        }
+        attempt += 1;
+    }
+}
+
+struct Generator {
+    seed: String,
+    counter: u64,
+    pool: Vec<u8>,
+    aliases: HashMap<String, String>,
+    sighash: [u8; 32],
+    pubkeys: usize,
+    signatures: usize,
+    keys: Vec<GeneratedKey>,
+}
+
+impl Generator {
+    fn bytes(&mut self, len: usize) -> Vec<u8> {
+        while self.pool.len() < len {
+            let block = sha256::Hash::hash(format!("{}/{}", self.seed, self.counter).as_bytes());
+            self.pool.extend_from_slice(&block.to_byte_array());
+            self.counter += 1;
+        }
+        self.pool.drain(..len).collect()
+    }
+
+    fn number(&mut self, bits: u32) -> u64 {
+        let bytes = self.bytes(8);
+        let n = u64::from_be_bytes(bytes.try_into().unwrap_or_default());
+        if bits >= 64 {
+            n
+        } else {
+            n & ((1 << bits) - 1)
+        }
+    }
+
+    fn hex(&mut self, len: usize) -> String {
+        format!("0x{}", hex::encode(&self.bytes(len)))
+    }
+
+    /// The `index`-th key, recorded in the key ring.
+    fn key(&mut self, index: usize) -> Keypair {
+        while self.keys.len() <= index {
+            let next = keypair(&self.seed, self.keys.len());
+            self.keys.push(GeneratedKey {
+                secret: hex::encode(&next.secret_bytes()),
+                pubkey: hex::encode(&next.x_only_public_key().0.serialize()),
+            });
+        }
+        keypair(&self.seed, index)
+    }
+
+    /// A random literal of type `ty`.
+    fn value(&mut self, ty: &str, depth: usize) -> Result<String, String> {
+        let ty = ty.trim();
+        if depth > MAX_DEPTH {
+            return Err(format!("type `{ty}` nests too deeply"));
+        }
+        if let Some(target) = self.aliases.get(ty).cloned() {
+            return self.value(&target, depth + 1);
+        }
+        let value = match ty {
+            "()" => "()".to_string(),
+            "bool" => (self.number(1) == 1).to_string(),
+            "u1" | "u2" | "u4" | "u8" | "u16" | "u32" | "u64" => self.number(ty[1..].parse().unwrap_or(64)).to_string(),
+            "Height" | "Lock" | "Time" => self.number(32).to_string(),
+            "Distance" | "Duration" => self.number(16).to_string(),
+            "u128" => self.hex(16),
+            "u256" | "Message" | "Scalar" | "Fe" => self.hex(32),
+            "Message64" => self.hex(64),
+            "Pubkey" => {
+                let keypair = self.key(self.pubkeys);
+                self.pubkeys += 1;
+                format!("0x{}", hex::encode(&keypair.x_only_public_key().0.serialize()))
+            }
+            "Signature" => {
+                let keypair = self.key(self.signatures);
+                self.signatures += 1;
+                let signature =
+                    Secp256k1::new().sign_schnorr_no_aux_rand(&Message::from_digest(self.sighash), &keypair);
+                format!("0x{}", hex::encode(signature.as_ref()))
+            }
+            _ if ty.starts_with('(') && ty.ends_with(')') => {
+                let parts = split_top(&ty[1..ty.len() - 1]);
+                let parts: Vec<&str> = parts.into_iter().filter(|part| !part.is_empty()).collect();
+                let values = parts
+                    .iter()
+                    .map(|part| self.value(part, depth + 1))
+                    .collect::<Result<Vec<_>, _>>()?;
+                match values.as_slice() {
+                    [one] => format!("({one},)"),
+                    _ => format!("({})", values.join(", ")),
+                }
+            }
+            _ if ty.starts_with('[') && ty.ends_with(']') => {
+                let (element, len) = ty[1..ty.len() - 1]
+                    .rsplit_once(';')
+                    .ok_or_else(|| format!("unsupported type `{ty}`"))?;
+                let len: usize = len.trim().parse().map_err(|_| format!("unsupported array length in `{ty}`"))?;
+                let values = (0..len)
+                    .map(|_| self.value(element, depth + 1))
+                    .collect::<Result<Vec<_>, _>>()?;
+                format!("[{}]", values.join(", "))
+            }
+            _ => {
+                if let Some(args) = generic_args(ty, "Either").filter(|args| args.len() == 2) {
+                    if self.number(1) == 0 {
+                        format!("Left({})", self.value(args[0], depth + 1)?)
+                    } else {
+                        format!("Right({})", self.value(args[1], depth + 1)?)
+                    }
+                } else if let Some(args) = generic_args(ty, "Option").filter(|args| args.len() == 1) {
+                    if self.number(1) == 0 {
+                        "None".to_string()
+                    } else {
+                        format!("Some({})", self.value(args[0], depth + 1)?)
+                    }
+                } else {
+                    return Err(format!("cannot generate values of type `{ty}`"));
+                }
+            }
+        };
+        Ok(value)
+    }
+}
+
+/// Fill the witness of `code` from `seed`. Signatures sign `sighash_hex`,
+/// 32 bytes as hex; when blank they sign 32 zero bytes.
+pub fn generate(code: &str, seed: &str, sighash_hex: &str) -> Result<RandomWitness, String> {
+    if seed.trim().is_empty() {
+        return Err("Enter a seed".to_string());
+    }
+    let mut notes = Vec::new();
+    let sighash = if sighash_hex.trim().is_empty() {
+        [0u8; 32]
+    } else {
+        let bytes = hex::decode(sighash_hex.trim().trim_start_matches("0x"))?;
+        bytes.try_into().map_err(|_| "The sighash must be 32 bytes".to_string())?
+    };
+
+    let mut generator = Generator {
+        seed: seed.trim().to_string(),
+        counter: 0,
+        pool: Vec::new(),
+        aliases: type_aliases(code),
+        sighash,
+        pubkeys: 0,
+        signatures: 0,
+        keys: Vec::new(),
+    };
+    let mut witness = Map::new();
+    let mut hashes = BTreeMap::new();
+    let mut complete = true;
+    for (name, ty) in witness_types(code) {
+        if ty.is_empty() {
+            notes.push(format!("`{name}` left out: its type is not annotated"));
+            complete = false;
+            continue;
+        }
+        match generator.value(&ty, 0) {
+            Ok(value) => {
+                let digits = value.strip_prefix("0x").filter(|digits| digits.len() == 64);
+                if let Some(bytes) = digits.and_then(|digits| hex::decode(digits).ok()) {
+                    if ty != "Pubkey" {
+                        hashes.insert(name.clone(), hex::encode(&sha256::Hash::hash(&bytes).to_byte_array()));
+                    }
+                }
+                witness.insert(name, serde_json::json!({ "value": value, "type": ty }));
+            }
+            Err(e) => {
+                notes.push(format!("`{name}` left out: {e}"));
+                complete = false;
+            }
+        }
+    }
+    if witness.is_empty() && complete {
+        notes.push("The program reads no witness".to_string());
+    }
+    if generator.signatures > 0 && sighash_hex.trim().is_empty() {
+        notes.push("No sighash given: signatures sign 32 zero bytes".to_string());
+    }
+
+    let witness_json = serde_json::to_string_pretty(&Value::Object(witness)).unwrap_or_default();
+    // Only a complete witness can satisfy the program.
+    if complete {
+        if let Err(e) = spend::satisfy(code, &witness_json) {
+            notes.push(format!("The witness does not fit the program: {e}"));
+        }
+    }
+    Ok(RandomWitness {
+        witness_json,
+        keys: generator.keys,
+        hashes,
+        notes,
+    })
+}
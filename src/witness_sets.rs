@@ -0,0 +1,143 @@
+//! Named witness sets: several witnesses kept side by side for one program.
+//!
+//! A program usually has more than one way to be spent, e.g. a "happy path"
+//! and a "timeout path", each with its own witness. The sets keep one
+//! witness per path; the active one is what the witness editor shows and
+//! what compiles use. Every set can be run as a passing test case, which is
+//! how "run all" checks that each path is still satisfiable.
+
+use serde::{Deserialize, Serialize};
+
+use crate::storage;
+use crate::test_runner::TestCase;
+
+const SETS_KEY: &str = "witness-sets";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WitnessSet {
+    pub name: String,
+    /// Witness JSON, as in the witness editor.
+    pub witness: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WitnessSets {
+    /// Never empty.
+    pub sets: Vec<WitnessSet>,
+    pub active: usize,
+}
+
+impl Default for WitnessSets {
+    fn default() -> Self {
+        WitnessSets {
+            sets: vec![WitnessSet {
+                name: "Default".to_string(),
+                witness: String::new(),
+            }],
+            active: 0,
+        }
+    }
+}
+
+impl WitnessSets {
+    pub fn load() -> Self {
+        storage::load(SETS_KEY)
+            .and_then(|json| serde_json::from_str::<WitnessSets>(&json).ok())
+            .filter(|sets| !sets.sets.is_empty())
+            .map(|mut sets| {
+                sets.active = sets.active.min(sets.sets.len() - 1);
+                sets
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Ok(json) = serde_json::to_string(self) {
+            storage::save(SETS_KEY, &json);
+        }
+    }
+
+    pub fn active_set(&self) -> &WitnessSet {
+        &self.sets[self.active]
+    }
+
+    /// Record `witness` as the active set's witness.
+    pub fn set_active_witness(&mut self, witness: &str) {
+        self.sets[self.active].witness = witness.to_string();
+    }
+
+    /// Make set `index` active and return its witness.
+    pub fn select(&mut self, index: usize) -> String {
+        self.active = index.min(self.sets.len() - 1);
+        self.active_set().witness.clone()
+    }
+
+    fn check_name(&self, name: &str, except: Option<usize>) -> Result<String, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("A witness set needs a name".to_string());
+        }
+        if self.sets.iter().enumerate().any(|(i, s)| Some(i) != except && s.name == name) {
+            return Err(format!("There is already a witness set named \"{name}\""));
+        }
+        Ok(name.to_string())
+    }
+
+    /// A name for a new set that no set has yet: "Set 2", "Set 3", ...
+    pub fn next_name(&self) -> String {
+        (self.sets.len() + 1..)
+            .map(|n| format!("Set {n}"))
+            .find(|name| self.sets.iter().all(|s| &s.name != name))
+            .unwrap_or_default()
+    }
+
+    /// Add a set holding `witness` and make it active.
+    pub fn add(&mut self, name: &str, witness: &str) -> Result<(), String> {
+        let name = self.check_name(name, None)?;
+        self.sets.push(WitnessSet {
+            name,
+            witness: witness.to_string(),
+        });
+        self.active = self.sets.len() - 1;
+        Ok(())
+    }
+
+    pub fn rename(&mut self, index: usize, name: &str) -> Result<(), String> {
+        let name = self.check_name(name, Some(index))?;
+        if let Some(set) = self.sets.get_mut(index) {
+            set.name = name;
+        }
+        Ok(())
+    }
+
+    /// Remove set `index`, unless it is the last one. The set before it
+    /// becomes active if it was.
+    pub fn remove(&mut self, index: usize) {
+        if self.sets.len() <= 1 || index >= self.sets.len() {
+            return;
+        }
+        self.sets.remove(index);
+        if self.active >= index && self.active > 0 {
+            self.active -= 1;
+        }
+    }
+
+    /// One passing test case per set, named after it. An unparsable witness
+    /// is passed on as is, so the run reports it against that set.
+    pub fn to_tests(&self) -> Vec<TestCase> {
+        self.sets
+            .iter()
+            .map(|set| TestCase {
+                name: set.name.clone(),
+                witness: if set.witness.trim().is_empty() {
+                    serde_json::Value::Null
+                } else {
+                    serde_json::from_str(&set.witness).unwrap_or_else(|_| serde_json::Value::String(set.witness.clone()))
+                },
+                args: None,
+                env: Default::default(),
+                expect: Default::default(),
+            })
+            .collect()
+    }
+}
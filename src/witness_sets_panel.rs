@@ -0,0 +1,140 @@
+//! Tab strip for switching between named witness sets, with "run all".
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::api_types::TestRunResult;
+use crate::compiler;
+use crate::i18n::{self, t, Msg};
+use crate::test_runner::TestOutcome;
+use crate::witness_sets::WitnessSets;
+
+#[component]
+pub fn WitnessSetTabs(
+    #[prop(into)] code: Signal<String>,
+    /// Saved on every change.
+    sets: RwSignal<WitnessSets>,
+    witness: ReadSignal<String>,
+    set_witness: WriteSignal<String>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let (error, set_error) = signal(None::<String>);
+    let (busy, set_busy) = signal(false);
+    // Outcomes of the last "run all", by set name.
+    let (outcomes, set_outcomes) = signal(Vec::<TestOutcome>::new());
+
+    // The editor always holds the active set's witness.
+    Effect::new(move |_| {
+        let witness = witness.get();
+        sets.update(|sets| sets.set_active_witness(&witness));
+    });
+    Effect::new(move |_| sets.with(WitnessSets::save));
+
+    let select = move |index: usize| {
+        let mut selected = String::new();
+        sets.update(|sets| selected = sets.select(index));
+        set_witness.set(selected);
+        set_error.set(None);
+    };
+
+    let add = move |_| {
+        let result = sets.try_update(|sets| {
+            let name = sets.next_name();
+            sets.add(&name, "")
+        });
+        match result {
+            Some(Ok(())) => {
+                set_witness.set(String::new());
+                set_error.set(None);
+            }
+            Some(Err(e)) => set_error.set(Some(e)),
+            None => {}
+        }
+    };
+
+    let rename = move |name: String| {
+        let result = sets.try_update(|sets| sets.rename(sets.active, &name));
+        set_error.set(result.and_then(Result::err));
+    };
+
+    let remove = move |_| {
+        let mut selected = String::new();
+        sets.update(|sets| {
+            sets.remove(sets.active);
+            selected = sets.active_set().witness.clone();
+        });
+        set_witness.set(selected);
+    };
+
+    let run_all = move |_| {
+        let code = code.get_untracked();
+        let tests = serde_json::to_string(&sets.with_untracked(WitnessSets::to_tests)).unwrap_or_default();
+        set_busy.set(true);
+        spawn_local(async move {
+            let json = compiler::run_tests(&code, &tests).await;
+            match serde_json::from_str::<TestRunResult>(&json) {
+                Ok(TestRunResult { error: Some(e), .. }) => set_error.set(Some(e)),
+                Ok(result) => {
+                    set_error.set(None);
+                    set_outcomes.set(result.results);
+                }
+                Err(e) => set_error.set(Some(e.to_string())),
+            }
+            set_busy.set(false);
+        });
+    };
+
+    let tabs = move || {
+        let (names, active) = sets.with(|sets| (sets.sets.iter().map(|s| s.name.clone()).collect::<Vec<_>>(), sets.active));
+        names
+            .into_iter()
+            .enumerate()
+            .map(|(index, name)| {
+                let outcome = outcomes.with(|outcomes| outcomes.iter().find(|o| o.name == name).cloned());
+                let badge = outcome.map(|o| {
+                    let (class, mark) = if o.passed { ("status-badge paid", "✓") } else { ("status-badge expired", "✗") };
+                    view! { <span class=class title=o.error.unwrap_or_default()>{mark}</span> }
+                });
+                view! {
+                    <button
+                        class="witness-tab"
+                        class:active=index == active
+                        role="tab"
+                        aria-selected=(index == active).to_string()
+                        on:click=move |_| select(index)
+                    >
+                        {name}
+                        {badge}
+                    </button>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <div class="witness-tabs">
+            <div class="witness-tab-strip" role="tablist" aria-label=t(Msg::WitnessSets)>
+                {tabs}
+                <button class="witness-tab" title=t(Msg::AddWitnessSet) on:click=add>"+"</button>
+            </div>
+            <div class="witness-tab-actions">
+                <input
+                    aria-label=t(Msg::WitnessSetName)
+                    prop:value=move || sets.with(|sets| sets.active_set().name.clone())
+                    on:change=move |ev| rename(event_target_value(&ev))
+                />
+                <button
+                    class="secondary"
+                    disabled=move || sets.with(|sets| sets.sets.len() <= 1)
+                    on:click=remove
+                >
+                    {t(Msg::Remove)}
+                </button>
+                <button on:click=run_all disabled=move || busy.get()>
+                    {move || i18n::text(lang.get(), if busy.get() { Msg::Running } else { Msg::RunAllSets })}
+                </button>
+            </div>
+            {move || error.get().map(|e| view! { <p class="tool-error">{e}</p> })}
+        </div>
+    }
+}
@@ -0,0 +1,360 @@
+//! Worst-case witness size of each spend path, before any witness exists.
+//!
+//! A witness value takes exactly as many bits as its type allows at most:
+//! a signature is always 64 bytes, a `u32` always 32 bits, and a sum type a
+//! tag bit plus its larger side. So the size of a spend can be bounded from
+//! the types alone. Each path counts the witnesses it reads; where a path
+//! matches on a witness, only the side its arm takes is counted. Types come
+//! from annotated bindings such as `let sig: Signature = witness::SIG;` or
+//! from the typed patterns of a `match` on the witness.
+//!
+//! The compiled program bounds the whole witness as well: every witness
+//! node of the program at its largest, whichever path is taken.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use simplicityhl::simplicity::dag::{DagLike, MaxSharing};
+use simplicityhl::simplicity::jet::Elements;
+use simplicityhl::simplicity::node::{Commit, Inner};
+
+use crate::coverage::{layout, ArmRef, Layout};
+use crate::options::CompileOptions;
+use crate::sanity::{qualified_names, strip_comment};
+use crate::spend_paths::arm_paths;
+use crate::witness_form;
+
+/// Bytes of the leaf script, which is the CMR.
+const LEAF_SCRIPT_BYTES: usize = 32;
+/// Control block of a single-leaf tree: version byte and internal key.
+const CONTROL_BLOCK_BYTES: usize = 33;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WitnessItem {
+    pub name: String,
+    /// SimplicityHL type, empty when it could not be found.
+    pub ty: String,
+    /// Largest encoding of the value on this path, `None` for an unknown type.
+    pub bits: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PathEstimate {
+    /// The arm taken at each match, as in the spend conditions.
+    pub choices: Vec<String>,
+    pub witnesses: Vec<WitnessItem>,
+    /// Witness data in bytes, counting only witnesses of known type.
+    pub witness_bytes: usize,
+    /// Witness weight of the input: witness, program, leaf script and
+    /// control block, each with its length prefix.
+    pub weight: usize,
+    /// Every witness on the path has a known type.
+    pub complete: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct WitnessEstimate {
+    pub paths: Vec<PathEstimate>,
+    /// More paths exist than are listed.
+    pub truncated: bool,
+    /// The encoded program before pruning; a spend usually prunes it.
+    pub program_bytes: usize,
+    /// All witness nodes of the program at their largest, in bytes.
+    pub max_witness_bytes: usize,
+}
+
+/// Split `s` at commas outside brackets.
+pub(crate) fn split_top(s: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0usize, 0);
+    for (at, c) in s.char_indices() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(s[start..at].trim());
+                start = at + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(s[start..].trim());
+    parts.retain(|p| !p.is_empty());
+    parts
+}
+
+/// The arguments of `name<...>`.
+pub(crate) fn generic_args<'a>(ty: &'a str, name: &str) -> Option<Vec<&'a str>> {
+    let inner = ty.strip_prefix(name)?.trim_start().strip_prefix('<')?.strip_suffix('>')?;
+    Some(split_top(inner))
+}
+
+/// Most bits a value of type `ty` encodes to, with `type` aliases of the
+/// program resolved. `None` for types it does not know.
+pub fn max_bits(ty: &str, aliases: &HashMap<String, String>) -> Option<usize> {
+    bits_at(ty, aliases, 0)
+}
+
+fn bits_at(ty: &str, aliases: &HashMap<String, String>, depth: usize) -> Option<usize> {
+    // Aliases that refer to themselves would recurse forever.
+    if depth > 32 {
+        return None;
+    }
+    let size = |ty: &str| bits_at(ty, aliases, depth + 1);
+    let ty = ty.trim();
+    let builtin = match ty {
+        "()" => Some(0),
+        "bool" | "u1" => Some(1),
+        "u2" => Some(2),
+        "u4" => Some(4),
+        "u8" => Some(8),
+        "u16" | "Distance" | "Duration" => Some(16),
+        "u32" | "Height" | "Time" | "Lock" => Some(32),
+        "u64" => Some(64),
+        "u128" => Some(128),
+        "u256" | "Pubkey" | "Message" | "Scalar" | "Fe" | "ExplicitAsset" => Some(256),
+        "Point" => Some(257),
+        "Signature" | "Message64" | "Ge" => Some(512),
+        "Gej" => Some(768),
+        "Outpoint" => Some(288),
+        "Asset1" | "Amount1" | "TokenAmount1" => Some(258),
+        "Nonce" => Some(259),
+        _ => None,
+    };
+    if builtin.is_some() {
+        return builtin;
+    }
+    if let Some(inner) = ty.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+        return split_top(inner).into_iter().map(size).sum();
+    }
+    if let Some(inner) = ty.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+        let (element, len) = inner.rsplit_once(';')?;
+        return size(element)?.checked_mul(len.trim().parse().ok()?);
+    }
+    if let Some(args) = generic_args(ty, "Either") {
+        let [left, right] = args[..] else { return None };
+        return Some(1 + size(left)?.max(size(right)?));
+    }
+    if let Some(args) = generic_args(ty, "Option") {
+        let [inner] = args[..] else { return None };
+        return Some(1 + size(inner)?);
+    }
+    if let Some(args) = generic_args(ty, "List") {
+        // Fewer than `bound` elements, stored as optional blocks of 1, 2, 4… .
+        let [element, bound] = args[..] else { return None };
+        let (element, bound) = (size(element)?, bound.parse::<usize>().ok()?);
+        if !bound.is_power_of_two() || bound < 2 {
+            return None;
+        }
+        return Some((0..bound.trailing_zeros()).map(|i| 1 + (element << i)).sum());
+    }
+    aliases.get(ty).and_then(|target| size(target))
+}
+
+/// `type NAME = TYPE;` declarations of `code`.
+pub(crate) fn type_aliases(code: &str) -> HashMap<String, String> {
+    code.lines()
+        .filter_map(|line| {
+            let rest = strip_comment(line).trim().strip_prefix("type ")?;
+            let (name, ty) = rest.split_once('=')?;
+            Some((name.trim().to_string(), ty.trim().trim_end_matches(';').trim().to_string()))
+        })
+        .collect()
+}
+
+/// The witness `expr` reads, directly or through a `let` binding.
+fn witness_of<'a>(layout: &'a Layout, expr: &'a str) -> Option<&'a str> {
+    let expr = expr.trim();
+    expr.strip_prefix("witness::").or_else(|| {
+        layout
+            .aliases
+            .iter()
+            .find(|(name, _)| name == expr)
+            .map(|(_, witness)| witness.as_str())
+    })
+}
+
+/// The type after `:` in a pattern such as `Left(sig: Signature)`.
+fn pattern_type(pattern: &str) -> Option<&str> {
+    let inner = pattern.split_once('(')?.1.strip_suffix(')')?;
+    Some(inner.split_once(':')?.1.trim())
+}
+
+/// The type of `witness` as spelled by the typed arms of a match on it.
+fn matched_type(layout: &Layout, witness: &str) -> Option<String> {
+    let m = layout.matches.iter().find(|m| witness_of(layout, &m.scrutinee) == Some(witness))?;
+    let side = |kind: &str| m.arms.iter().find(|a| a.kind == kind).and_then(|a| pattern_type(&a.pattern));
+    match m.arms.first()?.kind.as_str() {
+        "Left" | "Right" => Some(format!("Either<{}, {}>", side("Left")?, side("Right")?)),
+        "Some" | "None" => Some(format!("Option<{}>", side("Some")?)),
+        "true" | "false" => Some("bool".to_string()),
+        _ => None,
+    }
+}
+
+/// The type of each witness `code` reads, from annotated bindings or the
+/// arms of a match on it; empty where neither gives one.
+fn types_in(code: &str, layout: &Layout) -> HashMap<String, String> {
+    witness_form::fields(code, "{}")
+        .into_iter()
+        .map(|field| {
+            let ty = if field.ty.is_empty() {
+                matched_type(layout, &field.name).unwrap_or_default()
+            } else {
+                field.ty
+            };
+            (field.name, ty)
+        })
+        .collect()
+}
+
+/// The witnesses `code` reads with their types, in order of first use.
+pub fn witness_types(code: &str) -> Vec<(String, String)> {
+    let mut types = types_in(code, &layout(code));
+    witness_form::fields(code, "{}")
+        .into_iter()
+        .map(|field| {
+            let ty = types.remove(&field.name).unwrap_or_default();
+            (field.name, ty)
+        })
+        .collect()
+}
+
+/// Bytes of a witness stack item with its compact-size length prefix.
+fn stack_item(len: usize) -> usize {
+    let prefix = match len {
+        0..=0xfc => 1,
+        0xfd..=0xffff => 3,
+        _ => 5,
+    };
+    prefix + len
+}
+
+/// Witness weight of a Simplicity spend: the item count and four items.
+pub fn input_weight(witness_bytes: usize, program_bytes: usize) -> usize {
+    1 + stack_item(witness_bytes)
+        + stack_item(program_bytes)
+        + stack_item(LEAF_SCRIPT_BYTES)
+        + stack_item(CONTROL_BLOCK_BYTES)
+}
+
+fn estimate_path(
+    code: &str,
+    layout: &Layout,
+    aliases: &HashMap<String, String>,
+    types: &HashMap<String, String>,
+    arms: &[ArmRef],
+    program_bytes: usize,
+) -> PathEstimate {
+    let choices = arms
+        .iter()
+        .map(|&(index, arm)| {
+            let m = &layout.matches[index];
+            format!("`{}` is `{}`", m.scrutinee, m.arms[arm].pattern)
+        })
+        .collect();
+
+    let mut names: Vec<&str> = Vec::new();
+    for (index, line) in code.lines().enumerate() {
+        if layout.line_parents.get(index).copied().flatten().is_some_and(|parent| !arms.contains(&parent)) {
+            continue;
+        }
+        for name in qualified_names(strip_comment(line), "witness::") {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+
+    let witnesses: Vec<WitnessItem> = names
+        .into_iter()
+        .map(|name| {
+            let ty = types.get(name).cloned().unwrap_or_default();
+            // The side a match on this witness takes on the path.
+            let taken = arms.iter().find_map(|&(index, arm)| {
+                let m = &layout.matches[index];
+                (witness_of(layout, &m.scrutinee) == Some(name)).then(|| m.arms[arm].kind.as_str())
+            });
+            let side = match taken {
+                Some(side @ ("Left" | "Right")) => generic_args(&ty, "Either")
+                    .filter(|args| args.len() == 2)
+                    .map(|args| if side == "Left" { args[0] } else { args[1] }),
+                Some("Some") => generic_args(&ty, "Option").filter(|args| args.len() == 1).map(|args| args[0]),
+                Some("None") => Some("()"),
+                _ => None,
+            };
+            let bits = match side {
+                Some(side) => max_bits(side, aliases).map(|bits| 1 + bits),
+                None => max_bits(&ty, aliases),
+            };
+            WitnessItem { name: name.to_string(), ty, bits }
+        })
+        .collect();
+
+    let bits: usize = witnesses.iter().filter_map(|w| w.bits).sum();
+    let witness_bytes = bits.div_ceil(8);
+    PathEstimate {
+        choices,
+        complete: witnesses.iter().all(|w| w.bits.is_some()),
+        witnesses,
+        witness_bytes,
+        weight: input_weight(witness_bytes, program_bytes),
+    }
+}
+
+/// Compile `code` and bound the witness of each of its spend paths.
+pub fn estimate(code: &str) -> Result<WitnessEstimate, String> {
+    let compiled = CompileOptions::default().compile_source(code, code)?;
+    let committed = compiled.commit();
+    let program_bytes = committed.to_vec_without_witness().len();
+    let max_witness_bits: usize = committed
+        .post_order_iter::<MaxSharing<Commit<Elements>>>()
+        .filter(|item| matches!(item.node.inner(), Inner::Witness(_)))
+        .map(|item| item.node.arrow().target.bit_width())
+        .sum();
+
+    let layout = layout(code);
+    let aliases = type_aliases(code);
+    let types = types_in(code, &layout);
+
+    let mut truncated = false;
+    let paths = arm_paths(&layout, None, &mut truncated)
+        .iter()
+        .map(|arms| estimate_path(code, &layout, &aliases, &types, arms, program_bytes))
+        .collect();
+    Ok(WitnessEstimate {
+        paths,
+        truncated,
+        program_bytes,
+        max_witness_bytes: max_witness_bits.div_ceil(8),
+    })
+}
+
+impl WitnessEstimate {
+    /// One line per path with its witness bytes and input weight.
+    pub fn to_text(&self) -> String {
+        let mut text = format!(
+            "program: {} bytes unpruned; all witnesses at most {} bytes\n",
+            self.program_bytes, self.max_witness_bytes
+        );
+        for (index, path) in self.paths.iter().enumerate() {
+            let unknown: Vec<&str> =
+                path.witnesses.iter().filter(|w| w.bits.is_none()).map(|w| w.name.as_str()).collect();
+            text.push_str(&format!(
+                "path {}: witness ≤ {} bytes, input weight ≈ {} WU ({} vB)",
+                index + 1,
+                path.witness_bytes,
+                path.weight,
+                path.weight.div_ceil(4)
+            ));
+            if !unknown.is_empty() {
+                text.push_str(&format!(", type unknown for {}", unknown.join(", ")));
+            }
+            text.push('\n');
+        }
+        if self.truncated {
+            text.push_str("(only the first paths are listed)\n");
+        }
+        text
+    }
+}
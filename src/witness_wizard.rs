@@ -0,0 +1,394 @@
+//! Form editor for witness data, as an alternative to raw JSON.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::api_types::{DecodeResult, EncodeResult, RandomWitnessResult};
+use crate::compiler;
+use crate::i18n::{self, t, Msg};
+use crate::locktime;
+use crate::value_codec::Encoded;
+use crate::witness_form::{self, InputKind, WitnessField};
+use crate::witness_gen::RandomWitness;
+
+/// `unix_secs` as a `datetime-local` input value in the local time zone.
+fn datetime_local(unix_secs: &str) -> String {
+    let Ok(secs) = unix_secs.trim().parse::<f64>() else {
+        return String::new();
+    };
+    let date = js_sys::Date::new(&(secs * 1000.0).into());
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}",
+        date.get_full_year(),
+        date.get_month() + 1,
+        date.get_date(),
+        date.get_hours(),
+        date.get_minutes()
+    )
+}
+
+/// What a timelock value means, with the input sequence relative locks need.
+fn lock_hint(ty: &str, value: &str) -> Option<String> {
+    let value: u64 = value.trim().parse().ok()?;
+    let lock = match ty {
+        "Height" => locktime::lock_height(value),
+        "Time" => locktime::lock_time(value),
+        "Lock" if value < u64::from(locktime::LOCKTIME_THRESHOLD) => locktime::lock_height(value),
+        "Lock" => locktime::lock_time(value),
+        "Distance" => locktime::lock_distance(value),
+        "Duration" => u16::try_from(value).map_err(|e| e.to_string()).map(locktime::duration),
+        _ => return None,
+    }
+    .ok()?;
+    Some(match lock.sequence {
+        Some(sequence) => format!("{}; spend with sequence 0x{sequence:08x}", lock.description),
+        None => lock.description,
+    })
+}
+
+#[component]
+pub fn WitnessWizard(
+    #[prop(into)] code: Signal<String>,
+    witness: ReadSignal<String>,
+    set_witness: WriteSignal<String>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let fields = Memo::new(move |_| witness_form::fields(&code.get(), &witness.get()));
+
+    // Every edit goes straight into the witness JSON.
+    let update = move |name: &str, edit: &dyn Fn(&mut WitnessField)| {
+        let mut current = fields.get_untracked();
+        if let Some(field) = current.iter_mut().find(|f| f.name == name) {
+            edit(field);
+        }
+        set_witness.set(witness_form::to_json(&current, &witness.get_untracked()));
+    };
+
+    let row = move |name: String| {
+        let label = name.clone();
+        let field = Memo::new(move |_| fields.with(|fs| fs.iter().find(|f| f.name == name).cloned()));
+        let value = move || field.get().map(|f| f.value).unwrap_or_default();
+        // Memos, so inputs are only rebuilt when the type changes.
+        let ty = Memo::new(move |_| field.with(|f| f.as_ref().map(|f| f.ty.clone()).unwrap_or_default()));
+        let kind = Memo::new(move |_| InputKind::of(&ty.get()));
+        let name = StoredValue::new(label.clone());
+        let set_value = move |value: String| update(&name.get_value(), &|f| f.value = value.clone());
+        let set_type = move |ty: String| update(&name.get_value(), &|f| f.ty = ty.clone());
+
+        let input = move || {
+            match kind.get() {
+                InputKind::Bool => view! {
+                    <input
+                        type="checkbox"
+                        prop:checked=move || value() == "true"
+                        on:change=move |ev| set_value(event_target_checked(&ev).to_string())
+                    />
+                }
+                .into_any(),
+                InputKind::Number(_) => view! {
+                    <input
+                        type="number"
+                        min="0"
+                        prop:value=value
+                        on:input=move |ev| set_value(event_target_value(&ev))
+                    />
+                }
+                .into_any(),
+                InputKind::Date => view! {
+                    <input
+                        type="datetime-local"
+                        prop:value=move || datetime_local(&value())
+                        on:input=move |ev| {
+                            let ms = js_sys::Date::parse(&event_target_value(&ev));
+                            if !ms.is_nan() {
+                                set_value(((ms / 1000.0) as u64).to_string());
+                            }
+                        }
+                    />
+                }
+                .into_any(),
+                InputKind::Hex(len) => view! {
+                    <input
+                        type="text"
+                        spellcheck="false"
+                        placeholder=move || match len {
+                            32 => i18n::text(lang.get(), Msg::HexOrLabelPlaceholder).to_string(),
+                            _ => i18n::format(i18n::text(lang.get(), Msg::HexBytesPlaceholder), &[&len.to_string()]),
+                        }
+                        list=(len == 32).then_some("address-book")
+                        prop:value=value
+                        on:input=move |ev| set_value(event_target_value(&ev))
+                    />
+                }
+                .into_any(),
+                InputKind::Text => view! {
+                    <input
+                        type="text"
+                        spellcheck="false"
+                        placeholder=t(Msg::Value)
+                        prop:value=value
+                        on:input=move |ev| set_value(event_target_value(&ev))
+                    />
+                }
+                .into_any(),
+            }
+        };
+
+        // "Inspect bytes": the field's bit encoding, and a way back from one.
+        let (inspect, set_inspect) = signal(false);
+        let (encoding, set_encoding) = signal::<Option<Result<Encoded, String>>>(None);
+        Effect::new(move |_| {
+            let (ty, value) = (ty.get(), value());
+            if !inspect.get() || ty.is_empty() || value.is_empty() {
+                set_encoding.set(None);
+                return;
+            }
+            spawn_local(async move {
+                let json = compiler::encode_value(&ty, &serde_json::Value::String(value).to_string()).await;
+                set_encoding.set(Some(match serde_json::from_str::<EncodeResult>(&json) {
+                    Ok(EncodeResult { encoded: Some(encoded), .. }) => Ok(encoded),
+                    Ok(EncodeResult { error, .. }) => Err(error.unwrap_or_default()),
+                    Err(e) => Err(e.to_string()),
+                }));
+            });
+        });
+        let decode = move |bits_hex: String| {
+            let ty = ty.get_untracked();
+            spawn_local(async move {
+                let json = compiler::decode_value(&ty, &bits_hex).await;
+                match serde_json::from_str::<DecodeResult>(&json) {
+                    Ok(DecodeResult { value: Some(value), .. }) => set_value(value),
+                    Ok(DecodeResult { error, .. }) => set_encoding.set(Some(Err(error.unwrap_or_default()))),
+                    Err(e) => set_encoding.set(Some(Err(e.to_string()))),
+                }
+            });
+        };
+        let inspector = move || {
+            inspect.get().then(|| {
+                let encoded = move || match encoding.get() {
+                    None => None,
+                    Some(Ok(encoded)) => Some(
+                        view! {
+                            <span class="witness-field-hint">
+                                {i18n::format(
+                                    i18n::text(lang.get(), Msg::BitsEncoding),
+                                    &[&encoded.bits.to_string(), &encoded.hex],
+                                )}
+                                <br />
+                                <code>{encoded.binary}</code>
+                            </span>
+                        }
+                        .into_any(),
+                    ),
+                    Some(Err(e)) => Some(view! { <span class="witness-field-error">{e}</span> }.into_any()),
+                };
+                view! {
+                    <input
+                        class="witness-field-bytes"
+                        type="text"
+                        spellcheck="false"
+                        placeholder=t(Msg::PasteEncodedHex)
+                        on:change=move |ev| decode(event_target_value(&ev))
+                    />
+                    {encoded}
+                }
+            })
+        };
+
+        // Inline converters from human units for the timelock types.
+        let converter = move || match ty.get().as_str() {
+            "Lock" => Some(
+                view! {
+                    <input
+                        type="datetime-local"
+                        title=t(Msg::PickDateInstead)
+                        on:input=move |ev| {
+                            let ms = js_sys::Date::parse(&event_target_value(&ev));
+                            if let Ok(lock) = locktime::lock_time((ms / 1000.0).max(0.0) as u64) {
+                                set_value(lock.value.to_string());
+                            }
+                        }
+                    />
+                }
+                .into_any(),
+            ),
+            "Duration" => Some(
+                view! {
+                    <input
+                        type="number"
+                        min="0"
+                        step="any"
+                        placeholder=t(Msg::OrHours)
+                        on:input=move |ev| {
+                            if let Ok(hours) = event_target_value(&ev).parse::<f64>() {
+                                if let Ok(lock) = locktime::lock_duration((hours * 3600.0).max(0.0) as u64) {
+                                    set_value(lock.value.to_string());
+                                }
+                            }
+                        }
+                    />
+                }
+                .into_any(),
+            ),
+            "Distance" => Some(
+                view! {
+                    <input
+                        type="number"
+                        min="0"
+                        step="any"
+                        placeholder=t(Msg::OrHours)
+                        on:input=move |ev| {
+                            if let Ok(hours) = event_target_value(&ev).parse::<f64>() {
+                                let blocks = (hours * 3600.0 / locktime::BLOCK_INTERVAL_SECS as f64).ceil();
+                                if let Ok(lock) = locktime::lock_distance(blocks.max(0.0) as u64) {
+                                    set_value(lock.value.to_string());
+                                }
+                            }
+                        }
+                    />
+                }
+                .into_any(),
+            ),
+            _ => None,
+        };
+
+        view! {
+            <div class="witness-field">
+                <span class="witness-field-name">{label}</span>
+                <input
+                    class="witness-field-type"
+                    type="text"
+                    placeholder=t(Msg::Type)
+                    prop:value=move || ty.get()
+                    on:input=move |ev| set_type(event_target_value(&ev))
+                />
+                {input}
+                {converter}
+                {move || field.get().and_then(|f| f.doc).map(|doc| view! { <span class="witness-field-hint">{doc}</span> })}
+                <label class="witness-field-inspect">
+                    <input type="checkbox" prop:checked=inspect on:change=move |ev| set_inspect.set(event_target_checked(&ev)) />
+                    {t(Msg::InspectBytes)}
+                </label>
+                {inspector}
+                {move || lock_hint(&ty.get(), &value()).map(|hint| view! { <span class="witness-field-hint">{hint}</span> })}
+                {move || {
+                    let value = value();
+                    // `@label` is expanded at compile time, which reports unknown labels.
+                    (!value.is_empty() && !value.starts_with('@'))
+                        .then(|| kind.get().validate(&value).err())
+                        .flatten()
+                        .map(|e| view! { <span class="witness-field-error">{e}</span> })
+                }}
+            </div>
+        }
+    };
+
+    let (seed, set_seed) = signal(String::new());
+    let (sighash, set_sighash) = signal(String::new());
+    let (generated, set_generated) = signal::<Option<RandomWitness>>(None);
+    let (generate_error, set_generate_error) = signal::<Option<String>>(None);
+
+    let generate = move |_| {
+        let (code, seed, sighash) = (code.get_untracked(), seed.get_untracked(), sighash.get_untracked());
+        let no_witness = i18n::text(lang.get_untracked(), Msg::NoWitness);
+        spawn_local(async move {
+            let json = compiler::random_witness(&code, &seed, &sighash).await;
+            match serde_json::from_str::<RandomWitnessResult>(&json) {
+                Ok(RandomWitnessResult { witness: Some(generated), .. }) => {
+                    set_generate_error.set(None);
+                    set_witness.set(generated.witness_json.clone());
+                    set_generated.set(Some(generated));
+                }
+                Ok(RandomWitnessResult { error, .. }) => {
+                    set_generate_error.set(Some(error.unwrap_or_else(|| no_witness.to_string())));
+                    set_generated.set(None);
+                }
+                Err(e) => set_generate_error.set(Some(e.to_string())),
+            }
+        });
+    };
+
+    let generated_view = move || {
+        generated.get().map(|generated| {
+            let keys = generated
+                .keys
+                .into_iter()
+                .enumerate()
+                .map(|(index, key)| {
+                    view! {
+                        <li class="signature-row">
+                            <span>{i18n::format(i18n::text(lang.get(), Msg::KeyNumber), &[&(index + 1).to_string()])}</span>
+                            <code title=t(Msg::PublicKeyTitle)>{key.pubkey}</code>
+                            <code class="reminder-note" title=t(Msg::PrivateKeyTitle)>{key.secret}</code>
+                        </li>
+                    }
+                })
+                .collect_view();
+            let hashes = generated
+                .hashes
+                .into_iter()
+                .map(|(name, hash)| {
+                    view! {
+                        <li class="signature-row">
+                            <span>{format!("sha256({name})")}</span>
+                            <code>{hash}</code>
+                        </li>
+                    }
+                })
+                .collect_view();
+            let notes = generated
+                .notes
+                .into_iter()
+                .map(|note| view! { <p class="reminder-note">{note}</p> })
+                .collect_view();
+            view! {
+                <ul class="signature-list">{keys}{hashes}</ul>
+                {notes}
+            }
+        })
+    };
+
+    view! {
+        <div class="witness-wizard">
+            <details class="manifest-editor">
+                <summary>{t(Msg::RandomWitness)}</summary>
+                <div class="button-group">
+                    <input
+                        type="text"
+                        spellcheck="false"
+                        aria-label=t(Msg::Seed)
+                        placeholder=t(Msg::SeedPlaceholder)
+                        prop:value=move || seed.get()
+                        on:input=move |ev| set_seed.set(event_target_value(&ev))
+                    />
+                    <input
+                        type="text"
+                        spellcheck="false"
+                        aria-label=t(Msg::SighashHexLabel)
+                        placeholder=t(Msg::SighashOptionalPlaceholder)
+                        prop:value=move || sighash.get()
+                        on:input=move |ev| set_sighash.set(event_target_value(&ev))
+                    />
+                    <button disabled=move || seed.with(|s| s.trim().is_empty()) on:click=generate>
+                        {t(Msg::Generate)}
+                    </button>
+                </div>
+                <p class="drop-zone-hint">
+                    {t(Msg::RandomWitnessHint)}
+                </p>
+                {move || generate_error.get().map(|e| view! { <p class="tool-error">{e}</p> })}
+                {generated_view}
+            </details>
+            <Show
+                when=move || fields.with(|fs| !fs.is_empty())
+                fallback=|| view! { <p class="signature-empty">{t(Msg::NoWitnessValues)}</p> }
+            >
+                <For
+                    each=move || fields.with(|fs| fs.iter().map(|f| f.name.clone()).collect::<Vec<_>>())
+                    key=|name| name.clone()
+                    children=row
+                />
+            </Show>
+        </div>
+    }
+}
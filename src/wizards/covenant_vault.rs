@@ -0,0 +1,92 @@
+//! Covenant vault: the hot key can only move funds to one destination.
+//!
+//! Unlike the key-only [vault](super::vault), the restriction holds even
+//! against a thief with the hot key: the transaction itself is checked, and
+//! its first output must pay the destination nearly all of the coin. The
+//! destination is usually a timelocked contract the cold key can claw back
+//! from.
+
+use super::{value, Step, Values, Wizard};
+use crate::templates::Placeholder;
+
+pub const WIZARD: Wizard = Wizard {
+    name: "Covenant vault",
+    description: "The hot key can only send the coin to a fixed destination; the cold key can spend it anywhere.",
+    steps: &[
+        Step {
+            title: "Keys",
+            fields: &[
+                Placeholder {
+                    name: "hot",
+                    ty: "Pubkey",
+                    label: "Hot (everyday) public key",
+                },
+                Placeholder {
+                    name: "cold",
+                    ty: "Pubkey",
+                    label: "Cold (recovery) public key",
+                },
+            ],
+        },
+        Step {
+            title: "Destination",
+            fields: &[
+                Placeholder {
+                    name: "destination",
+                    ty: "u256",
+                    label: "SHA-256 of the destination's scriptPubKey",
+                },
+                Placeholder {
+                    name: "max_fee",
+                    ty: "u64",
+                    label: "Most the fee may take from the coin (sat)",
+                },
+            ],
+        },
+    ],
+    consistent: None,
+    build,
+};
+
+fn build(values: &Values) -> String {
+    format!(
+        r#"// Covenant vault: the hot key pays only the destination; the cold key anywhere
+fn checksig(pk: Pubkey, sig: Signature) {{
+    let msg: u256 = jet::sig_all_hash();
+    jet::bip_0340_verify((pk, msg), sig);
+}}
+
+// Output 0 pays the destination all but at most `max_fee` of this coin.
+fn pays_destination() {{
+    let destination: u256 = {destination};
+    let max_fee: u64 = {max_fee};
+    let script: u256 = unwrap(jet::output_script_hash(0));
+    assert!(jet::eq_256(script, destination));
+    let (_, amount): (Asset1, Amount1) = jet::current_amount();
+    let input_value: u64 = unwrap_right::<(u1, u256)>(amount);
+    let (_, paid): (Asset1, Amount1) = unwrap(jet::output_amount(0));
+    let paid_value: u64 = unwrap_right::<(u1, u256)>(paid);
+    let (borrow, least): (bool, u64) = jet::subtract_64(input_value, max_fee);
+    match borrow {{
+        true => {{}},
+        false => assert!(jet::le_64(least, paid_value)),
+    }}
+}}
+
+fn main() {{
+    let hot: Pubkey = {hot};
+    let cold: Pubkey = {cold};
+    match witness::HOT_OR_COLD {{
+        Left(sig: Signature) => {{
+            pays_destination();
+            checksig(hot, sig)
+        }},
+        Right(sig: Signature) => checksig(cold, sig),
+    }}
+}}"#,
+        hot = value(values, "hot"),
+        cold = value(values, "cold"),
+        destination = value(values, "destination"),
+        max_fee = value(values, "max_fee"),
+    )
+}
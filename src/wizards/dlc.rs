@@ -0,0 +1,135 @@
+//! Discreet log contract between two parties over one oracle event.
+//!
+//! The oracle attests to an outcome by signing its message, the SHA-256 of
+//! the outcome's label; whichever party the outcome favours spends with
+//! that attestation and their own signature. If the oracle never attests,
+//! both parties can spend together after a block height.
+
+use simplicityhl::simplicity::hashes::{sha256, Hash};
+
+use super::{value, Step, Values, Wizard};
+use crate::hex;
+use crate::templates::Placeholder;
+
+pub const WIZARD: Wizard = Wizard {
+    name: "DLC",
+    description: "An oracle's attestation decides which of two parties spends; both together after a timeout.",
+    steps: &[
+        Step {
+            title: "Parties",
+            fields: &[
+                Placeholder {
+                    name: "alice",
+                    ty: "Pubkey",
+                    label: "First party public key",
+                },
+                Placeholder {
+                    name: "bob",
+                    ty: "Pubkey",
+                    label: "Second party public key",
+                },
+            ],
+        },
+        Step {
+            title: "Oracle",
+            fields: &[
+                Placeholder {
+                    name: "oracle",
+                    ty: "Pubkey",
+                    label: "Oracle public key",
+                },
+                Placeholder {
+                    name: "alice_outcome",
+                    ty: "String",
+                    label: "Outcome paying the first party, as the oracle words it",
+                },
+                Placeholder {
+                    name: "bob_outcome",
+                    ty: "String",
+                    label: "Outcome paying the second party, as the oracle words it",
+                },
+            ],
+        },
+        Step {
+            title: "Timeout",
+            fields: &[Placeholder {
+                name: "timeout",
+                ty: "Height",
+                label: "Block height after which both parties may spend together",
+            }],
+        },
+    ],
+    consistent: Some(distinct_parties),
+    build,
+};
+
+/// Equal outcomes or keys would let either party take everything.
+fn distinct_parties(values: &Values) -> Result<(), (String, String)> {
+    if value(values, "alice") == value(values, "bob") {
+        return Err(("bob".to_string(), "The parties must have different keys".to_string()));
+    }
+    if value(values, "alice_outcome") == value(values, "bob_outcome") {
+        return Err(("bob_outcome".to_string(), "The outcomes must differ".to_string()));
+    }
+    Ok(())
+}
+
+/// The message the oracle signs for `outcome`.
+fn outcome_message(outcome: &str) -> String {
+    format!("0x{}", hex::encode(&sha256::Hash::hash(outcome.as_bytes()).to_byte_array()))
+}
+
+fn build(values: &Values) -> String {
+    let alice_outcome = value(values, "alice_outcome");
+    let bob_outcome = value(values, "bob_outcome");
+    format!(
+        r#"// DLC: the oracle's attestation decides; both parties together after block {timeout}
+fn checksig(pk: Pubkey, sig: Signature) {{
+    let msg: u256 = jet::sig_all_hash();
+    jet::bip_0340_verify((pk, msg), sig);
+}}
+
+fn attested(oracle: Pubkey, outcome: u256, attestation: Signature) {{
+    jet::bip_0340_verify((oracle, outcome), attestation);
+}}
+
+fn main() {{
+    let alice: Pubkey = {alice};
+    let bob: Pubkey = {bob};
+    let oracle: Pubkey = {oracle};
+    // SHA-256 of "{alice_label}"
+    let alice_wins: u256 = {alice_message};
+    // SHA-256 of "{bob_label}"
+    let bob_wins: u256 = {bob_message};
+    match witness::OUTCOME {{
+        Left(sigs: (Signature, Signature)) => {{
+            let (attestation, sig): (Signature, Signature) = sigs;
+            attested(oracle, alice_wins, attestation);
+            checksig(alice, sig)
+        }},
+        Right(bob_or_refund: Either<(Signature, Signature), (Signature, Signature)>) => match bob_or_refund {{
+            Left(sigs: (Signature, Signature)) => {{
+                let (attestation, sig): (Signature, Signature) = sigs;
+                attested(oracle, bob_wins, attestation);
+                checksig(bob, sig)
+            }},
+            Right(sigs: (Signature, Signature)) => {{
+                let timeout: Height = {timeout};
+                jet::check_lock_height(timeout);
+                let (alice_sig, bob_sig): (Signature, Signature) = sigs;
+                checksig(alice, alice_sig);
+                checksig(bob, bob_sig)
+            }},
+        }},
+    }}
+}}"#,
+        alice = value(values, "alice"),
+        bob = value(values, "bob"),
+        oracle = value(values, "oracle"),
+        alice_label = alice_outcome.replace('\n', " "),
+        alice_message = outcome_message(alice_outcome),
+        bob_label = bob_outcome.replace('\n', " "),
+        bob_message = outcome_message(bob_outcome),
+        timeout = value(values, "timeout"),
+    )
+}
@@ -0,0 +1,94 @@
+//! Hashed timelock contract, as used by swaps and payment channels.
+
+use super::{value, Step, Values, Wizard};
+use crate::templates::Placeholder;
+
+pub const WIZARD: Wizard = Wizard {
+    name: "HTLC",
+    description: "The recipient spends by revealing a secret; the sender is refunded after a block height.",
+    steps: &[
+        Step {
+            title: "Parties",
+            fields: &[
+                Placeholder {
+                    name: "recipient",
+                    ty: "Pubkey",
+                    label: "Recipient public key",
+                },
+                Placeholder {
+                    name: "sender",
+                    ty: "Pubkey",
+                    label: "Sender (refund) public key",
+                },
+            ],
+        },
+        Step {
+            title: "Payment hash",
+            fields: &[Placeholder {
+                name: "payment_hash",
+                ty: "u256",
+                label: "SHA-256 of the 32-byte secret",
+            }],
+        },
+        Step {
+            title: "Timeout",
+            fields: &[Placeholder {
+                name: "timeout",
+                ty: "Height",
+                label: "Refund block height",
+            }],
+        },
+    ],
+    consistent: None,
+    build,
+};
+
+fn build(values: &Values) -> String {
+    format!(
+        r#"// HTLC: the recipient with the secret, or the sender after block {timeout}
+fn checksig(pk: Pubkey, sig: Signature) {{
+    let msg: u256 = jet::sig_all_hash();
+    jet::bip_0340_verify((pk, msg), sig);
+}}
+
+fn sha256(secret: u256) -> u256 {{
+    let ctx: Ctx8 = jet::sha_256_ctx_8_init();
+    let ctx: Ctx8 = jet::sha_256_ctx_8_add_32(ctx, secret);
+    jet::sha_256_ctx_8_finalize(ctx)
+}}
+
+fn main() {{
+    let recipient: Pubkey = {recipient};
+    let sender: Pubkey = {sender};
+    let payment_hash: u256 = {payment_hash};
+    match witness::COMPLETE_OR_REFUND {{
+        Left(secret_and_sig: (u256, Signature)) => {{
+            let (secret, sig): (u256, Signature) = secret_and_sig;
+            assert!(jet::eq_256(sha256(secret), payment_hash));
+            checksig(recipient, sig)
+        }},
+        Right(sig: Signature) => {{
+            let timeout: Height = {timeout};
+            jet::check_lock_height(timeout);
+            checksig(sender, sig)
+        }},
+    }}
+}}"#,
+        recipient = value(values, "recipient"),
+        sender = value(values, "sender"),
+        payment_hash = value(values, "payment_hash"),
+        timeout = value(values, "timeout"),
+    )
+}
+
+/// The program for sample values: block 1000 as the timeout.
+#[cfg(test)]
+pub(crate) fn example() -> String {
+    let key = "0x79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+    let hash = format!("0x{}", "11".repeat(32));
+    let values: Values = [("recipient", key), ("sender", key), ("payment_hash", hash.as_str()), ("timeout", "1000")]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    WIZARD.generate(&values).expect("valid sample values")
+}
@@ -0,0 +1,88 @@
+//! Step-by-step builders for common contract archetypes.
+//!
+//! A wizard collects the keys, hashes and timeouts of one archetype over a
+//! few steps and fills them into a fixed program whose spending logic has
+//! been reviewed; users choose values, never control flow. Values are typed
+//! and checked like template placeholders, so public keys may be vault
+//! `@label`s.
+
+use std::collections::HashMap;
+
+use crate::templates::{self, Placeholder};
+
+pub mod covenant_vault;
+pub mod dlc;
+pub mod htlc;
+pub mod rate_limit;
+pub mod vault;
+#[cfg(feature = "ui")]
+pub mod wizard_panel;
+
+/// Values entered so far, by field name.
+pub type Values = HashMap<String, String>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub title: &'static str,
+    pub fields: &'static [Placeholder],
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Wizard {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub steps: &'static [Step],
+    /// A check across fields of the last step, e.g. that two outcomes differ.
+    consistent: Option<fn(&Values) -> Result<(), (String, String)>>,
+    /// The program for `values`, which have all been checked.
+    build: fn(&Values) -> String,
+}
+
+pub const WIZARDS: &[Wizard] = &[
+    htlc::WIZARD,
+    dlc::WIZARD,
+    vault::WIZARD,
+    covenant_vault::WIZARD,
+    rate_limit::WIZARD,
+];
+
+/// The trimmed value of field `name`.
+fn value<'a>(values: &'a Values, name: &str) -> &'a str {
+    values.get(name).map(|v| v.trim()).unwrap_or_default()
+}
+
+impl Wizard {
+    pub fn find(name: &str) -> Option<&'static Wizard> {
+        WIZARDS.iter().find(|w| w.name == name)
+    }
+
+    /// Problems with the values of step `step`, as (field, message).
+    pub fn check_step(&self, step: usize, values: &Values) -> Vec<(String, String)> {
+        self.steps
+            .get(step)
+            .map_or(&[][..], |s| s.fields)
+            .iter()
+            .filter_map(|field| {
+                templates::validate(field.ty, value(values, field.name))
+                    .err()
+                    .map(|e| (field.name.to_string(), e))
+            })
+            .chain(
+                (step + 1 == self.steps.len())
+                    .then_some(self.consistent)
+                    .flatten()
+                    .and_then(|consistent| consistent(values).err()),
+            )
+            .collect()
+    }
+
+    /// The finished program, or the problems with every step's values.
+    pub fn generate(&self, values: &Values) -> Result<String, Vec<(String, String)>> {
+        let errors: Vec<_> = (0..self.steps.len()).flat_map(|step| self.check_step(step, values)).collect();
+        if errors.is_empty() {
+            Ok((self.build)(values))
+        } else {
+            Err(errors)
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! Rate-limited withdrawals: a spending allowance per period.
+//!
+//! Each spend may take at most a fixed amount; the rest must go back to the
+//! contract itself as the second output. Because that output is new, the
+//! relative timelock starts over, so the owner can withdraw once per period.
+
+use super::{value, Step, Values, Wizard};
+use crate::templates::Placeholder;
+
+pub const WIZARD: Wizard = Wizard {
+    name: "Rate-limited withdrawals",
+    description: "The owner withdraws at most a fixed amount per period; the rest stays in the contract.",
+    steps: &[
+        Step {
+            title: "Owner",
+            fields: &[Placeholder {
+                name: "owner",
+                ty: "Pubkey",
+                label: "Owner public key",
+            }],
+        },
+        Step {
+            title: "Limit",
+            fields: &[
+                Placeholder {
+                    name: "limit",
+                    ty: "u64",
+                    label: "Most a withdrawal may take, fee included (sat)",
+                },
+                Placeholder {
+                    name: "period",
+                    ty: "Distance",
+                    label: "Blocks between withdrawals",
+                },
+            ],
+        },
+    ],
+    consistent: None,
+    build,
+};
+
+fn build(values: &Values) -> String {
+    format!(
+        r#"// Rate-limited withdrawals: at most {limit} sat every {period} blocks
+fn checksig(pk: Pubkey, sig: Signature) {{
+    let msg: u256 = jet::sig_all_hash();
+    jet::bip_0340_verify((pk, msg), sig);
+}}
+
+// Output 1 pays back to this contract everything above the limit.
+fn keeps_remainder(limit: u64) {{
+    let (_, amount): (Asset1, Amount1) = jet::current_amount();
+    let input_value: u64 = unwrap_right::<(u1, u256)>(amount);
+    let (borrow, remaining): (bool, u64) = jet::subtract_64(input_value, limit);
+    match borrow {{
+        // Less than the limit is left: all of it may go.
+        true => {{}},
+        false => {{
+            let own_script: u256 = jet::current_script_hash();
+            let change_script: u256 = unwrap(jet::output_script_hash(1));
+            assert!(jet::eq_256(own_script, change_script));
+            let (_, change): (Asset1, Amount1) = unwrap(jet::output_amount(1));
+            let change_value: u64 = unwrap_right::<(u1, u256)>(change);
+            assert!(jet::le_64(remaining, change_value));
+        }},
+    }}
+}}
+
+fn main() {{
+    let owner: Pubkey = {owner};
+    let period: Distance = {period};
+    jet::check_lock_distance(period);
+    keeps_remainder({limit});
+    checksig(owner, witness::OWNER_SIGNATURE)
+}}"#,
+        owner = value(values, "owner"),
+        limit = value(values, "limit"),
+        period = value(values, "period"),
+    )
+}
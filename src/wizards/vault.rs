@@ -0,0 +1,65 @@
+//! Vault: a hot key that must wait, and a cold key that need not.
+//!
+//! Funds leave through the hot key only after a relative delay, which gives
+//! the owner time to sweep them with the cold key if the hot key is stolen.
+
+use super::{value, Step, Values, Wizard};
+use crate::templates::Placeholder;
+
+pub const WIZARD: Wizard = Wizard {
+    name: "Vault",
+    description: "The hot key spends after a delay; the cold key can sweep the funds at any time.",
+    steps: &[
+        Step {
+            title: "Keys",
+            fields: &[
+                Placeholder {
+                    name: "hot",
+                    ty: "Pubkey",
+                    label: "Hot (everyday) public key",
+                },
+                Placeholder {
+                    name: "cold",
+                    ty: "Pubkey",
+                    label: "Cold (recovery) public key",
+                },
+            ],
+        },
+        Step {
+            title: "Delay",
+            fields: &[Placeholder {
+                name: "delay",
+                ty: "Distance",
+                label: "Blocks the hot key waits after funding",
+            }],
+        },
+    ],
+    consistent: None,
+    build,
+};
+
+fn build(values: &Values) -> String {
+    format!(
+        r#"// Vault: the hot key after {delay} blocks, or the cold key at any time
+fn checksig(pk: Pubkey, sig: Signature) {{
+    let msg: u256 = jet::sig_all_hash();
+    jet::bip_0340_verify((pk, msg), sig);
+}}
+
+fn main() {{
+    let hot: Pubkey = {hot};
+    let cold: Pubkey = {cold};
+    match witness::HOT_OR_COLD {{
+        Left(sig: Signature) => {{
+            let delay: Distance = {delay};
+            jet::check_lock_distance(delay);
+            checksig(hot, sig)
+        }},
+        Right(sig: Signature) => checksig(cold, sig),
+    }}
+}}"#,
+        hot = value(values, "hot"),
+        cold = value(values, "cold"),
+        delay = value(values, "delay"),
+    )
+}
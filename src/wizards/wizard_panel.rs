@@ -0,0 +1,206 @@
+//! The contract wizards, one step at a time.
+
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use super::{Values, Wizard, WIZARDS};
+use crate::address::{parse_cmr, taproot_address, Network};
+use crate::api_types::CompileResult;
+use crate::i18n::{self, t, Msg};
+use crate::templates;
+use crate::witness_form::InputKind;
+use crate::{address_book, compiler};
+
+/// What the last step produced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Generated {
+    source: String,
+    cmr: Option<String>,
+    address: Option<String>,
+    error: Option<String>,
+}
+
+#[component]
+pub fn WizardPanel(
+    #[prop(into)] network: Signal<Network>,
+    /// Called with the generated source to load it into the editor.
+    on_open: Callback<String>,
+) -> impl IntoView {
+    let lang = i18n::use_lang();
+    let (chosen, set_chosen) = signal(WIZARDS[0].name);
+    let (step, set_step) = signal(0usize);
+    let values = RwSignal::new(Values::new());
+    let (errors, set_errors) = signal(Vec::<(String, String)>::new());
+    let (generated, set_generated) = signal::<Option<Generated>>(None);
+    let wizard = move || Wizard::find(chosen.get()).unwrap_or(&WIZARDS[0]);
+    let last_step = move || step.get() + 1 == wizard().steps.len();
+
+    let next = move |_| {
+        let wizard = wizard();
+        let problems = wizard.check_step(step.get_untracked(), &values.get_untracked());
+        if !problems.is_empty() {
+            set_errors.set(problems);
+            return;
+        }
+        set_errors.set(Vec::new());
+        if !last_step() {
+            set_step.update(|s| *s += 1);
+            return;
+        }
+        let source = match wizard.generate(&values.get_untracked()) {
+            Ok(source) => source,
+            Err(problems) => {
+                set_errors.set(problems);
+                return;
+            }
+        };
+        set_generated.set(Some(Generated {
+            source: source.clone(),
+            cmr: None,
+            address: None,
+            error: None,
+        }));
+        let network = network.get_untracked();
+        spawn_local(async move {
+            let expanded = address_book::expand_all(&source);
+            let result = compiler::compile_simplicity(&expanded).await;
+            let result = serde_json::from_str::<CompileResult>(&result).map_err(|e| e.to_string());
+            let (cmr, error) = match result {
+                Ok(CompileResult { cmr: Some(cmr), .. }) => (Some(cmr), None),
+                Ok(CompileResult { error, .. }) => (None, error),
+                Err(e) => (None, Some(e)),
+            };
+            let address = cmr
+                .as_deref()
+                .and_then(|cmr| parse_cmr(cmr).ok())
+                .map(|cmr| taproot_address(cmr, network).to_string());
+            set_generated.set(Some(Generated {
+                source,
+                cmr,
+                address,
+                error,
+            }));
+        });
+    };
+
+    let back = move |_| {
+        set_errors.set(Vec::new());
+        set_generated.set(None);
+        set_step.update(|s| *s = s.saturating_sub(1));
+    };
+
+    let fields = move || {
+        let Some(current) = wizard().steps.get(step.get()) else {
+            return Vec::new();
+        };
+        current
+            .fields
+            .iter()
+            .map(|field| {
+                let name = field.name;
+                let ty = field.ty;
+                let labelled = InputKind::of(ty) == InputKind::Hex(32);
+                let value = move || values.with(|v| v.get(name).cloned().unwrap_or_default());
+                let error = move || {
+                    let value = value();
+                    let shown = errors.with(|e| e.iter().find(|(n, _)| n == name).map(|(_, e)| e.clone()));
+                    (!value.is_empty()).then(|| templates::validate(ty, &value).err()).flatten().or(shown)
+                };
+                view! {
+                    <div class="witness-field">
+                        <span class="witness-field-name">{field.label}</span>
+                        <span class="witness-field-type">{ty}</span>
+                        <input
+                            type="text"
+                            spellcheck="false"
+                            placeholder=move || if labelled { i18n::text(lang.get(), Msg::HexOrLabelShort) } else { ty }
+                            list=labelled.then_some("address-book")
+                            prop:value=value
+                            on:input=move |ev| {
+                                let value = event_target_value(&ev);
+                                values.update(|v| {
+                                    v.insert(name.to_string(), value);
+                                });
+                                set_generated.set(None);
+                            }
+                        />
+                        {move || error().map(|e| view! { <span class="witness-field-error">{e}</span> })}
+                    </div>
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let result = move || {
+        generated.get().map(|generated| {
+            let source = generated.source.clone();
+            view! {
+                <div class="output-group">
+                    <div class="output-label">{t(Msg::ProgramLabel)}</div>
+                    <pre class="output-box listing">{generated.source}</pre>
+                </div>
+                {match (generated.cmr, generated.error) {
+                    (Some(cmr), _) => view! {
+                        <div class="output-group">
+                            <div class="output-label">{t(Msg::CmrShortLabel)}</div>
+                            <div class="output-box">{cmr}</div>
+                        </div>
+                        <div class="output-group">
+                            <div class="output-label">{t(Msg::AddressLabel)}</div>
+                            <div class="output-box">{generated.address.unwrap_or_default()}</div>
+                        </div>
+                    }
+                    .into_any(),
+                    (None, Some(error)) => view! { <p class="tool-error">{error}</p> }.into_any(),
+                    (None, None) => view! { <p class="reminder-note">{t(Msg::Compiling)}</p> }.into_any(),
+                }}
+                <div class="button-group">
+                    <button on:click=move |_| on_open.run(source.clone())>{t(Msg::OpenInEditorButton)}</button>
+                </div>
+            }
+        })
+    };
+
+    view! {
+        <details class="manifest-editor">
+            <summary>{t(Msg::ContractWizards)}</summary>
+            <div class="button-group">
+                <select
+                    aria-label=t(Msg::ContractType)
+                    on:change=move |ev| {
+                        if let Some(wizard) = Wizard::find(&event_target_value(&ev)) {
+                            set_chosen.set(wizard.name);
+                            set_step.set(0);
+                            set_errors.set(Vec::new());
+                            set_generated.set(None);
+                        }
+                    }
+                >
+                    {WIZARDS
+                        .iter()
+                        .map(|w| view! { <option value=w.name selected=move || chosen.get() == w.name>{w.name}</option> })
+                        .collect_view()}
+                </select>
+            </div>
+            <p class="reminder-note">{move || wizard().description}</p>
+            <p class="output-label">
+                {move || {
+                    let wizard = wizard();
+                    let title = wizard.steps.get(step.get()).map(|s| s.title).unwrap_or_default();
+                    let (number, count) = ((step.get() + 1).to_string(), wizard.steps.len().to_string());
+                    i18n::format(i18n::text(lang.get(), Msg::StepOf), &[&number, &count, title])
+                }}
+            </p>
+            <div class="witness-wizard">{fields}</div>
+            <div class="button-group">
+                <button class="secondary" disabled=move || step.get() == 0 on:click=back>
+                    {t(Msg::Back)}
+                </button>
+                <button on:click=next>
+                    {move || i18n::text(lang.get(), if last_step() { Msg::GenerateCompile } else { Msg::Next })}
+                </button>
+            </div>
+            {result}
+        </details>
+    }
+}
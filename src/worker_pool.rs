@@ -0,0 +1,277 @@
+//! Compiling many independent sources at once on a pool of Web Workers.
+//!
+//! Each worker imports the headless core (the same module `lazy-core`
+//! loads, see [`crate::compiler`]) and runs one job at a time. The scheduler
+//! gives every worker its own lane that keeps taking the next job off a
+//! shared queue until the queue is empty, so a slow source holds up one
+//! worker rather than a whole batch. The pool has one worker per logical
+//! core (`navigator.hardwareConcurrency`, at most [`MAX_WORKERS`](crate::settings::MAX_WORKERS)) unless
+//! the settings name a count, and is kept for reuse once started.
+//!
+//! Long arguments are sent as bytes: in a shared buffer when the page is
+//! cross-origin isolated, otherwise in one whose ownership moves to the
+//! worker. Binary results such as [`Artifact`]s come back the same way, so
+//! multi-megabyte programs are never copied between threads.
+//!
+//! Mock transaction runs get a worker of their own instead, outside the
+//! pool, so one that outlasts its time limit can be stopped without losing
+//! any other job.
+//!
+//! Without `lazy-core` there is no separate core for workers to load, and
+//! where workers cannot be started the jobs run one after another on the
+//! page instead, through [`crate::compiler`], with the same results.
+
+use simplicityhl::simplicity::Cmr;
+
+use crate::artifact::Artifact;
+use crate::compiler;
+use crate::limits::ExecLimits;
+#[cfg(feature = "lazy-core")]
+use crate::logging::{self, Category};
+
+#[cfg(feature = "lazy-core")]
+mod pool {
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+
+    use wasm_bindgen::prelude::*;
+    use wasm_bindgen_futures::{future_to_promise, JsFuture};
+
+    use crate::logging::{self, Category};
+    use crate::settings::{Settings, MAX_WORKERS};
+
+    #[wasm_bindgen(inline_js = r#"
+    // Strings longer than this travel as bytes rather than being cloned.
+    const LARGE_ARG = 64 * 1024;
+    export function spawn_worker(url) {
+        const source = `
+            const core = import(${JSON.stringify(url)}).then(async (module) => {
+                await module.default();
+                return module;
+            });
+            const decoder = new TextDecoder();
+            const text = (arg) => typeof arg === "string"
+                ? arg
+                // TextDecoder refuses views of shared memory.
+                : decoder.decode(arg.buffer instanceof ArrayBuffer ? arg : arg.slice());
+            self.onmessage = async (event) => {
+                const { name, args } = event.data;
+                try {
+                    const module = await core;
+                    const value = module[name](...args.map(text));
+                    const transfer = value instanceof Uint8Array ? [value.buffer] : [];
+                    self.postMessage({ ok: true, value }, transfer);
+                } catch (e) {
+                    self.postMessage({ ok: false, value: String(e) });
+                }
+            };
+        `;
+        const blob = new Blob([source], { type: "text/javascript" });
+        return new Worker(URL.createObjectURL(blob), { type: "module" });
+    }
+    export function worker_call(worker, name, args) {
+        const encoder = new TextEncoder();
+        const transfer = [];
+        const packed = args.map((arg) => {
+            if (arg.length < LARGE_ARG) {
+                return arg;
+            }
+            const bytes = encoder.encode(arg);
+            if (self.crossOriginIsolated) {
+                const shared = new Uint8Array(new SharedArrayBuffer(bytes.length));
+                shared.set(bytes);
+                return shared;
+            }
+            transfer.push(bytes.buffer);
+            return bytes;
+        });
+        return new Promise((resolve, reject) => {
+            worker.onmessage = (event) => (event.data.ok ? resolve : reject)(event.data.value);
+            worker.onerror = (event) => reject(event.message || "Worker failed");
+            worker.postMessage({ name, args: packed }, transfer);
+        });
+    }
+    export function worker_call_timeout(worker, name, args, ms) {
+        const timeout = new Promise((_, reject) => setTimeout(
+            () => reject(`Budget exceeded: the run took longer than ${ms} ms`),
+            ms,
+        ));
+        return Promise.race([worker_call(worker, name, args), timeout]).finally(() => worker.terminate());
+    }
+    export function hardware_concurrency() {
+        return navigator.hardwareConcurrency || 1;
+    }
+    export function absolute_url(url) {
+        return new URL(url, document.baseURI).href;
+    }
+    "#)]
+    extern "C" {
+        #[derive(Clone)]
+        type PoolWorker;
+
+        #[wasm_bindgen(catch)]
+        fn spawn_worker(url: &str) -> Result<PoolWorker, JsValue>;
+        fn worker_call(worker: &PoolWorker, name: &str, args: js_sys::Array) -> js_sys::Promise;
+        fn worker_call_timeout(worker: &PoolWorker, name: &str, args: js_sys::Array, ms: u32) -> js_sys::Promise;
+        fn hardware_concurrency() -> u32;
+        fn absolute_url(url: &str) -> String;
+    }
+
+    thread_local! {
+        /// Workers started so far; later runs reuse them.
+        static WORKERS: RefCell<Vec<PoolWorker>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// `count` workers, starting any the pool does not have yet.
+    fn workers(count: usize) -> Result<Vec<PoolWorker>, String> {
+        WORKERS.with(|workers| {
+            let mut workers = workers.borrow_mut();
+            if workers.len() < count {
+                let url = absolute_url(&crate::compiler::core_url());
+                while workers.len() < count {
+                    let worker = spawn_worker(&url).map_err(|e| format!("Failed to start a worker: {e:?}"))?;
+                    workers.push(worker);
+                }
+                logging::info(Category::Worker, format!("Worker pool has {count} workers"));
+            }
+            Ok(workers[..count].to_vec())
+        })
+    }
+
+    pub(super) fn size() -> usize {
+        match Settings::load().workers {
+            0 => (hardware_concurrency() as usize).clamp(1, MAX_WORKERS as usize),
+            workers => workers as usize,
+        }
+    }
+
+    /// Call the core's `function` once per entry of `jobs`, spread over the
+    /// pool. Results are in job order: strings, or `Uint8Array`s moved out of
+    /// the worker without a copy. Fails only if no worker could start.
+    pub(super) async fn map(function: &'static str, jobs: Vec<Vec<String>>) -> Result<Vec<Result<JsValue, String>>, String> {
+        let workers = workers(size().min(jobs.len()).max(1))?;
+        let results = Rc::new(RefCell::new(vec![None; jobs.len()]));
+        let queue = Rc::new(RefCell::new(jobs.into_iter().enumerate().collect::<VecDeque<_>>()));
+
+        let lanes = workers
+            .into_iter()
+            .map(|worker| {
+                let (queue, results) = (queue.clone(), results.clone());
+                future_to_promise(async move {
+                    loop {
+                        let Some((index, args)) = queue.borrow_mut().pop_front() else {
+                            break;
+                        };
+                        let args = args.iter().map(|a| JsValue::from_str(a)).collect::<js_sys::Array>();
+                        let result = JsFuture::from(worker_call(&worker, function, args))
+                            .await
+                            .map_err(|e| e.as_string().unwrap_or_else(|| format!("{e:?}")));
+                        results.borrow_mut()[index] = Some(result);
+                    }
+                    Ok(JsValue::UNDEFINED)
+                })
+            })
+            .collect::<js_sys::Array>();
+        JsFuture::from(js_sys::Promise::all(&lanes))
+            .await
+            .map_err(|e| format!("Worker pool failed: {e:?}"))?;
+
+        let results = results.take();
+        Ok(results
+            .into_iter()
+            .map(|r| r.unwrap_or_else(|| Err("Job was not run".to_string())))
+            .collect())
+    }
+
+    /// Call the core's `function` on a worker of its own, which is stopped
+    /// after `timeout_ms` whether or not it has answered. The Bit Machine
+    /// cannot be interrupted, so this is the only way to give up on a run.
+    /// Fails only if the worker could not start.
+    pub(super) async fn call_with_timeout(
+        function: &str,
+        args: &[&str],
+        timeout_ms: u32,
+    ) -> Result<Result<String, String>, String> {
+        let worker = spawn_worker(&absolute_url(&crate::compiler::core_url()))
+            .map_err(|e| format!("Failed to start a worker: {e:?}"))?;
+        let args = args.iter().map(|a| JsValue::from_str(a)).collect::<js_sys::Array>();
+        Ok(JsFuture::from(worker_call_timeout(&worker, function, args, timeout_ms))
+            .await
+            .map(|value| value.as_string().unwrap_or_default())
+            .map_err(|e| e.as_string().unwrap_or_else(|| format!("{e:?}"))))
+    }
+}
+
+/// How many workers a batch can use at once.
+pub fn size() -> usize {
+    #[cfg(feature = "lazy-core")]
+    return pool::size();
+    #[cfg(not(feature = "lazy-core"))]
+    return 1;
+}
+
+/// The CMR of each of `sources`, in order, with parameter values read from
+/// the sources themselves.
+pub async fn compile_cmrs(sources: Vec<String>) -> Vec<Result<Cmr, String>> {
+    #[cfg(feature = "lazy-core")]
+    if sources.len() > 1 {
+        let jobs = sources.iter().map(|code| vec![code.clone(), String::new()]).collect();
+        match pool::map("cmr_of", jobs).await {
+            Ok(results) => {
+                return results
+                    .into_iter()
+                    .map(|r| r.and_then(|hex| crate::address::parse_cmr(&hex.as_string().unwrap_or_default())))
+                    .collect()
+            }
+            Err(e) => logging::warn(Category::Worker, format!("{e}; compiling on the page instead")),
+        }
+    }
+    let mut cmrs = Vec::with_capacity(sources.len());
+    for code in &sources {
+        cmrs.push(compiler::cmr_of(code, "").await);
+    }
+    cmrs
+}
+
+/// [`artifact::build`](crate::artifact::build) on a worker, so a large program is compiled and
+/// encoded off the page and its bytes arrive without a JSON round trip.
+pub async fn build_artifact(code: &str, witness_json: &str) -> Result<Artifact, String> {
+    #[cfg(feature = "lazy-core")]
+    match pool::map("encode_artifact", vec![vec![code.to_string(), witness_json.to_string()]]).await {
+        Ok(mut results) => {
+            let bytes = results.pop().unwrap_or_else(|| Err("Job was not run".to_string()))?;
+            return Artifact::from_bytes(&js_sys::Uint8Array::new(&bytes).to_vec());
+        }
+        Err(e) => logging::warn(Category::Worker, format!("{e}; encoding on the page instead")),
+    }
+    Artifact::from_bytes(&compiler::encode_artifact(code, witness_json).await?)
+}
+
+/// [`compiler::run_program`] under `limits`. With `lazy-core` the run gets a
+/// worker of its own that is stopped after `limits.timeout_ms`, so a program
+/// that runs too long cannot hang the page; elsewhere only the budgets
+/// checked before the run apply.
+pub async fn run_program(code: &str, witness_json: &str, mock_json: &str, limits: &ExecLimits) -> String {
+    let limits_json = serde_json::to_string(limits).unwrap_or_default();
+    #[cfg(feature = "lazy-core")]
+    match pool::call_with_timeout("run_program", &[code, witness_json, mock_json, &limits_json], limits.timeout_ms).await {
+        Ok(result) => return result.unwrap_or_else(|e| serde_json::json!({ "run": null, "error": e }).to_string()),
+        Err(e) => logging::warn(Category::Worker, format!("{e}; running on the page instead")),
+    }
+    compiler::run_program(code, witness_json, mock_json, &limits_json).await
+}
+
+/// [`compiler::trace_program`] under `limits`, stopped after
+/// `limits.timeout_ms` as [`run_program`] is.
+pub async fn trace_program(code: &str, witness_json: &str, mock_json: &str, limits: &ExecLimits) -> String {
+    let limits_json = serde_json::to_string(limits).unwrap_or_default();
+    #[cfg(feature = "lazy-core")]
+    match pool::call_with_timeout("trace_program", &[code, witness_json, mock_json, &limits_json], limits.timeout_ms).await {
+        Ok(result) => {
+            return result.unwrap_or_else(|e| serde_json::json!({ "trace": null, "text": null, "error": e }).to_string())
+        }
+        Err(e) => logging::warn(Category::Worker, format!("{e}; tracing on the page instead")),
+    }
+    compiler::trace_program(code, witness_json, mock_json, &limits_json).await
+}
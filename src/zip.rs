@@ -0,0 +1,284 @@
+//! Minimal ZIP archives.
+//!
+//! Projects are a few kilobytes of text, so [`pack`] stores entries without
+//! compression; its archives open in any unzip tool. [`unpack`] reads stored
+//! and deflated entries, the two methods zip tools and file managers write,
+//! so a project archive that was unpacked, edited and zipped again still
+//! opens. Any other method is reported rather than misread.
+
+const LOCAL_HEADER: u32 = 0x0403_4b50;
+const CENTRAL_HEADER: u32 = 0x0201_4b50;
+const END_OF_DIRECTORY: u32 = 0x0605_4b50;
+const STORED: u16 = 0;
+const DEFLATED: u16 = 8;
+/// Names are UTF-8 (general purpose flag bit 11).
+const UTF8_FLAG: u16 = 1 << 11;
+/// 1980-01-01, the earliest DOS date; entries carry no meaningful time.
+const DOS_DATE: u16 = (1 << 5) | 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+impl Entry {
+    pub fn new(name: &str, data: impl Into<Vec<u8>>) -> Self {
+        Entry {
+            name: name.to_string(),
+            data: data.into(),
+        }
+    }
+
+    pub fn text(&self) -> Result<String, String> {
+        String::from_utf8(self.data.clone()).map_err(|_| format!("`{}` is not UTF-8 text", self.name))
+    }
+}
+
+/// CRC-32 (IEEE), as ZIP stores it.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn put_u16(out: &mut Vec<u8>, value: u16) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+/// An archive holding `entries` in order.
+pub fn pack(entries: &[Entry]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut directory = Vec::new();
+    for entry in entries {
+        let offset = out.len() as u32;
+        let crc = crc32(&entry.data);
+        let size = entry.data.len() as u32;
+        let name = entry.name.as_bytes();
+
+        put_u32(&mut out, LOCAL_HEADER);
+        put_u16(&mut out, 20); // version needed
+        put_u16(&mut out, UTF8_FLAG);
+        put_u16(&mut out, STORED);
+        put_u16(&mut out, 0); // time
+        put_u16(&mut out, DOS_DATE);
+        put_u32(&mut out, crc);
+        put_u32(&mut out, size);
+        put_u32(&mut out, size);
+        put_u16(&mut out, name.len() as u16);
+        put_u16(&mut out, 0); // extra field length
+        out.extend_from_slice(name);
+        out.extend_from_slice(&entry.data);
+
+        put_u32(&mut directory, CENTRAL_HEADER);
+        put_u16(&mut directory, 20); // version made by
+        put_u16(&mut directory, 20); // version needed
+        put_u16(&mut directory, UTF8_FLAG);
+        put_u16(&mut directory, STORED);
+        put_u16(&mut directory, 0);
+        put_u16(&mut directory, DOS_DATE);
+        put_u32(&mut directory, crc);
+        put_u32(&mut directory, size);
+        put_u32(&mut directory, size);
+        put_u16(&mut directory, name.len() as u16);
+        put_u16(&mut directory, 0); // extra field length
+        put_u16(&mut directory, 0); // comment length
+        put_u16(&mut directory, 0); // disk number
+        put_u16(&mut directory, 0); // internal attributes
+        put_u32(&mut directory, 0); // external attributes
+        put_u32(&mut directory, offset);
+        directory.extend_from_slice(name);
+    }
+
+    let directory_offset = out.len() as u32;
+    out.extend_from_slice(&directory);
+    put_u32(&mut out, END_OF_DIRECTORY);
+    put_u16(&mut out, 0); // this disk
+    put_u16(&mut out, 0); // disk with the directory
+    put_u16(&mut out, entries.len() as u16);
+    put_u16(&mut out, entries.len() as u16);
+    put_u32(&mut out, directory.len() as u32);
+    put_u32(&mut out, directory_offset);
+    put_u16(&mut out, 0); // comment length
+    out
+}
+
+fn u16_at(bytes: &[u8], at: usize) -> Result<u16, String> {
+    bytes
+        .get(at..at + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or_else(|| "Truncated ZIP archive".to_string())
+}
+
+fn u32_at(bytes: &[u8], at: usize) -> Result<u32, String> {
+    bytes
+        .get(at..at + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| "Truncated ZIP archive".to_string())
+}
+
+/// The file entries of an archive; directories are skipped.
+pub fn unpack(bytes: &[u8]) -> Result<Vec<Entry>, String> {
+    // The end record is the last 22 bytes, before an optional comment.
+    let end = (0..bytes.len().saturating_sub(21))
+        .rev()
+        .find(|&at| u32_at(bytes, at) == Ok(END_OF_DIRECTORY))
+        .ok_or_else(|| "Not a ZIP archive".to_string())?;
+    let count = u16_at(bytes, end + 10)?;
+    let mut at = u32_at(bytes, end + 16)? as usize;
+
+    let mut entries = Vec::new();
+    for _ in 0..count {
+        if u32_at(bytes, at)? != CENTRAL_HEADER {
+            return Err("Corrupt ZIP directory".to_string());
+        }
+        let method = u16_at(bytes, at + 10)?;
+        let crc = u32_at(bytes, at + 16)?;
+        let compressed_size = u32_at(bytes, at + 20)? as usize;
+        let size = u32_at(bytes, at + 24)? as usize;
+        let name_len = usize::from(u16_at(bytes, at + 28)?);
+        let skip = usize::from(u16_at(bytes, at + 30)?) + usize::from(u16_at(bytes, at + 32)?);
+        let offset = u32_at(bytes, at + 42)? as usize;
+        let name = bytes
+            .get(at + 46..at + 46 + name_len)
+            .map(|n| String::from_utf8_lossy(n).into_owned())
+            .ok_or_else(|| "Truncated ZIP archive".to_string())?;
+        at += 46 + name_len + skip;
+
+        if name.ends_with('/') {
+            continue;
+        }
+        if method != STORED && method != DEFLATED {
+            return Err(format!(
+                "`{name}` uses compression method {method}; only stored and deflated entries can be read"
+            ));
+        }
+        if u32_at(bytes, offset)? != LOCAL_HEADER {
+            return Err(format!("Corrupt ZIP entry `{name}`"));
+        }
+        let start = offset + 30 + usize::from(u16_at(bytes, offset + 26)?) + usize::from(u16_at(bytes, offset + 28)?);
+        let raw = bytes
+            .get(start..start + compressed_size)
+            .ok_or_else(|| "Truncated ZIP archive".to_string())?;
+        let data = if method == DEFLATED {
+            miniz_oxide::inflate::decompress_to_vec_with_limit(raw, size)
+                .map_err(|_| format!("`{name}` is corrupt (bad deflate data)"))?
+        } else {
+            raw.to_vec()
+        };
+        if data.len() != size {
+            return Err(format!("`{name}` is corrupt (size mismatch)"));
+        }
+        if crc32(&data) != crc {
+            return Err(format!("`{name}` is corrupt (CRC mismatch)"));
+        }
+        entries.push(Entry { name, data });
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LASTWILL: &str = include_str!("../contract/lastwill.simf");
+    const LASTWILL_WITNESS: &str = include_str!("../contract/lastwill.wit");
+
+    /// A one-entry archive with the entry deflated, as zip tools write it.
+    fn deflated(entry: &Entry) -> Vec<u8> {
+        let compressed = miniz_oxide::deflate::compress_to_vec(&entry.data, 6);
+        let name = entry.name.as_bytes();
+        // Shared by the local header and the directory record.
+        let mut fields = Vec::new();
+        put_u16(&mut fields, 20); // version needed
+        put_u16(&mut fields, 0); // flags
+        put_u16(&mut fields, DEFLATED);
+        put_u16(&mut fields, 0); // time
+        put_u16(&mut fields, DOS_DATE);
+        put_u32(&mut fields, crc32(&entry.data));
+        put_u32(&mut fields, compressed.len() as u32);
+        put_u32(&mut fields, entry.data.len() as u32);
+        put_u16(&mut fields, name.len() as u16);
+        put_u16(&mut fields, 0); // extra field length
+
+        let mut out = Vec::new();
+        put_u32(&mut out, LOCAL_HEADER);
+        out.extend_from_slice(&fields);
+        out.extend_from_slice(name);
+        out.extend_from_slice(&compressed);
+
+        let directory_offset = out.len();
+        put_u32(&mut out, CENTRAL_HEADER);
+        put_u16(&mut out, 20); // version made by
+        out.extend_from_slice(&fields);
+        put_u16(&mut out, 0); // comment length
+        put_u16(&mut out, 0); // disk number
+        put_u16(&mut out, 0); // internal attributes
+        put_u32(&mut out, 0); // external attributes
+        put_u32(&mut out, 0); // offset of the local header
+        out.extend_from_slice(name);
+        let directory_len = out.len() - directory_offset;
+
+        put_u32(&mut out, END_OF_DIRECTORY);
+        put_u16(&mut out, 0);
+        put_u16(&mut out, 0);
+        put_u16(&mut out, 1);
+        put_u16(&mut out, 1);
+        put_u32(&mut out, directory_len as u32);
+        put_u32(&mut out, directory_offset as u32);
+        put_u16(&mut out, 0);
+        out
+    }
+
+    #[test]
+    fn packed_projects_unpack_unchanged() {
+        let entries = vec![
+            Entry::new("contract.simf", LASTWILL),
+            Entry::new("witness.json", LASTWILL_WITNESS),
+            Entry::new("tests.json", ""),
+        ];
+        let unpacked = unpack(&pack(&entries)).unwrap();
+        assert_eq!(unpacked, entries);
+        assert_eq!(unpacked[0].text().unwrap(), LASTWILL);
+    }
+
+    #[test]
+    fn deflated_entries_are_inflated() {
+        let entry = Entry::new("lastwill.simf", LASTWILL);
+        let archive = deflated(&entry);
+        assert!(archive.len() < LASTWILL.len());
+        assert_eq!(unpack(&archive).unwrap(), vec![entry]);
+    }
+
+    #[test]
+    fn corrupt_entries_are_reported() {
+        let mut archive = pack(&[Entry::new("contract.simf", LASTWILL)]);
+        // First byte of the data, after the 30-byte local header and the name.
+        archive[30 + "contract.simf".len()] ^= 1;
+        assert!(unpack(&archive).unwrap_err().contains("CRC mismatch"));
+
+        let mut archive = deflated(&Entry::new("contract.simf", LASTWILL));
+        let data = 30 + "contract.simf".len();
+        archive[data..data + 8].fill(0xff);
+        assert!(unpack(&archive).unwrap_err().contains("contract.simf"));
+    }
+
+    #[test]
+    fn other_methods_are_refused() {
+        let mut archive = pack(&[Entry::new("contract.simf", LASTWILL)]);
+        let end = archive.len() - 22;
+        let directory = u32_at(&archive, end + 16).unwrap() as usize;
+        archive[directory + 10] = 12; // bzip2
+        let error = unpack(&archive).unwrap_err();
+        assert!(error.contains("compression method 12"), "{error}");
+    }
+}
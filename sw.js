@@ -0,0 +1,65 @@
+// Offline cache for the Simplicity playground, registered by `src/pwa.rs`.
+//
+// The cache is named after the crate version passed as `?v=`, so a new
+// release starts from an empty cache and the previous one is dropped on
+// activation. Pages are fetched fresh when online and served from the cache
+// when not; every other same-origin asset is cache-first, since Trunk gives
+// them content-hashed names. Cross-origin requests (block explorers,
+// faucets) are never cached or intercepted.
+
+const PREFIX = 'simplicity-wasm-';
+const CACHE = PREFIX + (new URL(self.location.href).searchParams.get('v') || 'dev');
+
+function remember(request, response) {
+    if (response.ok) {
+        const copy = response.clone();
+        caches.open(CACHE).then((cache) => cache.put(request, copy));
+    }
+    return response;
+}
+
+self.addEventListener('install', (event) => {
+    event.waitUntil(
+        caches.open(CACHE)
+            .then((cache) => cache.addAll(['./', './index.html']))
+            .then(() => self.skipWaiting()),
+    );
+});
+
+self.addEventListener('activate', (event) => {
+    event.waitUntil(
+        caches.keys()
+            .then((keys) => Promise.all(
+                keys.filter((key) => key.startsWith(PREFIX) && key !== CACHE).map((key) => caches.delete(key)),
+            ))
+            .then(() => self.clients.claim()),
+    );
+});
+
+// Assets the page loaded before this worker controlled it.
+self.addEventListener('message', (event) => {
+    if (!event.data || event.data.type !== 'precache') {
+        return;
+    }
+    event.waitUntil(caches.open(CACHE).then((cache) => Promise.all(
+        event.data.urls.map((url) => cache.match(url).then((hit) => hit || cache.add(url).catch(() => {}))),
+    )));
+});
+
+self.addEventListener('fetch', (event) => {
+    const request = event.request;
+    if (request.method !== 'GET' || new URL(request.url).origin !== self.location.origin) {
+        return;
+    }
+    if (request.mode === 'navigate') {
+        event.respondWith(
+            fetch(request)
+                .then((response) => remember(request, response))
+                .catch(() => caches.match(request).then((hit) => hit || caches.match('./index.html'))),
+        );
+        return;
+    }
+    event.respondWith(
+        caches.match(request).then((hit) => hit || fetch(request).then((response) => remember(request, response))),
+    );
+});